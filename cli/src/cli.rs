@@ -5,9 +5,12 @@ use {
         print::Print,
     },
     edit::{Builder, edit_file, edit_with_builder},
-    futures::executor::block_on,
+    futures::{
+        TryStreamExt,
+        executor::{block_on, block_on_stream},
+    },
     gluesql_core::{
-        prelude::Glue,
+        prelude::{Glue, Payload, PayloadStream, Result as GlueResult},
         store::{GStore, GStoreMut},
     },
     rustyline::{Editor, error::ReadlineError},
@@ -162,29 +165,88 @@ where
     }
 
     fn execute(&mut self, sql: impl AsRef<str>) -> Result<()> {
-        match block_on(self.glue.execute(sql)) {
-            Ok(payloads) => self.print.payloads(&payloads)?,
-            Err(e) => {
-                println!("[error] {e}\n");
-            }
-        };
+        if let Err(e) = self.execute_stream(sql)? {
+            println!("[error] {e}\n");
+        }
 
         Ok(())
     }
 
+    /// Executes the statements of `sql`, printing the rows of `SELECT` as they are read from
+    /// the storage instead of collecting them first.
+    fn execute_stream(&mut self, sql: impl AsRef<str>) -> Result<GlueResult<()>> {
+        let statements = match block_on(self.glue.plan(sql)) {
+            Ok(statements) => statements,
+            Err(e) => return Ok(Err(e)),
+        };
+
+        for statement in statements {
+            let payload = match block_on(self.glue.execute_stmt_stream(statement)) {
+                Ok(payload) => payload,
+                Err(e) => return Ok(Err(e)),
+            };
+
+            match payload {
+                PayloadStream::Select { labels, rows } => {
+                    let mut error = None;
+                    let rows = block_on_stream(rows)
+                        .map_while(|row| row.map_err(|e| error = Some(e)).ok());
+                    self.print.select(&labels, rows)?;
+
+                    if let Some(e) = error {
+                        return Ok(Err(e));
+                    }
+                }
+                PayloadStream::SelectMap(rows) => match block_on(rows.try_collect()) {
+                    Ok(rows) => self.print.payload(&Payload::SelectMap(rows))?,
+                    Err(e) => return Ok(Err(e)),
+                },
+                PayloadStream::Payload(payload) => self.print.payload(&payload)?,
+            }
+        }
+
+        Ok(Ok(()))
+    }
+
     pub fn load<P: AsRef<Path>>(&mut self, filename: P) -> Result<()> {
         let mut sqls = String::new();
         File::open(filename)?.read_to_string(&mut sqls)?;
         for sql in sqls.split(';').filter(|sql| !sql.trim().is_empty()) {
-            match block_on(self.glue.execute(sql)) {
-                Ok(payloads) => self.print.payloads(&payloads)?,
-                Err(e) => {
-                    println!("[error] {e}\n");
-                    break;
-                }
+            if let Err(e) = self.execute_stream(sql)? {
+                println!("[error] {e}\n");
+                break;
             }
         }
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {super::Cli, crate::command::SetOption, gluesql_memory_storage::MemoryStorage};
+
+    #[test]
+    fn execute_stream() {
+        let mut cli = Cli::new(MemoryStorage::default(), Vec::new());
+        cli.print.set_option(SetOption::Tabular(false));
+
+        cli.execute("CREATE TABLE Item (id INTEGER); INSERT INTO Item VALUES (1), (0), (2);")
+            .unwrap();
+        cli.print.output.clear();
+
+        cli.execute("SELECT id FROM Item").unwrap();
+        assert_eq!(
+            String::from_utf8(cli.print.output.clone()).unwrap(),
+            "id\n1\n0\n2\n"
+        );
+        cli.print.output.clear();
+
+        // rows read before the error are printed already
+        cli.execute("SELECT 10 / id AS n FROM Item").unwrap();
+        assert_eq!(
+            String::from_utf8(cli.print.output.clone()).unwrap(),
+            "n\n10\n"
+        );
+    }
+}
@@ -93,15 +93,13 @@ impl<'a, W: Write> Print<W> {
         }
     }
 
-    pub fn payloads(&mut self, payloads: &[Payload]) -> IOResult<()> {
-        payloads.iter().try_for_each(|p| self.payload(p))
-    }
-
     pub fn payload(&mut self, payload: &Payload) -> IOResult<()> {
         #[derive(Display)]
         #[strum(serialize_all = "snake_case")]
         enum Target {
             Table,
+            View,
+            Sequence,
             Row,
         }
         let mut affected = |n: usize, target: Target, msg: &str| -> IOResult<()> {
@@ -114,15 +112,25 @@ impl<'a, W: Write> Print<W> {
             Payload::Create => self.writeln("Table created")?,
             Payload::DropTable(n) => affected(*n, Table, "dropped")?,
             Payload::DropFunction => self.writeln("Function dropped")?,
+            Payload::DropTrigger => self.writeln("Trigger dropped")?,
+            Payload::DropView(n) => affected(*n, View, "dropped")?,
+            Payload::CreateSequence => self.writeln("Sequence created")?,
+            Payload::DropSequence(n) => affected(*n, Sequence, "dropped")?,
+            Payload::RefreshMaterializedView(n) => affected(*n, Row, "refreshed")?,
+            Payload::Analyze(n) => affected(*n, Table, "analyzed")?,
             Payload::AlterTable => self.writeln("Table altered")?,
             Payload::CreateIndex => self.writeln("Index created")?,
             Payload::DropIndex => self.writeln("Index dropped")?,
             Payload::Commit => self.writeln("Commit completed")?,
             Payload::Rollback => self.writeln("Rollback completed")?,
+            Payload::Savepoint => self.writeln("Savepoint created")?,
+            Payload::RollbackToSavepoint => self.writeln("Rollback to savepoint completed")?,
+            Payload::ReleaseSavepoint => self.writeln("Savepoint released")?,
             Payload::StartTransaction => self.writeln("Transaction started")?,
             Payload::Insert(n) => affected(*n, Row, "inserted")?,
             Payload::Delete(n) => affected(*n, Row, "deleted")?,
             Payload::Update(n) => affected(*n, Row, "updated")?,
+            Payload::Explain(plan) => self.writeln(plan)?,
             Payload::ShowVariable(PayloadVariable::Version(v)) => self.writeln(format!("v{v}"))?,
             Payload::ShowVariable(PayloadVariable::Tables(names)) => {
                 let mut table = self.get_table(["tables"]);
@@ -148,24 +156,7 @@ impl<'a, W: Write> Print<W> {
                 let table = self.build_table(table);
                 self.writeln(table)?;
             }
-            Payload::Select { labels, rows } => match &self.option.tabular {
-                true => {
-                    let labels = labels.iter().map(AsRef::as_ref);
-                    let mut table = self.get_table(labels);
-                    for row in rows {
-                        let row: Vec<String> = row.iter().map(Into::into).collect();
-
-                        table.add_record(row);
-                    }
-                    let table = self.build_table(table);
-                    self.writeln(table)?;
-                }
-                false => {
-                    self.write_header(labels.iter().map(|s| s.as_str()))?;
-                    let rows = rows.iter().map(|row| row.iter().map(String::from));
-                    self.write_rows(rows)?;
-                }
-            },
+            Payload::Select { labels, rows } => self.select(labels, rows.iter())?,
             Payload::SelectMap(rows) => {
                 let mut labels = rows
                     .iter()
@@ -207,6 +198,36 @@ impl<'a, W: Write> Print<W> {
         Ok(())
     }
 
+    /// Prints the rows of `SELECT` as `rows` yields them. Tabular output holds every row until
+    /// the end, as each column is as wide as its widest value; the other modes write each row
+    /// right away.
+    pub fn select<R, V>(&mut self, labels: &[String], rows: impl Iterator<Item = R>) -> IOResult<()>
+    where
+        R: IntoIterator<Item = V>,
+        V: Into<String>,
+    {
+        match &self.option.tabular {
+            true => {
+                let labels = labels.iter().map(AsRef::as_ref);
+                let mut table = self.get_table(labels);
+                for row in rows {
+                    let row: Vec<String> = row.into_iter().map(Into::into).collect();
+
+                    table.add_record(row);
+                }
+                let table = self.build_table(table);
+                self.writeln(table)?;
+            }
+            false => {
+                self.write_header(labels.iter().map(|s| s.as_str()))?;
+                let rows = rows.map(|row| row.into_iter().map(Into::into));
+                self.write_rows(rows)?;
+            }
+        }
+
+        Ok(())
+    }
+
     fn write_rows(
         &mut self,
         rows: impl Iterator<Item = impl Iterator<Item = String>>,
@@ -365,16 +386,20 @@ mod tests {
 
     #[test]
     fn print_payload() {
-        use gluesql_core::{
-            ast::DataType,
-            prelude::{Payload, PayloadVariable, Value},
+        use {
+            gluesql_core::{
+                ast::DataType,
+                executor::{PlanActual, PlanNode},
+                prelude::{Payload, PayloadVariable, Value},
+            },
+            std::time::Duration,
         };
 
         let mut print = Print::new(Vec::new(), None, Default::default());
 
         macro_rules! test {
             ($payload: expr, $expected: literal ) => {
-                print.payloads(&[$payload]).unwrap();
+                print.payload(&$payload).unwrap();
 
                 assert_eq!(
                     String::from_utf8(print.output.clone())
@@ -394,8 +419,39 @@ mod tests {
         test!(Payload::CreateIndex, "Index created");
         test!(Payload::DropIndex, "Index dropped");
         test!(Payload::DropFunction, "Function dropped");
+        test!(Payload::DropTrigger, "Trigger dropped");
+        test!(
+            Payload::Explain(PlanNode {
+                operator: "Project".to_owned(),
+                detail: Some("id".to_owned()),
+                actual: None,
+                children: vec![PlanNode {
+                    operator: "Seq Scan".to_owned(),
+                    detail: Some("Foo".to_owned()),
+                    actual: Some(PlanActual {
+                        rows: 3,
+                        elapsed: Duration::from_micros(5),
+                    }),
+                    children: Vec::new(),
+                }],
+            }),
+            "
+Project: id
+  Seq Scan: Foo (rows: 3, elapsed: 5µs)"
+        );
+        test!(Payload::DropView(2), "2 views dropped");
+        test!(Payload::CreateSequence, "Sequence created");
+        test!(Payload::DropSequence(2), "2 sequences dropped");
+        test!(Payload::RefreshMaterializedView(3), "3 rows refreshed");
+        test!(Payload::Analyze(2), "2 tables analyzed");
         test!(Payload::Commit, "Commit completed");
         test!(Payload::Rollback, "Rollback completed");
+        test!(Payload::Savepoint, "Savepoint created");
+        test!(
+            Payload::RollbackToSavepoint,
+            "Rollback to savepoint completed"
+        );
+        test!(Payload::ReleaseSavepoint, "Savepoint released");
         test!(Payload::StartTransaction, "Transaction started");
         test!(Payload::Insert(0), "0 row inserted");
         test!(Payload::Insert(1), "1 row inserted");
@@ -5,6 +5,7 @@ mod expr;
 mod function;
 mod operator;
 mod query;
+mod window;
 
 pub use {
     ast_literal::{AstLiteral, DateTimeField, TrimWhereField},
@@ -14,6 +15,7 @@ pub use {
     function::{Aggregate, AggregateFunction, CountArgExpr, Function},
     operator::*,
     query::*,
+    window::{WindowFrame, WindowFrameBound, WindowFrameUnits, WindowSpec},
 };
 
 use {
@@ -39,10 +41,36 @@ pub struct ForeignKey {
     pub on_update: ReferentialAction,
 }
 
+/// `[ CONSTRAINT <name> ] { PRIMARY KEY | UNIQUE } (<column>, ...)` declared on the table,
+/// only kept when it spans more than one column
+#[derive(PartialEq, Debug, Clone, Eq, Hash, Serialize, Deserialize)]
+pub struct KeyConstraint {
+    pub name: Option<String>,
+    pub columns: Vec<String>,
+    pub is_primary: bool,
+}
+
+/// `[ CONSTRAINT <name> ] CHECK (<expr>)`
+#[derive(PartialEq, Debug, Clone, Eq, Hash, Serialize, Deserialize)]
+pub struct CheckConstraint {
+    pub name: Option<String>,
+    pub expr: Expr,
+}
+
+/// Action taken on the referencing rows when a referenced row is deleted or its referenced
+/// column is updated. `RESTRICT` fails like `NO ACTION` as constraints are never deferred.
 #[derive(PartialEq, Debug, Clone, Eq, Hash, Serialize, Deserialize, Display)]
 pub enum ReferentialAction {
     #[strum(to_string = "NO ACTION")]
     NoAction,
+    #[strum(to_string = "RESTRICT")]
+    Restrict,
+    #[strum(to_string = "CASCADE")]
+    Cascade,
+    #[strum(to_string = "SET NULL")]
+    SetNull,
+    #[strum(to_string = "SET DEFAULT")]
+    SetDefault,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -52,6 +80,12 @@ pub enum Statement {
     },
     /// SELECT, VALUES
     Query(Query),
+    /// EXPLAIN [ANALYZE]
+    Explain {
+        /// Runs the query and reports the rows and time of each operator
+        analyze: bool,
+        query: Box<Query>,
+    },
     /// INSERT
     Insert {
         /// TABLE
@@ -60,6 +94,10 @@ pub enum Statement {
         columns: Vec<String>,
         /// A SQL query that specifies what to insert
         source: Query,
+        /// ON CONFLICT
+        on_conflict: Option<OnConflict>,
+        /// RETURNING, empty when omitted
+        returning: Vec<SelectItem>,
     },
     /// UPDATE
     Update {
@@ -69,6 +107,8 @@ pub enum Statement {
         assignments: Vec<Assignment>,
         /// WHERE
         selection: Option<Expr>,
+        /// RETURNING, empty when omitted
+        returning: Vec<SelectItem>,
     },
     /// DELETE
     Delete {
@@ -76,6 +116,8 @@ pub enum Statement {
         table_name: String,
         /// WHERE
         selection: Option<Expr>,
+        /// RETURNING, empty when omitted
+        returning: Vec<SelectItem>,
     },
     /// CREATE TABLE
     CreateTable {
@@ -86,7 +128,9 @@ pub enum Statement {
         columns: Option<Vec<ColumnDef>>,
         source: Option<Box<Query>>,
         engine: Option<String>,
+        key_constraints: Vec<KeyConstraint>,
         foreign_keys: Vec<ForeignKey>,
+        checks: Vec<CheckConstraint>,
         comment: Option<String>,
     },
     /// CREATE FUNCTION
@@ -119,23 +163,82 @@ pub enum Statement {
         /// One or more objects to drop. (ANSI SQL requires exactly one.)
         names: Vec<String>,
     },
-    /// CREATE INDEX
+    /// CREATE [UNIQUE] INDEX
     CreateIndex {
         name: String,
         table_name: String,
-        column: OrderByExpr,
+        columns: Vec<OrderByExpr>,
+        unique: bool,
     },
     /// DROP INDEX
     DropIndex {
         name: String,
         table_name: String,
     },
+    /// CREATE [MATERIALIZED] VIEW
+    CreateView {
+        or_replace: bool,
+        materialized: bool,
+        name: String,
+        /// Column aliases, empty when omitted
+        columns: Vec<String>,
+        query: Box<Query>,
+    },
+    /// DROP [MATERIALIZED] VIEW
+    DropView {
+        if_exists: bool,
+        names: Vec<String>,
+    },
+    /// REFRESH MATERIALIZED VIEW
+    RefreshMaterializedView {
+        name: String,
+    },
+    /// CREATE SEQUENCE
+    CreateSequence {
+        if_not_exists: bool,
+        name: String,
+        options: SequenceOptions,
+    },
+    /// DROP SEQUENCE
+    DropSequence {
+        if_exists: bool,
+        names: Vec<String>,
+    },
+    /// CREATE TRIGGER
+    CreateTrigger {
+        or_replace: bool,
+        name: String,
+        table_name: String,
+        timing: TriggerTiming,
+        events: Vec<TriggerEvent>,
+        /// WHEN
+        condition: Option<Expr>,
+        /// Statements run for each row, referring to the row through `NEW` and `OLD`
+        body: Vec<Statement>,
+    },
+    /// DROP TRIGGER
+    DropTrigger {
+        if_exists: bool,
+        name: String,
+        table_name: String,
+    },
+    /// ANALYZE [table]
+    Analyze {
+        /// Table to collect the statistics of, every table when omitted
+        table_name: Option<String>,
+    },
     /// START TRANSACTION, BEGIN
     StartTransaction,
     /// COMMIT
     Commit,
     /// ROLLBACK
     Rollback,
+    /// SAVEPOINT name
+    Savepoint(String),
+    /// ROLLBACK TO SAVEPOINT name
+    RollbackToSavepoint(String),
+    /// RELEASE SAVEPOINT name
+    ReleaseSavepoint(String),
     /// SHOW VARIABLE
     ShowVariable(Variable),
     ShowIndexes(String),
@@ -147,6 +250,23 @@ pub struct Assignment {
     pub value: Expr,
 }
 
+/// `ON CONFLICT [(target)] DO NOTHING | DO UPDATE SET ... [WHERE ...]`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct OnConflict {
+    /// Columns of the unique constraint to check, empty when omitted
+    pub target: Vec<String>,
+    pub action: OnConflictAction,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum OnConflictAction {
+    DoNothing,
+    DoUpdate {
+        assignments: Vec<Assignment>,
+        selection: Option<Expr>,
+    },
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Variable {
     Tables,
@@ -177,6 +297,39 @@ impl ToSql for ForeignKey {
     }
 }
 
+impl ToSql for KeyConstraint {
+    fn to_sql(&self) -> String {
+        let KeyConstraint {
+            name,
+            columns,
+            is_primary,
+        } = self;
+        let key = if *is_primary { "PRIMARY KEY" } else { "UNIQUE" };
+        let columns = columns
+            .iter()
+            .map(|column| format!(r#""{column}""#))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        match name {
+            Some(name) => format!(r#"CONSTRAINT "{name}" {key} ({columns})"#),
+            None => format!("{key} ({columns})"),
+        }
+    }
+}
+
+impl ToSql for CheckConstraint {
+    fn to_sql(&self) -> String {
+        let CheckConstraint { name, expr } = self;
+        let expr = expr.to_sql();
+
+        match name {
+            Some(name) => format!(r#"CONSTRAINT "{name}" CHECK ({expr})"#),
+            None => format!("CHECK ({expr})"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Array {
     pub elem: Vec<Expr>,
@@ -1,13 +1,14 @@
 use {
-    super::{DataType, Expr},
+    super::{CheckConstraint, DataType, Expr},
     crate::ast::ToSql,
     serde::{Deserialize, Serialize},
+    strum_macros::Display,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum AlterTableOperation {
     /// `ADD [ COLUMN ] <column_def>`
-    AddColumn { column_def: ColumnDef },
+    AddColumn { column_def: Box<ColumnDef> },
     /// `DROP [ COLUMN ] [ IF EXISTS ] <column_name> [ CASCADE ]`
     DropColumn {
         column_name: String,
@@ -29,11 +30,52 @@ pub struct ColumnDef {
     pub nullable: bool,
     /// `DEFAULT <restricted-expr>`
     pub default: Option<Expr>,
+    /// `GENERATED { ALWAYS | BY DEFAULT } AS IDENTITY`, or `SERIAL`
+    pub identity: Option<ColumnIdentity>,
+    /// `GENERATED ALWAYS AS (<expr>) STORED`, computed from the other columns of the row
+    pub generated: Option<Expr>,
     /// `{ PRIMARY KEY | UNIQUE }`
     pub unique: Option<ColumnUniqueOption>,
+    /// `[ CONSTRAINT <name> ] CHECK (<expr>)`
+    pub check: Option<CheckConstraint>,
     pub comment: Option<String>,
 }
 
+/// Identity column taking its values from the sequence named by [`ColumnIdentity::sequence_name`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ColumnIdentity {
+    /// `ALWAYS` rejects values given by `INSERT`, `BY DEFAULT` only fills the missing ones
+    pub always: bool,
+    pub options: SequenceOptions,
+}
+
+/// `[ INCREMENT [ BY ] <n> ] [ MINVALUE <n> ] [ MAXVALUE <n> ] [ START [ WITH ] <n> ] [ CYCLE ]`,
+/// left out options are given by the direction of the sequence
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SequenceOptions {
+    pub increment: Option<i64>,
+    pub min_value: Option<i64>,
+    pub max_value: Option<i64>,
+    pub start: Option<i64>,
+    pub cycle: bool,
+}
+
+/// `{ BEFORE | AFTER }`, when a trigger fires relative to the rows being written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Display)]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum TriggerTiming {
+    Before,
+    After,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Display)]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum TriggerEvent {
+    Insert,
+    Update,
+    Delete,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ColumnUniqueOption {
     pub is_primary: bool,
@@ -78,7 +120,10 @@ impl ToSql for ColumnDef {
             data_type,
             nullable,
             default,
+            identity,
+            generated,
             unique,
+            check,
             comment,
         } = self;
         {
@@ -90,20 +135,78 @@ impl ToSql for ColumnDef {
             let default = default
                 .as_ref()
                 .map(|expr| format!("DEFAULT {}", expr.to_sql()));
+            let identity = identity.as_ref().map(ToSql::to_sql);
+            let generated = generated
+                .as_ref()
+                .map(|expr| format!("GENERATED ALWAYS AS ({}) STORED", expr.to_sql()));
             let unique = unique.as_ref().map(ToSql::to_sql);
+            let check = check.as_ref().map(ToSql::to_sql);
             let comment = comment
                 .as_ref()
                 .map(|comment| format!("COMMENT '{comment}'"));
 
-            [Some(column_def), default, unique, comment]
-                .into_iter()
-                .flatten()
-                .collect::<Vec<_>>()
-                .join(" ")
+            [
+                Some(column_def),
+                default,
+                identity,
+                generated,
+                unique,
+                check,
+                comment,
+            ]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" ")
+        }
+    }
+}
+
+impl ColumnIdentity {
+    pub fn sequence_name(table_name: &str, column_name: &str) -> String {
+        format!("{table_name}_{column_name}_seq")
+    }
+}
+
+impl ToSql for ColumnIdentity {
+    fn to_sql(&self) -> String {
+        let ColumnIdentity { always, options } = self;
+        let generated = match always {
+            true => "GENERATED ALWAYS AS IDENTITY",
+            false => "GENERATED BY DEFAULT AS IDENTITY",
+        };
+
+        match options == &SequenceOptions::default() {
+            true => generated.to_owned(),
+            false => format!("{generated} ({})", options.to_sql()),
         }
     }
 }
 
+impl ToSql for SequenceOptions {
+    fn to_sql(&self) -> String {
+        let SequenceOptions {
+            increment,
+            min_value,
+            max_value,
+            start,
+            cycle,
+        } = self;
+
+        [
+            increment.map(|n| format!("INCREMENT BY {n}")),
+            min_value.map(|n| format!("MINVALUE {n}")),
+            max_value.map(|n| format!("MAXVALUE {n}")),
+            start.map(|n| format!("START WITH {n}")),
+            cycle.then(|| "CYCLE".to_owned()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" ")
+    }
+}
+
 impl ToSql for ColumnUniqueOption {
     fn to_sql(&self) -> String {
         if self.is_primary {
@@ -133,7 +236,8 @@ impl ToSql for OperateFunctionArg {
 #[cfg(test)]
 mod tests {
     use crate::ast::{
-        AstLiteral, ColumnDef, ColumnUniqueOption, DataType, Expr, OperateFunctionArg, ToSql,
+        AstLiteral, BinaryOperator, CheckConstraint, ColumnDef, ColumnIdentity, ColumnUniqueOption,
+        DataType, Expr, OperateFunctionArg, SequenceOptions, ToSql,
     };
 
     #[test]
@@ -145,7 +249,10 @@ mod tests {
                 data_type: DataType::Text,
                 nullable: false,
                 default: None,
+                identity: None,
+                generated: None,
                 unique: Some(ColumnUniqueOption { is_primary: false }),
+                check: None,
                 comment: None,
             }
             .to_sql()
@@ -158,7 +265,10 @@ mod tests {
                 data_type: DataType::Boolean,
                 nullable: true,
                 default: None,
+                identity: None,
+                generated: None,
                 unique: None,
+                check: None,
                 comment: None,
             }
             .to_sql()
@@ -171,7 +281,10 @@ mod tests {
                 data_type: DataType::Int,
                 nullable: false,
                 default: None,
+                identity: None,
+                generated: None,
                 unique: Some(ColumnUniqueOption { is_primary: true }),
+                check: None,
                 comment: None,
             }
             .to_sql()
@@ -184,7 +297,10 @@ mod tests {
                 data_type: DataType::Boolean,
                 nullable: false,
                 default: Some(Expr::Literal(AstLiteral::Boolean(false))),
+                identity: None,
+                generated: None,
                 unique: None,
+                check: None,
                 comment: None,
             }
             .to_sql()
@@ -197,7 +313,10 @@ mod tests {
                 data_type: DataType::Boolean,
                 nullable: false,
                 default: Some(Expr::Literal(AstLiteral::Boolean(false))),
+                identity: None,
+                generated: None,
                 unique: Some(ColumnUniqueOption { is_primary: false }),
+                check: None,
                 comment: None,
             }
             .to_sql()
@@ -210,11 +329,106 @@ mod tests {
                 data_type: DataType::Boolean,
                 nullable: false,
                 default: None,
+                identity: None,
+                generated: None,
                 unique: None,
+                check: None,
                 comment: Some("this is comment".to_owned()),
             }
             .to_sql()
         );
+
+        assert_eq!(
+            r#""age" INT NULL CONSTRAINT "adult" CHECK ("age" >= 18)"#,
+            ColumnDef {
+                name: "age".to_owned(),
+                data_type: DataType::Int,
+                nullable: true,
+                default: None,
+                identity: None,
+                generated: None,
+                unique: None,
+                check: Some(CheckConstraint {
+                    name: Some("adult".to_owned()),
+                    expr: Expr::BinaryOp {
+                        left: Box::new(Expr::Identifier("age".to_owned())),
+                        op: BinaryOperator::GtEq,
+                        right: Box::new(Expr::Literal(AstLiteral::Number(18.into()))),
+                    },
+                }),
+                comment: None,
+            }
+            .to_sql()
+        );
+    }
+
+    #[test]
+    fn to_sql_column_identity() {
+        assert_eq!(
+            r#""id" INT NOT NULL GENERATED ALWAYS AS IDENTITY"#,
+            ColumnDef {
+                name: "id".to_owned(),
+                data_type: DataType::Int,
+                nullable: false,
+                default: None,
+                identity: Some(ColumnIdentity {
+                    always: true,
+                    options: SequenceOptions::default(),
+                }),
+                generated: None,
+                unique: None,
+                check: None,
+                comment: None,
+            }
+            .to_sql()
+        );
+
+        assert_eq!(
+            r#""id" INT NOT NULL GENERATED BY DEFAULT AS IDENTITY (INCREMENT BY -1 MAXVALUE 100 CYCLE)"#,
+            ColumnDef {
+                name: "id".to_owned(),
+                data_type: DataType::Int,
+                nullable: false,
+                default: None,
+                identity: Some(ColumnIdentity {
+                    always: false,
+                    options: SequenceOptions {
+                        increment: Some(-1),
+                        max_value: Some(100),
+                        cycle: true,
+                        ..SequenceOptions::default()
+                    },
+                }),
+                generated: None,
+                unique: None,
+                check: None,
+                comment: None,
+            }
+            .to_sql()
+        );
+    }
+
+    #[test]
+    fn to_sql_column_generated() {
+        assert_eq!(
+            r#""total" INT NULL GENERATED ALWAYS AS ("price" * "qty") STORED"#,
+            ColumnDef {
+                name: "total".to_owned(),
+                data_type: DataType::Int,
+                nullable: true,
+                default: None,
+                identity: None,
+                generated: Some(Expr::BinaryOp {
+                    left: Box::new(Expr::Identifier("price".to_owned())),
+                    op: BinaryOperator::Multiply,
+                    right: Box::new(Expr::Identifier("qty".to_owned())),
+                }),
+                unique: None,
+                check: None,
+                comment: None,
+            }
+            .to_sql()
+        );
     }
 
     #[test]
@@ -80,6 +80,8 @@ pub enum Expr {
     Array {
         elem: Vec<Expr>,
     },
+    /// Bind parameter `$n` of a prepared statement, holding its 1-based position
+    Parameter(usize),
 }
 
 impl ToSql for Expr {
@@ -251,6 +253,7 @@ impl Expr {
                 format!("[{elem}]")
             }
             Expr::Subquery(query) => format!("({})", query.to_sql()),
+            Expr::Parameter(index) => format!("${index}"),
             Expr::Interval {
                 expr,
                 leading_field,
@@ -442,6 +445,7 @@ mod tests {
             Expr::InSubquery {
                 expr: Box::new(Expr::Identifier("id".to_owned())),
                 subquery: Box::new(Query {
+                    with: None,
                     body: SetExpr::Select(Box::new(Select {
                         distinct: false,
                         projection: vec![SelectItem::Wildcard],
@@ -471,6 +475,7 @@ mod tests {
             Expr::InSubquery {
                 expr: Box::new(Expr::Identifier("id".to_owned())),
                 subquery: Box::new(Query {
+                    with: None,
                     body: SetExpr::Select(Box::new(Select {
                         distinct: false,
                         projection: vec![SelectItem::Wildcard],
@@ -499,6 +504,7 @@ mod tests {
             r#"EXISTS(SELECT * FROM "FOO")"#,
             Expr::Exists {
                 subquery: Box::new(Query {
+                    with: None,
                     body: SetExpr::Select(Box::new(Select {
                         distinct: false,
                         projection: vec![SelectItem::Wildcard],
@@ -527,6 +533,7 @@ mod tests {
             r#"NOT EXISTS(SELECT * FROM "FOO")"#,
             Expr::Exists {
                 subquery: Box::new(Query {
+                    with: None,
                     body: SetExpr::Select(Box::new(Select {
                         distinct: false,
                         projection: vec![SelectItem::Wildcard],
@@ -554,6 +561,7 @@ mod tests {
         assert_eq!(
             r#"(SELECT * FROM "FOO")"#,
             Expr::Subquery(Box::new(Query {
+                with: None,
                 body: SetExpr::Select(Box::new(Select {
                     distinct: false,
                     projection: vec![SelectItem::Wildcard],
@@ -686,6 +694,8 @@ mod tests {
             .to_sql()
         );
 
+        assert_eq!("$2", Expr::Parameter(2).to_sql());
+
         assert_eq!(
             r#"INTERVAL "col1" + 3 DAY"#,
             &Expr::Interval {
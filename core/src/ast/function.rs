@@ -1,5 +1,5 @@
 use {
-    super::{DataType, DateTimeField, Expr, ast_literal::TrimWhereField},
+    super::{DataType, DateTimeField, Expr, WindowSpec, ast_literal::TrimWhereField},
     crate::ast::ToSql,
     serde::{Deserialize, Serialize},
     strum_macros::Display,
@@ -141,6 +141,10 @@ pub enum Function {
         selector: Expr,
     },
     GenerateUuid(),
+    #[strum(to_string = "NEXTVAL")]
+    NextVal(Expr),
+    #[strum(to_string = "CURRVAL")]
+    CurrVal(Expr),
     Greatest(Vec<Expr>),
     Format {
         expr: Expr,
@@ -386,6 +390,8 @@ impl ToSql for Function {
                 format!("UNWRAP({}, {})", expr.to_sql(), selector.to_sql())
             }
             Function::GenerateUuid() => "GENERATE_UUID()".to_owned(),
+            Function::NextVal(expr) => format!("NEXTVAL({})", expr.to_sql()),
+            Function::CurrVal(expr) => format!("CURRVAL({})", expr.to_sql()),
             Function::Greatest(items) => {
                 let items = items
                     .iter()
@@ -520,17 +526,39 @@ pub enum AggregateFunction {
     Avg(Expr),
     Variance(Expr),
     Stdev(Expr),
+    /// Ranking and navigation functions below are only valid with an `OVER` clause.
+    RowNumber,
+    Rank,
+    DenseRank,
+    Lag {
+        expr: Expr,
+        offset: Option<Box<Expr>>,
+        default: Option<Box<Expr>>,
+    },
+    Lead {
+        expr: Expr,
+        offset: Option<Box<Expr>>,
+        default: Option<Box<Expr>>,
+    },
+    FirstValue(Expr),
+    LastValue(Expr),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Aggregate {
     pub func: AggregateFunction,
     pub distinct: bool,
+    /// `Some` when the aggregate is evaluated as a window function, `f(...) OVER (...)`
+    pub over: Option<Box<WindowSpec>>,
 }
 
 impl Aggregate {
     pub fn new(func: AggregateFunction, distinct: bool) -> Self {
-        Self { func, distinct }
+        Self {
+            func,
+            distinct,
+            over: None,
+        }
     }
 
     pub fn count(expr: CountArgExpr, distinct: bool) -> Self {
@@ -563,15 +591,75 @@ impl Aggregate {
 }
 
 impl AggregateFunction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            AggregateFunction::Count(_) => "COUNT",
+            AggregateFunction::Sum(_) => "SUM",
+            AggregateFunction::Max(_) => "MAX",
+            AggregateFunction::Min(_) => "MIN",
+            AggregateFunction::Avg(_) => "AVG",
+            AggregateFunction::Variance(_) => "VARIANCE",
+            AggregateFunction::Stdev(_) => "STDEV",
+            AggregateFunction::RowNumber => "ROW_NUMBER",
+            AggregateFunction::Rank => "RANK",
+            AggregateFunction::DenseRank => "DENSE_RANK",
+            AggregateFunction::Lag { .. } => "LAG",
+            AggregateFunction::Lead { .. } => "LEAD",
+            AggregateFunction::FirstValue(_) => "FIRST_VALUE",
+            AggregateFunction::LastValue(_) => "LAST_VALUE",
+        }
+    }
+
+    /// Returns `true` for functions which can only be evaluated over a window.
+    pub fn is_window_only(&self) -> bool {
+        matches!(
+            self,
+            AggregateFunction::RowNumber
+                | AggregateFunction::Rank
+                | AggregateFunction::DenseRank
+                | AggregateFunction::Lag { .. }
+                | AggregateFunction::Lead { .. }
+                | AggregateFunction::FirstValue(_)
+                | AggregateFunction::LastValue(_)
+        )
+    }
+
     fn to_sql_with_distinct(&self, distinct: bool) -> String {
-        let (name, arg) = match self {
-            AggregateFunction::Count(expr) => ("COUNT", expr.to_sql()),
-            AggregateFunction::Sum(expr) => ("SUM", expr.to_sql()),
-            AggregateFunction::Max(expr) => ("MAX", expr.to_sql()),
-            AggregateFunction::Min(expr) => ("MIN", expr.to_sql()),
-            AggregateFunction::Avg(expr) => ("AVG", expr.to_sql()),
-            AggregateFunction::Variance(expr) => ("VARIANCE", expr.to_sql()),
-            AggregateFunction::Stdev(expr) => ("STDEV", expr.to_sql()),
+        let name = self.name();
+        let arg = match self {
+            AggregateFunction::Count(expr) => expr.to_sql(),
+            AggregateFunction::Sum(expr)
+            | AggregateFunction::Max(expr)
+            | AggregateFunction::Min(expr)
+            | AggregateFunction::Avg(expr)
+            | AggregateFunction::Variance(expr)
+            | AggregateFunction::Stdev(expr)
+            | AggregateFunction::FirstValue(expr)
+            | AggregateFunction::LastValue(expr) => expr.to_sql(),
+            AggregateFunction::RowNumber
+            | AggregateFunction::Rank
+            | AggregateFunction::DenseRank => String::new(),
+            AggregateFunction::Lag {
+                expr,
+                offset,
+                default,
+            }
+            | AggregateFunction::Lead {
+                expr,
+                offset,
+                default,
+            } => match (offset, default) {
+                (None, None) => expr.to_sql(),
+                (Some(offset), None) => format!("{}, {}", expr.to_sql(), offset.to_sql()),
+                (offset, Some(default)) => format!(
+                    "{}, {}, {}",
+                    expr.to_sql(),
+                    offset
+                        .as_ref()
+                        .map_or_else(|| "1".to_owned(), |offset| offset.to_sql()),
+                    default.to_sql()
+                ),
+            },
         };
         if distinct {
             format!("{name}(DISTINCT {arg})")
@@ -583,7 +671,12 @@ impl AggregateFunction {
 
 impl ToSql for Aggregate {
     fn to_sql(&self) -> String {
-        self.func.to_sql_with_distinct(self.distinct)
+        let func = self.func.to_sql_with_distinct(self.distinct);
+
+        match &self.over {
+            Some(window) => format!("{func} OVER ({})", window.to_sql()),
+            None => func,
+        }
     }
 }
 
@@ -1258,6 +1351,22 @@ mod tests {
             .to_sql()
         );
 
+        assert_eq!(
+            "NEXTVAL('seq')",
+            &Expr::Function(Box::new(Function::NextVal(Expr::Literal(
+                AstLiteral::QuotedString("seq".to_owned())
+            ))))
+            .to_sql()
+        );
+
+        assert_eq!(
+            "CURRVAL('seq')",
+            &Expr::Function(Box::new(Function::CurrVal(Expr::Literal(
+                AstLiteral::QuotedString("seq".to_owned())
+            ))))
+            .to_sql()
+        );
+
         assert_eq!(
             r#"CHR(72)"#,
             &Expr::Function(Box::new(Function::Chr(Expr::Literal(AstLiteral::Number(
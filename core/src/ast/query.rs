@@ -8,16 +8,43 @@ use {
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Query {
+    pub with: Option<With>,
     pub body: SetExpr,
     pub order_by: Vec<OrderByExpr>,
     pub limit: Option<Expr>,
     pub offset: Option<Expr>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct With {
+    pub recursive: bool,
+    pub cte_tables: Vec<Cte>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Cte {
+    pub alias: TableAlias,
+    pub query: Query,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum SetExpr {
     Select(Box<Select>),
     Values(Values),
+    SetOperation {
+        op: SetOperator,
+        all: bool,
+        left: Box<Query>,
+        right: Box<Query>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Display)]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum SetOperator {
+    Union,
+    Except,
+    Intersect,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -50,9 +77,15 @@ pub struct TableWithJoins {
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum IndexItem {
     PrimaryKey(Expr),
+    /// Values of the leading columns of a composite primary key, fetching a single row when
+    /// all the key columns are given and scanning the key prefix otherwise
+    CompositePrimaryKey(Vec<Expr>),
     NonClustered {
         name: String,
         asc: Option<bool>,
+        /// Values of the leading expressions of a multi-column index, `cmp_expr` compares
+        /// the expression following them
+        prefix: Vec<Expr>,
         cmp_expr: Option<(IndexOperator, Expr)>,
     },
 }
@@ -115,11 +148,16 @@ pub enum JoinExecutor {
 pub enum JoinOperator {
     Inner(JoinConstraint),
     LeftOuter(JoinConstraint),
+    RightOuter(JoinConstraint),
+    FullOuter(JoinConstraint),
+    Cross,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum JoinConstraint {
     On(Expr),
+    Using(Vec<String>),
+    Natural,
     None,
 }
 
@@ -152,6 +190,7 @@ impl Query {
         };
 
         let Query {
+            with,
             body,
             order_by,
             limit,
@@ -180,16 +219,65 @@ impl Query {
             _ => "".to_owned(),
         };
 
-        let string = [order_by, limit, offset]
+        let with = match with {
+            Some(with) => with.to_sql_with(quoted),
+            None => "".to_owned(),
+        };
+
+        [with, body.to_sql_with(quoted), order_by, limit, offset]
             .iter()
             .filter(|sql| !sql.is_empty())
-            .join(" ");
+            .join(" ")
+    }
+}
 
-        if string.is_empty() {
-            body.to_sql_with(quoted)
-        } else {
-            format!("{} {}", body.to_sql_with(quoted), string)
-        }
+impl ToSql for With {
+    fn to_sql(&self) -> String {
+        self.to_sql_with(true)
+    }
+}
+
+impl ToSqlUnquoted for With {
+    fn to_sql_unquoted(&self) -> String {
+        self.to_sql_with(false)
+    }
+}
+
+impl With {
+    fn to_sql_with(&self, quoted: bool) -> String {
+        let With {
+            recursive,
+            cte_tables,
+        } = self;
+
+        let recursive = if *recursive { "RECURSIVE " } else { "" };
+        let cte_tables = cte_tables
+            .iter()
+            .map(|Cte { alias, query }| {
+                let TableAlias { name, columns } = alias;
+                let quote = |name: &str| match quoted {
+                    true => format!(r#""{name}""#),
+                    false => name.to_owned(),
+                };
+
+                let columns = if columns.is_empty() {
+                    "".to_owned()
+                } else {
+                    format!(
+                        " ({})",
+                        columns.iter().map(|column| quote(column)).join(", ")
+                    )
+                };
+
+                format!(
+                    "{}{columns} AS ({})",
+                    quote(name),
+                    query.to_sql_with(quoted)
+                )
+            })
+            .join(", ");
+
+        format!("WITH {recursive}{cte_tables}")
     }
 }
 
@@ -212,6 +300,53 @@ impl SetExpr {
             (SetExpr::Select(select), false) => select.to_sql_unquoted(),
             (SetExpr::Values(values), true) => format!("VALUES {}", values.to_sql()),
             (SetExpr::Values(values), false) => format!("VALUES {}", values.to_sql_unquoted()),
+            (
+                SetExpr::SetOperation {
+                    op,
+                    all,
+                    left,
+                    right,
+                },
+                _,
+            ) => {
+                let operand = |query: &Query, nested: bool| {
+                    let Query {
+                        with,
+                        body,
+                        order_by,
+                        limit,
+                        offset,
+                    } = query;
+
+                    let bare = with.is_none()
+                        && order_by.is_empty()
+                        && limit.is_none()
+                        && offset.is_none();
+                    let sql = query.to_sql_with(quoted);
+
+                    match (bare, body) {
+                        (true, SetExpr::SetOperation { .. }) if !nested => sql,
+                        (true, SetExpr::Select(_) | SetExpr::Values(_)) => sql,
+                        _ => format!("({sql})"),
+                    }
+                };
+
+                // INTERSECT binds tighter than UNION and EXCEPT, so a nested left operand
+                // is only left bare when it cannot be regrouped by the parser.
+                let nested_left = match &left.body {
+                    SetExpr::SetOperation { op: left_op, .. } => {
+                        *op == SetOperator::Intersect && *left_op != SetOperator::Intersect
+                    }
+                    _ => false,
+                };
+                let all = if *all { " ALL" } else { "" };
+
+                format!(
+                    "{} {op}{all} {}",
+                    operand(left, nested_left),
+                    operand(right, true)
+                )
+            }
         }
     }
 }
@@ -446,8 +581,32 @@ impl Join {
         let (join_operator, join_constraint) = match join_operator {
             JoinOperator::Inner(join_constraint) => ("INNER JOIN", join_constraint),
             JoinOperator::LeftOuter(join_constraint) => ("LEFT OUTER JOIN", join_constraint),
+            JoinOperator::RightOuter(join_constraint) => ("RIGHT OUTER JOIN", join_constraint),
+            JoinOperator::FullOuter(join_constraint) => ("FULL OUTER JOIN", join_constraint),
+            JoinOperator::Cross => ("CROSS JOIN", &JoinConstraint::None),
         };
 
+        match join_constraint {
+            JoinConstraint::Natural => {
+                return format!("NATURAL {join_operator} {}", relation.to_sql_with(quoted));
+            }
+            JoinConstraint::Using(columns) => {
+                let columns = columns
+                    .iter()
+                    .map(|column| match quoted {
+                        true => format!(r#""{column}""#),
+                        false => column.to_owned(),
+                    })
+                    .join(", ");
+
+                return format!(
+                    "{join_operator} {} USING ({columns})",
+                    relation.to_sql_with(quoted)
+                );
+            }
+            JoinConstraint::On(_) | JoinConstraint::None => {}
+        }
+
         let (join_constraint, join_executor) = match quoted {
             true => (join_constraint.to_sql(), join_executor.to_sql()),
             false => (
@@ -525,7 +684,9 @@ impl JoinConstraint {
         match (self, quoted) {
             (JoinConstraint::On(expr), true) => expr.to_sql(),
             (JoinConstraint::On(expr), false) => expr.to_sql_unquoted(),
-            (JoinConstraint::None, _) => "".to_owned(),
+            (JoinConstraint::Using(_) | JoinConstraint::Natural | JoinConstraint::None, _) => {
+                "".to_owned()
+            }
         }
     }
 }
@@ -597,11 +758,11 @@ mod tests {
         crate::{
             ast::{
                 AstLiteral, BinaryOperator, Dictionary, Expr, Join, JoinConstraint, JoinExecutor,
-                JoinOperator, OrderByExpr, Query, Select, SelectItem, SetExpr, TableAlias,
-                TableFactor, TableWithJoins, ToSql, ToSqlUnquoted, Values,
+                JoinOperator, OrderByExpr, Query, Select, SelectItem, SetExpr, SetOperator,
+                TableAlias, TableFactor, TableWithJoins, ToSql, ToSqlUnquoted, Values,
             },
-            parse_sql::parse_expr,
-            translate::translate_expr,
+            parse_sql::{parse_expr, parse_query},
+            translate::{translate_expr, translate_query},
         },
         bigdecimal::BigDecimal,
         std::str::FromStr,
@@ -613,6 +774,12 @@ mod tests {
         translate_expr(&parsed).expect(sql)
     }
 
+    fn query(sql: &str) -> Query {
+        let parsed = parse_query(sql).expect(sql);
+
+        translate_query(&parsed).expect(sql)
+    }
+
     #[test]
     fn to_sql_query() {
         let order_by = vec![OrderByExpr {
@@ -622,6 +789,7 @@ mod tests {
         let actual =
             r#"SELECT * FROM "FOO" AS "F" ORDER BY "name" ASC LIMIT 10 OFFSET 3"#.to_owned();
         let expected = Query {
+            with: None,
             body: SetExpr::Select(Box::new(Select {
                 distinct: false,
                 projection: vec![SelectItem::Wildcard],
@@ -660,6 +828,7 @@ mod tests {
         }];
         let actual = "SELECT * FROM FOO AS F ORDER BY name ASC LIMIT 10 OFFSET 3".to_owned();
         let expected = Query {
+            with: None,
             body: SetExpr::Select(Box::new(Select {
                 distinct: false,
                 projection: vec![SelectItem::Wildcard],
@@ -994,6 +1163,7 @@ mod tests {
         let actual = r#"(SELECT * FROM "FOO") AS "F""#;
         let expected = TableFactor::Derived {
             subquery: Query {
+                with: None,
                 body: SetExpr::Select(Box::new(Select {
                     distinct: false,
                     projection: vec![SelectItem::Wildcard],
@@ -1061,6 +1231,7 @@ mod tests {
         let actual = "(SELECT * FROM FOO) AS F";
         let expected = TableFactor::Derived {
             subquery: Query {
+                with: None,
                 body: SetExpr::Select(Box::new(Select {
                     distinct: false,
                     projection: vec![SelectItem::Wildcard],
@@ -1213,6 +1384,63 @@ mod tests {
         }
         .to_sql();
         assert_eq!(actual, expected);
+
+        let actual = r#"RIGHT OUTER JOIN "PlayerItem" ON "PlayerItem"."user_id" = "Player"."id""#;
+        let expected = Join {
+            relation: TableFactor::Table {
+                name: "PlayerItem".to_owned(),
+                alias: None,
+                index: None,
+            },
+            join_operator: JoinOperator::RightOuter(JoinConstraint::On(expr(
+                r#""PlayerItem"."user_id" = "Player"."id""#,
+            ))),
+            join_executor: JoinExecutor::NestedLoop,
+        }
+        .to_sql();
+        assert_eq!(actual, expected);
+
+        let actual = r#"FULL OUTER JOIN "PlayerItem" USING ("id", "name")"#;
+        let expected = Join {
+            relation: TableFactor::Table {
+                name: "PlayerItem".to_owned(),
+                alias: None,
+                index: None,
+            },
+            join_operator: JoinOperator::FullOuter(JoinConstraint::Using(vec![
+                "id".to_owned(),
+                "name".to_owned(),
+            ])),
+            join_executor: JoinExecutor::NestedLoop,
+        }
+        .to_sql();
+        assert_eq!(actual, expected);
+
+        let actual = r#"NATURAL LEFT OUTER JOIN "PlayerItem""#;
+        let expected = Join {
+            relation: TableFactor::Table {
+                name: "PlayerItem".to_owned(),
+                alias: None,
+                index: None,
+            },
+            join_operator: JoinOperator::LeftOuter(JoinConstraint::Natural),
+            join_executor: JoinExecutor::NestedLoop,
+        }
+        .to_sql();
+        assert_eq!(actual, expected);
+
+        let actual = r#"CROSS JOIN "PlayerItem""#;
+        let expected = Join {
+            relation: TableFactor::Table {
+                name: "PlayerItem".to_owned(),
+                alias: None,
+                index: None,
+            },
+            join_operator: JoinOperator::Cross,
+            join_executor: JoinExecutor::NestedLoop,
+        }
+        .to_sql();
+        assert_eq!(actual, expected);
     }
 
     #[test]
@@ -1354,4 +1582,52 @@ mod tests {
         .to_sql_unquoted();
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn to_sql_set_operation() {
+        let actual = r#"SELECT * FROM "Foo" UNION ALL SELECT * FROM "Bar""#;
+        let expected = SetExpr::SetOperation {
+            op: SetOperator::Union,
+            all: true,
+            left: Box::new(query("SELECT * FROM Foo")),
+            right: Box::new(query("SELECT * FROM Bar")),
+        }
+        .to_sql();
+        assert_eq!(actual, expected);
+
+        let actual = "SELECT * FROM Foo EXCEPT (SELECT * FROM Bar ORDER BY id LIMIT 1)";
+        let expected = SetExpr::SetOperation {
+            op: SetOperator::Except,
+            all: false,
+            left: Box::new(query("SELECT * FROM Foo")),
+            right: Box::new(query("SELECT * FROM Bar ORDER BY id LIMIT 1")),
+        }
+        .to_sql_unquoted();
+        assert_eq!(actual, expected);
+
+        let sql = "SELECT * FROM Foo UNION SELECT * FROM Bar EXCEPT SELECT * FROM Baz";
+        assert_eq!(query(sql).to_sql_unquoted(), sql);
+
+        let sql = "(SELECT * FROM Foo UNION SELECT * FROM Bar) INTERSECT SELECT * FROM Baz";
+        assert_eq!(query(sql).to_sql_unquoted(), sql);
+
+        let sql = "SELECT * FROM Foo UNION (SELECT * FROM Bar INTERSECT SELECT * FROM Baz)";
+        assert_eq!(query(sql).to_sql_unquoted(), sql);
+
+        let sql = "VALUES (1), (2) UNION ALL VALUES (3) ORDER BY column1 DESC LIMIT 2";
+        assert_eq!(query(sql).to_sql_unquoted(), sql);
+    }
+
+    #[test]
+    fn to_sql_with() {
+        let actual = r#"WITH "Sub" AS (SELECT * FROM "Foo") SELECT * FROM "Sub""#;
+        let expected = query("WITH Sub AS (SELECT * FROM Foo) SELECT * FROM Sub").to_sql();
+        assert_eq!(actual, expected);
+
+        let sql = "WITH RECURSIVE Nums (n) AS (VALUES (1) UNION ALL SELECT * FROM Nums LIMIT 3), Two AS (SELECT * FROM Nums) SELECT * FROM Two";
+        assert_eq!(query(sql).to_sql_unquoted(), sql);
+
+        let sql = "SELECT * FROM Foo UNION (WITH Sub AS (SELECT * FROM Bar) SELECT * FROM Sub)";
+        assert_eq!(query(sql).to_sql_unquoted(), sql);
+    }
 }
@@ -0,0 +1,209 @@
+use {
+    super::{Expr, OrderByExpr, ToSql},
+    serde::{Deserialize, Serialize},
+};
+
+/// Window specification of `OVER (PARTITION BY ... ORDER BY ... <frame>)`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct WindowSpec {
+    pub partition_by: Vec<Expr>,
+    pub order_by: Vec<OrderByExpr>,
+    pub frame: Option<WindowFrame>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct WindowFrame {
+    pub units: WindowFrameUnits,
+    pub start: WindowFrameBound,
+    pub end: WindowFrameBound,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WindowFrameUnits {
+    Rows,
+    Range,
+}
+
+/// `None` offset of `Preceding` or `Following` stands for `UNBOUNDED`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WindowFrameBound {
+    CurrentRow,
+    Preceding(Option<Expr>),
+    Following(Option<Expr>),
+}
+
+impl ToSql for WindowSpec {
+    fn to_sql(&self) -> String {
+        let WindowSpec {
+            partition_by,
+            order_by,
+            frame,
+        } = self;
+
+        let partition_by = (!partition_by.is_empty()).then(|| {
+            let exprs = partition_by
+                .iter()
+                .map(ToSql::to_sql)
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            format!("PARTITION BY {exprs}")
+        });
+        let order_by = (!order_by.is_empty()).then(|| {
+            let exprs = order_by
+                .iter()
+                .map(ToSql::to_sql)
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            format!("ORDER BY {exprs}")
+        });
+        let frame = frame.as_ref().map(ToSql::to_sql);
+
+        [partition_by, order_by, frame]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl ToSql for WindowFrame {
+    fn to_sql(&self) -> String {
+        let WindowFrame { units, start, end } = self;
+        let units = match units {
+            WindowFrameUnits::Rows => "ROWS",
+            WindowFrameUnits::Range => "RANGE",
+        };
+
+        format!("{units} BETWEEN {} AND {}", start.to_sql(), end.to_sql())
+    }
+}
+
+impl ToSql for WindowFrameBound {
+    fn to_sql(&self) -> String {
+        match self {
+            WindowFrameBound::CurrentRow => "CURRENT ROW".to_owned(),
+            WindowFrameBound::Preceding(None) => "UNBOUNDED PRECEDING".to_owned(),
+            WindowFrameBound::Preceding(Some(expr)) => format!("{} PRECEDING", expr.to_sql()),
+            WindowFrameBound::Following(None) => "UNBOUNDED FOLLOWING".to_owned(),
+            WindowFrameBound::Following(Some(expr)) => format!("{} FOLLOWING", expr.to_sql()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        crate::ast::{
+            Aggregate, AggregateFunction, AstLiteral, CountArgExpr, Expr, OrderByExpr, ToSql,
+            WindowFrame, WindowFrameBound, WindowFrameUnits, WindowSpec,
+        },
+        bigdecimal::BigDecimal,
+    };
+
+    fn over(aggregate: Aggregate, window: WindowSpec) -> Expr {
+        Expr::Aggregate(Box::new(Aggregate {
+            over: Some(Box::new(window)),
+            ..aggregate
+        }))
+    }
+
+    #[test]
+    fn to_sql() {
+        let id = || Expr::Identifier("id".to_owned());
+        let num = |n: i64| Expr::Literal(AstLiteral::Number(BigDecimal::from(n)));
+
+        assert_eq!(
+            "ROW_NUMBER() OVER ()",
+            over(
+                Aggregate::new(AggregateFunction::RowNumber, false),
+                WindowSpec {
+                    partition_by: Vec::new(),
+                    order_by: Vec::new(),
+                    frame: None,
+                }
+            )
+            .to_sql()
+        );
+
+        assert_eq!(
+            r#"RANK() OVER (PARTITION BY "team", "year" ORDER BY "score" DESC)"#,
+            over(
+                Aggregate::new(AggregateFunction::Rank, false),
+                WindowSpec {
+                    partition_by: vec![
+                        Expr::Identifier("team".to_owned()),
+                        Expr::Identifier("year".to_owned()),
+                    ],
+                    order_by: vec![OrderByExpr {
+                        expr: Expr::Identifier("score".to_owned()),
+                        asc: Some(false),
+                    }],
+                    frame: None,
+                }
+            )
+            .to_sql()
+        );
+
+        assert_eq!(
+            r#"LAG("id", 2, 0) OVER (ORDER BY "id")"#,
+            over(
+                Aggregate::new(
+                    AggregateFunction::Lag {
+                        expr: id(),
+                        offset: Some(Box::new(num(2))),
+                        default: Some(Box::new(num(0))),
+                    },
+                    false
+                ),
+                WindowSpec {
+                    partition_by: Vec::new(),
+                    order_by: vec![OrderByExpr {
+                        expr: id(),
+                        asc: None,
+                    }],
+                    frame: None,
+                }
+            )
+            .to_sql()
+        );
+
+        assert_eq!(
+            r#"SUM("id") OVER (ORDER BY "id" ROWS BETWEEN 1 PRECEDING AND UNBOUNDED FOLLOWING)"#,
+            over(
+                Aggregate::sum(id(), false),
+                WindowSpec {
+                    partition_by: Vec::new(),
+                    order_by: vec![OrderByExpr {
+                        expr: id(),
+                        asc: None,
+                    }],
+                    frame: Some(WindowFrame {
+                        units: WindowFrameUnits::Rows,
+                        start: WindowFrameBound::Preceding(Some(num(1))),
+                        end: WindowFrameBound::Following(None),
+                    }),
+                }
+            )
+            .to_sql()
+        );
+
+        assert_eq!(
+            "COUNT(*) OVER (RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW)",
+            over(
+                Aggregate::count(CountArgExpr::Wildcard, false),
+                WindowSpec {
+                    partition_by: Vec::new(),
+                    order_by: Vec::new(),
+                    frame: Some(WindowFrame {
+                        units: WindowFrameUnits::Range,
+                        start: WindowFrameBound::Preceding(None),
+                        end: WindowFrameBound::CurrentRow,
+                    }),
+                }
+            )
+            .to_sql()
+        );
+    }
+}
@@ -41,7 +41,9 @@ pub use expr::{
     function,
 };
 /// Functions for building transaction statements
-pub use transaction::{begin, commit, rollback};
+pub use transaction::{
+    begin, commit, release_savepoint, rollback, rollback_to_savepoint, savepoint,
+};
 pub use {
     alter_table::{
         AddColumnNode, AlterTableNode, DropColumnNode, RenameColumnNode, RenameTableNode,
@@ -62,13 +64,17 @@ pub use {
     index_item::{
         CmpExprNode, IndexItemNode, NonClusteredNode, PrimaryKeyNode, non_clustered, primary_key,
     },
-    insert::InsertNode,
+    insert::{
+        InsertNode, InsertSourceNode, OnConflictDoNothingNode, OnConflictDoUpdateNode,
+        OnConflictNode,
+    },
     order_by_expr::OrderByExprNode,
     order_by_expr_list::OrderByExprList,
     query::QueryNode,
     select::{
         FilterNode, GroupByNode, HashJoinNode, HavingNode, JoinConstraintNode, JoinNode, LimitNode,
-        OffsetLimitNode, OffsetNode, OrderByNode, ProjectNode, SelectNode, select, values,
+        OffsetLimitNode, OffsetNode, OrderByNode, ProjectNode, SelectNode, SetOperationNode,
+        select, values,
     },
     select_item::SelectItemNode,
     select_item_list::SelectItemList,
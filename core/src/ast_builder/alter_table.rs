@@ -65,7 +65,7 @@ impl Build for AddColumnNode {
     fn build(self) -> Result<Statement> {
         let table_name = self.table_node.table_name;
         let operation = AlterTableOperation::AddColumn {
-            column_def: self.column_def.try_into()?,
+            column_def: Box::new(self.column_def.try_into()?),
         };
         Ok(Statement::AlterTable {
             name: table_name,
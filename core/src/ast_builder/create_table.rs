@@ -52,7 +52,9 @@ impl Build for CreateTableNode {
             columns,
             source: None,
             engine: None,
+            key_constraints: Vec::new(),
             foreign_keys: Vec::new(),
+            checks: Vec::new(),
             comment: None,
         })
     }
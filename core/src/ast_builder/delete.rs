@@ -35,6 +35,7 @@ impl<'a> Build for DeleteNode<'a> {
         Ok(Statement::Delete {
             table_name,
             selection,
+            returning: Vec::new(),
         })
     }
 }
@@ -168,6 +168,7 @@ mod test {
             };
 
             let query = Query {
+                with: None,
                 body: SetExpr::Select(Box::new(select)),
                 order_by: Vec::new(),
                 limit: None,
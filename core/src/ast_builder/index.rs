@@ -1,5 +1,5 @@
 use {
-    super::{Build, OrderByExprNode},
+    super::{Build, OrderByExprList},
     crate::{ast::Statement, result::Result},
 };
 
@@ -7,15 +7,15 @@ use {
 pub struct CreateIndexNode<'a> {
     name: String,
     table_name: String,
-    column: OrderByExprNode<'a>,
+    columns: OrderByExprList<'a>,
 }
 
 impl<'a> CreateIndexNode<'a> {
-    pub fn new(table_name: String, name: String, column: OrderByExprNode<'a>) -> Self {
+    pub fn new(table_name: String, name: String, columns: OrderByExprList<'a>) -> Self {
         Self {
             table_name,
             name,
-            column,
+            columns,
         }
     }
 }
@@ -24,12 +24,13 @@ impl<'a> Build for CreateIndexNode<'a> {
     fn build(self) -> Result<Statement> {
         let table_name = self.table_name;
         let name = self.name;
-        let column = self.column.try_into()?;
+        let columns = self.columns.try_into()?;
 
         Ok(Statement::CreateIndex {
             name,
             table_name,
-            column,
+            columns,
+            unique: false,
         })
     }
 }
@@ -68,6 +69,12 @@ mod tests {
         let actual = table("Foo").create_index("nameIndex", "name desc").build();
         let expected = "CREATE INDEX nameIndex ON Foo (name Desc)";
         test(actual, expected);
+
+        let actual = table("Foo")
+            .create_index("idNameIndex", "id, name desc")
+            .build();
+        let expected = "CREATE INDEX idNameIndex ON Foo (id, name Desc)";
+        test(actual, expected);
     }
 
     #[test]
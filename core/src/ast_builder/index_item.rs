@@ -59,6 +59,7 @@ impl<'a> Prebuild<IndexItem> for IndexItemNode<'a> {
                 Ok(IndexItem::NonClustered {
                     name,
                     asc,
+                    prefix: Vec::new(),
                     cmp_expr: cmp_expr_result,
                 })
             }
@@ -62,6 +62,7 @@ mod tests {
         let expected = IndexItem::NonClustered {
             name: "idx".to_owned(),
             asc: Some(true),
+            prefix: Vec::new(),
             cmp_expr: Some((
                 IndexOperator::Eq,
                 Expr::Literal(AstLiteral::Number(1.into())),
@@ -77,6 +78,7 @@ mod tests {
         let expected = IndexItem::NonClustered {
             name: "idx".to_owned(),
             asc: Some(false),
+            prefix: Vec::new(),
             cmp_expr: Some((
                 IndexOperator::Eq,
                 Expr::Literal(AstLiteral::Number(2.into())),
@@ -89,6 +91,7 @@ mod tests {
         let expected = IndexItem::NonClustered {
             name: "idx".to_owned(),
             asc: None,
+            prefix: Vec::new(),
             cmp_expr: Some((
                 IndexOperator::Eq,
                 Expr::Literal(AstLiteral::Number(3.into())),
@@ -52,6 +52,7 @@ mod tests {
         let expected = IndexItem::NonClustered {
             name: "idx".to_owned(),
             asc: None,
+            prefix: Vec::new(),
             cmp_expr: Some((
                 IndexOperator::Gt,
                 Expr::Literal(AstLiteral::Number(1.into())),
@@ -64,6 +65,7 @@ mod tests {
         let expected = IndexItem::NonClustered {
             name: "idx".to_owned(),
             asc: None,
+            prefix: Vec::new(),
             cmp_expr: Some((
                 IndexOperator::Lt,
                 Expr::Literal(AstLiteral::Number(1.into())),
@@ -76,6 +78,7 @@ mod tests {
         let expected = IndexItem::NonClustered {
             name: "idx".to_owned(),
             asc: None,
+            prefix: Vec::new(),
             cmp_expr: Some((
                 IndexOperator::GtEq,
                 Expr::Literal(AstLiteral::Number(1.into())),
@@ -88,6 +91,7 @@ mod tests {
         let expected = IndexItem::NonClustered {
             name: "idx".to_owned(),
             asc: None,
+            prefix: Vec::new(),
             cmp_expr: Some((
                 IndexOperator::LtEq,
                 Expr::Literal(AstLiteral::Number(1.into())),
@@ -100,6 +104,7 @@ mod tests {
         let expected = IndexItem::NonClustered {
             name: "idx".to_owned(),
             asc: None,
+            prefix: Vec::new(),
             cmp_expr: Some((
                 IndexOperator::Eq,
                 Expr::Literal(AstLiteral::Number(1.into())),
@@ -112,6 +117,7 @@ mod tests {
         let expected = IndexItem::NonClustered {
             name: "idx".to_owned(),
             asc: None,
+            prefix: Vec::new(),
             cmp_expr: None,
         };
         assert_eq!(actual, expected);
@@ -1,6 +1,9 @@
 use {
-    super::{Build, ColumnList, ExprList, QueryNode},
-    crate::{ast::Statement, result::Result},
+    super::{AssignmentNode, Build, ColumnList, ExprList, ExprNode, QueryNode},
+    crate::{
+        ast::{Assignment, Expr, OnConflict, OnConflictAction, Statement},
+        result::Result,
+    },
 };
 
 #[derive(Clone, Debug)]
@@ -45,8 +48,26 @@ pub struct InsertSourceNode<'a> {
     source: QueryNode<'a>,
 }
 
-impl<'a> Build for InsertSourceNode<'a> {
-    fn build(self) -> Result<Statement> {
+impl<'a> InsertSourceNode<'a> {
+    /// `ON CONFLICT (columns)`, `columns` must form a primary key or a unique constraint.
+    pub fn on_conflict<T: Into<ColumnList>>(self, columns: T) -> OnConflictNode<'a> {
+        OnConflictNode {
+            insert_source_node: self,
+            target: Some(columns.into()),
+        }
+    }
+
+    /// `ON CONFLICT DO NOTHING`, which skips rows violating any unique constraint.
+    pub fn on_conflict_do_nothing(self) -> OnConflictDoNothingNode<'a> {
+        OnConflictDoNothingNode {
+            on_conflict_node: OnConflictNode {
+                insert_source_node: self,
+                target: None,
+            },
+        }
+    }
+
+    fn build_with(self, on_conflict: Option<OnConflict>) -> Result<Statement> {
         let table_name = self.insert_node.table_name;
         let columns = self.insert_node.columns;
         let columns = columns.map_or_else(|| Ok(vec![]), |v| v.try_into())?;
@@ -56,13 +77,107 @@ impl<'a> Build for InsertSourceNode<'a> {
             table_name,
             columns,
             source,
+            on_conflict,
+            returning: Vec::new(),
         })
     }
 }
 
+impl<'a> Build for InsertSourceNode<'a> {
+    fn build(self) -> Result<Statement> {
+        self.build_with(None)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct OnConflictNode<'a> {
+    insert_source_node: InsertSourceNode<'a>,
+    target: Option<ColumnList>,
+}
+
+impl<'a> OnConflictNode<'a> {
+    pub fn do_nothing(self) -> OnConflictDoNothingNode<'a> {
+        OnConflictDoNothingNode {
+            on_conflict_node: self,
+        }
+    }
+
+    pub fn do_update_set<T: Into<ExprNode<'a>>>(
+        self,
+        id: &str,
+        value: T,
+    ) -> OnConflictDoUpdateNode<'a> {
+        OnConflictDoUpdateNode {
+            on_conflict_node: self,
+            assignments: vec![AssignmentNode::Expr(id.to_owned(), value.into())],
+            selection: None,
+        }
+    }
+
+    fn build_with(self, action: OnConflictAction) -> Result<Statement> {
+        let target = self.target.map_or_else(|| Ok(vec![]), |v| v.try_into())?;
+
+        self.insert_source_node
+            .build_with(Some(OnConflict { target, action }))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct OnConflictDoNothingNode<'a> {
+    on_conflict_node: OnConflictNode<'a>,
+}
+
+impl<'a> Build for OnConflictDoNothingNode<'a> {
+    fn build(self) -> Result<Statement> {
+        self.on_conflict_node
+            .build_with(OnConflictAction::DoNothing)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct OnConflictDoUpdateNode<'a> {
+    on_conflict_node: OnConflictNode<'a>,
+    assignments: Vec<AssignmentNode<'a>>,
+    selection: Option<ExprNode<'a>>,
+}
+
+impl<'a> OnConflictDoUpdateNode<'a> {
+    pub fn set<T: Into<ExprNode<'a>>>(mut self, id: &str, value: T) -> Self {
+        self.assignments
+            .push(AssignmentNode::Expr(id.to_owned(), value.into()));
+        self
+    }
+
+    pub fn filter<T: Into<ExprNode<'a>>>(mut self, expr: T) -> Self {
+        let expr = expr.into();
+        self.selection = Some(match self.selection {
+            Some(selection) => selection.and(expr),
+            None => expr,
+        });
+        self
+    }
+}
+
+impl<'a> Build for OnConflictDoUpdateNode<'a> {
+    fn build(self) -> Result<Statement> {
+        let assignments = self
+            .assignments
+            .into_iter()
+            .map(Assignment::try_from)
+            .collect::<Result<Vec<_>>>()?;
+        let selection = self.selection.map(Expr::try_from).transpose()?;
+
+        self.on_conflict_node
+            .build_with(OnConflictAction::DoUpdate {
+                assignments,
+                selection,
+            })
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::ast_builder::{Build, num, table, test};
+    use crate::ast_builder::{Build, col, num, table, test};
 
     #[test]
     fn insert() {
@@ -93,4 +208,40 @@ mod tests {
         let expected = r#"INSERT INTO Foo SELECT id, name FROM Bar LIMIT 10"#;
         test(actual, expected);
     }
+
+    #[test]
+    fn insert_on_conflict() {
+        let actual = table("Foo")
+            .insert()
+            .values(vec!["1, 'a'"])
+            .on_conflict("id")
+            .do_nothing()
+            .build();
+        let expected = r#"INSERT INTO Foo VALUES (1, 'a') ON CONFLICT (id) DO NOTHING"#;
+        test(actual, expected);
+
+        let actual = table("Foo")
+            .insert()
+            .values(vec!["1, 'a'"])
+            .on_conflict_do_nothing()
+            .build();
+        let expected = r#"INSERT INTO Foo VALUES (1, 'a') ON CONFLICT DO NOTHING"#;
+        test(actual, expected);
+
+        let actual = table("Foo")
+            .insert()
+            .columns("id, name, cnt")
+            .values(vec!["1, 'a', 1"])
+            .on_conflict("id")
+            .do_update_set("name", col("EXCLUDED.name"))
+            .set("cnt", col("cnt").add(num(1)))
+            .filter(col("Foo.name").neq(col("EXCLUDED.name")))
+            .build();
+        let expected = r#"
+            INSERT INTO Foo (id, name, cnt) VALUES (1, 'a', 1)
+            ON CONFLICT (id) DO UPDATE SET name = EXCLUDED.name, cnt = cnt + 1
+            WHERE Foo.name <> EXCLUDED.name
+        "#;
+        test(actual, expected);
+    }
 }
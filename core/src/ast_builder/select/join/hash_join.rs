@@ -192,6 +192,7 @@ mod tests {
             };
 
             Ok(Statement::Query(Query {
+                with: None,
                 body: SetExpr::Select(Box::new(select)),
                 order_by: Vec::new(),
                 limit: None,
@@ -242,6 +243,7 @@ mod tests {
             };
 
             Ok(Statement::Query(Query {
+                with: None,
                 body: SetExpr::Select(Box::new(select)),
                 order_by: Vec::new(),
                 limit: None,
@@ -296,6 +298,7 @@ mod tests {
                 from: TableWithJoins {
                     relation: TableFactor::Derived {
                         subquery: Query {
+                            with: None,
                             body: SetExpr::Select(Box::new(subquery)),
                             order_by: Vec::new(),
                             limit: None,
@@ -314,6 +317,7 @@ mod tests {
             };
 
             Ok(Statement::Query(Query {
+                with: None,
                 body: SetExpr::Select(Box::new(select)),
                 order_by: Vec::new(),
                 limit: None,
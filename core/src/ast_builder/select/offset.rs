@@ -5,7 +5,7 @@ use {
         ast_builder::{
             ExprNode, FilterNode, GroupByNode, HashJoinNode, HavingNode, JoinConstraintNode,
             JoinNode, OffsetLimitNode, OrderByNode, ProjectNode, QueryNode, SelectNode,
-            TableFactorNode,
+            SetOperationNode, TableFactorNode,
         },
         result::Result,
     },
@@ -23,6 +23,7 @@ pub enum PrevNode<'a> {
     Filter(FilterNode<'a>),
     OrderBy(OrderByNode<'a>),
     ProjectNode(Box<ProjectNode<'a>>),
+    SetOperation(SetOperationNode<'a>),
 }
 
 impl<'a> Prebuild<Query> for PrevNode<'a> {
@@ -38,6 +39,7 @@ impl<'a> Prebuild<Query> for PrevNode<'a> {
             Self::Filter(node) => node.prebuild(),
             Self::OrderBy(node) => node.prebuild(),
             Self::ProjectNode(node) => node.prebuild(),
+            Self::SetOperation(node) => node.prebuild(),
         }
     }
 }
@@ -102,6 +104,12 @@ impl<'a> From<ProjectNode<'a>> for PrevNode<'a> {
     }
 }
 
+impl<'a> From<SetOperationNode<'a>> for PrevNode<'a> {
+    fn from(node: SetOperationNode<'a>) -> Self {
+        PrevNode::SetOperation(node)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct OffsetNode<'a> {
     prev_node: PrevNode<'a>,
@@ -257,6 +265,7 @@ mod tests {
             };
 
             Ok(Statement::Query(Query {
+                with: None,
                 body: SetExpr::Select(Box::new(select)),
                 order_by: Vec::new(),
                 limit: None,
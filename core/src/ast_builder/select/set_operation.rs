@@ -0,0 +1,173 @@
+use {
+    super::Prebuild,
+    crate::{
+        ast::{Query, SetExpr, SetOperator},
+        ast_builder::{
+            ExprNode, LimitNode, OffsetNode, OrderByExprList, OrderByNode, QueryNode,
+            TableFactorNode,
+        },
+        result::Result,
+    },
+};
+
+#[derive(Clone, Debug)]
+pub struct SetOperationNode<'a> {
+    left: Box<QueryNode<'a>>,
+    op: SetOperator,
+    all: bool,
+    right: Box<QueryNode<'a>>,
+}
+
+impl<'a> SetOperationNode<'a> {
+    pub fn new<L: Into<QueryNode<'a>>, R: Into<QueryNode<'a>>>(
+        left: L,
+        op: SetOperator,
+        all: bool,
+        right: R,
+    ) -> Self {
+        Self {
+            left: Box::new(left.into()),
+            op,
+            all,
+            right: Box::new(right.into()),
+        }
+    }
+
+    pub fn union<T: Into<QueryNode<'a>>>(self, query: T) -> Self {
+        Self::new(self, SetOperator::Union, false, query)
+    }
+
+    pub fn union_all<T: Into<QueryNode<'a>>>(self, query: T) -> Self {
+        Self::new(self, SetOperator::Union, true, query)
+    }
+
+    pub fn intersect<T: Into<QueryNode<'a>>>(self, query: T) -> Self {
+        Self::new(self, SetOperator::Intersect, false, query)
+    }
+
+    pub fn intersect_all<T: Into<QueryNode<'a>>>(self, query: T) -> Self {
+        Self::new(self, SetOperator::Intersect, true, query)
+    }
+
+    pub fn except<T: Into<QueryNode<'a>>>(self, query: T) -> Self {
+        Self::new(self, SetOperator::Except, false, query)
+    }
+
+    pub fn except_all<T: Into<QueryNode<'a>>>(self, query: T) -> Self {
+        Self::new(self, SetOperator::Except, true, query)
+    }
+
+    pub fn order_by<T: Into<OrderByExprList<'a>>>(self, order_by_exprs: T) -> OrderByNode<'a> {
+        OrderByNode::new(self, order_by_exprs)
+    }
+
+    pub fn offset<T: Into<ExprNode<'a>>>(self, expr: T) -> OffsetNode<'a> {
+        OffsetNode::new(self, expr)
+    }
+
+    pub fn limit<T: Into<ExprNode<'a>>>(self, expr: T) -> LimitNode<'a> {
+        LimitNode::new(self, expr)
+    }
+
+    pub fn alias_as(self, table_alias: &'a str) -> TableFactorNode<'a> {
+        QueryNode::SetOperationNode(self).alias_as(table_alias)
+    }
+}
+
+impl<'a> Prebuild<Query> for SetOperationNode<'a> {
+    fn prebuild(self) -> Result<Query> {
+        let body = SetExpr::SetOperation {
+            op: self.op,
+            all: self.all,
+            left: Box::new(Query::try_from(*self.left)?),
+            right: Box::new(Query::try_from(*self.right)?),
+        };
+
+        Ok(Query {
+            with: None,
+            body,
+            order_by: Vec::new(),
+            limit: None,
+            offset: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast_builder::{Build, col, num, table, test, values};
+
+    #[test]
+    fn set_operation() {
+        // select node -> union node -> build
+        let actual = table("Foo").select().union(table("Bar").select()).build();
+        let expected = "SELECT * FROM Foo UNION SELECT * FROM Bar";
+        test(actual, expected);
+
+        // project node -> union all node -> build
+        let actual = table("Foo")
+            .select()
+            .project("id")
+            .union_all(table("Bar").select().project("id"))
+            .build();
+        let expected = "SELECT id FROM Foo UNION ALL SELECT id FROM Bar";
+        test(actual, expected);
+
+        // filter node -> intersect node -> except node -> build
+        let actual = table("Foo")
+            .select()
+            .filter(col("id").gt(num(1)))
+            .intersect("SELECT * FROM Bar")
+            .except(table("Baz").select())
+            .build();
+        let expected = "
+            SELECT * FROM Foo WHERE id > 1
+            INTERSECT SELECT * FROM Bar
+            EXCEPT SELECT * FROM Baz
+        ";
+        test(actual, expected);
+
+        // values node -> intersect all node -> except all node -> build
+        let actual = values(vec!["1", "2"])
+            .intersect_all(values(vec!["2"]))
+            .except_all(values(vec!["3"]))
+            .build();
+        let expected = "VALUES (1), (2) INTERSECT ALL VALUES (2) EXCEPT ALL VALUES (3)";
+        test(actual, expected);
+
+        // union node -> order by node -> limit node -> build
+        let actual = table("Foo")
+            .select()
+            .project("id")
+            .union(table("Bar").select().project("id"))
+            .order_by("id DESC")
+            .limit(3)
+            .build();
+        let expected = "
+            SELECT id FROM Foo
+            UNION SELECT id FROM Bar
+            ORDER BY id DESC
+            LIMIT 3
+        ";
+        test(actual, expected);
+
+        // union node -> offset node -> build
+        let actual = table("Foo")
+            .select()
+            .union(table("Bar").select())
+            .offset(2)
+            .build();
+        let expected = "SELECT * FROM Foo UNION SELECT * FROM Bar OFFSET 2";
+        test(actual, expected);
+
+        // union node -> derived subquery
+        let actual = table("Foo")
+            .select()
+            .union(table("Bar").select())
+            .alias_as("Sub")
+            .select()
+            .build();
+        let expected = "SELECT * FROM (SELECT * FROM Foo UNION SELECT * FROM Bar) AS Sub";
+        test(actual, expected);
+    }
+}
@@ -1,8 +1,8 @@
 use crate::{
-    ast::{Expr, Query, SetExpr, Values},
+    ast::{Expr, Query, SetExpr, SetOperator, Values},
     ast_builder::{
         ExprList, ExprNode, LimitNode, OffsetNode, OrderByExprList, OrderByNode, QueryNode,
-        TableFactorNode, select::Prebuild,
+        SetOperationNode, TableFactorNode, select::Prebuild,
     },
     result::Result,
 };
@@ -25,6 +25,30 @@ impl<'a> ValuesNode<'a> {
         LimitNode::new(self, expr)
     }
 
+    pub fn union<T: Into<QueryNode<'a>>>(self, query: T) -> SetOperationNode<'a> {
+        SetOperationNode::new(self, SetOperator::Union, false, query)
+    }
+
+    pub fn union_all<T: Into<QueryNode<'a>>>(self, query: T) -> SetOperationNode<'a> {
+        SetOperationNode::new(self, SetOperator::Union, true, query)
+    }
+
+    pub fn intersect<T: Into<QueryNode<'a>>>(self, query: T) -> SetOperationNode<'a> {
+        SetOperationNode::new(self, SetOperator::Intersect, false, query)
+    }
+
+    pub fn intersect_all<T: Into<QueryNode<'a>>>(self, query: T) -> SetOperationNode<'a> {
+        SetOperationNode::new(self, SetOperator::Intersect, true, query)
+    }
+
+    pub fn except<T: Into<QueryNode<'a>>>(self, query: T) -> SetOperationNode<'a> {
+        SetOperationNode::new(self, SetOperator::Except, false, query)
+    }
+
+    pub fn except_all<T: Into<QueryNode<'a>>>(self, query: T) -> SetOperationNode<'a> {
+        SetOperationNode::new(self, SetOperator::Except, true, query)
+    }
+
     pub fn alias_as(self, table_alias: &'a str) -> TableFactorNode<'a> {
         QueryNode::ValuesNode(self).alias_as(table_alias)
     }
@@ -41,6 +65,7 @@ impl<'a> Prebuild<Query> for ValuesNode<'a> {
         let body = SetExpr::Values(Values(values));
 
         Ok(Query {
+            with: None,
             body,
             order_by: Vec::new(),
             limit: None,
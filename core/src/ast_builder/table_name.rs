@@ -1,6 +1,6 @@
 use super::{
     AlterTableNode, CreateIndexNode, CreateTableNode, DeleteNode, DropIndexNode, DropTableNode,
-    IndexItemNode, InsertNode, OrderByExprNode, SelectNode, ShowColumnsNode, TableFactorNode,
+    IndexItemNode, InsertNode, OrderByExprList, SelectNode, ShowColumnsNode, TableFactorNode,
     UpdateNode, table_factor::TableType,
 };
 #[derive(Clone, Debug)]
@@ -73,12 +73,12 @@ impl<'a> TableNameNode {
         DropIndexNode::new(self.table_name, name.to_owned())
     }
 
-    pub fn create_index<T: Into<OrderByExprNode<'a>>>(
+    pub fn create_index<T: Into<OrderByExprList<'a>>>(
         self,
         name: &str,
-        column: T,
+        columns: T,
     ) -> CreateIndexNode<'a> {
-        CreateIndexNode::new(self.table_name, name.to_owned(), column.into())
+        CreateIndexNode::new(self.table_name, name.to_owned(), columns.into())
     }
 
     pub fn alter_table(self) -> AlterTableNode {
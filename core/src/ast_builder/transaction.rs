@@ -9,10 +9,21 @@ pub fn commit() -> Statement {
 pub fn rollback() -> Statement {
     Statement::Rollback
 }
+pub fn savepoint(name: &str) -> Statement {
+    Statement::Savepoint(name.to_owned())
+}
+pub fn rollback_to_savepoint(name: &str) -> Statement {
+    Statement::RollbackToSavepoint(name.to_owned())
+}
+pub fn release_savepoint(name: &str) -> Statement {
+    Statement::ReleaseSavepoint(name.to_owned())
+}
 
 #[cfg(test)]
 mod tests {
-    use crate::ast_builder::{begin, commit, rollback, test};
+    use crate::ast_builder::{
+        begin, commit, release_savepoint, rollback, rollback_to_savepoint, savepoint, test,
+    };
 
     #[test]
     fn transaction() {
@@ -27,5 +38,17 @@ mod tests {
         let actual = rollback();
         let expected = "ROLLBACK";
         test(Ok(actual), expected);
+
+        let actual = savepoint("sp1");
+        let expected = "SAVEPOINT sp1";
+        test(Ok(actual), expected);
+
+        let actual = rollback_to_savepoint("sp1");
+        let expected = "ROLLBACK TO SAVEPOINT sp1";
+        test(Ok(actual), expected);
+
+        let actual = release_savepoint("sp1");
+        let expected = "RELEASE SAVEPOINT sp1";
+        test(Ok(actual), expected);
     }
 }
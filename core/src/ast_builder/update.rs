@@ -92,6 +92,7 @@ impl<'a> Build for UpdateSetNode<'a> {
             table_name,
             assignments,
             selection,
+            returning: Vec::new(),
         })
     }
 }
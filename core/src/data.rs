@@ -5,8 +5,12 @@ mod key;
 mod literal;
 mod point;
 mod row;
+mod sequence;
+mod statistics;
 mod string_ext;
 mod table;
+mod trigger;
+mod view;
 
 pub mod schema;
 pub mod value;
@@ -20,7 +24,11 @@ pub use {
     point::Point,
     row::{Row, RowError},
     schema::{Schema, SchemaIndex, SchemaIndexOrd, SchemaParseError},
+    sequence::{Sequence, SequenceError},
+    statistics::{ColumnStatistics, TableStatistics},
     string_ext::{StringExt, StringExtError},
     table::{TableError, get_alias, get_index},
+    trigger::{Trigger, TriggerError},
     value::{BTreeMapJsonExt, ConvertError, NumericBinaryOperator, Value, ValueError},
+    view::View,
 };
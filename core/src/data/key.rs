@@ -51,6 +51,8 @@ pub enum Key {
     Uuid(u128),
     Inet(IpAddr),
     None,
+    /// Key over multiple columns, compared column by column
+    Composite(Vec<Key>),
 }
 
 impl Ord for Key {
@@ -81,6 +83,7 @@ impl Ord for Key {
             }),
             (Key::Uuid(l), Key::Uuid(r)) => l.cmp(r),
             (Key::Inet(l), Key::Inet(r)) => l.cmp(r),
+            (Key::Composite(l), Key::Composite(r)) => l.cmp(r),
             (Key::None, Key::None) => Ordering::Equal,
             (Key::None, _) => Ordering::Greater,
             (_, Key::None) => Ordering::Less,
@@ -173,6 +176,7 @@ impl From<Key> for Value {
             Key::Interval(v) => Value::Interval(v),
             Key::Uuid(v) => Value::Uuid(v),
             Key::None => Value::Null,
+            Key::Composite(keys) => Value::List(keys.into_iter().map(Value::from).collect()),
         }
     }
 }
@@ -180,7 +184,39 @@ impl From<Key> for Value {
 const VALUE: u8 = 0;
 const NONE: u8 = 1;
 
+/// Escapes `0x00` inside composite key elements, see [`Key::to_cmp_be_bytes`]
+const ESCAPE: u8 = 0xFF;
+const TERMINATOR: u8 = 1;
+
 impl Key {
+    /// Builds the key of a row from the columns at `indexes`, a single column gives its own
+    /// key and multiple columns give [`Key::Composite`]
+    pub fn from_columns(indexes: &[usize], values: &[Value]) -> Result<Self> {
+        let key = |index: &usize| values.get(*index).map_or(Ok(Key::None), Key::try_from);
+
+        match indexes {
+            [index] => key(index),
+            _ => indexes
+                .iter()
+                .map(key)
+                .collect::<Result<Vec<_>>>()
+                .map(Key::Composite),
+        }
+    }
+
+    /// Returns `true` if `self` is a composite key starting with `prefix`, keys of storages
+    /// which only keep the encoded bytes are compared by their encoding
+    pub fn starts_with(&self, prefix: &[Key]) -> Result<bool> {
+        match self {
+            Key::Composite(keys) => Ok(keys.starts_with(prefix)),
+            key => {
+                let prefix = Key::Composite(prefix.to_vec()).to_cmp_be_bytes()?;
+
+                Ok(key.to_cmp_be_bytes()?.starts_with(&prefix))
+            }
+        }
+    }
+
     /// Key to Big-Endian for comparison purpose
     pub fn to_cmp_be_bytes(&self) -> Result<Vec<u8>> {
         Ok(match self {
@@ -337,6 +373,25 @@ impl Key {
                 .copied()
                 .collect::<Vec<_>>(),
             Key::None => vec![NONE],
+            // Each element is escaped and terminated with `[0x00, 0x01]`, so the encoding
+            // keeps the element-wise order and a key prefix is also a byte prefix.
+            Key::Composite(keys) => {
+                let mut bytes = Vec::new();
+
+                for key in keys {
+                    for byte in key.to_cmp_be_bytes()? {
+                        bytes.push(byte);
+
+                        if byte == VALUE {
+                            bytes.push(ESCAPE);
+                        }
+                    }
+
+                    bytes.extend([VALUE, TERMINATOR]);
+                }
+
+                bytes
+            }
         })
     }
 
@@ -364,7 +419,8 @@ impl Key {
             Key::Interval(_) => 20,
             Key::Uuid(_) => 21,
             Key::Inet(_) => 22,
-            Key::None => 23,
+            Key::Composite(_) => 23,
+            Key::None => 24,
         }
     }
 }
@@ -553,6 +609,13 @@ mod tests {
         assert!(Key::Inet(inet("127.0.0.1")) > Key::Inet(inet("0.0.0.1")));
         assert!(Key::Inet(inet("192.168.1.19")) < Key::None);
 
+        assert!(
+            Key::Composite(vec![Key::I64(1), Key::Str("b".to_owned())])
+                > Key::Composite(vec![Key::I64(1), Key::Str("a".to_owned())])
+        );
+        assert!(Key::Composite(vec![Key::I64(1)]) < Key::Composite(vec![Key::I64(1), Key::None]));
+        assert!(Key::Composite(vec![Key::I64(1)]) < Key::None);
+
         assert_eq!(Key::None.partial_cmp(&Key::None), Some(Ordering::Equal));
         assert!(Key::None > Key::I8(100));
     }
@@ -817,12 +880,56 @@ mod tests {
         assert_eq!(cmp(&n2, &n1), Ordering::Greater);
         assert_eq!(cmp(&n1, &null), Ordering::Less);
 
+        let n1 = Composite(vec![I64(1), Bytea(vec![0])]).to_cmp_be_bytes();
+        let n2 = Composite(vec![I64(1), Bytea(vec![0, 0])]).to_cmp_be_bytes();
+        let n3 = Composite(vec![I64(1), Bytea(vec![1])]).to_cmp_be_bytes();
+        let n4 = Composite(vec![I64(2), Bytea(vec![])]).to_cmp_be_bytes();
+        let n5 = Composite(vec![I64(1), None]).to_cmp_be_bytes();
+
+        assert_eq!(cmp(&n1, &n1), Ordering::Equal);
+        assert_eq!(cmp(&n1, &n2), Ordering::Less);
+        assert_eq!(cmp(&n2, &n3), Ordering::Less);
+        assert_eq!(cmp(&n3, &n4), Ordering::Less);
+        assert_eq!(cmp(&n5, &n4), Ordering::Less);
+
+        let prefix = Composite(vec![I64(1)]).to_cmp_be_bytes().unwrap();
+        assert!(n1.as_ref().unwrap().starts_with(&prefix));
+        assert!(n5.as_ref().unwrap().starts_with(&prefix));
+        assert!(!n4.as_ref().unwrap().starts_with(&prefix));
+
         assert_eq!(
             F64(12.34.into()).to_cmp_be_bytes(),
             Err(KeyError::FloatToCmpBigEndianNotSupported.into())
         );
     }
 
+    #[test]
+    fn composite() {
+        let values = vec![Value::I64(1), Value::Str("a".to_owned()), Value::Null];
+
+        assert_eq!(
+            Key::from_columns(&[1], &values),
+            Ok(Key::Str("a".to_owned()))
+        );
+        assert_eq!(
+            Key::from_columns(&[0, 2], &values),
+            Ok(Key::Composite(vec![Key::I64(1), Key::None]))
+        );
+
+        let key = Key::Composite(vec![Key::I64(1), Key::Str("a".to_owned())]);
+        assert_eq!(key.starts_with(&[Key::I64(1)]), Ok(true));
+        assert_eq!(key.starts_with(&[Key::I64(2)]), Ok(false));
+
+        let encoded = Key::Bytea(key.to_cmp_be_bytes().unwrap());
+        assert_eq!(encoded.starts_with(&[Key::I64(1)]), Ok(true));
+        assert_eq!(encoded.starts_with(&[Key::I64(2)]), Ok(false));
+
+        assert_eq!(
+            Value::from(key),
+            Value::List(vec![Value::I64(1), Value::Str("a".to_owned())])
+        );
+    }
+
     #[test]
     fn from_key_to_value() {
         use {crate::data::Interval as I, uuid::Uuid};
@@ -1,6 +1,9 @@
 use {
     crate::{
-        ast::{ColumnDef, Expr, ForeignKey, OrderByExpr, Statement, ToSql},
+        ast::{
+            CheckConstraint, ColumnDef, ColumnUniqueOption, Expr, ForeignKey, KeyConstraint,
+            OrderByExpr, Statement, ToSql,
+        },
         prelude::{parse, translate},
         result::Result,
     },
@@ -22,9 +25,11 @@ pub enum SchemaIndexOrd {
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct SchemaIndex {
     pub name: String,
-    pub expr: Expr,
+    /// Key parts of the index, more than one for a multi-column index
+    pub exprs: Vec<Expr>,
     pub order: SchemaIndexOrd,
     pub created: NaiveDateTime,
+    pub unique: bool,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -33,27 +38,65 @@ pub struct Schema {
     pub column_defs: Option<Vec<ColumnDef>>,
     pub indexes: Vec<SchemaIndex>,
     pub engine: Option<String>,
+    pub key_constraints: Vec<KeyConstraint>,
     pub foreign_keys: Vec<ForeignKey>,
+    pub checks: Vec<CheckConstraint>,
     pub comment: Option<String>,
 }
 
+/// Indexes of the primary key columns, either the `PRIMARY KEY` column or the columns of
+/// the table-level composite primary key
+pub fn primary_key_indexes(
+    column_defs: &[ColumnDef],
+    key_constraints: &[KeyConstraint],
+) -> Option<Vec<usize>> {
+    let position = |name: &String| {
+        column_defs
+            .iter()
+            .position(|column_def| &column_def.name == name)
+    };
+
+    column_defs
+        .iter()
+        .position(|ColumnDef { unique, .. }| {
+            unique == &Some(ColumnUniqueOption { is_primary: true })
+        })
+        .map(|index| vec![index])
+        .or_else(|| {
+            key_constraints
+                .iter()
+                .find(|key_constraint| key_constraint.is_primary)
+                .map(|KeyConstraint { columns, .. }| columns.iter().filter_map(position).collect())
+        })
+}
+
 impl Schema {
+    pub fn primary_key_indexes(&self) -> Option<Vec<usize>> {
+        primary_key_indexes(self.column_defs.as_deref()?, &self.key_constraints)
+    }
+
     pub fn to_ddl(&self) -> String {
         let Schema {
             table_name,
             column_defs,
             indexes,
             engine,
+            key_constraints,
             foreign_keys,
+            checks,
             comment,
         } = self;
 
         let columns = column_defs.as_ref().map(|column_defs| {
+            let key_constraints = key_constraints.iter().map(ToSql::to_sql);
             let foreign_keys = foreign_keys.iter().map(ToSql::to_sql);
+            let checks = checks.iter().map(ToSql::to_sql);
             let body = column_defs
                 .iter()
                 .map(ToSql::to_sql)
+                .chain(key_constraints)
                 .chain(foreign_keys)
+                .chain(checks)
                 .collect::<Vec<_>>()
                 .join(", ");
 
@@ -76,11 +119,23 @@ impl Schema {
         .join(" ")
             + ";";
 
-        let create_indexes = indexes.iter().map(|SchemaIndex { name, expr, .. }| {
-            let expr = expr.to_sql();
-
-            format!(r#"CREATE INDEX "{name}" ON "{table_name}" ({expr});"#)
-        });
+        let create_indexes = indexes.iter().map(
+            |SchemaIndex {
+                 name,
+                 exprs,
+                 unique,
+                 ..
+             }| {
+                let exprs = exprs
+                    .iter()
+                    .map(ToSql::to_sql)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let unique = if *unique { "UNIQUE " } else { "" };
+
+                format!(r#"CREATE {unique}INDEX "{name}" ON "{table_name}" ({exprs});"#)
+            },
+        );
 
         iter::once(create_table)
             .chain(create_indexes)
@@ -100,18 +155,29 @@ impl Schema {
                 match create_index {
                     Statement::CreateIndex {
                         name,
-                        column: OrderByExpr { expr, asc },
+                        columns,
+                        unique,
                         ..
                     } => {
-                        let order = asc
-                            .and_then(|bool| bool.then_some(SchemaIndexOrd::Asc))
-                            .unwrap_or(SchemaIndexOrd::Both);
+                        let asc = columns
+                            .iter()
+                            .all(|OrderByExpr { asc, .. }| asc == &Some(true));
+                        let order = if asc {
+                            SchemaIndexOrd::Asc
+                        } else {
+                            SchemaIndexOrd::Both
+                        };
+                        let exprs = columns
+                            .into_iter()
+                            .map(|OrderByExpr { expr, .. }| expr)
+                            .collect();
 
                         let index = SchemaIndex {
                             name,
-                            expr,
+                            exprs,
                             order,
                             created,
+                            unique,
                         };
 
                         Ok(index)
@@ -129,7 +195,9 @@ impl Schema {
                 name,
                 columns,
                 engine,
+                key_constraints,
                 foreign_keys,
+                checks,
                 comment,
                 ..
             } => Ok(Schema {
@@ -137,7 +205,9 @@ impl Schema {
                 column_defs: columns,
                 indexes,
                 engine,
+                key_constraints,
                 foreign_keys,
+                checks,
                 comment,
             }),
             _ => Err(SchemaParseError::CannotParseDDL.into()),
@@ -156,7 +226,10 @@ mod tests {
     use {
         super::SchemaParseError,
         crate::{
-            ast::{AstLiteral, ColumnDef, ColumnUniqueOption, Expr},
+            ast::{
+                AstLiteral, BinaryOperator, CheckConstraint, ColumnDef, ColumnUniqueOption, Expr,
+                ForeignKey, Function, KeyConstraint, ReferentialAction,
+            },
             chrono::Utc,
             data::{Schema, SchemaIndex, SchemaIndexOrd},
             prelude::DataType,
@@ -169,7 +242,9 @@ mod tests {
             column_defs,
             indexes,
             engine,
+            key_constraints,
             foreign_keys,
+            checks,
             comment,
         } = actual;
 
@@ -178,14 +253,18 @@ mod tests {
             column_defs: column_defs_e,
             indexes: indexes_e,
             engine: engine_e,
+            key_constraints: key_constraints_e,
             foreign_keys: foreign_keys_e,
+            checks: checks_e,
             comment: comment_e,
         } = expected;
 
         assert_eq!(table_name, table_name_e);
         assert_eq!(column_defs, column_defs_e);
         assert_eq!(engine, engine_e);
+        assert_eq!(key_constraints, key_constraints_e);
         assert_eq!(foreign_keys, foreign_keys_e);
+        assert_eq!(checks, checks_e);
         assert_eq!(comment, comment_e);
         indexes
             .into_iter()
@@ -195,18 +274,24 @@ mod tests {
 
     fn assert_index(actual: SchemaIndex, expected: SchemaIndex) {
         let SchemaIndex {
-            name, expr, order, ..
+            name,
+            exprs,
+            order,
+            unique,
+            ..
         } = actual;
         let SchemaIndex {
             name: name_e,
-            expr: expr_e,
+            exprs: exprs_e,
             order: order_e,
+            unique: unique_e,
             ..
         } = expected;
 
         assert_eq!(name, name_e);
-        assert_eq!(expr, expr_e);
+        assert_eq!(exprs, exprs_e);
         assert_eq!(order, order_e);
+        assert_eq!(unique, unique_e);
     }
 
     #[test]
@@ -219,7 +304,10 @@ mod tests {
                     data_type: DataType::Int,
                     nullable: false,
                     default: None,
+                    identity: None,
+                    generated: None,
                     unique: None,
+                    check: None,
                     comment: None,
                 },
                 ColumnDef {
@@ -227,13 +315,18 @@ mod tests {
                     data_type: DataType::Text,
                     nullable: true,
                     default: Some(Expr::Literal(AstLiteral::QuotedString("glue".to_owned()))),
+                    identity: None,
+                    generated: None,
                     unique: None,
+                    check: None,
                     comment: None,
                 },
             ]),
             indexes: Vec::new(),
             engine: None,
+            key_constraints: Vec::new(),
             foreign_keys: Vec::new(),
+            checks: Vec::new(),
             comment: None,
         };
 
@@ -248,7 +341,9 @@ mod tests {
             column_defs: None,
             indexes: Vec::new(),
             engine: None,
+            key_constraints: Vec::new(),
             foreign_keys: Vec::new(),
+            checks: Vec::new(),
             comment: None,
         };
         let ddl = r#"CREATE TABLE "Test";"#;
@@ -267,12 +362,17 @@ mod tests {
                 data_type: DataType::Int,
                 nullable: false,
                 default: None,
+                identity: None,
+                generated: None,
                 unique: Some(ColumnUniqueOption { is_primary: true }),
+                check: None,
                 comment: None,
             }]),
             indexes: Vec::new(),
             engine: None,
+            key_constraints: Vec::new(),
             foreign_keys: Vec::new(),
+            checks: Vec::new(),
             comment: None,
         };
 
@@ -283,6 +383,206 @@ mod tests {
         assert_schema(actual, schema);
     }
 
+    #[test]
+    fn table_check() {
+        let schema = Schema {
+            table_name: "Item".to_owned(),
+            column_defs: Some(vec![
+                ColumnDef {
+                    name: "price".to_owned(),
+                    data_type: DataType::Int,
+                    nullable: true,
+                    default: None,
+                    identity: None,
+                    generated: None,
+                    unique: None,
+                    check: Some(CheckConstraint {
+                        name: None,
+                        expr: Expr::BinaryOp {
+                            left: Box::new(Expr::Identifier("price".to_owned())),
+                            op: BinaryOperator::Gt,
+                            right: Box::new(Expr::Literal(AstLiteral::Number(0.into()))),
+                        },
+                    }),
+                    comment: None,
+                },
+                ColumnDef {
+                    name: "discount".to_owned(),
+                    data_type: DataType::Int,
+                    nullable: true,
+                    default: None,
+                    identity: None,
+                    generated: None,
+                    unique: None,
+                    check: None,
+                    comment: None,
+                },
+            ]),
+            indexes: Vec::new(),
+            engine: None,
+            key_constraints: Vec::new(),
+            foreign_keys: Vec::new(),
+            checks: vec![CheckConstraint {
+                name: Some("valid_discount".to_owned()),
+                expr: Expr::BinaryOp {
+                    left: Box::new(Expr::Identifier("discount".to_owned())),
+                    op: BinaryOperator::Lt,
+                    right: Box::new(Expr::Identifier("price".to_owned())),
+                },
+            }],
+            comment: None,
+        };
+
+        let ddl = r#"CREATE TABLE "Item" ("price" INT NULL CHECK ("price" > 0), "discount" INT NULL, CONSTRAINT "valid_discount" CHECK ("discount" < "price"));"#;
+        assert_eq!(schema.to_ddl(), ddl);
+
+        let actual = Schema::from_ddl(ddl).unwrap();
+        assert_schema(actual, schema);
+    }
+
+    #[test]
+    fn table_generated() {
+        let column_def = |name: &str, generated| ColumnDef {
+            name: name.to_owned(),
+            data_type: DataType::Text,
+            nullable: true,
+            default: None,
+            identity: None,
+            generated,
+            unique: None,
+            check: None,
+            comment: None,
+        };
+        let schema = Schema {
+            table_name: "User".to_owned(),
+            column_defs: Some(vec![
+                column_def("email", None),
+                column_def(
+                    "email_lower",
+                    Some(Expr::Function(Box::new(Function::Lower(Expr::Identifier(
+                        "email".to_owned(),
+                    ))))),
+                ),
+            ]),
+            indexes: Vec::new(),
+            engine: None,
+            key_constraints: Vec::new(),
+            foreign_keys: Vec::new(),
+            checks: Vec::new(),
+            comment: None,
+        };
+
+        let ddl = r#"CREATE TABLE "User" ("email" TEXT NULL, "email_lower" TEXT NULL GENERATED ALWAYS AS (LOWER("email")) STORED);"#;
+        assert_eq!(schema.to_ddl(), ddl);
+
+        let actual = Schema::from_ddl(ddl).unwrap();
+        assert_schema(actual, schema);
+    }
+
+    #[test]
+    fn table_key_constraints() {
+        let column_def = |name: &str| ColumnDef {
+            name: name.to_owned(),
+            data_type: DataType::Int,
+            nullable: false,
+            default: None,
+            identity: None,
+            generated: None,
+            unique: None,
+            check: None,
+            comment: None,
+        };
+        let schema = Schema {
+            table_name: "Member".to_owned(),
+            column_defs: Some(vec![
+                column_def("tenant_id"),
+                column_def("id"),
+                ColumnDef {
+                    nullable: true,
+                    ..column_def("num")
+                },
+            ]),
+            indexes: Vec::new(),
+            engine: None,
+            key_constraints: vec![
+                KeyConstraint {
+                    name: None,
+                    columns: vec!["tenant_id".to_owned(), "id".to_owned()],
+                    is_primary: true,
+                },
+                KeyConstraint {
+                    name: Some("tenant_num".to_owned()),
+                    columns: vec!["tenant_id".to_owned(), "num".to_owned()],
+                    is_primary: false,
+                },
+            ],
+            foreign_keys: Vec::new(),
+            checks: Vec::new(),
+            comment: None,
+        };
+
+        let ddl = r#"CREATE TABLE "Member" ("tenant_id" INT NOT NULL, "id" INT NOT NULL, "num" INT NULL, PRIMARY KEY ("tenant_id", "id"), CONSTRAINT "tenant_num" UNIQUE ("tenant_id", "num"));"#;
+        assert_eq!(schema.to_ddl(), ddl);
+        assert_eq!(schema.primary_key_indexes(), Some(vec![0, 1]));
+
+        let actual = Schema::from_ddl(ddl).unwrap();
+        assert_schema(actual, schema);
+    }
+
+    #[test]
+    fn table_foreign_keys() {
+        let column_def = |name: &str| ColumnDef {
+            name: name.to_owned(),
+            data_type: DataType::Int,
+            nullable: true,
+            default: None,
+            identity: None,
+            generated: None,
+            unique: None,
+            check: None,
+            comment: None,
+        };
+        let foreign_key = |name: &str, on_delete, on_update| ForeignKey {
+            name: name.to_owned(),
+            referencing_column_name: name.to_owned(),
+            referenced_table_name: "Parent".to_owned(),
+            referenced_column_name: "id".to_owned(),
+            on_delete,
+            on_update,
+        };
+        let schema = Schema {
+            table_name: "Child".to_owned(),
+            column_defs: Some(vec![
+                column_def("a"),
+                column_def("b"),
+                ColumnDef {
+                    default: Some(Expr::Literal(AstLiteral::Number(1.into()))),
+                    ..column_def("c")
+                },
+            ]),
+            indexes: Vec::new(),
+            engine: None,
+            key_constraints: Vec::new(),
+            foreign_keys: vec![
+                foreign_key("a", ReferentialAction::Cascade, ReferentialAction::Restrict),
+                foreign_key("b", ReferentialAction::SetNull, ReferentialAction::NoAction),
+                foreign_key(
+                    "c",
+                    ReferentialAction::SetDefault,
+                    ReferentialAction::Cascade,
+                ),
+            ],
+            checks: Vec::new(),
+            comment: None,
+        };
+
+        let ddl = r#"CREATE TABLE "Child" ("a" INT NULL, "b" INT NULL, "c" INT NULL DEFAULT 1, CONSTRAINT "a" FOREIGN KEY ("a") REFERENCES "Parent" ("id") ON DELETE CASCADE ON UPDATE RESTRICT, CONSTRAINT "b" FOREIGN KEY ("b") REFERENCES "Parent" ("id") ON DELETE SET NULL ON UPDATE NO ACTION, CONSTRAINT "c" FOREIGN KEY ("c") REFERENCES "Parent" ("id") ON DELETE SET DEFAULT ON UPDATE CASCADE);"#;
+        assert_eq!(schema.to_ddl(), ddl);
+
+        let actual = Schema::from_ddl(ddl).unwrap();
+        assert_schema(actual, schema);
+    }
+
     #[test]
     fn invalid_ddl() {
         // Only Statement::CreateTable is supported
@@ -301,7 +601,10 @@ mod tests {
                     data_type: DataType::Int,
                     nullable: false,
                     default: None,
+                    identity: None,
+                    generated: None,
                     unique: None,
+                    check: None,
                     comment: None,
                 },
                 ColumnDef {
@@ -309,31 +612,49 @@ mod tests {
                     data_type: DataType::Text,
                     nullable: false,
                     default: None,
+                    identity: None,
+                    generated: None,
                     unique: None,
+                    check: None,
                     comment: None,
                 },
             ]),
             indexes: vec![
                 SchemaIndex {
                     name: "User_id".to_owned(),
-                    expr: Expr::Identifier("id".to_owned()),
+                    exprs: vec![Expr::Identifier("id".to_owned())],
                     order: SchemaIndexOrd::Both,
                     created: Utc::now().naive_utc(),
+                    unique: false,
                 },
                 SchemaIndex {
                     name: "User_name".to_owned(),
-                    expr: Expr::Identifier("name".to_owned()),
+                    exprs: vec![Expr::Identifier("name".to_owned())],
+                    order: SchemaIndexOrd::Both,
+                    created: Utc::now().naive_utc(),
+                    unique: false,
+                },
+                SchemaIndex {
+                    name: "User_id_name".to_owned(),
+                    exprs: vec![
+                        Expr::Identifier("id".to_owned()),
+                        Expr::Identifier("name".to_owned()),
+                    ],
                     order: SchemaIndexOrd::Both,
                     created: Utc::now().naive_utc(),
+                    unique: true,
                 },
             ],
             engine: None,
+            key_constraints: Vec::new(),
             foreign_keys: Vec::new(),
+            checks: Vec::new(),
             comment: None,
         };
         let ddl = r#"CREATE TABLE "User" ("id" INT NOT NULL, "name" TEXT NOT NULL);
 CREATE INDEX "User_id" ON "User" ("id");
-CREATE INDEX "User_name" ON "User" ("name");"#;
+CREATE INDEX "User_name" ON "User" ("name");
+CREATE UNIQUE INDEX "User_id_name" ON "User" ("id", "name");"#;
         assert_eq!(schema.to_ddl(), ddl);
 
         let actual = Schema::from_ddl(ddl).unwrap();
@@ -355,7 +676,10 @@ CREATE TABLE "User" ("id" INT NOT NULL, "name" TEXT NOT NULL);"#;
                     data_type: DataType::Int,
                     nullable: true,
                     default: None,
+                    identity: None,
+                    generated: None,
                     unique: None,
+                    check: None,
                     comment: None,
                 },
                 ColumnDef {
@@ -363,18 +687,24 @@ CREATE TABLE "User" ("id" INT NOT NULL, "name" TEXT NOT NULL);"#;
                     data_type: DataType::Int,
                     nullable: true,
                     default: None,
+                    identity: None,
+                    generated: None,
                     unique: None,
+                    check: None,
                     comment: None,
                 },
             ]),
             indexes: vec![SchemaIndex {
                 name: ".".to_owned(),
-                expr: Expr::Identifier(";".to_owned()),
+                exprs: vec![Expr::Identifier(";".to_owned())],
                 order: SchemaIndexOrd::Both,
                 created: Utc::now().naive_utc(),
+                unique: false,
             }],
             engine: None,
+            key_constraints: Vec::new(),
             foreign_keys: Vec::new(),
+            checks: Vec::new(),
             comment: None,
         };
         let ddl = r#"CREATE TABLE "1" ("2" INT NULL, ";" INT NULL);
@@ -0,0 +1,213 @@
+use {
+    crate::{ast::SequenceOptions, result::Result},
+    serde::{Deserialize, Serialize},
+    std::fmt::Debug,
+    thiserror::Error as ThisError,
+};
+
+#[derive(ThisError, Serialize, Debug, PartialEq, Eq)]
+pub enum SequenceError {
+    #[error("sequence already exists: {0}")]
+    AlreadyExists(String),
+
+    #[error("sequence does not exist: {0}")]
+    NotFound(String),
+
+    #[error("sequence increment must not be zero")]
+    ZeroIncrement,
+
+    #[error("sequence MINVALUE ({min_value}) must be less than MAXVALUE ({max_value})")]
+    InvalidRange { min_value: i64, max_value: i64 },
+
+    #[error("sequence START value ({start}) must be between MINVALUE and MAXVALUE")]
+    StartOutOfRange { start: i64 },
+
+    #[error("sequence reached its limit: {0}")]
+    Exhausted(String),
+
+    #[error("CURRVAL of sequence is not defined before NEXTVAL is called: {0}")]
+    CurrentValueNotDefined(String),
+}
+
+/// Counter created by `CREATE SEQUENCE` or for an identity column.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Sequence {
+    pub name: String,
+    pub increment: i64,
+    pub min_value: i64,
+    pub max_value: i64,
+    pub start: i64,
+    pub cycle: bool,
+    /// Last value given by `NEXTVAL`, `None` until it is called for the first time
+    pub last_value: Option<i64>,
+}
+
+impl Sequence {
+    /// Fills the options left out by the defaults of its direction, ascending sequences count
+    /// from 1 and descending ones from -1.
+    pub fn new(name: String, options: &SequenceOptions) -> Result<Self> {
+        let SequenceOptions {
+            increment,
+            min_value,
+            max_value,
+            start,
+            cycle,
+        } = options;
+
+        let increment = increment.unwrap_or(1);
+        let (min_value, max_value) = match increment {
+            0 => return Err(SequenceError::ZeroIncrement.into()),
+            1.. => (min_value.unwrap_or(1), max_value.unwrap_or(i64::MAX)),
+            _ => (min_value.unwrap_or(i64::MIN), max_value.unwrap_or(-1)),
+        };
+
+        if min_value >= max_value {
+            return Err(SequenceError::InvalidRange {
+                min_value,
+                max_value,
+            }
+            .into());
+        }
+
+        let start = start.unwrap_or(if increment > 0 { min_value } else { max_value });
+        if !(min_value..=max_value).contains(&start) {
+            return Err(SequenceError::StartOutOfRange { start }.into());
+        }
+
+        Ok(Self {
+            name,
+            increment,
+            min_value,
+            max_value,
+            start,
+            cycle: *cycle,
+            last_value: None,
+        })
+    }
+
+    /// Moves to the next value and returns it, wrapping around to the other bound when the
+    /// sequence cycles.
+    pub fn advance(&mut self) -> Result<i64> {
+        let next = match self.last_value {
+            None => self.start,
+            Some(last_value) => match last_value
+                .checked_add(self.increment)
+                .filter(|next| (self.min_value..=self.max_value).contains(next))
+            {
+                Some(next) => next,
+                None if self.cycle && self.increment > 0 => self.min_value,
+                None if self.cycle => self.max_value,
+                None => return Err(SequenceError::Exhausted(self.name.clone()).into()),
+            },
+        };
+
+        self.last_value = Some(next);
+
+        Ok(next)
+    }
+
+    /// Last value given by `NEXTVAL`
+    pub fn current(&self) -> Result<i64> {
+        self.last_value
+            .ok_or_else(|| SequenceError::CurrentValueNotDefined(self.name.clone()).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{Sequence, SequenceError},
+        crate::{ast::SequenceOptions, result::Error},
+    };
+
+    fn sequence(options: SequenceOptions) -> Result<Sequence, Error> {
+        Sequence::new("Seq".to_owned(), &options)
+    }
+
+    #[test]
+    fn advance() {
+        let mut seq = sequence(SequenceOptions::default()).unwrap();
+        assert_eq!(
+            seq.current(),
+            Err(SequenceError::CurrentValueNotDefined("Seq".to_owned()).into())
+        );
+        assert_eq!(seq.advance(), Ok(1));
+        assert_eq!(seq.advance(), Ok(2));
+        assert_eq!(seq.current(), Ok(2));
+
+        let mut seq = sequence(SequenceOptions {
+            increment: Some(-2),
+            ..SequenceOptions::default()
+        })
+        .unwrap();
+        assert_eq!(seq.advance(), Ok(-1));
+        assert_eq!(seq.advance(), Ok(-3));
+
+        let mut seq = sequence(SequenceOptions {
+            increment: Some(5),
+            max_value: Some(10),
+            start: Some(3),
+            ..SequenceOptions::default()
+        })
+        .unwrap();
+        assert_eq!(seq.advance(), Ok(3));
+        assert_eq!(seq.advance(), Ok(8));
+        assert_eq!(
+            seq.advance(),
+            Err(SequenceError::Exhausted("Seq".to_owned()).into())
+        );
+
+        let mut seq = sequence(SequenceOptions {
+            min_value: Some(1),
+            max_value: Some(2),
+            cycle: true,
+            ..SequenceOptions::default()
+        })
+        .unwrap();
+        assert_eq!(seq.advance(), Ok(1));
+        assert_eq!(seq.advance(), Ok(2));
+        assert_eq!(seq.advance(), Ok(1));
+
+        let mut seq = sequence(SequenceOptions {
+            start: Some(i64::MAX),
+            ..SequenceOptions::default()
+        })
+        .unwrap();
+        assert_eq!(seq.advance(), Ok(i64::MAX));
+        assert_eq!(
+            seq.advance(),
+            Err(SequenceError::Exhausted("Seq".to_owned()).into())
+        );
+    }
+
+    #[test]
+    fn new() {
+        assert_eq!(
+            sequence(SequenceOptions {
+                increment: Some(0),
+                ..SequenceOptions::default()
+            }),
+            Err(SequenceError::ZeroIncrement.into())
+        );
+        assert_eq!(
+            sequence(SequenceOptions {
+                min_value: Some(10),
+                max_value: Some(10),
+                ..SequenceOptions::default()
+            }),
+            Err(SequenceError::InvalidRange {
+                min_value: 10,
+                max_value: 10
+            }
+            .into())
+        );
+        assert_eq!(
+            sequence(SequenceOptions {
+                min_value: Some(10),
+                start: Some(5),
+                ..SequenceOptions::default()
+            }),
+            Err(SequenceError::StartOutOfRange { start: 5 }.into())
+        );
+    }
+}
@@ -0,0 +1,219 @@
+use {
+    super::{Key, Literal, Value},
+    crate::ast::IndexOperator,
+    serde::{Deserialize, Serialize},
+    std::{cmp::Ordering, collections::HashSet},
+};
+
+/// Number of equi-depth buckets of a column histogram
+const HISTOGRAM_BUCKETS: usize = 10;
+
+/// Statistics of a table collected by `ANALYZE`, used by the planner to estimate how many
+/// rows each access path reads.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableStatistics {
+    pub table_name: String,
+    pub row_count: usize,
+    pub columns: Vec<ColumnStatistics>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnStatistics {
+    pub column_name: String,
+    /// Number of distinct non-null values
+    pub distinct_count: usize,
+    pub null_count: usize,
+    /// Bounds of equi-depth buckets over the non-null values in ascending order, empty when
+    /// the values cannot be ordered
+    pub histogram: Vec<Value>,
+}
+
+impl TableStatistics {
+    pub fn column(&self, column_name: &str) -> Option<&ColumnStatistics> {
+        self.columns
+            .iter()
+            .find(|column| column.column_name == column_name)
+    }
+}
+
+impl ColumnStatistics {
+    pub fn new<'a>(column_name: String, values: impl IntoIterator<Item = &'a Value>) -> Self {
+        let (nulls, values): (Vec<_>, Vec<_>) =
+            values.into_iter().partition(|value| value.is_null());
+        let distinct_count = values.iter().collect::<HashSet<_>>().len();
+        let histogram = values
+            .into_iter()
+            .map(Key::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map(histogram)
+            .unwrap_or_default();
+
+        Self {
+            column_name,
+            distinct_count,
+            null_count: nulls.len(),
+            histogram,
+        }
+    }
+
+    /// Estimated fraction of `row_count` rows whose value compares with `literal` by
+    /// `index_op`, `None` when `literal` cannot be compared with the values of the column.
+    /// `NULL` literals stand for the `IS NULL` and `IS NOT NULL` index scans.
+    pub fn selectivity(
+        &self,
+        row_count: usize,
+        index_op: &IndexOperator,
+        literal: &Literal<'_>,
+    ) -> Option<f64> {
+        if row_count == 0 {
+            return Some(0.0);
+        }
+
+        let row_count = row_count as f64;
+        let null_fraction = self.null_count as f64 / row_count;
+        let non_null_fraction = 1.0 - null_fraction;
+
+        match (index_op, literal) {
+            (IndexOperator::Eq, Literal::Null) => Some(null_fraction),
+            (IndexOperator::Lt, Literal::Null) => Some(non_null_fraction),
+            (_, Literal::Null) => None,
+            (IndexOperator::Eq, _) => {
+                let uniform = 1.0 / self.distinct_count.max(1) as f64;
+                let frequent = self.fraction_equal(literal).unwrap_or(0.0);
+
+                Some(non_null_fraction * uniform.max(frequent))
+            }
+            (IndexOperator::Lt | IndexOperator::LtEq, _) => self
+                .fraction_below(literal)
+                .map(|fraction| non_null_fraction * fraction),
+            (IndexOperator::Gt | IndexOperator::GtEq, _) => self
+                .fraction_below(literal)
+                .map(|fraction| non_null_fraction * (1.0 - fraction)),
+        }
+    }
+
+    /// Fraction of the non-null values equal to `literal` from the buckets bounded by it on
+    /// both sides, which finds the values much more frequent than the others
+    fn fraction_equal(&self, literal: &Literal<'_>) -> Option<f64> {
+        let buckets = self.histogram.len().checked_sub(1).filter(|n| *n > 0)?;
+        let bounds = self
+            .histogram
+            .iter()
+            .filter(|bound| bound.evaluate_cmp_with_literal(literal) == Some(Ordering::Equal))
+            .count();
+
+        Some(bounds.saturating_sub(1) as f64 / buckets as f64)
+    }
+
+    /// Fraction of the non-null values less than `literal`, counting half of the bucket
+    /// `literal` falls in
+    fn fraction_below(&self, literal: &Literal<'_>) -> Option<f64> {
+        let buckets = self.histogram.len().checked_sub(1).filter(|n| *n > 0)?;
+        let below = self
+            .histogram
+            .iter()
+            .map(|bound| bound.evaluate_cmp_with_literal(literal))
+            .collect::<Option<Vec<_>>>()?
+            .into_iter()
+            .filter(|ordering| *ordering == Ordering::Less)
+            .count();
+
+        let fraction = match below {
+            0 => 0.0,
+            n if n > buckets => 1.0,
+            n => (n as f64 - 0.5) / buckets as f64,
+        };
+
+        Some(fraction)
+    }
+}
+
+fn histogram(mut keys: Vec<Key>) -> Vec<Value> {
+    keys.sort();
+
+    let Some(last) = keys.len().checked_sub(1) else {
+        return Vec::new();
+    };
+
+    (0..=HISTOGRAM_BUCKETS)
+        .map(|bucket| keys[last * bucket / HISTOGRAM_BUCKETS].clone())
+        .map(Value::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::ColumnStatistics,
+        crate::{
+            ast::IndexOperator,
+            data::{Literal, Value},
+        },
+        bigdecimal::BigDecimal,
+        std::borrow::Cow,
+    };
+
+    fn number(n: i64) -> Literal<'static> {
+        Literal::Number(Cow::Owned(BigDecimal::from(n)))
+    }
+
+    #[test]
+    fn new() {
+        let values = [1, 1, 2, 3]
+            .into_iter()
+            .map(Value::I64)
+            .chain([Value::Null])
+            .collect::<Vec<_>>();
+        let statistics = ColumnStatistics::new("id".to_owned(), &values);
+
+        assert_eq!(statistics.distinct_count, 3);
+        assert_eq!(statistics.null_count, 1);
+        assert_eq!(statistics.histogram.len(), 11);
+        assert_eq!(statistics.histogram.first(), Some(&Value::I64(1)));
+        assert_eq!(statistics.histogram.last(), Some(&Value::I64(3)));
+
+        let values = [Value::Null, Value::Null];
+        let statistics = ColumnStatistics::new("id".to_owned(), &values);
+        assert_eq!(statistics.distinct_count, 0);
+        assert_eq!(statistics.histogram, Vec::new());
+    }
+
+    #[test]
+    fn selectivity_of_frequent_value() {
+        let values = (1..=10).chain([0; 10]).map(Value::I64).collect::<Vec<_>>();
+        let statistics = ColumnStatistics::new("num".to_owned(), &values);
+
+        assert_eq!(
+            statistics.selectivity(20, &IndexOperator::Eq, &number(0)),
+            Some(0.5)
+        );
+        assert_eq!(
+            statistics.selectivity(20, &IndexOperator::Eq, &number(5)),
+            Some(1.0 / 11.0)
+        );
+    }
+
+    #[test]
+    fn selectivity() {
+        let values = (0..100)
+            .map(Value::I64)
+            .chain(std::iter::repeat_n(Value::Null, 100))
+            .collect::<Vec<_>>();
+        let statistics = ColumnStatistics::new("id".to_owned(), &values);
+        let selectivity = |index_op, literal| statistics.selectivity(200, &index_op, &literal);
+
+        assert_eq!(selectivity(IndexOperator::Eq, number(3)), Some(0.005));
+        assert_eq!(selectivity(IndexOperator::Eq, number(1000)), Some(0.005));
+        assert_eq!(selectivity(IndexOperator::Eq, Literal::Null), Some(0.5));
+        assert_eq!(selectivity(IndexOperator::Lt, Literal::Null), Some(0.5));
+        assert_eq!(selectivity(IndexOperator::Lt, number(-1)), Some(0.0));
+        assert_eq!(selectivity(IndexOperator::GtEq, number(-1)), Some(0.5));
+        assert_eq!(selectivity(IndexOperator::Gt, number(200)), Some(0.0));
+        assert_eq!(selectivity(IndexOperator::Lt, number(5)), Some(0.025));
+        assert_eq!(selectivity(IndexOperator::Gt, number(5)), Some(0.475));
+        assert_eq!(
+            selectivity(IndexOperator::Lt, Literal::Text(Cow::Borrowed("a"))),
+            None
+        );
+    }
+}
@@ -0,0 +1,33 @@
+use {
+    crate::ast::{Expr, Statement, TriggerEvent, TriggerTiming},
+    serde::{Deserialize, Serialize},
+    std::fmt::Debug,
+    thiserror::Error as ThisError,
+};
+
+#[derive(ThisError, Serialize, Debug, PartialEq, Eq)]
+pub enum TriggerError {
+    #[error("trigger already exists: {name} on {table_name}")]
+    AlreadyExists { name: String, table_name: String },
+
+    #[error("trigger does not exist: {name} on {table_name}")]
+    NotFound { name: String, table_name: String },
+
+    #[error("table not found: {0}")]
+    TableNotFound(String),
+
+    #[error("triggers fired more than {0} levels deep")]
+    DepthExceeded(usize),
+}
+
+/// Trigger created by `CREATE TRIGGER`, running its body for each row written to the table.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Trigger {
+    pub name: String,
+    pub table_name: String,
+    pub timing: TriggerTiming,
+    pub events: Vec<TriggerEvent>,
+    /// `WHEN` condition, the body only runs for the rows satisfying it
+    pub condition: Option<Expr>,
+    pub body: Vec<Statement>,
+}
@@ -0,0 +1,15 @@
+use {
+    crate::ast::Query,
+    serde::{Deserialize, Serialize},
+};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct View {
+    pub view_name: String,
+    /// Column aliases, empty to use the labels of the query
+    pub columns: Vec<String>,
+    pub query: Query,
+    /// Rows of a materialized view are kept in a backing table of the same name,
+    /// written by `REFRESH MATERIALIZED VIEW`.
+    pub materialized: bool,
+}
@@ -1,29 +1,47 @@
 mod aggregate;
 mod alter;
+mod analyze;
+mod cascade;
 mod context;
 mod delete;
 mod evaluate;
 mod execute;
+mod explain;
 mod fetch;
 mod filter;
+mod guard;
 mod insert;
 mod join;
 mod limit;
+mod returning;
+mod scan_filter;
 mod select;
 mod sort;
+mod stream;
+mod trigger;
 mod update;
 mod validate;
+mod window;
 
 pub use {
     alter::{AlterError, Referencing},
     context::RowContext,
     delete::DeleteError,
     evaluate::{EvaluateError, evaluate_stateless},
-    execute::{ExecuteError, Payload, PayloadVariable, execute},
+    execute::{
+        ExecuteError, Payload, PayloadVariable, execute, execute_with_options, execute_with_params,
+    },
+    explain::{PlanActual, PlanNode},
     fetch::FetchError,
+    guard::{CancelToken, ExecuteOptions, LimitError},
     insert::InsertError,
+    join::JoinError,
     select::SelectError,
     sort::SortError,
+    stream::{PayloadStream, execute_stream},
     update::UpdateError,
     validate::ValidateError,
+    window::WindowError,
 };
+
+pub(crate) use stream::stream_statement;
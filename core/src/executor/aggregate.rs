@@ -1,11 +1,14 @@
 mod state;
 
+pub use self::state::{Accumulator, evaluate_argument};
+
 use {
     self::state::State,
     super::{
         context::{AggregateContext, RowContext},
         evaluate::{Evaluated, evaluate},
         filter::check_expr,
+        guard::watch,
     },
     crate::{
         ast::{Expr, SelectItem},
@@ -26,7 +29,7 @@ enum S<T1, T2> {
     Aggregate(T2),
 }
 
-fn check_aggregate<'a>(fields: &'a [SelectItem], group_by: &'a [Expr]) -> bool {
+pub(super) fn check_aggregate<'a>(fields: &'a [SelectItem], group_by: &'a [Expr]) -> bool {
     if !group_by.is_empty() {
         return true;
     }
@@ -53,12 +56,15 @@ pub async fn apply<'a, T: GStore, U: Stream<Item = Result<Arc<RowContext<'a>>>>
         return Ok(S::NonAggregate(rows));
     }
 
-    let state = rows
-        .into_stream()
+    let guard = filter_context
+        .as_ref()
+        .and_then(|context| context.get_guard());
+    let state = watch(guard.clone(), rows)
         .enumerate()
         .map(|(i, row)| row.map(|row| (i, row)))
         .try_fold(State::new(storage), |state, (index, project_context)| {
             let filter_context = filter_context.clone();
+            let guard = guard.clone();
 
             async move {
                 let filter_context = match filter_context {
@@ -84,6 +90,10 @@ pub async fn apply<'a, T: GStore, U: Stream<Item = Result<Arc<RowContext<'a>>>>
                     .collect::<Result<Vec<Key>>>()?;
 
                 let state = state.apply(index, group, Arc::clone(&project_context));
+                if let Some(guard) = guard {
+                    guard.check_buffered(state.num_groups())?;
+                }
+
                 let state = stream::iter(fields)
                     .map(Ok)
                     .try_fold(state, |state, field| {
@@ -195,6 +205,14 @@ where
 
                 Ok(state)
             }
+            Expr::Aggregate(aggr_expr) if aggr_expr.over.is_some() => {
+                let mut state = state;
+                for expr in aggr_expr.as_exprs() {
+                    state = aggregate(state, filter_context.clone(), expr).await?;
+                }
+
+                Ok(state)
+            }
             Expr::Aggregate(aggr_expr) => {
                 state.accumulate(filter_context, aggr_expr.as_ref()).await
             }
@@ -225,7 +243,7 @@ fn check(expr: &Expr) -> bool {
                     .map(|expr| check(expr))
                     .unwrap_or(false)
         }
-        Expr::Aggregate(_) => true,
+        Expr::Aggregate(aggregate) => aggregate.over.is_none() || aggregate.as_exprs().any(check),
         _ => false,
     }
 }
@@ -5,6 +5,7 @@ use {
         executor::{
             context::RowContext,
             evaluate::{EvaluateError, evaluate},
+            window::WindowError,
         },
         result::Result,
         store::GStore,
@@ -192,6 +193,15 @@ impl AggrValue {
                     distinct_values,
                 }
             }
+            AggregateFunction::RowNumber
+            | AggregateFunction::Rank
+            | AggregateFunction::DenseRank
+            | AggregateFunction::Lag { .. }
+            | AggregateFunction::Lead { .. }
+            | AggregateFunction::FirstValue(_)
+            | AggregateFunction::LastValue(_) => {
+                return Err(WindowError::OverClauseRequired(aggr.func.name().to_owned()).into());
+            }
             AggregateFunction::Stdev(_) => {
                 let mut distinct_values = if aggr.distinct {
                     Some(HashSet::new())
@@ -433,6 +443,11 @@ impl<'a, T: GStore> State<'a, T> {
         }
     }
 
+    /// Number of the groups found so far, each of which keeps its first row
+    pub fn num_groups(&self) -> usize {
+        self.contexts.len()
+    }
+
     fn update(self, aggr: &'a Aggregate, value: AggrValue) -> Self {
         let key = (Arc::clone(&self.group), aggr);
         let (values, _) = self.values.insert(key, (self.index, value));
@@ -482,29 +497,7 @@ impl<'a, T: GStore> State<'a, T> {
         filter_context: Option<Arc<RowContext<'a>>>,
         aggr: &'a Aggregate,
     ) -> Result<State<'a, T>> {
-        let value = match &aggr.func {
-            AggregateFunction::Count(CountArgExpr::Wildcard) => {
-                if aggr.distinct {
-                    let context = filter_context.as_ref().ok_or_else(|| {
-                        EvaluateError::FilterContextRequiredForAggregate(aggr.clone())
-                    })?;
-                    let entries = context.get_all_entries();
-                    let values: Vec<Value> = entries.into_iter().map(|(_, v)| v).collect();
-                    Value::List(values)
-                } else {
-                    Value::Null
-                }
-            }
-            AggregateFunction::Count(CountArgExpr::Expr(expr))
-            | AggregateFunction::Sum(expr)
-            | AggregateFunction::Min(expr)
-            | AggregateFunction::Max(expr)
-            | AggregateFunction::Avg(expr)
-            | AggregateFunction::Variance(expr)
-            | AggregateFunction::Stdev(expr) => evaluate(self.storage, filter_context, None, expr)
-                .await?
-                .try_into()?,
-        };
+        let value = evaluate_argument(self.storage, filter_context, None, aggr).await?;
         let aggr_value = match self.get(aggr) {
             Some((index, _)) if self.index <= *index => None,
             Some((_, aggr_value)) => aggr_value.accumulate(&value)?,
@@ -517,3 +510,80 @@ impl<'a, T: GStore> State<'a, T> {
         }
     }
 }
+
+/// Evaluates the argument of `aggr` which is fed into its aggregate state.
+pub async fn evaluate_argument<'a, T: GStore>(
+    storage: &'a T,
+    filter_context: Option<Arc<RowContext<'a>>>,
+    aggregated: Option<Arc<HashMap<&'a Aggregate, Value>>>,
+    aggr: &'a Aggregate,
+) -> Result<Value> {
+    match &aggr.func {
+        AggregateFunction::Count(CountArgExpr::Wildcard) => {
+            if aggr.distinct {
+                let context = filter_context.as_ref().ok_or_else(|| {
+                    EvaluateError::FilterContextRequiredForAggregate(aggr.clone())
+                })?;
+                let entries = context.get_all_entries();
+                let values: Vec<Value> = entries.into_iter().map(|(_, v)| v).collect();
+
+                Ok(Value::List(values))
+            } else {
+                Ok(Value::Null)
+            }
+        }
+        AggregateFunction::Count(CountArgExpr::Expr(expr))
+        | AggregateFunction::Sum(expr)
+        | AggregateFunction::Min(expr)
+        | AggregateFunction::Max(expr)
+        | AggregateFunction::Avg(expr)
+        | AggregateFunction::Variance(expr)
+        | AggregateFunction::Stdev(expr) => evaluate(storage, filter_context, aggregated, expr)
+            .await?
+            .try_into(),
+        AggregateFunction::RowNumber
+        | AggregateFunction::Rank
+        | AggregateFunction::DenseRank
+        | AggregateFunction::Lag { .. }
+        | AggregateFunction::Lead { .. }
+        | AggregateFunction::FirstValue(_)
+        | AggregateFunction::LastValue(_) => {
+            Err(WindowError::OverClauseRequired(aggr.func.name().to_owned()).into())
+        }
+    }
+}
+
+/// Folds the values of a window frame into a single aggregate state.
+pub struct Accumulator<'a> {
+    aggr: &'a Aggregate,
+    value: Option<AggrValue>,
+}
+
+impl<'a> Accumulator<'a> {
+    pub fn new(aggr: &'a Aggregate) -> Self {
+        Self { aggr, value: None }
+    }
+
+    pub fn accumulate(&mut self, value: &Value) -> Result<()> {
+        let aggr_value = match &self.value {
+            Some(aggr_value) => aggr_value.accumulate(value)?,
+            None => Some(AggrValue::new(self.aggr, value)?),
+        };
+
+        if let Some(aggr_value) = aggr_value {
+            self.value = Some(aggr_value);
+        }
+
+        Ok(())
+    }
+
+    pub async fn export(&self) -> Result<Value> {
+        match &self.value {
+            Some(aggr_value) => aggr_value.clone().export().await,
+            None => match self.aggr.func {
+                AggregateFunction::Count(_) => Ok(Value::I64(0)),
+                _ => Ok(Value::Null),
+            },
+        }
+    }
+}
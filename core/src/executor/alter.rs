@@ -2,15 +2,28 @@ mod alter_table;
 mod error;
 mod function;
 mod index;
+mod sequence;
 mod table;
+mod trigger;
 mod validate;
+mod view;
 
-use validate::{validate, validate_arg_names, validate_column_names, validate_default_args};
+use {
+    sequence::{identity_sequences, rename_identity_sequence},
+    trigger::{drop_table_triggers, rename_table_triggers},
+    validate::{
+        evaluate_on_null_row, validate, validate_arg_names, validate_checks, validate_column_names,
+        validate_default_args, validate_generated, validate_key_constraints,
+    },
+};
 
 pub use {
     alter_table::alter_table,
     error::AlterError,
     function::{delete_function, insert_function},
     index::create_index,
+    sequence::{create_sequence, drop_sequence},
     table::{CreateTableOptions, Referencing, create_table, drop_table},
+    trigger::{CreateTriggerOptions, create_trigger, drop_trigger},
+    view::{create_view, drop_view, refresh_materialized_view},
 };
@@ -1,11 +1,19 @@
 use {
-    super::{AlterError, Referencing, validate},
+    super::{
+        AlterError, Referencing, evaluate_on_null_row, rename_identity_sequence,
+        rename_table_triggers, validate, validate_checks,
+    },
     crate::{
-        ast::{AlterTableOperation, Expr, Function},
-        data::{Schema, SchemaIndex},
-        result::Result,
-        store::{GStore, GStoreMut},
+        ast::{AlterTableOperation, ColumnDef, ColumnIdentity, Expr, Function, ToSql},
+        data::{Schema, SchemaIndex, Value},
+        executor::{
+            EvaluateError, evaluate_stateless,
+            validate::{check_label, validate_check},
+        },
+        result::{Error, Result},
+        store::{DataRow, GStore, GStoreMut},
     },
+    futures::stream::TryStreamExt,
 };
 
 pub async fn alter_table<T: GStore + GStoreMut>(
@@ -20,6 +28,22 @@ pub async fn alter_table<T: GStore + GStoreMut>(
     | AlterTableOperation::DropColumn { column_name, .. } = operation
     {
         if let Some(schema) = storage.fetch_schema(table_name).await? {
+            let key_constraint = schema
+                .key_constraints
+                .iter()
+                .find(|key_constraint| key_constraint.columns.contains(column_name));
+
+            if let Some(key_constraint) = key_constraint {
+                return Err(AlterError::CannotAlterKeyColumn {
+                    column_name: column_name.to_owned(),
+                    constraint: key_constraint
+                        .name
+                        .to_owned()
+                        .unwrap_or_else(|| key_constraint.to_sql()),
+                }
+                .into());
+            }
+
             let referencing_foreign_key = schema
                 .foreign_keys
                 .into_iter()
@@ -46,22 +70,61 @@ pub async fn alter_table<T: GStore + GStoreMut>(
         if let Some(referencing) = referencing {
             return Err(AlterError::CannotAlterReferencedColumn { referencing }.into());
         }
+
+        let is_drop = matches!(operation, AlterTableOperation::DropColumn { .. });
+        validate_unchecked_column(storage, table_name, column_name, is_drop).await?;
     }
 
     match operation {
         AlterTableOperation::RenameTable {
             table_name: new_table_name,
-        } => storage.rename_schema(table_name, new_table_name).await,
+        } => {
+            let column_names = identity_column_names(storage, table_name).await?;
+            storage.rename_schema(table_name, new_table_name).await?;
+            rename_table_triggers(storage, table_name, new_table_name).await?;
+
+            for column_name in column_names {
+                rename_identity_sequence(
+                    storage,
+                    &ColumnIdentity::sequence_name(table_name, &column_name),
+                    &ColumnIdentity::sequence_name(new_table_name, &column_name),
+                )
+                .await?;
+            }
+
+            Ok(())
+        }
         AlterTableOperation::RenameColumn {
             old_column_name,
             new_column_name,
         } => {
+            let column_names = identity_column_names(storage, table_name).await?;
             storage
                 .rename_column(table_name, old_column_name, new_column_name)
-                .await
+                .await?;
+
+            if column_names.contains(old_column_name) {
+                rename_identity_sequence(
+                    storage,
+                    &ColumnIdentity::sequence_name(table_name, old_column_name),
+                    &ColumnIdentity::sequence_name(table_name, new_column_name),
+                )
+                .await?;
+            }
+
+            Ok(())
         }
         AlterTableOperation::AddColumn { column_def } => {
+            if column_def.identity.is_some() {
+                return Err(AlterError::CannotAddIdentityColumn(column_def.name.to_owned()).into());
+            } else if column_def.generated.is_some() {
+                return Err(
+                    AlterError::CannotAddGeneratedColumn(column_def.name.to_owned()).into(),
+                );
+            }
+
             validate(column_def).await?;
+            validate_added_column_check(storage, table_name, column_def).await?;
 
             storage.add_column(table_name, column_def).await
         }
@@ -69,8 +132,12 @@ pub async fn alter_table<T: GStore + GStoreMut>(
             column_name,
             if_exists,
         } => {
-            let indexes = match storage.fetch_schema(table_name).await? {
-                Some(Schema { indexes, .. }) => indexes,
+            let (indexes, column_defs) = match storage.fetch_schema(table_name).await? {
+                Some(Schema {
+                    indexes,
+                    column_defs,
+                    ..
+                }) => (indexes, column_defs),
                 None => {
                     return Err(AlterError::TableNotFound(table_name.to_owned()).into());
                 }
@@ -78,7 +145,9 @@ pub async fn alter_table<T: GStore + GStoreMut>(
 
             let indexes = indexes
                 .iter()
-                .filter(|SchemaIndex { expr, .. }| find_column(expr, column_name))
+                .filter(|SchemaIndex { exprs, .. }| {
+                    exprs.iter().any(|expr| find_column(expr, column_name))
+                })
                 .map(|SchemaIndex { name, .. }| name);
 
             for index_name in indexes {
@@ -87,9 +156,165 @@ pub async fn alter_table<T: GStore + GStoreMut>(
 
             storage
                 .drop_column(table_name, column_name, *if_exists)
-                .await
+                .await?;
+
+            let is_identity = column_defs
+                .iter()
+                .flatten()
+                .any(|column_def| &column_def.name == column_name && column_def.identity.is_some());
+
+            match is_identity {
+                true => {
+                    let sequence_name = ColumnIdentity::sequence_name(table_name, column_name);
+
+                    storage.delete_sequence(&sequence_name).await
+                }
+                false => Ok(()),
+            }
+        }
+    }
+}
+
+async fn identity_column_names<T: GStore>(storage: &T, table_name: &str) -> Result<Vec<String>> {
+    let column_defs = match storage.fetch_schema(table_name).await? {
+        Some(Schema {
+            column_defs: Some(column_defs),
+            ..
+        }) => column_defs,
+        _ => return Ok(Vec::new()),
+    };
+
+    Ok(column_defs
+        .into_iter()
+        .filter(|column_def| column_def.identity.is_some())
+        .map(|column_def| column_def.name)
+        .collect())
+}
+
+/// Checks the rows already in the table against the `CHECK` constraint of the column being
+/// added, filled with the value the column gets on them.
+async fn validate_added_column_check<T: GStore>(
+    storage: &T,
+    table_name: &str,
+    column_def: &ColumnDef,
+) -> Result<()> {
+    let ColumnDef {
+        data_type,
+        nullable,
+        default,
+        check,
+        ..
+    } = column_def;
+
+    if check.is_none() {
+        return Ok(());
+    }
+
+    let column_defs = match storage.fetch_schema(table_name).await? {
+        Some(Schema {
+            column_defs: Some(column_defs),
+            ..
+        }) => [column_defs, vec![column_def.clone()]].concat(),
+        _ => return Ok(()),
+    };
+    validate_checks(&column_defs, &[]).await?;
+
+    let value = match default {
+        Some(expr) => evaluate_stateless(None, expr)
+            .await?
+            .try_into_value(data_type, *nullable)?,
+        None => Value::Null,
+    };
+    let rows = storage
+        .scan_data(table_name)
+        .await?
+        .try_filter_map(|(_, data_row)| {
+            let value = value.clone();
+
+            async move {
+                Ok(match data_row {
+                    DataRow::Vec(values) => Some([values, vec![value]].concat()),
+                    DataRow::Map(_) => None,
+                })
+            }
+        })
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    validate_check(
+        table_name,
+        &column_defs,
+        &[],
+        rows.iter().map(Vec::as_slice),
+    )
+    .await
+}
+
+/// Rejects renaming or dropping a column the `CHECK` constraints or the generated columns of the
+/// table refer to. The check of a dropped column itself goes away with the column.
+async fn validate_unchecked_column<T: GStore>(
+    storage: &T,
+    table_name: &str,
+    column_name: &str,
+    is_drop: bool,
+) -> Result<()> {
+    let (column_defs, checks) = match storage.fetch_schema(table_name).await? {
+        Some(Schema {
+            column_defs: Some(column_defs),
+            checks,
+            ..
+        }) => (column_defs, checks),
+        _ => return Ok(()),
+    };
+
+    let columns = column_defs
+        .iter()
+        .filter(|column_def| column_def.name != column_name)
+        .map(|column_def| column_def.name.to_owned())
+        .collect::<Vec<_>>();
+    let checks = column_defs
+        .iter()
+        .filter(|column_def| !(is_drop && column_def.name == column_name))
+        .filter_map(|column_def| column_def.check.as_ref())
+        .chain(&checks);
+
+    for check in checks {
+        match evaluate_on_null_row(&columns, &check.expr).await {
+            Err(Error::Evaluate(EvaluateError::IdentifierNotFound(ident)))
+                if ident == column_name =>
+            {
+                return Err(AlterError::CannotAlterCheckedColumn {
+                    column_name: column_name.to_owned(),
+                    constraint: check_label(check),
+                }
+                .into());
+            }
+            _ => {}
         }
     }
+
+    let generated_columns = column_defs.iter().filter_map(|column_def| {
+        let expr = column_def.generated.as_ref()?;
+
+        Some((&column_def.name, expr))
+    });
+
+    for (generated_column, expr) in generated_columns {
+        match evaluate_on_null_row(&columns, expr).await {
+            Err(Error::Evaluate(EvaluateError::IdentifierNotFound(ident)))
+                if ident == column_name =>
+            {
+                return Err(AlterError::CannotAlterGeneratingColumn {
+                    column_name: column_name.to_owned(),
+                    generated_column: generated_column.to_owned(),
+                }
+                .into());
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
 }
 
 fn find_column(expr: &Expr, column_name: &str) -> bool {
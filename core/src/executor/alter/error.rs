@@ -18,6 +18,18 @@ pub enum AlterError {
     #[error("function does not exist: {0}")]
     FunctionNotFound(String),
 
+    #[error("view already exists: {0}")]
+    ViewAlreadyExists(String),
+
+    #[error("view does not exist: {0}")]
+    ViewNotFound(String),
+
+    #[error("view is not materialized: {0}")]
+    NotMaterializedView(String),
+
+    #[error("view specifies more column names than the query returns: {0}")]
+    TooManyViewColumns(String),
+
     // CREATE INDEX, DROP TABLE
     #[error("table does not exist: {0}")]
     TableNotFound(String),
@@ -29,6 +41,31 @@ pub enum AlterError {
     #[error("column '{0}' of data type '{1:?}' is unsupported for unique constraint")]
     UnsupportedDataTypeForUniqueColumn(String, DataType),
 
+    #[error("column '{0}' of data type '{1:?}' is unsupported for identity column")]
+    UnsupportedDataTypeForIdentityColumn(String, DataType),
+
+    #[error("identity column cannot have a default value: {0}")]
+    IdentityColumnWithDefault(String),
+
+    #[error("identity column cannot be added to an existing table: {0}")]
+    CannotAddIdentityColumn(String),
+
+    #[error("cannot drop sequence '{0}' used by an identity column")]
+    CannotDropIdentitySequence(String),
+
+    #[error("generated column cannot have a default value: {0}")]
+    GeneratedColumnWithDefault(String),
+
+    #[error("generated column cannot be added to an existing table: {0}")]
+    CannotAddGeneratedColumn(String),
+
+    // validate key constraints
+    #[error("key constraint column not found: {0}")]
+    KeyColumnNotFound(String),
+
+    #[error("multiple primary keys are not allowed")]
+    MultiplePrimaryKeys,
+
     // validate index expr
     #[error("unsupported index expr: {0:#?}")]
     UnsupportedIndexExpr(Expr),
@@ -88,6 +125,24 @@ pub enum AlterError {
     #[error("cannot drop column '{}.{}' referencing with '{}'", referencing.table_name, referencing.foreign_key.referencing_column_name, referencing)]
     CannotAlterReferencingColumn { referencing: Referencing },
 
+    #[error("cannot alter column '{column_name}' used by check constraint '{constraint}'")]
+    CannotAlterCheckedColumn {
+        column_name: String,
+        constraint: String,
+    },
+
+    #[error("cannot alter column '{column_name}' used by generated column '{generated_column}'")]
+    CannotAlterGeneratingColumn {
+        column_name: String,
+        generated_column: String,
+    },
+
+    #[error("cannot alter column '{column_name}' used by key constraint '{constraint}'")]
+    CannotAlterKeyColumn {
+        column_name: String,
+        constraint: String,
+    },
+
     #[error("unreachable")]
     Unreachable,
 }
@@ -12,9 +12,9 @@ pub async fn create_index<T: GStore + GStoreMut>(
     storage: &mut T,
     table_name: &str,
     index_name: &str,
-    column: &OrderByExpr,
+    index_columns: &[OrderByExpr],
+    unique: bool,
 ) -> Result<()> {
-    let expr = &column.expr;
     let Schema { column_defs, .. } = storage
         .fetch_schema(table_name)
         .await?
@@ -25,14 +25,18 @@ pub async fn create_index<T: GStore + GStoreMut>(
         .map(|ColumnDef { name, .. }| name)
         .collect::<Vec<_>>();
 
-    let (valid, has_ident) = validate_index_expr(&columns, expr);
-    if !valid {
-        return Err(AlterError::UnsupportedIndexExpr(expr.clone()).into());
-    } else if !has_ident {
-        return Err(AlterError::IdentifierNotFound(expr.clone()).into());
+    for OrderByExpr { expr, .. } in index_columns {
+        let (valid, has_ident) = validate_index_expr(&columns, expr);
+        if !valid {
+            return Err(AlterError::UnsupportedIndexExpr(expr.clone()).into());
+        } else if !has_ident {
+            return Err(AlterError::IdentifierNotFound(expr.clone()).into());
+        }
     }
 
-    storage.create_index(table_name, index_name, column).await
+    storage
+        .create_index(table_name, index_name, index_columns, unique)
+        .await
 }
 
 fn validate_index_expr(columns: &[String], expr: &Expr) -> (bool, bool) {
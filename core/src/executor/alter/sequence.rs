@@ -0,0 +1,95 @@
+use {
+    super::AlterError,
+    crate::{
+        ast::{ColumnDef, ColumnIdentity, SequenceOptions},
+        data::{Schema, Sequence, SequenceError},
+        result::Result,
+        store::{GStore, GStoreMut},
+    },
+};
+
+pub async fn create_sequence<T: GStore + GStoreMut>(
+    storage: &mut T,
+    name: &str,
+    options: &SequenceOptions,
+    if_not_exists: bool,
+) -> Result<()> {
+    let sequence = Sequence::new(name.to_owned(), options)?;
+
+    if storage.fetch_sequence(name).await?.is_some() {
+        return match if_not_exists {
+            true => Ok(()),
+            false => Err(SequenceError::AlreadyExists(name.to_owned()).into()),
+        };
+    }
+
+    storage.insert_sequence(sequence).await
+}
+
+pub async fn drop_sequence<T: GStore + GStoreMut>(
+    storage: &mut T,
+    sequence_names: &[String],
+    if_exists: bool,
+) -> Result<usize> {
+    let identity_sequences = storage
+        .fetch_all_schemas()
+        .await?
+        .iter()
+        .flat_map(identity_sequences)
+        .map(|(sequence_name, _)| sequence_name)
+        .collect::<Vec<_>>();
+    let mut n = 0;
+
+    for sequence_name in sequence_names {
+        match (storage.fetch_sequence(sequence_name).await?, if_exists) {
+            (None, true) => continue,
+            (None, false) => {
+                return Err(SequenceError::NotFound(sequence_name.to_owned()).into());
+            }
+            (Some(_), _) => {}
+        }
+
+        if identity_sequences.contains(sequence_name) {
+            return Err(AlterError::CannotDropIdentitySequence(sequence_name.to_owned()).into());
+        }
+
+        storage.delete_sequence(sequence_name).await?;
+        n += 1;
+    }
+
+    Ok(n)
+}
+
+/// Sequences owned by the identity columns of `schema`, along with their identities
+pub fn identity_sequences(schema: &Schema) -> impl Iterator<Item = (String, &ColumnIdentity)> {
+    schema
+        .column_defs
+        .iter()
+        .flatten()
+        .filter_map(|ColumnDef { name, identity, .. }| {
+            let sequence_name = ColumnIdentity::sequence_name(&schema.table_name, name);
+
+            identity.as_ref().map(|identity| (sequence_name, identity))
+        })
+}
+
+/// Moves an identity sequence to the name derived from its renamed table or column, keeping
+/// its current value.
+pub async fn rename_identity_sequence<T: GStore + GStoreMut>(
+    storage: &mut T,
+    old_name: &str,
+    new_name: &str,
+) -> Result<()> {
+    let mut sequence = storage
+        .fetch_sequence(old_name)
+        .await?
+        .ok_or_else(|| SequenceError::NotFound(old_name.to_owned()))?;
+
+    if storage.fetch_sequence(new_name).await?.is_some() {
+        return Err(SequenceError::AlreadyExists(new_name.to_owned()).into());
+    }
+
+    sequence.name = new_name.to_owned();
+    storage.delete_sequence(old_name).await?;
+    storage.insert_sequence(sequence).await
+}
@@ -1,10 +1,14 @@
 use {
-    super::{AlterError, validate, validate_column_names},
+    super::{
+        AlterError, drop_table_triggers, identity_sequences, validate, validate_checks,
+        validate_column_names, validate_generated, validate_key_constraints,
+    },
     crate::{
         ast::{
-            ColumnDef, ColumnUniqueOption, ForeignKey, Query, SetExpr, TableFactor, ToSql, Values,
+            CheckConstraint, ColumnDef, ColumnIdentity, ForeignKey, KeyConstraint, Query, SetExpr,
+            TableFactor, ToSql, Values,
         },
-        data::Schema,
+        data::{Schema, Sequence, SequenceError},
         executor::{evaluate_stateless, select::select},
         prelude::{DataType, Value},
         result::Result,
@@ -21,7 +25,9 @@ pub struct CreateTableOptions<'a> {
     pub if_not_exists: bool,
     pub source: &'a Option<Box<Query>>,
     pub engine: &'a Option<String>,
+    pub key_constraints: &'a Vec<KeyConstraint>,
     pub foreign_keys: &'a Vec<ForeignKey>,
+    pub checks: &'a Vec<CheckConstraint>,
     pub comment: &'a Option<String>,
 }
 
@@ -33,12 +39,14 @@ pub async fn create_table<T: GStore + GStoreMut>(
         if_not_exists,
         source,
         engine,
+        key_constraints,
         foreign_keys,
+        checks,
         comment,
     }: CreateTableOptions<'_>,
 ) -> Result<()> {
     let target_columns_defs = match source.as_deref() {
-        Some(Query { body, .. }) => match body {
+        Some(Query { body, .. }) => match leftmost_set_expr(body) {
             SetExpr::Select(select_query) => match &select_query.from.relation {
                 TableFactor::Table { name, .. } => {
                     let schema = storage.fetch_schema(name).await?;
@@ -48,7 +56,18 @@ pub async fn create_table<T: GStore + GStoreMut>(
                     } = schema
                         .ok_or_else(|| AlterError::CtasSourceTableNotFound(name.to_owned()))?;
 
-                    source_column_defs
+                    // Copied rows keep their values, the new table neither owns the sequences
+                    // nor recomputes the generated columns.
+                    source_column_defs.map(|column_defs| {
+                        column_defs
+                            .into_iter()
+                            .map(|column_def| ColumnDef {
+                                identity: None,
+                                generated: None,
+                                ..column_def
+                            })
+                            .collect()
+                    })
                 }
                 TableFactor::Series { .. } => {
                     let column_def = ColumnDef {
@@ -56,7 +75,10 @@ pub async fn create_table<T: GStore + GStoreMut>(
                         data_type: DataType::Int,
                         nullable: false,
                         default: None,
+                        identity: None,
+                        generated: None,
                         unique: None,
+                        check: None,
                         comment: None,
                     };
 
@@ -66,6 +88,9 @@ pub async fn create_table<T: GStore + GStoreMut>(
                     return Err(AlterError::Unreachable.into());
                 }
             },
+            SetExpr::SetOperation { .. } => {
+                return Err(AlterError::Unreachable.into());
+            }
             SetExpr::Values(Values(values_list)) => {
                 let first_len = values_list[0].len();
                 let mut column_types = vec![None; first_len];
@@ -99,7 +124,10 @@ pub async fn create_table<T: GStore + GStoreMut>(
                         data_type,
                         nullable: true,
                         default: None,
+                        identity: None,
+                        generated: None,
                         unique: None,
+                        check: None,
                         comment: None,
                     })
                     .collect::<Vec<_>>();
@@ -117,8 +145,16 @@ pub async fn create_table<T: GStore + GStoreMut>(
         for column_def in column_defs {
             validate(column_def).await?;
         }
+
+        validate_checks(column_defs, checks).await?;
+        validate_generated(column_defs).await?;
     }
 
+    validate_key_constraints(
+        target_columns_defs.as_deref().unwrap_or_default(),
+        key_constraints,
+    )?;
+
     for foreign_key in foreign_keys {
         let ForeignKey {
             referencing_column_name,
@@ -127,18 +163,22 @@ pub async fn create_table<T: GStore + GStoreMut>(
             ..
         } = foreign_key;
 
-        let column_defs = if referenced_table_name == target_table_name {
-            target_columns_defs.clone()
-        } else {
-            let referenced_schema = storage
-                .fetch_schema(referenced_table_name)
-                .await?
-                .ok_or_else(|| {
-                    AlterError::ReferencedTableNotFound(referenced_table_name.to_owned())
-                })?;
-
-            referenced_schema.column_defs
-        };
+        let (column_defs, referenced_key_constraints) =
+            if referenced_table_name == target_table_name {
+                (target_columns_defs.clone(), key_constraints.clone())
+            } else {
+                let referenced_schema = storage
+                    .fetch_schema(referenced_table_name)
+                    .await?
+                    .ok_or_else(|| {
+                        AlterError::ReferencedTableNotFound(referenced_table_name.to_owned())
+                    })?;
+
+                (
+                    referenced_schema.column_defs,
+                    referenced_schema.key_constraints,
+                )
+            };
 
         let referenced_column_def = column_defs
             .and_then(|column_defs| {
@@ -170,7 +210,14 @@ pub async fn create_table<T: GStore + GStoreMut>(
             .into());
         }
 
-        if referenced_column_def.unique != Some(ColumnUniqueOption { is_primary: true }) {
+        // The referenced column has to be unique on its own, either the primary key or a
+        // `UNIQUE` column.
+        let is_unique = referenced_column_def.unique.is_some()
+            || referenced_key_constraints
+                .iter()
+                .any(|KeyConstraint { columns, .. }| *columns == [referenced_column_name.as_str()]);
+
+        if !is_unique {
             return Err(AlterError::ReferencingNonPKColumn {
                 referenced_table: referenced_table_name.to_owned(),
                 referenced_column: referenced_column_name.to_owned(),
@@ -179,17 +226,42 @@ pub async fn create_table<T: GStore + GStoreMut>(
         }
     }
 
+    if storage.fetch_view(target_table_name).await?.is_some() {
+        return match if_not_exists {
+            true => Ok(()),
+            false => Err(AlterError::ViewAlreadyExists(target_table_name.to_owned()).into()),
+        };
+    }
+
     if storage.fetch_schema(target_table_name).await?.is_none() {
         let schema = Schema {
             table_name: target_table_name.to_owned(),
             column_defs: target_columns_defs,
             indexes: vec![],
             engine: engine.clone(),
+            key_constraints: key_constraints.clone(),
             foreign_keys: foreign_keys.clone(),
+            checks: checks.clone(),
             comment: comment.clone(),
         };
 
+        let sequences = identity_sequences(&schema)
+            .map(|(sequence_name, ColumnIdentity { options, .. })| {
+                Sequence::new(sequence_name, options)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        for Sequence { name, .. } in &sequences {
+            if storage.fetch_sequence(name).await?.is_some() {
+                return Err(SequenceError::AlreadyExists(name.to_owned()).into());
+            }
+        }
+
         storage.insert_schema(&schema).await?;
+
+        for sequence in sequences {
+            storage.insert_sequence(sequence).await?;
+        }
     } else if !if_not_exists {
         return Err(AlterError::TableAlreadyExists(target_table_name.to_owned()).into());
     }
@@ -211,6 +283,14 @@ pub async fn create_table<T: GStore + GStoreMut>(
     }
 }
 
+/// Set operations take their column definitions from the leftmost operand.
+fn leftmost_set_expr(body: &SetExpr) -> &SetExpr {
+    match body {
+        SetExpr::SetOperation { left, .. } => leftmost_set_expr(&left.body),
+        _ => body,
+    }
+}
+
 pub async fn drop_table<T: GStore + GStoreMut>(
     storage: &mut T,
     table_names: &[String],
@@ -222,7 +302,7 @@ pub async fn drop_table<T: GStore + GStoreMut>(
     for table_name in table_names {
         let schema = storage.fetch_schema(table_name).await?;
 
-        match (schema, if_exists) {
+        match (&schema, if_exists) {
             (None, true) => {
                 continue;
             }
@@ -256,7 +336,15 @@ pub async fn drop_table<T: GStore + GStoreMut>(
                 .retain(|foreign_key| foreign_key.name != name);
             storage.insert_schema(&schema).await?;
         }
+        if let Some(schema) = &schema {
+            for (sequence_name, _) in identity_sequences(schema) {
+                storage.delete_sequence(&sequence_name).await?;
+            }
+        }
+
         storage.delete_schema(table_name).await?;
+        storage.delete_statistics(table_name).await?;
+        drop_table_triggers(storage, table_name).await?;
 
         n += 1;
     }
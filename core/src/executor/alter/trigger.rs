@@ -0,0 +1,116 @@
+use crate::{
+    ast::{Expr, Statement, TriggerEvent, TriggerTiming},
+    data::{Trigger, TriggerError},
+    result::Result,
+    store::{GStore, GStoreMut},
+};
+
+pub struct CreateTriggerOptions<'a> {
+    pub or_replace: bool,
+    pub name: &'a str,
+    pub table_name: &'a str,
+    pub timing: TriggerTiming,
+    pub events: &'a [TriggerEvent],
+    pub condition: &'a Option<Expr>,
+    pub body: &'a [Statement],
+}
+
+pub async fn create_trigger<T: GStore + GStoreMut>(
+    storage: &mut T,
+    options: CreateTriggerOptions<'_>,
+) -> Result<()> {
+    let CreateTriggerOptions {
+        or_replace,
+        name,
+        table_name,
+        timing,
+        events,
+        condition,
+        body,
+    } = options;
+
+    if storage.fetch_schema(table_name).await?.is_none() {
+        return Err(TriggerError::TableNotFound(table_name.to_owned()).into());
+    }
+
+    let exists = storage
+        .fetch_triggers(table_name)
+        .await?
+        .iter()
+        .any(|trigger| trigger.name == name);
+
+    if exists && !or_replace {
+        return Err(TriggerError::AlreadyExists {
+            name: name.to_owned(),
+            table_name: table_name.to_owned(),
+        }
+        .into());
+    }
+
+    let trigger = Trigger {
+        name: name.to_owned(),
+        table_name: table_name.to_owned(),
+        timing,
+        events: events.to_vec(),
+        condition: condition.clone(),
+        body: body.to_vec(),
+    };
+
+    storage.insert_trigger(trigger).await
+}
+
+pub async fn drop_trigger<T: GStore + GStoreMut>(
+    storage: &mut T,
+    name: &str,
+    table_name: &str,
+    if_exists: bool,
+) -> Result<()> {
+    let exists = storage
+        .fetch_triggers(table_name)
+        .await?
+        .iter()
+        .any(|trigger| trigger.name == name);
+
+    match (exists, if_exists) {
+        (true, _) => storage.delete_trigger(table_name, name).await,
+        (false, true) => Ok(()),
+        (false, false) => Err(TriggerError::NotFound {
+            name: name.to_owned(),
+            table_name: table_name.to_owned(),
+        }
+        .into()),
+    }
+}
+
+/// Drops the triggers of a dropped table.
+pub async fn drop_table_triggers<T: GStore + GStoreMut>(
+    storage: &mut T,
+    table_name: &str,
+) -> Result<()> {
+    for trigger in storage.fetch_triggers(table_name).await? {
+        storage.delete_trigger(table_name, &trigger.name).await?;
+    }
+
+    Ok(())
+}
+
+/// Moves the triggers of a renamed table to its new name.
+pub async fn rename_table_triggers<T: GStore + GStoreMut>(
+    storage: &mut T,
+    old_table_name: &str,
+    new_table_name: &str,
+) -> Result<()> {
+    for trigger in storage.fetch_triggers(old_table_name).await? {
+        storage
+            .delete_trigger(old_table_name, &trigger.name)
+            .await?;
+        storage
+            .insert_trigger(Trigger {
+                table_name: new_table_name.to_owned(),
+                ..trigger
+            })
+            .await?;
+    }
+
+    Ok(())
+}
@@ -1,8 +1,12 @@
 use {
     super::AlterError,
     crate::{
-        ast::{ColumnDef, ColumnUniqueOption, DataType, OperateFunctionArg},
-        executor::evaluate_stateless,
+        ast::{
+            CheckConstraint, ColumnDef, ColumnUniqueOption, DataType, Expr, KeyConstraint,
+            OperateFunctionArg,
+        },
+        data::Value,
+        executor::{context::RowContext, evaluate::uses_sequence, evaluate_stateless},
         result::Result,
     },
 };
@@ -11,6 +15,8 @@ pub async fn validate(column_def: &ColumnDef) -> Result<()> {
     let ColumnDef {
         data_type,
         default,
+        identity,
+        generated,
         unique,
         name,
         ..
@@ -27,13 +33,125 @@ pub async fn validate(column_def: &ColumnDef) -> Result<()> {
         .into());
     }
 
-    if let Some(expr) = default {
+    if identity.is_some() {
+        if default.is_some() {
+            return Err(AlterError::IdentityColumnWithDefault(name.to_owned()).into());
+        } else if !matches!(
+            data_type,
+            DataType::Int8
+                | DataType::Int16
+                | DataType::Int32
+                | DataType::Int
+                | DataType::Int128
+                | DataType::Uint8
+                | DataType::Uint16
+                | DataType::Uint32
+                | DataType::Uint64
+                | DataType::Uint128
+        ) {
+            return Err(AlterError::UnsupportedDataTypeForIdentityColumn(
+                name.to_owned(),
+                data_type.clone(),
+            )
+            .into());
+        }
+    }
+
+    if generated.is_some() && default.is_some() {
+        return Err(AlterError::GeneratedColumnWithDefault(name.to_owned()).into());
+    }
+
+    // `NEXTVAL` would advance its sequence, so defaults using one are only checked on insert.
+    if let Some(expr) = default.as_ref().filter(|expr| !uses_sequence(expr)) {
         evaluate_stateless(None, expr).await?;
     }
 
     Ok(())
 }
 
+pub async fn validate_checks(column_defs: &[ColumnDef], checks: &[CheckConstraint]) -> Result<()> {
+    let columns = column_defs
+        .iter()
+        .map(|column_def| column_def.name.to_owned())
+        .collect::<Vec<_>>();
+    let checks = column_defs
+        .iter()
+        .filter_map(|column_def| column_def.check.as_ref())
+        .chain(checks);
+
+    for CheckConstraint { expr, .. } in checks {
+        evaluate_on_null_row(&columns, expr).await?;
+    }
+
+    Ok(())
+}
+
+/// Generated columns are computed from the other columns, never from each other.
+pub async fn validate_generated(column_defs: &[ColumnDef]) -> Result<()> {
+    let columns = column_defs
+        .iter()
+        .filter(|column_def| column_def.generated.is_none())
+        .map(|column_def| column_def.name.to_owned())
+        .collect::<Vec<_>>();
+
+    for expr in column_defs
+        .iter()
+        .filter_map(|column_def| column_def.generated.as_ref())
+    {
+        evaluate_on_null_row(&columns, expr).await?;
+    }
+
+    Ok(())
+}
+
+/// Evaluates `expr` over a row of `NULL`s, which fails when it refers to a column missing
+/// from `columns` or to anything a single row cannot provide, such as a subquery.
+pub async fn evaluate_on_null_row(columns: &[String], expr: &Expr) -> Result<()> {
+    let values = vec![Value::Null; columns.len()];
+    let context = RowContext::RefVecData {
+        columns,
+        values: &values,
+    };
+
+    evaluate_stateless(Some(context), expr).await.map(|_| ())
+}
+
+pub fn validate_key_constraints(
+    column_defs: &[ColumnDef],
+    key_constraints: &[KeyConstraint],
+) -> Result<()> {
+    for column_name in key_constraints.iter().flat_map(|c| c.columns.iter()) {
+        let ColumnDef {
+            name, data_type, ..
+        } = column_defs
+            .iter()
+            .find(|column_def| &column_def.name == column_name)
+            .ok_or_else(|| AlterError::KeyColumnNotFound(column_name.to_owned()))?;
+
+        if matches!(data_type, DataType::Float | DataType::Map) {
+            return Err(AlterError::UnsupportedDataTypeForUniqueColumn(
+                name.to_owned(),
+                data_type.clone(),
+            )
+            .into());
+        }
+    }
+
+    let primary_keys = column_defs
+        .iter()
+        .filter(|column_def| column_def.unique == Some(ColumnUniqueOption { is_primary: true }))
+        .count()
+        + key_constraints
+            .iter()
+            .filter(|key_constraint| key_constraint.is_primary)
+            .count();
+
+    match primary_keys {
+        0 | 1 => Ok(()),
+        _ => Err(AlterError::MultiplePrimaryKeys.into()),
+    }
+}
+
 pub fn validate_column_names(column_defs: &[ColumnDef]) -> Result<()> {
     let duplicate_column_name = column_defs
         .iter()
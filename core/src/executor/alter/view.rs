@@ -0,0 +1,201 @@
+use {
+    super::{AlterError, validate_column_names},
+    crate::{
+        ast::{ColumnDef, DataType, Query, Statement},
+        data::{Row, Schema, View},
+        executor::select::select_with_labels,
+        plan::{plan, plan_view},
+        result::Result,
+        store::{DataRow, GStore, GStoreMut},
+    },
+    futures::stream::TryStreamExt,
+    std::collections::HashMap,
+};
+
+pub async fn create_view<T: GStore + GStoreMut>(
+    storage: &mut T,
+    name: &str,
+    columns: &[String],
+    query: &Query,
+    or_replace: bool,
+    materialized: bool,
+) -> Result<()> {
+    let replaced = storage.fetch_view(name).await?;
+
+    match &replaced {
+        Some(_) if !or_replace => {
+            return Err(AlterError::ViewAlreadyExists(name.to_owned()).into());
+        }
+        None if storage.fetch_schema(name).await?.is_some() => {
+            return Err(AlterError::TableAlreadyExists(name.to_owned()).into());
+        }
+        _ => {}
+    }
+
+    let view = View {
+        view_name: name.to_owned(),
+        columns: columns.to_vec(),
+        query: query.clone(),
+        materialized,
+    };
+
+    let mut view_map = storage
+        .fetch_all_views()
+        .await?
+        .into_iter()
+        .map(|view| (view.view_name.clone(), view))
+        .collect::<HashMap<_, _>>();
+    view_map.insert(name.to_owned(), view.clone());
+    plan_view(&view_map, Statement::Query(query.clone()))?;
+
+    let backing_table = match materialized {
+        true => Some(materialize(storage, &view).await?),
+        false => None,
+    };
+
+    if let Some(View {
+        materialized: true, ..
+    }) = replaced
+    {
+        storage.delete_schema(name).await?;
+    }
+
+    storage.insert_view(view).await?;
+
+    match backing_table {
+        Some((schema, rows)) => write_backing_table(storage, schema, rows).await.map(|_| ()),
+        None => Ok(()),
+    }
+}
+
+pub async fn drop_view<T: GStore + GStoreMut>(
+    storage: &mut T,
+    view_names: &[String],
+    if_exists: bool,
+) -> Result<usize> {
+    let mut n = 0;
+
+    for view_name in view_names {
+        let view = match (storage.fetch_view(view_name).await?, if_exists) {
+            (Some(view), _) => view,
+            (None, true) => continue,
+            (None, false) => {
+                return Err(AlterError::ViewNotFound(view_name.to_owned()).into());
+            }
+        };
+
+        if view.materialized {
+            storage.delete_schema(view_name).await?;
+            storage.delete_statistics(view_name).await?;
+        }
+
+        storage.delete_view(view_name).await?;
+        n += 1;
+    }
+
+    Ok(n)
+}
+
+/// Replaces the rows of the backing table with the current result of the view query,
+/// returning the number of rows written.
+pub async fn refresh_materialized_view<T: GStore + GStoreMut>(
+    storage: &mut T,
+    view_name: &str,
+) -> Result<usize> {
+    let view = match storage.fetch_view(view_name).await? {
+        Some(view) if view.materialized => view,
+        Some(_) => return Err(AlterError::NotMaterializedView(view_name.to_owned()).into()),
+        None => return Err(AlterError::ViewNotFound(view_name.to_owned()).into()),
+    };
+
+    let (schema, rows) = materialize(storage, &view).await?;
+
+    storage.delete_schema(view_name).await?;
+    write_backing_table(storage, schema, rows).await
+}
+
+/// Runs the view query, typing each column of the backing table after its first non-null
+/// value. Queries over schemaless tables make a schemaless backing table.
+async fn materialize<T: GStore>(storage: &T, view: &View) -> Result<(Schema, Vec<DataRow>)> {
+    let View {
+        view_name,
+        columns,
+        query,
+        ..
+    } = view;
+
+    let query = match plan(storage, Statement::Query(query.clone())).await? {
+        Statement::Query(query) => query,
+        _ => return Err(AlterError::Unreachable.into()),
+    };
+    let (labels, rows) = select_with_labels(storage, &query, None).await?;
+    let rows = rows.try_collect::<Vec<_>>().await?;
+
+    let column_defs = labels
+        .map(|labels| {
+            if columns.len() > labels.len() {
+                return Err(AlterError::TooManyViewColumns(view_name.to_owned()));
+            }
+
+            let column_defs = columns
+                .iter()
+                .chain(labels.iter().skip(columns.len()))
+                .enumerate()
+                .map(|(i, name)| {
+                    let data_type = rows
+                        .iter()
+                        .find_map(|row| match row {
+                            Row::Vec { values, .. } => values.get(i)?.get_type(),
+                            Row::Map(_) => None,
+                        })
+                        .unwrap_or(DataType::Text);
+
+                    ColumnDef {
+                        name: name.to_owned(),
+                        data_type,
+                        nullable: true,
+                        default: None,
+                        identity: None,
+                        generated: None,
+                        unique: None,
+                        check: None,
+                        comment: None,
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            Ok(column_defs)
+        })
+        .transpose()?;
+
+    if let Some(column_defs) = column_defs.as_deref() {
+        validate_column_names(column_defs)?;
+    }
+
+    let schema = Schema {
+        table_name: view_name.to_owned(),
+        column_defs,
+        indexes: Vec::new(),
+        engine: None,
+        key_constraints: Vec::new(),
+        foreign_keys: Vec::new(),
+        checks: Vec::new(),
+        comment: None,
+    };
+    let rows = rows.into_iter().map(Into::into).collect();
+
+    Ok((schema, rows))
+}
+
+async fn write_backing_table<T: GStore + GStoreMut>(
+    storage: &mut T,
+    schema: Schema,
+    rows: Vec<DataRow>,
+) -> Result<usize> {
+    let num_rows = rows.len();
+
+    storage.insert_schema(&schema).await?;
+    storage.append_data(&schema.table_name, rows).await?;
+
+    Ok(num_rows)
+}
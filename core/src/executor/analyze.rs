@@ -0,0 +1,74 @@
+use {
+    super::execute::ExecuteError,
+    crate::{
+        ast::ColumnDef,
+        data::{ColumnStatistics, Schema, TableStatistics},
+        result::Result,
+        store::{DataRow, GStore, GStoreMut},
+    },
+    futures::stream::TryStreamExt,
+};
+
+/// Collects the statistics of `table_name`, or of every table when omitted, and returns the
+/// number of tables analyzed.
+pub async fn analyze<T: GStore + GStoreMut>(
+    storage: &mut T,
+    table_name: Option<&str>,
+) -> Result<usize> {
+    let schemas = match table_name {
+        Some(table_name) => {
+            let schema = storage
+                .fetch_schema(table_name)
+                .await?
+                .ok_or_else(|| ExecuteError::TableNotFound(table_name.to_owned()))?;
+
+            vec![schema]
+        }
+        None => storage.fetch_all_schemas().await?,
+    };
+
+    let num_tables = schemas.len();
+    for schema in schemas {
+        let statistics = collect_statistics(storage, schema).await?;
+
+        storage.insert_statistics(statistics).await?;
+    }
+
+    Ok(num_tables)
+}
+
+/// Scans the rows of the table of `schema`, schemaless tables only get their row count.
+async fn collect_statistics<T: GStore>(storage: &T, schema: Schema) -> Result<TableStatistics> {
+    let Schema {
+        table_name,
+        column_defs,
+        ..
+    } = schema;
+
+    let rows = storage
+        .scan_data(&table_name)
+        .await?
+        .map_ok(|(_, row)| row)
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    let columns = column_defs
+        .unwrap_or_default()
+        .into_iter()
+        .enumerate()
+        .map(|(i, ColumnDef { name, .. })| {
+            let values = rows.iter().filter_map(|row| match row {
+                DataRow::Vec(values) => values.get(i),
+                DataRow::Map(_) => None,
+            });
+
+            ColumnStatistics::new(name, values)
+        })
+        .collect();
+
+    Ok(TableStatistics {
+        table_name,
+        row_count: rows.len(),
+        columns,
+    })
+}
@@ -0,0 +1,602 @@
+use {
+    super::{
+        Referencing,
+        context::RowContext,
+        delete::DeleteError,
+        evaluate::evaluate_stateless,
+        fetch::{fetch, fetch_columns},
+        insert::fill_generated,
+        trigger::Triggers,
+        update::UpdateError,
+        validate::{ColumnValidation, validate_check, validate_unique},
+    },
+    crate::{
+        ast::{ColumnDef, Expr, ForeignKey, ReferentialAction, TriggerEvent, TriggerTiming},
+        data::{Key, Row, Schema, Value},
+        result::Result,
+        store::{GStore, GStoreMut},
+    },
+    async_recursion::async_recursion,
+    futures::stream::TryStreamExt,
+    std::{
+        collections::{BTreeMap, HashSet},
+        sync::Arc,
+    },
+};
+
+/// Changes made by the `ON DELETE` and `ON UPDATE` actions of the foreign keys, collected
+/// before any of them is applied so that a failing action leaves every table untouched.
+#[derive(Default)]
+pub struct Cascade {
+    /// Rows scheduled for deletion, including the rows of the `DELETE` statement itself
+    deleted: HashSet<(String, Key)>,
+    deletes: Vec<(String, Vec<(Key, Row)>)>,
+    /// Stored row and new values of each row scheduled for update
+    updates: BTreeMap<String, BTreeMap<Key, (Row, Vec<Value>)>>,
+}
+
+impl Cascade {
+    /// Plans the changes made by deleting `rows` of `table_name`.
+    pub async fn delete<T: GStore>(
+        storage: &T,
+        table_name: &str,
+        rows: &[(Key, Row)],
+    ) -> Result<Self> {
+        let mut cascade = Self {
+            deleted: rows
+                .iter()
+                .map(|(key, _)| (table_name.to_owned(), key.clone()))
+                .collect(),
+            ..Self::default()
+        };
+        cascade.plan_delete(storage, table_name, rows).await?;
+
+        Ok(cascade)
+    }
+
+    /// Plans the changes made by updating `rows` of `table_name`, the key, the stored row and
+    /// the new row of each updated row. Returns the plan along with `rows`, whose new rows
+    /// the actions of self-referencing foreign keys may change further.
+    pub async fn update<T: GStore>(
+        storage: &T,
+        table_name: &str,
+        columns: Arc<[String]>,
+        rows: Vec<(Key, Row, Row)>,
+    ) -> Result<(Self, Vec<(Key, Row, Row)>)> {
+        let mut cascade = Self::default();
+        let mut keys = Vec::with_capacity(rows.len());
+        let mut changed = Vec::with_capacity(rows.len());
+        let updates = cascade.updates.entry(table_name.to_owned()).or_default();
+
+        for (key, old_row, new_row) in rows {
+            updates.insert(
+                key.clone(),
+                (old_row.clone(), new_row.clone().try_into_vec()?),
+            );
+            keys.push(key);
+            changed.push((old_row, new_row));
+        }
+
+        cascade.plan_update(storage, table_name, &changed).await?;
+
+        let updates = cascade.updates.entry(table_name.to_owned()).or_default();
+        let rows = keys
+            .into_iter()
+            .filter_map(|key| {
+                let (old_row, values) = updates.remove(&key)?;
+                let new_row = Row::Vec {
+                    columns: Arc::clone(&columns),
+                    values,
+                };
+
+                Some((key, old_row, new_row))
+            })
+            .collect();
+
+        Ok((cascade, rows))
+    }
+
+    /// Plans the `ON DELETE` action of every foreign key referencing `table_name` for `rows`,
+    /// which are about to be deleted. Cascaded deletes recurse into the tables referencing
+    /// the deleted rows, and rows already scheduled for deletion are skipped so that cyclic
+    /// references stop.
+    #[async_recursion]
+    async fn plan_delete<T>(
+        &mut self,
+        storage: &T,
+        table_name: &str,
+        rows: &[(Key, Row)],
+    ) -> Result<()>
+    where
+        T: GStore,
+    {
+        for Referencing {
+            table_name: referencing_table_name,
+            foreign_key,
+        } in fetch_referencings(storage, table_name).await?
+        {
+            let ForeignKey {
+                referencing_column_name,
+                referenced_column_name,
+                on_delete,
+                ..
+            } = &foreign_key;
+
+            let values = rows
+                .iter()
+                .map(|(_, row)| {
+                    row.get_value(referenced_column_name)
+                        .ok_or_else(|| DeleteError::ValueNotFound(referenced_column_name.clone()))
+                        .cloned()
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let referencing_rows = fetch_rows_in(
+                storage,
+                &referencing_table_name,
+                referencing_column_name,
+                values,
+            )
+            .await?
+            .into_iter()
+            .filter(|(key, _)| {
+                !self
+                    .deleted
+                    .contains(&(referencing_table_name.clone(), key.clone()))
+            })
+            .collect::<Vec<_>>();
+
+            if referencing_rows.is_empty() {
+                continue;
+            }
+
+            match on_delete {
+                ReferentialAction::NoAction | ReferentialAction::Restrict => {
+                    return Err(DeleteError::ReferencingColumnExists(format!(
+                        "{referencing_table_name}.{referencing_column_name}"
+                    ))
+                    .into());
+                }
+                ReferentialAction::Cascade => {
+                    self.deleted.extend(
+                        referencing_rows
+                            .iter()
+                            .map(|(key, _)| (referencing_table_name.clone(), key.clone())),
+                    );
+                    self.plan_delete(storage, &referencing_table_name, &referencing_rows)
+                        .await?;
+
+                    self.deletes
+                        .push((referencing_table_name, referencing_rows));
+                }
+                ReferentialAction::SetNull | ReferentialAction::SetDefault => {
+                    let schema = fetch_schema(storage, &referencing_table_name).await?;
+                    let value = self
+                        .referencing_value(storage, &schema, &foreign_key, on_delete)
+                        .await?;
+                    let rows = referencing_rows
+                        .into_iter()
+                        .map(|(key, row)| (key, row, value.clone()))
+                        .collect();
+
+                    self.set_referencing_values(storage, schema, referencing_column_name, rows)
+                        .await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Plans the `ON UPDATE` action of every foreign key referencing `table_name` for `rows`,
+    /// pairs of the stored and the new row of each updated row. Referencing rows are looked
+    /// up by the stored value, and rows already holding the new value are skipped so that
+    /// cyclic references stop.
+    #[async_recursion]
+    async fn plan_update<T>(
+        &mut self,
+        storage: &T,
+        table_name: &str,
+        rows: &[(Row, Row)],
+    ) -> Result<()>
+    where
+        T: GStore,
+    {
+        for Referencing {
+            table_name: referencing_table_name,
+            foreign_key,
+        } in fetch_referencings(storage, table_name).await?
+        {
+            let ForeignKey {
+                referencing_column_name,
+                referenced_column_name,
+                on_update,
+                ..
+            } = &foreign_key;
+
+            let changes = rows
+                .iter()
+                .filter_map(|(old_row, new_row)| {
+                    let old = old_row.get_value(referenced_column_name)?;
+                    let new = new_row.get_value(referenced_column_name)?;
+
+                    (!old.is_null() && old != new).then(|| (old.clone(), new.clone()))
+                })
+                .collect::<Vec<_>>();
+
+            if changes.is_empty() {
+                continue;
+            }
+
+            let schema = fetch_schema(storage, &referencing_table_name).await?;
+            let index = column_index(&schema, referencing_column_name)?;
+            let values = changes.iter().map(|(old, _)| old.clone()).collect();
+            let referencing_rows = fetch_rows_in(
+                storage,
+                &referencing_table_name,
+                referencing_column_name,
+                values,
+            )
+            .await?
+            .into_iter()
+            .filter(|(key, _)| {
+                !self
+                    .deleted
+                    .contains(&(referencing_table_name.clone(), key.clone()))
+            })
+            .filter_map(|(key, row)| {
+                let pending = self
+                    .updates
+                    .get(&referencing_table_name)
+                    .and_then(|rows| rows.get(&key))
+                    .and_then(|(_, values)| values.get(index));
+                let value = pending.or_else(|| row.get_value(referencing_column_name))?;
+                let (_, new) = changes.iter().find(|(old, _)| old == value)?;
+                let new = new.clone();
+
+                Some((key, row, new))
+            })
+            .collect::<Vec<_>>();
+
+            if referencing_rows.is_empty() {
+                continue;
+            }
+
+            match on_update {
+                ReferentialAction::NoAction | ReferentialAction::Restrict => {
+                    return Err(UpdateError::ReferencingColumnExists(format!(
+                        "{referencing_table_name}.{referencing_column_name}"
+                    ))
+                    .into());
+                }
+                ReferentialAction::Cascade => {
+                    self.set_referencing_values(
+                        storage,
+                        schema,
+                        referencing_column_name,
+                        referencing_rows,
+                    )
+                    .await?;
+                }
+                ReferentialAction::SetNull | ReferentialAction::SetDefault => {
+                    let value = self
+                        .referencing_value(storage, &schema, &foreign_key, on_update)
+                        .await?;
+                    let rows = referencing_rows
+                        .into_iter()
+                        .map(|(key, row, _)| (key, row, value.clone()))
+                        .collect();
+
+                    self.set_referencing_values(storage, schema, referencing_column_name, rows)
+                        .await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Value a `SET NULL` or `SET DEFAULT` action writes into the referencing column, which
+    /// must still be referenced once the planned changes are applied.
+    async fn referencing_value<T: GStore>(
+        &self,
+        storage: &T,
+        schema: &Schema,
+        foreign_key: &ForeignKey,
+        action: &ReferentialAction,
+    ) -> Result<Value> {
+        let ForeignKey {
+            referencing_column_name,
+            referenced_table_name,
+            referenced_column_name,
+            ..
+        } = foreign_key;
+
+        let column_defs = schema.column_defs.as_deref().unwrap_or_default();
+        let ColumnDef {
+            data_type,
+            nullable,
+            default,
+            ..
+        } = &column_defs[column_index(schema, referencing_column_name)?];
+
+        let value = match (action, default) {
+            (ReferentialAction::SetDefault, Some(expr)) => evaluate_stateless(None, expr)
+                .await?
+                .try_into_value(data_type, *nullable)?,
+            _ => Value::Null,
+        };
+
+        if value.is_null()
+            || self
+                .is_referenced(
+                    storage,
+                    referenced_table_name,
+                    referenced_column_name,
+                    &value,
+                )
+                .await?
+        {
+            return Ok(value);
+        }
+
+        Err(UpdateError::CannotFindReferencedValue {
+            table_name: referenced_table_name.to_owned(),
+            column_name: referenced_column_name.to_owned(),
+            referenced_value: String::from(value),
+        }
+        .into())
+    }
+
+    /// Returns `true` if a row of `table_name` holds `value` in `column_name` once the planned
+    /// changes are applied.
+    async fn is_referenced<T: GStore>(
+        &self,
+        storage: &T,
+        table_name: &str,
+        column_name: &str,
+        value: &Value,
+    ) -> Result<bool> {
+        let updates = self.updates.get(table_name);
+        let is_kept = |key: &Key| !self.deleted.contains(&(table_name.to_owned(), key.clone()));
+        let is_updated = |key: &Key| updates.is_some_and(|rows| rows.contains_key(key));
+
+        let stored = fetch_rows_in(storage, table_name, column_name, vec![value.clone()])
+            .await?
+            .into_iter()
+            .any(|(key, _)| is_kept(&key) && !is_updated(&key));
+        let updated = updates.into_iter().flatten().any(|(key, (row, values))| {
+            let new_value = match row {
+                Row::Vec { columns, .. } => columns
+                    .iter()
+                    .position(|column| column == column_name)
+                    .and_then(|index| values.get(index)),
+                Row::Map(_) => None,
+            };
+
+            is_kept(key) && new_value == Some(value)
+        });
+
+        Ok(stored || updated)
+    }
+
+    /// Schedules writing the value paired with each of `rows` into `column_name`, merged with
+    /// the changes already planned for the row, and plans the `ON UPDATE` actions of the
+    /// foreign keys referencing the changed rows in turn.
+    async fn set_referencing_values<T: GStore>(
+        &mut self,
+        storage: &T,
+        schema: Schema,
+        column_name: &str,
+        rows: Vec<(Key, Row, Value)>,
+    ) -> Result<()> {
+        let index = column_index(&schema, column_name)?;
+
+        if schema
+            .primary_key_indexes()
+            .is_some_and(|indexes| indexes.contains(&index))
+        {
+            return Err(UpdateError::UpdateOnPrimaryKeyNotSupported(column_name.to_owned()).into());
+        }
+
+        let Schema {
+            table_name,
+            column_defs,
+            indexes,
+            key_constraints,
+            checks,
+            ..
+        } = schema;
+        let column_defs = column_defs.unwrap_or_default();
+        let columns: Arc<[String]> = column_defs
+            .iter()
+            .map(|column_def| column_def.name.to_owned())
+            .collect();
+
+        let updates = self.updates.entry(table_name.clone()).or_default();
+        let mut planned = Vec::with_capacity(rows.len());
+        let mut changed = Vec::with_capacity(rows.len());
+
+        for (key, row, value) in rows {
+            value.validate_null(column_defs[index].nullable)?;
+
+            let (stored, values) = match updates.remove(&key) {
+                Some(update) => update,
+                None => {
+                    let values = row.clone().try_into_vec()?;
+
+                    (row, values)
+                }
+            };
+            let mut new_values = values.clone();
+            new_values[index] = value;
+            let new_values = fill_generated(&column_defs, new_values).await?;
+
+            changed.push((
+                Row::Vec {
+                    columns: Arc::clone(&columns),
+                    values,
+                },
+                Row::Vec {
+                    columns: Arc::clone(&columns),
+                    values: new_values.clone(),
+                },
+            ));
+            planned.push((key, (stored, new_values)));
+        }
+
+        let values = planned.iter().map(|(_, (_, values))| values.as_slice());
+        validate_check(&table_name, &column_defs, &checks, values.clone()).await?;
+        validate_unique(
+            storage,
+            &table_name,
+            ColumnValidation::SpecifiedColumns(&column_defs, vec![column_name.to_owned()]),
+            &key_constraints,
+            &indexes,
+            values,
+        )
+        .await?;
+
+        updates.extend(planned);
+        self.plan_update(storage, &table_name, &changed).await
+    }
+
+    /// Writes the planned changes, updating rows before deleting so that a row both updated
+    /// and deleted by different foreign keys ends up deleted. The `UPDATE` and `DELETE`
+    /// triggers of each table fire for its changed rows, within `context` of the statement.
+    pub async fn apply<T: GStore + GStoreMut>(
+        self,
+        storage: &mut T,
+        context: Option<&Arc<RowContext<'_>>>,
+    ) -> Result<()> {
+        for (table_name, rows) in self.updates {
+            if rows.is_empty() {
+                continue;
+            }
+
+            let triggers = Triggers::fetch(storage, &table_name, TriggerEvent::Update).await?;
+            let fired_rows = match triggers.is_empty() {
+                true => Vec::new(),
+                false => {
+                    let columns: Arc<[String]> = fetch_columns(storage, &table_name)
+                        .await?
+                        .map(Arc::from)
+                        .unwrap_or_default();
+
+                    rows.values()
+                        .map(|(old_row, values)| {
+                            let new_row = Row::Vec {
+                                columns: Arc::clone(&columns),
+                                values: values.clone(),
+                            };
+
+                            (old_row.clone(), new_row)
+                        })
+                        .collect()
+                }
+            };
+            let fired_rows = fired_rows
+                .iter()
+                .map(|(old_row, new_row)| (Some(old_row), Some(new_row)))
+                .collect::<Vec<_>>();
+            let rows = rows
+                .into_iter()
+                .map(|(key, (_, values))| (key, values.into()))
+                .collect();
+
+            triggers
+                .fire(storage, TriggerTiming::Before, &fired_rows, context)
+                .await?;
+            storage.insert_data(&table_name, rows).await?;
+            triggers
+                .fire(storage, TriggerTiming::After, &fired_rows, context)
+                .await?;
+        }
+
+        for (table_name, rows) in self.deletes {
+            let triggers = Triggers::fetch(storage, &table_name, TriggerEvent::Delete).await?;
+            let fired_rows = rows
+                .iter()
+                .map(|(_, row)| (Some(row), None))
+                .collect::<Vec<_>>();
+
+            triggers
+                .fire(storage, TriggerTiming::Before, &fired_rows, context)
+                .await?;
+
+            let keys = rows.iter().map(|(key, _)| key.clone()).collect();
+            storage.delete_data(&table_name, keys).await?;
+            triggers
+                .fire(storage, TriggerTiming::After, &fired_rows, context)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+async fn fetch_schema<T: GStore>(storage: &T, table_name: &str) -> Result<Schema> {
+    storage
+        .fetch_schema(table_name)
+        .await?
+        .ok_or_else(|| DeleteError::TableNotFound(table_name.to_owned()).into())
+}
+
+fn column_index(schema: &Schema, column_name: &str) -> Result<usize> {
+    schema
+        .column_defs
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .position(|column_def| column_def.name == column_name)
+        .ok_or_else(|| DeleteError::ReferencingColumnNotFound(column_name.to_owned()).into())
+}
+
+/// Foreign keys referencing `table_name`, including the ones of `table_name` itself, which
+/// `Store::fetch_referencings` leaves out
+async fn fetch_referencings<T: GStore>(storage: &T, table_name: &str) -> Result<Vec<Referencing>> {
+    let referencings = storage
+        .fetch_all_schemas()
+        .await?
+        .into_iter()
+        .flat_map(|schema| {
+            let Schema {
+                table_name: referencing_table_name,
+                foreign_keys,
+                ..
+            } = schema;
+
+            foreign_keys.into_iter().filter_map(move |foreign_key| {
+                (foreign_key.referenced_table_name == table_name).then(|| Referencing {
+                    table_name: referencing_table_name.clone(),
+                    foreign_key,
+                })
+            })
+        })
+        .collect();
+
+    Ok(referencings)
+}
+
+/// Rows of `table_name` whose `column_name` is one of `values`, keyed as the storage keys them
+pub async fn fetch_rows_in<T: GStore>(
+    storage: &T,
+    table_name: &str,
+    column_name: &str,
+    values: Vec<Value>,
+) -> Result<Vec<(Key, Row)>> {
+    let list = values
+        .into_iter()
+        .map(Expr::try_from)
+        .collect::<Result<Vec<_>>>()?;
+    let expr = Expr::InList {
+        expr: Box::new(Expr::Identifier(column_name.to_owned())),
+        list,
+        negated: false,
+    };
+    let columns = fetch_columns(storage, table_name).await?.map(Arc::from);
+
+    fetch(storage, table_name, columns, Some(&expr), None)
+        .await?
+        .try_collect()
+        .await
+}
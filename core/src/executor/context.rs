@@ -1,4 +1,7 @@
 mod aggregate_context;
 mod row_context;
 
-pub use {aggregate_context::AggregateContext, row_context::RowContext};
+pub use {
+    aggregate_context::AggregateContext,
+    row_context::{CteTable, RowContext},
+};
@@ -1,8 +1,18 @@
 use {
-    crate::data::{Row, Value},
+    crate::{
+        data::{Row, Value},
+        executor::guard::Guard,
+    },
     std::{borrow::Cow, collections::BTreeMap, fmt::Debug, sync::Arc},
 };
 
+/// Materialized result of a common table expression
+#[derive(Debug)]
+pub struct CteTable {
+    pub labels: Option<Vec<String>>,
+    pub rows: Vec<Row>,
+}
+
 #[derive(Debug)]
 pub enum RowContext<'a> {
     Data {
@@ -19,6 +29,33 @@ pub enum RowContext<'a> {
         left: Arc<RowContext<'a>>,
         right: Arc<RowContext<'a>>,
     },
+    Cte {
+        name: &'a str,
+        table: Arc<CteTable>,
+        next: Option<Arc<RowContext<'a>>>,
+    },
+    /// Columns merged by `JOIN ... USING` or `NATURAL JOIN`, which hide the joined columns
+    /// of the same name from unqualified lookups.
+    Using {
+        row: Row,
+        next: Arc<RowContext<'a>>,
+    },
+    /// Values bound to the parameters of a prepared statement, at the root of every context
+    /// the statement evaluates.
+    Params(&'a [Value]),
+    /// Rows a trigger fires for, referred to as `NEW` and `OLD` by the statements of its body
+    /// and at the root of every context they evaluate.
+    Trigger {
+        new: Option<&'a Row>,
+        old: Option<&'a Row>,
+        /// Number of triggers fired so far to reach this one
+        depth: usize,
+    },
+    /// Limits of the running statement, wrapping the root of its contexts.
+    Guarded {
+        guard: Arc<Guard>,
+        next: Option<Arc<RowContext<'a>>>,
+    },
 }
 
 impl<'a> RowContext<'a> {
@@ -34,6 +71,118 @@ impl<'a> RowContext<'a> {
         Self::Bridge { left, right }
     }
 
+    pub fn cte(name: &'a str, table: CteTable, next: Option<Arc<RowContext<'a>>>) -> Self {
+        Self::Cte {
+            name,
+            table: Arc::new(table),
+            next,
+        }
+    }
+
+    pub fn using(row: Row, next: Arc<RowContext<'a>>) -> Self {
+        Self::Using { row, next }
+    }
+
+    pub fn params(params: &'a [Value]) -> Self {
+        Self::Params(params)
+    }
+
+    pub fn trigger(new: Option<&'a Row>, old: Option<&'a Row>, depth: usize) -> Self {
+        Self::Trigger { new, old, depth }
+    }
+
+    pub fn guarded(guard: Arc<Guard>, next: Option<Arc<RowContext<'a>>>) -> Self {
+        Self::Guarded { guard, next }
+    }
+
+    /// Returns the root of the context, either the bound parameters or the rows of a trigger.
+    fn get_root(&self) -> Option<&Self> {
+        match self {
+            Self::Params(_) | Self::Trigger { .. } => Some(self),
+            Self::Data { next, .. } | Self::Cte { next, .. } | Self::Guarded { next, .. } => {
+                next.as_ref().and_then(|next| next.get_root())
+            }
+            Self::Bridge { left, right } => left.get_root().or_else(|| right.get_root()),
+            Self::Using { next, .. } => next.get_root(),
+            Self::RefVecData { .. } | Self::RefMapData(_) => None,
+        }
+    }
+
+    pub fn get_params(&self) -> Option<&'a [Value]> {
+        match self.get_root()? {
+            Self::Params(params) => Some(*params),
+            _ => None,
+        }
+    }
+
+    /// Depth of the trigger whose body is being run, `None` outside of triggers.
+    pub fn get_trigger_depth(&self) -> Option<usize> {
+        match self.get_root()? {
+            Self::Trigger { depth, .. } => Some(*depth),
+            _ => None,
+        }
+    }
+
+    /// Limits of the running statement, `None` when it runs without any.
+    pub fn get_guard(&self) -> Option<Arc<Guard>> {
+        match self {
+            Self::Guarded { guard, .. } => Some(Arc::clone(guard)),
+            Self::Data { next, .. } | Self::Cte { next, .. } => {
+                next.as_ref().and_then(|next| next.get_guard())
+            }
+            Self::Bridge { left, right } => left.get_guard().or_else(|| right.get_guard()),
+            Self::Using { next, .. } => next.get_guard(),
+            Self::RefVecData { .. }
+            | Self::RefMapData(_)
+            | Self::Params(_)
+            | Self::Trigger { .. } => None,
+        }
+    }
+
+    /// Context holding only the root of the statement, the bound parameters or the rows of
+    /// the firing trigger, for evaluating stateless expressions.
+    pub fn root_context(&self) -> Option<RowContext<'a>> {
+        match self.get_root()? {
+            Self::Params(params) => Some(Self::Params(params)),
+            Self::Trigger { new, old, depth } => Some(Self::Trigger {
+                new: *new,
+                old: *old,
+                depth: *depth,
+            }),
+            _ => None,
+        }
+    }
+
+    fn get_trigger_row(&self, alias: &str) -> Option<&'a Row> {
+        match self {
+            Self::Trigger { new, .. } if alias.eq_ignore_ascii_case("NEW") => *new,
+            Self::Trigger { old, .. } if alias.eq_ignore_ascii_case("OLD") => *old,
+            _ => None,
+        }
+    }
+
+    /// Returns the value bound to the 1-based parameter `index`.
+    pub fn get_param(&self, index: usize) -> Option<&'a Value> {
+        let index = index.checked_sub(1)?;
+
+        self.get_params().and_then(|params| params.get(index))
+    }
+
+    pub fn get_cte(&self, target: &str) -> Option<Arc<CteTable>> {
+        match self {
+            Self::Cte { name, table, .. } if *name == target => Some(Arc::clone(table)),
+            Self::Data { next, .. } | Self::Cte { next, .. } | Self::Guarded { next, .. } => {
+                next.as_ref().and_then(|next| next.get_cte(target))
+            }
+            Self::Bridge { left, right } => left.get_cte(target).or_else(|| right.get_cte(target)),
+            Self::Using { next, .. } => next.get_cte(target),
+            Self::RefVecData { .. }
+            | Self::RefMapData(_)
+            | Self::Params(_)
+            | Self::Trigger { .. } => None,
+        }
+    }
+
     pub fn get_value(&'a self, target: &str) -> Option<&'a Value> {
         match self {
             Self::Data {
@@ -52,6 +201,9 @@ impl<'a> RowContext<'a> {
                 .position(|column| column == target)
                 .and_then(|index| values.get(index)),
             Self::RefMapData(values) => values.get(target),
+            Self::Cte { next, .. } => next.as_ref().and_then(|next| next.get_value(target)),
+            Self::Using { row, next } => row.get_value(target).or_else(|| next.get_value(target)),
+            Self::Params(_) | Self::Trigger { .. } | Self::Guarded { .. } => None,
         }
     }
 
@@ -78,6 +230,13 @@ impl<'a> RowContext<'a> {
             Self::Bridge { left, right } => left
                 .get_alias_value(target_table_alias, target)
                 .or_else(|| right.get_alias_value(target_table_alias, target)),
+            Self::Cte { next, .. } | Self::Guarded { next, .. } => next
+                .as_ref()
+                .and_then(|next| next.get_alias_value(target_table_alias, target)),
+            Self::Using { next, .. } => next.get_alias_value(target_table_alias, target),
+            Self::Trigger { .. } => self
+                .get_trigger_row(target_table_alias)
+                .and_then(|row| row.get_value(target)),
             _ => None,
         }
     }
@@ -94,6 +253,13 @@ impl<'a> RowContext<'a> {
             Self::Bridge { left, right } => left
                 .get_alias_entries(alias)
                 .or_else(|| right.get_alias_entries(alias)),
+            Self::Cte { next, .. } | Self::Guarded { next, .. } => {
+                next.as_ref().and_then(|next| next.get_alias_entries(alias))
+            }
+            Self::Using { next, .. } => next.get_alias_entries(alias),
+            Self::Trigger { .. } => self
+                .get_trigger_row(alias)
+                .map(|row| row.iter().map(|(k, v)| (k, v.clone())).collect()),
             _ => None,
         }
     }
@@ -115,6 +281,18 @@ impl<'a> RowContext<'a> {
             Self::Bridge { left, right } => {
                 [left.get_all_entries(), right.get_all_entries()].concat()
             }
+            Self::Cte {
+                next: Some(next), ..
+            } => next.get_all_entries(),
+            Self::Using { row, next } => row
+                .iter()
+                .map(|(k, v)| (k, v.clone()))
+                .chain(
+                    next.get_all_entries()
+                        .into_iter()
+                        .filter(|(k, _)| row.get_value(k).is_none()),
+                )
+                .collect(),
             _ => vec![],
         }
     }
@@ -1,14 +1,18 @@
 use {
     super::{
-        Payload, Referencing,
+        Payload,
+        cascade::Cascade,
+        context::RowContext,
         fetch::{fetch, fetch_columns},
+        returning::returning,
+        trigger::Triggers,
     },
     crate::{
-        ast::{BinaryOperator, Expr, ForeignKey, ReferentialAction},
-        result::{Error, Result},
+        ast::{Expr, SelectItem, TriggerEvent, TriggerTiming},
+        result::Result,
         store::{GStore, GStoreMut},
     },
-    futures::stream::{StreamExt, TryStreamExt},
+    futures::stream::TryStreamExt,
     serde::Serialize,
     std::sync::Arc,
     thiserror::Error as ThisError,
@@ -21,64 +25,80 @@ pub enum DeleteError {
 
     #[error("Value not found on column: {0}")]
     ValueNotFound(String),
+
+    #[error("table not found: {0}")]
+    TableNotFound(String),
+
+    #[error("referencing column not found: {0}")]
+    ReferencingColumnNotFound(String),
 }
 
 pub async fn delete<T: GStore + GStoreMut>(
     storage: &mut T,
     table_name: &str,
     selection: &Option<Expr>,
+    returning_items: &[SelectItem],
+    filter_context: Option<Arc<RowContext<'_>>>,
 ) -> Result<Payload> {
-    let columns = fetch_columns(storage, table_name).await?.map(Arc::from);
-    let referencings = storage.fetch_referencings(table_name).await?;
-    let keys = fetch(storage, table_name, columns, selection.as_ref())
-        .await?
-        .into_stream()
-        .then(|item| async {
-            let (key, row) = item?;
+    let columns: Option<Arc<[String]>> = fetch_columns(storage, table_name).await?.map(Arc::from);
+    let rows = fetch(
+        storage,
+        table_name,
+        columns.as_ref().map(Arc::clone),
+        selection.as_ref(),
+        filter_context.as_ref().map(Arc::clone),
+    )
+    .await?
+    .try_collect::<Vec<_>>()
+    .await?;
 
-            for Referencing {
-                table_name: referencing_table_name,
-                foreign_key:
-                    ForeignKey {
-                        referencing_column_name,
-                        referenced_column_name,
-                        on_delete,
-                        ..
-                    },
-            } in &referencings
-            {
-                let value = row
-                    .get_value(referenced_column_name)
-                    .ok_or(DeleteError::ValueNotFound(referenced_column_name.clone()))?
-                    .clone();
+    let cascade = Cascade::delete(storage, table_name, &rows).await?;
 
-                let expr = &Expr::BinaryOp {
-                    left: Box::new(Expr::Identifier(referencing_column_name.clone())),
-                    op: BinaryOperator::Eq,
-                    right: Box::new(Expr::try_from(value)?),
-                };
-
-                let columns = Some(Arc::from(Vec::new()));
-                let referencing_rows =
-                    fetch(storage, referencing_table_name, columns, Some(expr)).await?;
+    let num_keys = rows.len();
+    let (keys, rows): (Vec<_>, Vec<_>) = rows.into_iter().unzip();
+    let triggers = Triggers::fetch(storage, table_name, TriggerEvent::Delete).await?;
+    let fired_rows = match triggers.is_empty() {
+        true => Vec::new(),
+        false => rows.clone(),
+    };
+    let fired_rows = fired_rows
+        .iter()
+        .map(|row| (Some(row), None))
+        .collect::<Vec<_>>();
+    triggers
+        .fire(
+            storage,
+            TriggerTiming::Before,
+            &fired_rows,
+            filter_context.as_ref(),
+        )
+        .await?;
 
-                let referencing_row_exists = Box::pin(referencing_rows).next().await.is_some();
-                if referencing_row_exists && on_delete == &ReferentialAction::NoAction {
-                    return Err(DeleteError::ReferencingColumnExists(format!(
-                        "{referencing_table_name}.{referencing_column_name}"
-                    ))
-                    .into());
-                }
-            }
+    let payload = match returning_items.is_empty() {
+        true => Payload::Delete(num_keys),
+        false => {
+            returning(
+                storage,
+                table_name,
+                columns.as_deref(),
+                returning_items,
+                rows,
+                filter_context.as_ref().map(Arc::clone),
+            )
+            .await?
+        }
+    };
 
-            Ok::<_, Error>(key)
-        })
-        .try_collect::<Vec<_>>()
+    cascade.apply(storage, filter_context.as_ref()).await?;
+    storage.delete_data(table_name, keys).await?;
+    triggers
+        .fire(
+            storage,
+            TriggerTiming::After,
+            &fired_rows,
+            filter_context.as_ref(),
+        )
         .await?;
-    let num_keys = keys.len();
 
-    storage
-        .delete_data(table_name, keys)
-        .await
-        .map(|_| Payload::Delete(num_keys))
+    Ok(payload)
 }
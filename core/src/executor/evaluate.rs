@@ -8,8 +8,9 @@ use {
     super::{context::RowContext, select::select},
     crate::{
         ast::{Aggregate, Expr, Function},
-        data::{CustomFunction, Interval, Literal, Row, Value},
+        data::{CustomFunction, Interval, Literal, Row, SequenceError, Value},
         mock::MockStorage,
+        plan::PlanExpr,
         result::{Error, Result},
         store::GStore,
     },
@@ -49,6 +50,29 @@ pub async fn evaluate_stateless<'a, 'b: 'a>(
     evaluate_inner(storage, context, None, expr).await
 }
 
+/// Whether `expr` calls `NEXTVAL` or `CURRVAL`, which read the storage even where the other
+/// expressions are evaluated statelessly.
+pub(crate) fn uses_sequence(expr: &Expr) -> bool {
+    if let Expr::Function(func) = expr {
+        if matches!(func.as_ref(), Function::NextVal(_) | Function::CurrVal(_)) {
+            return true;
+        }
+    }
+
+    match expr.into() {
+        PlanExpr::None
+        | PlanExpr::Identifier(_)
+        | PlanExpr::CompoundIdentifier { .. }
+        | PlanExpr::Query(_) => false,
+        PlanExpr::Expr(expr) | PlanExpr::QueryAndExpr { expr, .. } => uses_sequence(expr),
+        PlanExpr::TwoExprs(expr, expr2) => uses_sequence(expr) || uses_sequence(expr2),
+        PlanExpr::ThreeExprs(expr, expr2, expr3) => {
+            uses_sequence(expr) || uses_sequence(expr2) || uses_sequence(expr3)
+        }
+        PlanExpr::MultiExprs(exprs) => exprs.into_iter().any(uses_sequence),
+    }
+}
+
 #[async_recursion]
 async fn evaluate_inner<'a, 'b, 'c, T>(
     storage: Option<&'a T>,
@@ -83,6 +107,12 @@ where
             }
             .map(Evaluated::Value)
         }
+        Expr::Parameter(index) => context
+            .as_ref()
+            .and_then(|context| context.get_param(*index))
+            .cloned()
+            .map(Evaluated::Value)
+            .ok_or_else(|| EvaluateError::UnboundParameter(*index).into()),
         Expr::Nested(expr) => eval(expr).await,
         Expr::CompoundIdentifier { alias, ident } => {
             let context = context
@@ -589,6 +619,26 @@ async fn evaluate_function<'a, 'b: 'a, 'c: 'a, T: GStore>(
             f::unwrap(name, expr, selector)
         }
         Function::GenerateUuid() => return Ok(f::generate_uuid()),
+        Function::NextVal(expr) | Function::CurrVal(expr) => {
+            let storage = storage.ok_or_else(|| {
+                EvaluateError::UnsupportedStatelessExpr(Expr::Function(Box::new(func.clone())))
+            })?;
+            let sequence_name = match eval(expr).await?.try_into()? {
+                Value::Str(sequence_name) => sequence_name,
+                _ => return Err(EvaluateError::FunctionRequiresStringValue(name).into()),
+            };
+            let value = match func {
+                Function::NextVal(_) => storage.next_sequence_value(&sequence_name).await?,
+                _ => storage
+                    .fetch_sequence(&sequence_name)
+                    .await?
+                    .map(|sequence| sequence.current())
+                    .transpose()?,
+            }
+            .ok_or(SequenceError::NotFound(sequence_name))?;
+
+            return Ok(Evaluated::Value(Value::I64(value)));
+        }
         Function::Greatest(exprs) => {
             let exprs = stream::iter(exprs).then(eval).try_collect().await?;
             return f::greatest(name, exprs);
@@ -86,6 +86,9 @@ pub enum EvaluateError {
     #[error("context is required for identifier evaluation: {}", .0.to_sql())]
     ContextRequiredForIdentEvaluation(Expr),
 
+    #[error("no value bound to parameter: ${0}")]
+    UnboundParameter(usize),
+
     #[error("unreachable empty aggregate value: {0:?}")]
     UnreachableEmptyAggregateValue(Aggregate),
 
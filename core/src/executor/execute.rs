@@ -1,22 +1,25 @@
 use {
     super::{
         alter::{
-            CreateTableOptions, alter_table, create_index, create_table, delete_function,
-            drop_table, insert_function,
+            CreateTableOptions, CreateTriggerOptions, alter_table, create_index, create_sequence,
+            create_table, create_trigger, create_view, delete_function, drop_sequence, drop_table,
+            drop_trigger, drop_view, insert_function, refresh_materialized_view,
         },
+        analyze::analyze,
+        context::RowContext,
         delete::delete,
-        fetch::fetch,
+        explain::{PlanNode, explain},
+        guard::{ExecuteOptions, Guard, watch_produced},
         insert::insert,
         select::{select, select_with_labels},
-        update::Update,
-        validate::{ColumnValidation, validate_unique},
+        update::update,
     },
     crate::{
         ast::{
             AstLiteral, BinaryOperator, DataType, Dictionary, Expr, Query, SelectItem, SetExpr,
             Statement, TableAlias, TableFactor, TableWithJoins, Variable,
         },
-        data::{Key, Row, Schema, Value},
+        data::{Schema, Value},
         result::Result,
         store::{GStore, GStoreMut},
     },
@@ -35,6 +38,15 @@ use {
 pub enum ExecuteError {
     #[error("table not found: {0}")]
     TableNotFound(String),
+
+    #[error("prepared statement must be a single statement, found {0}")]
+    PrepareRequiresSingleStatement(usize),
+
+    #[error("streamed statement must be a single statement, found {0}")]
+    StreamRequiresSingleStatement(usize),
+
+    #[error("prepared statement takes {expected} parameters but {found} were bound")]
+    ParameterCountMismatch { expected: usize, found: usize },
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
@@ -51,13 +63,23 @@ pub enum Payload {
     Update(usize),
     DropTable(usize),
     DropFunction,
+    DropTrigger,
+    DropView(usize),
+    CreateSequence,
+    DropSequence(usize),
+    RefreshMaterializedView(usize),
+    Analyze(usize),
     AlterTable,
     CreateIndex,
     DropIndex,
     StartTransaction,
     Commit,
     Rollback,
+    Savepoint,
+    RollbackToSavepoint,
+    ReleaseSavepoint,
     ShowVariable(PayloadVariable),
+    Explain(PlanNode),
 }
 
 impl Payload {
@@ -103,23 +125,72 @@ pub enum PayloadVariable {
 pub async fn execute<T: GStore + GStoreMut>(
     storage: &mut T,
     statement: &Statement,
+) -> Result<Payload> {
+    execute_with_params(storage, statement, &[]).await
+}
+
+/// Executes `statement` with `params` bound to its `$1`, `$2`, ... parameters.
+pub async fn execute_with_params<T: GStore + GStoreMut>(
+    storage: &mut T,
+    statement: &Statement,
+    params: &[Value],
+) -> Result<Payload> {
+    execute_with_options(storage, statement, params, &ExecuteOptions::default()).await
+}
+
+/// Executes `statement` like [`execute_with_params`], failing once it runs past the limits
+/// of `options`.
+pub async fn execute_with_options<T: GStore + GStoreMut>(
+    storage: &mut T,
+    statement: &Statement,
+    params: &[Value],
+    options: &ExecuteOptions,
 ) -> Result<Payload> {
     if matches!(
         statement,
-        Statement::StartTransaction | Statement::Rollback | Statement::Commit
+        Statement::StartTransaction
+            | Statement::Rollback
+            | Statement::Commit
+            | Statement::Savepoint(_)
+            | Statement::RollbackToSavepoint(_)
+            | Statement::ReleaseSavepoint(_)
     ) {
-        return execute_inner(storage, statement).await;
+        return execute_inner(storage, statement, None).await;
     }
 
+    let context = statement_context(params, options);
     let autocommit = storage.begin(true).await?;
-    let result = execute_inner(storage, statement).await;
+    let result = execute_inner(storage, statement, context).await;
+
+    end_autocommit(storage, autocommit, result).await
+}
+
+/// Root context of a statement, holding the bound `params` and the limits of `options`.
+pub(super) fn statement_context<'a>(
+    params: &'a [Value],
+    options: &ExecuteOptions,
+) -> Option<Arc<RowContext<'a>>> {
+    let context = (!params.is_empty()).then(|| Arc::new(RowContext::params(params)));
+
+    match Guard::new(options) {
+        Some(guard) => Some(Arc::new(RowContext::guarded(Arc::new(guard), context))),
+        None => context,
+    }
+}
 
+/// Commits the transaction `begin` started for a single statement, or rolls it back when
+/// the statement failed. Statements run in an explicit transaction leave it as it is.
+pub(super) async fn end_autocommit<T: GStore + GStoreMut, R>(
+    storage: &mut T,
+    autocommit: bool,
+    result: Result<R>,
+) -> Result<R> {
     if !autocommit {
         return result;
     }
 
     match result {
-        Ok(payload) => storage.commit().await.map(|_| payload),
+        Ok(output) => storage.commit().await.map(|_| output),
         Err(error) => {
             storage.rollback().await?;
 
@@ -128,9 +199,12 @@ pub async fn execute<T: GStore + GStoreMut>(
     }
 }
 
-async fn execute_inner<T: GStore + GStoreMut>(
+/// Executes `statement` within the current transaction. `context` holds the bound parameters,
+/// or the rows of the trigger whose body the statement belongs to.
+pub(super) async fn execute_inner<T: GStore + GStoreMut>(
     storage: &mut T,
     statement: &Statement,
+    context: Option<Arc<RowContext<'_>>>,
 ) -> Result<Payload> {
     match statement {
         //- Modification
@@ -141,7 +215,9 @@ async fn execute_inner<T: GStore + GStoreMut>(
             if_not_exists,
             source,
             engine,
+            key_constraints,
             foreign_keys,
+            checks,
             comment,
         } => {
             let options = CreateTableOptions {
@@ -150,7 +226,9 @@ async fn execute_inner<T: GStore + GStoreMut>(
                 if_not_exists: *if_not_exists,
                 source,
                 engine,
+                key_constraints,
                 foreign_keys,
+                checks,
                 comment,
             };
 
@@ -166,20 +244,79 @@ async fn execute_inner<T: GStore + GStoreMut>(
         } => drop_table(storage, names, *if_exists, *cascade)
             .await
             .map(Payload::DropTable),
+        Statement::CreateView {
+            or_replace,
+            materialized,
+            name,
+            columns,
+            query,
+        } => create_view(storage, name, columns, query, *or_replace, *materialized)
+            .await
+            .map(|_| Payload::Create),
+        Statement::DropView { if_exists, names } => drop_view(storage, names, *if_exists)
+            .await
+            .map(Payload::DropView),
+        Statement::CreateSequence {
+            if_not_exists,
+            name,
+            options,
+        } => create_sequence(storage, name, options, *if_not_exists)
+            .await
+            .map(|_| Payload::CreateSequence),
+        Statement::DropSequence { if_exists, names } => drop_sequence(storage, names, *if_exists)
+            .await
+            .map(Payload::DropSequence),
+        Statement::RefreshMaterializedView { name } => refresh_materialized_view(storage, name)
+            .await
+            .map(Payload::RefreshMaterializedView),
+        Statement::Analyze { table_name } => analyze(storage, table_name.as_deref())
+            .await
+            .map(Payload::Analyze),
         Statement::AlterTable { name, operation } => alter_table(storage, name, operation)
             .await
             .map(|_| Payload::AlterTable),
         Statement::CreateIndex {
             name,
             table_name,
-            column,
-        } => create_index(storage, table_name, name, column)
+            columns,
+            unique,
+        } => create_index(storage, table_name, name, columns, *unique)
             .await
             .map(|_| Payload::CreateIndex),
         Statement::DropIndex { name, table_name } => storage
             .drop_index(table_name, name)
             .await
             .map(|_| Payload::DropIndex),
+        Statement::CreateTrigger {
+            or_replace,
+            name,
+            table_name,
+            timing,
+            events,
+            condition,
+            body,
+        } => {
+            let options = CreateTriggerOptions {
+                or_replace: *or_replace,
+                name,
+                table_name,
+                timing: *timing,
+                events,
+                condition,
+                body,
+            };
+
+            create_trigger(storage, options)
+                .await
+                .map(|_| Payload::Create)
+        }
+        Statement::DropTrigger {
+            if_exists,
+            name,
+            table_name,
+        } => drop_trigger(storage, name, table_name, *if_exists)
+            .await
+            .map(|_| Payload::DropTrigger),
         //- Transaction
         Statement::StartTransaction => storage
             .begin(false)
@@ -187,89 +324,61 @@ async fn execute_inner<T: GStore + GStoreMut>(
             .map(|_| Payload::StartTransaction),
         Statement::Commit => storage.commit().await.map(|_| Payload::Commit),
         Statement::Rollback => storage.rollback().await.map(|_| Payload::Rollback),
+        Statement::Savepoint(name) => storage.savepoint(name).await.map(|_| Payload::Savepoint),
+        Statement::RollbackToSavepoint(name) => storage
+            .rollback_to_savepoint(name)
+            .await
+            .map(|_| Payload::RollbackToSavepoint),
+        Statement::ReleaseSavepoint(name) => storage
+            .release_savepoint(name)
+            .await
+            .map(|_| Payload::ReleaseSavepoint),
         //-- Rows
         Statement::Insert {
             table_name,
             columns,
             source,
-        } => insert(storage, table_name, columns, source)
+            on_conflict,
+            returning,
+        } => {
+            insert(
+                storage,
+                table_name,
+                columns,
+                source,
+                on_conflict.as_ref(),
+                returning,
+                context,
+            )
             .await
-            .map(Payload::Insert),
+        }
         Statement::Update {
             table_name,
             selection,
             assignments,
+            returning,
         } => {
-            let Schema {
-                column_defs,
-                foreign_keys,
-                ..
-            } = storage
-                .fetch_schema(table_name)
-                .await?
-                .ok_or_else(|| ExecuteError::TableNotFound(table_name.to_owned()))?;
-
-            let all_columns = column_defs.as_deref().map(|columns| {
-                columns
-                    .iter()
-                    .map(|col_def| col_def.name.to_owned())
-                    .collect()
-            });
-            let columns_to_update: Vec<String> = assignments
-                .iter()
-                .map(|assignment| assignment.id.to_owned())
-                .collect();
-
-            let update = Update::new(storage, table_name, assignments, column_defs.as_deref())?;
-
-            let foreign_keys = Arc::new(foreign_keys);
-
-            let rows = fetch(storage, table_name, all_columns, selection.as_ref())
-                .await?
-                .and_then(|item| {
-                    let update = &update;
-                    let (key, row) = item;
-
-                    let foreign_keys = Arc::clone(&foreign_keys);
-                    async move {
-                        let row = update.apply(row, foreign_keys.as_ref()).await?;
-
-                        Ok((key, row))
-                    }
-                })
-                .try_collect::<Vec<(Key, Row)>>()
-                .await?;
-
-            if let Some(column_defs) = column_defs {
-                let column_validation =
-                    ColumnValidation::SpecifiedColumns(&column_defs, columns_to_update);
-                let rows = rows.iter().filter_map(|(_, row)| match row {
-                    Row::Vec { values, .. } => Some(values.as_slice()),
-                    Row::Map(_) => None,
-                });
-
-                validate_unique(storage, table_name, column_validation, rows).await?;
-            }
-
-            let num_rows = rows.len();
-            let rows = rows
-                .into_iter()
-                .map(|(key, row)| (key, row.into()))
-                .collect();
-
-            storage
-                .insert_data(table_name, rows)
-                .await
-                .map(|_| Payload::Update(num_rows))
+            update(
+                storage,
+                table_name,
+                selection,
+                assignments,
+                returning,
+                context,
+            )
+            .await
         }
         Statement::Delete {
             table_name,
             selection,
-        } => delete(storage, table_name, selection).await,
+            returning,
+        } => delete(storage, table_name, selection, returning, context).await,
 
         //- Selection
         Statement::Query(query) => {
-            let (labels, rows) = select_with_labels(storage, query, None).await?;
+            let guard = context.as_ref().and_then(|context| context.get_guard());
+            let (labels, rows) = select_with_labels(storage, query, context).await?;
+            let rows = watch_produced(guard, rows);
 
             match labels {
                 Some(labels) => rows
@@ -284,6 +393,7 @@ async fn execute_inner<T: GStore + GStoreMut>(
                     .map(Payload::SelectMap),
             }
         }
+        Statement::Explain { analyze, query } => explain(storage, query, *analyze, context).await,
         Statement::ShowColumns { table_name } => {
             let Schema { column_defs, .. } = storage
                 .fetch_schema(table_name)
@@ -300,6 +410,7 @@ async fn execute_inner<T: GStore + GStoreMut>(
         }
         Statement::ShowIndexes(table_name) => {
             let query = Query {
+                with: None,
                 body: SetExpr::Select(Box::new(crate::ast::Select {
                     distinct: false,
                     projection: vec![SelectItem::Wildcard],
@@ -344,6 +455,7 @@ async fn execute_inner<T: GStore + GStoreMut>(
         Statement::ShowVariable(variable) => match variable {
             Variable::Tables => {
                 let query = Query {
+                    with: None,
                     body: SetExpr::Select(Box::new(crate::ast::Select {
                         distinct: false,
                         projection: vec![SelectItem::Expr {
@@ -0,0 +1,480 @@
+use {
+    super::{
+        Payload, aggregate::check_aggregate, context::RowContext, select::select_with_profile,
+    },
+    crate::{
+        ast::{
+            BinaryOperator, Expr, IndexItem, Join, JoinConstraint, JoinExecutor, JoinOperator,
+            Query, Select, SelectItem, SetExpr, SetOperator, TableFactor, ToSql, ToSqlUnquoted,
+            Values,
+        },
+        result::Result,
+        store::GStore,
+    },
+    chrono::{DateTime, Utc},
+    futures::{
+        future,
+        stream::{Stream, TryStreamExt},
+        task::{Context, Poll},
+    },
+    itertools::Itertools,
+    serde::{Deserialize, Serialize},
+    std::{
+        fmt,
+        pin::Pin,
+        sync::{
+            Arc,
+            atomic::{AtomicI64, AtomicUsize, Ordering},
+        },
+        time::Duration,
+    },
+};
+
+/// Operator of the plan tree returned by `EXPLAIN`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlanNode {
+    pub operator: String,
+    /// Table, index, condition or keys the operator works on
+    pub detail: Option<String>,
+    /// Rows and time reported by `EXPLAIN ANALYZE`
+    pub actual: Option<PlanActual>,
+    pub children: Vec<PlanNode>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PlanActual {
+    pub rows: usize,
+    /// Time from the start of the query until the operator was done
+    pub elapsed: Duration,
+}
+
+impl PlanNode {
+    fn new(operator: &str, detail: Option<String>, children: Vec<PlanNode>) -> Self {
+        Self {
+            operator: operator.to_owned(),
+            detail,
+            actual: None,
+            children,
+        }
+    }
+
+    fn with_actual<'a>(
+        self,
+        profile: Option<&'a Profile>,
+        counter: impl FnOnce(&'a Profile) -> &'a Counter,
+    ) -> Self {
+        Self {
+            actual: profile.map(|profile| counter(profile).actual(profile.started)),
+            ..self
+        }
+    }
+
+    fn fmt_with_depth(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        write!(f, "{:indent$}{}", "", self.operator, indent = depth * 2)?;
+
+        if let Some(detail) = &self.detail {
+            write!(f, ": {detail}")?;
+        }
+
+        if let Some(PlanActual { rows, elapsed }) = &self.actual {
+            write!(f, " (rows: {rows}, elapsed: {elapsed:?})")?;
+        }
+
+        for child in &self.children {
+            writeln!(f)?;
+            child.fmt_with_depth(f, depth + 1)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for PlanNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_with_depth(f, 0)
+    }
+}
+
+/// Rows produced by an operator of the query run by `EXPLAIN ANALYZE`, and when it was last
+/// polled
+#[derive(Default)]
+pub struct Counter {
+    rows: AtomicUsize,
+    done: AtomicI64,
+}
+
+impl Counter {
+    fn poll(&self, row: bool) {
+        if row {
+            self.rows.fetch_add(1, Ordering::Relaxed);
+        }
+
+        let now = Utc::now().timestamp_nanos_opt().unwrap_or_default();
+        self.done.fetch_max(now, Ordering::Relaxed);
+    }
+
+    fn actual(&self, started: DateTime<Utc>) -> PlanActual {
+        let started = started.timestamp_nanos_opt().unwrap_or_default();
+        let elapsed = self.done.load(Ordering::Relaxed).saturating_sub(started);
+
+        PlanActual {
+            rows: self.rows.load(Ordering::Relaxed),
+            elapsed: Duration::from_nanos(u64::try_from(elapsed).unwrap_or_default()),
+        }
+    }
+}
+
+/// Counters of the operators of a `SELECT`, in the order the executor applies them
+pub struct Profile {
+    started: DateTime<Utc>,
+    /// Rows of the whole query
+    total: Counter,
+    pub scan: Counter,
+    pub joins: Vec<Counter>,
+    pub filter: Counter,
+    pub aggregate: Counter,
+    pub project: Counter,
+    pub sort: Counter,
+    pub distinct: Counter,
+    pub limit: Counter,
+}
+
+impl Profile {
+    fn new(query: &Query) -> Self {
+        let joins = match &query.body {
+            SetExpr::Select(select) => select
+                .from
+                .joins
+                .iter()
+                .map(|_| Counter::default())
+                .collect(),
+            SetExpr::Values(_) | SetExpr::SetOperation { .. } => Vec::new(),
+        };
+
+        Self {
+            started: Utc::now(),
+            total: Counter::default(),
+            scan: Counter::default(),
+            joins,
+            filter: Counter::default(),
+            aggregate: Counter::default(),
+            project: Counter::default(),
+            sort: Counter::default(),
+            distinct: Counter::default(),
+            limit: Counter::default(),
+        }
+    }
+}
+
+/// Counts the rows produced by `rows` and records when it was last polled, when `counter` is
+/// given.
+pub fn track<'a, S, I>(
+    counter: Option<&'a Counter>,
+    rows: S,
+) -> impl Stream<Item = Result<I>> + 'a + use<'a, S, I>
+where
+    S: Stream<Item = Result<I>> + 'a,
+{
+    #[derive(futures_enum::Stream)]
+    enum Rows<S1, S2> {
+        Untracked(S1),
+        Tracked(S2),
+    }
+
+    match counter {
+        Some(counter) => Rows::Tracked(Tracked {
+            counter,
+            rows: Box::pin(rows),
+        }),
+        None => Rows::Untracked(rows),
+    }
+}
+
+struct Tracked<'a, S> {
+    counter: &'a Counter,
+    rows: Pin<Box<S>>,
+}
+
+impl<S, I> Stream for Tracked<'_, S>
+where
+    S: Stream<Item = Result<I>>,
+{
+    type Item = Result<I>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let poll = self.rows.as_mut().poll_next(cx);
+
+        if poll.is_ready() {
+            self.counter.poll(matches!(poll, Poll::Ready(Some(Ok(_)))));
+        }
+
+        poll
+    }
+}
+
+pub async fn explain<'a, T: GStore>(
+    storage: &'a T,
+    query: &'a Query,
+    analyze: bool,
+    filter_context: Option<Arc<RowContext<'a>>>,
+) -> Result<Payload> {
+    if !analyze {
+        return Ok(Payload::Explain(query_node(query, None)));
+    }
+
+    let profile = Profile::new(query);
+    let (_, rows) = select_with_profile(storage, query, filter_context, Some(&profile)).await?;
+    track(Some(&profile.total), rows)
+        .try_for_each(|_| future::ok(()))
+        .await?;
+
+    let node = query_node(query, Some(&profile));
+    let node = match node.actual {
+        Some(_) => node,
+        None => node.with_actual(Some(&profile), |profile| &profile.total),
+    };
+
+    Ok(Payload::Explain(node))
+}
+
+fn query_node(query: &Query, profile: Option<&Profile>) -> PlanNode {
+    let Query {
+        body,
+        order_by,
+        limit,
+        offset,
+        ..
+    } = query;
+
+    let (node, distinct, profile) = match body {
+        SetExpr::Select(select) => (select_node(select, profile), select.distinct, profile),
+        SetExpr::Values(Values(rows)) => {
+            let detail = format!("{} rows", rows.len());
+
+            (
+                PlanNode::new("Values", Some(detail), Vec::new()),
+                false,
+                None,
+            )
+        }
+        SetExpr::SetOperation {
+            op,
+            all,
+            left,
+            right,
+        } => {
+            let operator = match (op, all) {
+                (SetOperator::Union, false) => "Union",
+                (SetOperator::Union, true) => "Union All",
+                (SetOperator::Intersect, false) => "Intersect",
+                (SetOperator::Intersect, true) => "Intersect All",
+                (SetOperator::Except, false) => "Except",
+                (SetOperator::Except, true) => "Except All",
+            };
+            let children = vec![query_node(left, None), query_node(right, None)];
+
+            (PlanNode::new(operator, None, children), false, None)
+        }
+    };
+
+    let node = match order_by.is_empty() {
+        true => node,
+        false => {
+            let detail = order_by
+                .iter()
+                .map(ToSqlUnquoted::to_sql_unquoted)
+                .join(", ");
+
+            PlanNode::new("Sort", Some(detail), vec![node])
+                .with_actual(profile, |profile| &profile.sort)
+        }
+    };
+
+    let node = match distinct {
+        true => PlanNode::new("Distinct", None, vec![node])
+            .with_actual(profile, |profile| &profile.distinct),
+        false => node,
+    };
+
+    if limit.is_none() && offset.is_none() {
+        return node;
+    }
+
+    let limit = limit.as_ref().map(ToSqlUnquoted::to_sql_unquoted);
+    let offset = offset
+        .as_ref()
+        .map(|expr| format!("offset {}", expr.to_sql_unquoted()));
+    let detail = limit.into_iter().chain(offset).join(" ");
+
+    PlanNode::new("Limit", Some(detail), vec![node]).with_actual(profile, |profile| &profile.limit)
+}
+
+fn select_node(select: &Select, profile: Option<&Profile>) -> PlanNode {
+    let Select {
+        projection,
+        from,
+        selection,
+        group_by,
+        having,
+        ..
+    } = select;
+
+    let node = table_factor_node(&from.relation).with_actual(profile, |profile| &profile.scan);
+    let node = from.joins.iter().enumerate().fold(node, |node, (i, join)| {
+        join_node(join, node).with_actual(profile, |profile| &profile.joins[i])
+    });
+
+    let node = match selection {
+        Some(expr) => PlanNode::new("Filter", Some(expr.to_sql_unquoted()), vec![node])
+            .with_actual(profile, |profile| &profile.filter),
+        None => node,
+    };
+
+    let node = match check_aggregate(projection, group_by) {
+        true => {
+            let group_by =
+                (!group_by.is_empty()).then(|| format!("group by: {}", exprs_to_sql(group_by)));
+            let having = having
+                .as_ref()
+                .map(|expr| format!("having: {}", expr.to_sql_unquoted()));
+            let detail = group_by.into_iter().chain(having).join(", ");
+            let detail = (!detail.is_empty()).then_some(detail);
+
+            PlanNode::new("Aggregate", detail, vec![node])
+                .with_actual(profile, |profile| &profile.aggregate)
+        }
+        false => node,
+    };
+
+    let detail = projection
+        .iter()
+        .map(|item| match item {
+            SelectItem::Expr { expr, label } if &expr.to_sql_unquoted() == label => label.clone(),
+            _ => item.to_sql_unquoted(),
+        })
+        .join(", ");
+
+    PlanNode::new("Project", Some(detail), vec![node])
+        .with_actual(profile, |profile| &profile.project)
+}
+
+fn join_node(join: &Join, left: PlanNode) -> PlanNode {
+    let Join {
+        relation,
+        join_operator,
+        join_executor,
+    } = join;
+
+    let (kind, constraint) = match join_operator {
+        JoinOperator::Inner(constraint) => ("inner", constraint),
+        JoinOperator::LeftOuter(constraint) => ("left outer", constraint),
+        JoinOperator::RightOuter(constraint) => ("right outer", constraint),
+        JoinOperator::FullOuter(constraint) => ("full outer", constraint),
+        JoinOperator::Cross => ("cross", &JoinConstraint::None),
+    };
+    let constraint = match constraint {
+        JoinConstraint::On(expr) => Some(format!("on: {}", expr.to_sql_unquoted())),
+        JoinConstraint::Using(columns) => Some(format!("using: {}", columns.join(", "))),
+        JoinConstraint::Natural => Some("natural".to_owned()),
+        JoinConstraint::None => None,
+    };
+
+    let (operator, executor) = match join_executor {
+        JoinExecutor::NestedLoop => ("Nested Loop Join", None),
+        JoinExecutor::Hash {
+            key_expr,
+            value_expr,
+            where_clause,
+        } => {
+            let hash = format!(
+                "hash: {} = {}",
+                key_expr.to_sql_unquoted(),
+                value_expr.to_sql_unquoted()
+            );
+            let where_clause = where_clause
+                .as_ref()
+                .map(|expr| format!("hash filter: {}", expr.to_sql_unquoted()));
+
+            ("Hash Join", Some(std::iter::once(hash).chain(where_clause)))
+        }
+    };
+
+    let detail = std::iter::once(kind.to_owned())
+        .chain(constraint)
+        .chain(executor.into_iter().flatten())
+        .join(", ");
+
+    PlanNode::new(
+        operator,
+        Some(detail),
+        vec![left, table_factor_node(relation)],
+    )
+}
+
+fn table_factor_node(table_factor: &TableFactor) -> PlanNode {
+    match table_factor {
+        TableFactor::Table { name, alias, index } => {
+            let table = match alias {
+                Some(alias) if &alias.name != name => format!("{name} AS {}", alias.name),
+                _ => name.to_owned(),
+            };
+
+            let (operator, detail) = match index {
+                None => ("Seq Scan", table),
+                Some(IndexItem::PrimaryKey(expr)) => (
+                    "Primary Key Lookup",
+                    format!("{table}, key: {}", expr.to_sql_unquoted()),
+                ),
+                Some(IndexItem::CompositePrimaryKey(exprs)) => (
+                    "Primary Key Lookup",
+                    format!("{table}, key: ({})", exprs_to_sql(exprs)),
+                ),
+                Some(IndexItem::NonClustered {
+                    name: index_name,
+                    asc,
+                    prefix,
+                    cmp_expr,
+                }) => {
+                    let prefix =
+                        (!prefix.is_empty()).then(|| format!("prefix: {}", exprs_to_sql(prefix)));
+                    let cmp_expr = cmp_expr.as_ref().map(|(op, expr)| {
+                        let op = BinaryOperator::from(op.clone());
+
+                        format!("key {} {}", op.to_sql(), expr.to_sql_unquoted())
+                    });
+                    let order = asc.map(|asc| match asc {
+                        true => "order: ASC".to_owned(),
+                        false => "order: DESC".to_owned(),
+                    });
+                    let detail = [table, format!("index: {index_name}")]
+                        .into_iter()
+                        .chain(prefix)
+                        .chain(cmp_expr)
+                        .chain(order)
+                        .join(", ");
+
+                    ("Index Scan", detail)
+                }
+            };
+
+            PlanNode::new(operator, Some(detail), Vec::new())
+        }
+        TableFactor::Derived { subquery, alias } => PlanNode::new(
+            "Subquery Scan",
+            Some(alias.name.clone()),
+            vec![query_node(subquery, None)],
+        ),
+        TableFactor::Series { size, .. } => PlanNode::new(
+            "Series Scan",
+            Some(format!("SERIES({})", size.to_sql_unquoted())),
+            Vec::new(),
+        ),
+        TableFactor::Dictionary { dict, .. } => {
+            PlanNode::new("Dictionary Scan", Some(dict.to_string()), Vec::new())
+        }
+    }
+}
+
+fn exprs_to_sql(exprs: &[Expr]) -> String {
+    exprs.iter().map(ToSqlUnquoted::to_sql_unquoted).join(", ")
+}
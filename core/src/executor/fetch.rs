@@ -1,10 +1,15 @@
 use {
-    super::{context::RowContext, evaluate::evaluate_stateless, filter::check_expr},
+    super::{
+        context::{CteTable, RowContext},
+        evaluate::evaluate_stateless,
+        filter::check_expr,
+        join::{coalesce_columns, using_columns},
+        scan_filter::scan_filter,
+    },
     crate::{
         ast::{
-            ColumnDef, ColumnUniqueOption, Dictionary, Expr, IndexItem, Join, Query, Select,
-            SelectItem, SetExpr, TableAlias, TableFactor, TableWithJoins, ToSql, ToSqlUnquoted,
-            Values,
+            Cte, Dictionary, Expr, IndexItem, Join, Query, Select, SelectItem, SetExpr, TableAlias,
+            TableFactor, TableWithJoins, ToSql, ToSqlUnquoted, Values, With,
         },
         data::{Key, Row, Value, get_alias, get_index},
         executor::{
@@ -47,6 +52,7 @@ pub async fn fetch<'a, T: GStore>(
     table_name: &'a str,
     columns: Option<Arc<[String]>>,
     where_clause: Option<&'a Expr>,
+    filter_context: Option<Arc<RowContext<'a>>>,
 ) -> Result<impl Stream<Item = Result<(Key, Row)>> + 'a> {
     let columns = columns.unwrap_or_else(|| Arc::from([]));
     let rows = storage
@@ -60,6 +66,7 @@ pub async fn fetch<'a, T: GStore>(
                 },
                 DataRow::Map(values) => Row::Map(values),
             };
+            let filter_context = filter_context.as_ref().map(Arc::clone);
 
             async move {
                 let expr = match where_clause {
@@ -69,7 +76,7 @@ pub async fn fetch<'a, T: GStore>(
                     Some(expr) => expr,
                 };
 
-                let context = RowContext::new(table_name, Cow::Borrowed(&row), None);
+                let context = RowContext::new(table_name, Cow::Borrowed(&row), filter_context);
 
                 check_expr(storage, Some(Arc::new(context)), None, expr)
                     .await
@@ -81,22 +88,30 @@ pub async fn fetch<'a, T: GStore>(
 }
 
 #[derive(futures_enum::Stream)]
-pub enum Rows<I1, I2, I3, I4> {
+pub enum Rows<I1, I2, I3, I4, I5> {
     Derived(I1),
     Table(I2),
     Series(I3),
     Dictionary(I4),
+    Cte(I5),
 }
 
+/// Fetches the rows of `table_factor`, scanning them with the filter built from `query` when
+/// it is the first table of `query`
 pub async fn fetch_relation_rows<'a, T: GStore>(
     storage: &'a T,
     table_factor: &'a TableFactor,
     filter_context: &Option<Arc<RowContext<'a>>>,
+    query: Option<&Query>,
 ) -> Result<impl Stream<Item = Result<Row>> + 'a> {
     let columns = Arc::from(
-        fetch_relation_columns(storage, table_factor)
-            .await?
-            .unwrap_or_default(),
+        fetch_relation_columns(
+            storage,
+            table_factor,
+            filter_context.as_ref().map(Arc::clone),
+        )
+        .await?
+        .unwrap_or_default(),
     );
 
     match table_factor {
@@ -116,24 +131,51 @@ pub async fn fetch_relation_rows<'a, T: GStore>(
             Ok(Rows::Derived(rows))
         }
         TableFactor::Table { name, .. } => {
+            if let Some(table) = filter_context
+                .as_ref()
+                .and_then(|context| context.get_cte(name))
+            {
+                let rows = table.rows.clone().into_iter().map(move |row| match row {
+                    Row::Vec { values, .. } => Ok(Row::Vec {
+                        columns: Arc::clone(&columns),
+                        values,
+                    }),
+                    Row::Map(values) => Ok(Row::Map(values)),
+                });
+
+                return Ok(Rows::Cte(stream::iter(rows)));
+            }
+
             let rows = {
                 #[derive(futures_enum::Stream)]
-                enum Rows<I1, I2, I3, I4> {
+                enum Rows<I1, I2, I3, I4, I5> {
                     Indexed(I1),
                     PrimaryKey(I2),
                     PrimaryKeyEmpty(I3),
-                    FullScan(I4),
+                    PrimaryKeyPrefix(I4),
+                    FullScan(I5),
                 }
 
                 match get_index(table_factor) {
                     Some(IndexItem::NonClustered {
                         name: index_name,
                         asc,
+                        prefix,
                         cmp_expr,
                     }) => {
+                        let mut prefix_values = Vec::with_capacity(prefix.len());
+                        for expr in prefix {
+                            let filter_context = filter_context.as_ref().map(Arc::clone);
+                            let evaluated = evaluate(storage, filter_context, None, expr).await?;
+
+                            prefix_values.push(evaluated.try_into()?);
+                        }
+
                         let cmp_value = match cmp_expr {
                             Some((op, expr)) => {
-                                let evaluated = evaluate(storage, None, None, expr).await?;
+                                let filter_context = filter_context.as_ref().map(Arc::clone);
+                                let evaluated =
+                                    evaluate(storage, filter_context, None, expr).await?;
 
                                 Some((op, evaluated.try_into()?))
                             }
@@ -141,7 +183,7 @@ pub async fn fetch_relation_rows<'a, T: GStore>(
                         };
 
                         let rows = storage
-                            .scan_indexed_data(name, index_name, *asc, cmp_value)
+                            .scan_indexed_data(name, index_name, *asc, &prefix_values, cmp_value)
                             .await?
                             .map_ok(move |(_, data_row)| match data_row {
                                 DataRow::Vec(values) => Row::Vec {
@@ -196,15 +238,83 @@ pub async fn fetch_relation_rows<'a, T: GStore>(
                             None => Rows::PrimaryKeyEmpty(stream::empty()),
                         }
                     }
-                    _ => {
-                        let rows = storage.scan_data(name).await?.map_ok(move |(_, data_row)| {
-                            match data_row {
-                                DataRow::Vec(values) => Row::Vec {
-                                    columns: Arc::clone(&columns),
-                                    values,
+                    Some(IndexItem::CompositePrimaryKey(exprs)) => {
+                        let schema = storage
+                            .fetch_schema(name)
+                            .await?
+                            .ok_or(FetchError::Unreachable)?;
+                        let indexes = schema
+                            .primary_key_indexes()
+                            .ok_or(FetchError::Unreachable)?;
+                        let column_defs = schema
+                            .column_defs
+                            .as_deref()
+                            .ok_or(FetchError::Unreachable)?;
+
+                        let mut keys = Vec::with_capacity(exprs.len());
+                        for (expr, index) in exprs.iter().zip(indexes.iter()) {
+                            let filter_context = filter_context.as_ref().map(Arc::clone);
+                            let evaluated = evaluate(storage, filter_context, None, expr).await?;
+                            let value = match evaluated {
+                                Evaluated::Literal(literal) => Value::try_from_literal(
+                                    &column_defs[*index].data_type,
+                                    &literal,
+                                ),
+                                eval => eval.try_into(),
+                            }?;
+
+                            keys.push(Key::try_from(value)?);
+                        }
+
+                        if keys.len() == indexes.len() {
+                            match storage.fetch_data(name, &Key::Composite(keys)).await? {
+                                Some(data_row) => {
+                                    let row = match data_row {
+                                        DataRow::Vec(values) => Row::Vec {
+                                            columns: Arc::clone(&columns),
+                                            values,
+                                        },
+                                        DataRow::Map(values) => Row::Map(values),
+                                    };
+
+                                    Rows::PrimaryKey(stream::once(future::ready(Ok(row))))
+                                }
+                                None => Rows::PrimaryKeyEmpty(stream::empty()),
+                            }
+                        } else {
+                            let rows = storage.scan_data_by_prefix(name, &keys).await?.map_ok(
+                                move |(_, data_row)| match data_row {
+                                    DataRow::Vec(values) => Row::Vec {
+                                        columns: Arc::clone(&columns),
+                                        values,
+                                    },
+                                    DataRow::Map(values) => Row::Map(values),
                                 },
-                                DataRow::Map(values) => Row::Map(values),
+                            );
+
+                            Rows::PrimaryKeyPrefix(rows)
+                        }
+                    }
+                    _ => {
+                        let scan_filter = match query {
+                            Some(query) => storage
+                                .fetch_schema(name)
+                                .await?
+                                .and_then(|schema| scan_filter(query, &schema)),
+                            None => None,
+                        };
+                        let rows = match scan_filter {
+                            Some(scan_filter) => {
+                                storage.scan_data_filtered(name, &scan_filter).await?
                             }
+                            None => storage.scan_data(name).await?,
+                        };
+                        let rows = rows.map_ok(move |(_, data_row)| match data_row {
+                            DataRow::Vec(values) => Row::Vec {
+                                columns: Arc::clone(&columns),
+                                values,
+                            },
+                            DataRow::Map(values) => Row::Map(values),
                         });
 
                         Rows::FullScan(rows)
@@ -244,12 +354,32 @@ pub async fn fetch_relation_rows<'a, T: GStore>(
 
                 match dict {
                     Dictionary::GlueObjects => {
+                        let views = storage.fetch_all_views().await?;
                         let schemas = storage.fetch_all_schemas().await?;
                         let table_metas = storage
                             .scan_table_meta()
                             .await?
                             .collect::<Result<BTreeMap<_, _>>>()?;
-                        let rows = schemas.into_iter().flat_map(move |schema| {
+                        let view_rows = views
+                            .iter()
+                            .map(|view| {
+                                let object_type = match view.materialized {
+                                    true => "MATERIALIZED VIEW",
+                                    false => "VIEW",
+                                };
+
+                                BTreeMap::from([
+                                    ("OBJECT_NAME".to_owned(), Value::Str(view.view_name.clone())),
+                                    ("OBJECT_TYPE".to_owned(), Value::Str(object_type.to_owned())),
+                                ])
+                            })
+                            .map(|hash_map| Ok(Row::Map(hash_map)))
+                            .collect::<Vec<_>>();
+                        // Backing tables of materialized views are listed as the views.
+                        let schemas = schemas.into_iter().filter(move |schema| {
+                            !views.iter().any(|view| view.view_name == schema.table_name)
+                        });
+                        let rows = schemas.flat_map(move |schema| {
                             let meta = table_metas
                                 .iter()
                                 .find_map(|(table_name, hash_map)| {
@@ -276,6 +406,7 @@ pub async fn fetch_relation_rows<'a, T: GStore>(
                                 .chain(index_rows)
                                 .map(|hash_map| Ok(Row::Map(hash_map)))
                         });
+                        let rows = rows.chain(view_rows);
 
                         Rows::Objects(stream::iter(rows))
                     }
@@ -318,6 +449,10 @@ pub async fn fetch_relation_rows<'a, T: GStore>(
                                             .default
                                             .map(|expr| Value::Str(expr.to_sql()))
                                             .unwrap_or(Value::Null),
+                                        column_def
+                                            .generated
+                                            .map(|expr| Value::Str(expr.to_sql()))
+                                            .unwrap_or(Value::Null),
                                         column_def.comment.map(Value::Str).unwrap_or(Value::Null),
                                     ];
 
@@ -333,12 +468,14 @@ pub async fn fetch_relation_rows<'a, T: GStore>(
                     Dictionary::GlueIndexes => {
                         let schemas = storage.fetch_all_schemas().await?;
                         let rows = schemas.into_iter().flat_map(move |schema| {
-                            let column_defs = schema.column_defs.unwrap_or_default();
-                            let primary_column = column_defs.iter().find_map(|column_def| {
-                                let ColumnDef { name, unique, .. } = column_def;
-
-                                (unique == &Some(ColumnUniqueOption { is_primary: true }))
-                                    .then_some(name)
+                            let primary_column = schema.primary_key_indexes().map(|indexes| {
+                                let column_defs = schema.column_defs.as_deref().unwrap_or_default();
+
+                                indexes
+                                    .into_iter()
+                                    .map(|index| column_defs[index].name.as_str())
+                                    .collect::<Vec<_>>()
+                                    .join(", ")
                             });
 
                             let clustered = match primary_column {
@@ -367,8 +504,15 @@ pub async fn fetch_relation_rows<'a, T: GStore>(
                                     Value::Str(schema.table_name.clone()),
                                     Value::Str(index.name),
                                     Value::Str(index.order.to_string()),
-                                    Value::Str(index.expr.to_sql_unquoted()),
-                                    Value::Bool(false),
+                                    Value::Str(
+                                        index
+                                            .exprs
+                                            .iter()
+                                            .map(ToSqlUnquoted::to_sql_unquoted)
+                                            .collect::<Vec<_>>()
+                                            .join(", "),
+                                    ),
+                                    Value::Bool(index.unique),
                                 ];
 
                                 Ok(Row::Vec {
@@ -409,37 +553,53 @@ pub async fn fetch_columns<T: GStore>(
     Ok(columns)
 }
 
+/// Replaces the leading labels with the column aliases given to the relation.
+pub fn apply_column_aliases(
+    name: &str,
+    alias_columns: &[String],
+    labels: Option<Vec<String>>,
+) -> Result<Option<Vec<String>>> {
+    match labels {
+        None => Ok(None),
+        Some(labels) if alias_columns.is_empty() => Ok(Some(labels)),
+        Some(labels) if alias_columns.len() > labels.len() => Err(
+            FetchError::TooManyColumnAliases(name.to_owned(), labels.len(), alias_columns.len())
+                .into(),
+        ),
+        Some(labels) => Ok(Some(
+            alias_columns
+                .iter()
+                .cloned()
+                .chain(labels[alias_columns.len()..labels.len()].to_vec())
+                .collect(),
+        )),
+    }
+}
+
 #[async_recursion]
-pub async fn fetch_relation_columns<T>(
-    storage: &T,
-    table_factor: &TableFactor,
+pub async fn fetch_relation_columns<'a, T>(
+    storage: &'a T,
+    table_factor: &'a TableFactor,
+    filter_context: Option<Arc<RowContext<'a>>>,
 ) -> Result<Option<Vec<String>>>
 where
     T: GStore,
 {
     match table_factor {
         TableFactor::Table { name, alias, .. } => {
-            let columns = fetch_columns(storage, name).await?;
-            match (columns, alias) {
-                (columns, None) => Ok(columns),
-                (None, Some(_)) => Ok(None),
-                (Some(columns), Some(alias)) if alias.columns.len() > columns.len() => {
-                    Err(FetchError::TooManyColumnAliases(
-                        name.to_string(),
-                        columns.len(),
-                        alias.columns.len(),
-                    )
-                    .into())
-                }
-                (Some(columns), Some(alias)) => Ok(Some(
-                    alias
-                        .columns
-                        .iter()
-                        .cloned()
-                        .chain(columns[alias.columns.len()..columns.len()].to_vec())
-                        .collect(),
-                )),
-            }
+            let columns = match filter_context
+                .as_ref()
+                .and_then(|context| context.get_cte(name))
+            {
+                Some(table) => table.labels.clone(),
+                None => fetch_columns(storage, name).await?,
+            };
+            let alias_columns = alias
+                .as_ref()
+                .map(|alias| alias.columns.as_slice())
+                .unwrap_or_default();
+
+            apply_column_aliases(name, alias_columns, columns)
         }
         TableFactor::Series { .. } => Ok(Some(vec!["N".to_owned()])),
         TableFactor::Dictionary { dict, .. } => Ok(Some(match dict {
@@ -456,6 +616,7 @@ where
                 "NULLABLE".to_owned(),
                 "KEY".to_owned(),
                 "DEFAULT".to_owned(),
+                "GENERATED".to_owned(),
                 "COMMENT".to_owned(),
             ],
             Dictionary::GlueIndexes => vec![
@@ -467,75 +628,85 @@ where
             ],
         })),
         TableFactor::Derived {
-            subquery: Query { body, .. },
+            subquery,
             alias:
                 TableAlias {
                     columns: alias_columns,
                     name,
                 },
-        } => match body {
-            SetExpr::Select(statement) => {
-                let Select {
-                    from:
-                        TableWithJoins {
-                            relation, joins, ..
-                        },
-                    projection,
-                    ..
-                } = statement.as_ref();
+        } => {
+            let labels = fetch_query_labels(storage, subquery, filter_context).await?;
 
-                let labels = fetch_labels(storage, relation, joins, projection).await?;
-                match labels {
-                    None => Ok(None),
-                    Some(labels) if alias_columns.is_empty() => Ok(Some(labels)),
-                    Some(labels) if alias_columns.len() > labels.len() => {
-                        Err(FetchError::TooManyColumnAliases(
-                            name.to_string(),
-                            labels.len(),
-                            alias_columns.len(),
-                        )
-                        .into())
-                    }
-                    Some(labels) => Ok(Some(
-                        alias_columns
-                            .iter()
-                            .cloned()
-                            .chain(labels[alias_columns.len()..labels.len()].to_vec())
-                            .collect(),
-                    )),
-                }
-            }
-            SetExpr::Values(Values(values_list)) => {
-                let total_len = values_list[0].len();
-                let alias_len = alias_columns.len();
-                if alias_len > total_len {
-                    return Err(FetchError::TooManyColumnAliases(
-                        name.into(),
-                        total_len,
-                        alias_len,
-                    )
-                    .into());
-                }
-                let labels = (alias_len + 1..=total_len).map(|i| format!("column{i}"));
-                let labels = alias_columns
-                    .iter()
-                    .cloned()
-                    .chain(labels)
-                    .collect::<Vec<_>>();
+            apply_column_aliases(name, alias_columns, labels)
+        }
+    }
+}
 
-                Ok(Some(labels))
-            }
-        },
+/// CTEs of the query are bound with their labels only, which is enough to resolve the labels
+/// of the relations referring to them.
+#[async_recursion]
+async fn fetch_query_labels<'a, T>(
+    storage: &'a T,
+    query: &'a Query,
+    filter_context: Option<Arc<RowContext<'a>>>,
+) -> Result<Option<Vec<String>>>
+where
+    T: GStore,
+{
+    let Query { with, body, .. } = query;
+
+    let mut filter_context = filter_context;
+    for Cte { alias, query } in with.iter().flat_map(|With { cte_tables, .. }| cte_tables) {
+        let labels =
+            fetch_query_labels(storage, query, filter_context.as_ref().map(Arc::clone)).await?;
+        let table = CteTable {
+            labels: apply_column_aliases(&alias.name, &alias.columns, labels)?,
+            rows: Vec::new(),
+        };
+
+        filter_context = Some(Arc::new(RowContext::cte(
+            &alias.name,
+            table,
+            filter_context,
+        )));
+    }
+
+    match body {
+        SetExpr::Select(statement) => {
+            let Select {
+                from: TableWithJoins {
+                    relation, joins, ..
+                },
+                projection,
+                ..
+            } = statement.as_ref();
+
+            fetch_labels(storage, relation, joins, projection, filter_context).await
+        }
+        SetExpr::Values(Values(values_list)) => {
+            let labels = (1..=values_list[0].len())
+                .map(|i| format!("column{i}"))
+                .collect();
+
+            Ok(Some(labels))
+        }
+        SetExpr::SetOperation { left, .. } => {
+            fetch_query_labels(storage, left, filter_context).await
+        }
     }
 }
 
 async fn fetch_join_columns<'a, T: GStore>(
-    storage: &T,
+    storage: &'a T,
     joins: &'a [Join],
+    filter_context: Option<Arc<RowContext<'a>>>,
 ) -> Result<Option<Vec<(&'a String, Vec<String>)>>> {
     let mut all_columns = Vec::with_capacity(joins.len());
     for join in joins {
-        if let Some(columns) = fetch_relation_columns(storage, &join.relation).await? {
+        let filter_context = filter_context.as_ref().map(Arc::clone);
+        if let Some(columns) =
+            fetch_relation_columns(storage, &join.relation, filter_context).await?
+        {
             let alias = get_alias(&join.relation);
             all_columns.push((alias, columns));
         } else {
@@ -545,15 +716,17 @@ async fn fetch_join_columns<'a, T: GStore>(
     Ok(Some(all_columns))
 }
 
-pub async fn fetch_labels<T: GStore>(
-    storage: &T,
-    relation: &TableFactor,
-    joins: &[Join],
+pub async fn fetch_labels<'a, T: GStore>(
+    storage: &'a T,
+    relation: &'a TableFactor,
+    joins: &'a [Join],
     projection: &[SelectItem],
+    filter_context: Option<Arc<RowContext<'a>>>,
 ) -> Result<Option<Vec<String>>> {
     let table_alias = get_alias(relation);
-    let columns = fetch_relation_columns(storage, relation).await?;
-    let join_columns = fetch_join_columns(storage, joins).await?;
+    let columns =
+        fetch_relation_columns(storage, relation, filter_context.as_ref().map(Arc::clone)).await?;
+    let join_columns = fetch_join_columns(storage, joins, filter_context).await?;
 
     if (columns.is_none() || join_columns.is_none())
         && projection.iter().any(|item| {
@@ -573,10 +746,21 @@ pub async fn fetch_labels<T: GStore>(
         .iter()
         .flat_map(|item| match item {
             SelectItem::Wildcard => {
-                let columns = columns.iter().cloned();
-                let join_columns = join_columns.iter().flat_map(|(_, columns)| columns.clone());
+                let mut labels = columns.clone();
+                for ((_, columns), join) in join_columns.iter().zip(joins) {
+                    let using =
+                        match using_columns(&join.join_operator, Some(&labels), Some(columns)) {
+                            Ok(using) => using,
+                            Err(error) => return vec![Err(error)],
+                        };
+
+                    labels = match using {
+                        Some(using) => coalesce_columns(&labels, columns, &using),
+                        None => labels.into_iter().chain(columns.iter().cloned()).collect(),
+                    };
+                }
 
-                columns.chain(join_columns).map(Ok).collect()
+                labels.into_iter().map(Ok).collect()
             }
             SelectItem::QualifiedWildcard(target_table_alias) => {
                 if table_alias == target_table_alias {
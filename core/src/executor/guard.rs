@@ -0,0 +1,193 @@
+use {
+    crate::result::Result,
+    chrono::{DateTime, TimeDelta, Utc},
+    futures::{
+        future,
+        stream::{Stream, StreamExt, TryStreamExt},
+    },
+    serde::Serialize,
+    std::{
+        sync::{
+            Arc,
+            atomic::{AtomicBool, Ordering},
+        },
+        time::Duration,
+    },
+    thiserror::Error as ThisError,
+};
+
+#[derive(ThisError, Serialize, Debug, PartialEq, Eq)]
+pub enum LimitError {
+    #[error("statement cancelled")]
+    Cancelled,
+
+    #[error("statement timed out after {0:?}")]
+    Timeout(Duration),
+
+    #[error("statement produced more than {0} rows")]
+    RowLimitExceeded(usize),
+
+    #[error("statement buffered more than {0} rows")]
+    BufferedRowLimitExceeded(usize),
+}
+
+/// Limits applied to each statement run by [`crate::prelude::Glue`]
+#[derive(Debug, Clone, Default)]
+pub struct ExecuteOptions {
+    /// Time a statement may run for, counted from when it starts
+    pub timeout: Option<Duration>,
+    pub cancel: Option<CancelToken>,
+    /// Rows a `SELECT` may return
+    pub max_rows: Option<usize>,
+    /// Rows a sort, an aggregate or a join may hold in memory at once
+    pub max_buffered_rows: Option<usize>,
+}
+
+/// Cancels the statements running with it from another task or thread
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// [`ExecuteOptions`] of a running statement, checked between the rows of its stages
+#[derive(Debug)]
+pub struct Guard {
+    cancel: Option<CancelToken>,
+    deadline: Option<(DateTime<Utc>, Duration)>,
+    max_rows: Option<usize>,
+    max_buffered_rows: Option<usize>,
+}
+
+impl Guard {
+    /// Starts the clock of a statement, or returns `None` when `options` set no limit.
+    pub fn new(options: &ExecuteOptions) -> Option<Self> {
+        let ExecuteOptions {
+            timeout,
+            cancel,
+            max_rows,
+            max_buffered_rows,
+        } = options;
+
+        if timeout.is_none()
+            && cancel.is_none()
+            && max_rows.is_none()
+            && max_buffered_rows.is_none()
+        {
+            return None;
+        }
+
+        let deadline = timeout.and_then(|timeout| {
+            let deadline = Utc::now().checked_add_signed(TimeDelta::from_std(timeout).ok()?)?;
+
+            Some((deadline, timeout))
+        });
+
+        Some(Self {
+            cancel: cancel.clone(),
+            deadline,
+            max_rows: *max_rows,
+            max_buffered_rows: *max_buffered_rows,
+        })
+    }
+
+    pub fn check(&self) -> Result<()> {
+        if self.cancel.as_ref().is_some_and(CancelToken::is_cancelled) {
+            return Err(LimitError::Cancelled.into());
+        }
+
+        match self.deadline {
+            Some((deadline, timeout)) if Utc::now() >= deadline => {
+                Err(LimitError::Timeout(timeout).into())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Fails once the statement has produced more than `max_rows` rows.
+    pub fn check_produced(&self, rows: usize) -> Result<()> {
+        match self.max_rows {
+            Some(max_rows) if rows > max_rows => Err(LimitError::RowLimitExceeded(max_rows).into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Fails once a stage holds more than `max_buffered_rows` rows.
+    pub fn check_buffered(&self, rows: usize) -> Result<()> {
+        match self.max_buffered_rows {
+            Some(max_rows) if rows > max_rows => {
+                Err(LimitError::BufferedRowLimitExceeded(max_rows).into())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Checks `guard` before each row of `rows` is passed on.
+pub fn watch<'a, S, I>(
+    guard: Option<Arc<Guard>>,
+    rows: S,
+) -> impl Stream<Item = Result<I>> + 'a + use<'a, S, I>
+where
+    S: Stream<Item = Result<I>> + 'a,
+    I: 'a,
+{
+    rows.and_then(move |row| {
+        let checked = guard.as_deref().map_or(Ok(()), Guard::check);
+
+        future::ready(checked.map(|()| row))
+    })
+}
+
+/// Checks `guard` like [`watch`] for rows the caller holds in memory all at once, failing
+/// once there are more of them than the buffered row limit.
+pub fn watch_buffered<'a, S, I>(
+    guard: Option<Arc<Guard>>,
+    rows: S,
+) -> impl Stream<Item = Result<I>> + 'a + use<'a, S, I>
+where
+    S: Stream<Item = Result<I>> + 'a,
+    I: 'a,
+{
+    rows.enumerate().map(move |(i, row)| {
+        let row = row?;
+
+        if let Some(guard) = guard.as_deref() {
+            guard.check()?;
+            guard.check_buffered(i + 1)?;
+        }
+
+        Ok(row)
+    })
+}
+
+/// Counts the rows `rows` returns against the produced row limit of `guard`.
+pub fn watch_produced<'a, S, I>(
+    guard: Option<Arc<Guard>>,
+    rows: S,
+) -> impl Stream<Item = Result<I>> + 'a + use<'a, S, I>
+where
+    S: Stream<Item = Result<I>> + 'a,
+    I: 'a,
+{
+    rows.enumerate().map(move |(i, row)| {
+        let row = row?;
+
+        if let Some(guard) = guard.as_deref() {
+            guard.check_produced(i + 1)?;
+        }
+
+        Ok(row)
+    })
+}
@@ -1,12 +1,25 @@
+mod on_conflict;
+
 use {
     super::{
+        Payload,
+        returning::{data_row_to_row, returning},
         select::select,
-        validate::{ColumnValidation, validate_unique},
+        update::validate_foreign_keys,
+        validate::{ColumnValidation, find_unreferenced, validate_check, validate_unique},
     },
     crate::{
-        ast::{ColumnDef, ColumnUniqueOption, Expr, ForeignKey, Query, SetExpr, Values},
-        data::{Key, Row, Schema, Value},
-        executor::{evaluate::evaluate_stateless, limit::Limit},
+        ast::{
+            ColumnDef, ColumnIdentity, Expr, ForeignKey, KeyConstraint, OnConflict,
+            OnConflictAction, Query, SelectItem, SetExpr, TriggerEvent, TriggerTiming, Values,
+        },
+        data::{Key, Row, Schema, SequenceError, Value, schema::primary_key_indexes},
+        executor::{
+            context::RowContext,
+            evaluate::{evaluate, evaluate_stateless, uses_sequence},
+            limit::Limit,
+            trigger::Triggers,
+        },
         result::Result,
         store::{DataRow, GStore, GStoreMut},
     },
@@ -50,6 +63,21 @@ pub enum InsertError {
 
     #[error("unreachable referencing column name: {0}")]
     ConflictReferencingColumnName(String),
+
+    #[error("there is no unique or primary key constraint matching the ON CONFLICT target: {0}")]
+    ConflictTargetNotUnique(String),
+
+    #[error("ON CONFLICT DO UPDATE requires a conflict target")]
+    ConflictTargetRequired,
+
+    #[error("ON CONFLICT DO UPDATE cannot affect the same row a second time")]
+    ConflictRowAffectedTwice,
+
+    #[error("cannot insert a value into identity column generated always: {0}")]
+    IdentityColumnGeneratedAlways(String),
+
+    #[error("cannot insert a value into generated column: {0}")]
+    CannotInsertIntoGeneratedColumn(String),
 }
 
 enum RowsData {
@@ -62,67 +90,202 @@ pub async fn insert<T: GStore + GStoreMut>(
     table_name: &str,
     columns: &[String],
     source: &Query,
-) -> Result<usize> {
+    on_conflict: Option<&OnConflict>,
+    returning_items: &[SelectItem],
+    filter_context: Option<Arc<RowContext<'_>>>,
+) -> Result<Payload> {
+    let schema = storage
+        .fetch_schema(table_name)
+        .await?
+        .ok_or_else(|| InsertError::TableNotFound(table_name.to_owned()))?;
     let Schema {
         column_defs,
+        indexes,
+        key_constraints,
         foreign_keys,
+        checks,
         ..
-    } = storage
-        .fetch_schema(table_name)
-        .await?
-        .ok_or_else(|| InsertError::TableNotFound(table_name.to_owned()))?;
+    } = &schema;
+    let labels: Option<Arc<[String]>> = column_defs.as_deref().map(|column_defs| {
+        column_defs
+            .iter()
+            .map(|column_def| column_def.name.to_owned())
+            .collect()
+    });
 
-    let rows = match column_defs {
+    let (rows, updated_rows) = match column_defs {
         Some(column_defs) => {
-            fetch_vec_rows(
+            let column_defs = Arc::from(column_defs.as_slice());
+            let rows = fetch_vec_rows(
                 storage,
                 table_name,
-                column_defs,
+                &column_defs,
                 columns,
                 source,
-                foreign_keys,
+                filter_context.as_ref(),
+            )
+            .await?;
+
+            let (rows, updated_rows) = match on_conflict {
+                Some(on_conflict) => {
+                    on_conflict::resolve(
+                        storage,
+                        &schema,
+                        on_conflict,
+                        rows,
+                        filter_context.as_ref(),
+                    )
+                    .await?
+                }
+                None => {
+                    validate_unique(
+                        storage,
+                        table_name,
+                        ColumnValidation::All(&column_defs),
+                        key_constraints,
+                        indexes,
+                        rows.iter().map(|values| values.as_slice()),
+                    )
+                    .await?;
+
+                    (rows, Vec::new())
+                }
+            };
+
+            let updated_values = updated_rows.iter().filter_map(|(_, row)| match row {
+                DataRow::Vec(values) => Some(values.as_slice()),
+                DataRow::Map(_) => None,
+            });
+            validate_check(
+                table_name,
+                &column_defs,
+                checks,
+                rows.iter().map(Vec::as_slice).chain(updated_values),
+            )
+            .await?;
+            validate_foreign_key(storage, &column_defs, foreign_keys, &rows).await?;
+
+            if let Some(OnConflict {
+                action: OnConflictAction::DoUpdate { assignments, .. },
+                ..
+            }) = on_conflict
+            {
+                let columns = assignments
+                    .iter()
+                    .map(|assignment| assignment.id.to_owned())
+                    .collect::<Vec<_>>();
+                let updated_values = updated_rows.iter().filter_map(|(_, row)| match row {
+                    DataRow::Vec(values) => Some(values.as_slice()),
+                    DataRow::Map(_) => None,
+                });
+
+                validate_foreign_keys(
+                    storage,
+                    &column_defs,
+                    foreign_keys,
+                    &columns,
+                    updated_values,
+                )
+                .await?;
+            }
+
+            (
+                vec_rows_data(&column_defs, key_constraints, rows)?,
+                updated_rows,
             )
-            .await
         }
-        None => fetch_map_rows(storage, source).await.map(RowsData::Append),
-    }?;
+        None => {
+            if let Some(on_conflict) = on_conflict {
+                on_conflict::unique_constraints(&[], &[], on_conflict)?;
+            }
 
-    match rows {
-        RowsData::Append(rows) => {
-            let num_rows = rows.len();
+            let rows = fetch_map_rows(storage, source, filter_context.as_ref()).await?;
 
-            storage
-                .append_data(table_name, rows)
-                .await
-                .map(|_| num_rows)
+            (RowsData::Append(rows), Vec::new())
         }
-        RowsData::Insert(rows) => {
-            let num_rows = rows.len();
+    };
 
-            storage
-                .insert_data(table_name, rows)
-                .await
-                .map(|_| num_rows)
+    let inserted_rows: Vec<&DataRow> = match &rows {
+        RowsData::Append(rows) => rows.iter().collect(),
+        RowsData::Insert(rows) => rows.iter().map(|(_, row)| row).collect(),
+    };
+    let triggers = Triggers::fetch(storage, table_name, TriggerEvent::Insert).await?;
+    let fired_rows = match triggers.is_empty() {
+        true => Vec::new(),
+        false => inserted_rows
+            .iter()
+            .map(|row| data_row_to_row((*row).clone(), labels.as_ref()))
+            .collect(),
+    };
+    let fired_rows = fired_rows
+        .iter()
+        .map(|row| (None, Some(row)))
+        .collect::<Vec<_>>();
+    triggers
+        .fire(
+            storage,
+            TriggerTiming::Before,
+            &fired_rows,
+            filter_context.as_ref(),
+        )
+        .await?;
+
+    let payload = match returning_items.is_empty() {
+        true => Payload::Insert(inserted_rows.len() + updated_rows.len()),
+        false => {
+            let returned_rows = inserted_rows
+                .into_iter()
+                .chain(updated_rows.iter().map(|(_, row)| row))
+                .map(|row| data_row_to_row(row.clone(), labels.as_ref()))
+                .collect();
+
+            returning(
+                storage,
+                table_name,
+                labels.as_deref(),
+                returning_items,
+                returned_rows,
+                filter_context.as_ref().map(Arc::clone),
+            )
+            .await?
         }
+    };
+
+    if !updated_rows.is_empty() {
+        storage.insert_data(table_name, updated_rows).await?;
     }
+
+    match rows {
+        RowsData::Append(rows) => storage.append_data(table_name, rows).await?,
+        RowsData::Insert(rows) => storage.insert_data(table_name, rows).await?,
+    }
+
+    triggers
+        .fire(
+            storage,
+            TriggerTiming::After,
+            &fired_rows,
+            filter_context.as_ref(),
+        )
+        .await?;
+
+    Ok(payload)
 }
 
-async fn fetch_vec_rows<T: GStore>(
-    storage: &T,
-    table_name: &str,
-    column_defs: Vec<ColumnDef>,
+async fn fetch_vec_rows<'a, T: GStore>(
+    storage: &'a T,
+    table_name: &'a str,
+    column_defs: &Arc<[ColumnDef]>,
     columns: &[String],
-    source: &Query,
-    foreign_keys: Vec<ForeignKey>,
-) -> Result<RowsData> {
+    source: &'a Query,
+    filter_context: Option<&Arc<RowContext<'a>>>,
+) -> Result<Vec<Vec<Value>>> {
     let labels = Arc::from(
         column_defs
             .iter()
             .map(|column_def| column_def.name.to_owned())
             .collect::<Vec<_>>(),
     );
-    let column_defs = Arc::from(column_defs);
-    let column_validation = ColumnValidation::All(&column_defs);
 
     #[derive(futures_enum::Stream)]
     enum Rows<I1, I2> {
@@ -130,17 +293,32 @@ async fn fetch_vec_rows<T: GStore>(
         Select(I2),
     }
 
-    let rows = match &source.body {
+    match &source.body {
         SetExpr::Values(Values(values_list)) => {
-            let limit = Limit::new(source.limit.as_ref(), source.offset.as_ref()).await?;
+            let limit = Limit::new(
+                source.limit.as_ref(),
+                source.offset.as_ref(),
+                filter_context,
+            )
+            .await?;
             let rows = stream::iter(values_list).then(|values| {
-                let column_defs = Arc::clone(&column_defs);
+                let column_defs = Arc::clone(column_defs);
                 let labels = Arc::clone(&labels);
 
                 async move {
+                    let values = fill_values(
+                        storage,
+                        table_name,
+                        &column_defs,
+                        columns,
+                        values.iter().map(Given::Expr).collect(),
+                        filter_context,
+                    )
+                    .await?;
+
                     Ok(Row::Vec {
                         columns: labels,
-                        values: fill_values(&column_defs, columns, values).await?,
+                        values,
                     })
                 }
             });
@@ -149,56 +327,43 @@ async fn fetch_vec_rows<T: GStore>(
 
             Rows::Values(rows)
         }
-        SetExpr::Select(_) => {
-            let rows = select(storage, source, None).await?.map(|row| {
-                let values = row?.try_into_vec()?;
-
-                column_defs
-                    .iter()
-                    .zip(values.iter())
-                    .try_for_each(|(column_def, value)| {
-                        let ColumnDef {
-                            data_type,
-                            nullable,
-                            ..
-                        } = column_def;
-
-                        value.validate_type(data_type)?;
-                        value.validate_null(*nullable)
-                    })?;
-
-                Ok(values)
-            });
+        SetExpr::Select(_) | SetExpr::SetOperation { .. } => {
+            let rows = select(storage, source, filter_context.map(Arc::clone))
+                .await?
+                .and_then(|row| {
+                    let column_defs = Arc::clone(column_defs);
+
+                    async move {
+                        let values = row.try_into_vec()?;
+
+                        fill_values(
+                            storage,
+                            table_name,
+                            &column_defs,
+                            columns,
+                            values.into_iter().map(Given::Value).collect(),
+                            filter_context,
+                        )
+                        .await
+                    }
+                });
 
             Rows::Select(rows)
         }
     }
     .try_collect::<Vec<Vec<Value>>>()
-    .await?;
-
-    validate_unique(
-        storage,
-        table_name,
-        column_validation,
-        rows.iter().map(|values| values.as_slice()),
-    )
-    .await?;
-
-    validate_foreign_key(storage, &column_defs, foreign_keys, &rows).await?;
-
-    let primary_key = column_defs.iter().position(|ColumnDef { unique, .. }| {
-        unique == &Some(ColumnUniqueOption { is_primary: true })
-    });
+    .await
+}
 
-    match primary_key {
-        Some(i) => rows
+fn vec_rows_data(
+    column_defs: &[ColumnDef],
+    key_constraints: &[KeyConstraint],
+    rows: Vec<Vec<Value>>,
+) -> Result<RowsData> {
+    match primary_key_indexes(column_defs, key_constraints) {
+        Some(indexes) => rows
             .into_iter()
-            .filter_map(|values| {
-                values
-                    .get(i)
-                    .map(Key::try_from)
-                    .map(|result| result.map(|key| (key, values.into())))
-            })
+            .map(|values| Key::from_columns(&indexes, &values).map(|key| (key, values.into())))
             .collect::<Result<Vec<_>>>()
             .map(RowsData::Insert),
         None => Ok(RowsData::Append(rows.into_iter().map(Into::into).collect())),
@@ -208,7 +373,7 @@ async fn fetch_vec_rows<T: GStore>(
 async fn validate_foreign_key<T: GStore>(
     storage: &T,
     column_defs: &Arc<[ColumnDef]>,
-    foreign_keys: Vec<ForeignKey>,
+    foreign_keys: &[ForeignKey],
     rows: &[Vec<Value>],
 ) -> Result<()> {
     for foreign_key in foreign_keys {
@@ -217,7 +382,7 @@ async fn validate_foreign_key<T: GStore>(
             referenced_table_name,
             referenced_column_name,
             ..
-        } = &foreign_key;
+        } = foreign_key;
 
         let target_index = column_defs
             .iter()
@@ -227,37 +392,33 @@ async fn validate_foreign_key<T: GStore>(
                 InsertError::ConflictReferencingColumnName(referencing_column_name.to_owned())
             })?;
 
-        for row in rows.iter() {
-            let value =
-                row.get(target_index.0)
-                    .ok_or(InsertError::ConflictReferencingColumnName(
-                        referencing_column_name.to_owned(),
-                    ))?;
-
-            if value == &Value::Null {
-                continue;
-            }
-
-            let no_referenced = storage
-                .fetch_data(referenced_table_name, &Key::try_from(value)?)
-                .await?
-                .is_none();
+        let values = rows
+            .iter()
+            .map(|row| {
+                row.get(target_index.0).cloned().ok_or_else(|| {
+                    InsertError::ConflictReferencingColumnName(referencing_column_name.to_owned())
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
 
-            if no_referenced {
-                return Err(InsertError::CannotFindReferencedValue {
-                    table_name: referenced_table_name.to_owned(),
-                    column_name: referenced_column_name.to_owned(),
-                    referenced_value: String::from(value),
-                }
-                .into());
+        if let Some(value) = find_unreferenced(storage, foreign_key, values).await? {
+            return Err(InsertError::CannotFindReferencedValue {
+                table_name: referenced_table_name.to_owned(),
+                column_name: referenced_column_name.to_owned(),
+                referenced_value: String::from(value),
             }
+            .into());
         }
     }
 
     Ok(())
 }
 
-async fn fetch_map_rows<T: GStore>(storage: &T, source: &Query) -> Result<Vec<DataRow>> {
+async fn fetch_map_rows<'a, T: GStore>(
+    storage: &'a T,
+    source: &'a Query,
+    filter_context: Option<&Arc<RowContext<'a>>>,
+) -> Result<Vec<DataRow>> {
     #[derive(futures_enum::Stream)]
     enum Rows<I1, I2> {
         Values(I1),
@@ -266,13 +427,19 @@ async fn fetch_map_rows<T: GStore>(storage: &T, source: &Query) -> Result<Vec<Da
 
     let rows = match &source.body {
         SetExpr::Values(Values(values_list)) => {
-            let limit = Limit::new(source.limit.as_ref(), source.offset.as_ref()).await?;
+            let limit = Limit::new(
+                source.limit.as_ref(),
+                source.offset.as_ref(),
+                filter_context,
+            )
+            .await?;
             let rows = stream::iter(values_list).then(|values| async move {
                 if values.len() > 1 {
                     return Err(InsertError::OnlySingleValueAcceptedForSchemalessRow.into());
                 }
 
-                evaluate_stateless(None, &values[0])
+                let context = filter_context.and_then(|context| context.root_context());
+                evaluate_stateless(context, &values[0])
                     .await?
                     .try_into()
                     .map(Row::Map)
@@ -282,8 +449,9 @@ async fn fetch_map_rows<T: GStore>(storage: &T, source: &Query) -> Result<Vec<Da
 
             Rows::Values(rows)
         }
-        SetExpr::Select(_) => {
-            let rows = select(storage, source, None).await?.map(|row| {
+        SetExpr::Select(_) | SetExpr::SetOperation { .. } => {
+            let filter_context = filter_context.map(Arc::clone);
+            let rows = select(storage, source, filter_context).await?.map(|row| {
                 let row = row?;
 
                 if let Row::Vec { values, .. } = &row {
@@ -306,10 +474,23 @@ async fn fetch_map_rows<T: GStore>(storage: &T, source: &Query) -> Result<Vec<Da
     Ok(rows)
 }
 
-async fn fill_values(
+/// Value the source of an INSERT gives for a column
+enum Given<'a> {
+    /// Expression of a VALUES list, evaluated against the column type
+    Expr(&'a Expr),
+    /// Value of a row returned by a query, which must already match the column type
+    Value(Value),
+}
+
+/// Builds a row in the column order of `column_defs` from the values given for `columns`,
+/// filling the columns left out with their identity, default or generated values.
+async fn fill_values<T: GStore>(
+    storage: &T,
+    table_name: &str,
     column_defs: &[ColumnDef],
     columns: &[String],
-    values: &[Expr],
+    values: Vec<Given<'_>>,
+    filter_context: Option<&Arc<RowContext<'_>>>,
 ) -> Result<Vec<Value>> {
     if !columns.is_empty() && values.len() != columns.len() {
         return Err(InsertError::ColumnAndValuesNotMatched.into());
@@ -348,18 +529,62 @@ async fn fill_values(
                     name: def_name,
                     data_type,
                     nullable,
+                    identity,
+                    generated,
                     ..
                 } = column_def;
 
                 let value = column_name_value_list
                     .iter()
                     .find(|(name, _)| name == &def_name)
-                    .map(|(_, value)| value);
+                    .map(|(_, value)| *value);
+
+                if generated.is_some() {
+                    return match value {
+                        Some(_) => Err(InsertError::CannotInsertIntoGeneratedColumn(
+                            def_name.to_owned(),
+                        )
+                        .into()),
+                        // filled by `fill_generated` once the other columns have their values
+                        None => Ok(Value::Null),
+                    };
+                }
+
+                match (value, identity) {
+                    (Some(_), Some(ColumnIdentity { always: true, .. })) => {
+                        return Err(InsertError::IdentityColumnGeneratedAlways(
+                            def_name.to_owned(),
+                        )
+                        .into());
+                    }
+                    (None, Some(_)) => {
+                        let sequence_name = ColumnIdentity::sequence_name(table_name, def_name);
+                        let value = storage
+                            .next_sequence_value(&sequence_name)
+                            .await?
+                            .ok_or(SequenceError::NotFound(sequence_name))?;
+
+                        return Value::I64(value).cast(data_type);
+                    }
+                    _ => {}
+                }
 
                 match (value, &column_def.default, nullable) {
-                    (Some(&expr), _, _) | (None, Some(expr), _) => evaluate_stateless(None, expr)
-                        .await?
-                        .try_into_value(data_type, *nullable),
+                    (Some(Given::Value(value)), _, _) => {
+                        value.validate_type(data_type)?;
+                        value.validate_null(*nullable)?;
+
+                        Ok(value.clone())
+                    }
+                    (Some(&Given::Expr(expr)), _, _) | (None, Some(expr), _) => {
+                        let context = filter_context.and_then(|context| context.root_context());
+                        let evaluated = match uses_sequence(expr) {
+                            true => evaluate(storage, context.map(Arc::new), None, expr).await?,
+                            false => evaluate_stateless(context, expr).await?,
+                        };
+
+                        evaluated.try_into_value(data_type, *nullable)
+                    }
                     (None, None, true) => Ok(Value::Null),
                     (None, None, false) => {
                         Err(InsertError::LackOfRequiredColumn(def_name.to_owned()).into())
@@ -370,5 +595,47 @@ async fn fill_values(
         .try_collect::<Vec<Value>>()
         .await?;
 
+    fill_generated(column_defs, values).await
+}
+
+/// Computes the generated columns of a row from its other columns.
+pub(super) async fn fill_generated(
+    column_defs: &[ColumnDef],
+    mut values: Vec<Value>,
+) -> Result<Vec<Value>> {
+    if column_defs
+        .iter()
+        .all(|column_def| column_def.generated.is_none())
+    {
+        return Ok(values);
+    }
+
+    let columns = column_defs
+        .iter()
+        .map(|column_def| column_def.name.to_owned())
+        .collect::<Vec<_>>();
+
+    for (i, column_def) in column_defs.iter().enumerate() {
+        let ColumnDef {
+            data_type,
+            nullable,
+            generated: Some(expr),
+            ..
+        } = column_def
+        else {
+            continue;
+        };
+
+        let context = RowContext::RefVecData {
+            columns: &columns,
+            values: &values,
+        };
+        let value = evaluate_stateless(Some(context), expr)
+            .await?
+            .try_into_value(data_type, *nullable)?;
+
+        values[i] = value;
+    }
+
     Ok(values)
 }
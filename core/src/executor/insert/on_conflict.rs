@@ -0,0 +1,395 @@
+use {
+    super::InsertError,
+    crate::{
+        ast::{ColumnDef, IndexOperator, KeyConstraint, OnConflict, OnConflictAction},
+        data::{Key, Row, Schema, SchemaIndex, Value},
+        executor::{
+            context::RowContext,
+            filter::check_expr,
+            update::Update,
+            validate::{ValidateError, duplicate_entry, unique_columns, unique_index, unique_key},
+        },
+        result::Result,
+        store::{DataRow, GStore},
+    },
+    futures::stream::TryStreamExt,
+    std::{
+        borrow::Cow,
+        collections::{HashMap, HashSet},
+        sync::Arc,
+    },
+};
+
+/// Unique constraint given as column indexes, flagged when conflicts on it are handled by
+/// the `ON CONFLICT` action instead of failing the statement.
+pub struct UniqueConstraint {
+    columns: Vec<usize>,
+    is_primary: bool,
+    arbiter: bool,
+}
+
+pub fn unique_constraints(
+    column_defs: &[ColumnDef],
+    key_constraints: &[KeyConstraint],
+    on_conflict: &OnConflict,
+) -> Result<Vec<UniqueConstraint>> {
+    let OnConflict { target, action } = on_conflict;
+    let mut constraints = unique_columns(column_defs, key_constraints)
+        .into_iter()
+        .map(|(columns, is_primary)| UniqueConstraint {
+            columns,
+            is_primary,
+            arbiter: target.is_empty(),
+        })
+        .collect::<Vec<_>>();
+
+    if target.is_empty() {
+        return match action {
+            OnConflictAction::DoNothing => Ok(constraints),
+            OnConflictAction::DoUpdate { .. } => Err(InsertError::ConflictTargetRequired.into()),
+        };
+    }
+
+    let arbiter = constraints.iter_mut().find(|constraint| {
+        constraint.columns.len() == target.len()
+            && constraint
+                .columns
+                .iter()
+                .all(|index| target.contains(&column_defs[*index].name))
+    });
+
+    match arbiter {
+        Some(constraint) => {
+            constraint.arbiter = true;
+
+            Ok(constraints)
+        }
+        None => Err(InsertError::ConflictTargetNotUnique(target.join(", ")).into()),
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Owner {
+    /// Index of the stored row
+    Stored(usize),
+    Inserted,
+}
+
+/// How the stored rows holding a key of a unique constraint are found
+enum Lookup<'a> {
+    PrimaryKey,
+    Index(&'a str),
+}
+
+/// Rows owning the keys of each unique constraint. Stored rows are fetched by key or looked
+/// up in a unique index when their keys are first asked for, unless a constraint has neither
+/// and the table is scanned once for the whole statement.
+struct Owners<'a, T: GStore> {
+    storage: &'a T,
+    table_name: &'a str,
+    constraints: &'a [UniqueConstraint],
+    /// `None` when the table is scanned
+    lookups: Option<Vec<Lookup<'a>>>,
+    /// Owner of each key of each constraint, `None` for keys no row holds anymore
+    owners: Vec<HashMap<Key, Option<Owner>>>,
+    stored: Vec<(Key, Vec<Value>)>,
+    loaded: HashSet<Key>,
+}
+
+impl<'a, T: GStore> Owners<'a, T> {
+    async fn new(
+        storage: &'a T,
+        table_name: &'a str,
+        column_defs: &[ColumnDef],
+        indexes: &'a [SchemaIndex],
+        constraints: &'a [UniqueConstraint],
+    ) -> Result<Self> {
+        let lookups = constraints
+            .iter()
+            .map(
+                |UniqueConstraint {
+                     columns,
+                     is_primary,
+                     ..
+                 }| match is_primary {
+                    true => Some(Lookup::PrimaryKey),
+                    false => unique_index(indexes, column_defs, columns).map(Lookup::Index),
+                },
+            )
+            .collect::<Option<Vec<_>>>();
+        let mut owners = Self {
+            storage,
+            table_name,
+            constraints,
+            lookups,
+            owners: constraints.iter().map(|_| HashMap::new()).collect(),
+            stored: Vec::new(),
+            loaded: HashSet::new(),
+        };
+
+        if owners.lookups.is_none() {
+            let rows = storage
+                .scan_data(table_name)
+                .await?
+                .try_collect::<Vec<_>>()
+                .await?;
+
+            for (key, data_row) in rows {
+                owners.load(key, data_row)?;
+            }
+        }
+
+        Ok(owners)
+    }
+
+    /// Registers a stored row as the owner of its keys, unless they are already known.
+    fn load(&mut self, key: Key, data_row: DataRow) -> Result<()> {
+        let values = match data_row {
+            DataRow::Vec(values) => values,
+            DataRow::Map(_) => {
+                return Err(ValidateError::ConflictOnUnexpectedSchemalessRowFound.into());
+            }
+        };
+
+        if !self.loaded.insert(key.clone()) {
+            return Ok(());
+        }
+
+        let index = self.stored.len();
+        for (constraint, owners) in self.constraints.iter().zip(self.owners.iter_mut()) {
+            if let Some(key) = unique_key(&constraint.columns, &values)? {
+                owners.entry(key).or_insert(Some(Owner::Stored(index)));
+            }
+        }
+        self.stored.push((key, values));
+
+        Ok(())
+    }
+
+    /// Row holding the key of `values` on the constraint at `index`, looked up in the
+    /// storage when the key was never asked for.
+    async fn get(&mut self, index: usize, values: &[Value]) -> Result<Option<Owner>> {
+        let Some(key) = unique_key(&self.constraints[index].columns, values)? else {
+            return Ok(None);
+        };
+
+        if let Some(owner) = self.owners[index].get(&key) {
+            return Ok(*owner);
+        }
+
+        let found = match self.lookups.as_ref().map(|lookups| &lookups[index]) {
+            None => None,
+            Some(Lookup::PrimaryKey) => self
+                .storage
+                .fetch_data(self.table_name, &key)
+                .await?
+                .map(|data_row| (key.clone(), data_row)),
+            Some(Lookup::Index(index_name)) => {
+                let mut prefix = self.constraints[index]
+                    .columns
+                    .iter()
+                    .map(|index| values[*index].clone())
+                    .collect::<Vec<_>>();
+                let value = prefix.pop().unwrap_or(Value::Null);
+
+                self.storage
+                    .scan_indexed_data(
+                        self.table_name,
+                        index_name,
+                        None,
+                        &prefix,
+                        Some((&IndexOperator::Eq, value)),
+                    )
+                    .await?
+                    .try_next()
+                    .await?
+            }
+        };
+
+        if let Some((stored_key, data_row)) = found {
+            self.load(stored_key, data_row)?;
+        }
+
+        Ok(*self.owners[index].entry(key).or_insert(None))
+    }
+
+    /// Makes `owner` the owner of the keys of `values`, failing on keys another row holds.
+    async fn register(
+        &mut self,
+        column_defs: &[ColumnDef],
+        values: &[Value],
+        owner: Owner,
+    ) -> Result<()> {
+        for index in 0..self.constraints.len() {
+            let Some(key) = unique_key(&self.constraints[index].columns, values)? else {
+                continue;
+            };
+
+            if self
+                .get(index, values)
+                .await?
+                .is_some_and(|found| found != owner)
+            {
+                let UniqueConstraint {
+                    columns,
+                    is_primary,
+                    ..
+                } = &self.constraints[index];
+
+                return Err(duplicate_entry(column_defs, columns, *is_primary, values)?.into());
+            }
+
+            self.owners[index].insert(key, Some(owner));
+        }
+
+        Ok(())
+    }
+
+    /// Frees the keys the stored row at `index` holds before it is updated.
+    fn vacate(&mut self, index: usize) -> Result<()> {
+        let (_, values) = &self.stored[index];
+
+        for (constraint, owners) in self.constraints.iter().zip(self.owners.iter_mut()) {
+            if let Some(key) = unique_key(&constraint.columns, values)? {
+                owners.insert(key, None);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Splits `rows` into the ones to insert and the stored rows updated by `DO UPDATE`.
+/// Every unique constraint is checked here against both stored and new rows, so the
+/// statement fails on conflicts not covered by the conflict target.
+pub async fn resolve<T: GStore>(
+    storage: &T,
+    schema: &Schema,
+    on_conflict: &OnConflict,
+    rows: Vec<Vec<Value>>,
+    filter_context: Option<&Arc<RowContext<'_>>>,
+) -> Result<(Vec<Vec<Value>>, Vec<(Key, DataRow)>)> {
+    let Schema {
+        table_name,
+        column_defs,
+        indexes,
+        key_constraints,
+        ..
+    } = schema;
+    let column_defs = column_defs.as_deref().unwrap_or_default();
+    let constraints = unique_constraints(column_defs, key_constraints, on_conflict)?;
+    let labels: Arc<[String]> = column_defs
+        .iter()
+        .map(|column_def| column_def.name.to_owned())
+        .collect();
+    let update = match &on_conflict.action {
+        OnConflictAction::DoNothing => None,
+        OnConflictAction::DoUpdate {
+            assignments,
+            selection,
+        } => Some((
+            Update::new(
+                storage,
+                table_name,
+                assignments,
+                Some(column_defs),
+                key_constraints,
+            )?,
+            selection.as_ref(),
+        )),
+    };
+
+    if constraints.is_empty() {
+        return Ok((rows, Vec::new()));
+    }
+
+    let mut owners = Owners::new(storage, table_name, column_defs, indexes, &constraints).await?;
+    let mut inserted_rows = Vec::new();
+    let mut updated = HashSet::new();
+    for values in rows {
+        let mut conflict = None;
+        for (index, constraint) in constraints.iter().enumerate() {
+            if !constraint.arbiter {
+                continue;
+            }
+
+            conflict = owners.get(index, &values).await?;
+
+            if conflict.is_some() {
+                break;
+            }
+        }
+
+        let owner = match conflict {
+            Some(owner) => owner,
+            None => {
+                owners
+                    .register(column_defs, &values, Owner::Inserted)
+                    .await?;
+                inserted_rows.push(values);
+                continue;
+            }
+        };
+        let Some((update, selection)) = &update else {
+            continue;
+        };
+        let index = match owner {
+            Owner::Stored(index) if !updated.contains(&index) => index,
+            Owner::Stored(_) | Owner::Inserted => {
+                return Err(InsertError::ConflictRowAffectedTwice.into());
+            }
+        };
+
+        let stored_row = Row::Vec {
+            columns: Arc::clone(&labels),
+            values: owners.stored[index].1.clone(),
+        };
+        let excluded_row = Row::Vec {
+            columns: Arc::clone(&labels),
+            values,
+        };
+        // `EXCLUDED` is a keyword, so both spellings refer to the proposed row.
+        let excluded = RowContext::new(
+            "excluded",
+            Cow::Borrowed(&excluded_row),
+            filter_context.map(Arc::clone),
+        );
+        let excluded = RowContext::new(
+            "EXCLUDED",
+            Cow::Borrowed(&excluded_row),
+            Some(Arc::new(excluded)),
+        );
+        let excluded = Some(Arc::new(excluded));
+
+        if let Some(expr) = selection {
+            let context = RowContext::new(
+                table_name,
+                Cow::Borrowed(&stored_row),
+                excluded.as_ref().map(Arc::clone),
+            );
+
+            if !check_expr(storage, Some(Arc::new(context)), None, expr).await? {
+                continue;
+            }
+        }
+
+        let values = update.apply(stored_row, excluded).await?.try_into_vec()?;
+
+        owners.vacate(index)?;
+        owners
+            .register(column_defs, &values, Owner::Stored(index))
+            .await?;
+        owners.stored[index].1 = values;
+        updated.insert(index);
+    }
+
+    let updated_rows = owners
+        .stored
+        .into_iter()
+        .enumerate()
+        .filter(|(index, _)| updated.contains(index))
+        .map(|(_, (key, values))| (key, DataRow::Vec(values)))
+        .collect();
+
+    Ok((inserted_rows, updated_rows))
+}
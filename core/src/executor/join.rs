@@ -6,8 +6,14 @@ use {
             JoinOperator as AstJoinOperator, TableFactor,
         },
         data::{Key, Row, Value, get_alias},
-        executor::{context::RowContext, evaluate::evaluate, filter::check_expr},
-        result::Result,
+        executor::{
+            context::RowContext,
+            evaluate::evaluate,
+            explain::{Counter, track},
+            filter::check_expr,
+            guard::{watch, watch_buffered},
+        },
+        result::{Error, Result},
         store::GStore,
     },
     futures::{
@@ -15,34 +21,53 @@ use {
         stream::{self, Stream, StreamExt, TryStreamExt, empty, once},
     },
     itertools::Itertools,
+    serde::Serialize,
     std::{
         borrow::Cow,
         collections::{BTreeMap, HashMap},
         pin::Pin,
-        sync::Arc,
+        sync::{Arc, Mutex},
     },
+    thiserror::Error as ThisError,
     utils::OrStream,
 };
 
+#[derive(ThisError, Serialize, Debug, PartialEq, Eq)]
+pub enum JoinError {
+    #[error("column specified in USING clause does not exist: {0}")]
+    UsingColumnNotFound(String),
+
+    #[error("NATURAL JOIN requires both sides to have a schema")]
+    NaturalJoinRequiresSchema,
+}
+
 pub struct Join<'a, T: GStore> {
     storage: &'a T,
+    relation: &'a TableFactor,
     join_clauses: &'a [AstJoin],
     filter_context: Option<Arc<RowContext<'a>>>,
+    /// Counters of each join for `EXPLAIN ANALYZE`
+    counters: Option<&'a [Counter]>,
 }
 
 type JoinItem<'a> = Arc<RowContext<'a>>;
+type Columns = Option<Arc<[String]>>;
 type Joined<'a> = Pin<Box<dyn Stream<Item = Result<JoinItem<'a>>> + Send + 'a>>;
 
 impl<'a, T: GStore> Join<'a, T> {
     pub fn new(
         storage: &'a T,
+        relation: &'a TableFactor,
         join_clauses: &'a [AstJoin],
         filter_context: Option<Arc<RowContext<'a>>>,
+        counters: Option<&'a [Counter]>,
     ) -> Self {
         Self {
             storage,
+            relation,
             join_clauses,
             filter_context,
+            counters,
         }
     }
 
@@ -51,72 +76,261 @@ impl<'a, T: GStore> Join<'a, T> {
         rows: impl Stream<Item = Result<RowContext<'a>>> + Send + 'a,
     ) -> Result<Joined<'a>> {
         let init_rows: Joined = Box::pin(rows.map(|row| row.map(Arc::new)));
+        let columns = fetch_relation_columns(
+            self.storage,
+            self.relation,
+            self.filter_context.as_ref().map(Arc::clone),
+        )
+        .await?
+        .map(Arc::from);
+        let layout = Layout {
+            relation: (get_alias(self.relation), columns),
+            joins: Vec::new(),
+        };
 
-        stream::iter(self.join_clauses)
+        let (rows, _) = stream::iter(self.join_clauses.iter().enumerate())
             .map(Ok)
-            .try_fold(init_rows, |rows, join_clause| {
+            .try_fold((init_rows, layout), |(rows, layout), (i, join_clause)| {
                 let filter_context = self.filter_context.as_ref().map(Arc::clone);
+                let counter = self.counters.and_then(|counters| counters.get(i));
+
+                async move {
+                    let (rows, layout) =
+                        join(self.storage, filter_context, join_clause, layout, rows).await?;
+                    let rows: Joined = Box::pin(track(counter, rows));
+
+                    Ok::<_, Error>((rows, layout))
+                }
+            })
+            .await?;
+
+        Ok(rows)
+    }
+}
+
+/// Aliases and columns of the relations joined so far, in the order their contexts are nested.
+struct Layout<'a> {
+    relation: (&'a str, Columns),
+    /// Alias, columns and merged `USING` columns of each join
+    joins: Vec<(&'a str, Columns, Columns)>,
+}
+
+impl<'a> Layout<'a> {
+    fn columns(&self) -> Option<Vec<String>> {
+        let (_, columns) = &self.relation;
+        let mut labels = columns.as_deref()?.to_vec();
+
+        for (_, columns, using) in &self.joins {
+            let columns = columns.as_deref()?;
+
+            labels = match using {
+                Some(using) => coalesce_columns(&labels, columns, using),
+                None => labels.into_iter().chain(columns.iter().cloned()).collect(),
+            };
+        }
+
+        Some(labels)
+    }
 
-                async move { join(self.storage, filter_context, join_clause, rows).await }
+    /// Builds the left side of a row which has no match in RIGHT and FULL joins.
+    fn null_context(&self) -> Arc<RowContext<'a>> {
+        let (table_alias, columns) = &self.relation;
+        let row = null_row(columns.as_ref());
+        let context = Arc::new(RowContext::new(table_alias, Cow::Owned(row), None));
+
+        self.joins
+            .iter()
+            .fold(context, |context, (table_alias, columns, using)| {
+                let row = null_row(columns.as_ref());
+                let context =
+                    Arc::new(RowContext::new(table_alias, Cow::Owned(row), Some(context)));
+
+                match using {
+                    Some(using) => Arc::new(RowContext::using(null_row(Some(using)), context)),
+                    None => context,
+                }
             })
-            .await
     }
 }
 
+fn null_row(columns: Option<&Arc<[String]>>) -> Row {
+    match columns {
+        Some(columns) => Row::Vec {
+            columns: Arc::clone(columns),
+            values: columns.iter().map(|_| Value::Null).collect(),
+        },
+        None => Row::Map(BTreeMap::new()),
+    }
+}
+
+/// Returns the columns merged by `USING` or `NATURAL`, or `None` for the other constraints.
+pub fn using_columns(
+    join_operator: &AstJoinOperator,
+    left: Option<&[String]>,
+    right: Option<&[String]>,
+) -> Result<Option<Vec<String>>> {
+    let join_constraint = match join_operator {
+        AstJoinOperator::Inner(join_constraint)
+        | AstJoinOperator::LeftOuter(join_constraint)
+        | AstJoinOperator::RightOuter(join_constraint)
+        | AstJoinOperator::FullOuter(join_constraint) => join_constraint,
+        AstJoinOperator::Cross => return Ok(None),
+    };
+
+    match join_constraint {
+        JoinConstraint::Using(using) => {
+            for column in using {
+                let exists = |columns: Option<&[String]>| {
+                    columns.is_none_or(|columns| columns.contains(column))
+                };
+
+                if !exists(left) || !exists(right) {
+                    return Err(JoinError::UsingColumnNotFound(column.to_owned()).into());
+                }
+            }
+
+            Ok(Some(using.clone()))
+        }
+        JoinConstraint::Natural => {
+            let (Some(left), Some(right)) = (left, right) else {
+                return Err(JoinError::NaturalJoinRequiresSchema.into());
+            };
+            let using = left
+                .iter()
+                .filter(|column| right.contains(column))
+                .unique()
+                .cloned()
+                .collect::<Vec<_>>();
+
+            Ok((!using.is_empty()).then_some(using))
+        }
+        JoinConstraint::On(_) | JoinConstraint::None => Ok(None),
+    }
+}
+
+/// Merged columns come first, followed by the remaining columns of each side.
+pub fn coalesce_columns(left: &[String], right: &[String], using: &[String]) -> Vec<String> {
+    using
+        .iter()
+        .chain(
+            left.iter()
+                .chain(right)
+                .filter(|column| !using.contains(column)),
+        )
+        .cloned()
+        .collect()
+}
+
 async fn join<'a, T: GStore>(
     storage: &'a T,
     filter_context: Option<Arc<RowContext<'a>>>,
     ast_join: &'a AstJoin,
+    mut layout: Layout<'a>,
     left_rows: impl Stream<Item = Result<JoinItem<'a>>> + Send + 'a,
-) -> Result<Joined<'a>> {
+) -> Result<(Joined<'a>, Layout<'a>)> {
     let AstJoin {
         relation,
-        join_operator,
+        join_operator: ast_join_operator,
         join_executor,
     } = ast_join;
 
+    let (join_operator, where_clause) = match ast_join_operator {
+        AstJoinOperator::Inner(join_constraint) => (JoinOperator::Inner, join_constraint),
+        AstJoinOperator::LeftOuter(join_constraint) => (JoinOperator::LeftOuter, join_constraint),
+        AstJoinOperator::RightOuter(join_constraint) => (JoinOperator::RightOuter, join_constraint),
+        AstJoinOperator::FullOuter(join_constraint) => (JoinOperator::FullOuter, join_constraint),
+        AstJoinOperator::Cross => (JoinOperator::Inner, &JoinConstraint::None),
+    };
+    let where_clause = match where_clause {
+        JoinConstraint::On(where_clause) => Some(where_clause),
+        JoinConstraint::Using(_) | JoinConstraint::Natural | JoinConstraint::None => None,
+    };
+
     let table_alias = get_alias(relation);
+    let guard = filter_context
+        .as_ref()
+        .and_then(|context| context.get_guard());
+    let columns =
+        fetch_relation_columns(storage, relation, filter_context.as_ref().map(Arc::clone))
+            .await?
+            .map(Arc::<[String]>::from);
+    let using = using_columns(
+        ast_join_operator,
+        layout.columns().as_deref(),
+        columns.as_deref(),
+    )?
+    .map(Arc::<[String]>::from);
+
     let join_executor = JoinExecutor::new(
         storage,
         relation,
         filter_context.as_ref().map(Arc::clone),
         join_executor,
+        join_operator.keeps_right(),
     )
     .await
     .map(Arc::new)?;
-
-    let (join_operator, where_clause) = match join_operator {
-        AstJoinOperator::Inner(JoinConstraint::None) => (JoinOperator::Inner, None),
-        AstJoinOperator::Inner(JoinConstraint::On(where_clause)) => {
-            (JoinOperator::Inner, Some(where_clause))
-        }
-        AstJoinOperator::LeftOuter(JoinConstraint::None) => (JoinOperator::LeftOuter, None),
-        AstJoinOperator::LeftOuter(JoinConstraint::On(where_clause)) => {
-            (JoinOperator::LeftOuter, Some(where_clause))
+    let matched = match join_executor.as_ref() {
+        JoinExecutor::Materialized(rows) | JoinExecutor::Hash { rows, .. }
+            if join_operator.keeps_right() =>
+        {
+            Some(Arc::new(Mutex::new(vec![false; rows.len()])))
         }
+        _ => None,
     };
 
-    let columns = fetch_relation_columns(storage, relation)
-        .await?
-        .map(Arc::from);
-    let rows = left_rows.and_then(move |project_context| {
-        let init_context = {
-            let init_row = match columns.as_ref() {
-                Some(columns) => Row::Vec {
-                    columns: Arc::clone(columns),
-                    values: columns.iter().map(|_| Value::Null).collect(),
-                },
-                None => Row::Map(BTreeMap::new()),
+    let unmatched_rows = {
+        let null_context = join_operator.keeps_right().then(|| layout.null_context());
+        let join_executor = Arc::clone(&join_executor);
+        let matched = matched.as_ref().map(Arc::clone);
+        let using = using.as_ref().map(Arc::clone);
+
+        once(async move {
+            let rows = match (null_context, join_executor.as_ref(), matched) {
+                (
+                    Some(null_context),
+                    JoinExecutor::Materialized(rows) | JoinExecutor::Hash { rows, .. },
+                    Some(matched),
+                ) => {
+                    let matched = matched.lock().unwrap_or_else(|error| error.into_inner());
+
+                    rows.iter()
+                        .zip(matched.iter())
+                        .filter(|(_, matched)| !**matched)
+                        .map(|(row, _)| {
+                            Ok(joined_context(
+                                table_alias,
+                                Cow::Owned(row.clone()),
+                                Some(Arc::clone(&null_context)),
+                                using.as_ref(),
+                            ))
+                        })
+                        .collect()
+                }
+                _ => Vec::new(),
             };
 
-            Arc::new(RowContext::new(
-                table_alias,
-                Cow::Owned(init_row),
-                Some(Arc::clone(&project_context)),
-            ))
-        };
+            Ok::<_, Error>(stream::iter(rows))
+        })
+        .try_flatten()
+    };
+
+    layout
+        .joins
+        .push((table_alias, columns.clone(), using.clone()));
+
+    let rows = left_rows.and_then(move |project_context| {
+        let init_context = joined_context(
+            table_alias,
+            Cow::Owned(null_row(columns.as_ref())),
+            Some(Arc::clone(&project_context)),
+            using.as_ref(),
+        );
         let filter_context = filter_context.as_ref().map(Arc::clone);
         let join_executor = Arc::clone(&join_executor);
+        let matched = matched.as_ref().map(Arc::clone);
+        let using = using.as_ref().map(Arc::clone);
+        let guard = guard.clone();
 
         async move {
             let filter_context = match filter_context {
@@ -136,8 +350,9 @@ async fn join<'a, T: GStore>(
             }
             let rows = match join_executor.as_ref() {
                 JoinExecutor::NestedLoop => {
-                    let rows = fetch_relation_rows(storage, relation, &filter_context)
-                        .await?
+                    let rows =
+                        fetch_relation_rows(storage, relation, &filter_context, None).await?;
+                    let rows = watch(guard, rows)
                         .and_then(|row| future::ok(Cow::Owned(row)))
                         .try_filter_map(move |row| {
                             check_where_clause(
@@ -146,16 +361,33 @@ async fn join<'a, T: GStore>(
                                 filter_context.as_ref().map(Arc::clone),
                                 Some(Arc::clone(&project_context)),
                                 where_clause,
+                                using.as_ref().map(Arc::clone),
                                 row,
                             )
                         });
                     Rows::NestedLoop(rows)
                 }
+                JoinExecutor::Materialized(rows) => {
+                    let rows = check_rows(
+                        storage,
+                        table_alias,
+                        filter_context,
+                        project_context,
+                        where_clause,
+                        using,
+                        rows.iter().enumerate(),
+                    )
+                    .await?;
+                    let rows = mark_matched(matched.as_deref(), rows);
+
+                    Rows::Hash(stream::iter(rows.into_iter().map(Ok)))
+                }
                 JoinExecutor::Hash {
+                    rows,
                     rows_map,
                     value_expr,
                 } => {
-                    let rows = evaluate(
+                    let indexes = evaluate(
                         storage,
                         filter_context.as_ref().map(Arc::clone),
                         None,
@@ -165,39 +397,30 @@ async fn join<'a, T: GStore>(
                     .map(Key::try_from)?
                     .map(|hash_key| rows_map.get(&hash_key))?;
 
-                    match rows {
+                    match indexes {
                         None => Rows::Empty(empty()),
-                        Some(rows) => {
-                            let rows = stream::iter(rows)
-                                .filter_map(|row| {
-                                    let filter_context = filter_context.as_ref().map(Arc::clone);
-                                    let project_context = Some(Arc::clone(&project_context));
-
-                                    async {
-                                        check_where_clause(
-                                            storage,
-                                            table_alias,
-                                            filter_context,
-                                            project_context,
-                                            where_clause,
-                                            Cow::Borrowed(row),
-                                        )
-                                        .await
-                                        .transpose()
-                                    }
-                                })
-                                .collect::<Vec<_>>()
-                                .await;
-
-                            Rows::Hash(stream::iter(rows))
+                        Some(indexes) => {
+                            let rows = check_rows(
+                                storage,
+                                table_alias,
+                                filter_context,
+                                project_context,
+                                where_clause,
+                                using,
+                                indexes.iter().map(|index| (*index, &rows[*index])),
+                            )
+                            .await?;
+                            let rows = mark_matched(matched.as_deref(), rows);
+
+                            Rows::Hash(stream::iter(rows.into_iter().map(Ok)))
                         }
                     }
                 }
             };
 
-            let rows: Joined = match join_operator {
-                JoinOperator::Inner => Box::pin(rows),
-                JoinOperator::LeftOuter => {
+            let rows: Joined = match join_operator.keeps_left() {
+                false => Box::pin(rows),
+                true => {
                     let init_rows = once(async { Ok(init_context) });
 
                     Box::pin(OrStream::new(rows, init_rows))
@@ -208,19 +431,37 @@ async fn join<'a, T: GStore>(
         }
     });
 
-    Ok(Box::pin(rows.try_flatten()))
+    let rows: Joined = Box::pin(rows.try_flatten().chain(unmatched_rows));
+
+    Ok((rows, layout))
 }
 
 #[derive(Copy, Clone)]
 enum JoinOperator {
     Inner,
     LeftOuter,
+    RightOuter,
+    FullOuter,
+}
+
+impl JoinOperator {
+    fn keeps_left(self) -> bool {
+        matches!(self, Self::LeftOuter | Self::FullOuter)
+    }
+
+    fn keeps_right(self) -> bool {
+        matches!(self, Self::RightOuter | Self::FullOuter)
+    }
 }
 
 enum JoinExecutor<'a> {
     NestedLoop,
+    /// Rows of the joined relation are fetched once, so that RIGHT and FULL joins can
+    /// find the ones which never matched.
+    Materialized(Vec<Row>),
     Hash {
-        rows_map: HashMap<Key, Vec<Row>>,
+        rows: Vec<Row>,
+        rows_map: HashMap<Key, Vec<usize>>,
         value_expr: &'a Expr,
     },
 }
@@ -231,8 +472,18 @@ impl<'a> JoinExecutor<'a> {
         relation: &TableFactor,
         filter_context: Option<Arc<RowContext<'a>>>,
         ast_join_executor: &'a AstJoinExecutor,
+        keeps_right: bool,
     ) -> Result<JoinExecutor<'a>> {
+        let guard = filter_context
+            .as_ref()
+            .and_then(|context| context.get_guard());
         let (key_expr, value_expr, where_clause) = match ast_join_executor {
+            AstJoinExecutor::NestedLoop if keeps_right => {
+                let rows = fetch_relation_rows(storage, relation, &filter_context, None).await?;
+                let rows = watch_buffered(guard, rows).try_collect().await?;
+
+                return Ok(Self::Materialized(rows));
+            }
             AstJoinExecutor::NestedLoop => return Ok(Self::NestedLoop),
             AstJoinExecutor::Hash {
                 key_expr,
@@ -241,8 +492,8 @@ impl<'a> JoinExecutor<'a> {
             } => (key_expr, value_expr, where_clause),
         };
 
-        let rows_map = fetch_relation_rows(storage, relation, &filter_context)
-            .await?
+        let rows = fetch_relation_rows(storage, relation, &filter_context, None).await?;
+        let (rows, keys): (Vec<_>, Vec<_>) = watch_buffered(guard, rows)
             .try_filter_map(|row| {
                 let filter_context = filter_context.as_ref().map(Arc::clone);
 
@@ -258,37 +509,113 @@ impl<'a> JoinExecutor<'a> {
                             .await?
                             .try_into()?;
 
-                    if matches!(hash_key, Key::None) {
-                        return Ok(None);
-                    }
+                    let hash_key = match hash_key {
+                        Key::None => None,
+                        hash_key => match where_clause {
+                            Some(expr) => check_expr(storage, Some(filter_context), None, expr)
+                                .await?
+                                .then_some(hash_key),
+                            None => Some(hash_key),
+                        },
+                    };
 
-                    match where_clause {
-                        Some(expr) => check_expr(storage, Some(filter_context), None, expr)
-                            .await
-                            .map(|pass| pass.then_some((hash_key, row))),
-                        None => Ok(Some((hash_key, row))),
-                    }
+                    Ok((hash_key.is_some() || keeps_right).then_some((row, hash_key)))
                 }
             })
             .try_collect::<Vec<_>>()
             .await?
             .into_iter()
+            .unzip();
+
+        let rows_map = keys
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, hash_key)| hash_key.map(|hash_key| (hash_key, index)))
             .into_group_map();
+
         Ok(Self::Hash {
+            rows,
             rows_map,
             value_expr,
         })
     }
 }
 
+async fn check_rows<'a, 'b, T: GStore>(
+    storage: &'a T,
+    table_alias: &'a str,
+    filter_context: Option<Arc<RowContext<'a>>>,
+    project_context: Arc<RowContext<'a>>,
+    where_clause: Option<&'a Expr>,
+    using: Option<Arc<[String]>>,
+    rows: impl Iterator<Item = (usize, &'b Row)>,
+) -> Result<Vec<(usize, JoinItem<'a>)>> {
+    let guard = filter_context
+        .as_ref()
+        .and_then(|context| context.get_guard());
+    let mut joined = Vec::new();
+    for (index, row) in rows {
+        if let Some(guard) = guard.as_deref() {
+            guard.check()?;
+        }
+
+        let context = check_where_clause(
+            storage,
+            table_alias,
+            filter_context.as_ref().map(Arc::clone),
+            Some(Arc::clone(&project_context)),
+            where_clause,
+            using.as_ref().map(Arc::clone),
+            Cow::Borrowed(row),
+        )
+        .await?;
+
+        if let Some(context) = context {
+            joined.push((index, context));
+        }
+    }
+
+    Ok(joined)
+}
+
+fn mark_matched<'a>(
+    matched: Option<&Mutex<Vec<bool>>>,
+    rows: Vec<(usize, JoinItem<'a>)>,
+) -> Vec<JoinItem<'a>> {
+    if let Some(matched) = matched {
+        let mut matched = matched.lock().unwrap_or_else(|error| error.into_inner());
+
+        for (index, _) in &rows {
+            matched[*index] = true;
+        }
+    }
+
+    rows.into_iter().map(|(_, row)| row).collect()
+}
+
 async fn check_where_clause<'a, 'b, T: GStore>(
     storage: &'a T,
     table_alias: &'a str,
     filter_context: Option<Arc<RowContext<'a>>>,
     project_context: Option<Arc<RowContext<'a>>>,
     where_clause: Option<&'a Expr>,
+    using: Option<Arc<[String]>>,
     row: Cow<'b, Row>,
 ) -> Result<Option<Arc<RowContext<'a>>>> {
+    let using_matched = match (using.as_deref(), project_context.as_deref()) {
+        (Some(using), Some(project_context)) => using.iter().all(|column| {
+            match (project_context.get_value(column), row.get_value(column)) {
+                (Some(left), Some(right)) => left.evaluate_eq(right).is_true(),
+                _ => false,
+            }
+        }),
+        _ => true,
+    };
+
+    if !using_matched {
+        return Ok(None);
+    }
+
     let filter_context = RowContext::new(table_alias, Cow::Borrowed(&row), filter_context);
     let filter_context = Some(Arc::new(filter_context));
 
@@ -296,8 +623,48 @@ async fn check_where_clause<'a, 'b, T: GStore>(
         Some(expr) => check_expr(storage, filter_context, None, expr).await?,
         None => true,
     }
-    .then(|| RowContext::new(table_alias, Cow::Owned(row.into_owned()), project_context))
-    .map(Arc::new)
+    .then(|| {
+        joined_context(
+            table_alias,
+            Cow::Owned(row.into_owned()),
+            project_context,
+            using.as_ref(),
+        )
+    })
     .map(Ok)
     .transpose()
 }
+
+/// Pushes the joined row onto the left context, merging `USING` columns on top of it.
+fn joined_context<'a>(
+    table_alias: &'a str,
+    row: Cow<'a, Row>,
+    project_context: Option<Arc<RowContext<'a>>>,
+    using: Option<&Arc<[String]>>,
+) -> Arc<RowContext<'a>> {
+    let using = using.map(|using| {
+        let values = using
+            .iter()
+            .map(|column| {
+                project_context
+                    .as_ref()
+                    .and_then(|context| context.get_value(column))
+                    .filter(|value| !value.is_null())
+                    .or_else(|| row.get_value(column))
+                    .cloned()
+                    .unwrap_or(Value::Null)
+            })
+            .collect();
+
+        Row::Vec {
+            columns: Arc::clone(using),
+            values,
+        }
+    });
+    let context = Arc::new(RowContext::new(table_alias, row, project_context));
+
+    match using {
+        Some(using) => Arc::new(RowContext::using(using, context)),
+        None => context,
+    }
+}
@@ -1,11 +1,12 @@
 use {
-    super::evaluate::evaluate_stateless,
+    super::{context::RowContext, evaluate::evaluate_stateless},
     crate::{
         ast::Expr,
         data::{Row, Value},
         result::{Error, Result},
     },
     futures::stream::{Stream, StreamExt},
+    std::sync::Arc,
 };
 
 pub struct Limit {
@@ -14,14 +15,19 @@ pub struct Limit {
 }
 
 impl Limit {
-    pub async fn new(limit: Option<&Expr>, offset: Option<&Expr>) -> Result<Self> {
+    pub async fn new(
+        limit: Option<&Expr>,
+        offset: Option<&Expr>,
+        context: Option<&Arc<RowContext<'_>>>,
+    ) -> Result<Self> {
         let eval = |expr| async move {
             let expr = match expr {
                 Some(expr) => expr,
                 None => return Ok(None),
             };
 
-            let evaluated = evaluate_stateless(None, expr).await?;
+            let context = context.and_then(|context| context.root_context());
+            let evaluated = evaluate_stateless(context, expr).await?;
             let size: usize = Value::try_from(evaluated)?.try_into()?;
 
             Result::<Option<usize>, Error>::Ok(Some(size))
@@ -33,6 +39,12 @@ impl Limit {
         Ok(Self { limit, offset })
     }
 
+    /// Number of the leading rows of the input needed by the limit, `None` without `LIMIT`
+    pub fn bound(&self) -> Option<usize> {
+        self.limit
+            .map(|limit| limit.saturating_add(self.offset.unwrap_or(0)))
+    }
+
     pub fn apply<'a, T: Stream<Item = Result<Row>> + 'a>(
         &self,
         rows: T,
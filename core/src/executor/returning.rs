@@ -0,0 +1,94 @@
+use {
+    super::{Payload, context::RowContext, fetch::FetchError, select::Project},
+    crate::{
+        ast::SelectItem,
+        data::Row,
+        result::Result,
+        store::{DataRow, GStore},
+    },
+    futures::stream::{self, StreamExt, TryStreamExt},
+    std::{borrow::Cow, sync::Arc},
+};
+
+/// Converts a row taken from storage back into a [`Row`] to evaluate `RETURNING` against.
+pub fn data_row_to_row(data_row: DataRow, columns: Option<&Arc<[String]>>) -> Row {
+    match data_row {
+        DataRow::Vec(values) => Row::Vec {
+            columns: columns.map(Arc::clone).unwrap_or_else(|| Arc::from([])),
+            values,
+        },
+        DataRow::Map(values) => Row::Map(values),
+    }
+}
+
+/// Projects `returning` items over the rows about to be written to (or deleted from)
+/// `table_name`, each row bound to the table name just as in `SELECT ... FROM table_name`.
+/// Runs before the write, so subqueries see the table as it was before the statement.
+pub async fn returning<'a, T: GStore>(
+    storage: &'a T,
+    table_name: &'a str,
+    columns: Option<&[String]>,
+    returning: &'a [SelectItem],
+    rows: Vec<Row>,
+    filter_context: Option<Arc<RowContext<'a>>>,
+) -> Result<Payload> {
+    let labels = returning_labels(table_name, columns, returning)?.map(Arc::from);
+    let project = Project::new(storage, filter_context, returning);
+
+    let rows = stream::iter(rows)
+        .then(|row| {
+            let project = &project;
+            let labels = labels.as_ref().map(Arc::clone);
+
+            async move {
+                let context = Arc::new(RowContext::new(table_name, Cow::Owned(row), None));
+
+                project.apply(None, labels, context).await
+            }
+        })
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    match labels {
+        Some(labels) => rows
+            .into_iter()
+            .map(Row::try_into_vec)
+            .collect::<Result<Vec<_>>>()
+            .map(|rows| Payload::Select {
+                labels: labels.to_vec(),
+                rows,
+            }),
+        None => rows
+            .into_iter()
+            .map(Row::try_into_map)
+            .collect::<Result<Vec<_>>>()
+            .map(Payload::SelectMap),
+    }
+}
+
+fn returning_labels(
+    table_name: &str,
+    columns: Option<&[String]>,
+    returning: &[SelectItem],
+) -> Result<Option<Vec<String>>> {
+    let mut labels = Vec::new();
+
+    for item in returning {
+        match item {
+            SelectItem::Wildcard => match columns {
+                Some(columns) => labels.extend(columns.iter().cloned()),
+                None => return Ok(None),
+            },
+            SelectItem::QualifiedWildcard(alias) if alias != table_name => {
+                return Err(FetchError::TableAliasNotFound(alias.to_owned()).into());
+            }
+            SelectItem::QualifiedWildcard(_) => match columns {
+                Some(columns) => labels.extend(columns.iter().cloned()),
+                None => return Ok(None),
+            },
+            SelectItem::Expr { label, .. } => labels.push(label.to_owned()),
+        }
+    }
+
+    Ok(Some(labels))
+}
@@ -0,0 +1,342 @@
+use {
+    crate::{
+        ast::{
+            AstLiteral, BinaryOperator, Expr, IndexOperator, Join, JoinConstraint, JoinExecutor,
+            JoinOperator, OrderByExpr, Query, Select, SelectItem, SetExpr, TableFactor,
+        },
+        data::{Literal, Schema, Value, get_alias},
+        plan::PlanExpr,
+        store::{ScanFilter, ScanPredicate},
+    },
+    std::{cmp::Ordering, collections::BTreeSet},
+};
+
+/// Builds the filter of the full scan of `schema` as the first table of `query`, with the
+/// columns used by the query and the conditions of `WHERE` comparing a column of the table
+/// with a literal. `None` when the table is not fully scanned.
+pub(super) fn scan_filter(query: &Query, schema: &Schema) -> Option<ScanFilter> {
+    let SetExpr::Select(select) = &query.body else {
+        return None;
+    };
+    let relation = &select.from.relation;
+    let TableFactor::Table { index: None, .. } = relation else {
+        return None;
+    };
+    let column_defs = schema.column_defs.as_ref()?;
+    let alias = get_alias(relation);
+    let joined = !select.from.joins.is_empty();
+
+    // unqualified columns of a join can be columns of the other tables
+    let find_column = |column_alias: Option<&str>, column: &str| {
+        let matched = match column_alias {
+            Some(column_alias) => column_alias == alias,
+            None => !joined,
+        };
+
+        column_defs
+            .iter()
+            .find(|column_def| matched && column_def.name == column)
+    };
+
+    let predicates = select
+        .selection
+        .as_ref()
+        .map(|selection| {
+            let mut conditions = Vec::new();
+            split(selection, &mut conditions);
+
+            conditions
+                .into_iter()
+                .filter_map(|condition| {
+                    let (column_alias, column, op, literal) = compare(condition)?;
+                    let column_def = find_column(column_alias, column)?;
+                    let literal = Literal::try_from(literal).ok()?;
+                    let value = Value::try_from_literal(&column_def.data_type, &literal)
+                        .ok()
+                        // skips the literals changed by the conversion such as `1.5` to `1`
+                        .filter(|value| {
+                            value.evaluate_cmp_with_literal(&literal) == Some(Ordering::Equal)
+                        })?;
+
+                    Some(ScanPredicate {
+                        column: column_def.name.clone(),
+                        op,
+                        value,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let columns = columns(select, &query.order_by).map(|columns| {
+        column_defs
+            .iter()
+            .filter(|column_def| {
+                columns.iter().any(|(column_alias, column)| {
+                    *column == column_def.name
+                        && column_alias.is_none_or(|column_alias| column_alias == alias)
+                })
+            })
+            .map(|column_def| column_def.name.clone())
+            .collect()
+    });
+
+    Some(ScanFilter {
+        columns,
+        predicates,
+    })
+}
+
+fn split<'a>(expr: &'a Expr, conditions: &mut Vec<&'a Expr>) {
+    match expr {
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::And,
+            right,
+        } => {
+            split(left, conditions);
+            split(right, conditions);
+        }
+        Expr::Nested(expr) => split(expr, conditions),
+        expr => conditions.push(expr),
+    }
+}
+
+/// Returns the alias, the column, the comparison and the literal of `column op literal`,
+/// reversing the comparison of `literal op column`
+fn compare(condition: &Expr) -> Option<(Option<&str>, &str, IndexOperator, &AstLiteral)> {
+    let Expr::BinaryOp { left, op, right } = condition else {
+        return None;
+    };
+
+    let op = match op {
+        BinaryOperator::Eq => IndexOperator::Eq,
+        BinaryOperator::Gt => IndexOperator::Gt,
+        BinaryOperator::GtEq => IndexOperator::GtEq,
+        BinaryOperator::Lt => IndexOperator::Lt,
+        BinaryOperator::LtEq => IndexOperator::LtEq,
+        _ => return None,
+    };
+    fn column(expr: &Expr) -> Option<(Option<&str>, &str)> {
+        match expr {
+            Expr::Identifier(ident) => Some((None, ident)),
+            Expr::CompoundIdentifier { alias, ident } => Some((Some(alias), ident)),
+            _ => None,
+        }
+    }
+
+    match (left.as_ref(), right.as_ref()) {
+        (expr, Expr::Literal(literal)) => {
+            column(expr).map(|(alias, column)| (alias, column, op, literal))
+        }
+        (Expr::Literal(literal), expr) => {
+            column(expr).map(|(alias, column)| (alias, column, op.reverse(), literal))
+        }
+        _ => None,
+    }
+}
+
+/// Columns used by `select` with their aliases, `None` when all of them can be used
+fn columns<'a>(
+    select: &'a Select,
+    order_by: &'a [OrderByExpr],
+) -> Option<BTreeSet<(Option<&'a str>, &'a str)>> {
+    let mut exprs = Vec::new();
+    for item in &select.projection {
+        match item {
+            SelectItem::Expr { expr, .. } => exprs.push(expr),
+            SelectItem::QualifiedWildcard(_) | SelectItem::Wildcard => return None,
+        }
+    }
+
+    let mut columns = BTreeSet::new();
+    for Join {
+        join_operator,
+        join_executor,
+        ..
+    } in &select.from.joins
+    {
+        if let JoinExecutor::Hash {
+            key_expr,
+            value_expr,
+            where_clause,
+        } = join_executor
+        {
+            exprs.extend([key_expr, value_expr]);
+            exprs.extend(where_clause);
+        }
+
+        let join_constraint = match join_operator {
+            JoinOperator::Inner(join_constraint)
+            | JoinOperator::LeftOuter(join_constraint)
+            | JoinOperator::RightOuter(join_constraint)
+            | JoinOperator::FullOuter(join_constraint) => join_constraint,
+            JoinOperator::Cross => continue,
+        };
+
+        match join_constraint {
+            JoinConstraint::On(expr) => exprs.push(expr),
+            JoinConstraint::Using(using) => {
+                columns.extend(using.iter().map(|column| (None, column.as_str())));
+            }
+            JoinConstraint::Natural => return None,
+            JoinConstraint::None => {}
+        }
+    }
+
+    exprs.extend(select.selection.iter());
+    exprs.extend(select.group_by.iter());
+    exprs.extend(select.having.iter());
+    exprs.extend(order_by.iter().map(|OrderByExpr { expr, .. }| expr));
+
+    exprs
+        .into_iter()
+        .all(|expr| collect_columns(expr, &mut columns))
+        .then_some(columns)
+}
+
+/// Collects the columns of `expr`, `false` when it has a subquery which can use any column
+fn collect_columns<'a>(expr: &'a Expr, columns: &mut BTreeSet<(Option<&'a str>, &'a str)>) -> bool {
+    match PlanExpr::from(expr) {
+        PlanExpr::None => true,
+        PlanExpr::Identifier(ident) => {
+            columns.insert((None, ident));
+            true
+        }
+        PlanExpr::CompoundIdentifier { alias, ident } => {
+            columns.insert((Some(alias), ident));
+            true
+        }
+        PlanExpr::Expr(expr) => collect_columns(expr, columns),
+        PlanExpr::TwoExprs(expr, expr2) => {
+            collect_columns(expr, columns) && collect_columns(expr2, columns)
+        }
+        PlanExpr::ThreeExprs(expr, expr2, expr3) => {
+            collect_columns(expr, columns)
+                && collect_columns(expr2, columns)
+                && collect_columns(expr3, columns)
+        }
+        PlanExpr::MultiExprs(exprs) => exprs.into_iter().all(|expr| collect_columns(expr, columns)),
+        PlanExpr::Query(_) | PlanExpr::QueryAndExpr { .. } => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::scan_filter,
+        crate::{
+            ast::{ColumnDef, DataType, IndexOperator},
+            data::{Schema, Value},
+            parse_sql::parse_query,
+            store::{ScanFilter, ScanPredicate},
+            translate::translate_query,
+        },
+    };
+
+    fn schema() -> Schema {
+        let column_def = |name: &str, data_type| ColumnDef {
+            name: name.to_owned(),
+            data_type,
+            nullable: true,
+            default: None,
+            identity: None,
+            generated: None,
+            unique: None,
+            check: None,
+            comment: None,
+        };
+
+        Schema {
+            table_name: "Item".to_owned(),
+            column_defs: Some(vec![
+                column_def("id", DataType::Int),
+                column_def("name", DataType::Text),
+                column_def("price", DataType::Int),
+                column_def("memo", DataType::Text),
+            ]),
+            indexes: Vec::new(),
+            engine: None,
+            key_constraints: Vec::new(),
+            foreign_keys: Vec::new(),
+            checks: Vec::new(),
+            comment: None,
+        }
+    }
+
+    fn test(sql: &str) -> Option<ScanFilter> {
+        let query = translate_query(&parse_query(sql).unwrap()).unwrap();
+
+        scan_filter(&query, &schema())
+    }
+
+    fn predicate(column: &str, op: IndexOperator, value: i64) -> ScanPredicate {
+        ScanPredicate {
+            column: column.to_owned(),
+            op,
+            value: Value::I64(value),
+        }
+    }
+
+    fn columns(columns: &[&str]) -> Option<Vec<String>> {
+        Some(columns.iter().map(|column| column.to_string()).collect())
+    }
+
+    #[test]
+    fn single_table() {
+        assert_eq!(
+            test("SELECT name FROM Item WHERE price > 10 AND (3 >= id AND memo IS NULL)"),
+            Some(ScanFilter {
+                columns: columns(&["id", "name", "price", "memo"]),
+                predicates: vec![
+                    predicate("price", IndexOperator::Gt, 10),
+                    predicate("id", IndexOperator::LtEq, 3),
+                ],
+            })
+        );
+        assert_eq!(
+            test("SELECT COUNT(*) FROM Item WHERE id = 1.5 OR price = 1"),
+            Some(ScanFilter {
+                columns: columns(&["id", "price"]),
+                predicates: Vec::new(),
+            })
+        );
+        assert_eq!(
+            test("SELECT * FROM Item WHERE id = 1.5"),
+            Some(ScanFilter::default())
+        );
+        assert_eq!(
+            test("SELECT id FROM Item WHERE price < 3 AND id IN (SELECT 1)"),
+            Some(ScanFilter {
+                columns: None,
+                predicates: vec![predicate("price", IndexOperator::Lt, 3)],
+            })
+        );
+        assert_eq!(test("VALUES (1)"), None);
+    }
+
+    #[test]
+    fn join() {
+        assert_eq!(
+            test(
+                "
+                SELECT i.name FROM Item i
+                JOIN Other o ON o.id = i.id
+                WHERE price > 1 AND i.price < 5 AND o.price < 5
+                ORDER BY i.memo
+                "
+            ),
+            Some(ScanFilter {
+                columns: columns(&["id", "name", "price", "memo"]),
+                predicates: vec![predicate("price", IndexOperator::Lt, 5)],
+            })
+        );
+        assert_eq!(
+            test("SELECT i.name FROM Item i JOIN Other o USING (id)"),
+            Some(ScanFilter {
+                columns: columns(&["id", "name"]),
+                predicates: Vec::new(),
+            })
+        );
+    }
+}
@@ -1,21 +1,26 @@
+mod cte;
 mod error;
 mod project;
 
 pub use error::SelectError;
+pub(super) use project::Project;
 use {
-    self::project::Project,
+    self::cte::bind_ctes,
     super::{
         aggregate,
         context::{AggregateContext, RowContext},
         evaluate::evaluate_stateless,
+        explain::{Profile, track},
         fetch::{fetch_labels, fetch_relation_rows},
         filter::Filter,
+        guard::{watch, watch_buffered},
         join::Join,
         limit::Limit,
         sort::Sort,
+        window,
     },
     crate::{
-        ast::{Expr, OrderByExpr, Query, Select, SetExpr, TableWithJoins, Values},
+        ast::{Expr, OrderByExpr, Query, Select, SetExpr, SetOperator, TableWithJoins, Values},
         data::{Key, Row, Value, get_alias},
         result::Result,
         store::GStore,
@@ -24,30 +29,125 @@ use {
     futures::stream::{self, Stream, StreamExt, TryStreamExt},
     std::{
         borrow::Cow,
-        collections::{BTreeMap, HashSet},
+        collections::{BTreeMap, HashMap, HashSet},
         sync::Arc,
     },
     utils::Vector,
 };
 
+fn row_key(row: &Row) -> Vec<Value> {
+    match row {
+        Row::Vec { values, .. } => values.clone(),
+        Row::Map(map) => {
+            let sorted_map: BTreeMap<_, _> = map.iter().collect();
+            sorted_map.into_values().cloned().collect()
+        }
+    }
+}
+
 fn apply_distinct(rows: Vec<Row>) -> Vec<Row> {
     let mut seen = HashSet::new();
 
     rows.into_iter()
-        .filter(|row| {
-            let key = match row {
-                Row::Vec { values, .. } => values.clone(),
-                Row::Map(map) => {
-                    let sorted_map: BTreeMap<_, _> = map.iter().collect();
-                    sorted_map.into_values().cloned().collect()
-                }
-            };
-            seen.insert(key)
-        })
+        .filter(|row| seen.insert(row_key(row)))
         .collect()
 }
 
-async fn rows_with_labels(exprs_list: &[Vec<Expr>]) -> Result<(Vec<Row>, Vec<String>)> {
+async fn set_operation<'a, T>(
+    storage: &'a T,
+    op: SetOperator,
+    all: bool,
+    left: &'a Query,
+    right: &'a Query,
+    filter_context: Option<Arc<RowContext<'a>>>,
+) -> Result<(Option<Vec<String>>, Vec<Row>)>
+where
+    T: GStore,
+{
+    let (left_labels, left_rows) =
+        select_with_labels(storage, left, filter_context.as_ref().map(Arc::clone)).await?;
+    let left_rows = left_rows.try_collect::<Vec<_>>().await?;
+    let (right_labels, right_rows) = select_with_labels(storage, right, filter_context).await?;
+    let right_rows = right_rows.try_collect::<Vec<_>>().await?;
+
+    let columns = match (&left_labels, &right_labels) {
+        (Some(left_labels), Some(right_labels)) if left_labels.len() != right_labels.len() => {
+            return Err(SelectError::SetOperationColumnCountMismatch {
+                op,
+                left: left_labels.len(),
+                right: right_labels.len(),
+            }
+            .into());
+        }
+        (Some(labels), Some(_)) => Some(Arc::<[String]>::from(labels.as_slice())),
+        (None, None) => None,
+        (Some(_), None) | (None, Some(_)) => {
+            return Err(SelectError::SetOperationSchemalessMismatch(op).into());
+        }
+    };
+
+    let right_rows = right_rows.into_iter().map(|row| match (&columns, row) {
+        (Some(columns), Row::Vec { values, .. }) => Row::Vec {
+            columns: Arc::clone(columns),
+            values,
+        },
+        (_, row) => row,
+    });
+
+    let mut counts = HashMap::<Vec<Value>, usize>::new();
+    let rows = match op {
+        SetOperator::Union => {
+            let rows = left_rows.into_iter().chain(right_rows).collect();
+
+            if all { rows } else { apply_distinct(rows) }
+        }
+        SetOperator::Intersect => {
+            for row in right_rows {
+                *counts.entry(row_key(&row)).or_default() += 1;
+            }
+
+            left_rows
+                .into_iter()
+                .filter(|row| match counts.get_mut(&row_key(row)) {
+                    Some(count) if *count > 0 => {
+                        *count = if all { *count - 1 } else { 0 };
+
+                        true
+                    }
+                    _ => false,
+                })
+                .collect()
+        }
+        SetOperator::Except => {
+            for row in right_rows {
+                *counts.entry(row_key(&row)).or_default() += 1;
+            }
+
+            let rows = left_rows
+                .into_iter()
+                .filter(|row| match counts.get_mut(&row_key(row)) {
+                    Some(count) if all && *count > 0 => {
+                        *count -= 1;
+
+                        false
+                    }
+                    Some(_) if all => true,
+                    Some(_) => false,
+                    None => true,
+                })
+                .collect();
+
+            if all { rows } else { apply_distinct(rows) }
+        }
+    };
+
+    Ok((left_labels, rows))
+}
+
+async fn rows_with_labels(
+    exprs_list: &[Vec<Expr>],
+    filter_context: Option<&Arc<RowContext<'_>>>,
+) -> Result<(Vec<Row>, Vec<String>)> {
     let first_len = exprs_list[0].len();
     let labels = (1..=first_len)
         .map(|i| format!("column{i}"))
@@ -65,7 +165,8 @@ async fn rows_with_labels(exprs_list: &[Vec<Expr>]) -> Result<(Vec<Row>, Vec<Str
         let mut values = Vec::with_capacity(exprs.len());
 
         for (i, expr) in exprs.iter().enumerate() {
-            let evaluated = evaluate_stateless(None, expr).await?;
+            let context = filter_context.and_then(|context| context.root_context());
+            let evaluated = evaluate_stateless(context, expr).await?;
 
             let value = match column_types[i] {
                 Some(ref data_type) => evaluated.try_into_value(data_type, true)?,
@@ -119,7 +220,6 @@ async fn sort_stateless(rows: Vec<Row>, order_by: &[OrderByExpr]) -> Result<Vec<
     Ok(sorted)
 }
 
-#[async_recursion]
 pub async fn select_with_labels<'a, T>(
     storage: &'a T,
     query: &'a Query,
@@ -128,15 +228,38 @@ pub async fn select_with_labels<'a, T>(
     Option<Vec<String>>,
     impl Stream<Item = Result<Row>> + Send + 'a,
 )>
+where
+    T: GStore,
+{
+    select_with_profile(storage, query, filter_context, None).await
+}
+
+/// Selects like [`select_with_labels`], counting the rows and time of each operator of a
+/// `SELECT` body into `profile` for `EXPLAIN ANALYZE`.
+#[async_recursion]
+pub(super) async fn select_with_profile<'a, T>(
+    storage: &'a T,
+    query: &'a Query,
+    filter_context: Option<Arc<RowContext<'a>>>,
+    profile: Option<&'a Profile>,
+) -> Result<(
+    Option<Vec<String>>,
+    impl Stream<Item = Result<Row>> + Send + 'a,
+)>
 where
     T: GStore,
 {
     #[derive(futures_enum::Stream)]
     enum Row<S1, S2> {
         Select(S2),
-        Values(S1),
+        Materialized(S1),
     }
 
+    let filter_context = match &query.with {
+        Some(with) => bind_ctes(storage, with, filter_context).await?,
+        None => filter_context,
+    };
+
     let Select {
         distinct,
         from: table_with_joins,
@@ -147,38 +270,79 @@ where
     } = match &query.body {
         SetExpr::Select(statement) => statement.as_ref(),
         SetExpr::Values(Values(values_list)) => {
-            let limit = Limit::new(query.limit.as_ref(), query.offset.as_ref()).await?;
-            let (rows, labels) = rows_with_labels(values_list).await?;
+            let limit = Limit::new(
+                query.limit.as_ref(),
+                query.offset.as_ref(),
+                filter_context.as_ref(),
+            )
+            .await?;
+            let (rows, labels) = rows_with_labels(values_list, filter_context.as_ref()).await?;
             let rows = sort_stateless(rows, &query.order_by).await?;
             let rows = stream::iter(rows.into_iter().map(Ok));
             let rows = limit.apply(rows);
 
-            return Ok((Some(labels), Row::Values(rows)));
+            return Ok((Some(labels), Row::Materialized(rows)));
+        }
+        SetExpr::SetOperation {
+            op,
+            all,
+            left,
+            right,
+        } => {
+            let limit = Limit::new(
+                query.limit.as_ref(),
+                query.offset.as_ref(),
+                filter_context.as_ref(),
+            )
+            .await?;
+            let (labels, rows) =
+                set_operation(storage, *op, *all, left, right, filter_context).await?;
+            let rows = sort_stateless(rows, &query.order_by).await?;
+            let rows = stream::iter(rows.into_iter().map(Ok));
+            let rows = limit.apply(rows);
+
+            return Ok((labels, Row::Materialized(rows)));
         }
     };
 
     let TableWithJoins { relation, joins } = &table_with_joins;
-    let rows = fetch_relation_rows(storage, relation, &None)
-        .await?
-        .map(move |row| {
-            let row = row?;
-            let alias = get_alias(relation);
-
-            Ok(RowContext::new(alias, Cow::Owned(row), None))
-        });
+    let guard = filter_context
+        .as_ref()
+        .and_then(|context| context.get_guard());
+    let rows = fetch_relation_rows(storage, relation, &filter_context, Some(query)).await?;
+    let rows = watch(guard.clone(), rows).map(move |row| {
+        let row = row?;
+        let alias = get_alias(relation);
+
+        Ok(RowContext::new(alias, Cow::Owned(row), None))
+    });
+    let rows = track(profile.map(|profile| &profile.scan), rows);
 
-    let join = Join::new(storage, joins, filter_context.as_ref().map(Arc::clone));
+    let join = Join::new(
+        storage,
+        relation,
+        joins,
+        filter_context.as_ref().map(Arc::clone),
+        profile.map(|profile| profile.joins.as_slice()),
+    );
     let filter = Arc::new(Filter::new(
         storage,
         where_clause.as_ref(),
         filter_context.as_ref().map(Arc::clone),
         None,
     ));
-    let limit = Limit::new(query.limit.as_ref(), query.offset.as_ref()).await?;
+    let limit = Limit::new(
+        query.limit.as_ref(),
+        query.offset.as_ref(),
+        filter_context.as_ref(),
+    )
+    .await?;
+    // `DISTINCT` removes rows after sorting, so all of them have to be sorted
     let sort = Sort::new(
         storage,
         filter_context.as_ref().map(Arc::clone),
         &query.order_by,
+        limit.bound().filter(|_| !distinct),
     );
 
     let rows = join.apply(rows).await?;
@@ -192,20 +356,36 @@ where
                 .map(|pass| pass.then_some(project_context))
         }
     });
+    let rows = track(profile.map(|profile| &profile.filter), rows);
 
-    let rows = aggregate::apply(
+    let aggregate_counter = profile.map(|profile| &profile.aggregate);
+    let aggregated = aggregate::apply(
         storage,
         projection,
         group_by,
         having.as_ref(),
         filter_context.as_ref().map(Arc::clone),
         rows,
+    );
+    let rows = track(aggregate_counter, aggregated.await?);
+    let rows = window::apply(
+        storage,
+        projection,
+        &query.order_by,
+        filter_context.as_ref().map(Arc::clone),
+        rows,
     )
     .await?;
 
-    let labels = fetch_labels(storage, relation, joins, projection)
-        .await?
-        .map(Arc::from);
+    let labels = fetch_labels(
+        storage,
+        relation,
+        joins,
+        projection,
+        filter_context.as_ref().map(Arc::clone),
+    )
+    .await?
+    .map(Arc::from);
 
     let project = Arc::new(Project::new(storage, filter_context, projection));
     let project_labels = labels.as_ref().map(Arc::clone);
@@ -227,16 +407,24 @@ where
             Ok((aggregated, next, row))
         }
     });
+    let rows = track(profile.map(|profile| &profile.project), rows);
 
+    let sort_counter = profile.map(|profile| &profile.sort);
     let rows = sort.apply(rows, get_alias(relation)).await?;
+    let rows = track(sort_counter, rows);
 
+    let limit_counter = profile.map(|profile| &profile.limit);
     let rows: Box<dyn Stream<Item = Result<crate::data::Row>> + Unpin + Send> = if *distinct {
-        let all_rows: Vec<crate::data::Row> = rows.try_collect().await?;
+        let distinct_counter = profile.map(|profile| &profile.distinct);
+        let all_rows: Vec<crate::data::Row> = watch_buffered(guard, rows).try_collect().await?;
         let unique_rows = apply_distinct(all_rows);
-        let unique_stream = stream::iter(unique_rows.into_iter().map(Ok));
-        Box::new(limit.apply(unique_stream))
+        let unique_stream = track(
+            distinct_counter,
+            stream::iter(unique_rows.into_iter().map(Ok)),
+        );
+        Box::new(track(limit_counter, limit.apply(unique_stream)))
     } else {
-        Box::new(limit.apply(rows))
+        Box::new(track(limit_counter, limit.apply(rows)))
     };
     let labels = labels.map(|labels| labels.iter().cloned().collect());
 
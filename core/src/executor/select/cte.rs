@@ -0,0 +1,206 @@
+use {
+    super::{SelectError, row_key, select_with_labels, sort_stateless},
+    crate::{
+        ast::{Cte, Query, SetExpr, SetOperator, TableAlias, TableFactor, TableWithJoins, With},
+        data::Row,
+        executor::{
+            context::{CteTable, RowContext},
+            fetch::apply_column_aliases,
+            limit::Limit,
+        },
+        result::Result,
+        store::GStore,
+    },
+    futures::stream::{self, TryStreamExt},
+    std::{collections::HashSet, iter, sync::Arc},
+};
+
+/// Iterations a recursive CTE may run before it is considered not to terminate
+const MAX_RECURSION_DEPTH: usize = 1000;
+
+/// Evaluates every CTE in order and returns the context extended with their results.
+/// Each CTE can refer to the ones defined before it.
+pub async fn bind_ctes<'a, T: GStore>(
+    storage: &'a T,
+    with: &'a With,
+    filter_context: Option<Arc<RowContext<'a>>>,
+) -> Result<Option<Arc<RowContext<'a>>>> {
+    let With {
+        recursive,
+        cte_tables,
+    } = with;
+
+    let mut filter_context = filter_context;
+    for Cte { alias, query } in cte_tables {
+        let context = filter_context.as_ref().map(Arc::clone);
+        let table = match &query.body {
+            SetExpr::SetOperation {
+                op: SetOperator::Union,
+                all,
+                left,
+                right,
+            } if *recursive && refers_to(right, &alias.name) => {
+                fixpoint(storage, alias, query, *all, left, right, context).await?
+            }
+            _ => {
+                let (labels, rows) = select_with_labels(storage, query, context).await?;
+                let rows = rows.try_collect().await?;
+
+                cte_table(&alias.name, &alias.columns, labels, rows)?
+            }
+        };
+
+        filter_context = Some(Arc::new(RowContext::cte(
+            &alias.name,
+            table,
+            filter_context,
+        )));
+    }
+
+    Ok(filter_context)
+}
+
+/// Evaluates `anchor UNION [ALL] recursive` by feeding the rows produced in each iteration
+/// back into the recursive term, until no new rows are found or [`MAX_RECURSION_DEPTH`]
+/// iterations have run.
+async fn fixpoint<'a, T: GStore>(
+    storage: &'a T,
+    alias: &'a TableAlias,
+    query: &'a Query,
+    all: bool,
+    anchor: &'a Query,
+    recursive: &'a Query,
+    filter_context: Option<Arc<RowContext<'a>>>,
+) -> Result<CteTable> {
+    let TableAlias {
+        name,
+        columns: alias_columns,
+    } = alias;
+
+    let (labels, rows) =
+        select_with_labels(storage, anchor, filter_context.as_ref().map(Arc::clone)).await?;
+    let rows = rows.try_collect().await?;
+    let CteTable { labels, rows } = cte_table(name, alias_columns, labels, rows)?;
+
+    let mut seen = HashSet::new();
+    let mut distinct = |rows: Vec<Row>| -> Vec<Row> {
+        match all {
+            true => rows,
+            false => rows
+                .into_iter()
+                .filter(|row| seen.insert(row_key(row)))
+                .collect(),
+        }
+    };
+
+    let mut result = distinct(rows);
+    let mut working_rows = result.clone();
+    let guard = filter_context
+        .as_ref()
+        .and_then(|context| context.get_guard());
+    let mut depth = 0;
+
+    while !working_rows.is_empty() {
+        if let Some(guard) = guard.as_deref() {
+            guard.check()?;
+        }
+
+        if depth == MAX_RECURSION_DEPTH {
+            return Err(SelectError::RecursionLimitExceeded {
+                name: name.to_owned(),
+                limit: MAX_RECURSION_DEPTH,
+            }
+            .into());
+        }
+        depth += 1;
+
+        let working_table = CteTable {
+            labels: labels.clone(),
+            rows: working_rows,
+        };
+        let context = RowContext::cte(name, working_table, filter_context.as_ref().map(Arc::clone));
+
+        let (recursive_labels, rows) =
+            select_with_labels(storage, recursive, Some(Arc::new(context))).await?;
+        let rows = rows.try_collect::<Vec<_>>().await?;
+
+        if let (Some(labels), Some(recursive_labels)) = (&labels, &recursive_labels) {
+            if labels.len() != recursive_labels.len() {
+                return Err(SelectError::SetOperationColumnCountMismatch {
+                    op: SetOperator::Union,
+                    left: labels.len(),
+                    right: recursive_labels.len(),
+                }
+                .into());
+            }
+        }
+
+        let rows = cte_table(name, &[], labels.clone(), rows)?.rows;
+
+        working_rows = distinct(rows);
+        result.extend(working_rows.iter().cloned());
+    }
+
+    let limit = Limit::new(
+        query.limit.as_ref(),
+        query.offset.as_ref(),
+        filter_context.as_ref(),
+    )
+    .await?;
+    let rows = sort_stateless(result, &query.order_by).await?;
+    let rows = limit
+        .apply(stream::iter(rows.into_iter().map(Ok)))
+        .try_collect()
+        .await?;
+
+    Ok(CteTable { labels, rows })
+}
+
+fn cte_table(
+    name: &str,
+    alias_columns: &[String],
+    labels: Option<Vec<String>>,
+    rows: Vec<Row>,
+) -> Result<CteTable> {
+    let labels = apply_column_aliases(name, alias_columns, labels)?;
+    let rows = match &labels {
+        Some(labels) => {
+            let columns = Arc::<[String]>::from(labels.as_slice());
+
+            rows.into_iter()
+                .map(|row| match row {
+                    Row::Vec { values, .. } => Row::Vec {
+                        columns: Arc::clone(&columns),
+                        values,
+                    },
+                    Row::Map(values) => Row::Map(values),
+                })
+                .collect()
+        }
+        None => rows,
+    };
+
+    Ok(CteTable { labels, rows })
+}
+
+fn refers_to(query: &Query, name: &str) -> bool {
+    match &query.body {
+        SetExpr::Select(select) => {
+            let TableWithJoins { relation, joins } = &select.from;
+
+            iter::once(relation)
+                .chain(joins.iter().map(|join| &join.relation))
+                .any(|table_factor| match table_factor {
+                    TableFactor::Table {
+                        name: table_name, ..
+                    } => table_name == name,
+                    TableFactor::Derived { subquery, .. } => refers_to(subquery, name),
+                    TableFactor::Series { .. } | TableFactor::Dictionary { .. } => false,
+                })
+        }
+        SetExpr::Values(_) => false,
+        SetExpr::SetOperation { left, right, .. } => {
+            refers_to(left, name) || refers_to(right, name)
+        }
+    }
+}
@@ -1,7 +1,20 @@
-use {serde::Serialize, std::fmt::Debug, thiserror::Error};
+use {crate::ast::SetOperator, serde::Serialize, std::fmt::Debug, thiserror::Error};
 
 #[derive(Error, Serialize, Debug, PartialEq, Eq)]
 pub enum SelectError {
     #[error("VALUES lists must all be the same length")]
     NumberOfValuesDifferent,
+
+    #[error("each {op} query must have the same number of columns: left {left}, right {right}")]
+    SetOperationColumnCountMismatch {
+        op: SetOperator,
+        left: usize,
+        right: usize,
+    },
+
+    #[error("{0} cannot combine schemaless and schema query results")]
+    SetOperationSchemalessMismatch(SetOperator),
+
+    #[error("recursive CTE {name} did not finish within {limit} iterations")]
+    RecursionLimitExceeded { name: String, limit: usize },
 }
@@ -1,5 +1,9 @@
 use {
-    super::{context::RowContext, evaluate::evaluate},
+    super::{
+        context::RowContext,
+        evaluate::evaluate,
+        guard::{watch, watch_buffered},
+    },
     crate::{
         ast::{Aggregate, AstLiteral, Expr, OrderByExpr, UnaryOperator},
         data::{Key, Row, Value},
@@ -10,7 +14,7 @@ use {
     futures::stream::{self, Stream, StreamExt, TryStreamExt},
     im::HashMap,
     serde::Serialize,
-    std::{borrow::Cow, cmp::Ordering, fmt::Debug, sync::Arc},
+    std::{borrow::Cow, cmp::Ordering, collections::BinaryHeap, fmt::Debug, sync::Arc},
     thiserror::Error as ThisError,
     utils::Vector,
 };
@@ -27,6 +31,37 @@ pub struct Sort<'a, T: GStore> {
     storage: &'a T,
     context: Option<Arc<RowContext<'a>>>,
     order_by: &'a [OrderByExpr],
+    /// Number of the leading sorted rows needed, kept in a bounded heap instead of sorting
+    /// all the rows
+    limit: Option<usize>,
+}
+
+/// Row kept by a top-N sort, ordered by its sort keys and then by its position in the input
+/// to keep the sort stable
+struct Ranked {
+    keys: Vec<(Key, Option<bool>)>,
+    position: usize,
+    row: Row,
+}
+
+impl PartialEq for Ranked {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Ranked {}
+
+impl PartialOrd for Ranked {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Ranked {
+    fn cmp(&self, other: &Self) -> Ordering {
+        sort_by(&self.keys, &other.keys).then(self.position.cmp(&other.position))
+    }
 }
 
 impl<'a, T: GStore> Sort<'a, T> {
@@ -34,11 +69,13 @@ impl<'a, T: GStore> Sort<'a, T> {
         storage: &'a T,
         context: Option<Arc<RowContext<'a>>>,
         order_by: &'a [OrderByExpr],
+        limit: Option<usize>,
     ) -> Self {
         Self {
             storage,
             context,
             order_by,
+            limit,
         }
     }
 
@@ -152,12 +189,48 @@ impl<'a, T: GStore> Sort<'a, T> {
                     Ok((keys, row))
                 }
             })
-            .try_collect::<Vec<(Vec<(Key, Option<bool>)>, Row)>>()
-            .await
-            .map(Vector::from)?
-            .sort_by(|(keys_a, ..), (keys_b, ..)| sort_by(keys_a, keys_b))
-            .into_iter()
-            .map(|(.., row)| Ok(row));
+            .enumerate()
+            .map(|(position, item)| {
+                item.map(|(keys, row)| Ranked {
+                    keys,
+                    position,
+                    row,
+                })
+            });
+
+        let guard = self
+            .context
+            .as_ref()
+            .and_then(|context| context.get_guard());
+        let rows = match self.limit {
+            Some(limit) => watch(guard.clone(), rows)
+                .try_fold(BinaryHeap::new(), |mut heap, ranked| {
+                    let guard = guard.clone();
+
+                    async move {
+                        heap.push(ranked);
+                        if heap.len() > limit {
+                            heap.pop();
+                        }
+
+                        if let Some(guard) = guard {
+                            guard.check_buffered(heap.len())?;
+                        }
+
+                        Ok::<_, Error>(heap)
+                    }
+                })
+                .await?
+                .into_sorted_vec(),
+            None => watch_buffered(guard, rows)
+                .try_collect::<Vec<_>>()
+                .await
+                .map(Vector::from)?
+                .sort_by(|ranked_a, ranked_b| sort_by(&ranked_a.keys, &ranked_b.keys))
+                .into(),
+        }
+        .into_iter()
+        .map(|Ranked { row, .. }| Ok(row));
 
         Ok(Rows::OrderBy(stream::iter(rows)))
     }
@@ -0,0 +1,158 @@
+use {
+    super::{
+        execute::{Payload, end_autocommit, execute_with_options, statement_context},
+        guard::{ExecuteOptions, watch_produced},
+        select::select_with_labels,
+    },
+    crate::{
+        ast::Statement,
+        data::{Row, Value},
+        result::Result,
+        store::{GStore, GStoreMut},
+    },
+    async_stream::stream,
+    futures::stream::{BoxStream, StreamExt, TryStreamExt},
+    serde_json::{Map as JsonMap, Value as JsonValue},
+    std::collections::BTreeMap,
+};
+
+/// Payload of [`execute_stream`], which yields the rows of `SELECT` one at a time instead of
+/// collecting them as [`Payload::Select`] does.
+pub enum PayloadStream<'a> {
+    Select {
+        labels: Vec<String>,
+        rows: BoxStream<'a, Result<Vec<Value>>>,
+    },
+    SelectMap(BoxStream<'a, Result<BTreeMap<String, Value>>>),
+    /// Payload of the statements other than `SELECT`
+    Payload(Payload),
+}
+
+impl<'a> PayloadStream<'a> {
+    /// Converts each row of `SELECT` into a JSON object keyed by column name as it is read,
+    /// returning `None` for the payloads of the other statements.
+    pub fn into_json_rows(self) -> Option<BoxStream<'a, Result<JsonValue>>> {
+        match self {
+            PayloadStream::Select { labels, rows } => Some(
+                rows.map(move |values| {
+                    labels
+                        .iter()
+                        .cloned()
+                        .zip(values?)
+                        .map(|(label, value)| Ok((label, value.try_into()?)))
+                        .collect::<Result<JsonMap<_, _>>>()
+                        .map(JsonValue::Object)
+                })
+                .boxed(),
+            ),
+            PayloadStream::SelectMap(rows) => {
+                Some(rows.map(|row| Value::Map(row?).try_into()).boxed())
+            }
+            PayloadStream::Payload(_) => None,
+        }
+    }
+}
+
+enum Streamed {
+    Labels(Option<Vec<String>>),
+    Row(Row),
+}
+
+impl Streamed {
+    fn into_row(self) -> Row {
+        match self {
+            Streamed::Row(row) => row,
+            Streamed::Labels(_) => unreachable!("labels are streamed once, before the rows"),
+        }
+    }
+}
+
+/// Executes `statement` like [`execute`], but returns the rows of `SELECT` as a stream read
+/// from the storage as it is polled. Out of explicit transactions, the transaction of the
+/// statement ends once the stream is exhausted or yields an error, so a stream dropped before
+/// then leaves it open.
+pub async fn execute_stream<T: GStore + GStoreMut>(
+    storage: &mut T,
+    statement: Statement,
+) -> Result<PayloadStream<'_>> {
+    stream_statement(storage, statement, &ExecuteOptions::default(), None).await
+}
+
+/// Streams `statement` like [`execute_stream`] within the limits of `options`, keeping `open`
+/// set while the stream holds the transaction it began for the statement.
+pub(crate) async fn stream_statement<'a, T: GStore + GStoreMut>(
+    storage: &'a mut T,
+    statement: Statement,
+    options: &ExecuteOptions,
+    mut open: Option<&'a mut bool>,
+) -> Result<PayloadStream<'a>> {
+    let query = match statement {
+        Statement::Query(query) => query,
+        statement => {
+            return execute_with_options(storage, &statement, &[], options)
+                .await
+                .map(PayloadStream::Payload);
+        }
+    };
+    let context = statement_context(&[], options);
+
+    let mut streamed = stream! {
+        let autocommit = match storage.begin(true).await {
+            Ok(autocommit) => autocommit,
+            Err(error) => {
+                yield Err(error);
+                return;
+            }
+        };
+
+        if let Some(open) = open.as_deref_mut() {
+            *open = autocommit;
+        }
+
+        let guard = context.as_ref().and_then(|context| context.get_guard());
+        let result = match select_with_labels(&*storage, &query, context).await {
+            Ok((labels, rows)) => {
+                yield Ok(Streamed::Labels(labels));
+
+                let mut result = Ok(());
+                for await row in watch_produced(guard, rows) {
+                    match row {
+                        Ok(row) => yield Ok(Streamed::Row(row)),
+                        Err(error) => {
+                            result = Err(error);
+                            break;
+                        }
+                    }
+                }
+
+                result
+            }
+            Err(error) => Err(error),
+        };
+
+        let result = end_autocommit(storage, autocommit, result).await;
+
+        if let Some(open) = open {
+            *open = false;
+        }
+
+        if let Err(error) = result {
+            yield Err(error);
+        }
+    }
+    .boxed();
+
+    let labels = match streamed.try_next().await? {
+        Some(Streamed::Labels(labels)) => labels,
+        _ => unreachable!("labels are streamed before the rows"),
+    };
+    let rows = streamed.map_ok(Streamed::into_row);
+
+    Ok(match labels {
+        Some(labels) => PayloadStream::Select {
+            labels,
+            rows: rows.map(|row| row?.try_into_vec()).boxed(),
+        },
+        None => PayloadStream::SelectMap(rows.map(|row| row?.try_into_map()).boxed()),
+    })
+}
@@ -0,0 +1,112 @@
+use {
+    super::{context::RowContext, execute::execute_inner, filter::check_expr},
+    crate::{
+        ast::{TriggerEvent, TriggerTiming},
+        data::{Row, Trigger, TriggerError},
+        result::Result,
+        store::{GStore, GStoreMut},
+    },
+    futures::future::BoxFuture,
+    std::sync::Arc,
+};
+
+/// Triggers fired by the statements of trigger bodies fail past this depth, which stops
+/// triggers writing to their own table from recursing forever.
+pub const MAX_TRIGGER_DEPTH: usize = 16;
+
+/// `OLD` and `NEW` rows of a single row trigger firing
+pub type TriggerRow<'a> = (Option<&'a Row>, Option<&'a Row>);
+
+/// Triggers of a table fired by an event, fetched once for each statement.
+pub struct Triggers(Vec<Trigger>);
+
+impl Triggers {
+    pub async fn fetch<T: GStore>(
+        storage: &T,
+        table_name: &str,
+        event: TriggerEvent,
+    ) -> Result<Self> {
+        let triggers = storage
+            .fetch_triggers(table_name)
+            .await?
+            .into_iter()
+            .filter(|trigger| trigger.events.contains(&event))
+            .collect();
+
+        Ok(Self(triggers))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Runs the triggers of `timing` for each of `rows` in turn, within the transaction of
+    /// the statement. `context` is the context of the statement, which tells how deep the
+    /// statement is nested in other triggers and holds the limits its triggers run under.
+    pub async fn fire<T: GStore + GStoreMut>(
+        &self,
+        storage: &mut T,
+        timing: TriggerTiming,
+        rows: &[TriggerRow<'_>],
+        context: Option<&Arc<RowContext<'_>>>,
+    ) -> Result<()> {
+        let triggers = self
+            .0
+            .iter()
+            .filter(|trigger| trigger.timing == timing)
+            .collect::<Vec<_>>();
+
+        if triggers.is_empty() {
+            return Ok(());
+        }
+
+        let depth = context
+            .and_then(|context| context.get_trigger_depth())
+            .unwrap_or(0)
+            + 1;
+
+        if depth > MAX_TRIGGER_DEPTH {
+            return Err(TriggerError::DepthExceeded(MAX_TRIGGER_DEPTH).into());
+        }
+
+        let guard = context.and_then(|context| context.get_guard());
+
+        for (old, new) in rows {
+            let context = Arc::new(RowContext::trigger(*new, *old, depth));
+            let context = match &guard {
+                Some(guard) => Arc::new(RowContext::guarded(Arc::clone(guard), Some(context))),
+                None => context,
+            };
+
+            for trigger in &triggers {
+                run(storage, trigger, Arc::clone(&context)).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Runs the body of `trigger` when the row satisfies its condition, boxed as the statements
+/// of the body fire triggers in turn.
+fn run<'a, T: GStore + GStoreMut>(
+    storage: &'a mut T,
+    trigger: &'a Trigger,
+    context: Arc<RowContext<'a>>,
+) -> BoxFuture<'a, Result<()>> {
+    Box::pin(async move {
+        if let Some(condition) = &trigger.condition {
+            let context = Some(Arc::clone(&context));
+
+            if !check_expr(storage, context, None, condition).await? {
+                return Ok(());
+            }
+        }
+
+        for statement in &trigger.body {
+            execute_inner(storage, statement, Some(Arc::clone(&context))).await?;
+        }
+
+        Ok(())
+    })
+}
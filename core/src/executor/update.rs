@@ -1,13 +1,24 @@
 use {
     super::{
+        Payload,
+        cascade::Cascade,
         context::RowContext,
         evaluate::{Evaluated, evaluate},
+        execute::ExecuteError,
+        fetch::fetch,
+        insert::fill_generated,
+        returning::returning,
+        trigger::Triggers,
+        validate::{ColumnValidation, find_unreferenced, validate_check, validate_unique},
     },
     crate::{
-        ast::{Assignment, ColumnDef, ColumnUniqueOption, ForeignKey},
-        data::{Key, Row, Value},
+        ast::{
+            Assignment, ColumnDef, ColumnUniqueOption, Expr, ForeignKey, KeyConstraint, SelectItem,
+            TriggerEvent, TriggerTiming,
+        },
+        data::{Row, Schema, Value},
         result::{Error, Result},
-        store::GStore,
+        store::{GStore, GStoreMut},
     },
     futures::stream::{self, StreamExt, TryStreamExt},
     serde::Serialize,
@@ -23,6 +34,9 @@ pub enum UpdateError {
     #[error("update on primary key is not supported: {0}")]
     UpdateOnPrimaryKeyNotSupported(String),
 
+    #[error("cannot update generated column: {0}")]
+    UpdateOnGeneratedColumn(String),
+
     #[error("conflict on schema, row data does not fit to schema")]
     ConflictOnSchema,
 
@@ -34,6 +48,195 @@ pub enum UpdateError {
         column_name: String,
         referenced_value: String,
     },
+
+    #[error("referencing column exists: {0}")]
+    ReferencingColumnExists(String),
+}
+
+pub async fn update<T: GStore + GStoreMut>(
+    storage: &mut T,
+    table_name: &str,
+    selection: &Option<Expr>,
+    assignments: &[Assignment],
+    returning_items: &[SelectItem],
+    context: Option<Arc<RowContext<'_>>>,
+) -> Result<Payload> {
+    let Schema {
+        column_defs,
+        indexes,
+        key_constraints,
+        foreign_keys,
+        checks,
+        ..
+    } = storage
+        .fetch_schema(table_name)
+        .await?
+        .ok_or_else(|| ExecuteError::TableNotFound(table_name.to_owned()))?;
+
+    let all_columns: Option<Arc<[String]>> = column_defs.as_deref().map(|columns| {
+        columns
+            .iter()
+            .map(|col_def| col_def.name.to_owned())
+            .collect()
+    });
+    let columns_to_update: Vec<String> = assignments
+        .iter()
+        .map(|assignment| assignment.id.to_owned())
+        .collect();
+
+    let update = Update::new(
+        storage,
+        table_name,
+        assignments,
+        column_defs.as_deref(),
+        &key_constraints,
+    )?;
+
+    let rows = fetch(
+        storage,
+        table_name,
+        all_columns.as_ref().map(Arc::clone),
+        selection.as_ref(),
+        context.as_ref().map(Arc::clone),
+    )
+    .await?
+    .and_then(|(key, row)| {
+        let update = &update;
+        let context = context.as_ref().map(Arc::clone);
+
+        async move {
+            let new_row = update.apply(row.clone(), context).await?;
+
+            Ok((key, row, new_row))
+        }
+    })
+    .try_collect::<Vec<_>>()
+    .await?;
+
+    let (cascade, rows) = match (column_defs, all_columns.as_ref()) {
+        (Some(column_defs), Some(columns)) => {
+            let values = rows.iter().filter_map(|(_, _, row)| match row {
+                Row::Vec { values, .. } => Some(values.as_slice()),
+                Row::Map(_) => None,
+            });
+
+            validate_foreign_keys(
+                storage,
+                &column_defs,
+                &foreign_keys,
+                &columns_to_update,
+                values.clone(),
+            )
+            .await?;
+            validate_check(table_name, &column_defs, &checks, values.clone()).await?;
+            validate_unique(
+                storage,
+                table_name,
+                ColumnValidation::SpecifiedColumns(&column_defs, columns_to_update),
+                &key_constraints,
+                &indexes,
+                values,
+            )
+            .await?;
+
+            Cascade::update(storage, table_name, Arc::clone(columns), rows).await?
+        }
+        _ => (Cascade::default(), rows),
+    };
+
+    let triggers = Triggers::fetch(storage, table_name, TriggerEvent::Update).await?;
+    let fired_rows = match triggers.is_empty() {
+        true => Vec::new(),
+        false => rows
+            .iter()
+            .map(|(_, old_row, new_row)| (old_row.clone(), new_row.clone()))
+            .collect(),
+    };
+    let fired_rows = fired_rows
+        .iter()
+        .map(|(old_row, new_row)| (Some(old_row), Some(new_row)))
+        .collect::<Vec<_>>();
+
+    triggers
+        .fire(
+            storage,
+            TriggerTiming::Before,
+            &fired_rows,
+            context.as_ref(),
+        )
+        .await?;
+
+    let payload = match returning_items.is_empty() {
+        true => Payload::Update(rows.len()),
+        false => {
+            let returned_rows = rows.iter().map(|(_, _, row)| row.clone()).collect();
+
+            returning(
+                storage,
+                table_name,
+                all_columns.as_deref(),
+                returning_items,
+                returned_rows,
+                context.as_ref().map(Arc::clone),
+            )
+            .await?
+        }
+    };
+    let rows = rows
+        .into_iter()
+        .map(|(key, _, row)| (key, row.into()))
+        .collect();
+
+    storage.insert_data(table_name, rows).await?;
+    cascade.apply(storage, context.as_ref()).await?;
+    triggers
+        .fire(storage, TriggerTiming::After, &fired_rows, context.as_ref())
+        .await?;
+
+    Ok(payload)
+}
+
+/// Fails when a value assigned to one of `columns` is missing from the column its foreign key
+/// references.
+pub async fn validate_foreign_keys<'a, T: GStore>(
+    storage: &T,
+    column_defs: &[ColumnDef],
+    foreign_keys: &[ForeignKey],
+    columns: &[String],
+    rows: impl Iterator<Item = &'a [Value]> + Clone,
+) -> Result<()> {
+    for foreign_key in foreign_keys {
+        let ForeignKey {
+            referencing_column_name,
+            referenced_table_name,
+            referenced_column_name,
+            ..
+        } = foreign_key;
+
+        let Some(index) = column_defs
+            .iter()
+            .position(|column_def| &column_def.name == referencing_column_name)
+            .filter(|_| columns.contains(referencing_column_name))
+        else {
+            continue;
+        };
+
+        let values = rows
+            .clone()
+            .filter_map(|values| values.get(index).cloned())
+            .collect();
+
+        if let Some(value) = find_unreferenced(storage, foreign_key, values).await? {
+            return Err(UpdateError::CannotFindReferencedValue {
+                table_name: referenced_table_name.to_owned(),
+                column_name: referenced_column_name.to_owned(),
+                referenced_value: String::from(value),
+            }
+            .into());
+        }
+    }
+
+    Ok(())
 }
 
 pub struct Update<'a, T: GStore> {
@@ -49,6 +252,7 @@ impl<'a, T: GStore> Update<'a, T> {
         table_name: &'a str,
         fields: &'a [Assignment],
         column_defs: Option<&'a [ColumnDef]>,
+        key_constraints: &[KeyConstraint],
     ) -> Result<Self> {
         if let Some(column_defs) = column_defs {
             for assignment in fields.iter() {
@@ -58,8 +262,15 @@ impl<'a, T: GStore> Update<'a, T> {
                     return Err(UpdateError::ColumnNotFound(id.to_owned()).into());
                 } else if column_defs.iter().any(|ColumnDef { name, unique, .. }| {
                     name == id && matches!(unique, Some(ColumnUniqueOption { is_primary: true }))
+                }) || key_constraints.iter().any(|key_constraint| {
+                    key_constraint.is_primary && key_constraint.columns.contains(id)
                 }) {
                     return Err(UpdateError::UpdateOnPrimaryKeyNotSupported(id.to_owned()).into());
+                } else if column_defs
+                    .iter()
+                    .any(|column_def| &column_def.name == id && column_def.generated.is_some())
+                {
+                    return Err(UpdateError::UpdateOnGeneratedColumn(id.to_owned()).into());
                 }
             }
         }
@@ -72,8 +283,10 @@ impl<'a, T: GStore> Update<'a, T> {
         })
     }
 
-    pub async fn apply(&self, row: Row, foreign_keys: &[ForeignKey]) -> Result<Row> {
-        let context = RowContext::new(self.table_name, Cow::Borrowed(&row), None);
+    /// Evaluates assignments against `row` first and then `next`, e.g. the `EXCLUDED`
+    /// row of `INSERT ... ON CONFLICT DO UPDATE` or the bound parameters.
+    pub async fn apply(&self, row: Row, next: Option<Arc<RowContext<'_>>>) -> Result<Row> {
+        let context = RowContext::new(self.table_name, Cow::Borrowed(&row), next);
         let context = Some(Arc::new(context));
 
         let assignments = stream::iter(self.fields.iter())
@@ -118,41 +331,6 @@ impl<'a, T: GStore> Update<'a, T> {
                     Ok::<_, Error>((id.as_ref(), value))
                 }
             })
-            .and_then(|(id, value)| async move {
-                if value == Value::Null {
-                    return Ok((id, value));
-                }
-
-                for foreign_key in foreign_keys {
-                    let ForeignKey {
-                        referencing_column_name,
-                        referenced_table_name,
-                        referenced_column_name,
-                        ..
-                    } = foreign_key;
-
-                    if referencing_column_name != id {
-                        continue;
-                    }
-
-                    let no_referenced = self
-                        .storage
-                        .fetch_data(referenced_table_name, &Key::try_from(&value)?)
-                        .await?
-                        .is_none();
-
-                    if no_referenced {
-                        return Err(UpdateError::CannotFindReferencedValue {
-                            table_name: referenced_table_name.to_owned(),
-                            column_name: referenced_column_name.to_owned(),
-                            referenced_value: String::from(value),
-                        }
-                        .into());
-                    }
-                }
-
-                Ok((id, value))
-            })
             .try_collect::<Vec<(&str, Value)>>()
             .await?;
 
@@ -168,6 +346,10 @@ impl<'a, T: GStore> Update<'a, T> {
                             .unwrap_or(value)
                     })
                     .collect();
+                let values = match self.column_defs {
+                    Some(column_defs) => fill_generated(column_defs, values).await?,
+                    None => values,
+                };
 
                 Row::Vec { columns, values }
             }
@@ -1,16 +1,19 @@
 use {
+    super::{cascade::fetch_rows_in, context::RowContext, evaluate_stateless},
     crate::{
-        ast::{ColumnDef, ColumnUniqueOption},
-        data::{Key, Value},
+        ast::{
+            CheckConstraint, ColumnDef, ColumnUniqueOption, Expr, ForeignKey, IndexOperator,
+            KeyConstraint, ToSql,
+        },
+        data::{Key, SchemaIndex, Value},
         result::Result,
-        store::{DataRow, Store},
+        store::{DataRow, GStore},
     },
     futures::stream::TryStreamExt,
     im::HashSet,
     serde::Serialize,
     std::fmt::Debug,
     thiserror::Error as ThisError,
-    utils::Vector,
 };
 
 #[derive(ThisError, Debug, PartialEq, Serialize)]
@@ -26,6 +29,18 @@ pub enum ValidateError {
 
     #[error("duplicate entry '{0:?}' for primary_key field")]
     DuplicateEntryOnPrimaryKeyField(Key),
+
+    #[error("duplicate entry '{0:?}' for unique columns '{1}'")]
+    DuplicateEntryOnUniqueColumns(Key, String),
+
+    #[error("row of table '{table_name}' violates check constraint '{constraint}'")]
+    CheckConstraintViolation {
+        table_name: String,
+        constraint: String,
+    },
+
+    #[error("check constraint '{0}' must evaluate to a boolean")]
+    CheckConstraintNotBoolean(String),
 }
 
 pub enum ColumnValidation<'column_def> {
@@ -36,97 +51,225 @@ pub enum ColumnValidation<'column_def> {
 }
 
 #[derive(Debug)]
-struct UniqueConstraint {
-    column_index: usize,
-    column_name: String,
+struct UniqueConstraint<'a> {
+    column_defs: &'a [ColumnDef],
+    columns: Vec<usize>,
+    is_primary: bool,
     keys: HashSet<Key>,
 }
 
-impl UniqueConstraint {
-    fn new(column_index: usize, column_name: String) -> Self {
+impl<'a> UniqueConstraint<'a> {
+    fn new(column_defs: &'a [ColumnDef], columns: Vec<usize>, is_primary: bool) -> Self {
         Self {
-            column_index,
-            column_name,
+            column_defs,
+            columns,
+            is_primary,
             keys: HashSet::new(),
         }
     }
 
-    fn add(self, value: &Value) -> Result<Self> {
-        let new_key = self.check(value)?;
-
-        if matches!(new_key, Key::None) {
-            return Ok(self);
-        }
+    fn add(self, values: &[Value]) -> Result<Self> {
+        let new_key = match self.check(values)? {
+            Some(new_key) => new_key,
+            None => return Ok(self),
+        };
 
         let keys = self.keys.update(new_key);
 
-        Ok(Self {
-            column_index: self.column_index,
-            column_name: self.column_name,
-            keys,
-        })
+        Ok(Self { keys, ..self })
     }
 
-    fn check(&self, value: &Value) -> Result<Key> {
-        let key = Key::try_from(value)?;
+    fn check(&self, values: &[Value]) -> Result<Option<Key>> {
+        let Some(key) = unique_key(&self.columns, values)? else {
+            return Ok(None);
+        };
 
         if !self.keys.contains(&key) {
-            Ok(key)
+            Ok(Some(key))
         } else {
-            Err(ValidateError::DuplicateEntryOnUniqueField(
-                value.clone(),
-                self.column_name.to_owned(),
-            )
-            .into())
+            Err(duplicate_entry(self.column_defs, &self.columns, self.is_primary, values)?.into())
+        }
+    }
+
+    /// Looks the rows up in the unique index on the constraint columns instead of scanning
+    /// the whole table for them
+    async fn lookup<'b, T: GStore>(
+        &self,
+        storage: &T,
+        table_name: &str,
+        index_name: &str,
+        row_iter: impl Iterator<Item = &'b [Value]>,
+    ) -> Result<()> {
+        for values in row_iter {
+            let mut prefix = self
+                .columns
+                .iter()
+                .map(|index| {
+                    values
+                        .get(*index)
+                        .cloned()
+                        .ok_or(ValidateError::ConflictOnStorageColumnIndex(*index))
+                })
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+
+            if prefix.iter().any(Value::is_null) {
+                continue;
+            }
+
+            let Some(value) = prefix.pop() else {
+                continue;
+            };
+            let found = storage
+                .scan_indexed_data(
+                    table_name,
+                    index_name,
+                    None,
+                    &prefix,
+                    Some((&IndexOperator::Eq, value)),
+                )
+                .await?
+                .try_next()
+                .await?
+                .is_some();
+
+            if found {
+                return Err(duplicate_entry(
+                    self.column_defs,
+                    &self.columns,
+                    self.is_primary,
+                    values,
+                )?
+                .into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Name of the unique index on exactly the `columns`, which can replace a table scan
+pub fn unique_index<'b>(
+    indexes: &'b [SchemaIndex],
+    column_defs: &[ColumnDef],
+    columns: &[usize],
+) -> Option<&'b str> {
+    indexes
+        .iter()
+        .find(|SchemaIndex { exprs, unique, .. }| {
+            *unique
+                && exprs.len() == columns.len()
+                && exprs.iter().zip(columns).all(|(expr, index)| {
+                    matches!(expr, Expr::Identifier(ident) if ident == &column_defs[*index].name)
+                })
+        })
+        .map(|SchemaIndex { name, .. }| name.as_str())
+}
+
+/// Key of a row on the unique `columns`, built as the primary key of the row would be.
+/// Returns `None` when any of the columns is `NULL`, which never conflicts.
+pub fn unique_key(columns: &[usize], values: &[Value]) -> Result<Option<Key>> {
+    for index in columns {
+        let value = values
+            .get(*index)
+            .ok_or(ValidateError::ConflictOnStorageColumnIndex(*index))?;
+
+        if value.is_null() {
+            return Ok(None);
         }
     }
+
+    Key::from_columns(columns, values).map(Some)
+}
+
+/// Error for a row whose values on the unique `columns` are already taken
+pub fn duplicate_entry(
+    column_defs: &[ColumnDef],
+    columns: &[usize],
+    is_primary: bool,
+    values: &[Value],
+) -> Result<ValidateError> {
+    let key = Key::from_columns(columns, values)?;
+
+    Ok(match (is_primary, columns) {
+        (true, _) => ValidateError::DuplicateEntryOnPrimaryKeyField(key),
+        (false, [index]) => ValidateError::DuplicateEntryOnUniqueField(
+            values[*index].clone(),
+            column_defs[*index].name.to_owned(),
+        ),
+        (false, _) => {
+            let names = columns
+                .iter()
+                .map(|index| column_defs[*index].name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            ValidateError::DuplicateEntryOnUniqueColumns(key, names)
+        }
+    })
 }
 
-pub async fn validate_unique<T: Store>(
+/// Column indexes of the unique columns and the table-level key constraints, with whether
+/// each of them is the primary key
+pub fn unique_columns(
+    column_defs: &[ColumnDef],
+    key_constraints: &[KeyConstraint],
+) -> Vec<(Vec<usize>, bool)> {
+    let column_constraints =
+        column_defs
+            .iter()
+            .enumerate()
+            .filter_map(|(index, ColumnDef { unique, .. })| {
+                unique.map(|ColumnUniqueOption { is_primary }| (vec![index], is_primary))
+            });
+    let key_constraints = key_constraints.iter().map(|key_constraint| {
+        let columns = key_constraint
+            .columns
+            .iter()
+            .filter_map(|name| {
+                column_defs
+                    .iter()
+                    .position(|column_def| &column_def.name == name)
+            })
+            .collect();
+
+        (columns, key_constraint.is_primary)
+    });
+
+    column_constraints.chain(key_constraints).collect()
+}
+
+/// Validates the unique columns and key constraints of the rows to write. Existing rows are
+/// looked up in a unique index on the constraint columns when there is one, and found by
+/// scanning the table otherwise.
+pub async fn validate_unique<T: GStore>(
     storage: &T,
     table_name: &str,
     column_validation: ColumnValidation<'_>,
+    key_constraints: &[KeyConstraint],
+    indexes: &[SchemaIndex],
     row_iter: impl Iterator<Item = &[Value]> + Clone,
 ) -> Result<()> {
-    enum Columns {
-        /// key index
-        PrimaryKeyOnly(usize),
-        /// `[(key_index, table_name)]`
-        All(Vec<(usize, String)>),
-    }
-
-    let columns = match &column_validation {
-        ColumnValidation::All(column_defs) => {
-            let primary_key_index = column_defs
-                .iter()
-                .enumerate()
-                .find(|(_, ColumnDef { unique, .. })| {
-                    unique == &Some(ColumnUniqueOption { is_primary: true })
-                })
-                .map(|(i, _)| i);
-            let other_unique_column_def_count = column_defs
-                .iter()
-                .filter(|ColumnDef { unique, .. }| {
-                    unique == &Some(ColumnUniqueOption { is_primary: false })
-                })
-                .count();
-
-            match (primary_key_index, other_unique_column_def_count) {
-                (Some(primary_key_index), 0) => Columns::PrimaryKeyOnly(primary_key_index),
-                _ => Columns::All(fetch_all_unique_columns(column_defs)),
-            }
+    let (column_defs, specified_columns) = match &column_validation {
+        ColumnValidation::All(column_defs) => (*column_defs, None),
+        ColumnValidation::SpecifiedColumns(column_defs, specified_columns) => {
+            (*column_defs, Some(specified_columns))
         }
-        ColumnValidation::SpecifiedColumns(column_defs, specified_columns) => Columns::All(
-            fetch_specified_unique_columns(column_defs, specified_columns),
-        ),
     };
+    let constraints = unique_columns(column_defs, key_constraints)
+        .into_iter()
+        .filter(|(columns, _)| match specified_columns {
+            Some(specified_columns) => columns
+                .iter()
+                .any(|index| specified_columns.contains(&column_defs[*index].name)),
+            None => true,
+        })
+        .collect::<Vec<_>>();
 
-    match columns {
-        Columns::PrimaryKeyOnly(primary_key_index) => {
-            for primary_key in
-                row_iter.filter_map(|row| row.get(primary_key_index).map(Key::try_from))
-            {
-                let key = primary_key?;
+    match constraints.as_slice() {
+        [] => Ok(()),
+        [(columns, true)] if specified_columns.is_none() => {
+            for values in row_iter {
+                let key = Key::from_columns(columns, values)?;
 
                 if storage.fetch_data(table_name, &key).await?.is_some() {
                     return Err(ValidateError::DuplicateEntryOnPrimaryKeyField(key).into());
@@ -135,17 +278,38 @@ pub async fn validate_unique<T: Store>(
 
             Ok(())
         }
-        Columns::All(columns) => {
-            let unique_constraints: Vec<_> = create_unique_constraints(columns, row_iter)?.into();
-            if unique_constraints.is_empty() {
+        _ => {
+            let unique_constraints = constraints
+                .into_iter()
+                .map(|(columns, is_primary)| {
+                    row_iter.clone().try_fold(
+                        UniqueConstraint::new(column_defs, columns, is_primary),
+                        |constraint, values| constraint.add(values),
+                    )
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            let mut scanned_constraints = Vec::new();
+            for constraint in unique_constraints {
+                match unique_index(indexes, column_defs, &constraint.columns) {
+                    Some(index_name) => {
+                        constraint
+                            .lookup(storage, table_name, index_name, row_iter.clone())
+                            .await?;
+                    }
+                    None => scanned_constraints.push(constraint),
+                }
+            }
+
+            if scanned_constraints.is_empty() {
                 return Ok(());
             }
 
-            let unique_constraints = &unique_constraints;
+            let unique_constraints = &scanned_constraints;
             storage
                 .scan_data(table_name)
                 .await?
-                .try_for_each(|(_, data_row)| async {
+                .try_for_each(|(_, data_row)| async move {
                     let values = match data_row {
                         DataRow::Vec(values) => values,
                         DataRow::Map(_) => {
@@ -155,63 +319,141 @@ pub async fn validate_unique<T: Store>(
                         }
                     };
 
-                    unique_constraints.iter().try_for_each(|constraint| {
-                        let col_idx = constraint.column_index;
-                        let val = values
-                            .get(col_idx)
-                            .ok_or(ValidateError::ConflictOnStorageColumnIndex(col_idx))?;
-
-                        constraint.check(val)?;
-
-                        Ok(())
-                    })
+                    unique_constraints
+                        .iter()
+                        .try_for_each(|constraint| constraint.check(&values).map(|_| ()))
                 })
                 .await
         }
     }
 }
 
-fn create_unique_constraints<'a>(
-    unique_columns: Vec<(usize, String)>,
-    row_iter: impl Iterator<Item = &'a [Value]> + Clone,
-) -> Result<Vector<UniqueConstraint>> {
-    unique_columns
+/// Returns the first of `values` missing from the column `foreign_key` references. Values
+/// are looked up by key when the column is the primary key of the referenced table, and
+/// fetched at once when it is only unique. `NULL` never needs a referenced row.
+pub async fn find_unreferenced<T: GStore>(
+    storage: &T,
+    foreign_key: &ForeignKey,
+    values: Vec<Value>,
+) -> Result<Option<Value>> {
+    let ForeignKey {
+        referenced_table_name,
+        referenced_column_name,
+        ..
+    } = foreign_key;
+
+    let values = values
         .into_iter()
-        .try_fold(Vector::new(), |constraints, col| {
-            let (col_idx, col_name) = col;
-            let new_constraint = UniqueConstraint::new(col_idx, col_name);
-            let new_constraint = row_iter
-                .clone()
-                .try_fold(new_constraint, |constraint, row| {
-                    let val = row
-                        .get(col_idx)
-                        .ok_or(ValidateError::ConflictOnStorageColumnIndex(col_idx))?;
-
-                    constraint.add(val)
-                })?;
-            Ok(constraints.push(new_constraint))
+        .filter(|value| !value.is_null())
+        .collect::<Vec<_>>();
+
+    if values.is_empty() {
+        return Ok(None);
+    }
+
+    let is_primary_key = storage
+        .fetch_schema(referenced_table_name)
+        .await?
+        .and_then(|schema| {
+            let index = schema
+                .column_defs
+                .as_deref()?
+                .iter()
+                .position(|column_def| &column_def.name == referenced_column_name)?;
+
+            Some(schema.primary_key_indexes()? == [index])
         })
+        .unwrap_or(false);
+
+    if is_primary_key {
+        for value in values {
+            let key = Key::try_from(&value)?;
+
+            if storage
+                .fetch_data(referenced_table_name, &key)
+                .await?
+                .is_none()
+            {
+                return Ok(Some(value));
+            }
+        }
+
+        return Ok(None);
+    }
+
+    let referenced = fetch_rows_in(
+        storage,
+        referenced_table_name,
+        referenced_column_name,
+        values.clone(),
+    )
+    .await?
+    .into_iter()
+    .filter_map(|(_, row)| row.get_value(referenced_column_name).map(Key::try_from))
+    .collect::<Result<HashSet<Key>>>()?;
+
+    for value in values {
+        if !referenced.contains(&Key::try_from(&value)?) {
+            return Ok(Some(value));
+        }
+    }
+
+    Ok(None)
 }
 
-fn fetch_all_unique_columns(column_defs: &[ColumnDef]) -> Vec<(usize, String)> {
-    column_defs
+/// Evaluates column and table `CHECK` constraints against each row. A constraint is
+/// satisfied unless it evaluates to `FALSE`, so `NULL` results pass as in standard SQL.
+pub async fn validate_check<'a>(
+    table_name: &str,
+    column_defs: &[ColumnDef],
+    checks: &[CheckConstraint],
+    row_iter: impl Iterator<Item = &'a [Value]>,
+) -> Result<()> {
+    let checks = column_defs
         .iter()
-        .enumerate()
-        .filter_map(|(i, table_col)| table_col.unique.map(|_| (i, table_col.name.to_owned())))
-        .collect()
-}
+        .filter_map(|column_def| column_def.check.as_ref())
+        .chain(checks)
+        .collect::<Vec<_>>();
+
+    if checks.is_empty() {
+        return Ok(());
+    }
 
-fn fetch_specified_unique_columns(
-    all_column_defs: &[ColumnDef],
-    specified_columns: &[String],
-) -> Vec<(usize, String)> {
-    all_column_defs
+    let columns = column_defs
         .iter()
-        .enumerate()
-        .filter_map(|(i, table_col)| {
-            (table_col.unique.is_some()
-                && specified_columns.iter().any(|col| col == &table_col.name))
-            .then_some((i, table_col.name.to_owned()))
-        })
-        .collect()
+        .map(|column_def| column_def.name.to_owned())
+        .collect::<Vec<_>>();
+
+    for values in row_iter {
+        for check in checks.iter() {
+            let context = RowContext::RefVecData {
+                columns: &columns,
+                values,
+            };
+            let value = evaluate_stateless(Some(context), &check.expr)
+                .await
+                .and_then(Value::try_from)?;
+
+            match value {
+                Value::Bool(true) | Value::Null => {}
+                Value::Bool(false) => {
+                    return Err(ValidateError::CheckConstraintViolation {
+                        table_name: table_name.to_owned(),
+                        constraint: check_label(check),
+                    }
+                    .into());
+                }
+                _ => {
+                    return Err(ValidateError::CheckConstraintNotBoolean(check_label(check)).into());
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Names a check constraint in errors, after its expression when it has no name.
+pub fn check_label(CheckConstraint { name, expr }: &CheckConstraint) -> String {
+    name.to_owned().unwrap_or_else(|| expr.to_sql())
 }
@@ -0,0 +1,508 @@
+use {
+    super::{
+        aggregate::{Accumulator, evaluate_argument},
+        context::{AggregateContext, RowContext},
+        evaluate::{evaluate, evaluate_stateless},
+        sort::sort_by,
+    },
+    crate::{
+        ast::{
+            Aggregate, AggregateFunction, Expr, OrderByExpr, SelectItem, WindowFrame,
+            WindowFrameBound, WindowFrameUnits, WindowSpec,
+        },
+        data::{Key, Value},
+        result::Result,
+        store::GStore,
+    },
+    futures::stream::{self, Stream, TryStreamExt},
+    im::HashMap,
+    serde::Serialize,
+    std::{cmp::Ordering, collections::HashMap as StdHashMap, sync::Arc},
+    thiserror::Error as ThisError,
+};
+
+#[derive(ThisError, Serialize, Debug, PartialEq, Eq)]
+pub enum WindowError {
+    #[error("window function requires OVER clause: {0}")]
+    OverClauseRequired(String),
+
+    #[error("RANGE frame with offset requires exactly one ORDER BY expression, found: {0}")]
+    RangeOffsetRequiresSingleOrderBy(usize),
+}
+
+#[derive(futures_enum::Stream)]
+enum S<T1, T2> {
+    NonWindow(T1),
+    Window(T2),
+}
+
+/// Evaluates the window functions in `fields` and `order_by`, and stores their values into
+/// the aggregated values of each row, so the following project and sort stages can read
+/// them just like grouped aggregates.
+pub async fn apply<'a, T: GStore, U: Stream<Item = Result<AggregateContext<'a>>> + 'a>(
+    storage: &'a T,
+    fields: &'a [SelectItem],
+    order_by: &'a [OrderByExpr],
+    filter_context: Option<Arc<RowContext<'a>>>,
+    rows: U,
+) -> Result<impl Stream<Item = Result<AggregateContext<'a>>> + use<'a, T, U>> {
+    let mut windows = Vec::new();
+    let exprs = fields
+        .iter()
+        .filter_map(|field| match field {
+            SelectItem::Expr { expr, .. } => Some(expr),
+            _ => None,
+        })
+        .chain(order_by.iter().map(|order_by| &order_by.expr));
+    for expr in exprs {
+        collect_windows(expr, &mut windows);
+    }
+
+    if windows.is_empty() {
+        return Ok(S::NonWindow(rows));
+    }
+
+    let rows = rows.try_collect::<Vec<_>>().await?;
+    let mut aggregated = rows
+        .iter()
+        .map(|row| row.aggregated.clone().unwrap_or_default())
+        .collect::<Vec<_>>();
+
+    for aggr in windows {
+        let values = evaluate_window(storage, filter_context.as_ref(), aggr, &rows).await?;
+
+        for (aggregated, value) in aggregated.iter_mut().zip(values) {
+            aggregated.insert(aggr, value);
+        }
+    }
+
+    let rows =
+        rows.into_iter()
+            .zip(aggregated)
+            .map(|(AggregateContext { next, .. }, aggregated)| {
+                Ok(AggregateContext {
+                    aggregated: Some(aggregated),
+                    next,
+                })
+            });
+
+    Ok(S::Window(stream::iter(rows)))
+}
+
+fn collect_windows<'a>(expr: &'a Expr, windows: &mut Vec<&'a Aggregate>) {
+    match expr {
+        Expr::Between {
+            expr, low, high, ..
+        } => {
+            collect_windows(expr, windows);
+            collect_windows(low, windows);
+            collect_windows(high, windows);
+        }
+        Expr::BinaryOp { left, right, .. } => {
+            collect_windows(left, windows);
+            collect_windows(right, windows);
+        }
+        Expr::UnaryOp { expr, .. } | Expr::Nested(expr) => collect_windows(expr, windows),
+        Expr::Case {
+            operand,
+            when_then,
+            else_result,
+        } => {
+            let exprs = operand
+                .iter()
+                .chain(else_result.iter())
+                .map(AsRef::as_ref)
+                .chain(when_then.iter().flat_map(|(when, then)| [when, then]));
+
+            for expr in exprs {
+                collect_windows(expr, windows);
+            }
+        }
+        Expr::Function(func) => {
+            for expr in func.as_exprs() {
+                collect_windows(expr, windows);
+            }
+        }
+        Expr::Aggregate(aggr) if aggr.over.is_some() => {
+            if !windows.contains(&aggr.as_ref()) {
+                windows.push(aggr);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Inputs of a window function evaluated against a single row.
+struct Entry {
+    partition: Vec<Key>,
+    order: Vec<(Key, Option<bool>)>,
+    order_values: Vec<Value>,
+    value: Value,
+    offset: usize,
+    default: Value,
+}
+
+async fn evaluate_window<'a, T: GStore>(
+    storage: &'a T,
+    filter_context: Option<&Arc<RowContext<'a>>>,
+    aggr: &'a Aggregate,
+    rows: &[AggregateContext<'a>],
+) -> Result<Vec<Value>> {
+    let over = match &aggr.over {
+        Some(over) => over,
+        None => return Err(WindowError::OverClauseRequired(aggr.func.name().to_owned()).into()),
+    };
+
+    let mut entries = Vec::with_capacity(rows.len());
+    for AggregateContext { aggregated, next } in rows {
+        let context = match filter_context {
+            Some(filter_context) => Arc::new(RowContext::concat(
+                Arc::clone(next),
+                Arc::clone(filter_context),
+            )),
+            None => Arc::clone(next),
+        };
+        let aggregated = aggregated.clone().map(Arc::new);
+
+        entries.push(evaluate_entry(storage, context, aggregated, aggr, over).await?);
+    }
+
+    let mut partitions: Vec<Vec<usize>> = Vec::new();
+    let mut positions = StdHashMap::new();
+    for (index, entry) in entries.iter().enumerate() {
+        let position = *positions.entry(entry.partition.clone()).or_insert_with(|| {
+            partitions.push(Vec::new());
+            partitions.len() - 1
+        });
+
+        partitions[position].push(index);
+    }
+
+    let frame = Frame::new(over).await?;
+    let mut values = vec![Value::Null; entries.len()];
+    for mut partition in partitions {
+        partition.sort_by(|a, b| sort_by(&entries[*a].order, &entries[*b].order));
+
+        let partition = partition
+            .into_iter()
+            .map(|index| (index, &entries[index]))
+            .collect::<Vec<_>>();
+
+        for (index, value) in evaluate_partition(aggr, &frame, &partition).await? {
+            values[index] = value;
+        }
+    }
+
+    Ok(values)
+}
+
+async fn evaluate_entry<'a, T: GStore>(
+    storage: &'a T,
+    context: Arc<RowContext<'a>>,
+    aggregated: Option<Arc<HashMap<&'a Aggregate, Value>>>,
+    aggr: &'a Aggregate,
+    over: &'a WindowSpec,
+) -> Result<Entry> {
+    let eval = |expr: &'a Expr| {
+        let context = Some(Arc::clone(&context));
+        let aggregated = aggregated.as_ref().map(Arc::clone);
+
+        async move { Value::try_from(evaluate(storage, context, aggregated, expr).await?) }
+    };
+
+    let mut partition = Vec::with_capacity(over.partition_by.len());
+    for expr in &over.partition_by {
+        partition.push(Key::try_from(eval(expr).await?)?);
+    }
+
+    let mut order = Vec::with_capacity(over.order_by.len());
+    let mut order_values = Vec::with_capacity(over.order_by.len());
+    for OrderByExpr { expr, asc } in &over.order_by {
+        let value = eval(expr).await?;
+
+        order.push((Key::try_from(&value)?, *asc));
+        order_values.push(value);
+    }
+
+    let (mut offset, mut default) = (1, Value::Null);
+    let value = match &aggr.func {
+        AggregateFunction::RowNumber | AggregateFunction::Rank | AggregateFunction::DenseRank => {
+            Value::Null
+        }
+        AggregateFunction::Lag {
+            expr,
+            offset: offset_expr,
+            default: default_expr,
+        }
+        | AggregateFunction::Lead {
+            expr,
+            offset: offset_expr,
+            default: default_expr,
+        } => {
+            if let Some(offset_expr) = offset_expr {
+                offset = usize::try_from(&eval(offset_expr).await?)?;
+            }
+            if let Some(default_expr) = default_expr {
+                default = eval(default_expr).await?;
+            }
+
+            eval(expr).await?
+        }
+        AggregateFunction::FirstValue(expr) | AggregateFunction::LastValue(expr) => {
+            eval(expr).await?
+        }
+        _ => evaluate_argument(storage, Some(context.clone()), aggregated.clone(), aggr).await?,
+    };
+
+    Ok(Entry {
+        partition,
+        order,
+        order_values,
+        value,
+        offset,
+        default,
+    })
+}
+
+async fn evaluate_partition(
+    aggr: &Aggregate,
+    frame: &Frame,
+    partition: &[(usize, &Entry)],
+) -> Result<Vec<(usize, Value)>> {
+    let size = partition.len();
+    let is_peer = |a: usize, b: usize| {
+        sort_by(&partition[a].1.order, &partition[b].1.order) == Ordering::Equal
+    };
+
+    // peer groups of rows sharing the same ORDER BY keys: [first, last + 1)
+    let mut peers = Vec::with_capacity(size);
+    let mut dense_rank = 0;
+    let mut start = 0;
+    while start < size {
+        let end = (start + 1..size)
+            .find(|&i| !is_peer(start, i))
+            .unwrap_or(size);
+
+        dense_rank += 1;
+        peers.extend((start..end).map(|_| (start, end, dense_rank)));
+        start = end;
+    }
+
+    let mut accumulator = Accumulator::new(aggr);
+    let mut accumulated = 0;
+    let mut values = Vec::with_capacity(size);
+    for (position, (index, entry)) in partition.iter().enumerate() {
+        let (peer_start, peer_end, dense_rank) = peers[position];
+        let value = match &aggr.func {
+            AggregateFunction::RowNumber => Value::I64(position as i64 + 1),
+            AggregateFunction::Rank => Value::I64(peer_start as i64 + 1),
+            AggregateFunction::DenseRank => Value::I64(dense_rank),
+            AggregateFunction::Lag { .. } => position
+                .checked_sub(entry.offset)
+                .map(|target| partition[target].1.value.clone())
+                .unwrap_or_else(|| entry.default.clone()),
+            AggregateFunction::Lead { .. } => position
+                .checked_add(entry.offset)
+                .and_then(|target| partition.get(target))
+                .map(|(_, target)| target.value.clone())
+                .unwrap_or_else(|| entry.default.clone()),
+            AggregateFunction::FirstValue(_) | AggregateFunction::LastValue(_) => {
+                let (start, end) = frame.bounds(partition, position, (peer_start, peer_end))?;
+                let target = match aggr.func {
+                    AggregateFunction::FirstValue(_) => start,
+                    _ => end.wrapping_sub(1),
+                };
+
+                match start < end {
+                    true => partition[target].1.value.clone(),
+                    false => Value::Null,
+                }
+            }
+            _ => {
+                let (start, end) = frame.bounds(partition, position, (peer_start, peer_end))?;
+
+                // frames starting from the partition head only grow, so they are accumulated
+                // incrementally, the others are folded from scratch for every row.
+                if !frame.is_unbounded_preceding() {
+                    accumulator = Accumulator::new(aggr);
+                    accumulated = start;
+                }
+
+                for (_, entry) in partition.iter().take(end).skip(accumulated) {
+                    accumulator.accumulate(&entry.value)?;
+                }
+                accumulated = accumulated.max(end);
+
+                accumulator.export().await?
+            }
+        };
+
+        values.push((*index, value));
+    }
+
+    Ok(values)
+}
+
+enum Bound {
+    Unbounded,
+    CurrentRow,
+    Rows(isize),
+    Range { offset: Value, preceding: bool },
+}
+
+/// Window frame whose offsets are evaluated once for every partition.
+struct Frame {
+    units: WindowFrameUnits,
+    start: Bound,
+    end: Bound,
+}
+
+impl Frame {
+    async fn new(over: &WindowSpec) -> Result<Self> {
+        let WindowSpec {
+            order_by, frame, ..
+        } = over;
+
+        let WindowFrame { units, start, end } = match frame {
+            Some(frame) => frame,
+            None if order_by.is_empty() => {
+                return Ok(Self {
+                    units: WindowFrameUnits::Rows,
+                    start: Bound::Unbounded,
+                    end: Bound::Unbounded,
+                });
+            }
+            None => {
+                return Ok(Self {
+                    units: WindowFrameUnits::Range,
+                    start: Bound::Unbounded,
+                    end: Bound::CurrentRow,
+                });
+            }
+        };
+
+        let start = match start {
+            WindowFrameBound::Following(None) => Bound::Rows(isize::MAX),
+            start => Self::bound(*units, order_by.len(), start).await?,
+        };
+        let end = match end {
+            WindowFrameBound::Preceding(None) => Bound::Rows(isize::MIN),
+            end => Self::bound(*units, order_by.len(), end).await?,
+        };
+
+        Ok(Self {
+            units: *units,
+            start,
+            end,
+        })
+    }
+
+    async fn bound(
+        units: WindowFrameUnits,
+        order_by_len: usize,
+        bound: &WindowFrameBound,
+    ) -> Result<Bound> {
+        let (offset, preceding) = match bound {
+            WindowFrameBound::CurrentRow => return Ok(Bound::CurrentRow),
+            WindowFrameBound::Preceding(None) | WindowFrameBound::Following(None) => {
+                return Ok(Bound::Unbounded);
+            }
+            WindowFrameBound::Preceding(Some(offset)) => (offset, true),
+            WindowFrameBound::Following(Some(offset)) => (offset, false),
+        };
+        let offset = Value::try_from(evaluate_stateless(None, offset).await?)?;
+
+        match units {
+            WindowFrameUnits::Rows => {
+                let offset = usize::try_from(&offset)? as isize;
+
+                Ok(Bound::Rows(if preceding { -offset } else { offset }))
+            }
+            WindowFrameUnits::Range if order_by_len != 1 => {
+                Err(WindowError::RangeOffsetRequiresSingleOrderBy(order_by_len).into())
+            }
+            WindowFrameUnits::Range => Ok(Bound::Range { offset, preceding }),
+        }
+    }
+
+    fn is_unbounded_preceding(&self) -> bool {
+        matches!(self.start, Bound::Unbounded)
+    }
+
+    /// Returns the frame of the row at `position` as a half-open range of the partition.
+    fn bounds(
+        &self,
+        partition: &[(usize, &Entry)],
+        position: usize,
+        (peer_start, peer_end): (usize, usize),
+    ) -> Result<(usize, usize)> {
+        let size = partition.len();
+        let rows = |offset: isize| position.saturating_add_signed(offset).min(size);
+
+        let start = match &self.start {
+            Bound::Unbounded => 0,
+            Bound::CurrentRow if self.units == WindowFrameUnits::Range => peer_start,
+            Bound::CurrentRow => position,
+            Bound::Rows(offset) => rows(*offset),
+            Bound::Range { offset, preceding } => {
+                let target = range_target(partition, position, offset, *preceding)?;
+
+                partition.partition_point(|(_, entry)| {
+                    range_cmp(&entry.order_values[0], &target, entry.order[0].1) == Ordering::Less
+                })
+            }
+        };
+
+        let end = match &self.end {
+            Bound::Unbounded => size,
+            Bound::CurrentRow if self.units == WindowFrameUnits::Range => peer_end,
+            Bound::CurrentRow => position + 1,
+            Bound::Rows(offset) => match position.checked_add_signed(*offset) {
+                Some(end) => (end + 1).min(size),
+                None => 0,
+            },
+            Bound::Range { offset, preceding } => {
+                let target = range_target(partition, position, offset, *preceding)?;
+
+                partition.partition_point(|(_, entry)| {
+                    range_cmp(&entry.order_values[0], &target, entry.order[0].1)
+                        != Ordering::Greater
+                })
+            }
+        };
+
+        Ok((start, end))
+    }
+}
+
+/// Computes the ORDER BY value which is `offset` away from the current row.
+fn range_target(
+    partition: &[(usize, &Entry)],
+    position: usize,
+    offset: &Value,
+    preceding: bool,
+) -> Result<Value> {
+    let entry = partition[position].1;
+    let value = &entry.order_values[0];
+    let asc = entry.order[0].1.unwrap_or(true);
+
+    match preceding == asc {
+        true => value.subtract(offset),
+        false => value.add(offset),
+    }
+}
+
+/// Compares ORDER BY values in the sorted order of the window, NULL sorts last.
+fn range_cmp(value: &Value, target: &Value, asc: Option<bool>) -> Ordering {
+    let ordering = match (value.is_null(), target.is_null()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        (false, false) => value.evaluate_cmp(target).unwrap_or(Ordering::Equal),
+    };
+
+    match asc.unwrap_or(true) {
+        true => ordering,
+        false => ordering.reverse(),
+    }
+}
@@ -1,8 +1,12 @@
 use {
     crate::{
         ast::Statement,
-        executor::{Payload, execute},
-        parse_sql::parse,
+        data::Value,
+        executor::{
+            ExecuteError, ExecuteOptions, Payload, PayloadStream, execute_with_options,
+            stream_statement,
+        },
+        parse_sql::{count_params, parse},
         plan::plan,
         result::Result,
         store::{GStore, GStoreMut},
@@ -14,14 +18,58 @@ use {
     },
 };
 
+/// Statement planned once by [`Glue::prepare`], to be run with [`Glue::execute_with`]
+/// binding values to its `$n` or `?` parameters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreparedStatement {
+    statement: Statement,
+    num_params: usize,
+}
+
+impl PreparedStatement {
+    pub fn statement(&self) -> &Statement {
+        &self.statement
+    }
+
+    pub fn num_params(&self) -> usize {
+        self.num_params
+    }
+}
+
 #[derive(Debug)]
 pub struct Glue<T: GStore + GStoreMut> {
     pub storage: T,
+    /// Limits applied to each statement
+    options: ExecuteOptions,
+    /// Set while a stream of [`Glue::execute_stream`] holds the transaction of its statement
+    stream_open: bool,
 }
 
 impl<T: GStore + GStoreMut> Glue<T> {
     pub fn new(storage: T) -> Self {
-        Self { storage }
+        Self {
+            storage,
+            options: ExecuteOptions::default(),
+            stream_open: false,
+        }
+    }
+
+    pub fn options(&self) -> &ExecuteOptions {
+        &self.options
+    }
+
+    /// Sets the timeout, cancellation token and row limits of the statements run from now on.
+    pub fn set_options(&mut self, options: ExecuteOptions) {
+        self.options = options;
+    }
+
+    /// Rolls back the transaction left open by a stream dropped before its end.
+    async fn end_dropped_stream(&mut self) -> Result<()> {
+        if std::mem::take(&mut self.stream_open) {
+            self.storage.rollback().await?;
+        }
+
+        Ok(())
     }
 
     pub async fn plan<Sql: AsRef<str>>(&mut self, sql: Sql) -> Result<Vec<Statement>> {
@@ -35,7 +83,9 @@ impl<T: GStore + GStoreMut> Glue<T> {
     }
 
     pub async fn execute_stmt(&mut self, statement: &Statement) -> Result<Payload> {
-        execute(&mut self.storage, statement).await
+        self.end_dropped_stream().await?;
+
+        execute_with_options(&mut self.storage, statement, &[], &self.options).await
     }
 
     pub async fn execute<Sql: AsRef<str>>(&mut self, sql: Sql) -> Result<Vec<Payload>> {
@@ -48,4 +98,66 @@ impl<T: GStore + GStoreMut> Glue<T> {
 
         Ok(payloads)
     }
+
+    /// Executes `statement`, streaming the rows of `SELECT` instead of collecting them.
+    pub async fn execute_stmt_stream(&mut self, statement: Statement) -> Result<PayloadStream<'_>> {
+        self.end_dropped_stream().await?;
+
+        stream_statement(
+            &mut self.storage,
+            statement,
+            &self.options,
+            Some(&mut self.stream_open),
+        )
+        .await
+    }
+
+    /// Executes the single statement of `sql` like [`Glue::execute`], but returns the rows of
+    /// `SELECT` as a stream so that they are never held in memory all at once. A stream dropped
+    /// before its end is rolled back before the next statement runs.
+    pub async fn execute_stream<Sql: AsRef<str>>(&mut self, sql: Sql) -> Result<PayloadStream<'_>> {
+        let mut statements = self.plan(sql).await?;
+
+        match statements.len() {
+            1 => self.execute_stmt_stream(statements.remove(0)).await,
+            n => Err(ExecuteError::StreamRequiresSingleStatement(n).into()),
+        }
+    }
+
+    pub async fn prepare<Sql: AsRef<str>>(&mut self, sql: Sql) -> Result<PreparedStatement> {
+        let num_params = count_params(sql.as_ref())?;
+        let mut statements = self.plan(sql).await?;
+
+        match statements.len() {
+            1 => Ok(PreparedStatement {
+                statement: statements.remove(0),
+                num_params,
+            }),
+            n => Err(ExecuteError::PrepareRequiresSingleStatement(n).into()),
+        }
+    }
+
+    pub async fn execute_with(
+        &mut self,
+        prepared: &PreparedStatement,
+        params: &[Value],
+    ) -> Result<Payload> {
+        if params.len() != prepared.num_params {
+            return Err(ExecuteError::ParameterCountMismatch {
+                expected: prepared.num_params,
+                found: params.len(),
+            }
+            .into());
+        }
+
+        self.end_dropped_stream().await?;
+
+        execute_with_options(
+            &mut self.storage,
+            &prepared.statement,
+            params,
+            &self.options,
+        )
+        .await
+    }
 }
@@ -20,8 +20,11 @@ pub mod prelude {
     pub use crate::{
         ast::DataType,
         data::{Key, Value},
-        executor::{Payload, PayloadVariable, execute},
-        glue::Glue,
+        executor::{
+            CancelToken, ExecuteOptions, Payload, PayloadStream, PayloadVariable, execute,
+            execute_stream,
+        },
+        glue::{Glue, PreparedStatement},
         parse_sql::parse,
         plan::plan,
         result::{Error, Result},
@@ -4,7 +4,8 @@ use {
         result::{Error, Result},
         store::{
             AlterTable, CustomFunction, CustomFunctionMut, DataRow, Index, IndexMut, Metadata,
-            RowIter, Store, StoreMut, Transaction,
+            RowIter, Sequence, SequenceMut, Statistics, StatisticsMut, Store, StoreMut,
+            Transaction, Trigger, TriggerMut, View, ViewMut,
         },
     },
     async_trait::async_trait,
@@ -91,6 +92,14 @@ impl Index for MockStorage {}
 impl IndexMut for MockStorage {}
 impl Transaction for MockStorage {}
 impl Metadata for MockStorage {}
+impl View for MockStorage {}
+impl ViewMut for MockStorage {}
+impl Statistics for MockStorage {}
+impl StatisticsMut for MockStorage {}
+impl Sequence for MockStorage {}
+impl SequenceMut for MockStorage {}
+impl Trigger for MockStorage {}
+impl TriggerMut for MockStorage {}
 
 #[cfg(test)]
 mod tests {
@@ -130,7 +139,10 @@ mod tests {
                     data_type: DataType::Boolean,
                     nullable: false,
                     default: None,
+                    identity: None,
+                    generated: None,
                     unique: None,
+                    check: None,
                     comment: None,
                 },
             ))
@@ -139,18 +151,19 @@ mod tests {
         assert!(block_on(storage.drop_column("Foo", "col", false)).is_err());
 
         // Index & IndexMut
-        assert!(block_on(storage.scan_indexed_data("Foo", "idx_col", None, None)).is_err());
+        assert!(block_on(storage.scan_indexed_data("Foo", "idx_col", None, &[], None)).is_err());
         assert!(
             block_on(storage.create_index(
                 "Foo",
                 "idx_col",
-                &OrderByExpr {
+                &[OrderByExpr {
                     expr: Expr::TypedString {
                         data_type: DataType::Boolean,
                         value: "true".to_owned(),
                     },
                     asc: None,
-                },
+                }],
+                false,
             ))
             .is_err()
         );
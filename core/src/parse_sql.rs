@@ -3,19 +3,260 @@ use {
     sqlparser::{
         ast::{
             Assignment as SqlAssignment, ColumnDef as SqlColumnDef, DataType as SqlDataType,
-            Expr as SqlExpr, Ident as SqlIdent, OrderByExpr as SqlOrderByExpr, Query as SqlQuery,
+            Expr as SqlExpr, FunctionDesc as SqlFunctionDesc, Ident as SqlIdent,
+            ObjectName as SqlObjectName, OrderByExpr as SqlOrderByExpr, Query as SqlQuery,
             SelectItem as SqlSelectItem, Statement as SqlStatement,
+            TriggerExecBody as SqlTriggerExecBody, TriggerExecBodyType as SqlTriggerExecBodyType,
+            TriggerObject as SqlTriggerObject,
         },
         dialect::PostgreSqlDialect,
-        parser::Parser,
-        tokenizer::Tokenizer,
+        keywords::Keyword,
+        parser::{Parser, ParserError},
+        tokenizer::{Token, TokenWithLocation, Tokenizer},
     },
+    std::iter,
 };
 
 const DIALECT: PostgreSqlDialect = PostgreSqlDialect {};
 
 pub fn parse<Sql: AsRef<str>>(sql: Sql) -> Result<Vec<SqlStatement>> {
-    Parser::parse_sql(&DIALECT, sql.as_ref()).map_err(|e| Error::Parser(format!("{e:#?}")))
+    Tokenizer::new(&DIALECT, sql.as_ref())
+        .tokenize_with_location()
+        .map_err(ParserError::from)
+        .and_then(|tokens| {
+            let mut parser =
+                Parser::new(&DIALECT).with_tokens_with_locations(number_placeholders(tokens));
+
+            parse_statements(&mut parser)
+        })
+        .map_err(|e| Error::Parser(format!("{e:#?}")))
+}
+
+/// Same as [`Parser::parse_statements`], with the materialized view statements sqlparser does
+/// not support: `DROP MATERIALIZED VIEW` is parsed as `DROP VIEW`, and
+/// `REFRESH MATERIALIZED VIEW` is carried by `OPTIMIZE TABLE`, which [`PostgreSqlDialect`]
+/// never parses by itself. `ANALYZE` also takes a table without the `TABLE` keyword, or no
+/// table at all, which is parsed as an empty table name. `CREATE TRIGGER` takes a body of
+/// statements, see [`parse_create_trigger`].
+fn parse_statements(parser: &mut Parser) -> Result<Vec<SqlStatement>, ParserError> {
+    parse_statement_list(parser, false)
+}
+
+/// Parses statements until the end of the input, or until `END` closing a block when
+/// `in_block` is set.
+fn parse_statement_list(
+    parser: &mut Parser,
+    in_block: bool,
+) -> Result<Vec<SqlStatement>, ParserError> {
+    let mut statements = Vec::new();
+    let mut expecting_statement_delimiter = false;
+
+    loop {
+        while parser.consume_token(&Token::SemiColon) {
+            expecting_statement_delimiter = false;
+        }
+
+        match parser.peek_token().token {
+            Token::EOF => break,
+            Token::Word(word)
+                if (in_block || expecting_statement_delimiter) && word.keyword == Keyword::END =>
+            {
+                break;
+            }
+            _ => {}
+        }
+
+        if expecting_statement_delimiter {
+            return parser.expected("end of statement", parser.peek_token());
+        }
+
+        let statement = match parser.peek_token().token {
+            Token::Word(word) if word.value.eq_ignore_ascii_case("REFRESH") => {
+                parser.next_token();
+                parser.expect_keywords(&[Keyword::MATERIALIZED, Keyword::VIEW])?;
+
+                SqlStatement::OptimizeTable {
+                    name: parser.parse_object_name(false)?,
+                    on_cluster: None,
+                    partition: None,
+                    include_final: false,
+                    deduplicate: None,
+                }
+            }
+            Token::Word(word)
+                if word.keyword == Keyword::ANALYZE
+                    && !matches!(
+                        parser.peek_nth_token(1).token,
+                        Token::Word(next) if next.keyword == Keyword::TABLE
+                    ) =>
+            {
+                parser.next_token();
+
+                let table_name = match parser.peek_token().token {
+                    Token::EOF | Token::SemiColon => SqlObjectName(Vec::new()),
+                    _ => parser.parse_object_name(false)?,
+                };
+
+                SqlStatement::Analyze {
+                    table_name,
+                    partitions: None,
+                    for_columns: false,
+                    columns: Vec::new(),
+                    cache_metadata: false,
+                    noscan: false,
+                    compute_statistics: false,
+                }
+            }
+            _ if parser.parse_keywords(&[Keyword::DROP, Keyword::MATERIALIZED]) => {
+                parser.parse_drop()?
+            }
+            _ if parser.parse_keywords(&[Keyword::CREATE, Keyword::TRIGGER]) => {
+                parse_create_trigger(parser, false)?
+            }
+            _ if parser.parse_keywords(&[
+                Keyword::CREATE,
+                Keyword::OR,
+                Keyword::REPLACE,
+                Keyword::TRIGGER,
+            ]) =>
+            {
+                parse_create_trigger(parser, true)?
+            }
+            _ => parser.parse_statement()?,
+        };
+
+        statements.push(statement);
+        expecting_statement_delimiter = true;
+    }
+
+    Ok(statements)
+}
+
+/// Parses `CREATE TRIGGER` after the `TRIGGER` keyword. sqlparser only takes
+/// `EXECUTE { FUNCTION | PROCEDURE } <name>()`, so a body of statements, either a single one
+/// or a `BEGIN ... END` block, is carried by `CREATE PROCEDURE` whose body starts with the
+/// `CREATE TRIGGER` header.
+fn parse_create_trigger(
+    parser: &mut Parser,
+    or_replace: bool,
+) -> Result<SqlStatement, ParserError> {
+    let name = parser.parse_object_name(false)?;
+    let period = parser.parse_trigger_period()?;
+    let events = parser.parse_keyword_separated(Keyword::OR, Parser::parse_trigger_event)?;
+    parser.expect_keyword(Keyword::ON)?;
+    let table_name = parser.parse_object_name(false)?;
+    parser.expect_keyword(Keyword::FOR)?;
+    let include_each = parser.parse_keyword(Keyword::EACH);
+    let trigger_object = match parser.expect_one_of_keywords(&[Keyword::ROW, Keyword::STATEMENT])? {
+        Keyword::ROW => SqlTriggerObject::Row,
+        _ => SqlTriggerObject::Statement,
+    };
+    let condition = parser
+        .parse_keyword(Keyword::WHEN)
+        .then(|| parser.parse_expr())
+        .transpose()?;
+    parser.expect_keyword(Keyword::EXECUTE)?;
+
+    let body = match parser.peek_token().token {
+        Token::Word(word) if matches!(word.keyword, Keyword::FUNCTION | Keyword::PROCEDURE) => None,
+        Token::Word(word) if word.keyword == Keyword::BEGIN => {
+            parser.next_token();
+            let body = parse_statement_list(parser, true)?;
+            parser.expect_keyword(Keyword::END)?;
+
+            Some(body)
+        }
+        _ => Some(vec![parser.parse_statement()?]),
+    };
+    let exec_body = match body {
+        None => parser.parse_trigger_exec_body()?,
+        Some(_) => SqlTriggerExecBody {
+            exec_type: SqlTriggerExecBodyType::Function,
+            func_desc: SqlFunctionDesc {
+                name: name.clone(),
+                args: None,
+            },
+        },
+    };
+    let trigger = SqlStatement::CreateTrigger {
+        or_replace,
+        is_constraint: false,
+        name: name.clone(),
+        period,
+        events,
+        table_name,
+        referenced_table_name: None,
+        referencing: Vec::new(),
+        trigger_object,
+        include_each,
+        condition,
+        exec_body,
+        characteristics: None,
+    };
+
+    Ok(match body {
+        None => trigger,
+        Some(body) => SqlStatement::CreateProcedure {
+            or_alter: or_replace,
+            name,
+            params: None,
+            body: iter::once(trigger).chain(body).collect(),
+        },
+    })
+}
+
+/// Returns the number of bind parameters `sql` takes, i.e. the largest `$n` once `?`
+/// placeholders are numbered.
+pub fn count_params<Sql: AsRef<str>>(sql: Sql) -> Result<usize> {
+    let tokens = Tokenizer::new(&DIALECT, sql.as_ref())
+        .tokenize_with_location()
+        .map_err(|e| Error::Parser(format!("{e:#?}")))?;
+
+    Ok(number_placeholders(tokens)
+        .into_iter()
+        .filter_map(|TokenWithLocation { token, .. }| match token {
+            Token::Placeholder(placeholder) => placeholder.strip_prefix('$')?.parse().ok(),
+            _ => None,
+        })
+        .max()
+        .unwrap_or(0))
+}
+
+/// Rewrites `?` placeholders into `$n`, numbering each one after the largest parameter seen
+/// so far in the statement, so both styles are translated into the same parameters.
+fn number_placeholders(tokens: Vec<TokenWithLocation>) -> Vec<TokenWithLocation> {
+    let mut last = 0;
+
+    tokens
+        .into_iter()
+        .map(|TokenWithLocation { token, location }| {
+            let token = match token {
+                Token::Question => {
+                    last += 1;
+
+                    Token::Placeholder(format!("${last}"))
+                }
+                Token::Placeholder(placeholder) => {
+                    if let Some(index) = placeholder
+                        .strip_prefix('$')
+                        .and_then(|index| index.parse::<usize>().ok())
+                    {
+                        last = last.max(index);
+                    }
+
+                    Token::Placeholder(placeholder)
+                }
+                Token::SemiColon => {
+                    last = 0;
+
+                    Token::SemiColon
+                }
+                token => token,
+            };
+
+            TokenWithLocation { token, location }
+        })
+        .collect()
 }
 
 macro_rules! generate_parse_fn {
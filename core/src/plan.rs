@@ -7,20 +7,36 @@ mod join;
 mod planner;
 mod primary_key;
 mod schema;
+mod statistics;
 mod validate;
+mod view;
 
-use crate::{ast::Statement, result::Result, store::Store};
+use crate::{
+    ast::Statement,
+    result::Result,
+    store::{Statistics, Store, View},
+};
 
+pub(crate) use expr::PlanExpr;
 pub use {
     self::validate::validate, error::*, index::plan as plan_index, join::plan as plan_join,
     primary_key::plan as plan_primary_key, schema::fetch_schema_map,
+    statistics::fetch_statistics_map, statistics::plan as plan_statistics, view::fetch_view_map,
+    view::plan as plan_view,
 };
 
-pub async fn plan<T: Store>(storage: &T, statement: Statement) -> Result<Statement> {
+pub async fn plan<T: Store + View + Statistics>(
+    storage: &T,
+    statement: Statement,
+) -> Result<Statement> {
+    let view_map = fetch_view_map(storage, &statement).await?;
+    let statement = plan_view(&view_map, statement)?;
     let schema_map = fetch_schema_map(storage, &statement).await?;
     validate(&schema_map, &statement)?;
     let statement = plan_primary_key(&schema_map, statement);
     let statement = plan_index(&schema_map, statement)?;
+    let statistics_map = fetch_statistics_map(storage, &schema_map).await?;
+    let statement = plan_statistics(&schema_map, &statistics_map, statement);
     let statement = plan_join(&schema_map, statement);
 
     Ok(statement)
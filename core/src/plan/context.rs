@@ -1,9 +1,9 @@
-use std::sync::Arc;
+use std::{borrow::Cow, sync::Arc};
 
 pub enum Context<'a> {
     Data {
         alias: String,
-        columns: Vec<&'a str>,
+        columns: Vec<Cow<'a, str>>,
         primary_key: Option<&'a str>,
         next: Option<Arc<Context<'a>>>,
     },
@@ -11,12 +11,19 @@ pub enum Context<'a> {
         left: Arc<Context<'a>>,
         right: Arc<Context<'a>>,
     },
+    /// CTE defined by an enclosing `WITH` clause, `columns` is `None` when they cannot be
+    /// resolved before execution
+    Cte {
+        name: String,
+        columns: Option<Vec<String>>,
+        next: Option<Arc<Context<'a>>>,
+    },
 }
 
 impl<'a> Context<'a> {
     pub fn new(
         alias: String,
-        columns: Vec<&'a str>,
+        columns: Vec<Cow<'a, str>>,
         primary_key: Option<&'a str>,
         next: Option<Arc<Context<'a>>>,
     ) -> Self {
@@ -28,6 +35,14 @@ impl<'a> Context<'a> {
         }
     }
 
+    pub fn cte(name: String, columns: Option<Vec<String>>, next: Option<Arc<Context<'a>>>) -> Self {
+        Context::Cte {
+            name,
+            columns,
+            next,
+        }
+    }
+
     pub fn concat(
         left: Option<Arc<Context<'a>>>,
         right: Option<Arc<Context<'a>>>,
@@ -42,7 +57,7 @@ impl<'a> Context<'a> {
     pub fn contains_alias(&self, target: &str) -> bool {
         match self {
             Self::Data { alias, .. } if alias == target => true,
-            Self::Data { next, .. } => next
+            Self::Data { next, .. } | Self::Cte { next, .. } => next
                 .as_ref()
                 .map(|next| next.contains_alias(target))
                 .unwrap_or(false),
@@ -54,8 +69,8 @@ impl<'a> Context<'a> {
 
     pub fn contains_column(&self, target: &str) -> bool {
         match self {
-            Self::Data { columns, .. } if columns.iter().any(|column| column == &target) => true,
-            Self::Data { next, .. } => next
+            Self::Data { columns, .. } if columns.iter().any(|column| column == target) => true,
+            Self::Data { next, .. } | Self::Cte { next, .. } => next
                 .as_ref()
                 .map(|next| next.contains_column(target))
                 .unwrap_or(false),
@@ -68,9 +83,9 @@ impl<'a> Context<'a> {
     pub fn contains_aliased_column(&self, target_alias: &str, target_column: &str) -> bool {
         match self {
             Self::Data { alias, columns, .. } if alias == target_alias => {
-                columns.iter().any(|column| column == &target_column)
+                columns.iter().any(|column| column == target_column)
             }
-            Self::Data { next, .. } => next
+            Self::Data { next, .. } | Self::Cte { next, .. } => next
                 .as_ref()
                 .map(|next| next.contains_aliased_column(target_alias, target_column))
                 .unwrap_or(false),
@@ -87,7 +102,7 @@ impl<'a> Context<'a> {
                 primary_key: Some(primary_key),
                 ..
             } if primary_key == &target_column => true,
-            Self::Data { next, .. } => next
+            Self::Data { next, .. } | Self::Cte { next, .. } => next
                 .as_ref()
                 .map(|next| next.contains_primary_key(target_column))
                 .unwrap_or(false),
@@ -97,4 +112,16 @@ impl<'a> Context<'a> {
             }
         }
     }
+
+    /// Finds the nearest CTE named `target`, `Some(None)` means the CTE exists but its
+    /// columns are unknown.
+    pub fn get_cte(&self, target: &str) -> Option<Option<&[String]>> {
+        match self {
+            Self::Cte { name, columns, .. } if name == target => Some(columns.as_deref()),
+            Self::Data { next, .. } | Self::Cte { next, .. } => {
+                next.as_ref().and_then(|next| next.get_cte(target))
+            }
+            Self::Bridge { left, right } => left.get_cte(target).or_else(|| right.get_cte(target)),
+        }
+    }
 }
@@ -7,6 +7,9 @@ pub enum PlanError {
     #[error("column reference {0} is ambiguous, please specify the table name")]
     ColumnReferenceAmbiguous(String),
 
+    #[error("view is defined in terms of itself: {0}")]
+    RecursiveView(String),
+
     #[error("unreachable")]
     Unreachable,
 }
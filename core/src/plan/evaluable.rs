@@ -1,8 +1,8 @@
 use {
     super::{context::Context, expr::PlanExpr},
     crate::ast::{
-        Expr, Join, JoinConstraint, JoinOperator, Query, Select, SelectItem, SetExpr, TableAlias,
-        TableFactor, TableWithJoins, Values,
+        Cte, Expr, Join, JoinConstraint, JoinOperator, Query, Select, SelectItem, SetExpr,
+        TableAlias, TableFactor, TableWithJoins, Values, With,
     },
     std::sync::Arc,
 };
@@ -40,18 +40,31 @@ pub fn check_expr(context: Option<Arc<Context<'_>>>, expr: &Expr) -> bool {
 
 fn check_query(context: Option<Arc<Context<'_>>>, query: &Query) -> bool {
     let Query {
+        with,
         body,
         order_by,
         limit,
         offset,
     } = query;
 
+    let with = with
+        .iter()
+        .flat_map(|With { cte_tables, .. }| cte_tables)
+        .all(|Cte { query, .. }| check_query(context.as_ref().map(Arc::clone), query));
+    if !with {
+        return false;
+    }
+
     let body = match body {
         SetExpr::Select(select) => check_select(context.as_ref().map(Arc::clone), select),
         SetExpr::Values(Values(rows)) => rows
             .iter()
             .flatten()
             .all(|expr| check_expr(context.as_ref().map(Arc::clone), expr)),
+        SetExpr::SetOperation { left, right, .. } => {
+            check_query(context.as_ref().map(Arc::clone), left)
+                && check_query(context.as_ref().map(Arc::clone), right)
+        }
     };
 
     if !body {
@@ -108,11 +121,16 @@ fn check_select(context: Option<Arc<Context<'_>>>, select: &Select) -> bool {
 
         match join_operator {
             JoinOperator::Inner(JoinConstraint::On(expr))
-            | JoinOperator::LeftOuter(JoinConstraint::On(expr)) => {
+            | JoinOperator::LeftOuter(JoinConstraint::On(expr))
+            | JoinOperator::RightOuter(JoinConstraint::On(expr))
+            | JoinOperator::FullOuter(JoinConstraint::On(expr)) => {
                 check_expr(context.as_ref().map(Arc::clone), expr)
             }
-            JoinOperator::Inner(JoinConstraint::None)
-            | JoinOperator::LeftOuter(JoinConstraint::None) => true,
+            JoinOperator::Inner(_)
+            | JoinOperator::LeftOuter(_)
+            | JoinOperator::RightOuter(_)
+            | JoinOperator::FullOuter(_)
+            | JoinOperator::Cross => true,
         }
     }) {
         return false;
@@ -166,14 +184,14 @@ mod tests {
             let left_child = Context::new("Empty".to_owned(), Vec::new(), None, None);
             let left = Context::new(
                 "Foo".to_owned(),
-                vec!["id", "name"],
+                vec!["id".into(), "name".into()],
                 None,
                 Some(Arc::new(left_child)),
             );
             let right_child = Context::new("Src".to_owned(), Vec::new(), None, None);
             let right = Context::new(
                 "Bar".to_owned(),
-                vec!["id", "rate"],
+                vec!["id".into(), "rate".into()],
                 None,
                 Some(Arc::new(right_child)),
             );
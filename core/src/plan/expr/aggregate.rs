@@ -1,26 +1,79 @@
-use crate::ast::{Aggregate, AggregateFunction, CountArgExpr, Expr};
+use crate::ast::{
+    Aggregate, AggregateFunction, CountArgExpr, Expr, WindowFrame, WindowFrameBound, WindowSpec,
+};
 
 impl Aggregate {
     pub fn as_expr(&self) -> Option<&Expr> {
         match &self.func {
-            AggregateFunction::Count(CountArgExpr::Wildcard) => None,
+            AggregateFunction::Count(CountArgExpr::Wildcard)
+            | AggregateFunction::RowNumber
+            | AggregateFunction::Rank
+            | AggregateFunction::DenseRank => None,
             AggregateFunction::Count(CountArgExpr::Expr(expr))
             | AggregateFunction::Sum(expr)
             | AggregateFunction::Max(expr)
             | AggregateFunction::Min(expr)
             | AggregateFunction::Avg(expr)
             | AggregateFunction::Variance(expr)
-            | AggregateFunction::Stdev(expr) => Some(expr),
+            | AggregateFunction::Stdev(expr)
+            | AggregateFunction::Lag { expr, .. }
+            | AggregateFunction::Lead { expr, .. }
+            | AggregateFunction::FirstValue(expr)
+            | AggregateFunction::LastValue(expr) => Some(expr),
         }
     }
+
+    /// Returns every expression the aggregate refers to, including the ones in its window.
+    pub fn as_exprs(&self) -> impl Iterator<Item = &Expr> {
+        let (offset, default) = match &self.func {
+            AggregateFunction::Lag {
+                offset, default, ..
+            }
+            | AggregateFunction::Lead {
+                offset, default, ..
+            } => (offset.as_deref(), default.as_deref()),
+            _ => (None, None),
+        };
+
+        let window = self.over.iter().flat_map(|window| {
+            let WindowSpec {
+                partition_by,
+                order_by,
+                frame,
+            } = window.as_ref();
+
+            let bounds = frame.iter().flat_map(|WindowFrame { start, end, .. }| {
+                [start, end].into_iter().filter_map(|bound| match bound {
+                    WindowFrameBound::Preceding(expr) | WindowFrameBound::Following(expr) => {
+                        expr.as_ref()
+                    }
+                    WindowFrameBound::CurrentRow => None,
+                })
+            });
+
+            partition_by
+                .iter()
+                .chain(order_by.iter().map(|order_by| &order_by.expr))
+                .chain(bounds)
+        });
+
+        self.as_expr()
+            .into_iter()
+            .chain(offset)
+            .chain(default)
+            .chain(window)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{
-        ast::{Aggregate, Expr},
-        parse_sql::parse_expr,
-        translate::translate_expr,
+    use {
+        crate::{
+            ast::{Aggregate, AstLiteral, Expr},
+            parse_sql::parse_expr,
+            translate::translate_expr,
+        },
+        bigdecimal::BigDecimal,
     };
 
     fn parse(sql: &str) -> Aggregate {
@@ -60,5 +113,38 @@ mod tests {
         let actual = parse("VARIANCE(id)");
         let expected = Expr::Identifier("id".to_owned());
         assert_eq!(actual.as_expr(), Some(&expected));
+
+        assert_eq!(parse("ROW_NUMBER() OVER ()").as_expr(), None);
+
+        let actual = parse("LAG(id, 2) OVER (ORDER BY name)");
+        let expected = Expr::Identifier("id".to_owned());
+        assert_eq!(actual.as_expr(), Some(&expected));
+    }
+
+    #[test]
+    fn as_exprs() {
+        let ident = |name: &str| Expr::Identifier(name.to_owned());
+        let number = |n: i64| Expr::Literal(AstLiteral::Number(BigDecimal::from(n)));
+
+        let actual = parse("COUNT(*)");
+        assert_eq!(actual.as_exprs().collect::<Vec<_>>(), Vec::<&Expr>::new());
+
+        let actual = parse("SUM(id)");
+        assert_eq!(actual.as_exprs().collect::<Vec<_>>(), vec![&ident("id")]);
+
+        let actual = parse(
+            "LEAD(id, 1, 0) OVER (PARTITION BY team ORDER BY name ROWS BETWEEN 2 PRECEDING AND CURRENT ROW)",
+        );
+        assert_eq!(
+            actual.as_exprs().collect::<Vec<_>>(),
+            vec![
+                &ident("id"),
+                &number(1),
+                &number(0),
+                &ident("team"),
+                &ident("name"),
+                &number(2),
+            ]
+        );
     }
 }
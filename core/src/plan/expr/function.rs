@@ -52,6 +52,8 @@ impl Function {
             | Self::Md5(expr)
             | Self::Hex(expr)
             | Self::LastDay(expr)
+            | Self::NextVal(expr)
+            | Self::CurrVal(expr)
             | Self::Ltrim { expr, chars: None }
             | Self::Rtrim { expr, chars: None }
             | Self::Trim {
@@ -270,6 +272,8 @@ mod tests {
         test("ATAN(3.14)", &["3.14"]);
         test("RADIANS(180)", &["180"]);
         test("DEGREES(3.14)", &["3.14"]);
+        test("NEXTVAL('seq')", &["'seq'"]);
+        test("CURRVAL('seq')", &["'seq'"]);
         test("CEIL(1.23)", &["1.23"]);
         test("Rand(1.23)", &["1.23"]);
         test("ROUND(1.23)", &["1.23"]);
@@ -2,65 +2,211 @@ use {
     super::PlanError,
     crate::{
         ast::{
-            AstLiteral, BinaryOperator, Expr, Function, IndexItem, IndexOperator, OrderByExpr,
-            Query, Select, SetExpr, Statement, TableAlias, TableFactor, TableWithJoins,
+            AstLiteral, BinaryOperator, Cte, Expr, Function, IndexItem, IndexOperator, OrderByExpr,
+            Query, Select, SetExpr, Statement, TableAlias, TableFactor, TableWithJoins, With,
         },
         data::{Schema, SchemaIndex, SchemaIndexOrd},
         result::Result,
     },
-    std::collections::HashMap,
-    utils::Vector,
+    std::{borrow::Cow, collections::HashMap},
 };
 
 pub fn plan(schema_map: &HashMap<String, Schema>, statement: Statement) -> Result<Statement> {
     match statement {
         Statement::Query(query) => plan_query(schema_map, query).map(Statement::Query),
+        Statement::Explain { analyze, query } => {
+            plan_query(schema_map, *query).map(|query| Statement::Explain {
+                analyze,
+                query: Box::new(query),
+            })
+        }
         _ => Ok(statement),
     }
 }
 
 struct Indexes(Vec<SchemaIndex>);
 
+/// Multi-column index matched by the conditions of an `AND` chain
+struct CompositeIndex {
+    name: String,
+    /// Position of the condition, comparison and value for each covered index expression,
+    /// all of them `=` except the last one
+    parts: Vec<(usize, IndexOperator, Expr)>,
+}
+
 impl Indexes {
+    /// Finds an index whose leading expression is `target`, preferring the index of
+    /// `target` alone over multi-column indexes
     fn find(&self, target: &Expr) -> Option<String> {
         self.0
             .iter()
-            .find(|SchemaIndex { expr, .. }| expr == target)
+            .filter(|SchemaIndex { exprs, .. }| exprs.first() == Some(target))
+            .min_by_key(|SchemaIndex { exprs, .. }| exprs.len())
             .map(|SchemaIndex { name, .. }| name.to_owned())
     }
 
-    fn find_ordered(&self, target: &OrderByExpr) -> Option<String> {
+    /// Finds the index ordering the most trailing expressions of `order_by` from its leading
+    /// expression
+    fn find_ordered(&self, order_by: &[OrderByExpr]) -> Option<OrderedIndex> {
+        self.0
+            .iter()
+            .filter_map(|index| {
+                ordered_len(index, 0, order_by).map(|(len, asc)| OrderedIndex {
+                    name: index.name.to_owned(),
+                    asc,
+                    len,
+                })
+            })
+            .reduce(|best, ordered| match ordered.len > best.len {
+                true => ordered,
+                false => best,
+            })
+    }
+
+    /// Returns the number of the trailing `order_by` expressions which the index scan planned
+    /// from `WHERE` yields in order, with the direction to scan it in. `=` comparisons fix the
+    /// compared index expressions, so the rows are ordered by the expressions following them.
+    fn ordered_scan(
+        &self,
+        select: &Select,
+        order_by: &[OrderByExpr],
+    ) -> Option<(usize, Option<bool>)> {
+        let TableFactor::Table {
+            index:
+                Some(IndexItem::NonClustered {
+                    name,
+                    prefix,
+                    cmp_expr: Some((index_op, _)),
+                    ..
+                }),
+            ..
+        } = &select.from.relation
+        else {
+            return None;
+        };
+
+        let position = match index_op {
+            IndexOperator::Eq => prefix.len() + 1,
+            _ => prefix.len(),
+        };
+
+        self.0
+            .iter()
+            .find(|index| &index.name == name)
+            .and_then(|index| ordered_len(index, position, order_by))
+    }
+
+    /// Finds the multi-column index covering the most expressions with `conditions`, which
+    /// give `=` values to its leading expressions and optionally compare the next one.
+    /// Indexes covering less than two expressions are left to [`plan_index`].
+    fn find_composite(&self, conditions: &[&Expr]) -> Option<CompositeIndex> {
         self.0
             .iter()
-            .find(|SchemaIndex { expr, order, .. }| {
-                if expr != &target.expr {
-                    return false;
+            .filter(|SchemaIndex { exprs, .. }| exprs.len() > 1)
+            .map(|SchemaIndex { name, exprs, .. }| {
+                let mut parts: Vec<(usize, IndexOperator, Expr)> = Vec::new();
+                for target in exprs {
+                    let found = conditions.iter().enumerate().find_map(|(i, condition)| {
+                        let used = parts.iter().any(|(position, ..)| *position == i);
+
+                        (!used)
+                            .then(|| compare_index_expr(condition, target))
+                            .flatten()
+                            .map(|(index_op, value)| (i, index_op, value.clone()))
+                    });
+
+                    let Some(part) = found else {
+                        break;
+                    };
+                    let is_range = part.1 != IndexOperator::Eq;
+                    parts.push(part);
+
+                    if is_range {
+                        break;
+                    }
                 }
 
-                matches!(
-                    (target.asc, order),
-                    (_, SchemaIndexOrd::Both)
-                        | (Some(true), SchemaIndexOrd::Asc)
-                        | (None, SchemaIndexOrd::Asc)
-                        | (Some(false), SchemaIndexOrd::Desc)
-                )
+                CompositeIndex {
+                    name: name.to_owned(),
+                    parts,
+                }
             })
-            .map(|SchemaIndex { name, .. }| name.to_owned())
+            .filter(|CompositeIndex { parts, .. }| parts.len() > 1)
+            .reduce(|best, index| match index.parts.len() > best.parts.len() {
+                true => index,
+                false => best,
+            })
+    }
+}
+
+/// Returns the comparison operator and the value when `condition` compares `target` with a
+/// stateless value, reversing the operator when `target` is on the right side
+fn compare_index_expr<'b>(condition: &'b Expr, target: &Expr) -> Option<(IndexOperator, &'b Expr)> {
+    let Expr::BinaryOp { left, op, right } = condition else {
+        return None;
+    };
+
+    let index_op = match op {
+        BinaryOperator::Gt => IndexOperator::Gt,
+        BinaryOperator::Lt => IndexOperator::Lt,
+        BinaryOperator::GtEq => IndexOperator::GtEq,
+        BinaryOperator::LtEq => IndexOperator::LtEq,
+        BinaryOperator::Eq => IndexOperator::Eq,
+        _ => return None,
+    };
+
+    if left.as_ref() == target && is_stateless(right) {
+        Some((index_op, right))
+    } else if right.as_ref() == target && is_stateless(left) {
+        Some((index_op.reverse(), left))
+    } else {
+        None
     }
 }
 
 fn plan_query(schema_map: &HashMap<String, Schema>, query: Query) -> Result<Query> {
     let Query {
+        with,
         body,
         order_by,
         limit,
         offset,
     } = query;
 
+    let (with, schema_map) = match with {
+        Some(with) => {
+            plan_with(schema_map, with).map(|(with, schema_map)| (Some(with), schema_map))?
+        }
+        None => (None, Cow::Borrowed(schema_map)),
+    };
+    let schema_map = schema_map.as_ref();
+
     let select = match body {
         SetExpr::Select(select) => select,
         SetExpr::Values(_) => {
             return Ok(Query {
+                with,
+                body,
+                order_by,
+                limit,
+                offset,
+            });
+        }
+        SetExpr::SetOperation {
+            op,
+            all,
+            left,
+            right,
+        } => {
+            let body = SetExpr::SetOperation {
+                op,
+                all,
+                left: plan_query(schema_map, *left).map(Box::new)?,
+                right: plan_query(schema_map, *right).map(Box::new)?,
+            };
+
+            return Ok(Query {
+                with,
                 body,
                 order_by,
                 limit,
@@ -74,6 +220,7 @@ fn plan_query(schema_map: &HashMap<String, Schema>, query: Query) -> Result<Quer
         TableFactor::Table { name, .. } => name,
         TableFactor::Derived { .. } => {
             return Ok(Query {
+                with,
                 body: SetExpr::Select(select),
                 order_by,
                 limit,
@@ -94,6 +241,7 @@ fn plan_query(schema_map: &HashMap<String, Schema>, query: Query) -> Result<Quer
         Some(Schema { indexes, .. }) => Indexes(indexes.clone()),
         None => {
             return Ok(Query {
+                with,
                 body: SetExpr::Select(select),
                 order_by,
                 limit,
@@ -102,68 +250,161 @@ fn plan_query(schema_map: &HashMap<String, Schema>, query: Query) -> Result<Quer
         }
     };
 
-    let index = order_by.last().and_then(|value_expr| {
-        indexes
-            .find_ordered(value_expr)
-            .map(|name| IndexItem::NonClustered {
-                name,
-                asc: value_expr.asc,
-                cmp_expr: None,
-            })
-    });
+    let planned = plan_select(schema_map, &indexes, select.as_ref().clone())?;
+    let (select, order_by) = if let Some((len, asc)) = indexes.ordered_scan(&planned, &order_by) {
+        (order_scan(planned, asc), truncate(order_by, len))
+    } else if let Some(ordered) = indexes.find_ordered(&order_by) {
+        let OrderedIndex { name, asc, len } = ordered;
 
-    if index.is_some() {
-        let Select {
-            distinct,
-            projection,
-            from,
-            selection,
-            group_by,
-            having,
-        } = *select;
-
-        let TableWithJoins { relation, joins } = from;
-        let (name, alias) = match relation {
-            TableFactor::Table { name, alias, .. } => (name, alias),
-            TableFactor::Derived { .. }
-            | TableFactor::Series { .. }
-            | TableFactor::Dictionary { .. } => {
-                return Err(PlanError::Unreachable.into());
+        (scan_ordered(name, asc, *select)?, truncate(order_by, len))
+    } else {
+        (planned, order_by)
+    };
+
+    Ok(Query {
+        with,
+        body: SetExpr::Select(Box::new(select)),
+        order_by,
+        limit,
+        offset,
+    })
+}
+
+/// Index ordering the trailing `len` expressions of `ORDER BY`, scanned in the direction of
+/// `asc`
+struct OrderedIndex {
+    name: String,
+    asc: Option<bool>,
+    len: usize,
+}
+
+/// Returns the number of the trailing `order_by` expressions which a scan of `index` yields
+/// in order from its `position`-th expression, with the direction to scan it in.
+/// Sorting the rows by the other expressions keeps the order of the equal ones, so the
+/// trailing ones need no sort.
+fn ordered_len(
+    index: &SchemaIndex,
+    position: usize,
+    order_by: &[OrderByExpr],
+) -> Option<(usize, Option<bool>)> {
+    (1..=order_by.len()).rev().find_map(|len| {
+        let order_by = &order_by[order_by.len() - len..];
+        let exprs = index.exprs.get(position..position + len)?;
+        if !order_by.iter().map(|order| &order.expr).eq(exprs) {
+            return None;
+        }
+
+        let desc = order_by.iter().all(|order| order.asc == Some(false));
+        let asc = order_by.iter().all(|order| order.asc != Some(false));
+
+        match (desc, asc, &index.order) {
+            (true, _, SchemaIndexOrd::Desc | SchemaIndexOrd::Both) => Some((len, Some(false))),
+            (_, true, SchemaIndexOrd::Asc | SchemaIndexOrd::Both) => {
+                Some((len, order_by[len - 1].asc))
             }
-        };
+            _ => None,
+        }
+    })
+}
 
-        let from = TableWithJoins {
-            relation: TableFactor::Table { name, alias, index },
-            joins,
-        };
+fn truncate(mut order_by: Vec<OrderByExpr>, len: usize) -> Vec<OrderByExpr> {
+    order_by.truncate(order_by.len() - len);
 
-        let select = Select {
-            distinct,
-            projection,
-            from,
-            selection,
-            group_by,
-            having,
-        };
+    order_by
+}
 
-        Ok(Query {
-            body: SetExpr::Select(Box::new(select)),
-            order_by: Vector::from(order_by).pop().0.into(),
-            limit,
-            offset,
+/// Marks the index scan planned from `WHERE` as ordering the rows in the direction of `asc`
+fn order_scan(mut select: Select, asc: Option<bool>) -> Select {
+    if let TableFactor::Table {
+        index: Some(IndexItem::NonClustered { asc: index_asc, .. }),
+        ..
+    } = &mut select.from.relation
+    {
+        // `Some` tells the ordering scans apart from the other ones, even when ascending
+        *index_asc = Some(asc.unwrap_or(true));
+    }
+
+    select
+}
+
+/// Scans the whole table through the index `name` for `ORDER BY`, keeping `WHERE` as it was
+fn scan_ordered(name: String, asc: Option<bool>, select: Select) -> Result<Select> {
+    let Select {
+        distinct,
+        projection,
+        from,
+        selection,
+        group_by,
+        having,
+    } = select;
+
+    let TableWithJoins { relation, joins } = from;
+    let (table_name, alias) = match relation {
+        TableFactor::Table { name, alias, .. } => (name, alias),
+        TableFactor::Derived { .. }
+        | TableFactor::Series { .. }
+        | TableFactor::Dictionary { .. } => {
+            return Err(PlanError::Unreachable.into());
+        }
+    };
+
+    let index = Some(IndexItem::NonClustered {
+        name,
+        asc,
+        prefix: Vec::new(),
+        cmp_expr: None,
+    });
+    let from = TableWithJoins {
+        relation: TableFactor::Table {
+            name: table_name,
+            alias,
+            index,
+        },
+        joins,
+    };
+
+    Ok(Select {
+        distinct,
+        projection,
+        from,
+        selection,
+        group_by,
+        having,
+    })
+}
+
+/// CTE names shadow tables of the same name, so their schemas are hidden while planning
+/// the queries in which the CTEs are visible.
+fn plan_with<'a>(
+    schema_map: &'a HashMap<String, Schema>,
+    with: With,
+) -> Result<(With, Cow<'a, HashMap<String, Schema>>)> {
+    let With {
+        recursive,
+        cte_tables,
+    } = with;
+
+    let mut schema_map = Cow::Borrowed(schema_map);
+    let cte_tables = cte_tables
+        .into_iter()
+        .map(|Cte { alias, query }| {
+            if recursive {
+                schema_map.to_mut().remove(&alias.name);
+            }
+
+            let query = plan_query(&schema_map, query)?;
+            schema_map.to_mut().remove(&alias.name);
+
+            Ok(Cte { alias, query })
         })
-    } else {
-        let select = plan_select(schema_map, &indexes, *select)?;
-        let body = SetExpr::Select(Box::new(select));
-        let query = Query {
-            body,
-            order_by,
-            limit,
-            offset,
-        };
+        .collect::<Result<_>>()?;
 
-        Ok(query)
-    }
+    let with = With {
+        recursive,
+        cte_tables,
+    };
+
+    Ok((with, schema_map))
 }
 
 fn plan_select(
@@ -194,7 +435,7 @@ fn plan_select(
         }
     };
 
-    match plan_index(schema_map, indexes, selection)? {
+    match plan_composite_index(schema_map, indexes, selection)? {
         Planned::Expr(selection) => Ok(Select {
             distinct,
             projection,
@@ -205,6 +446,7 @@ fn plan_select(
         }),
         Planned::IndexedExpr {
             index_name,
+            index_prefix,
             index_op,
             index_value_expr,
             selection,
@@ -222,6 +464,7 @@ fn plan_select(
             let index = Some(IndexItem::NonClustered {
                 name: index_name,
                 asc: None,
+                prefix: index_prefix,
                 cmp_expr: Some((index_op, index_value_expr)),
             });
             let from = TableWithJoins {
@@ -244,6 +487,7 @@ fn plan_select(
 enum Planned {
     IndexedExpr {
         index_name: String,
+        index_prefix: Vec<Expr>,
         index_op: IndexOperator,
         index_value_expr: Expr,
         selection: Option<Expr>,
@@ -251,6 +495,82 @@ enum Planned {
     Expr(Expr),
 }
 
+/// Plans the multi-column index covering the most expressions with the conditions of the
+/// `AND` chain of `selection`, falling back to [`plan_index`] when no such index is found.
+fn plan_composite_index(
+    schema_map: &HashMap<String, Schema>,
+    indexes: &Indexes,
+    selection: Expr,
+) -> Result<Planned> {
+    fn split<'b>(expr: &'b Expr, conditions: &mut Vec<&'b Expr>) {
+        match expr {
+            Expr::BinaryOp {
+                left,
+                op: BinaryOperator::And,
+                right,
+            } => {
+                split(left, conditions);
+                split(right, conditions);
+            }
+            expr => conditions.push(expr),
+        }
+    }
+
+    fn split_owned(expr: Expr, conditions: &mut Vec<Expr>) {
+        match expr {
+            Expr::BinaryOp {
+                left,
+                op: BinaryOperator::And,
+                right,
+            } => {
+                split_owned(*left, conditions);
+                split_owned(*right, conditions);
+            }
+            expr => conditions.push(expr),
+        }
+    }
+
+    let mut conditions = Vec::new();
+    split(&selection, &mut conditions);
+
+    let Some(CompositeIndex { name, mut parts }) = indexes.find_composite(&conditions) else {
+        return plan_index(schema_map, indexes, selection);
+    };
+
+    let mut conditions = Vec::new();
+    split_owned(selection, &mut conditions);
+
+    let selection = conditions
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| parts.iter().all(|(position, ..)| position != i))
+        .map(|(_, condition)| condition)
+        .reduce(|left, right| Expr::BinaryOp {
+            left: Box::new(left),
+            op: BinaryOperator::And,
+            right: Box::new(right),
+        });
+    // subqueries in the remaining conditions are planned with their own indexes
+    let selection = match selection {
+        Some(selection) => match plan_index(schema_map, &Indexes(Vec::new()), selection)? {
+            Planned::Expr(selection) => Some(selection),
+            Planned::IndexedExpr { .. } => return Err(PlanError::Unreachable.into()),
+        },
+        None => None,
+    };
+
+    let (_, index_op, index_value_expr) = parts.pop().ok_or(PlanError::Unreachable)?;
+    let index_prefix = parts.into_iter().map(|(_, _, value)| value).collect();
+
+    Ok(Planned::IndexedExpr {
+        index_name: name,
+        index_prefix,
+        index_op,
+        index_value_expr,
+        selection,
+    })
+}
+
 fn plan_index(
     schema_map: &HashMap<String, Schema>,
     indexes: &Indexes,
@@ -289,6 +609,7 @@ fn plan_index(
                 Planned::Expr(selection) => selection,
                 Planned::IndexedExpr {
                     index_name,
+                    index_prefix,
                     index_value_expr,
                     index_op,
                     selection,
@@ -304,6 +625,7 @@ fn plan_index(
 
                     return Ok(Planned::IndexedExpr {
                         index_name,
+                        index_prefix,
                         index_op,
                         index_value_expr,
                         selection: Some(selection),
@@ -319,6 +641,7 @@ fn plan_index(
                 })),
                 Planned::IndexedExpr {
                     index_name,
+                    index_prefix,
                     index_op,
                     index_value_expr,
                     selection,
@@ -334,6 +657,7 @@ fn plan_index(
 
                     Ok(Planned::IndexedExpr {
                         index_name,
+                        index_prefix,
                         index_value_expr,
                         index_op,
                         selection: Some(selection),
@@ -381,6 +705,7 @@ fn search_is_null(indexes: &Indexes, null: bool, expr: Box<Expr>) -> Planned {
 
             Planned::IndexedExpr {
                 index_name,
+                index_prefix: Vec::new(),
                 index_op,
                 index_value_expr: Expr::Literal(AstLiteral::Null),
                 selection: None,
@@ -410,6 +735,7 @@ fn search_index_op(
     {
         Planned::IndexedExpr {
             index_name,
+            index_prefix: Vec::new(),
             index_op,
             index_value_expr: *right,
             selection: None,
@@ -420,6 +746,7 @@ fn search_index_op(
     {
         Planned::IndexedExpr {
             index_name,
+            index_prefix: Vec::new(),
             index_op: index_op.reverse(),
             index_value_expr: *left,
             selection: None,
@@ -441,7 +768,7 @@ fn is_stateless(expr: &Expr) -> bool {
     match expr {
         Expr::Literal(AstLiteral::Null) => false,
         Expr::Literal(_) => true,
-        Expr::TypedString { .. } => true,
+        Expr::TypedString { .. } | Expr::Parameter(_) => true,
         Expr::IsNull(expr)
         | Expr::IsNotNull(expr)
         | Expr::UnaryOp { expr, .. }
@@ -20,6 +20,10 @@ pub fn plan(schema_map: &HashMap<String, Schema>, statement: Statement) -> State
 
             Statement::Query(query)
         }
+        Statement::Explain { analyze, query } => Statement::Explain {
+            analyze,
+            query: Box::new(planner.query(None, *query)),
+        },
         _ => statement,
     }
 }
@@ -31,12 +35,14 @@ struct JoinPlanner<'a> {
 impl<'a> Planner<'a> for JoinPlanner<'a> {
     fn query(&self, outer_context: Option<Arc<Context<'a>>>, query: Query) -> Query {
         let Query {
+            with,
             body,
             order_by,
             limit,
             offset,
         } = query;
 
+        let (outer_context, with) = self.with(outer_context, with);
         let body = match body {
             SetExpr::Select(select) => {
                 let select = self.select(outer_context, *select);
@@ -44,9 +50,21 @@ impl<'a> Planner<'a> for JoinPlanner<'a> {
                 SetExpr::Select(Box::new(select))
             }
             SetExpr::Values(_) => body,
+            SetExpr::SetOperation {
+                op,
+                all,
+                left,
+                right,
+            } => SetExpr::SetOperation {
+                op,
+                all,
+                left: Box::new(self.query(outer_context.as_ref().map(Arc::clone), *left)),
+                right: Box::new(self.query(outer_context, *right)),
+            },
         };
 
         Query {
+            with,
             body,
             order_by,
             limit,
@@ -89,7 +107,7 @@ impl<'a> JoinPlanner<'a> {
         table_with_joins: TableWithJoins,
     ) -> (Option<Arc<Context<'a>>>, TableWithJoins) {
         let TableWithJoins { relation, joins } = table_with_joins;
-        let init_context = self.update_context(None, &relation);
+        let init_context = self.update_context(outer_context.as_ref(), None, &relation);
         let (context, joins) =
             joins
                 .into_iter()
@@ -119,7 +137,7 @@ impl<'a> JoinPlanner<'a> {
         } = join;
 
         if matches!(join_executor, JoinExecutor::Hash { .. }) {
-            let context = self.update_context(inner_context, &relation);
+            let context = self.update_context(outer_context.as_ref(), inner_context, &relation);
             let join = Join {
                 relation,
                 join_operator,
@@ -132,14 +150,21 @@ impl<'a> JoinPlanner<'a> {
         enum JoinOp {
             Inner,
             LeftOuter,
+            RightOuter,
+            FullOuter,
         }
 
         let (join_op, expr) = match join_operator {
             JoinOperator::Inner(JoinConstraint::On(expr)) => (JoinOp::Inner, expr),
             JoinOperator::LeftOuter(JoinConstraint::On(expr)) => (JoinOp::LeftOuter, expr),
-            JoinOperator::Inner(JoinConstraint::None)
-            | JoinOperator::LeftOuter(JoinConstraint::None) => {
-                let context = self.update_context(inner_context, &relation);
+            JoinOperator::RightOuter(JoinConstraint::On(expr)) => (JoinOp::RightOuter, expr),
+            JoinOperator::FullOuter(JoinConstraint::On(expr)) => (JoinOp::FullOuter, expr),
+            JoinOperator::Inner(_)
+            | JoinOperator::LeftOuter(_)
+            | JoinOperator::RightOuter(_)
+            | JoinOperator::FullOuter(_)
+            | JoinOperator::Cross => {
+                let context = self.update_context(outer_context.as_ref(), inner_context, &relation);
                 let join = Join {
                     relation,
                     join_operator,
@@ -150,22 +175,26 @@ impl<'a> JoinPlanner<'a> {
             }
         };
 
-        let current_context = self.update_context(None, &relation);
+        let current_context = self.update_context(outer_context.as_ref(), None, &relation);
         let (join_executor, expr) = self.join_expr(
-            outer_context,
+            outer_context.as_ref().map(Arc::clone),
             inner_context.as_ref().map(Arc::clone),
             current_context,
             expr,
         );
 
-        let join_operator = match (join_op, expr) {
-            (JoinOp::Inner, Some(expr)) => JoinOperator::Inner(JoinConstraint::On(expr)),
-            (JoinOp::Inner, None) => JoinOperator::Inner(JoinConstraint::None),
-            (JoinOp::LeftOuter, Some(expr)) => JoinOperator::LeftOuter(JoinConstraint::On(expr)),
-            (JoinOp::LeftOuter, None) => JoinOperator::LeftOuter(JoinConstraint::None),
+        let join_constraint = match expr {
+            Some(expr) => JoinConstraint::On(expr),
+            None => JoinConstraint::None,
+        };
+        let join_operator = match join_op {
+            JoinOp::Inner => JoinOperator::Inner(join_constraint),
+            JoinOp::LeftOuter => JoinOperator::LeftOuter(join_constraint),
+            JoinOp::RightOuter => JoinOperator::RightOuter(join_constraint),
+            JoinOp::FullOuter => JoinOperator::FullOuter(join_constraint),
         };
 
-        let context = self.update_context(inner_context, &relation);
+        let context = self.update_context(outer_context.as_ref(), inner_context, &relation);
         let join = Join {
             relation,
             join_operator,
@@ -425,7 +454,7 @@ mod tests {
     use {
         super::plan,
         crate::{
-            ast::{DateTimeField, Statement},
+            ast::{DateTimeField, Query, Statement},
             ast_builder::{Build, QueryNode, col, exists, num, subquery, table},
             mock::{MockStorage, run},
             parse_sql::parse,
@@ -949,4 +978,66 @@ mod tests {
         );
         test!(actual, expected, "case expr:\n{sql}");
     }
+
+    #[test]
+    fn cte() {
+        let storage = run("
+            CREATE TABLE Player (
+                id INTEGER,
+                name TEXT
+            );
+            CREATE TABLE PlayerItem (
+                user_id INTEGER,
+                amount INTEGER
+            );
+        ");
+
+        let plan_body = |sql: &str| match plan_join(&storage, sql) {
+            Statement::Query(query) => {
+                assert!(query.with.is_some(), "{sql}");
+
+                Statement::Query(Query {
+                    with: None,
+                    ..query
+                })
+            }
+            _ => unreachable!(),
+        };
+
+        let sql = "
+            WITH Item AS (SELECT user_id, amount FROM PlayerItem)
+            SELECT * FROM Player
+            JOIN Item ON Item.user_id = Player.id
+        ";
+        let actual = plan_body(sql);
+        let expected = table("Player")
+            .select()
+            .join("Item")
+            .hash_executor("Item.user_id", "Player.id");
+        test!(actual, expected, "CTE columns resolve in context:\n{sql}");
+
+        let sql = "
+            WITH Item (uid, amt) AS (SELECT * FROM PlayerItem)
+            SELECT * FROM Player
+            JOIN Item ON Item.uid = Player.id
+        ";
+        let actual = plan_body(sql);
+        let expected = table("Player")
+            .select()
+            .join("Item")
+            .hash_executor("Item.uid", "Player.id");
+        test!(actual, expected, "CTE column aliases over wildcard:\n{sql}");
+
+        let sql = "
+            WITH PlayerItem AS (SELECT id AS owner FROM Player)
+            SELECT * FROM Player
+            JOIN PlayerItem ON PlayerItem.user_id = Player.id
+        ";
+        let actual = plan_body(sql);
+        let expected = table("Player")
+            .select()
+            .join("PlayerItem")
+            .on("PlayerItem.user_id = Player.id");
+        test!(actual, expected, "CTE shadows the table columns:\n{sql}");
+    }
 }
@@ -1,10 +1,13 @@
 use {
     super::context::Context,
     crate::{
-        ast::{ColumnDef, ColumnUniqueOption, Expr, Function, Query, TableAlias, TableFactor},
+        ast::{
+            ColumnDef, ColumnUniqueOption, Cte, Expr, Function, Query, SelectItem, SetExpr,
+            TableAlias, TableFactor, Values, With,
+        },
         data::Schema,
     },
-    std::sync::Arc,
+    std::{borrow::Cow, sync::Arc},
 };
 
 pub trait Planner<'a> {
@@ -12,12 +15,145 @@ pub trait Planner<'a> {
 
     fn query(&self, outer_context: Option<Arc<Context<'a>>>, query: Query) -> Query;
 
+    /// Plans CTE queries and returns the outer context extended with the defined CTE names.
+    /// Each CTE is visible to the later ones, and also to itself when `WITH RECURSIVE` is used.
+    fn with(
+        &self,
+        outer_context: Option<Arc<Context<'a>>>,
+        with: Option<With>,
+    ) -> (Option<Arc<Context<'a>>>, Option<With>) {
+        let With {
+            recursive,
+            cte_tables,
+        } = match with {
+            Some(with) => with,
+            None => return (outer_context, None),
+        };
+
+        let mut context = outer_context;
+        let cte_tables = cte_tables
+            .into_iter()
+            .map(|Cte { alias, query }| {
+                let columns = self.cte_columns(context.as_ref(), &alias, &query);
+                let cte_context =
+                    |next| Some(Arc::new(Context::cte(alias.name.clone(), columns, next)));
+
+                let query = if recursive {
+                    context = cte_context(context.take());
+                    self.query(context.as_ref().map(Arc::clone), query)
+                } else {
+                    let query = self.query(context.as_ref().map(Arc::clone), query);
+                    context = cte_context(context.take());
+                    query
+                };
+
+                Cte { alias, query }
+            })
+            .collect();
+
+        let with = With {
+            recursive,
+            cte_tables,
+        };
+
+        (context, Some(with))
+    }
+
+    /// Resolves the output columns of a CTE from its leftmost SELECT or VALUES.
+    fn cte_columns(
+        &self,
+        outer_context: Option<&Arc<Context<'a>>>,
+        alias: &TableAlias,
+        query: &Query,
+    ) -> Option<Vec<String>> {
+        let mut body = &query.body;
+        let labels = loop {
+            match body {
+                SetExpr::Select(select) => {
+                    let table_factors = std::iter::once(&select.from.relation)
+                        .chain(select.from.joins.iter().map(|join| &join.relation));
+
+                    let mut labels = Vec::new();
+                    for item in &select.projection {
+                        match item {
+                            SelectItem::Expr { label, .. } => labels.push(label.to_owned()),
+                            SelectItem::Wildcard => {
+                                for table_factor in table_factors.clone() {
+                                    labels.extend(self.table_columns(outer_context, table_factor)?);
+                                }
+                            }
+                            SelectItem::QualifiedWildcard(target) => {
+                                let table_factor = table_factors.clone().find(|table_factor| {
+                                    matches!(
+                                        table_factor,
+                                        TableFactor::Table { name, alias: None, .. }
+                                        | TableFactor::Table { alias: Some(TableAlias { name, .. }), .. }
+                                            if name == target
+                                    )
+                                })?;
+
+                                labels.extend(self.table_columns(outer_context, table_factor)?);
+                            }
+                        }
+                    }
+
+                    break labels;
+                }
+                SetExpr::Values(Values(rows)) => {
+                    break (1..=rows.first()?.len())
+                        .map(|i| format!("column{i}"))
+                        .collect();
+                }
+                SetExpr::SetOperation { left, .. } => body = &left.body,
+            }
+        };
+
+        if alias.columns.len() > labels.len() {
+            return None;
+        }
+
+        let columns = alias
+            .columns
+            .iter()
+            .chain(labels.iter().skip(alias.columns.len()))
+            .cloned()
+            .collect();
+
+        Some(columns)
+    }
+
+    fn table_columns(
+        &self,
+        outer_context: Option<&Arc<Context<'a>>>,
+        table_factor: &TableFactor,
+    ) -> Option<Vec<String>> {
+        let name = match table_factor {
+            TableFactor::Table { name, .. } => name,
+            TableFactor::Derived { .. }
+            | TableFactor::Series { .. }
+            | TableFactor::Dictionary { .. } => return None,
+        };
+
+        if let Some(columns) = outer_context.and_then(|context| context.get_cte(name)) {
+            return columns.map(<[String]>::to_vec);
+        }
+
+        let column_defs = self.get_schema(name)?.column_defs.as_ref()?;
+        let columns = column_defs
+            .iter()
+            .map(|ColumnDef { name, .. }| name.to_owned())
+            .collect();
+
+        Some(columns)
+    }
+
     fn subquery_expr(&self, outer_context: Option<Arc<Context<'a>>>, expr: Expr) -> Expr {
         match expr {
             Expr::Identifier(_)
             | Expr::CompoundIdentifier { .. }
             | Expr::Literal(_)
-            | Expr::TypedString { .. } => expr,
+            | Expr::TypedString { .. }
+            | Expr::Parameter(_) => expr,
             Expr::IsNull(expr) => Expr::IsNull(Box::new(self.subquery_expr(outer_context, *expr))),
             Expr::IsNotNull(expr) => {
                 Expr::IsNotNull(Box::new(self.subquery_expr(outer_context, *expr)))
@@ -187,6 +323,7 @@ pub trait Planner<'a> {
 
     fn update_context(
         &self,
+        outer_context: Option<&Arc<Context<'a>>>,
         next: Option<Arc<Context<'a>>>,
         table_factor: &TableFactor,
     ) -> Option<Arc<Context<'a>>> {
@@ -194,6 +331,21 @@ pub trait Planner<'a> {
             TableFactor::Table { name, alias, .. } => {
                 let alias = alias.as_ref().map(|TableAlias { name, .. }| name.clone());
 
+                if let Some(columns) = outer_context.and_then(|context| context.get_cte(name)) {
+                    let columns = match columns {
+                        Some(columns) => columns.iter().cloned().map(Cow::Owned).collect(),
+                        None => return next,
+                    };
+                    let context = Context::new(
+                        alias.unwrap_or_else(|| name.to_owned()),
+                        columns,
+                        None,
+                        next,
+                    );
+
+                    return Some(Arc::new(context));
+                }
+
                 (name, alias)
             }
             TableFactor::Derived { .. }
@@ -213,7 +365,7 @@ pub trait Planner<'a> {
 
         let columns = column_defs
             .iter()
-            .map(|ColumnDef { name, .. }| name.as_str())
+            .map(|ColumnDef { name, .. }| Cow::Borrowed(name.as_str()))
             .collect::<Vec<_>>();
 
         let primary_key = column_defs
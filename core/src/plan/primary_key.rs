@@ -19,6 +19,10 @@ pub fn plan(schema_map: &HashMap<String, Schema>, statement: Statement) -> State
 
             Statement::Query(query)
         }
+        Statement::Explain { analyze, query } => Statement::Explain {
+            analyze,
+            query: Box::new(planner.query(None, *query)),
+        },
         _ => statement,
     }
 }
@@ -29,6 +33,7 @@ struct PrimaryKeyPlanner<'a> {
 
 impl<'a> Planner<'a> for PrimaryKeyPlanner<'a> {
     fn query(&self, outer_context: Option<Arc<Context<'a>>>, query: Query) -> Query {
+        let (outer_context, with) = self.with(outer_context, query.with);
         let body = match query.body {
             SetExpr::Select(select) => {
                 let select = self.select(outer_context, *select);
@@ -36,9 +41,24 @@ impl<'a> Planner<'a> for PrimaryKeyPlanner<'a> {
                 SetExpr::Select(Box::new(select))
             }
             SetExpr::Values(_) => query.body,
+            SetExpr::SetOperation {
+                op,
+                all,
+                left,
+                right,
+            } => SetExpr::SetOperation {
+                op,
+                all,
+                left: Box::new(self.query(outer_context.as_ref().map(Arc::clone), *left)),
+                right: Box::new(self.query(outer_context, *right)),
+            },
         };
 
-        Query { body, ..query }
+        Query {
+            with,
+            body,
+            ..query
+        }
     }
 
     fn get_schema(&self, name: &str) -> Option<&'a Schema> {
@@ -56,23 +76,29 @@ enum PrimaryKey {
 
 impl<'a> PrimaryKeyPlanner<'a> {
     fn select(&self, outer_context: Option<Arc<Context<'a>>>, select: Select) -> Select {
-        let current_context = self.update_context(None, &select.from.relation);
+        let current_context =
+            self.update_context(outer_context.as_ref(), None, &select.from.relation);
         let current_context = select
             .from
             .joins
             .iter()
             .fold(current_context, |context, join| {
-                self.update_context(context, &join.relation)
+                self.update_context(outer_context.as_ref(), context, &join.relation)
             });
 
-        let (index, selection) = select
-            .selection
-            .map(|expr| self.expr(outer_context, current_context, expr))
-            .map(|primary_key| match primary_key {
-                PrimaryKey::Found { index_item, expr } => (Some(index_item), expr),
-                PrimaryKey::NotFound(expr) => (None, Some(expr)),
-            })
-            .unwrap_or((None, None));
+        let composite_key = self.composite_key(outer_context.as_ref(), &select.from.relation);
+        let (index, selection) = match (select.selection, composite_key) {
+            (Some(expr), Some((alias, key_columns))) => {
+                self.composite_expr(outer_context, current_context, alias, &key_columns, expr)
+            }
+            (selection, _) => selection
+                .map(|expr| self.expr(outer_context, current_context, expr))
+                .map(|primary_key| match primary_key {
+                    PrimaryKey::Found { index_item, expr } => (Some(index_item), expr),
+                    PrimaryKey::NotFound(expr) => (None, Some(expr)),
+                })
+                .unwrap_or((None, None)),
+        };
 
         if let TableFactor::Table {
             name,
@@ -98,6 +124,132 @@ impl<'a> PrimaryKeyPlanner<'a> {
         }
     }
 
+    /// Returns the alias and the composite primary key columns of the table to plan, single
+    /// column primary keys are planned by [`PrimaryKeyPlanner::expr`].
+    fn composite_key<'b>(
+        &self,
+        outer_context: Option<&Arc<Context<'a>>>,
+        relation: &'b TableFactor,
+    ) -> Option<(&'b str, Vec<&'a str>)> {
+        let TableFactor::Table {
+            name,
+            alias,
+            index: None,
+        } = relation
+        else {
+            return None;
+        };
+
+        if outer_context.is_some_and(|context| context.get_cte(name).is_some()) {
+            return None;
+        }
+
+        let key_constraint = self
+            .get_schema(name)?
+            .key_constraints
+            .iter()
+            .find(|key_constraint| key_constraint.is_primary)?;
+        let alias = alias
+            .as_ref()
+            .map_or(name.as_str(), |alias| alias.name.as_str());
+        let key_columns = key_constraint.columns.iter().map(String::as_str).collect();
+
+        Some((alias, key_columns))
+    }
+
+    /// Takes the `<key column> = <value>` conditions of the `AND` chain for the longest
+    /// leading run of `key_columns`, which is the full key or a key prefix.
+    fn composite_expr(
+        &self,
+        outer_context: Option<Arc<Context<'a>>>,
+        current_context: Option<Arc<Context<'a>>>,
+        alias: &str,
+        key_columns: &[&str],
+        expr: Expr,
+    ) -> (Option<IndexItem>, Option<Expr>) {
+        fn split(expr: Expr, conditions: &mut Vec<Expr>) {
+            match expr {
+                Expr::BinaryOp {
+                    left,
+                    op: BinaryOperator::And,
+                    right,
+                } => {
+                    split(*left, conditions);
+                    split(*right, conditions);
+                }
+                expr => conditions.push(expr),
+            }
+        }
+
+        let key_position = |key: &Expr| {
+            let column = match key {
+                Expr::Identifier(ident) => ident,
+                Expr::CompoundIdentifier {
+                    alias: key_alias,
+                    ident,
+                } if key_alias == alias => ident,
+                _ => return None,
+            };
+
+            key_columns
+                .iter()
+                .position(|key_column| key_column == column)
+                .filter(|_| check_evaluable(current_context.as_ref().map(Arc::clone), key))
+        };
+        let key_value = |condition: &Expr| match condition {
+            Expr::BinaryOp {
+                left,
+                op: BinaryOperator::Eq,
+                right,
+            } => [(left, right), (right, left)]
+                .into_iter()
+                .find_map(|(key, value)| {
+                    key_position(key)
+                        .filter(|_| check_evaluable(None, value))
+                        .map(|position| (position, value.as_ref().clone()))
+                }),
+            _ => None,
+        };
+
+        let mut conditions = Vec::new();
+        split(expr, &mut conditions);
+
+        // `positions[i]` is the key column whose value is given by the i-th condition
+        let mut values = vec![None; key_columns.len()];
+        let mut positions = vec![None; conditions.len()];
+        for (i, condition) in conditions.iter().enumerate() {
+            if let Some((position, value)) = key_value(condition) {
+                if values[position].is_none() {
+                    values[position] = Some(value);
+                    positions[i] = Some(position);
+                }
+            }
+        }
+
+        let prefix = values
+            .into_iter()
+            .map_while(|value| value)
+            .collect::<Vec<_>>();
+        let prefix_len = prefix.len();
+        let outer_context = Context::concat(current_context, outer_context);
+        let selection = conditions
+            .into_iter()
+            .zip(positions)
+            .filter_map(|(condition, position)| match position {
+                Some(position) if position < prefix_len => None,
+                _ => Some(condition),
+            })
+            .map(|condition| self.subquery_expr(outer_context.as_ref().map(Arc::clone), condition))
+            .reduce(|left, right| Expr::BinaryOp {
+                left: Box::new(left),
+                op: BinaryOperator::And,
+                right: Box::new(right),
+            });
+        let index_item = (!prefix.is_empty()).then_some(IndexItem::CompositePrimaryKey(prefix));
+
+        (index_item, selection)
+    }
+
     fn expr(
         &self,
         outer_context: Option<Arc<Context<'a>>>,
@@ -241,6 +393,7 @@ mod tests {
 
     fn select(select: Select) -> Statement {
         Statement::Query(Query {
+            with: None,
             body: SetExpr::Select(Box::new(select)),
             limit: None,
             offset: None,
@@ -455,6 +608,7 @@ mod tests {
         let actual = plan(&storage, sql);
         let expected = {
             let subquery = Query {
+                with: None,
                 body: SetExpr::Select(Box::new(Select {
                     distinct: false,
                     projection: vec![SelectItem::Wildcard],
@@ -511,6 +665,7 @@ mod tests {
         let actual = plan(&storage, sql);
         let expected = {
             let subquery = Query {
+                with: None,
                 body: SetExpr::Select(Box::new(Select {
                     distinct: false,
                     projection: vec![SelectItem::Expr {
@@ -564,6 +719,7 @@ mod tests {
         let actual = plan(&storage, sql);
         let expected = {
             let subquery = Query {
+                with: None,
                 body: SetExpr::Select(Box::new(Select {
                     distinct: false,
                     projection: vec![SelectItem::Expr {
@@ -618,12 +774,14 @@ mod tests {
                 op: BinaryOperator::Eq,
                 right: Box::new(Expr::Literal(AstLiteral::Number(1.into()))),
             }),
+            returning: Vec::new(),
         };
         assert_eq!(actual, expected, "delete statement:\n{sql}");
 
         let sql = "VALUES (1), (2);";
         let actual = plan(&storage, sql);
         let expected = Statement::Query(Query {
+            with: None,
             body: SetExpr::Values(Values(vec![
                 vec![Expr::Literal(AstLiteral::Number(1.into()))],
                 vec![Expr::Literal(AstLiteral::Number(2.into()))],
@@ -653,4 +811,60 @@ mod tests {
         });
         assert_eq!(actual, expected, "nested:\n{sql}");
     }
+
+    #[test]
+    fn composite_key() {
+        let storage = run("
+            CREATE TABLE Member (
+                tenant_id INTEGER,
+                id INTEGER,
+                name TEXT,
+                PRIMARY KEY (tenant_id, id)
+            );
+        ");
+
+        let member = |index, selection| {
+            select(Select {
+                distinct: false,
+                projection: vec![SelectItem::Wildcard],
+                from: TableWithJoins {
+                    relation: TableFactor::Table {
+                        name: "Member".to_owned(),
+                        alias: None,
+                        index,
+                    },
+                    joins: Vec::new(),
+                },
+                selection,
+                group_by: Vec::new(),
+                having: None,
+            })
+        };
+
+        let sql = "SELECT * FROM Member WHERE id = 2 AND name = 'a' AND 1 = tenant_id;";
+        let actual = plan(&storage, sql);
+        let expected = member(
+            Some(IndexItem::CompositePrimaryKey(vec![expr("1"), expr("2")])),
+            Some(expr("name = 'a'")),
+        );
+        assert_eq!(actual, expected, "full composite key:\n{sql}");
+
+        let sql = "SELECT * FROM Member WHERE tenant_id = 1 AND name = 'a';";
+        let actual = plan(&storage, sql);
+        let expected = member(
+            Some(IndexItem::CompositePrimaryKey(vec![expr("1")])),
+            Some(expr("name = 'a'")),
+        );
+        assert_eq!(actual, expected, "leading key column:\n{sql}");
+
+        let sql = "SELECT * FROM Member WHERE id = 2;";
+        let actual = plan(&storage, sql);
+        let expected = member(None, Some(expr("id = 2")));
+        assert_eq!(actual, expected, "non-leading key column:\n{sql}");
+
+        let sql = "SELECT * FROM Member WHERE tenant_id = 1 OR id = 2;";
+        let actual = plan(&storage, sql);
+        let expected = member(None, Some(expr("tenant_id = 1 OR id = 2")));
+        assert_eq!(actual, expected, "OR binary op:\n{sql}");
+    }
 }
@@ -2,8 +2,8 @@ use {
     super::expr::PlanExpr,
     crate::{
         ast::{
-            Expr, Join, JoinConstraint, JoinOperator, Query, Select, SelectItem, SetExpr,
-            Statement, TableFactor, TableWithJoins,
+            Cte, Expr, Join, JoinConstraint, JoinOperator, Query, Select, SelectItem, SetExpr,
+            Statement, TableFactor, TableWithJoins, With,
         },
         data::Schema,
         result::Result,
@@ -20,6 +20,7 @@ pub async fn fetch_schema_map<T: Store>(
 ) -> Result<HashMap<String, Schema>> {
     match statement {
         Statement::Query(query) => scan_query(storage, query).await,
+        Statement::Explain { query, .. } => scan_query(storage, query).await,
         Statement::Insert {
             table_name, source, ..
         } => {
@@ -63,8 +64,13 @@ pub async fn fetch_schema_map<T: Store>(
     }
 }
 
-async fn scan_query<T: Store>(storage: &T, query: &Query) -> Result<HashMap<String, Schema>> {
+#[async_recursion]
+async fn scan_query<T>(storage: &T, query: &Query) -> Result<HashMap<String, Schema>>
+where
+    T: Store,
+{
     let Query {
+        with,
         body,
         limit,
         offset,
@@ -74,8 +80,26 @@ async fn scan_query<T: Store>(storage: &T, query: &Query) -> Result<HashMap<Stri
     let schema_list = match body {
         SetExpr::Select(select) => scan_select(storage, select).await?,
         SetExpr::Values(_) => HashMap::new(),
+        SetExpr::SetOperation { left, right, .. } => scan_query(storage, left)
+            .await?
+            .into_iter()
+            .chain(scan_query(storage, right).await?)
+            .collect(),
     };
 
+    let cte_queries = with
+        .iter()
+        .flat_map(|With { cte_tables, .. }| cte_tables)
+        .map(|Cte { query, .. }| query);
+    let schema_list: HashMap<String, Schema> = stream::iter(cte_queries)
+        .then(|query| scan_query(storage, query))
+        .try_collect::<Vec<HashMap<String, Schema>>>()
+        .await?
+        .into_iter()
+        .flatten()
+        .chain(schema_list)
+        .collect();
+
     let schema_list = match (limit, offset) {
         (Some(limit), Some(offset)) => schema_list
             .into_iter()
@@ -156,13 +180,18 @@ async fn scan_join<T: Store>(storage: &T, join: &Join) -> Result<HashMap<String,
     let schema_list = scan_table_factor(storage, relation).await?;
     let schema_list = match join_operator {
         JoinOperator::Inner(JoinConstraint::On(expr))
-        | JoinOperator::LeftOuter(JoinConstraint::On(expr)) => scan_expr(storage, expr)
+        | JoinOperator::LeftOuter(JoinConstraint::On(expr))
+        | JoinOperator::RightOuter(JoinConstraint::On(expr))
+        | JoinOperator::FullOuter(JoinConstraint::On(expr)) => scan_expr(storage, expr)
             .await?
             .into_iter()
             .chain(schema_list)
             .collect(),
-        JoinOperator::Inner(JoinConstraint::None)
-        | JoinOperator::LeftOuter(JoinConstraint::None) => schema_list,
+        JoinOperator::Inner(_)
+        | JoinOperator::LeftOuter(_)
+        | JoinOperator::RightOuter(_)
+        | JoinOperator::FullOuter(_)
+        | JoinOperator::Cross => schema_list,
     };
 
     Ok(schema_list)
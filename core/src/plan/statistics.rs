@@ -0,0 +1,630 @@
+use {
+    super::{context::Context, expr::PlanExpr, planner::Planner},
+    crate::{
+        ast::{
+            AstLiteral, BinaryOperator, Expr, IndexItem, IndexOperator, Join, JoinConstraint,
+            JoinExecutor, JoinOperator, OrderByExpr, Query, Select, SelectItem, SetExpr, Statement,
+            TableAlias, TableFactor, TableWithJoins,
+        },
+        data::{Literal, Schema, SchemaIndex, TableStatistics},
+        result::Result,
+        store::Statistics,
+    },
+    futures::stream::{self, StreamExt, TryStreamExt},
+    std::{collections::HashMap, iter::once, sync::Arc},
+};
+
+/// Index scans expected to read more than this fraction of the rows are replaced by a full
+/// scan, which reads the same rows without looking each of them up through the index.
+const MAX_INDEX_SELECTIVITY: f64 = 0.3;
+
+pub async fn fetch_statistics_map<T: Statistics>(
+    storage: &T,
+    schema_map: &HashMap<String, Schema>,
+) -> Result<HashMap<String, TableStatistics>> {
+    stream::iter(schema_map.keys())
+        .filter_map(|table_name| async move {
+            storage
+                .fetch_statistics(table_name)
+                .await
+                .map(|statistics| Some((table_name.clone(), statistics?)))
+                .transpose()
+        })
+        .try_collect()
+        .await
+}
+
+/// Revisits the access paths and the join order chosen by the rule-based planners with the
+/// statistics collected by `ANALYZE`. Tables without statistics are left as planned.
+pub fn plan(
+    schema_map: &HashMap<String, Schema>,
+    statistics_map: &HashMap<String, TableStatistics>,
+    statement: Statement,
+) -> Statement {
+    if statistics_map.is_empty() {
+        return statement;
+    }
+
+    let planner = StatisticsPlanner {
+        schema_map,
+        statistics_map,
+    };
+
+    match statement {
+        Statement::Query(query) => Statement::Query(planner.query(None, query)),
+        Statement::Explain { analyze, query } => Statement::Explain {
+            analyze,
+            query: Box::new(planner.query(None, *query)),
+        },
+        _ => statement,
+    }
+}
+
+struct StatisticsPlanner<'a> {
+    schema_map: &'a HashMap<String, Schema>,
+    statistics_map: &'a HashMap<String, TableStatistics>,
+}
+
+/// Table of an inner join with the number of rows its scan is expected to read
+struct JoinTable<'b> {
+    alias: &'b str,
+    columns: Vec<&'b str>,
+    rows: f64,
+}
+
+impl<'a> Planner<'a> for StatisticsPlanner<'a> {
+    fn query(&self, outer_context: Option<Arc<Context<'a>>>, query: Query) -> Query {
+        let Query {
+            with,
+            body,
+            order_by,
+            limit,
+            offset,
+        } = query;
+
+        let (outer_context, with) = self.with(outer_context, with);
+        let body = match body {
+            SetExpr::Select(select) => {
+                let select = self.select(outer_context, &order_by, *select);
+
+                SetExpr::Select(Box::new(select))
+            }
+            SetExpr::Values(_) => body,
+            SetExpr::SetOperation {
+                op,
+                all,
+                left,
+                right,
+            } => SetExpr::SetOperation {
+                op,
+                all,
+                left: Box::new(self.query(outer_context.as_ref().map(Arc::clone), *left)),
+                right: Box::new(self.query(outer_context, *right)),
+            },
+        };
+
+        Query {
+            with,
+            body,
+            order_by,
+            limit,
+            offset,
+        }
+    }
+
+    fn get_schema(&self, name: &str) -> Option<&'a Schema> {
+        self.schema_map.get(name)
+    }
+}
+
+impl<'a> StatisticsPlanner<'a> {
+    fn select(
+        &self,
+        outer_context: Option<Arc<Context<'a>>>,
+        order_by: &[OrderByExpr],
+        select: Select,
+    ) -> Select {
+        let Select {
+            distinct,
+            projection,
+            from,
+            selection,
+            group_by,
+            having,
+        } = select;
+
+        let (from, selection) = self.scan(from, selection);
+
+        // `*` lists the columns in the join order, which has to be kept
+        let wildcard = projection
+            .iter()
+            .any(|item| matches!(item, SelectItem::Wildcard));
+        let from = match wildcard {
+            true => from,
+            false => {
+                let exprs = projection
+                    .iter()
+                    .filter_map(|item| match item {
+                        SelectItem::Expr { expr, .. } => Some(expr),
+                        SelectItem::QualifiedWildcard(_) | SelectItem::Wildcard => None,
+                    })
+                    .chain(selection.as_ref())
+                    .chain(group_by.iter())
+                    .chain(having.as_ref())
+                    .chain(order_by.iter().map(|OrderByExpr { expr, .. }| expr))
+                    .collect::<Vec<_>>();
+
+                self.join_order(outer_context.as_ref(), from, &exprs)
+            }
+        };
+
+        let context = once(&from.relation)
+            .chain(from.joins.iter().map(|Join { relation, .. }| relation))
+            .fold(None, |context, relation| {
+                self.update_context(outer_context.as_ref(), context, relation)
+            });
+        let context = Context::concat(context, outer_context);
+        let selection = selection.map(|expr| self.subquery_expr(context, expr));
+
+        Select {
+            distinct,
+            projection,
+            from,
+            selection,
+            group_by,
+            having,
+        }
+    }
+
+    /// Replaces the index scan of the first table by a full scan when the statistics expect
+    /// it to read too many rows, moving the conditions of the index back to `selection`.
+    /// Index scans ordering the rows for `ORDER BY` are kept.
+    fn scan(
+        &self,
+        from: TableWithJoins,
+        selection: Option<Expr>,
+    ) -> (TableWithJoins, Option<Expr>) {
+        let TableWithJoins { relation, joins } = from;
+
+        let conditions = match &relation {
+            TableFactor::Table {
+                name,
+                index: Some(index),
+                ..
+            } if !is_ordered(&relation) => self
+                .index_parts(name, index)
+                .filter(|parts| {
+                    self.selectivity(name, parts)
+                        .is_some_and(|selectivity| selectivity > MAX_INDEX_SELECTIVITY)
+                })
+                .map(|parts| {
+                    parts
+                        .into_iter()
+                        .map(|(expr, index_op, value)| condition(expr, index_op, value))
+                        .collect::<Vec<_>>()
+                }),
+            _ => None,
+        };
+
+        let (relation, selection) = match (relation, conditions) {
+            (TableFactor::Table { name, alias, .. }, Some(conditions)) => {
+                let relation = TableFactor::Table {
+                    name,
+                    alias,
+                    index: None,
+                };
+                let selection = conditions
+                    .into_iter()
+                    .chain(selection)
+                    .reduce(|left, right| Expr::BinaryOp {
+                        left: Box::new(left),
+                        op: BinaryOperator::And,
+                        right: Box::new(right),
+                    });
+
+                (relation, selection)
+            }
+            (relation, _) => (relation, selection),
+        };
+
+        (TableWithJoins { relation, joins }, selection)
+    }
+
+    /// Index expressions compared by the index scan `index` of `table_name`, each with its
+    /// comparison and value. Scans only ordering the rows by an index have none.
+    fn index_parts<'b>(
+        &self,
+        table_name: &str,
+        index: &'b IndexItem,
+    ) -> Option<Vec<(&'a Expr, &'b IndexOperator, &'b Expr)>> {
+        let IndexItem::NonClustered {
+            name,
+            prefix,
+            cmp_expr: Some((index_op, value)),
+            ..
+        } = index
+        else {
+            return None;
+        };
+
+        let SchemaIndex { exprs, .. } = self
+            .schema_map
+            .get(table_name)?
+            .indexes
+            .iter()
+            .find(|schema_index| &schema_index.name == name)?;
+
+        let values = prefix
+            .iter()
+            .map(|value| (&IndexOperator::Eq, value))
+            .chain(once((index_op, value)));
+        let parts = exprs
+            .iter()
+            .zip(values)
+            .map(|(expr, (index_op, value))| (expr, index_op, value))
+            .collect::<Vec<_>>();
+
+        (parts.len() == prefix.len() + 1).then_some(parts)
+    }
+
+    /// Estimated fraction of the rows of `table_name` matching `parts`, from the columns
+    /// compared with literals. `None` when none of them can be estimated.
+    fn selectivity(
+        &self,
+        table_name: &str,
+        parts: &[(&Expr, &IndexOperator, &Expr)],
+    ) -> Option<f64> {
+        let statistics = self.statistics_map.get(table_name)?;
+
+        parts
+            .iter()
+            .filter_map(|(expr, index_op, value)| {
+                let (Expr::Identifier(column_name), Expr::Literal(literal)) = (expr, value) else {
+                    return None;
+                };
+                let literal = Literal::try_from(literal).ok()?;
+
+                statistics.column(column_name)?.selectivity(
+                    statistics.row_count,
+                    index_op,
+                    &literal,
+                )
+            })
+            .reduce(|a, b| a * b)
+    }
+
+    /// Reorders the tables of an inner join so that the one expected to read the most rows
+    /// drives the join, and each next table is the smallest one among the tables joinable by
+    /// a condition on the tables joined so far, which makes the smaller tables the build
+    /// sides of hash joins. `exprs` are the other expressions of the query, whose unqualified
+    /// columns must not depend on the order of the tables.
+    fn join_order(
+        &self,
+        outer_context: Option<&Arc<Context<'a>>>,
+        from: TableWithJoins,
+        exprs: &[&Expr],
+    ) -> TableWithJoins {
+        let Some((order, condition_tables)) = self.plan_join_order(outer_context, &from, exprs)
+        else {
+            return from;
+        };
+
+        let TableWithJoins { relation, joins } = from;
+        let mut relations = vec![(0, relation)];
+        let mut conditions = Vec::new();
+        for (i, join) in joins.into_iter().enumerate() {
+            let Join {
+                relation,
+                join_operator,
+                ..
+            } = join;
+
+            if let JoinOperator::Inner(JoinConstraint::On(expr)) = join_operator {
+                split(expr, &mut conditions);
+            }
+
+            relations.push((i + 1, relation));
+        }
+
+        relations.sort_by_key(|(i, _)| order.iter().position(|j| j == i));
+        let mut conditions = conditions
+            .into_iter()
+            .zip(condition_tables)
+            .map(Some)
+            .collect::<Vec<_>>();
+        let (first, relation) = relations.remove(0);
+        let mut joined = vec![first];
+        let joins = relations
+            .into_iter()
+            .map(|(i, relation)| {
+                joined.push(i);
+
+                let expr = conditions
+                    .iter_mut()
+                    .filter(|condition| {
+                        condition
+                            .as_ref()
+                            .is_some_and(|(_, tables)| tables.iter().all(|j| joined.contains(j)))
+                    })
+                    .filter_map(|condition| condition.take().map(|(expr, _)| expr))
+                    .reduce(|left, right| Expr::BinaryOp {
+                        left: Box::new(left),
+                        op: BinaryOperator::And,
+                        right: Box::new(right),
+                    });
+                let join_constraint = match expr {
+                    Some(expr) => JoinConstraint::On(expr),
+                    None => JoinConstraint::None,
+                };
+
+                Join {
+                    relation,
+                    join_operator: JoinOperator::Inner(join_constraint),
+                    join_executor: JoinExecutor::NestedLoop,
+                }
+            })
+            .collect();
+
+        TableWithJoins { relation, joins }
+    }
+
+    /// Returns the new order of the tables with the tables referenced by each condition of
+    /// the joins, `None` when the join has to be kept as written.
+    fn plan_join_order(
+        &self,
+        outer_context: Option<&Arc<Context<'a>>>,
+        from: &TableWithJoins,
+        exprs: &[&Expr],
+    ) -> Option<(Vec<usize>, Vec<Vec<usize>>)> {
+        let TableWithJoins { relation, joins } = from;
+        if joins.is_empty() || is_ordered(relation) {
+            return None;
+        }
+
+        let mut conditions = Vec::new();
+        for Join {
+            join_operator,
+            join_executor,
+            ..
+        } in joins
+        {
+            match (join_operator, join_executor) {
+                (JoinOperator::Inner(JoinConstraint::On(expr)), JoinExecutor::NestedLoop) => {
+                    split_ref(expr, &mut conditions);
+                }
+                (JoinOperator::Inner(JoinConstraint::None), JoinExecutor::NestedLoop) => {}
+                _ => return None,
+            }
+        }
+
+        let tables = once(relation)
+            .chain(joins.iter().map(|Join { relation, .. }| relation))
+            .map(|relation| self.join_table(outer_context, relation))
+            .collect::<Option<Vec<_>>>()?;
+        let unique = tables
+            .iter()
+            .enumerate()
+            .all(|(i, table)| tables[..i].iter().all(|other| other.alias != table.alias));
+        if !unique {
+            return None;
+        }
+
+        let find_tables = |expr: &Expr| {
+            let mut columns = Vec::new();
+            if !collect_columns(expr, &mut columns) {
+                return None;
+            }
+
+            let mut found = Vec::new();
+            for (alias, column) in columns {
+                let matched = tables
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, table)| match alias {
+                        Some(alias) => table.alias == alias,
+                        None => table.columns.contains(&column),
+                    })
+                    .map(|(i, _)| i)
+                    .collect::<Vec<_>>();
+
+                match matched.as_slice() {
+                    [] => {}
+                    [i] => found.push(*i),
+                    _ => return None,
+                }
+            }
+
+            Some(found)
+        };
+
+        if !exprs.iter().all(|expr| find_tables(expr).is_some()) {
+            return None;
+        }
+        let condition_tables = conditions
+            .into_iter()
+            .map(find_tables)
+            .collect::<Option<Vec<_>>>()?;
+
+        let by_rows = |a: &usize, b: &usize| tables[*a].rows.total_cmp(&tables[*b].rows);
+        let first = (0..tables.len()).max_by(|a, b| by_rows(a, b).then(b.cmp(a)))?;
+        let mut order = vec![first];
+        while order.len() < tables.len() {
+            let remaining = (0..tables.len()).filter(|i| !order.contains(i));
+            let joinable = |i: &usize| {
+                condition_tables.iter().any(|tables| {
+                    tables.contains(i)
+                        && tables.iter().any(|j| order.contains(j))
+                        && tables.iter().all(|j| j == i || order.contains(j))
+                })
+            };
+            let smallest = |a: &usize, b: &usize| by_rows(a, b).then(a.cmp(b));
+
+            let next = remaining
+                .clone()
+                .filter(joinable)
+                .min_by(smallest)
+                .or_else(|| remaining.min_by(smallest))?;
+            order.push(next);
+        }
+
+        order
+            .iter()
+            .copied()
+            .ne(0..tables.len())
+            .then_some((order, condition_tables))
+    }
+
+    fn join_table<'b>(
+        &self,
+        outer_context: Option<&Arc<Context<'a>>>,
+        relation: &'b TableFactor,
+    ) -> Option<JoinTable<'b>>
+    where
+        'a: 'b,
+    {
+        let TableFactor::Table { name, alias, index } = relation else {
+            return None;
+        };
+        if outer_context.is_some_and(|context| context.get_cte(name).is_some()) {
+            return None;
+        }
+
+        let statistics = self.statistics_map.get(name)?;
+        let columns = self
+            .schema_map
+            .get(name)?
+            .column_defs
+            .as_ref()?
+            .iter()
+            .map(|column_def| column_def.name.as_str())
+            .collect();
+
+        let row_count = statistics.row_count as f64;
+        let rows = match index {
+            Some(IndexItem::PrimaryKey(_)) => row_count.min(1.0),
+            Some(index) => self
+                .index_parts(name, index)
+                .and_then(|parts| self.selectivity(name, &parts))
+                .map_or(row_count, |selectivity| row_count * selectivity),
+            None => row_count,
+        };
+        let alias = match alias {
+            Some(TableAlias { name, .. }) => name,
+            None => name,
+        };
+
+        Some(JoinTable {
+            alias,
+            columns,
+            rows,
+        })
+    }
+}
+
+/// Whether `relation` is scanned through an index for `ORDER BY`, which needs the rows of
+/// the scan to come first and in the order of the index
+fn is_ordered(relation: &TableFactor) -> bool {
+    matches!(
+        relation,
+        TableFactor::Table {
+            index: Some(
+                IndexItem::NonClustered { asc: Some(_), .. }
+                    | IndexItem::NonClustered { cmp_expr: None, .. }
+            ),
+            ..
+        }
+    )
+}
+
+/// Condition of an index expression equivalent to comparing it through the index, where
+/// `NULL` values stand for the `IS NULL` and `IS NOT NULL` index scans
+fn condition(expr: &Expr, index_op: &IndexOperator, value: &Expr) -> Expr {
+    let expr = Box::new(expr.clone());
+
+    match (index_op, value) {
+        (IndexOperator::Eq, Expr::Literal(AstLiteral::Null)) => Expr::IsNull(expr),
+        (IndexOperator::Lt, Expr::Literal(AstLiteral::Null)) => Expr::IsNotNull(expr),
+        _ => Expr::BinaryOp {
+            left: expr,
+            op: index_op.clone().into(),
+            right: Box::new(value.clone()),
+        },
+    }
+}
+
+fn split(expr: Expr, conditions: &mut Vec<Expr>) {
+    match expr {
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::And,
+            right,
+        } => {
+            split(*left, conditions);
+            split(*right, conditions);
+        }
+        Expr::Nested(expr)
+            if matches!(
+                *expr,
+                Expr::BinaryOp {
+                    op: BinaryOperator::And,
+                    ..
+                }
+            ) =>
+        {
+            split(*expr, conditions)
+        }
+        expr => conditions.push(expr),
+    }
+}
+
+fn split_ref<'b>(expr: &'b Expr, conditions: &mut Vec<&'b Expr>) {
+    match expr {
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::And,
+            right,
+        } => {
+            split_ref(left, conditions);
+            split_ref(right, conditions);
+        }
+        Expr::Nested(expr)
+            if matches!(
+                **expr,
+                Expr::BinaryOp {
+                    op: BinaryOperator::And,
+                    ..
+                }
+            ) =>
+        {
+            split_ref(expr, conditions)
+        }
+        expr => conditions.push(expr),
+    }
+}
+
+/// Collects the columns `expr` references with their table aliases, returning `false` when
+/// `expr` has a subquery, whose columns are not collected.
+fn collect_columns<'b>(expr: &'b Expr, columns: &mut Vec<(Option<&'b str>, &'b str)>) -> bool {
+    match PlanExpr::from(expr) {
+        PlanExpr::None => true,
+        PlanExpr::Identifier(ident) => {
+            columns.push((None, ident));
+            true
+        }
+        PlanExpr::CompoundIdentifier { alias, ident } => {
+            columns.push((Some(alias), ident));
+            true
+        }
+        PlanExpr::Expr(expr) => collect_columns(expr, columns),
+        PlanExpr::TwoExprs(expr, expr2) => {
+            collect_columns(expr, columns) && collect_columns(expr2, columns)
+        }
+        PlanExpr::ThreeExprs(expr, expr2, expr3) => {
+            collect_columns(expr, columns)
+                && collect_columns(expr2, columns)
+                && collect_columns(expr3, columns)
+        }
+        PlanExpr::MultiExprs(exprs) => exprs.into_iter().all(|expr| collect_columns(expr, columns)),
+        PlanExpr::Query(_) | PlanExpr::QueryAndExpr { .. } => false,
+    }
+}
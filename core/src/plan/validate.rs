@@ -1,11 +1,14 @@
 use {
     super::PlanError,
     crate::{
-        ast::{Expr, Join, Query, SelectItem, SetExpr, Statement, TableFactor, TableWithJoins},
+        ast::{
+            Cte, Expr, Join, JoinConstraint, JoinOperator, Query, SelectItem, SetExpr, Statement,
+            TableFactor, TableWithJoins, With,
+        },
         data::Schema,
         result::Result,
     },
-    std::{collections::HashMap, sync::Arc},
+    std::{borrow::Cow, collections::HashMap, sync::Arc},
 };
 
 type SchemaMap = HashMap<String, Schema>;
@@ -13,17 +16,27 @@ type SchemaMap = HashMap<String, Schema>;
 pub fn validate(schema_map: &SchemaMap, statement: &Statement) -> Result<()> {
     let query = match statement {
         Statement::Query(query) => Some(query),
+        Statement::Explain { query, .. } => Some(query.as_ref()),
         Statement::Insert { source, .. } => Some(source),
         Statement::CreateTable { source, .. } => source.as_deref(),
         _ => None,
     };
 
-    if let Some(query) = query {
-        if let Query {
-            body: SetExpr::Select(select),
-            ..
-        } = query
-        {
+    match query {
+        Some(query) => validate_query(schema_map, query),
+        None => Ok(()),
+    }
+}
+
+fn validate_query(schema_map: &SchemaMap, query: &Query) -> Result<()> {
+    let schema_map = match &query.with {
+        Some(with) => validate_with(schema_map, with)?,
+        None => Cow::Borrowed(schema_map),
+    };
+    let schema_map = schema_map.as_ref();
+
+    match &query.body {
+        SetExpr::Select(select) => {
             for select_item in &select.projection {
                 if let SelectItem::Expr {
                     expr: Expr::Identifier(ident),
@@ -35,10 +48,36 @@ pub fn validate(schema_map: &SchemaMap, statement: &Statement) -> Result<()> {
                     }
                 }
             }
+
+            Ok(())
+        }
+        SetExpr::SetOperation { left, right, .. } => {
+            validate_query(schema_map, left)?;
+            validate_query(schema_map, right)
         }
+        SetExpr::Values(_) => Ok(()),
     }
+}
+
+/// CTE names shadow tables of the same name, so their schemas are excluded from the
+/// validation of the queries in which the CTEs are visible.
+fn validate_with<'a>(schema_map: &'a SchemaMap, with: &With) -> Result<Cow<'a, SchemaMap>> {
+    let With {
+        recursive,
+        cte_tables,
+    } = with;
+
+    let mut schema_map = Cow::Borrowed(schema_map);
+    for Cte { alias, query } in cte_tables {
+        if *recursive {
+            schema_map.to_mut().remove(&alias.name);
+        }
 
-    Ok(())
+        validate_query(&schema_map, query)?;
+        schema_map.to_mut().remove(&alias.name);
+    }
+
+    Ok(schema_map)
 }
 
 enum Context<'a> {
@@ -68,6 +107,40 @@ impl<'a> Context<'a> {
         }
     }
 
+    fn labels(&self) -> Vec<&'a str> {
+        match self {
+            Context::Data { labels, next } => labels
+                .iter()
+                .flatten()
+                .copied()
+                .chain(next.iter().flat_map(|next| next.labels()))
+                .collect(),
+            Context::Bridge { left, right } => [left.labels(), right.labels()].concat(),
+        }
+    }
+
+    /// Columns merged by `USING` or `NATURAL` are no longer ambiguous between both sides.
+    fn exclude(&self, using: &[&str]) -> Arc<Self> {
+        let context = match self {
+            Context::Data { labels, next } => Context::Data {
+                labels: labels.as_ref().map(|labels| {
+                    labels
+                        .iter()
+                        .filter(|label| !using.contains(label))
+                        .copied()
+                        .collect()
+                }),
+                next: next.as_ref().map(|next| next.exclude(using)),
+            },
+            Context::Bridge { left, right } => Context::Bridge {
+                left: left.exclude(using),
+                right: right.exclude(using),
+            },
+        };
+
+        Arc::new(context)
+    }
+
     fn validate_duplicated(&self, column_name: &str) -> Result<()> {
         fn validate(context: &Context, column_name: &str) -> Result<bool> {
             let (left, right) = match context {
@@ -121,14 +194,55 @@ fn contextualize_query<'a>(
         SetExpr::Select(select) => {
             let TableWithJoins { relation, joins } = &select.from;
             let by_table = contextualize_table_factor(schema_map, relation);
-            let by_joins = joins
-                .iter()
-                .map(|Join { relation, .. }| contextualize_table_factor(schema_map, relation))
-                .fold(None, Context::concat);
 
-            Context::concat(by_table, by_joins)
+            joins.iter().fold(by_table, |context, join| {
+                let Join {
+                    relation,
+                    join_operator,
+                    ..
+                } = join;
+                let by_join = contextualize_table_factor(schema_map, relation);
+                let join_constraint = match join_operator {
+                    JoinOperator::Inner(join_constraint)
+                    | JoinOperator::LeftOuter(join_constraint)
+                    | JoinOperator::RightOuter(join_constraint)
+                    | JoinOperator::FullOuter(join_constraint) => join_constraint,
+                    JoinOperator::Cross => &JoinConstraint::None,
+                };
+                let using = match join_constraint {
+                    JoinConstraint::Using(using) => {
+                        using.iter().map(String::as_str).collect::<Vec<_>>()
+                    }
+                    JoinConstraint::Natural => {
+                        let labels = |context: &Option<Arc<Context<'a>>>| {
+                            context
+                                .as_ref()
+                                .map(|context| context.labels())
+                                .unwrap_or_default()
+                        };
+                        let right = labels(&by_join);
+
+                        labels(&context)
+                            .into_iter()
+                            .filter(|label| right.contains(label))
+                            .collect()
+                    }
+                    JoinConstraint::On(_) | JoinConstraint::None => Vec::new(),
+                };
+
+                if using.is_empty() {
+                    return Context::concat(context, by_join);
+                }
+
+                let context = Context::concat(
+                    context.map(|context| context.exclude(&using)),
+                    by_join.map(|context| context.exclude(&using)),
+                );
+
+                Context::concat(Some(Arc::new(Context::new(Some(using), None))), context)
+            })
         }
-        SetExpr::Values(_) => None,
+        SetExpr::Values(_) | SetExpr::SetOperation { .. } => None,
     }
 }
 
@@ -170,6 +284,13 @@ mod tests {
             ("SELECT * FROM (SELECT * FROM Users) AS Sub", true),
             ("SELECT * FROM SERIES(3)", true),
             ("SELECT id FROM Users A JOIN Users B on A.id = B.id", false),
+            ("SELECT id FROM Users A JOIN Users B USING (id)", true),
+            ("SELECT name FROM Users A JOIN Users B USING (id)", false),
+            ("SELECT id, name FROM Users A NATURAL JOIN Users B", true),
+            (
+                "SELECT id FROM Users A JOIN Users B USING (id) JOIN Users C ON A.id = C.id",
+                false,
+            ),
             (
                 "INSERT INTO Users SELECT id FROM Users A JOIN Users B on A.id = B.id",
                 false,
@@ -0,0 +1,307 @@
+use {
+    super::{PlanError, context::Context, planner::Planner},
+    crate::{
+        ast::{
+            Assignment, Join, JoinConstraint, JoinOperator, OrderByExpr, Query, Select, SelectItem,
+            SetExpr, Statement, TableAlias, TableFactor, TableWithJoins,
+        },
+        data::{Schema, View as StructView},
+        result::Result,
+        store::View,
+    },
+    std::{cell::Cell, collections::HashMap, sync::Arc},
+};
+
+pub async fn fetch_view_map<T: View>(
+    storage: &T,
+    statement: &Statement,
+) -> Result<HashMap<String, StructView>> {
+    match statement {
+        Statement::Query(_)
+        | Statement::Explain { .. }
+        | Statement::Insert { .. }
+        | Statement::Update { .. }
+        | Statement::Delete { .. }
+        | Statement::CreateTable { .. } => Ok(storage
+            .fetch_all_views()
+            .await?
+            .into_iter()
+            .map(|view| (view.view_name.clone(), view))
+            .collect()),
+        _ => Ok(HashMap::new()),
+    }
+}
+
+/// Expands views into derived tables, so the rest of the planner and the executor only
+/// see base tables. Materialized views are read from their backing tables and kept as is.
+pub fn plan(view_map: &HashMap<String, StructView>, statement: Statement) -> Result<Statement> {
+    if view_map.is_empty() {
+        return Ok(statement);
+    }
+
+    let cycle = Cell::new(None);
+    let planner = ViewPlanner {
+        view_map,
+        expanding: Vec::new(),
+        cycle: &cycle,
+    };
+
+    let statement = match statement {
+        Statement::Query(query) => Statement::Query(planner.query(None, query)),
+        Statement::Explain { analyze, query } => Statement::Explain {
+            analyze,
+            query: Box::new(planner.query(None, *query)),
+        },
+        Statement::Insert {
+            table_name,
+            columns,
+            source,
+            on_conflict,
+            returning,
+        } => Statement::Insert {
+            table_name,
+            columns,
+            source: planner.query(None, source),
+            on_conflict,
+            returning,
+        },
+        Statement::Update {
+            table_name,
+            assignments,
+            selection,
+            returning,
+        } => Statement::Update {
+            table_name,
+            assignments: assignments
+                .into_iter()
+                .map(|Assignment { id, value }| Assignment {
+                    id,
+                    value: planner.subquery_expr(None, value),
+                })
+                .collect(),
+            selection: selection.map(|expr| planner.subquery_expr(None, expr)),
+            returning,
+        },
+        Statement::Delete {
+            table_name,
+            selection,
+            returning,
+        } => Statement::Delete {
+            table_name,
+            selection: selection.map(|expr| planner.subquery_expr(None, expr)),
+            returning,
+        },
+        Statement::CreateTable {
+            if_not_exists,
+            name,
+            columns,
+            source,
+            engine,
+            key_constraints,
+            foreign_keys,
+            checks,
+            comment,
+        } => Statement::CreateTable {
+            if_not_exists,
+            name,
+            columns,
+            source: source.map(|source| Box::new(planner.query(None, *source))),
+            engine,
+            key_constraints,
+            foreign_keys,
+            checks,
+            comment,
+        },
+        _ => statement,
+    };
+
+    match cycle.get() {
+        Some(view_name) => Err(PlanError::RecursiveView(view_name.to_owned()).into()),
+        None => Ok(statement),
+    }
+}
+
+struct ViewPlanner<'a, 'b> {
+    view_map: &'a HashMap<String, StructView>,
+    /// Views being expanded, to stop at a view defined in terms of itself
+    expanding: Vec<&'a str>,
+    cycle: &'b Cell<Option<&'a str>>,
+}
+
+impl<'a> Planner<'a> for ViewPlanner<'a, '_> {
+    fn query(&self, outer_context: Option<Arc<Context<'a>>>, query: Query) -> Query {
+        let Query {
+            with,
+            body,
+            order_by,
+            limit,
+            offset,
+        } = query;
+
+        let (outer_context, with) = self.with(outer_context, with);
+        let body = self.set_expr(outer_context.as_ref(), body);
+        let order_by = order_by
+            .into_iter()
+            .map(|OrderByExpr { expr, asc }| OrderByExpr {
+                expr: self.subquery_expr(outer_context.as_ref().map(Arc::clone), expr),
+                asc,
+            })
+            .collect();
+
+        Query {
+            with,
+            body,
+            order_by,
+            limit,
+            offset,
+        }
+    }
+
+    fn get_schema(&self, _name: &str) -> Option<&'a Schema> {
+        None
+    }
+}
+
+impl<'a> ViewPlanner<'a, '_> {
+    fn set_expr(&self, outer_context: Option<&Arc<Context<'a>>>, set_expr: SetExpr) -> SetExpr {
+        match set_expr {
+            SetExpr::Select(select) => {
+                SetExpr::Select(Box::new(self.select(outer_context, *select)))
+            }
+            SetExpr::Values(_) => set_expr,
+            SetExpr::SetOperation {
+                op,
+                all,
+                left,
+                right,
+            } => SetExpr::SetOperation {
+                op,
+                all,
+                left: Box::new(self.query(outer_context.map(Arc::clone), *left)),
+                right: Box::new(self.query(outer_context.map(Arc::clone), *right)),
+            },
+        }
+    }
+
+    fn select(&self, outer_context: Option<&Arc<Context<'a>>>, select: Select) -> Select {
+        let Select {
+            distinct,
+            projection,
+            from: TableWithJoins { relation, joins },
+            selection,
+            group_by,
+            having,
+        } = select;
+
+        let expr = |expr| self.subquery_expr(outer_context.map(Arc::clone), expr);
+        let projection = projection
+            .into_iter()
+            .map(|item| match item {
+                SelectItem::Expr { expr: e, label } => SelectItem::Expr {
+                    expr: expr(e),
+                    label,
+                },
+                SelectItem::QualifiedWildcard(_) | SelectItem::Wildcard => item,
+            })
+            .collect();
+        let joins = joins
+            .into_iter()
+            .map(
+                |Join {
+                     relation,
+                     join_operator,
+                     join_executor,
+                 }| {
+                    let join_operator = match join_operator {
+                        JoinOperator::Inner(JoinConstraint::On(e)) => {
+                            JoinOperator::Inner(JoinConstraint::On(expr(e)))
+                        }
+                        JoinOperator::LeftOuter(JoinConstraint::On(e)) => {
+                            JoinOperator::LeftOuter(JoinConstraint::On(expr(e)))
+                        }
+                        JoinOperator::RightOuter(JoinConstraint::On(e)) => {
+                            JoinOperator::RightOuter(JoinConstraint::On(expr(e)))
+                        }
+                        JoinOperator::FullOuter(JoinConstraint::On(e)) => {
+                            JoinOperator::FullOuter(JoinConstraint::On(expr(e)))
+                        }
+                        join_operator => join_operator,
+                    };
+
+                    Join {
+                        relation: self.table_factor(outer_context, relation),
+                        join_operator,
+                        join_executor,
+                    }
+                },
+            )
+            .collect();
+        let from = TableWithJoins {
+            relation: self.table_factor(outer_context, relation),
+            joins,
+        };
+
+        Select {
+            distinct,
+            projection,
+            from,
+            selection: selection.map(expr),
+            group_by: group_by.into_iter().map(expr).collect(),
+            having: having.map(expr),
+        }
+    }
+
+    fn table_factor(
+        &self,
+        outer_context: Option<&Arc<Context<'a>>>,
+        table_factor: TableFactor,
+    ) -> TableFactor {
+        match table_factor {
+            TableFactor::Table { name, alias, index }
+                if outer_context
+                    .and_then(|context| context.get_cte(&name))
+                    .is_none() =>
+            {
+                let view = match self.view_map.get(&name) {
+                    Some(view) if !view.materialized => view,
+                    _ => return TableFactor::Table { name, alias, index },
+                };
+
+                if self.expanding.contains(&view.view_name.as_str()) {
+                    self.cycle.set(Some(view.view_name.as_str()));
+
+                    return TableFactor::Table { name, alias, index };
+                }
+
+                let planner = ViewPlanner {
+                    view_map: self.view_map,
+                    expanding: [self.expanding.as_slice(), &[view.view_name.as_str()]].concat(),
+                    cycle: self.cycle,
+                };
+                let subquery = planner.query(None, view.query.clone());
+
+                let TableAlias { name, columns } = alias.unwrap_or(TableAlias {
+                    name,
+                    columns: Vec::new(),
+                });
+                let columns = columns
+                    .iter()
+                    .chain(view.columns.iter().skip(columns.len()))
+                    .cloned()
+                    .collect();
+
+                TableFactor::Derived {
+                    subquery,
+                    alias: TableAlias { name, columns },
+                }
+            }
+            TableFactor::Derived { subquery, alias } => TableFactor::Derived {
+                subquery: self.query(outer_context.map(Arc::clone), subquery),
+                alias,
+            },
+            TableFactor::Table { .. }
+            | TableFactor::Series { .. }
+            | TableFactor::Dictionary { .. } => table_factor,
+        }
+    }
+}
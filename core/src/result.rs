@@ -4,11 +4,11 @@ pub use crate::{
     ast_builder::AstBuilderError,
     data::{
         ConvertError, IntervalError, KeyError, LiteralError, RowError, SchemaParseError,
-        StringExtError, TableError, ValueError,
+        SequenceError, StringExtError, TableError, TriggerError, ValueError,
     },
     executor::{
-        AlterError, DeleteError, EvaluateError, ExecuteError, FetchError, InsertError, SelectError,
-        SortError, UpdateError, ValidateError,
+        AlterError, DeleteError, EvaluateError, ExecuteError, FetchError, InsertError, JoinError,
+        LimitError, SelectError, SortError, UpdateError, ValidateError, WindowError,
     },
     plan::PlanError,
     store::{AlterTableError, IndexError},
@@ -35,16 +35,22 @@ pub enum Error {
     Index(#[from] IndexError),
     #[error("execute: {0}")]
     Execute(#[from] ExecuteError),
+    #[error("limit: {0}")]
+    Limit(#[from] LimitError),
     #[error("alter: {0}")]
     Alter(Box<AlterError>),
     #[error("fetch: {0}")]
     Fetch(#[from] FetchError),
     #[error("select: {0}")]
     Select(#[from] SelectError),
+    #[error("join: {0}")]
+    Join(#[from] JoinError),
     #[error("evaluate: {0}")]
     Evaluate(#[from] EvaluateError),
     #[error("sort: {0}")]
     Sort(#[from] SortError),
+    #[error("window: {0}")]
+    Window(#[from] WindowError),
     #[error("insert: {0}")]
     Insert(#[from] InsertError),
     #[error("update: {0}")]
@@ -73,6 +79,10 @@ pub enum Error {
     Plan(#[from] PlanError),
     #[error("schema-parse: {0}")]
     Schema(#[from] SchemaParseError),
+    #[error("sequence: {0}")]
+    Sequence(#[from] SequenceError),
+    #[error("trigger: {0}")]
+    Trigger(#[from] TriggerError),
 }
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
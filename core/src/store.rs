@@ -3,17 +3,47 @@ mod data_row;
 mod function;
 mod index;
 mod metadata;
+mod scan_filter;
+mod sequence;
+mod statistics;
 mod transaction;
+mod trigger;
+mod view;
 
-pub trait GStore: Store + Index + Metadata + CustomFunction {}
-impl<S: Store + Index + Metadata + CustomFunction> GStore for S {}
+pub trait GStore:
+    Store + Index + Metadata + CustomFunction + View + Statistics + Sequence + Trigger
+{
+}
+impl<S: Store + Index + Metadata + CustomFunction + View + Statistics + Sequence + Trigger> GStore
+    for S
+{
+}
 
 pub trait GStoreMut:
-    StoreMut + IndexMut + AlterTable + Transaction + CustomFunction + CustomFunctionMut
+    StoreMut
+    + IndexMut
+    + AlterTable
+    + Transaction
+    + CustomFunction
+    + CustomFunctionMut
+    + ViewMut
+    + StatisticsMut
+    + SequenceMut
+    + TriggerMut
 {
 }
-impl<S: StoreMut + IndexMut + AlterTable + Transaction + CustomFunction + CustomFunctionMut>
-    GStoreMut for S
+impl<
+    S: StoreMut
+        + IndexMut
+        + AlterTable
+        + Transaction
+        + CustomFunction
+        + CustomFunctionMut
+        + ViewMut
+        + StatisticsMut
+        + SequenceMut
+        + TriggerMut,
+> GStoreMut for S
 {
 }
 
@@ -23,7 +53,12 @@ pub use {
     function::{CustomFunction, CustomFunctionMut},
     index::{Index, IndexError, IndexMut},
     metadata::{MetaIter, Metadata},
+    scan_filter::{ScanFilter, ScanPredicate},
+    sequence::{Sequence, SequenceMut},
+    statistics::{Statistics, StatisticsMut},
     transaction::Transaction,
+    trigger::{Trigger, TriggerMut},
+    view::{View, ViewMut},
 };
 
 use {
@@ -33,7 +68,10 @@ use {
         result::{Error, Result},
     },
     async_trait::async_trait,
-    futures::stream::Stream,
+    futures::{
+        future,
+        stream::{Stream, TryStreamExt},
+    },
     std::pin::Pin,
 };
 
@@ -50,6 +88,41 @@ pub trait Store: Send + Sync {
 
     async fn scan_data<'a>(&'a self, table_name: &str) -> Result<RowIter<'a>>;
 
+    /// Scans the rows like [`Store::scan_data`] for a query using only the columns and the
+    /// rows described by `filter`. Storages may skip the rows not satisfying its predicates
+    /// and give `NULL` for the columns it does not require, the default implementation
+    /// ignores it.
+    async fn scan_data_filtered<'a>(
+        &'a self,
+        table_name: &str,
+        _filter: &ScanFilter,
+    ) -> Result<RowIter<'a>> {
+        self.scan_data(table_name).await
+    }
+
+    /// Scans the rows whose composite primary key starts with `prefix`. The default
+    /// implementation filters [`Store::scan_data`], storages keeping keys in byte order can
+    /// range scan the encoded prefix instead.
+    async fn scan_data_by_prefix<'a>(
+        &'a self,
+        table_name: &str,
+        prefix: &[Key],
+    ) -> Result<RowIter<'a>> {
+        let prefix = prefix.to_vec();
+        let rows = self
+            .scan_data(table_name)
+            .await?
+            .try_filter_map(move |(key, row)| {
+                let matched = key
+                    .starts_with(&prefix)
+                    .map(|matched| matched.then_some((key, row)));
+
+                future::ready(matched)
+            });
+
+        Ok(Box::pin(rows))
+    }
+
     async fn fetch_referencings(&self, table_name: &str) -> Result<Vec<Referencing>> {
         let schemas = self.fetch_all_schemas().await?;
 
@@ -17,7 +17,7 @@ pub enum AlterTableError {
     RenamingColumnNotFound,
 
     #[error("Default value is required: {0:#?}")]
-    DefaultValueRequired(ColumnDef),
+    DefaultValueRequired(Box<ColumnDef>),
 
     #[error("Already existing column: {0}")]
     AlreadyExistingColumn(String),
@@ -101,7 +101,9 @@ pub trait AlterTable: Store + StoreMut {
             (Some(default), _) => evaluate_stateless(None, default).await?.try_into()?,
             (None, true) => Value::Null,
             (None, false) => {
-                return Err(AlterTableError::DefaultValueRequired(column_def.clone()).into());
+                return Err(
+                    AlterTableError::DefaultValueRequired(Box::new(column_def.clone())).into(),
+                );
             }
         };
 
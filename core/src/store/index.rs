@@ -36,15 +36,21 @@ pub enum IndexError {
 
     #[error("conflict - index sync - delete index data")]
     ConflictOnIndexDataDeleteSync,
+
+    #[error("duplicate entry for unique index: {0}")]
+    DuplicateEntryOnUniqueIndex(String),
 }
 
 #[async_trait]
 pub trait Index {
+    /// Scans the rows whose leading index values equal `prefix`, filtered by `cmp_value` on
+    /// the index value following them. `prefix` is empty for single-expression indexes.
     async fn scan_indexed_data<'a>(
         &'a self,
         _table_name: &str,
         _index_name: &str,
         _asc: Option<bool>,
+        _prefix: &[Value],
         _cmp_value: Option<(&IndexOperator, Value)>,
     ) -> Result<RowIter<'a>> {
         Err(Error::StorageMsg(
@@ -55,11 +61,15 @@ pub trait Index {
 
 #[async_trait]
 pub trait IndexMut {
+    /// Creates an index on `columns`. Writes to a `unique` index fail with
+    /// [`IndexError::DuplicateEntryOnUniqueIndex`] when another row already has the same
+    /// index values, while rows with any `NULL` index value never conflict.
     async fn create_index(
         &mut self,
         _table_name: &str,
         _index_name: &str,
-        _column: &OrderByExpr,
+        _columns: &[OrderByExpr],
+        _unique: bool,
     ) -> Result<()> {
         let msg = "[Storage] Index::create_index is not supported".to_owned();
 
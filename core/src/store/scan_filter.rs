@@ -0,0 +1,91 @@
+use {
+    crate::{ast::IndexOperator, data::Value},
+    std::cmp::Ordering,
+};
+
+/// Columns and conditions of a table scan, which storages can use to read less data.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ScanFilter {
+    /// Columns used by the query, all of them when `None`
+    pub columns: Option<Vec<String>>,
+    /// Conditions on the columns satisfied by every row used by the query
+    pub predicates: Vec<ScanPredicate>,
+}
+
+/// `column op value`, which is not satisfied by `NULL`
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScanPredicate {
+    pub column: String,
+    pub op: IndexOperator,
+    /// Value of the type of the column
+    pub value: Value,
+}
+
+impl ScanFilter {
+    pub fn requires(&self, column: &str) -> bool {
+        self.columns
+            .as_ref()
+            .is_none_or(|columns| columns.iter().any(|name| name == column))
+    }
+}
+
+impl ScanPredicate {
+    /// Whether `value` of the column satisfies the predicate, `true` when it cannot be
+    /// compared with the value of the predicate
+    pub fn check(&self, value: &Value) -> bool {
+        if value.is_null() {
+            return false;
+        }
+
+        let Some(ordering) = value.evaluate_cmp(&self.value) else {
+            return true;
+        };
+
+        match self.op {
+            IndexOperator::Eq => ordering == Ordering::Equal,
+            IndexOperator::Gt => ordering == Ordering::Greater,
+            IndexOperator::GtEq => ordering != Ordering::Less,
+            IndexOperator::Lt => ordering == Ordering::Less,
+            IndexOperator::LtEq => ordering != Ordering::Greater,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{ScanFilter, ScanPredicate},
+        crate::{ast::IndexOperator, data::Value},
+    };
+
+    #[test]
+    fn check() {
+        let predicate = |op| ScanPredicate {
+            column: "id".to_owned(),
+            op,
+            value: Value::I64(3),
+        };
+
+        assert!(predicate(IndexOperator::Eq).check(&Value::I64(3)));
+        assert!(!predicate(IndexOperator::Eq).check(&Value::I64(4)));
+        assert!(predicate(IndexOperator::Gt).check(&Value::I64(4)));
+        assert!(!predicate(IndexOperator::Gt).check(&Value::I64(3)));
+        assert!(predicate(IndexOperator::GtEq).check(&Value::I64(3)));
+        assert!(predicate(IndexOperator::Lt).check(&Value::I64(2)));
+        assert!(!predicate(IndexOperator::LtEq).check(&Value::I64(4)));
+        assert!(!predicate(IndexOperator::Lt).check(&Value::Null));
+        assert!(predicate(IndexOperator::Eq).check(&Value::Str("a".to_owned())));
+    }
+
+    #[test]
+    fn requires() {
+        let filter = ScanFilter {
+            columns: Some(vec!["id".to_owned()]),
+            predicates: Vec::new(),
+        };
+
+        assert!(filter.requires("id"));
+        assert!(!filter.requires("name"));
+        assert!(ScanFilter::default().requires("name"));
+    }
+}
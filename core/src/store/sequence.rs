@@ -0,0 +1,39 @@
+use {
+    crate::{
+        data::Sequence as StructSequence,
+        result::{Error, Result},
+    },
+    async_trait::async_trait,
+};
+
+/// `NEXTVAL` is evaluated along with the other expressions through a shared reference, so
+/// storages advance their sequences behind it and keep the new value in the current
+/// transaction.
+#[async_trait]
+pub trait Sequence: Send + Sync {
+    async fn fetch_sequence(&self, _sequence_name: &str) -> Result<Option<StructSequence>> {
+        Ok(None)
+    }
+
+    /// Advances the sequence and returns its new value, `None` when it does not exist.
+    async fn next_sequence_value(&self, _sequence_name: &str) -> Result<Option<i64>> {
+        Err(Error::StorageMsg(
+            "[Storage] Sequence is not supported".to_owned(),
+        ))
+    }
+}
+
+#[async_trait]
+pub trait SequenceMut: Send + Sync {
+    async fn insert_sequence(&mut self, _sequence: StructSequence) -> Result<()> {
+        Err(Error::StorageMsg(
+            "[Storage] Sequence is not supported".to_owned(),
+        ))
+    }
+
+    async fn delete_sequence(&mut self, _sequence_name: &str) -> Result<()> {
+        Err(Error::StorageMsg(
+            "[Storage] Sequence is not supported".to_owned(),
+        ))
+    }
+}
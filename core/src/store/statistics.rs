@@ -0,0 +1,25 @@
+use {
+    crate::{data::TableStatistics, result::Result},
+    async_trait::async_trait,
+};
+
+/// Storages without statistics have none to fetch, so queries on them are planned by rules
+/// alone.
+#[async_trait]
+pub trait Statistics: Send + Sync {
+    async fn fetch_statistics(&self, _table_name: &str) -> Result<Option<TableStatistics>> {
+        Ok(None)
+    }
+}
+
+/// Statistics only guide the planner, so storages may discard what `ANALYZE` collects.
+#[async_trait]
+pub trait StatisticsMut: Send + Sync {
+    async fn insert_statistics(&mut self, _statistics: TableStatistics) -> Result<()> {
+        Ok(())
+    }
+
+    async fn delete_statistics(&mut self, _table_name: &str) -> Result<()> {
+        Ok(())
+    }
+}
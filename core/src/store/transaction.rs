@@ -22,4 +22,33 @@ pub trait Transaction {
     async fn commit(&mut self) -> Result<()> {
         Ok(())
     }
+
+    /// Checks that the transaction can commit, so that a storage committing together with
+    /// others finds out before any of them has committed.
+    async fn prepare(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Marks the current state of the transaction as `name`, hiding an earlier savepoint of
+    /// the same name until this one is released.
+    async fn savepoint(&mut self, _name: &str) -> Result<()> {
+        Err(Error::StorageMsg(
+            "[Storage] Transaction::savepoint is not supported".to_owned(),
+        ))
+    }
+
+    /// Undoes the changes made since the savepoint `name`, which is kept while the
+    /// savepoints made after it are released.
+    async fn rollback_to_savepoint(&mut self, _name: &str) -> Result<()> {
+        Err(Error::StorageMsg(
+            "[Storage] Transaction::rollback_to_savepoint is not supported".to_owned(),
+        ))
+    }
+
+    /// Forgets the savepoint `name` and the savepoints made after it, keeping their changes.
+    async fn release_savepoint(&mut self, _name: &str) -> Result<()> {
+        Err(Error::StorageMsg(
+            "[Storage] Transaction::release_savepoint is not supported".to_owned(),
+        ))
+    }
 }
@@ -0,0 +1,31 @@
+use {
+    crate::{
+        data::Trigger as StructTrigger,
+        result::{Error, Result},
+    },
+    async_trait::async_trait,
+};
+
+/// Storages without triggers have none to fire, so rows are written as usual.
+#[async_trait]
+pub trait Trigger: Send + Sync {
+    /// Returns the triggers of the table, sorted by name which is the order they fire in.
+    async fn fetch_triggers(&self, _table_name: &str) -> Result<Vec<StructTrigger>> {
+        Ok(Vec::new())
+    }
+}
+
+#[async_trait]
+pub trait TriggerMut: Send + Sync {
+    async fn insert_trigger(&mut self, _trigger: StructTrigger) -> Result<()> {
+        Err(Error::StorageMsg(
+            "[Storage] Trigger is not supported".to_owned(),
+        ))
+    }
+
+    async fn delete_trigger(&mut self, _table_name: &str, _trigger_name: &str) -> Result<()> {
+        Err(Error::StorageMsg(
+            "[Storage] Trigger is not supported".to_owned(),
+        ))
+    }
+}
@@ -0,0 +1,34 @@
+use {
+    crate::{
+        data::View as StructView,
+        result::{Error, Result},
+    },
+    async_trait::async_trait,
+};
+
+/// Storages without views have none to fetch, so queries on them are planned as usual.
+#[async_trait]
+pub trait View: Send + Sync {
+    async fn fetch_view(&self, _view_name: &str) -> Result<Option<StructView>> {
+        Ok(None)
+    }
+
+    async fn fetch_all_views(&self) -> Result<Vec<StructView>> {
+        Ok(Vec::new())
+    }
+}
+
+#[async_trait]
+pub trait ViewMut: Send + Sync {
+    async fn insert_view(&mut self, _view: StructView) -> Result<()> {
+        Err(Error::StorageMsg(
+            "[Storage] View is not supported".to_owned(),
+        ))
+    }
+
+    async fn delete_view(&mut self, _view_name: &str) -> Result<()> {
+        Err(Error::StorageMsg(
+            "[Storage] View is not supported".to_owned(),
+        ))
+    }
+}
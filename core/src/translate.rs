@@ -17,28 +17,47 @@ pub use self::{
 
 use {
     crate::{
-        ast::{Assignment, ForeignKey, ReferentialAction, Statement, Variable},
+        ast::{
+            Assignment, CheckConstraint, ColumnDef, ColumnUniqueOption, ForeignKey, KeyConstraint,
+            OnConflict, OnConflictAction, ReferentialAction, SelectItem, Statement, TriggerEvent,
+            TriggerTiming, Variable,
+        },
         result::Result,
     },
-    ddl::translate_alter_table_operation,
+    ddl::{translate_alter_table_operation, translate_sequence_options},
     sqlparser::ast::{
         Assignment as SqlAssignment, AssignmentTarget as SqlAssignmentTarget,
-        CommentDef as SqlCommentDef, CreateFunctionBody as SqlCreateFunctionBody,
-        CreateIndex as SqlCreateIndex, CreateTable as SqlCreateTable, Delete as SqlDelete,
+        CommentDef as SqlCommentDef, ConflictTarget as SqlConflictTarget,
+        CreateFunctionBody as SqlCreateFunctionBody, CreateIndex as SqlCreateIndex,
+        CreateTable as SqlCreateTable, Delete as SqlDelete, DoUpdate as SqlDoUpdate,
         FromTable as SqlFromTable, Ident as SqlIdent, Insert as SqlInsert,
-        ObjectName as SqlObjectName, ObjectType as SqlObjectType,
-        ReferentialAction as SqlReferentialAction, Statement as SqlStatement,
-        TableConstraint as SqlTableConstraint, TableFactor, TableWithJoins,
+        ObjectName as SqlObjectName, ObjectType as SqlObjectType, OnConflict as SqlOnConflict,
+        OnConflictAction as SqlOnConflictAction, OnInsert as SqlOnInsert,
+        ReferentialAction as SqlReferentialAction, SelectItem as SqlSelectItem,
+        Statement as SqlStatement, TableConstraint as SqlTableConstraint, TableFactor,
+        TableWithJoins, TriggerEvent as SqlTriggerEvent, TriggerObject as SqlTriggerObject,
+        TriggerPeriod as SqlTriggerPeriod, ViewColumnDef as SqlViewColumnDef,
     },
 };
 
 pub fn translate(sql_statement: &SqlStatement) -> Result<Statement> {
     match sql_statement {
         SqlStatement::Query(query) => translate_query(query).map(Statement::Query),
+        SqlStatement::Explain {
+            analyze, statement, ..
+        } => match statement.as_ref() {
+            SqlStatement::Query(query) => Ok(Statement::Explain {
+                analyze: *analyze,
+                query: translate_query(query).map(Box::new)?,
+            }),
+            _ => Err(TranslateError::UnsupportedExplainStatement(statement.to_string()).into()),
+        },
         SqlStatement::Insert(SqlInsert {
             table_name,
             columns,
             source,
+            on,
+            returning,
             ..
         }) => {
             let table_name = translate_object_name(table_name)?;
@@ -49,17 +68,21 @@ pub fn translate(sql_statement: &SqlStatement) -> Result<Statement> {
                     TranslateError::DefaultValuesOnInsertNotSupported(table_name.clone()).into()
                 })
                 .and_then(translate_query)?;
+            let on_conflict = on.as_ref().map(translate_on_insert).transpose()?;
 
             Ok(Statement::Insert {
                 table_name,
                 columns,
                 source,
+                on_conflict,
+                returning: translate_returning(returning)?,
             })
         }
         SqlStatement::Update {
             table,
             assignments,
             selection,
+            returning,
             ..
         } => Ok(Statement::Update {
             table_name: translate_table_with_join(table)?,
@@ -68,9 +91,13 @@ pub fn translate(sql_statement: &SqlStatement) -> Result<Statement> {
                 .map(translate_assignment)
                 .collect::<Result<_>>()?,
             selection: selection.as_ref().map(translate_expr).transpose()?,
+            returning: translate_returning(returning)?,
         }),
         SqlStatement::Delete(SqlDelete {
-            from, selection, ..
+            from,
+            selection,
+            returning,
+            ..
         }) => {
             let from = match from {
                 SqlFromTable::WithFromKeyword(from) => from,
@@ -87,6 +114,7 @@ pub fn translate(sql_statement: &SqlStatement) -> Result<Statement> {
             Ok(Statement::Delete {
                 table_name,
                 selection: selection.as_ref().map(translate_expr).transpose()?,
+                returning: translate_returning(returning)?,
             })
         }
         SqlStatement::CreateTable(SqlCreateTable {
@@ -99,19 +127,41 @@ pub fn translate(sql_statement: &SqlStatement) -> Result<Statement> {
             comment,
             ..
         }) => {
-            let columns = columns
+            let mut columns = columns
                 .iter()
                 .map(translate_column_def)
                 .collect::<Result<Vec<_>>>()?;
 
-            let columns = (!columns.is_empty()).then_some(columns);
-
             let name = translate_object_name(name)?;
 
-            let foreign_keys = constraints
-                .iter()
+            let (key_constraints, constraints): (Vec<_>, Vec<_>) =
+                constraints.iter().partition(|constraint| {
+                    matches!(
+                        constraint,
+                        SqlTableConstraint::PrimaryKey { .. } | SqlTableConstraint::Unique { .. }
+                    )
+                });
+            let key_constraints = key_constraints
+                .into_iter()
+                .map(translate_key_constraint)
+                .collect::<Result<Vec<_>>>()?
+                .into_iter()
+                .filter_map(|key_constraint| fold_key_constraint(&mut columns, key_constraint))
+                .collect();
+
+            let columns = (!columns.is_empty()).then_some(columns);
+
+            let (checks, foreign_keys): (Vec<_>, Vec<_>) = constraints
+                .into_iter()
+                .partition(|constraint| matches!(constraint, SqlTableConstraint::Check { .. }));
+            let foreign_keys = foreign_keys
+                .into_iter()
                 .map(translate_foreign_key)
                 .collect::<Result<Vec<_>>>()?;
+            let checks = checks
+                .into_iter()
+                .map(translate_check_constraint)
+                .collect::<Result<Vec<_>>>()?;
 
             Ok(Statement::CreateTable {
                 if_not_exists: *if_not_exists,
@@ -124,7 +174,9 @@ pub fn translate(sql_statement: &SqlStatement) -> Result<Statement> {
                 engine: engine
                     .as_ref()
                     .map(|table_engine| table_engine.name.to_owned()),
+                key_constraints,
                 foreign_keys,
+                checks,
                 comment: comment.as_ref().map(|comment| match comment {
                     SqlCommentDef::WithEq(comment)
                     | SqlCommentDef::WithoutEq(comment)
@@ -178,12 +230,9 @@ pub fn translate(sql_statement: &SqlStatement) -> Result<Statement> {
             name,
             table_name,
             columns,
+            unique,
             ..
         }) => {
-            if columns.len() > 1 {
-                return Err(TranslateError::CompositeIndexNotSupported.into());
-            }
-
             let Some(name) = name else {
                 return Err(TranslateError::UnsupportedUnnamedIndex.into());
             };
@@ -197,7 +246,11 @@ pub fn translate(sql_statement: &SqlStatement) -> Result<Statement> {
             Ok(Statement::CreateIndex {
                 name,
                 table_name: translate_object_name(table_name)?,
-                column: translate_order_by_expr(&columns[0])?,
+                columns: columns
+                    .iter()
+                    .map(translate_order_by_expr)
+                    .collect::<Result<_>>()?,
+                unique: *unique,
             })
         }
         SqlStatement::Drop {
@@ -223,9 +276,97 @@ pub fn translate(sql_statement: &SqlStatement) -> Result<Statement> {
 
             Ok(Statement::DropIndex { name, table_name })
         }
+        SqlStatement::CreateView {
+            or_replace,
+            materialized,
+            name,
+            columns,
+            query,
+            ..
+        } => Ok(Statement::CreateView {
+            or_replace: *or_replace,
+            materialized: *materialized,
+            name: translate_object_name(name)?,
+            columns: columns
+                .iter()
+                .map(|SqlViewColumnDef { name, .. }| name.value.to_owned())
+                .collect(),
+            query: translate_query(query).map(Box::new)?,
+        }),
+        SqlStatement::Drop {
+            object_type: SqlObjectType::View,
+            if_exists,
+            names,
+            ..
+        } => Ok(Statement::DropView {
+            if_exists: *if_exists,
+            names: names
+                .iter()
+                .map(translate_object_name)
+                .collect::<Result<Vec<_>>>()?,
+        }),
+        SqlStatement::CreateSequence {
+            temporary: false,
+            if_not_exists,
+            name,
+            sequence_options,
+            owned_by: None,
+            ..
+        } => Ok(Statement::CreateSequence {
+            if_not_exists: *if_not_exists,
+            name: translate_object_name(name)?,
+            options: translate_sequence_options(sequence_options)?,
+        }),
+        SqlStatement::Drop {
+            object_type: SqlObjectType::Sequence,
+            if_exists,
+            names,
+            ..
+        } => Ok(Statement::DropSequence {
+            if_exists: *if_exists,
+            names: names
+                .iter()
+                .map(translate_object_name)
+                .collect::<Result<Vec<_>>>()?,
+        }),
+        // Parsed from `REFRESH MATERIALIZED VIEW`, see `parse_sql::parse`.
+        SqlStatement::OptimizeTable { name, .. } => Ok(Statement::RefreshMaterializedView {
+            name: translate_object_name(name)?,
+        }),
+        // `ANALYZE` without a table is parsed with an empty name, see `parse_sql::parse`.
+        SqlStatement::Analyze { table_name, .. } => Ok(Statement::Analyze {
+            table_name: (!table_name.0.is_empty())
+                .then(|| translate_object_name(table_name))
+                .transpose()?,
+        }),
+        // Parsed from `CREATE TRIGGER` with a body of statements, see `parse_sql::parse`.
+        SqlStatement::CreateProcedure { body, .. } => match body.as_slice() {
+            [trigger @ SqlStatement::CreateTrigger { .. }, body @ ..] => {
+                translate_create_trigger(trigger, body)
+            }
+            _ => Err(TranslateError::UnsupportedStatement(sql_statement.to_string()).into()),
+        },
+        SqlStatement::DropTrigger {
+            if_exists,
+            trigger_name,
+            table_name,
+            ..
+        } => Ok(Statement::DropTrigger {
+            if_exists: *if_exists,
+            name: translate_object_name(trigger_name)?,
+            table_name: translate_object_name(table_name)?,
+        }),
         SqlStatement::StartTransaction { .. } => Ok(Statement::StartTransaction),
         SqlStatement::Commit { .. } => Ok(Statement::Commit),
+        SqlStatement::Rollback {
+            savepoint: Some(name),
+            ..
+        } => Ok(Statement::RollbackToSavepoint(name.value.to_owned())),
         SqlStatement::Rollback { .. } => Ok(Statement::Rollback),
+        SqlStatement::Savepoint { name } => Ok(Statement::Savepoint(name.value.to_owned())),
+        SqlStatement::ReleaseSavepoint { name } => {
+            Ok(Statement::ReleaseSavepoint(name.value.to_owned()))
+        }
         SqlStatement::ShowTables {
             filter: None,
             db_name: None,
@@ -288,6 +429,113 @@ pub fn translate(sql_statement: &SqlStatement) -> Result<Statement> {
     }
 }
 
+fn translate_create_trigger(trigger: &SqlStatement, body: &[SqlStatement]) -> Result<Statement> {
+    let SqlStatement::CreateTrigger {
+        or_replace,
+        name,
+        period,
+        events,
+        table_name,
+        trigger_object,
+        condition,
+        ..
+    } = trigger
+    else {
+        return Err(TranslateError::UnsupportedStatement(trigger.to_string()).into());
+    };
+
+    let timing = match period {
+        SqlTriggerPeriod::Before => TriggerTiming::Before,
+        SqlTriggerPeriod::After => TriggerTiming::After,
+        SqlTriggerPeriod::InsteadOf => {
+            return Err(TranslateError::UnsupportedTriggerOption(period.to_string()).into());
+        }
+    };
+    let events = events
+        .iter()
+        .map(|event| match event {
+            SqlTriggerEvent::Insert => Ok(TriggerEvent::Insert),
+            SqlTriggerEvent::Update(columns) if columns.is_empty() => Ok(TriggerEvent::Update),
+            SqlTriggerEvent::Delete => Ok(TriggerEvent::Delete),
+            _ => Err(TranslateError::UnsupportedTriggerOption(event.to_string()).into()),
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    if trigger_object == &SqlTriggerObject::Statement {
+        return Err(
+            TranslateError::UnsupportedTriggerOption("FOR EACH STATEMENT".to_owned()).into(),
+        );
+    }
+
+    let body = body
+        .iter()
+        .map(|statement| match statement {
+            SqlStatement::Insert(_) | SqlStatement::Update { .. } | SqlStatement::Delete(_) => {
+                translate(statement)
+            }
+            _ => Err(TranslateError::UnsupportedTriggerStatement(statement.to_string()).into()),
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(Statement::CreateTrigger {
+        or_replace: *or_replace,
+        name: translate_object_name(name)?,
+        table_name: translate_object_name(table_name)?,
+        timing,
+        events,
+        condition: condition.as_ref().map(translate_expr).transpose()?,
+        body,
+    })
+}
+
+fn translate_on_insert(sql_on_insert: &SqlOnInsert) -> Result<OnConflict> {
+    let SqlOnConflict {
+        conflict_target,
+        action,
+    } = match sql_on_insert {
+        SqlOnInsert::OnConflict(on_conflict) => on_conflict,
+        SqlOnInsert::DuplicateKeyUpdate(_) => {
+            return Err(TranslateError::OnDuplicateKeyUpdateNotSupported.into());
+        }
+        _ => {
+            return Err(TranslateError::UnsupportedOnInsert(sql_on_insert.to_string()).into());
+        }
+    };
+
+    let target = match conflict_target {
+        Some(SqlConflictTarget::Columns(columns)) => translate_idents(columns),
+        Some(SqlConflictTarget::OnConstraint(name)) => {
+            return Err(
+                TranslateError::OnConflictOnConstraintNotSupported(name.to_string()).into(),
+            );
+        }
+        None => Vec::new(),
+    };
+    let action = match action {
+        SqlOnConflictAction::DoNothing => OnConflictAction::DoNothing,
+        SqlOnConflictAction::DoUpdate(SqlDoUpdate {
+            assignments,
+            selection,
+        }) => OnConflictAction::DoUpdate {
+            assignments: assignments
+                .iter()
+                .map(translate_assignment)
+                .collect::<Result<_>>()?,
+            selection: selection.as_ref().map(translate_expr).transpose()?,
+        },
+    };
+
+    Ok(OnConflict { target, action })
+}
+
+fn translate_returning(returning: &Option<Vec<SqlSelectItem>>) -> Result<Vec<SelectItem>> {
+    returning
+        .iter()
+        .flatten()
+        .map(translate_select_item)
+        .collect()
+}
+
 pub fn translate_assignment(sql_assignment: &SqlAssignment) -> Result<Assignment> {
     let SqlAssignment { target, value } = sql_assignment;
 
@@ -351,10 +599,13 @@ pub fn translate_referential_action(
 
     let action = action.unwrap_or(NoAction);
 
-    match action {
-        NoAction | Restrict => Ok(ReferentialAction::NoAction),
-        _ => Err(TranslateError::UnsupportedConstraint(action.to_string()).into()),
-    }
+    Ok(match action {
+        NoAction => ReferentialAction::NoAction,
+        Restrict => ReferentialAction::Restrict,
+        Cascade => ReferentialAction::Cascade,
+        SetNull => ReferentialAction::SetNull,
+        SetDefault => ReferentialAction::SetDefault,
+    })
 }
 
 pub fn translate_foreign_key(table_constraint: &SqlTableConstraint) -> Result<ForeignKey> {
@@ -406,6 +657,73 @@ pub fn translate_foreign_key(table_constraint: &SqlTableConstraint) -> Result<Fo
     }
 }
 
+pub fn translate_key_constraint(table_constraint: &SqlTableConstraint) -> Result<KeyConstraint> {
+    let (name, columns, is_primary) = match table_constraint {
+        SqlTableConstraint::PrimaryKey { name, columns, .. } => (name, columns, true),
+        SqlTableConstraint::Unique { name, columns, .. } => (name, columns, false),
+        _ => {
+            return Err(TranslateError::UnsupportedConstraint(table_constraint.to_string()).into());
+        }
+    };
+
+    Ok(KeyConstraint {
+        name: name.as_ref().map(|name| name.value.to_owned()),
+        columns: translate_idents(columns),
+        is_primary,
+    })
+}
+
+/// Primary key columns become `NOT NULL`, and a key over a single column is folded into
+/// its column definition so that only composite keys are kept as table constraints.
+fn fold_key_constraint(
+    column_defs: &mut [ColumnDef],
+    key_constraint: KeyConstraint,
+) -> Option<KeyConstraint> {
+    let KeyConstraint {
+        columns,
+        is_primary,
+        ..
+    } = &key_constraint;
+
+    for column_def in column_defs.iter_mut() {
+        if *is_primary && columns.contains(&column_def.name) {
+            column_def.nullable = false;
+        }
+    }
+
+    let column_def = match columns.as_slice() {
+        [column] => column_defs
+            .iter_mut()
+            .find(|column_def| &column_def.name == column),
+        _ => None,
+    };
+
+    match column_def {
+        Some(column_def) => {
+            if *is_primary || column_def.unique.is_none() {
+                column_def.unique = Some(ColumnUniqueOption {
+                    is_primary: *is_primary,
+                });
+            }
+
+            None
+        }
+        None => Some(key_constraint),
+    }
+}
+
+pub fn translate_check_constraint(
+    table_constraint: &SqlTableConstraint,
+) -> Result<CheckConstraint> {
+    match table_constraint {
+        SqlTableConstraint::Check { name, expr } => Ok(CheckConstraint {
+            name: name.as_ref().map(|name| name.value.to_owned()),
+            expr: translate_expr(expr)?,
+        }),
+        _ => Err(TranslateError::UnsupportedConstraint(table_constraint.to_string()).into()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use {super::*, crate::parse_sql::parse};
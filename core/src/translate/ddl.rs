@@ -3,13 +3,18 @@ use {
         TranslateError, data_type::translate_data_type, expr::translate_expr, translate_object_name,
     },
     crate::{
-        ast::{AlterTableOperation, ColumnDef, ColumnUniqueOption, OperateFunctionArg},
+        ast::{
+            AlterTableOperation, AstLiteral, CheckConstraint, ColumnDef, ColumnIdentity,
+            ColumnUniqueOption, DataType, Expr, OperateFunctionArg, SequenceOptions, UnaryOperator,
+        },
         result::Result,
     },
+    bigdecimal::{BigDecimal, ToPrimitive},
     sqlparser::ast::{
         AlterTableOperation as SqlAlterTableOperation, ColumnDef as SqlColumnDef,
         ColumnOption as SqlColumnOption, ColumnOptionDef as SqlColumnOptionDef,
-        OperateFunctionArg as SqlOperateFunctionArg,
+        DataType as SqlDataType, Expr as SqlExpr, GeneratedAs,
+        OperateFunctionArg as SqlOperateFunctionArg, SequenceOptions as SqlSequenceOptions,
     },
 };
 
@@ -19,7 +24,7 @@ pub fn translate_alter_table_operation(
     match sql_alter_table_operation {
         SqlAlterTableOperation::AddColumn { column_def, .. } => {
             Ok(AlterTableOperation::AddColumn {
-                column_def: translate_column_def(column_def)?,
+                column_def: translate_column_def(column_def).map(Box::new)?,
             })
         }
         SqlAlterTableOperation::DropColumn {
@@ -57,41 +62,166 @@ pub fn translate_column_def(sql_column_def: &SqlColumnDef) -> Result<ColumnDef>
         ..
     } = sql_column_def;
 
-    let (nullable, default, unique, comment) = options.iter().try_fold(
-        (true, None, None, None),
-        |(nullable, default, unique, comment), SqlColumnOptionDef { option, .. }| -> Result<_> {
+    let (data_type, serial) = match translate_serial(data_type) {
+        Some(data_type) => (data_type, true),
+        None => (translate_data_type(data_type)?, false),
+    };
+    let identity = serial.then(|| ColumnIdentity {
+        always: false,
+        options: SequenceOptions::default(),
+    });
+
+    let column_def = ColumnDef {
+        name: name.value.to_owned(),
+        data_type,
+        nullable: !serial,
+        default: None,
+        identity,
+        generated: None,
+        unique: None,
+        check: None,
+        comment: None,
+    };
+
+    options.iter().try_fold(
+        column_def,
+        |column_def, SqlColumnOptionDef { name, option }| -> Result<_> {
             match option {
-                SqlColumnOption::Null => Ok((nullable, default, unique, comment)),
-                SqlColumnOption::NotNull => Ok((false, default, unique, comment)),
-                SqlColumnOption::Default(default) => {
-                    let default = translate_expr(default).map(Some)?;
+                SqlColumnOption::Null => Ok(column_def),
+                SqlColumnOption::NotNull => Ok(ColumnDef {
+                    nullable: false,
+                    ..column_def
+                }),
+                SqlColumnOption::Default(default) => Ok(ColumnDef {
+                    default: translate_expr(default).map(Some)?,
+                    ..column_def
+                }),
+                SqlColumnOption::Generated {
+                    generated_as: generated_as @ (GeneratedAs::Always | GeneratedAs::ByDefault),
+                    sequence_options,
+                    generation_expr: None,
+                    ..
+                } if column_def.identity.is_none() && column_def.generated.is_none() => {
+                    let identity = Some(ColumnIdentity {
+                        always: matches!(generated_as, GeneratedAs::Always),
+                        options: translate_sequence_options(
+                            sequence_options.as_deref().unwrap_or_default(),
+                        )?,
+                    });
 
-                    Ok((nullable, default, unique, comment))
+                    Ok(ColumnDef {
+                        nullable: false,
+                        identity,
+                        ..column_def
+                    })
                 }
-                SqlColumnOption::Unique { is_primary, .. } => {
-                    let nullable = if *is_primary { false } else { nullable };
-                    let unique = Some(ColumnUniqueOption {
+                SqlColumnOption::Generated {
+                    generated_as: GeneratedAs::ExpStored,
+                    generation_expr: Some(expr),
+                    ..
+                } if column_def.identity.is_none() && column_def.generated.is_none() => {
+                    Ok(ColumnDef {
+                        generated: translate_expr(expr).map(Some)?,
+                        ..column_def
+                    })
+                }
+                SqlColumnOption::Unique { is_primary, .. } => Ok(ColumnDef {
+                    nullable: column_def.nullable && !is_primary,
+                    unique: Some(ColumnUniqueOption {
                         is_primary: *is_primary,
+                    }),
+                    ..column_def
+                }),
+                SqlColumnOption::Check(expr) if column_def.check.is_none() => {
+                    let check = Some(CheckConstraint {
+                        name: name.as_ref().map(|name| name.value.to_owned()),
+                        expr: translate_expr(expr)?,
                     });
 
-                    Ok((nullable, default, unique, comment))
-                }
-                SqlColumnOption::Comment(comment) => {
-                    Ok((nullable, default, unique, Some(comment.to_string())))
+                    Ok(ColumnDef {
+                        check,
+                        ..column_def
+                    })
                 }
+                SqlColumnOption::Comment(comment) => Ok(ColumnDef {
+                    comment: Some(comment.to_string()),
+                    ..column_def
+                }),
                 _ => Err(TranslateError::UnsupportedColumnOption(option.to_string()).into()),
             }
         },
-    )?;
+    )
+}
 
-    Ok(ColumnDef {
-        name: name.value.to_owned(),
-        data_type: translate_data_type(data_type)?,
-        nullable,
-        default,
-        unique,
-        comment,
-    })
+/// `SERIAL` types are shorthands of integer identity columns.
+fn translate_serial(data_type: &SqlDataType) -> Option<DataType> {
+    let SqlDataType::Custom(name, _) = data_type else {
+        return None;
+    };
+
+    match name.0.first()?.value.to_uppercase().as_str() {
+        "SMALLSERIAL" => Some(DataType::Int16),
+        "SERIAL" | "BIGSERIAL" => Some(DataType::Int),
+        _ => None,
+    }
+}
+
+pub fn translate_sequence_options(
+    sql_sequence_options: &[SqlSequenceOptions],
+) -> Result<SequenceOptions> {
+    sql_sequence_options.iter().try_fold(
+        SequenceOptions::default(),
+        |options, sql_sequence_option| {
+            let value = |expr: &SqlExpr| -> Result<i64> {
+                match translate_expr(expr)? {
+                    Expr::Literal(AstLiteral::Number(n)) => Some(n),
+                    Expr::UnaryOp {
+                        op: UnaryOperator::Minus,
+                        expr,
+                    } => match *expr {
+                        Expr::Literal(AstLiteral::Number(n)) => Some(-n),
+                        _ => None,
+                    },
+                    _ => None,
+                }
+                .filter(BigDecimal::is_integer)
+                .and_then(|n| n.to_i64())
+                .ok_or_else(|| {
+                    TranslateError::SequenceOptionIntegerRequired(expr.to_string()).into()
+                })
+            };
+
+            Ok(match sql_sequence_option {
+                SqlSequenceOptions::IncrementBy(expr, _) => SequenceOptions {
+                    increment: Some(value(expr)?),
+                    ..options
+                },
+                SqlSequenceOptions::MinValue(expr) => SequenceOptions {
+                    min_value: expr.as_ref().map(value).transpose()?,
+                    ..options
+                },
+                SqlSequenceOptions::MaxValue(expr) => SequenceOptions {
+                    max_value: expr.as_ref().map(value).transpose()?,
+                    ..options
+                },
+                SqlSequenceOptions::StartWith(expr, _) => SequenceOptions {
+                    start: Some(value(expr)?),
+                    ..options
+                },
+                // `Cycle(true)` is parsed from `NO CYCLE`
+                SqlSequenceOptions::Cycle(no_cycle) => SequenceOptions {
+                    cycle: !no_cycle,
+                    ..options
+                },
+                SqlSequenceOptions::Cache(_) => {
+                    return Err(TranslateError::UnsupportedSequenceOption(
+                        sql_sequence_option.to_string().trim_start().to_owned(),
+                    )
+                    .into());
+                }
+            })
+        },
+    )
 }
 
 pub fn translate_operate_function_arg(arg: &SqlOperateFunctionArg) -> Result<OperateFunctionArg> {
@@ -8,9 +8,6 @@ pub enum TranslateError {
     #[error("unimplemented - SELECT DISTINCT ON is not supported")]
     SelectDistinctOnNotSupported,
 
-    #[error("unimplemented - composite index is not supported")]
-    CompositeIndexNotSupported,
-
     #[error("unimplemented - join on update not supported")]
     JoinOnUpdateNotSupported,
 
@@ -62,6 +59,15 @@ pub enum TranslateError {
     #[error("INSERT INTO {0} DEFAULT VALUES is not supported")]
     DefaultValuesOnInsertNotSupported(String),
 
+    #[error("ON DUPLICATE KEY UPDATE is not supported, use ON CONFLICT instead")]
+    OnDuplicateKeyUpdateNotSupported,
+
+    #[error("ON CONFLICT ON CONSTRAINT is not supported: {0}")]
+    OnConflictOnConstraintNotSupported(String),
+
+    #[error("unsupported ON clause of INSERT: {0}")]
+    UnsupportedOnInsert(String),
+
     #[error("empty function body is not supported")]
     UnsupportedEmptyFunctionBody,
 
@@ -102,6 +108,18 @@ pub enum TranslateError {
     #[error("order by - NULLS (FIRST | LAST) is not supported")]
     OrderByNullsFirstOrLastNotSupported,
 
+    #[error("window function {0} requires an OVER clause")]
+    WindowFunctionRequiresOver(String),
+
+    #[error("function {0} cannot be used as a window function")]
+    UnsupportedWindowFunction(String),
+
+    #[error("named window is not supported: {0}")]
+    NamedWindowNotSupported(String),
+
+    #[error("window frame - GROUPS is not supported")]
+    WindowFrameGroupsNotSupported,
+
     #[error("unsupported SHOW VARIABLE keyword: {0}")]
     UnsupportedShowVariableKeyword(String),
 
@@ -111,6 +129,9 @@ pub enum TranslateError {
     #[error("unsupported statement: {0}")]
     UnsupportedStatement(String),
 
+    #[error("unsupported statement to explain, only queries can be explained: {0}")]
+    UnsupportedExplainStatement(String),
+
     #[error("unsupported expr: {0}")]
     UnsupportedExpr(String),
 
@@ -123,6 +144,9 @@ pub enum TranslateError {
     #[error("unsupported ast literal: {0}")]
     UnsupportedAstLiteral(String),
 
+    #[error("unsupported parameter, only $1, $2, ... or ? are supported: {0}")]
+    UnsupportedParameter(String),
+
     #[error("unreachable unary operator: {0}")]
     UnreachableUnaryOperator(String),
 
@@ -135,18 +159,33 @@ pub enum TranslateError {
     #[error("unsupported query set expr: {0}")]
     UnsupportedQuerySetExpr(String),
 
+    #[error("unsupported set quantifier: {0}")]
+    UnsupportedSetQuantifier(String),
+
+    #[error("WITH query name specified more than once: {0}")]
+    DuplicateCteName(String),
+
     #[error("unsupported query table factor: {0}")]
     UnsupportedQueryTableFactor(String),
 
-    #[error("unsupported join constraint: {0}")]
-    UnsupportedJoinConstraint(String),
-
     #[error("unsupported join operator: {0}")]
     UnsupportedJoinOperator(String),
 
     #[error("unsupported column option: {0}")]
     UnsupportedColumnOption(String),
 
+    #[error("unsupported sequence option: {0}")]
+    UnsupportedSequenceOption(String),
+
+    #[error("sequence option requires an integer: {0}")]
+    SequenceOptionIntegerRequired(String),
+
+    #[error("unsupported trigger option: {0}")]
+    UnsupportedTriggerOption(String),
+
+    #[error("trigger body only takes INSERT, UPDATE and DELETE statements: {0}")]
+    UnsupportedTriggerStatement(String),
+
     #[error("unsupported alter table operation: {0}")]
     UnsupportedAlterTableOperation(String),
 
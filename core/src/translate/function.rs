@@ -2,11 +2,14 @@ use {
     super::{
         TranslateError,
         ast_literal::{translate_datetime_field, translate_trim_where_field},
-        expr::translate_expr,
+        expr::{translate_expr, translate_order_by_expr},
         translate_data_type, translate_object_name,
     },
     crate::{
-        ast::{Aggregate, CountArgExpr, Expr, Function},
+        ast::{
+            Aggregate, AggregateFunction, CountArgExpr, Expr, Function, WindowFrame,
+            WindowFrameBound, WindowFrameUnits, WindowSpec,
+        },
         result::Result,
     },
     sqlparser::ast::{
@@ -14,7 +17,9 @@ use {
         DateTimeField as SqlDateTimeField, DuplicateTreatment as SqlDuplicateTreatment,
         Expr as SqlExpr, Function as SqlFunction, FunctionArg as SqlFunctionArg,
         FunctionArgExpr as SqlFunctionArgExpr, FunctionArguments as SqlFunctionArguments,
-        TrimWhereField as SqlTrimWhereField,
+        TrimWhereField as SqlTrimWhereField, WindowFrame as SqlWindowFrame,
+        WindowFrameBound as SqlWindowFrameBound, WindowFrameUnits as SqlWindowFrameUnits,
+        WindowSpec as SqlWindowSpec, WindowType as SqlWindowType,
     },
 };
 
@@ -195,6 +200,107 @@ pub fn translate_function_arg_exprs(
 }
 
 pub fn translate_function(sql_function: &SqlFunction) -> Result<Expr> {
+    let expr = translate_function_call(sql_function)?;
+
+    match (&sql_function.over, expr) {
+        (None, Expr::Aggregate(aggregate)) if aggregate.func.is_window_only() => {
+            Err(TranslateError::WindowFunctionRequiresOver(aggregate.func.name().to_owned()).into())
+        }
+        (None, expr) => Ok(expr),
+        (Some(over), Expr::Aggregate(aggregate)) => {
+            let over = Some(Box::new(translate_window_type(over)?));
+
+            Ok(Expr::Aggregate(Box::new(Aggregate { over, ..*aggregate })))
+        }
+        (Some(_), _) => {
+            let name = translate_object_name(&sql_function.name)?.to_uppercase();
+
+            Err(TranslateError::UnsupportedWindowFunction(name).into())
+        }
+    }
+}
+
+fn translate_window_type(sql_window_type: &SqlWindowType) -> Result<WindowSpec> {
+    let SqlWindowSpec {
+        window_name,
+        partition_by,
+        order_by,
+        window_frame,
+    } = match sql_window_type {
+        SqlWindowType::WindowSpec(sql_window_spec) => sql_window_spec,
+        SqlWindowType::NamedWindow(name) => {
+            return Err(TranslateError::NamedWindowNotSupported(name.value.clone()).into());
+        }
+    };
+
+    if let Some(name) = window_name {
+        return Err(TranslateError::NamedWindowNotSupported(name.value.clone()).into());
+    }
+
+    let partition_by = partition_by
+        .iter()
+        .map(translate_expr)
+        .collect::<Result<Vec<_>>>()?;
+    let order_by = order_by
+        .iter()
+        .map(translate_order_by_expr)
+        .collect::<Result<Vec<_>>>()?;
+    let frame = window_frame
+        .as_ref()
+        .map(translate_window_frame)
+        .transpose()?;
+
+    Ok(WindowSpec {
+        partition_by,
+        order_by,
+        frame,
+    })
+}
+
+fn translate_window_frame(sql_window_frame: &SqlWindowFrame) -> Result<WindowFrame> {
+    let SqlWindowFrame {
+        units,
+        start_bound,
+        end_bound,
+    } = sql_window_frame;
+
+    let units = match units {
+        SqlWindowFrameUnits::Rows => WindowFrameUnits::Rows,
+        SqlWindowFrameUnits::Range => WindowFrameUnits::Range,
+        SqlWindowFrameUnits::Groups => {
+            return Err(TranslateError::WindowFrameGroupsNotSupported.into());
+        }
+    };
+
+    let translate_bound = |bound: &SqlWindowFrameBound| -> Result<WindowFrameBound> {
+        let translate_offset = |offset: &Option<Box<SqlExpr>>| {
+            offset
+                .as_ref()
+                .map(|offset| translate_expr(offset))
+                .transpose()
+        };
+
+        match bound {
+            SqlWindowFrameBound::CurrentRow => Ok(WindowFrameBound::CurrentRow),
+            SqlWindowFrameBound::Preceding(offset) => {
+                translate_offset(offset).map(WindowFrameBound::Preceding)
+            }
+            SqlWindowFrameBound::Following(offset) => {
+                translate_offset(offset).map(WindowFrameBound::Following)
+            }
+        }
+    };
+
+    let start = translate_bound(start_bound)?;
+    let end = match end_bound {
+        Some(end_bound) => translate_bound(end_bound)?,
+        None => WindowFrameBound::CurrentRow,
+    };
+
+    Ok(WindowFrame { units, start, end })
+}
+
+fn translate_function_call(sql_function: &SqlFunction) -> Result<Expr> {
     let SqlFunction { name, args, .. } = sql_function;
     let name = translate_object_name(name)?.to_uppercase();
     let (args, distinct) = match args {
@@ -248,6 +354,55 @@ pub fn translate_function(sql_function: &SqlFunction) -> Result<Expr> {
         "AVG" => translate_aggregate_one_arg(Aggregate::avg, args, name, distinct),
         "VARIANCE" => translate_aggregate_one_arg(Aggregate::variance, args, name, distinct),
         "STDEV" => translate_aggregate_one_arg(Aggregate::stdev, args, name, distinct),
+        "ROW_NUMBER" | "RANK" | "DENSE_RANK" => {
+            check_len(name.clone(), args.len(), 0)?;
+
+            let func = match name.as_str() {
+                "ROW_NUMBER" => AggregateFunction::RowNumber,
+                "RANK" => AggregateFunction::Rank,
+                _ => AggregateFunction::DenseRank,
+            };
+
+            Ok(Expr::Aggregate(Box::new(Aggregate::new(func, false))))
+        }
+        "LAG" | "LEAD" => {
+            check_len_range(name.clone(), args.len(), 1, 3)?;
+
+            let expr = translate_expr(args[0])?;
+            let translate_arg = |index: usize| {
+                args.get(index)
+                    .map(|arg| translate_expr(arg).map(Box::new))
+                    .transpose()
+            };
+            let offset = translate_arg(1)?;
+            let default = translate_arg(2)?;
+            let func = match name.as_str() {
+                "LAG" => AggregateFunction::Lag {
+                    expr,
+                    offset,
+                    default,
+                },
+                _ => AggregateFunction::Lead {
+                    expr,
+                    offset,
+                    default,
+                },
+            };
+
+            Ok(Expr::Aggregate(Box::new(Aggregate::new(func, false))))
+        }
+        "FIRST_VALUE" => translate_aggregate_one_arg(
+            |expr, _| Aggregate::new(AggregateFunction::FirstValue(expr), false),
+            args,
+            name,
+            false,
+        ),
+        "LAST_VALUE" => translate_aggregate_one_arg(
+            |expr, _| Aggregate::new(AggregateFunction::LastValue(expr), false),
+            args,
+            name,
+            false,
+        ),
         "COALESCE" => {
             let exprs = args
                 .into_iter()
@@ -509,6 +664,8 @@ pub fn translate_function(sql_function: &SqlFunction) -> Result<Expr> {
         "ABS" => translate_function_one_arg(Function::Abs, args, name),
         "SIGN" => translate_function_one_arg(Function::Sign, args, name),
         "GENERATE_UUID" => translate_function_zero_arg(Function::GenerateUuid(), args, name),
+        "NEXTVAL" => translate_function_one_arg(Function::NextVal, args, name),
+        "CURRVAL" => translate_function_one_arg(Function::CurrVal, args, name),
         "FORMAT" => {
             check_len(name, args.len(), 2)?;
 
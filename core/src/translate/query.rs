@@ -5,23 +5,27 @@ use {
     },
     crate::{
         ast::{
-            AstLiteral, Dictionary, Expr, Join, JoinConstraint, JoinExecutor, JoinOperator, Query,
-            Select, SelectItem, SetExpr, TableAlias, TableFactor, TableWithJoins, Values,
+            AstLiteral, Cte, Dictionary, Expr, Join, JoinConstraint, JoinExecutor, JoinOperator,
+            Query, Select, SelectItem, SetExpr, SetOperator, TableAlias, TableFactor,
+            TableWithJoins, Values, With,
         },
         result::Result,
     },
     sqlparser::ast::{
-        Distinct as SqlDistinct, Expr as SqlExpr, FunctionArg as SqlFunctionArg,
+        Cte as SqlCte, Distinct as SqlDistinct, Expr as SqlExpr, FunctionArg as SqlFunctionArg,
         GroupByExpr as SqlGroupByExpr, Join as SqlJoin, JoinConstraint as SqlJoinConstraint,
         JoinOperator as SqlJoinOperator, Query as SqlQuery, Select as SqlSelect,
-        SelectItem as SqlSelectItem, SetExpr as SqlSetExpr, TableAlias as SqlTableAlias,
+        SelectItem as SqlSelectItem, SetExpr as SqlSetExpr, SetOperator as SqlSetOperator,
+        SetQuantifier as SqlSetQuantifier, TableAlias as SqlTableAlias,
         TableFactor as SqlTableFactor, TableFunctionArgs as SqlTableFunctionArgs,
-        TableWithJoins as SqlTableWithJoins,
+        TableWithJoins as SqlTableWithJoins, With as SqlWith,
     },
+    std::collections::HashSet,
 };
 
 pub fn translate_query(sql_query: &SqlQuery) -> Result<Query> {
     let SqlQuery {
+        with,
         body,
         order_by,
         limit,
@@ -29,6 +33,7 @@ pub fn translate_query(sql_query: &SqlQuery) -> Result<Query> {
         ..
     } = sql_query;
 
+    let with = with.as_ref().map(translate_with).transpose()?;
     let body = translate_set_expr(body)?;
     let order_by = order_by
         .iter()
@@ -41,6 +46,7 @@ pub fn translate_query(sql_query: &SqlQuery) -> Result<Query> {
         .transpose()?;
 
     Ok(Query {
+        with,
         body,
         order_by,
         limit,
@@ -48,6 +54,37 @@ pub fn translate_query(sql_query: &SqlQuery) -> Result<Query> {
     })
 }
 
+fn translate_with(sql_with: &SqlWith) -> Result<With> {
+    let SqlWith {
+        recursive,
+        cte_tables,
+    } = sql_with;
+
+    let mut names = HashSet::new();
+    let cte_tables = cte_tables
+        .iter()
+        .map(|SqlCte { alias, query, .. }| {
+            let name = alias.name.value.to_owned();
+            if !names.insert(name.clone()) {
+                return Err(TranslateError::DuplicateCteName(name).into());
+            }
+
+            Ok(Cte {
+                alias: TableAlias {
+                    name,
+                    columns: translate_idents(&alias.columns),
+                },
+                query: translate_query(query)?,
+            })
+        })
+        .collect::<Result<_>>()?;
+
+    Ok(With {
+        recursive: *recursive,
+        cte_tables,
+    })
+}
+
 fn translate_set_expr(sql_set_expr: &SqlSetExpr) -> Result<SetExpr> {
     match sql_set_expr {
         SqlSetExpr::Select(select) => translate_select(select).map(Box::new).map(SetExpr::Select),
@@ -57,10 +94,54 @@ fn translate_set_expr(sql_set_expr: &SqlSetExpr) -> Result<SetExpr> {
             .collect::<Result<_>>()
             .map(Values)
             .map(SetExpr::Values),
+        SqlSetExpr::SetOperation {
+            op,
+            set_quantifier,
+            left,
+            right,
+        } => {
+            let op = match op {
+                SqlSetOperator::Union => SetOperator::Union,
+                SqlSetOperator::Except => SetOperator::Except,
+                SqlSetOperator::Intersect => SetOperator::Intersect,
+            };
+            let all = match set_quantifier {
+                SqlSetQuantifier::All => true,
+                SqlSetQuantifier::Distinct | SqlSetQuantifier::None => false,
+                SqlSetQuantifier::ByName
+                | SqlSetQuantifier::AllByName
+                | SqlSetQuantifier::DistinctByName => {
+                    return Err(TranslateError::UnsupportedSetQuantifier(
+                        set_quantifier.to_string(),
+                    )
+                    .into());
+                }
+            };
+
+            Ok(SetExpr::SetOperation {
+                op,
+                all,
+                left: translate_set_operand(left).map(Box::new)?,
+                right: translate_set_operand(right).map(Box::new)?,
+            })
+        }
         _ => Err(TranslateError::UnsupportedQuerySetExpr(sql_set_expr.to_string()).into()),
     }
 }
 
+fn translate_set_operand(sql_set_expr: &SqlSetExpr) -> Result<Query> {
+    match sql_set_expr {
+        SqlSetExpr::Query(query) => translate_query(query),
+        _ => translate_set_expr(sql_set_expr).map(|body| Query {
+            with: None,
+            body,
+            order_by: Vec::new(),
+            limit: None,
+            offset: None,
+        }),
+    }
+}
+
 fn translate_select(sql_select: &SqlSelect) -> Result<Select> {
     let SqlSelect {
         projection,
@@ -255,13 +336,9 @@ fn translate_join(sql_join: &SqlJoin) -> Result<Join> {
 
     let translate_constraint = |sql_join_constraint: &SqlJoinConstraint| match sql_join_constraint {
         SqlJoinConstraint::On(expr) => translate_expr(expr).map(JoinConstraint::On),
+        SqlJoinConstraint::Using(idents) => Ok(JoinConstraint::Using(translate_idents(idents))),
+        SqlJoinConstraint::Natural => Ok(JoinConstraint::Natural),
         SqlJoinConstraint::None => Ok(JoinConstraint::None),
-        SqlJoinConstraint::Using(_) => {
-            Err(TranslateError::UnsupportedJoinConstraint("USING".to_owned()).into())
-        }
-        SqlJoinConstraint::Natural => {
-            Err(TranslateError::UnsupportedJoinConstraint("NATURAL".to_owned()).into())
-        }
     };
 
     let join_operator = match sql_join_operator {
@@ -271,6 +348,13 @@ fn translate_join(sql_join: &SqlJoin) -> Result<Join> {
         SqlJoinOperator::LeftOuter(sql_join_constraint) => {
             translate_constraint(sql_join_constraint).map(JoinOperator::LeftOuter)
         }
+        SqlJoinOperator::RightOuter(sql_join_constraint) => {
+            translate_constraint(sql_join_constraint).map(JoinOperator::RightOuter)
+        }
+        SqlJoinOperator::FullOuter(sql_join_constraint) => {
+            translate_constraint(sql_join_constraint).map(JoinOperator::FullOuter)
+        }
+        SqlJoinOperator::CrossJoin => Ok(JoinOperator::Cross),
         _ => Err(TranslateError::UnsupportedJoinOperator(format!("{sql_join_operator:?}")).into()),
     }?;
 
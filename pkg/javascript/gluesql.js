@@ -1,10 +1,14 @@
-import init, { Glue } from './dist_web/gluesql_js.js';
+import init, { Glue, RowIterator } from './dist_web/gluesql_js.js';
 
 let loaded = false;
 
 async function load(module_or_path) {
   await init(module_or_path);
 
+  RowIterator.prototype[Symbol.asyncIterator] = function () {
+    return this;
+  };
+
   loaded = true;
 }
 
@@ -1,4 +1,8 @@
-const { Glue } = require('./dist_nodejs/gluesql_js.js');
+const { Glue, RowIterator } = require('./dist_nodejs/gluesql_js.js');
+
+RowIterator.prototype[Symbol.asyncIterator] = function () {
+  return this;
+};
 
 function gluesql() {
   return new Glue();
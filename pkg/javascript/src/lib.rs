@@ -1,10 +1,17 @@
 #![cfg(target_arch = "wasm32")]
 
 mod payload;
+mod stream;
 mod utils;
 
+pub use stream::RowIterator;
+
 use {
-    gluesql_core::prelude::{execute, parse, plan, translate},
+    gluesql_core::{
+        ast::Statement,
+        error::ExecuteError,
+        prelude::{Glue as CoreGlue, parse, plan, translate},
+    },
     gluesql_memory_storage::MemoryStorage,
     js_sys::Promise,
     payload::convert,
@@ -26,13 +33,24 @@ extern "C" {
     fn debug(s: &str);
 }
 
+#[cfg(not(feature = "nodejs"))]
+type Storage = CompositeStorage;
+
+#[cfg(feature = "nodejs")]
+type Storage = MemoryStorage;
+
+/// Slot of the core [`CoreGlue`], left empty while a [`RowIterator`] or a running query has it
+type GlueCell = Rc<RefCell<Option<CoreGlue<Storage>>>>;
+
 #[wasm_bindgen]
 pub struct Glue {
-    #[cfg(not(feature = "nodejs"))]
-    storage: Rc<RefCell<Option<CompositeStorage>>>,
+    glue: GlueCell,
+}
 
-    #[cfg(feature = "nodejs")]
-    storage: Rc<RefCell<Option<MemoryStorage>>>,
+fn take_glue(cell: &GlueCell) -> Result<CoreGlue<Storage>, JsValue> {
+    cell.replace(None).ok_or_else(|| {
+        JsValue::from_str("a RowIterator of queryStream is still reading from the storage")
+    })
 }
 
 impl Default for Glue {
@@ -63,23 +81,23 @@ impl Glue {
         #[cfg(feature = "nodejs")]
         let storage = MemoryStorage::default();
 
-        let storage = Rc::new(RefCell::new(Some(storage)));
+        let glue = Rc::new(RefCell::new(Some(CoreGlue::new(storage))));
 
         debug("[GlueSQL] hello :)");
 
-        Self { storage }
+        Self { glue }
     }
 
     #[cfg(not(feature = "nodejs"))]
     #[wasm_bindgen(js_name = loadIndexedDB)]
     pub fn load_indexeddb(&mut self, namespace: Option<String>) -> Promise {
-        let cell = Rc::clone(&self.storage);
+        let cell = Rc::clone(&self.glue);
 
         future_to_promise(async move {
-            let mut storage = cell.replace(None).unwrap();
+            let mut glue = take_glue(&cell)?;
 
-            if storage.storages.contains_key("indexedDB") {
-                cell.replace(Some(storage));
+            if glue.storage.storages.contains_key("indexedDB") {
+                cell.replace(Some(glue));
 
                 return Err(JsValue::from_str("indexedDB storage is already loaded"));
             }
@@ -87,16 +105,16 @@ impl Glue {
             let idb_storage = match IdbStorage::new(namespace).await {
                 Ok(storage) => storage,
                 Err(error) => {
-                    cell.replace(Some(storage));
+                    cell.replace(Some(glue));
 
                     return Err(JsValue::from_str(&format!("{error}")));
                 }
             };
 
-            storage.push("indexedDB", idb_storage);
+            glue.storage.push("indexedDB", idb_storage);
             debug("[GlueSQL] loaded: indexedDB");
 
-            cell.replace(Some(storage));
+            cell.replace(Some(glue));
 
             Ok(JsValue::NULL)
         })
@@ -105,8 +123,9 @@ impl Glue {
     #[cfg(not(feature = "nodejs"))]
     #[wasm_bindgen(js_name = setDefaultEngine)]
     pub fn set_default_engine(&mut self, default_engine: String) -> Result<(), JsValue> {
-        let cell = Rc::clone(&self.storage);
-        let mut storage = cell.replace(None).unwrap();
+        let cell = Rc::clone(&self.glue);
+        let mut glue = take_glue(&cell)?;
+        let storage = &mut glue.storage;
 
         let result = {
             if !["memory", "localStorage", "sessionStorage", "indexedDB"]
@@ -127,40 +146,41 @@ impl Glue {
             }
         };
 
-        cell.replace(Some(storage));
+        cell.replace(Some(glue));
         result
     }
 
     pub fn query(&mut self, sql: String) -> Promise {
-        let cell = Rc::clone(&self.storage);
+        let cell = Rc::clone(&self.glue);
 
         future_to_promise(async move {
             let queries = parse(&sql).map_err(|error| JsValue::from_str(&format!("{error}")))?;
 
             let mut payloads = vec![];
-            let mut storage = cell.replace(None).unwrap();
+            let mut glue = take_glue(&cell)?;
 
             for query in queries.iter() {
                 let statement = translate(query);
                 let statement = match statement {
                     Ok(statement) => statement,
                     Err(error) => {
-                        cell.replace(Some(storage));
+                        cell.replace(Some(glue));
 
                         return Err(JsValue::from_str(&format!("{error}")));
                     }
                 };
-                let statement = plan(&storage, statement).await;
+                let statement = plan(&glue.storage, statement).await;
                 let statement = match statement {
                     Ok(statement) => statement,
                     Err(error) => {
-                        cell.replace(Some(storage));
+                        cell.replace(Some(glue));
 
                         return Err(JsValue::from_str(&format!("{error}")));
                     }
                 };
 
-                let result = execute(&mut storage, &statement)
+                let result = glue
+                    .execute_stmt(&statement)
                     .await
                     .map_err(|error| JsValue::from_str(&format!("{error}")));
 
@@ -169,16 +189,51 @@ impl Glue {
                         payloads.push(payload);
                     }
                     Err(error) => {
-                        cell.replace(Some(storage));
+                        cell.replace(Some(glue));
 
                         return Err(error);
                     }
                 };
             }
 
-            cell.replace(Some(storage));
+            cell.replace(Some(glue));
 
             Ok(convert(payloads))
         })
     }
+
+    /// Runs the single `SELECT` of `sql` and resolves to a [`RowIterator`] reading its rows
+    /// from the storage one at a time. Other queries fail until the iterator is done.
+    #[wasm_bindgen(js_name = queryStream)]
+    pub fn query_stream(&mut self, sql: String) -> Promise {
+        let cell = Rc::clone(&self.glue);
+
+        future_to_promise(async move {
+            let queries = parse(&sql).map_err(|error| JsValue::from_str(&format!("{error}")))?;
+            let [query] = queries.as_slice() else {
+                let error = ExecuteError::StreamRequiresSingleStatement(queries.len());
+
+                return Err(JsValue::from_str(&format!("{error}")));
+            };
+            let statement =
+                translate(query).map_err(|error| JsValue::from_str(&format!("{error}")))?;
+
+            let glue = take_glue(&cell)?;
+            let statement = match plan(&glue.storage, statement).await {
+                Ok(statement @ Statement::Query(_)) => statement,
+                Ok(_) => {
+                    cell.replace(Some(glue));
+
+                    return Err(JsValue::from_str("queryStream only runs SELECT statements"));
+                }
+                Err(error) => {
+                    cell.replace(Some(glue));
+
+                    return Err(JsValue::from_str(&format!("{error}")));
+                }
+            };
+
+            Ok(RowIterator::new(cell, glue, statement).into())
+        })
+    }
 }
@@ -98,6 +98,9 @@ fn convert_payload(payload: Payload) -> Json {
         Payload::StartTransaction => json!({ "type": "BEGIN" }),
         Payload::Commit => json!({ "type": "COMMIT" }),
         Payload::Rollback => json!({ "type": "ROLLBACK" }),
+        Payload::Savepoint => json!({ "type": "SAVEPOINT" }),
+        Payload::RollbackToSavepoint => json!({ "type": "ROLLBACK TO SAVEPOINT" }),
+        Payload::ReleaseSavepoint => json!({ "type": "RELEASE SAVEPOINT" }),
         Payload::ShowVariable(PayloadVariable::Version(version)) => {
             json!({
                 "type": "SHOW VERSION",
@@ -111,6 +114,18 @@ fn convert_payload(payload: Payload) -> Json {
             })
         }
         Payload::DropFunction => json!({ "type": "DROP FUNCTION" }),
+        Payload::DropTrigger => json!({ "type": "DROP TRIGGER" }),
+        Payload::Explain(plan) => json!({
+            "type": "EXPLAIN",
+            "plan": plan.to_string()
+        }),
+        Payload::DropView(num) => json!({ "type": "DROP VIEW", "affected": num }),
+        Payload::CreateSequence => json!({ "type": "CREATE SEQUENCE" }),
+        Payload::DropSequence(num) => json!({ "type": "DROP SEQUENCE", "affected": num }),
+        Payload::RefreshMaterializedView(num) => {
+            json!({ "type": "REFRESH MATERIALIZED VIEW", "affected": num })
+        }
+        Payload::Analyze(num) => json!({ "type": "ANALYZE", "affected": num }),
         Payload::ShowVariable(PayloadVariable::Functions(function_names)) => {
             json!({
                 "type": "SHOW FUNCTIONS",
@@ -0,0 +1,113 @@
+#![cfg(target_arch = "wasm32")]
+
+use {
+    crate::{GlueCell, Storage},
+    async_stream::stream,
+    futures::{
+        lock::Mutex,
+        stream::{LocalBoxStream, StreamExt},
+    },
+    gloo_utils::format::JsValueSerdeExt,
+    gluesql_core::{
+        ast::Statement,
+        prelude::{Glue as CoreGlue, Result},
+    },
+    js_sys::Promise,
+    serde_json::{Value as Json, json},
+    std::rc::Rc,
+    wasm_bindgen::prelude::*,
+    wasm_bindgen_futures::future_to_promise,
+};
+
+/// Rows of the `SELECT` run by `Glue.queryStream`, read from the storage as they are iterated.
+/// `gluesql.js` makes it an async iterable, so it can be consumed with `for await`.
+#[wasm_bindgen]
+pub struct RowIterator {
+    rows: Rc<Mutex<Option<LocalBoxStream<'static, Result<Json>>>>>,
+}
+
+/// Core glue lent to a [`RowIterator`], put back into its slot once the rows are dropped
+struct Lent {
+    cell: GlueCell,
+    glue: Option<CoreGlue<Storage>>,
+}
+
+impl Drop for Lent {
+    fn drop(&mut self) {
+        self.cell.replace(self.glue.take());
+    }
+}
+
+impl RowIterator {
+    pub(crate) fn new(cell: GlueCell, glue: CoreGlue<Storage>, statement: Statement) -> Self {
+        let rows = stream! {
+            let mut lent = Lent { cell, glue: Some(glue) };
+            let glue = lent.glue.as_mut().unwrap();
+
+            let rows = match glue.execute_stmt_stream(statement).await {
+                Ok(payload) => payload.into_json_rows(),
+                Err(error) => {
+                    yield Err(error);
+                    return;
+                }
+            };
+            let Some(rows) = rows else {
+                return;
+            };
+
+            for await row in rows {
+                yield row;
+            }
+        }
+        .boxed_local();
+
+        Self {
+            rows: Rc::new(Mutex::new(Some(rows))),
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl RowIterator {
+    /// Resolves to `{ value, done }` like the `next` of an async iterator.
+    pub fn next(&self) -> Promise {
+        let rows = Rc::clone(&self.rows);
+
+        future_to_promise(async move {
+            let mut rows = rows.lock().await;
+            let row = match rows.as_mut() {
+                Some(stream) => stream.next().await,
+                None => None,
+            };
+
+            let result = match row {
+                Some(Ok(row)) => json!({ "value": row, "done": false }),
+                Some(Err(error)) => {
+                    rows.take();
+
+                    return Err(JsValue::from_str(&format!("{error}")));
+                }
+                None => {
+                    rows.take();
+
+                    json!({ "done": true })
+                }
+            };
+
+            Ok(JsValue::from_serde(&result).unwrap())
+        })
+    }
+
+    /// Stops reading rows and gives the storage back to the `Glue`, like the `return` of an
+    /// async iterator which `for await` calls when the loop is left early.
+    #[wasm_bindgen(js_name = "return")]
+    pub fn finish(&self) -> Promise {
+        let rows = Rc::clone(&self.rows);
+
+        future_to_promise(async move {
+            rows.lock().await.take();
+
+            Ok(JsValue::from_serde(&json!({ "done": true })).unwrap())
+        })
+    }
+}
@@ -0,0 +1,78 @@
+#![cfg(target_arch = "wasm32")]
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+use {
+    gloo_utils::format::JsValueSerdeExt,
+    gluesql_js::Glue,
+    js_sys::{Function, Promise, Reflect},
+    serde_json::{Value as Json, json},
+    wasm_bindgen::{JsCast, prelude::JsValue},
+    wasm_bindgen_futures::JsFuture,
+    wasm_bindgen_test::*,
+};
+
+async fn call(iterator: &JsValue, method: &str) -> Result<Json, JsValue> {
+    let method: Function = Reflect::get(iterator, &JsValue::from_str(method))?.unchecked_into();
+    let promise: Promise = method.call0(iterator)?.unchecked_into();
+
+    Ok(JsFuture::from(promise).await?.into_serde().unwrap())
+}
+
+#[wasm_bindgen_test]
+async fn query_stream() {
+    let mut glue = Glue::new();
+
+    JsFuture::from(
+        glue.query(
+            "
+        CREATE TABLE Foo (id INTEGER, name TEXT);
+        INSERT INTO Foo VALUES (1, 'a'), (2, 'b');
+        "
+            .to_owned(),
+        ),
+    )
+    .await
+    .unwrap();
+
+    let rows = JsFuture::from(glue.query_stream("SELECT * FROM Foo ORDER BY id".to_owned()))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        call(&rows, "next").await,
+        Ok(json!({ "value": { "id": 1, "name": "a" }, "done": false }))
+    );
+    assert_eq!(
+        JsFuture::from(glue.query("SELECT * FROM Foo".to_owned())).await,
+        Err(JsValue::from_str(
+            "a RowIterator of queryStream is still reading from the storage"
+        ))
+    );
+    assert_eq!(
+        call(&rows, "next").await,
+        Ok(json!({ "value": { "id": 2, "name": "b" }, "done": false }))
+    );
+    assert_eq!(call(&rows, "next").await, Ok(json!({ "done": true })));
+
+    let rows = JsFuture::from(glue.query_stream("SELECT * FROM Foo".to_owned()))
+        .await
+        .unwrap();
+    call(&rows, "next").await.unwrap();
+    assert_eq!(call(&rows, "return").await, Ok(json!({ "done": true })));
+
+    let actual: Json = JsFuture::from(glue.query("SELECT COUNT(*) AS cnt FROM Foo".to_owned()))
+        .await
+        .unwrap()
+        .into_serde()
+        .unwrap();
+    assert_eq!(
+        actual,
+        json!([{ "type": "SELECT", "rows": [{ "cnt": 2 }] }])
+    );
+
+    assert_eq!(
+        JsFuture::from(glue.query_stream("DELETE FROM Foo".to_owned())).await,
+        Err(JsValue::from_str("queryStream only runs SELECT statements"))
+    );
+}
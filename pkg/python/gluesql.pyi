@@ -3,11 +3,20 @@ Type hints for Native Rust Extension
 """
 
 from abc import ABCMeta
-from typing import Any, Final
+from typing import Any, Final, Iterator
 
 class Glue:
     def __init__(self, storage: "Storage") -> None: ...
     def query(sql: str): ...
+    def query_stream(sql: str) -> "RowIterator": ...
+
+class RowIterator(Iterator[dict]):
+    """
+    Rows of a single SELECT, read from the storage one at a time.
+    The Glue rejects other queries until the iterator is exhausted or dropped.
+    """
+    def __iter__(self) -> "RowIterator": ...
+    def __next__(self) -> dict: ...
 
 class Storage(metaclass=ABCMeta):
     pass
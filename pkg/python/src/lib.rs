@@ -2,75 +2,92 @@
 
 use {
     error::GlueSQLError,
+    futures::lock::{Mutex, MutexGuard},
     gluesql_core::{
         ast::Statement,
-        prelude::{Payload, execute, parse, plan},
+        error::ExecuteError,
+        prelude::{Payload, parse, plan},
         translate::translate,
     },
     payload::{PyPayload, convert},
     pyo3::{prelude::*, types::PyString},
+    std::sync::Arc,
     storages::{
-        PyJsonStorage, PyMemoryStorage, PySharedMemoryStorage, PySledStorage, PySledStorageConfig,
-        PySledStorageModeConfig, PyStorageEngine,
+        GlueEngine, PyJsonStorage, PyMemoryStorage, PySharedMemoryStorage, PySledStorage,
+        PySledStorageConfig, PySledStorageModeConfig, PyStorageEngine,
     },
+    stream::PyRowIterator,
 };
 
 mod error;
 mod payload;
 mod storages;
+mod stream;
 
 #[pyclass(name = "Glue")]
 pub struct PyGlue {
-    pub storage: PyStorageEngine,
+    engine: Arc<Mutex<GlueEngine>>,
 }
 
 macro_rules! plan {
-    ($storage:expr, $statement:expr) => {{
-        plan(&$storage.0, $statement)
+    ($glue:expr, $statement:expr) => {{
+        plan(&$glue.storage, $statement)
             .await
             .map_err(|e| GlueSQLError::new_err(e.to_string()))
     }};
 }
 
 macro_rules! execute {
-    ($storage:expr, $statement:expr) => {{
-        execute(&mut $storage.0, $statement)
+    ($glue:expr, $statement:expr) => {{
+        $glue
+            .execute_stmt($statement)
             .await
             .map_err(|e| GlueSQLError::new_err(e.to_string()))
     }};
 }
 
 impl PyGlue {
+    /// Locks the storage, which fails while a [`PyRowIterator`] is still reading from it.
+    fn engine(&self) -> PyResult<MutexGuard<'_, GlueEngine>> {
+        self.engine.try_lock().ok_or_else(stream_open)
+    }
+
     #[tokio::main]
     pub async fn plan(&self, statement: Statement) -> PyResult<Statement> {
-        let storage = &self.storage;
+        let engine = self.engine()?;
 
-        match storage {
-            PyStorageEngine::Memory(storage) => plan!(storage, statement),
-            PyStorageEngine::Json(storage) => plan!(storage, statement),
-            PyStorageEngine::SharedMemory(storage) => plan!(storage, statement),
-            PyStorageEngine::Sled(storage) => plan!(storage, statement),
+        match &*engine {
+            GlueEngine::Memory(glue) => plan!(glue, statement),
+            GlueEngine::Json(glue) => plan!(glue, statement),
+            GlueEngine::SharedMemory(glue) => plan!(glue, statement),
+            GlueEngine::Sled(glue) => plan!(glue, statement),
         }
     }
 
     #[tokio::main]
     pub async fn execute(&mut self, statement: Statement) -> PyResult<Payload> {
-        let storage = &mut self.storage;
+        let mut engine = self.engine()?;
 
-        match storage {
-            PyStorageEngine::Memory(storage) => execute!(storage, &statement),
-            PyStorageEngine::Json(storage) => execute!(storage, &statement),
-            PyStorageEngine::SharedMemory(storage) => execute!(storage, &statement),
-            PyStorageEngine::Sled(storage) => execute!(storage, &statement),
+        match &mut *engine {
+            GlueEngine::Memory(glue) => execute!(glue, &statement),
+            GlueEngine::Json(glue) => execute!(glue, &statement),
+            GlueEngine::SharedMemory(glue) => execute!(glue, &statement),
+            GlueEngine::Sled(glue) => execute!(glue, &statement),
         }
     }
 }
 
+fn stream_open() -> PyErr {
+    GlueSQLError::new_err("a RowIterator of query_stream is still reading from the storage")
+}
+
 #[pymethods]
 impl PyGlue {
     #[new]
     pub fn new(storage: PyStorageEngine) -> Self {
-        PyGlue { storage }
+        let engine = Arc::new(Mutex::new(storage.into()));
+
+        PyGlue { engine }
     }
 
     pub fn query(&mut self, py: Python, sql: &PyString) -> PyResult<PyObject> {
@@ -89,11 +106,36 @@ impl PyGlue {
 
         Ok(convert(py, payloads))
     }
+
+    /// Runs the single `SELECT` of `sql` and returns an iterator reading its rows from the
+    /// storage one at a time. Other queries fail until the iterator is exhausted or dropped.
+    pub fn query_stream(&mut self, sql: &PyString) -> PyResult<PyRowIterator> {
+        let sql = sql.to_string();
+        let queries = parse(sql).map_err(|e| GlueSQLError::new_err(e.to_string()))?;
+        let [query] = queries.as_slice() else {
+            let error = ExecuteError::StreamRequiresSingleStatement(queries.len());
+
+            return Err(GlueSQLError::new_err(error.to_string()));
+        };
+
+        let statement = translate(query).map_err(|e| GlueSQLError::new_err(e.to_string()))?;
+        let statement = self.plan(statement)?;
+        if !matches!(statement, Statement::Query(_)) {
+            return Err(GlueSQLError::new_err(
+                "query_stream only runs SELECT statements",
+            ));
+        }
+
+        let engine = self.engine.try_lock_owned().ok_or_else(stream_open)?;
+
+        Ok(PyRowIterator::new(engine, statement))
+    }
 }
 
 #[pymodule]
 fn gluesql(py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<PyGlue>()?;
+    m.add_class::<PyRowIterator>()?;
     m.add_class::<PyMemoryStorage>()?;
     m.add_class::<PyJsonStorage>()?;
     m.add_class::<PySharedMemoryStorage>()?;
@@ -1,4 +1,5 @@
 use {
+    gluesql_core::prelude::Glue,
     gluesql_json_storage::JsonStorage,
     gluesql_memory_storage::MemoryStorage,
     gluesql_shared_memory_storage::SharedMemoryStorage,
@@ -15,6 +16,27 @@ pub enum PyStorageEngine {
     Sled(PySledStorage),
 }
 
+/// [`Glue`] running on the storage a `PyGlue` was created with
+pub enum GlueEngine {
+    Memory(Glue<MemoryStorage>),
+    Json(Glue<JsonStorage>),
+    SharedMemory(Glue<SharedMemoryStorage>),
+    Sled(Glue<SledStorage>),
+}
+
+impl From<PyStorageEngine> for GlueEngine {
+    fn from(storage: PyStorageEngine) -> Self {
+        match storage {
+            PyStorageEngine::Memory(storage) => GlueEngine::Memory(Glue::new(storage.0)),
+            PyStorageEngine::Json(storage) => GlueEngine::Json(Glue::new(storage.0)),
+            PyStorageEngine::SharedMemory(storage) => {
+                GlueEngine::SharedMemory(Glue::new(storage.0))
+            }
+            PyStorageEngine::Sled(storage) => GlueEngine::Sled(Glue::new(storage.0)),
+        }
+    }
+}
+
 #[pyclass(name = "MemoryStorage")]
 #[derive(Clone, Default)]
 pub struct PyMemoryStorage(pub MemoryStorage);
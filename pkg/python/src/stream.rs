@@ -0,0 +1,71 @@
+use {
+    crate::{error::GlueSQLError, storages::GlueEngine},
+    async_stream::stream,
+    futures::{
+        lock::OwnedMutexGuard,
+        stream::{BoxStream, StreamExt},
+    },
+    gluesql_core::{
+        ast::Statement,
+        prelude::{PayloadStream, Result},
+    },
+    pyo3::prelude::*,
+    pythonize::pythonize,
+    serde_json::Value as Json,
+};
+
+/// Rows of the `SELECT` run by `Glue.query_stream`, read from the storage as they are iterated
+#[pyclass(name = "RowIterator")]
+pub struct PyRowIterator {
+    rows: BoxStream<'static, Result<Json>>,
+}
+
+impl PyRowIterator {
+    /// Streams `statement` from the storage of `engine`, which stays locked until the last row
+    /// has been read or the iterator is dropped.
+    pub fn new(mut engine: OwnedMutexGuard<GlueEngine>, statement: Statement) -> Self {
+        let rows = stream! {
+            let payload = match &mut *engine {
+                GlueEngine::Memory(glue) => glue.execute_stmt_stream(statement).await,
+                GlueEngine::Json(glue) => glue.execute_stmt_stream(statement).await,
+                GlueEngine::SharedMemory(glue) => glue.execute_stmt_stream(statement).await,
+                GlueEngine::Sled(glue) => glue.execute_stmt_stream(statement).await,
+            };
+            let rows = match payload.map(PayloadStream::into_json_rows) {
+                Ok(Some(rows)) => rows,
+                Ok(None) => return,
+                Err(error) => {
+                    yield Err(error);
+                    return;
+                }
+            };
+
+            for await row in rows {
+                yield row;
+            }
+        }
+        .boxed();
+
+        Self { rows }
+    }
+
+    #[tokio::main]
+    async fn next_row(&mut self) -> Option<Result<Json>> {
+        self.rows.next().await
+    }
+}
+
+#[pymethods]
+impl PyRowIterator {
+    pub fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    pub fn __next__(&mut self, py: Python) -> PyResult<Option<PyObject>> {
+        match self.next_row() {
+            Some(Ok(row)) => Ok(Some(pythonize(py, &row).unwrap())),
+            Some(Err(e)) => Err(GlueSQLError::new_err(e.to_string())),
+            None => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,33 @@
+import pytest
+from gluesql import Glue, GlueSQLError, MemoryStorage
+
+
+def test_query_stream():
+    db = Glue(MemoryStorage())
+    db.query(
+        """
+    CREATE TABLE Foo (id INTEGER, name TEXT);
+    INSERT INTO Foo VALUES (1, 'a'), (2, 'b'), (3, 'c');
+    """
+    )
+
+    rows = db.query_stream("SELECT * FROM Foo ORDER BY id")
+    assert next(rows) == {"id": 1, "name": "a"}
+
+    with pytest.raises(GlueSQLError):
+        db.query("SELECT * FROM Foo")
+
+    assert list(rows) == [{"id": 2, "name": "b"}, {"id": 3, "name": "c"}]
+    assert db.query("SELECT COUNT(*) AS cnt FROM Foo") == [
+        {"type": "SELECT", "rows": [{"cnt": 3}]}
+    ]
+
+
+def test_query_stream_rejects_other_statements():
+    db = Glue(MemoryStorage())
+
+    with pytest.raises(GlueSQLError):
+        db.query_stream("CREATE TABLE Foo (id INTEGER)")
+
+    with pytest.raises(GlueSQLError):
+        db.query_stream("SELECT 1; SELECT 2")
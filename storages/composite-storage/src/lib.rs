@@ -11,6 +11,7 @@ use {
         store::{GStore, GStoreMut, Metadata, Store},
     },
     std::collections::HashMap,
+    transaction::TransactionState,
 };
 
 pub trait IStorage: GStore + GStoreMut {}
@@ -21,6 +22,7 @@ impl<T: GStore + GStoreMut> IStorage for T {}
 pub struct CompositeStorage {
     pub storages: HashMap<String, Box<dyn IStorage>>,
     pub default_engine: Option<String>,
+    transaction: TransactionState,
 }
 
 impl CompositeStorage {
@@ -92,3 +94,11 @@ impl gluesql_core::store::IndexMut for CompositeStorage {}
 impl Metadata for CompositeStorage {}
 impl gluesql_core::store::CustomFunction for CompositeStorage {}
 impl gluesql_core::store::CustomFunctionMut for CompositeStorage {}
+impl gluesql_core::store::View for CompositeStorage {}
+impl gluesql_core::store::ViewMut for CompositeStorage {}
+impl gluesql_core::store::Statistics for CompositeStorage {}
+impl gluesql_core::store::StatisticsMut for CompositeStorage {}
+impl gluesql_core::store::Sequence for CompositeStorage {}
+impl gluesql_core::store::SequenceMut for CompositeStorage {}
+impl gluesql_core::store::Trigger for CompositeStorage {}
+impl gluesql_core::store::TriggerMut for CompositeStorage {}
@@ -5,7 +5,7 @@ use {
     gluesql_core::{
         data::{Key, Schema},
         error::Result,
-        store::{DataRow, RowIter, Store},
+        store::{DataRow, RowIter, ScanFilter, Store},
     },
 };
 
@@ -49,4 +49,26 @@ impl Store for CompositeStorage {
             .scan_data(table_name)
             .await
     }
+
+    async fn scan_data_filtered<'a>(
+        &'a self,
+        table_name: &str,
+        filter: &ScanFilter,
+    ) -> Result<RowIter<'a>> {
+        self.fetch_storage(table_name)
+            .await?
+            .scan_data_filtered(table_name, filter)
+            .await
+    }
+
+    async fn scan_data_by_prefix<'a>(
+        &'a self,
+        table_name: &str,
+        prefix: &[Key],
+    ) -> Result<RowIter<'a>> {
+        self.fetch_storage(table_name)
+            .await?
+            .scan_data_by_prefix(table_name, prefix)
+            .await
+    }
 }
@@ -7,33 +7,218 @@ use {
     },
 };
 
+/// Transaction of a [`CompositeStorage`], begun in each of the engines it names
+#[derive(Debug, Default)]
+pub enum TransactionState {
+    #[default]
+    None,
+    Active {
+        autocommit: bool,
+        engines: Vec<String>,
+    },
+}
+
+impl CompositeStorage {
+    fn engines(&self) -> Vec<String> {
+        let mut engines = self.storages.keys().cloned().collect::<Vec<_>>();
+        engines.sort();
+
+        engines
+    }
+
+    fn take_engines(&mut self) -> Vec<String> {
+        match std::mem::take(&mut self.transaction) {
+            TransactionState::Active { engines, .. } => engines,
+            TransactionState::None => Vec::new(),
+        }
+    }
+
+    /// Rolls back `engines`, going through all of them before returning the first error.
+    async fn rollback_engines(&mut self, engines: &[String]) -> Result<()> {
+        let mut result = Ok(());
+
+        for engine in engines {
+            let Some(storage) = self.storages.get_mut(engine) else {
+                continue;
+            };
+
+            let rolled_back = storage.rollback().await;
+            result = result.and(rolled_back);
+        }
+
+        result
+    }
+}
+
 #[async_trait]
 impl Transaction for CompositeStorage {
     async fn begin(&mut self, autocommit: bool) -> Result<bool> {
-        if autocommit {
-            for storage in self.storages.values_mut() {
-                storage.begin(autocommit).await?;
+        match (&self.transaction, autocommit) {
+            (TransactionState::Active { .. }, true) => return Ok(false),
+            (TransactionState::Active { .. }, false) => {
+                return Err(Error::StorageMsg(
+                    "[CompositeStorage] nested transaction is not supported".to_owned(),
+                ));
             }
+            (TransactionState::None, _) => {}
+        }
+
+        let mut engines = Vec::new();
+
+        for engine in self.engines() {
+            let Some(storage) = self.storages.get_mut(&engine) else {
+                continue;
+            };
+
+            // A statement run in autocommit mode begins an explicit transaction in each engine
+            // supporting one, so that it is undone in all of them when it fails.
+            let begun = match autocommit {
+                true => match storage.begin(false).await {
+                    Ok(_) => Ok(true),
+                    Err(_) => storage.begin(true).await,
+                },
+                false => storage.begin(false).await,
+            };
+
+            match begun {
+                Ok(begun) => {
+                    if begun || !autocommit {
+                        engines.push(engine);
+                    }
+                }
+                Err(error) => {
+                    self.rollback_engines(&engines).await?;
+
+                    if autocommit {
+                        return Err(error);
+                    }
+
+                    return Err(Error::StorageMsg(format!(
+                        "[CompositeStorage] engine {engine} does not support transactions: {error}"
+                    )));
+                }
+            }
+        }
 
-            return Ok(true);
+        if autocommit && engines.is_empty() {
+            return Ok(false);
         }
 
-        Err(Error::StorageMsg(
-            "[CompositeStorage] Transaction::begin is not supported".to_owned(),
-        ))
+        self.transaction = TransactionState::Active {
+            autocommit,
+            engines,
+        };
+
+        Ok(autocommit)
     }
 
     async fn rollback(&mut self) -> Result<()> {
-        for storage in self.storages.values_mut() {
-            storage.commit().await?;
+        let engines = self.take_engines();
+
+        self.rollback_engines(&engines).await
+    }
+
+    /// Prepares each engine of the transaction before committing any of them, rolling all of
+    /// them back when one fails to prepare.
+    async fn commit(&mut self) -> Result<()> {
+        if let Err(error) = self.prepare().await {
+            self.rollback().await?;
+
+            return Err(error);
+        }
+
+        let engines = self.take_engines();
+
+        for (i, engine) in engines.iter().enumerate() {
+            let Some(storage) = self.storages.get_mut(engine) else {
+                continue;
+            };
+
+            if let Err(error) = storage.commit().await {
+                self.rollback_engines(&engines[i..]).await?;
+
+                return Err(match i {
+                    0 => error,
+                    _ => Error::StorageMsg(format!(
+                        "[CompositeStorage] engine {engine} failed to commit after {} committed: {error}",
+                        engines[..i].join(", ")
+                    )),
+                });
+            }
         }
 
         Ok(())
     }
 
-    async fn commit(&mut self) -> Result<()> {
-        for storage in self.storages.values_mut() {
-            storage.commit().await?;
+    async fn prepare(&mut self) -> Result<()> {
+        let TransactionState::Active { engines, .. } = &self.transaction else {
+            return Ok(());
+        };
+
+        for engine in engines.clone() {
+            if let Some(storage) = self.storages.get_mut(&engine) {
+                storage.prepare().await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn savepoint(&mut self, name: &str) -> Result<()> {
+        let engines = match &self.transaction {
+            TransactionState::Active {
+                autocommit: false,
+                engines,
+            } => engines.clone(),
+            _ => {
+                return Err(Error::StorageMsg(
+                    "[CompositeStorage] savepoint requires a transaction".to_owned(),
+                ));
+            }
+        };
+
+        for (i, engine) in engines.iter().enumerate() {
+            let Some(storage) = self.storages.get_mut(engine) else {
+                continue;
+            };
+
+            if let Err(error) = storage.savepoint(name).await {
+                for engine in engines[..i].iter() {
+                    if let Some(storage) = self.storages.get_mut(engine) {
+                        storage.release_savepoint(name).await?;
+                    }
+                }
+
+                return Err(error);
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn rollback_to_savepoint(&mut self, name: &str) -> Result<()> {
+        let TransactionState::Active { engines, .. } = &self.transaction else {
+            return Err(Error::StorageMsg(format!("savepoint not found: {name}")));
+        };
+
+        for engine in engines.clone() {
+            if let Some(storage) = self.storages.get_mut(&engine) {
+                storage.rollback_to_savepoint(name).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn release_savepoint(&mut self, name: &str) -> Result<()> {
+        let TransactionState::Active { engines, .. } = &self.transaction else {
+            return Err(Error::StorageMsg(format!("savepoint not found: {name}")));
+        };
+
+        for engine in engines.clone() {
+            if let Some(storage) = self.storages.get_mut(&engine) {
+                storage.release_savepoint(name).await?;
+            }
         }
 
         Ok(())
@@ -0,0 +1,66 @@
+use {
+    gluesql_composite_storage::CompositeStorage,
+    gluesql_core::{
+        error::{FetchError, ValueError},
+        prelude::{Error, Glue, Payload, Value::I64},
+    },
+    gluesql_json_storage::JsonStorage,
+    gluesql_memory_storage::MemoryStorage,
+    std::fs,
+    test_suite::*,
+};
+
+#[tokio::test]
+async fn memory_and_json() {
+    let memory_storage = MemoryStorage::default();
+    let json_storage = {
+        let path = "data/memory_and_json";
+        fs::remove_dir_all(path).unwrap_or(());
+
+        JsonStorage::new(path).unwrap()
+    };
+
+    let mut storage = CompositeStorage::new();
+    storage.push("MEMORY", memory_storage);
+    storage.push("JSON", json_storage);
+    storage.set_default("MEMORY");
+
+    let mut glue = Glue::new(storage);
+
+    glue.execute("CREATE TABLE Foo (foo_id INTEGER) ENGINE = MEMORY;")
+        .await
+        .unwrap();
+    glue.execute("CREATE TABLE Bar (bar_id INTEGER) ENGINE = JSON;")
+        .await
+        .unwrap();
+    glue.execute("INSERT INTO Foo VALUES (1);").await.unwrap();
+    glue.execute("INSERT INTO Bar VALUES (10);").await.unwrap();
+
+    assert_eq!(
+        glue.execute("BEGIN;").await.unwrap_err(),
+        Error::StorageMsg(
+            "[CompositeStorage] engine JSON does not support transactions: storage: [Storage] Transaction::begin is not supported"
+                .to_owned()
+        ),
+    );
+
+    glue.execute("INSERT INTO Foo VALUES (2);").await.unwrap();
+    assert_eq!(
+        glue.execute("SELECT foo_id FROM Foo;").await.unwrap()[0],
+        select!(foo_id I64; 1; 2),
+        "statements outside of a transaction still run in each engine"
+    );
+    assert_eq!(glue.execute("ROLLBACK;").await, Ok(vec![Payload::Rollback]),);
+
+    assert_eq!(
+        glue.execute("CREATE TABLE Baz AS SELECT 10 / (foo_id - 2) AS v FROM Foo;")
+            .await
+            .unwrap_err(),
+        ValueError::DivisorShouldNotBeZero.into(),
+    );
+    assert_eq!(
+        glue.execute("SELECT * FROM Baz;").await.unwrap_err(),
+        FetchError::TableNotFound("Baz".to_owned()).into(),
+        "engines with transactions undo a failing statement even when others have none"
+    );
+}
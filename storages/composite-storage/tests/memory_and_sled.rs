@@ -1,6 +1,10 @@
 use {
     gluesql_composite_storage::CompositeStorage,
-    gluesql_core::prelude::{Error, Glue, Value::I64},
+    gluesql_core::{
+        error::{FetchError, ValueError},
+        prelude::{Error, Glue, Value::I64},
+        store::Transaction,
+    },
     gluesql_memory_storage::MemoryStorage,
     gluesql_sled_storage::SledStorage,
     std::fs,
@@ -10,12 +14,17 @@ use {
 #[tokio::test]
 async fn memory_and_sled() {
     let memory_storage = MemoryStorage::default();
-    let sled_storage = {
+    let mut sled_storage = {
         let path = "data/memory_and_sled";
         fs::remove_dir_all(path).unwrap_or(());
 
         SledStorage::new(path).unwrap()
     };
+    assert_eq!(
+        sled_storage.prepare().await,
+        Ok(()),
+        "preparing without a transaction has nothing to check"
+    );
 
     let mut storage = CompositeStorage::new();
     storage.push("MEMORY", memory_storage);
@@ -56,8 +65,61 @@ async fn memory_and_sled() {
         )
     );
 
+    let count = |rows: i64| select!(cnt I64; rows);
+
+    glue.execute("BEGIN;").await.unwrap();
+    glue.execute("INSERT INTO Foo VALUES (6);").await.unwrap();
+    glue.execute("DELETE FROM Bar WHERE foo_id = 3;")
+        .await
+        .unwrap();
+    glue.execute("ROLLBACK;").await.unwrap();
+
+    assert_eq!(
+        glue.execute("SELECT COUNT(*) AS cnt FROM Foo;")
+            .await
+            .unwrap()[0],
+        count(5)
+    );
+    assert_eq!(
+        glue.execute("SELECT COUNT(*) AS cnt FROM Bar;")
+            .await
+            .unwrap()[0],
+        count(5)
+    );
+
+    glue.execute("BEGIN;").await.unwrap();
+    glue.execute("INSERT INTO Foo VALUES (6);").await.unwrap();
+    glue.execute("DELETE FROM Bar WHERE foo_id = 3;")
+        .await
+        .unwrap();
     assert_eq!(
         glue.execute("BEGIN;").await.unwrap_err(),
-        Error::StorageMsg("[CompositeStorage] Transaction::begin is not supported".to_owned()),
+        Error::StorageMsg("[CompositeStorage] nested transaction is not supported".to_owned()),
+    );
+    glue.execute("COMMIT;").await.unwrap();
+
+    assert_eq!(
+        glue.execute("SELECT COUNT(*) AS cnt FROM Foo;")
+            .await
+            .unwrap()[0],
+        count(6)
+    );
+    assert_eq!(
+        glue.execute("SELECT COUNT(*) AS cnt FROM Bar;")
+            .await
+            .unwrap()[0],
+        count(2)
+    );
+
+    assert_eq!(
+        glue.execute("CREATE TABLE Baz AS SELECT foo_id * 10 / (foo_id - 6) AS v FROM Foo;")
+            .await
+            .unwrap_err(),
+        ValueError::DivisorShouldNotBeZero.into(),
+    );
+    assert_eq!(
+        glue.execute("SELECT * FROM Baz;").await.unwrap_err(),
+        FetchError::TableNotFound("Baz".to_owned()).into(),
+        "a failing statement is undone in every engine in autocommit mode"
     );
 }
@@ -5,13 +5,14 @@ mod store_mut;
 use {
     error::{CsvStorageError, ResultExt},
     gluesql_core::{
-        ast::{ColumnDef, ColumnUniqueOption, DataType},
+        ast::{ColumnDef, DataType},
         data::{Key, Schema, Value},
         error::Result,
         parse_sql::parse_data_type,
         store::{
             AlterTable, CustomFunction, CustomFunctionMut, DataRow, Index, IndexMut, Metadata,
-            Transaction,
+            Sequence, SequenceMut, Statistics, StatisticsMut, Transaction, Trigger, TriggerMut,
+            View, ViewMut,
         },
         translate::translate_data_type,
     },
@@ -56,7 +57,10 @@ impl CsvStorage {
                             name: header.to_string(),
                             data_type: DataType::Text,
                             unique: None,
+                            check: None,
                             default: None,
+                            identity: None,
+                            generated: None,
                             nullable: true,
                             comment: None,
                         })
@@ -70,7 +74,9 @@ impl CsvStorage {
                 column_defs,
                 indexes: Vec::new(),
                 engine: None,
+                key_constraints: Vec::new(),
                 foreign_keys: Vec::new(),
+                checks: Vec::new(),
                 comment: None,
             };
 
@@ -134,6 +140,8 @@ impl CsvStorage {
                 .collect::<Vec<_>>())
         };
 
+        let primary_key_indexes = schema.primary_key_indexes();
+
         if let Schema {
             column_defs: Some(column_defs),
             ..
@@ -148,8 +156,6 @@ impl CsvStorage {
                 .into_records()
                 .enumerate()
                 .map(move |(index, record)| {
-                    let mut key: Option<Key> = None;
-
                     let values = record
                         .map_storage_err()?
                         .into_iter()
@@ -165,15 +171,14 @@ impl CsvStorage {
                                 data_type => value.cast(data_type)?,
                             };
 
-                            if column_def.unique == Some(ColumnUniqueOption { is_primary: true }) {
-                                key = Key::try_from(&value).map(Some)?;
-                            }
-
                             Ok(value)
                         })
                         .collect::<Result<Vec<Value>>>()?;
 
-                    let key = key.unwrap_or(Key::U64(index as u64));
+                    let key = match &primary_key_indexes {
+                        Some(indexes) => Key::from_columns(indexes, &values)?,
+                        None => Key::U64(index as u64),
+                    };
                     let row = DataRow::Vec(values);
 
                     Ok((key, row))
@@ -254,6 +259,14 @@ impl CsvStorage {
 impl AlterTable for CsvStorage {}
 impl CustomFunction for CsvStorage {}
 impl CustomFunctionMut for CsvStorage {}
+impl View for CsvStorage {}
+impl ViewMut for CsvStorage {}
+impl Statistics for CsvStorage {}
+impl StatisticsMut for CsvStorage {}
+impl Sequence for CsvStorage {}
+impl SequenceMut for CsvStorage {}
+impl Trigger for CsvStorage {}
+impl TriggerMut for CsvStorage {}
 impl Index for CsvStorage {}
 impl IndexMut for CsvStorage {}
 impl Transaction for CsvStorage {}
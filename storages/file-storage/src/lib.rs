@@ -1,5 +1,6 @@
 #![deny(clippy::str_to_string)]
 
+mod sequence;
 mod store;
 mod store_mut;
 
@@ -9,7 +10,7 @@ use {
         error::{Error, Result},
         store::{
             AlterTable, CustomFunction, CustomFunctionMut, DataRow, Index, IndexMut, Metadata,
-            Transaction,
+            Statistics, StatisticsMut, Transaction, Trigger, TriggerMut, View, ViewMut,
         },
     },
     hex::ToHex,
@@ -80,3 +81,9 @@ impl Transaction for FileStorage {}
 impl Metadata for FileStorage {}
 impl CustomFunction for FileStorage {}
 impl CustomFunctionMut for FileStorage {}
+impl View for FileStorage {}
+impl ViewMut for FileStorage {}
+impl Statistics for FileStorage {}
+impl StatisticsMut for FileStorage {}
+impl Trigger for FileStorage {}
+impl TriggerMut for FileStorage {}
@@ -0,0 +1,64 @@
+use {
+    super::{FileStorage, ResultExt},
+    async_trait::async_trait,
+    gluesql_core::{
+        data::Sequence as StructSequence,
+        error::Result,
+        store::{Sequence, SequenceMut},
+    },
+    ron::ser::{PrettyConfig, to_string_pretty},
+    std::{fs, path::PathBuf},
+};
+
+impl FileStorage {
+    fn sequence_path(&self, sequence_name: &str) -> PathBuf {
+        self.path(sequence_name).with_extension("seq")
+    }
+
+    fn write_sequence(&self, sequence: &StructSequence) -> Result<()> {
+        let data = to_string_pretty(sequence, PrettyConfig::default()).map_storage_err()?;
+
+        fs::write(self.sequence_path(&sequence.name), data).map_storage_err()
+    }
+}
+
+#[async_trait]
+impl Sequence for FileStorage {
+    async fn fetch_sequence(&self, sequence_name: &str) -> Result<Option<StructSequence>> {
+        let path = self.sequence_path(sequence_name);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let data = fs::read_to_string(path).map_storage_err()?;
+
+        ron::from_str(&data).map_storage_err().map(Some)
+    }
+
+    async fn next_sequence_value(&self, sequence_name: &str) -> Result<Option<i64>> {
+        let Some(mut sequence) = self.fetch_sequence(sequence_name).await? else {
+            return Ok(None);
+        };
+
+        let value = sequence.advance()?;
+        self.write_sequence(&sequence)?;
+
+        Ok(Some(value))
+    }
+}
+
+#[async_trait]
+impl SequenceMut for FileStorage {
+    async fn insert_sequence(&mut self, sequence: StructSequence) -> Result<()> {
+        self.write_sequence(&sequence)
+    }
+
+    async fn delete_sequence(&mut self, sequence_name: &str) -> Result<()> {
+        let path = self.sequence_path(sequence_name);
+
+        match path.exists() {
+            true => fs::remove_file(path).map_storage_err(),
+            false => Ok(()),
+        }
+    }
+}
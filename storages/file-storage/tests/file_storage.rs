@@ -31,6 +31,8 @@ impl Tester<FileStorage> for FileStorageTester {
 
 generate_store_tests!(tokio::test, FileStorageTester);
 generate_alter_table_tests!(tokio::test, FileStorageTester);
+generate_sequence_tests!(tokio::test, FileStorageTester);
+generate_alter_table_sequence_tests!(tokio::test, FileStorageTester);
 
 #[tokio::test]
 async fn scan_data_to_ignore_directory_items() {
@@ -9,8 +9,9 @@ use {
     gluesql_core::{
         error::{Error, Result},
         store::{
-            AlterTable, CustomFunction, CustomFunctionMut, Index, IndexMut, Metadata, Store,
-            StoreMut, Transaction,
+            AlterTable, CustomFunction, CustomFunctionMut, Index, IndexMut, Metadata, Sequence,
+            SequenceMut, Statistics, StatisticsMut, Store, StoreMut, Transaction, Trigger,
+            TriggerMut, View, ViewMut,
         },
     },
     gluesql_csv_storage::CsvStorage,
@@ -162,3 +163,11 @@ impl Transaction for GitStorage {}
 impl Metadata for GitStorage {}
 impl CustomFunction for GitStorage {}
 impl CustomFunctionMut for GitStorage {}
+impl View for GitStorage {}
+impl ViewMut for GitStorage {}
+impl Statistics for GitStorage {}
+impl StatisticsMut for GitStorage {}
+impl Sequence for GitStorage {}
+impl SequenceMut for GitStorage {}
+impl Trigger for GitStorage {}
+impl TriggerMut for GitStorage {}
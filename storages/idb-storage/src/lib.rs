@@ -609,6 +609,14 @@ impl gluesql_core::store::Transaction for IdbStorage {}
 impl Metadata for IdbStorage {}
 impl gluesql_core::store::CustomFunction for IdbStorage {}
 impl gluesql_core::store::CustomFunctionMut for IdbStorage {}
+impl gluesql_core::store::View for IdbStorage {}
+impl gluesql_core::store::ViewMut for IdbStorage {}
+impl gluesql_core::store::Statistics for IdbStorage {}
+impl gluesql_core::store::StatisticsMut for IdbStorage {}
+impl gluesql_core::store::Sequence for IdbStorage {}
+impl gluesql_core::store::SequenceMut for IdbStorage {}
+impl gluesql_core::store::Trigger for IdbStorage {}
+impl gluesql_core::store::TriggerMut for IdbStorage {}
 
 /// `IdbStorage` holds `web_sys` types that are not `Send` by default, but it
 /// is used only on `wasm32` targets where execution happens on a single
@@ -2,6 +2,7 @@ mod alter_table;
 pub mod error;
 mod function;
 mod index;
+mod sequence;
 mod store;
 mod store_mut;
 mod transaction;
@@ -9,10 +10,9 @@ mod transaction;
 use {
     error::{JsonStorageError, OptionExt, ResultExt},
     gluesql_core::{
-        ast::ColumnUniqueOption,
         data::{Key, Schema, value::BTreeMapJsonExt},
         error::{Error, Result},
-        store::{DataRow, Metadata},
+        store::{DataRow, Metadata, Statistics, StatisticsMut, Trigger, TriggerMut, View, ViewMut},
     },
     iter_enum::Iterator,
     serde_json::Value as JsonValue,
@@ -54,30 +54,39 @@ impl JsonStorage {
         }
 
         let schema_path = self.schema_path(table_name);
-        let (column_defs, foreign_keys, comment) = match schema_path.exists() {
-            true => {
-                let mut file = File::open(&schema_path).map_storage_err()?;
-                let mut ddl = String::new();
-                file.read_to_string(&mut ddl).map_storage_err()?;
-
-                let schema = Schema::from_ddl(&ddl)?;
-                if schema.table_name != table_name {
-                    return Err(Error::StorageMsg(
-                        JsonStorageError::TableNameDoesNotMatchWithFile.to_string(),
-                    ));
-                }
+        let (column_defs, key_constraints, foreign_keys, checks, comment) =
+            match schema_path.exists() {
+                true => {
+                    let mut file = File::open(&schema_path).map_storage_err()?;
+                    let mut ddl = String::new();
+                    file.read_to_string(&mut ddl).map_storage_err()?;
+
+                    let schema = Schema::from_ddl(&ddl)?;
+                    if schema.table_name != table_name {
+                        return Err(Error::StorageMsg(
+                            JsonStorageError::TableNameDoesNotMatchWithFile.to_string(),
+                        ));
+                    }
 
-                (schema.column_defs, schema.foreign_keys, schema.comment)
-            }
-            false => (None, Vec::new(), None),
-        };
+                    (
+                        schema.column_defs,
+                        schema.key_constraints,
+                        schema.foreign_keys,
+                        schema.checks,
+                        schema.comment,
+                    )
+                }
+                false => (None, Vec::new(), Vec::new(), Vec::new(), None),
+            };
 
         Ok(Some(Schema {
             table_name: table_name.to_owned(),
             column_defs,
             indexes: vec![],
             engine: None,
+            key_constraints,
             foreign_keys,
+            checks,
             comment,
         }))
     }
@@ -150,6 +159,7 @@ impl JsonStorage {
         };
 
         let schema2 = schema.clone();
+        let primary_key_indexes = schema.primary_key_indexes();
         let rows = jsons.enumerate().map(move |(index, json)| -> Result<_> {
             let json = json?;
             let get_index_key = || index.try_into().map(Key::I64).map_storage_err();
@@ -164,18 +174,12 @@ impl JsonStorage {
                 }
             };
 
-            let mut key: Option<Key> = None;
             let mut values = Vec::with_capacity(column_defs.len());
             for column_def in column_defs {
                 let value = json.get(&column_def.name).map_storage_err(
                     JsonStorageError::ColumnDoesNotExist(column_def.name.clone()),
                 )?;
 
-                if column_def.unique == Some(ColumnUniqueOption { is_primary: true }) {
-                    let value = value.cast(&column_def.data_type)?;
-                    key = Some(value.try_into().map_storage_err()?);
-                }
-
                 let value = match value.get_type() {
                     Some(data_type) if data_type != column_def.data_type => {
                         value.cast(&column_def.data_type)?
@@ -186,8 +190,8 @@ impl JsonStorage {
                 values.push(value);
             }
 
-            let key = match key {
-                Some(key) => key,
+            let key = match &primary_key_indexes {
+                Some(indexes) => Key::from_columns(indexes, &values)?,
                 None => get_index_key()?,
             };
             let row = DataRow::Vec(values);
@@ -208,3 +212,9 @@ where
 }
 
 impl Metadata for JsonStorage {}
+impl View for JsonStorage {}
+impl ViewMut for JsonStorage {}
+impl Statistics for JsonStorage {}
+impl StatisticsMut for JsonStorage {}
+impl Trigger for JsonStorage {}
+impl TriggerMut for JsonStorage {}
@@ -28,3 +28,5 @@ impl Tester<JsonStorage> for JsonTester {
 
 generate_store_tests!(tokio::test, JsonTester);
 generate_alter_table_tests!(tokio::test, JsonTester);
+generate_sequence_tests!(tokio::test, JsonTester);
+generate_alter_table_sequence_tests!(tokio::test, JsonTester);
@@ -12,6 +12,9 @@ use {
 #[async_trait]
 impl AlterTable for MemoryStorage {
     async fn rename_schema(&mut self, table_name: &str, new_table_name: &str) -> Result<()> {
+        self.save_table(table_name);
+        self.save_table_as(table_name, new_table_name);
+
         let mut item = self
             .items
             .remove(table_name)
@@ -29,6 +32,8 @@ impl AlterTable for MemoryStorage {
         old_column_name: &str,
         new_column_name: &str,
     ) -> Result<()> {
+        self.save_schema(table_name);
+
         let item = self
             .items
             .get_mut(table_name)
@@ -58,6 +63,8 @@ impl AlterTable for MemoryStorage {
     }
 
     async fn add_column(&mut self, table_name: &str, column_def: &ColumnDef) -> Result<()> {
+        self.save_table(table_name);
+
         let item = self
             .items
             .get_mut(table_name)
@@ -93,7 +100,9 @@ impl AlterTable for MemoryStorage {
             }
             (None, true) => Value::Null,
             (None, false) => {
-                return Err(AlterTableError::DefaultValueRequired(column_def.clone()).into());
+                return Err(
+                    AlterTableError::DefaultValueRequired(Box::new(column_def.clone())).into(),
+                );
             }
         };
 
@@ -121,6 +130,8 @@ impl AlterTable for MemoryStorage {
         column_name: &str,
         if_exists: bool,
     ) -> Result<()> {
+        self.save_table(table_name);
+
         let item = self
             .items
             .get_mut(table_name)
@@ -3,16 +3,25 @@
 mod alter_table;
 mod index;
 mod metadata;
+mod sequence;
 mod transaction;
 
+pub use {sequence::Sequences, transaction::Snapshot};
+
 use {
     async_trait::async_trait,
     futures::stream::iter,
     gluesql_core::{
         chrono::Utc,
-        data::{CustomFunction as StructCustomFunction, Key, Schema, Value},
+        data::{
+            CustomFunction as StructCustomFunction, Key, Schema, TableStatistics,
+            Trigger as StructTrigger, Value, View as StructView,
+        },
         error::Result,
-        store::{CustomFunction, CustomFunctionMut, DataRow, RowIter, Store, StoreMut},
+        store::{
+            CustomFunction, CustomFunctionMut, DataRow, RowIter, Statistics, StatisticsMut, Store,
+            StoreMut, Trigger, TriggerMut, View, ViewMut,
+        },
     },
     serde::{Deserialize, Serialize},
     std::collections::{BTreeMap, HashMap},
@@ -30,6 +39,15 @@ pub struct MemoryStorage {
     pub items: HashMap<String, Item>,
     pub metadata: HashMap<String, BTreeMap<String, Value>>,
     pub functions: HashMap<String, StructCustomFunction>,
+    pub views: HashMap<String, StructView>,
+    pub statistics: HashMap<String, TableStatistics>,
+    pub sequences: Sequences,
+    /// Triggers by table name, then by trigger name
+    pub triggers: HashMap<String, BTreeMap<String, StructTrigger>>,
+    /// Snapshots of the transaction in progress, the first one taken when it began and the
+    /// others for its savepoints
+    #[serde(skip)]
+    snapshots: Vec<Snapshot>,
 }
 
 impl MemoryStorage {
@@ -39,6 +57,18 @@ impl MemoryStorage {
             None => vec![],
         }
     }
+
+    pub fn scan_data_by_prefix(&self, table_name: &str, prefix: &[Key]) -> Vec<(Key, DataRow)> {
+        let Some(item) = self.items.get(table_name) else {
+            return vec![];
+        };
+
+        item.rows
+            .range(Key::Composite(prefix.to_vec())..)
+            .take_while(|(key, _)| matches!(key.starts_with(prefix), Ok(true)))
+            .map(|(key, row)| (key.clone(), row.clone()))
+            .collect()
+    }
 }
 
 #[async_trait]
@@ -67,6 +97,87 @@ impl CustomFunctionMut for MemoryStorage {
     }
 }
 
+#[async_trait]
+impl View for MemoryStorage {
+    async fn fetch_view(&self, view_name: &str) -> Result<Option<StructView>> {
+        Ok(self.views.get(view_name).cloned())
+    }
+
+    async fn fetch_all_views(&self) -> Result<Vec<StructView>> {
+        let mut views = self.views.values().cloned().collect::<Vec<_>>();
+        views.sort_by(|a, b| a.view_name.cmp(&b.view_name));
+
+        Ok(views)
+    }
+}
+
+#[async_trait]
+impl ViewMut for MemoryStorage {
+    async fn insert_view(&mut self, view: StructView) -> Result<()> {
+        self.views.insert(view.view_name.clone(), view);
+        Ok(())
+    }
+
+    async fn delete_view(&mut self, view_name: &str) -> Result<()> {
+        self.views.remove(view_name);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Trigger for MemoryStorage {
+    async fn fetch_triggers(&self, table_name: &str) -> Result<Vec<StructTrigger>> {
+        Ok(self
+            .triggers
+            .get(table_name)
+            .map(|triggers| triggers.values().cloned().collect())
+            .unwrap_or_default())
+    }
+}
+
+#[async_trait]
+impl TriggerMut for MemoryStorage {
+    async fn insert_trigger(&mut self, trigger: StructTrigger) -> Result<()> {
+        self.triggers
+            .entry(trigger.table_name.clone())
+            .or_default()
+            .insert(trigger.name.clone(), trigger);
+        Ok(())
+    }
+
+    async fn delete_trigger(&mut self, table_name: &str, trigger_name: &str) -> Result<()> {
+        if let Some(triggers) = self.triggers.get_mut(table_name) {
+            triggers.remove(trigger_name);
+
+            if triggers.is_empty() {
+                self.triggers.remove(table_name);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Statistics for MemoryStorage {
+    async fn fetch_statistics(&self, table_name: &str) -> Result<Option<TableStatistics>> {
+        Ok(self.statistics.get(table_name).cloned())
+    }
+}
+
+#[async_trait]
+impl StatisticsMut for MemoryStorage {
+    async fn insert_statistics(&mut self, statistics: TableStatistics) -> Result<()> {
+        self.statistics
+            .insert(statistics.table_name.clone(), statistics);
+        Ok(())
+    }
+
+    async fn delete_statistics(&mut self, table_name: &str) -> Result<()> {
+        self.statistics.remove(table_name);
+        Ok(())
+    }
+}
+
 #[async_trait]
 impl Store for MemoryStorage {
     async fn fetch_all_schemas(&self) -> Result<Vec<Schema>> {
@@ -102,6 +213,18 @@ impl Store for MemoryStorage {
 
         Ok(Box::pin(iter(rows)))
     }
+
+    async fn scan_data_by_prefix<'a>(
+        &'a self,
+        table_name: &str,
+        prefix: &[Key],
+    ) -> Result<RowIter<'a>> {
+        let rows = MemoryStorage::scan_data_by_prefix(self, table_name, prefix)
+            .into_iter()
+            .map(Ok);
+
+        Ok(Box::pin(iter(rows)))
+    }
 }
 
 #[async_trait]
@@ -114,6 +237,8 @@ impl StoreMut for MemoryStorage {
         let meta = HashMap::from([(schema.table_name.clone(), created)]);
         self.metadata.extend(meta);
 
+        self.save_table(&schema.table_name);
+
         let table_name = schema.table_name.clone();
         let item = Item {
             schema: schema.clone(),
@@ -125,6 +250,7 @@ impl StoreMut for MemoryStorage {
     }
 
     async fn delete_schema(&mut self, table_name: &str) -> Result<()> {
+        self.save_table(table_name);
         self.items.remove(table_name);
         self.metadata.remove(table_name);
 
@@ -132,6 +258,10 @@ impl StoreMut for MemoryStorage {
     }
 
     async fn append_data(&mut self, table_name: &str, rows: Vec<DataRow>) -> Result<()> {
+        let id_counter = self.id_counter;
+        let keys = (1..=rows.len() as i64).map(|i| Key::I64(id_counter + i));
+        self.save_rows(table_name, keys);
+
         if let Some(item) = self.items.get_mut(table_name) {
             for row in rows {
                 self.id_counter += 1;
@@ -144,6 +274,8 @@ impl StoreMut for MemoryStorage {
     }
 
     async fn insert_data(&mut self, table_name: &str, rows: Vec<(Key, DataRow)>) -> Result<()> {
+        self.save_rows(table_name, rows.iter().map(|(key, _)| key.clone()));
+
         if let Some(item) = self.items.get_mut(table_name) {
             for (key, row) in rows {
                 item.rows.insert(key, row);
@@ -154,6 +286,8 @@ impl StoreMut for MemoryStorage {
     }
 
     async fn delete_data(&mut self, table_name: &str, keys: Vec<Key>) -> Result<()> {
+        self.save_rows(table_name, keys.iter().cloned());
+
         if let Some(item) = self.items.get_mut(table_name) {
             for key in keys {
                 item.rows.remove(&key);
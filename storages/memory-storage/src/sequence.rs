@@ -0,0 +1,70 @@
+use {
+    super::MemoryStorage,
+    async_trait::async_trait,
+    gluesql_core::{
+        data::Sequence as StructSequence,
+        error::{Error, Result},
+        store::{Sequence, SequenceMut},
+    },
+    serde::{Deserialize, Serialize},
+    std::{
+        collections::HashMap,
+        sync::{Mutex, MutexGuard},
+    },
+};
+
+/// Sequences behind a lock, `NEXTVAL` advances them through a shared reference.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Sequences(Mutex<HashMap<String, StructSequence>>);
+
+impl Sequences {
+    pub(crate) fn lock(&self) -> Result<MutexGuard<'_, HashMap<String, StructSequence>>> {
+        self.0
+            .lock()
+            .map_err(|_| Error::StorageMsg("[MemoryStorage] sequence lock poisoned".to_owned()))
+    }
+}
+
+impl Clone for Sequences {
+    fn clone(&self) -> Self {
+        let sequences = self
+            .0
+            .lock()
+            .map(|sequences| sequences.clone())
+            .unwrap_or_default();
+
+        Self(Mutex::new(sequences))
+    }
+}
+
+#[async_trait]
+impl Sequence for MemoryStorage {
+    async fn fetch_sequence(&self, sequence_name: &str) -> Result<Option<StructSequence>> {
+        Ok(self.sequences.lock()?.get(sequence_name).cloned())
+    }
+
+    async fn next_sequence_value(&self, sequence_name: &str) -> Result<Option<i64>> {
+        self.sequences
+            .lock()?
+            .get_mut(sequence_name)
+            .map(StructSequence::advance)
+            .transpose()
+    }
+}
+
+#[async_trait]
+impl SequenceMut for MemoryStorage {
+    async fn insert_sequence(&mut self, sequence: StructSequence) -> Result<()> {
+        self.sequences
+            .lock()?
+            .insert(sequence.name.clone(), sequence);
+
+        Ok(())
+    }
+
+    async fn delete_sequence(&mut self, sequence_name: &str) -> Result<()> {
+        self.sequences.lock()?.remove(sequence_name);
+
+        Ok(())
+    }
+}
@@ -1,29 +1,447 @@
 use {
-    super::MemoryStorage,
+    super::{Item, MemoryStorage},
     async_trait::async_trait,
     gluesql_core::{
+        data::{
+            CustomFunction as StructCustomFunction, Key, Schema, Sequence as StructSequence,
+            TableStatistics, Trigger as StructTrigger, Value, View as StructView,
+        },
         error::{Error, Result},
-        store::Transaction,
+        store::{DataRow, Transaction},
     },
+    std::collections::{BTreeMap, HashMap},
 };
 
+/// State of a transaction, or of a savepoint in it, from before the changes made since it
+/// began. Tables and rows are copied on their first change, the other collections when the
+/// snapshot is taken.
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    savepoint: Option<String>,
+    id_counter: i64,
+    /// Schemas of the tables created, dropped or altered, `None` for the tables which did not
+    /// exist
+    schemas: HashMap<String, Option<Schema>>,
+    /// Rows by table name, `None` for the rows which did not exist
+    rows: HashMap<String, HashMap<Key, Option<DataRow>>>,
+    metadata: HashMap<String, BTreeMap<String, Value>>,
+    functions: HashMap<String, StructCustomFunction>,
+    views: HashMap<String, StructView>,
+    statistics: HashMap<String, TableStatistics>,
+    sequences: HashMap<String, StructSequence>,
+    triggers: HashMap<String, BTreeMap<String, StructTrigger>>,
+}
+
+impl Snapshot {
+    /// Keeps the entries of `other` this snapshot has no copy of yet, as `other` was taken
+    /// later.
+    fn merge(&mut self, other: Snapshot) {
+        for (table_name, schema) in other.schemas {
+            self.schemas.entry(table_name).or_insert(schema);
+        }
+
+        for (table_name, rows) in other.rows {
+            let saved = self.rows.entry(table_name).or_default();
+
+            for (key, row) in rows {
+                saved.entry(key).or_insert(row);
+            }
+        }
+    }
+}
+
+impl MemoryStorage {
+    fn take_snapshot(&self, savepoint: Option<String>) -> Result<Snapshot> {
+        Ok(Snapshot {
+            savepoint,
+            id_counter: self.id_counter,
+            schemas: HashMap::new(),
+            rows: HashMap::new(),
+            metadata: self.metadata.clone(),
+            functions: self.functions.clone(),
+            views: self.views.clone(),
+            statistics: self.statistics.clone(),
+            sequences: self.sequences.lock()?.clone(),
+            triggers: self.triggers.clone(),
+        })
+    }
+
+    fn restore(&mut self, snapshot: Snapshot) -> Result<()> {
+        let Snapshot {
+            savepoint: _,
+            id_counter,
+            schemas,
+            rows,
+            metadata,
+            functions,
+            views,
+            statistics,
+            sequences,
+            triggers,
+        } = snapshot;
+
+        for (table_name, schema) in schemas {
+            match schema {
+                Some(schema) => match self.items.get_mut(&table_name) {
+                    Some(item) => item.schema = schema,
+                    None => {
+                        let item = Item {
+                            schema,
+                            rows: BTreeMap::new(),
+                        };
+
+                        self.items.insert(table_name, item);
+                    }
+                },
+                None => {
+                    self.items.remove(&table_name);
+                }
+            }
+        }
+
+        for (table_name, rows) in rows {
+            let Some(item) = self.items.get_mut(&table_name) else {
+                continue;
+            };
+
+            for (key, row) in rows {
+                match row {
+                    Some(row) => item.rows.insert(key, row),
+                    None => item.rows.remove(&key),
+                };
+            }
+        }
+
+        self.id_counter = id_counter;
+        self.metadata = metadata;
+        self.functions = functions;
+        self.views = views;
+        self.statistics = statistics;
+        *self.sequences.lock()? = sequences;
+        self.triggers = triggers;
+
+        Ok(())
+    }
+
+    /// Copies the schema of `table_name` into the current snapshot before its first change.
+    pub(crate) fn save_schema(&mut self, table_name: &str) {
+        let Some(snapshot) = self.snapshots.last_mut() else {
+            return;
+        };
+
+        snapshot
+            .schemas
+            .entry(table_name.to_owned())
+            .or_insert_with(|| self.items.get(table_name).map(|item| item.schema.clone()));
+    }
+
+    /// Copies the rows of `keys` into the current snapshot before their first change.
+    pub(crate) fn save_rows(&mut self, table_name: &str, keys: impl IntoIterator<Item = Key>) {
+        let Some(snapshot) = self.snapshots.last_mut() else {
+            return;
+        };
+
+        let rows = self.items.get(table_name).map(|item| &item.rows);
+        let saved = snapshot.rows.entry(table_name.to_owned()).or_default();
+
+        for key in keys {
+            saved
+                .entry(key)
+                .or_insert_with_key(|key| rows.and_then(|rows| rows.get(key)).cloned());
+        }
+    }
+
+    /// Copies the schema and all the rows of `table_name` into the current snapshot.
+    pub(crate) fn save_table(&mut self, table_name: &str) {
+        if self.snapshots.is_empty() {
+            return;
+        }
+
+        self.save_schema(table_name);
+
+        let keys = self
+            .items
+            .get(table_name)
+            .map(|item| item.rows.keys().cloned().collect::<Vec<_>>())
+            .unwrap_or_default();
+        self.save_rows(table_name, keys);
+    }
+
+    /// Copies the rows `table_name` is about to be renamed into `new_table_name` with, as they
+    /// were in `new_table_name`.
+    pub(crate) fn save_table_as(&mut self, table_name: &str, new_table_name: &str) {
+        if self.snapshots.is_empty() {
+            return;
+        }
+
+        self.save_schema(new_table_name);
+
+        let keys = self
+            .items
+            .get(table_name)
+            .map(|item| item.rows.keys().cloned().collect::<Vec<_>>())
+            .unwrap_or_default();
+        self.save_rows(new_table_name, keys);
+    }
+
+    /// Returns the position of the latest savepoint named `name` among the snapshots.
+    fn find_savepoint(&self, name: &str) -> Result<usize> {
+        self.snapshots
+            .iter()
+            .rposition(|snapshot| snapshot.savepoint.as_deref() == Some(name))
+            .ok_or_else(|| Error::StorageMsg(format!("savepoint not found: {name}")))
+    }
+
+    /// Merges the snapshots of the savepoints into the one the transaction began with.
+    fn squash(&mut self) {
+        if self.snapshots.len() < 2 {
+            return;
+        }
+
+        let released = self.snapshots.split_off(1);
+        if let Some(snapshot) = self.snapshots.first_mut() {
+            for released in released {
+                snapshot.merge(released);
+            }
+        }
+    }
+
+    /// Returns whether the sequence `name` is still as it was when the transaction of this
+    /// storage began, so that its values can be drawn from the database the storage was copied
+    /// from instead.
+    pub fn keeps_sequence(&self, name: &str) -> Result<bool> {
+        let Some(snapshot) = self.snapshots.first() else {
+            return Ok(false);
+        };
+
+        let kept = match snapshot.sequences.get(name) {
+            Some(saved) => self.sequences.lock()?.get(name) == Some(saved),
+            None => false,
+        };
+
+        Ok(kept)
+    }
+
+    /// Checks that the transaction of this storage, a copy of `database` made when the
+    /// transaction began, can be applied to `database`. It cannot once `database` has changed
+    /// since then what the transaction changed.
+    pub fn prepare_commit(&mut self, database: &MemoryStorage) -> Result<()> {
+        self.squash();
+
+        let Some(snapshot) = self.snapshots.first() else {
+            return Ok(());
+        };
+
+        let conflict = |name: &str| {
+            Err(Error::StorageMsg(format!(
+                "[MemoryStorage] transaction conflict: {name} was changed by another transaction"
+            )))
+        };
+
+        for (table_name, schema) in snapshot.schemas.iter() {
+            let current = database.items.get(table_name).map(|item| &item.schema);
+
+            if current != schema.as_ref() {
+                return conflict(table_name);
+            }
+        }
+
+        for (table_name, rows) in snapshot.rows.iter() {
+            let current = database.items.get(table_name).map(|item| &item.rows);
+
+            for (key, row) in rows {
+                if current.and_then(|current| current.get(key)) != row.as_ref() {
+                    return conflict(table_name);
+                }
+            }
+        }
+
+        let sequences = self.sequences.lock()?.clone();
+        let database_sequences = database.sequences.lock()?.clone();
+
+        check(&self.metadata, &snapshot.metadata, &database.metadata)
+            .and_then(|_| check(&self.functions, &snapshot.functions, &database.functions))
+            .and_then(|_| check(&self.views, &snapshot.views, &database.views))
+            .and_then(|_| check(&self.statistics, &snapshot.statistics, &database.statistics))
+            .and_then(|_| check(&sequences, &snapshot.sequences, &database_sequences))
+            .and_then(|_| check(&self.triggers, &snapshot.triggers, &database.triggers))
+            .or_else(conflict)
+    }
+
+    /// Applies the transaction of this storage to `database` after checking it with
+    /// [`MemoryStorage::prepare_commit`], leaving `database` unchanged when the check fails.
+    pub fn commit_to(&mut self, database: &mut MemoryStorage) -> Result<()> {
+        self.prepare_commit(database)?;
+
+        let Some(snapshot) = self.snapshots.pop() else {
+            return Ok(());
+        };
+        let sequences = self.sequences.lock()?.clone();
+
+        for table_name in snapshot.schemas.keys() {
+            match self.items.get(table_name) {
+                Some(item) => {
+                    let rows = database
+                        .items
+                        .remove(table_name)
+                        .map(|item| item.rows)
+                        .unwrap_or_default();
+                    let item = Item {
+                        schema: item.schema.clone(),
+                        rows,
+                    };
+
+                    database.items.insert(table_name.clone(), item);
+                }
+                None => {
+                    database.items.remove(table_name);
+                }
+            }
+        }
+
+        for (table_name, rows) in snapshot.rows.iter() {
+            let (Some(item), Some(current)) = (
+                self.items.get(table_name),
+                database.items.get_mut(table_name),
+            ) else {
+                continue;
+            };
+
+            for key in rows.keys() {
+                match item.rows.get(key) {
+                    Some(row) => current.rows.insert(key.clone(), row.clone()),
+                    None => current.rows.remove(key),
+                };
+            }
+        }
+
+        apply(&self.metadata, &snapshot.metadata, &mut database.metadata);
+        apply(
+            &self.functions,
+            &snapshot.functions,
+            &mut database.functions,
+        );
+        apply(&self.views, &snapshot.views, &mut database.views);
+        apply(
+            &self.statistics,
+            &snapshot.statistics,
+            &mut database.statistics,
+        );
+        apply(
+            &sequences,
+            &snapshot.sequences,
+            &mut *database.sequences.lock()?,
+        );
+        apply(&self.triggers, &snapshot.triggers, &mut database.triggers);
+
+        Ok(())
+    }
+}
+
+/// Returns the names in `map` changed since `saved`.
+fn changed<'a, V: PartialEq>(
+    map: &'a HashMap<String, V>,
+    saved: &'a HashMap<String, V>,
+) -> impl Iterator<Item = &'a String> {
+    map.keys()
+        .chain(saved.keys())
+        .filter(|name| map.get(*name) != saved.get(*name))
+}
+
+/// Returns the first name changed both in `map` and in `database` since `saved`.
+fn check<'a, V: PartialEq>(
+    map: &'a HashMap<String, V>,
+    saved: &'a HashMap<String, V>,
+    database: &HashMap<String, V>,
+) -> std::result::Result<(), &'a str> {
+    match changed(map, saved).find(|name| database.get(*name) != saved.get(*name)) {
+        Some(name) => Err(name),
+        None => Ok(()),
+    }
+}
+
+fn apply<V: Clone + PartialEq>(
+    map: &HashMap<String, V>,
+    saved: &HashMap<String, V>,
+    database: &mut HashMap<String, V>,
+) {
+    for name in changed(map, saved) {
+        match map.get(name) {
+            Some(value) => database.insert(name.clone(), value.clone()),
+            None => database.remove(name),
+        };
+    }
+}
+
 #[async_trait]
 impl Transaction for MemoryStorage {
     async fn begin(&mut self, autocommit: bool) -> Result<bool> {
-        if autocommit {
-            return Ok(false);
-        }
+        match (self.snapshots.is_empty(), autocommit) {
+            (_, true) => Ok(false),
+            (true, false) => {
+                let snapshot = self.take_snapshot(None)?;
+                self.snapshots.push(snapshot);
 
-        Err(Error::StorageMsg(
-            "[MemoryStorage] transaction is not supported".to_owned(),
-        ))
+                Ok(false)
+            }
+            (false, false) => Err(Error::StorageMsg(
+                "[MemoryStorage] nested transaction is not supported".to_owned(),
+            )),
+        }
     }
 
     async fn rollback(&mut self) -> Result<()> {
+        while let Some(snapshot) = self.snapshots.pop() {
+            self.restore(snapshot)?;
+        }
+
         Ok(())
     }
 
     async fn commit(&mut self) -> Result<()> {
+        self.snapshots.clear();
+
+        Ok(())
+    }
+
+    async fn savepoint(&mut self, name: &str) -> Result<()> {
+        if self.snapshots.is_empty() {
+            return Err(Error::StorageMsg(
+                "[MemoryStorage] savepoint requires a transaction".to_owned(),
+            ));
+        }
+
+        let snapshot = self.take_snapshot(Some(name.to_owned()))?;
+        self.snapshots.push(snapshot);
+
+        Ok(())
+    }
+
+    async fn rollback_to_savepoint(&mut self, name: &str) -> Result<()> {
+        let position = self.find_savepoint(name)?;
+
+        while self.snapshots.len() > position {
+            if let Some(snapshot) = self.snapshots.pop() {
+                self.restore(snapshot)?;
+            }
+        }
+
+        let snapshot = self.take_snapshot(Some(name.to_owned()))?;
+        self.snapshots.push(snapshot);
+
+        Ok(())
+    }
+
+    async fn release_savepoint(&mut self, name: &str) -> Result<()> {
+        let position = self.find_savepoint(name)?;
+        let released = self.snapshots.split_off(position);
+
+        if let Some(snapshot) = self.snapshots.last_mut() {
+            for released in released {
+                snapshot.merge(released);
+            }
+        }
+
         Ok(())
     }
 }
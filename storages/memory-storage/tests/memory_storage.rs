@@ -25,10 +25,23 @@ generate_store_tests!(tokio::test, MemoryTester);
 
 generate_alter_table_tests!(tokio::test, MemoryTester);
 
+generate_view_tests!(tokio::test, MemoryTester);
+
+generate_statistics_tests!(tokio::test, MemoryTester);
+
 generate_metadata_table_tests!(tokio::test, MemoryTester);
 
 generate_custom_function_tests!(tokio::test, MemoryTester);
 
+generate_sequence_tests!(tokio::test, MemoryTester);
+generate_trigger_tests!(tokio::test, MemoryTester);
+
+generate_transaction_tests!(tokio::test, MemoryTester);
+generate_transaction_alter_table_tests!(tokio::test, MemoryTester);
+generate_transaction_trigger_tests!(tokio::test, MemoryTester);
+
+generate_alter_table_sequence_tests!(tokio::test, MemoryTester);
+
 macro_rules! exec {
     ($glue: ident $sql: literal) => {
         $glue.execute($sql).await.unwrap();
@@ -63,7 +76,7 @@ async fn memory_storage_index() {
 
     assert_eq!(
         storage
-            .scan_indexed_data("Idx", "hello", None, None)
+            .scan_indexed_data("Idx", "hello", None, &[], None)
             .await
             .map(|_| ()),
         Err(Error::StorageMsg(
@@ -92,7 +105,17 @@ async fn memory_storage_transaction() {
     let mut glue = Glue::new(storage);
 
     exec!(glue "CREATE TABLE TxTest (id INTEGER);");
-    test!(glue "BEGIN", Err(Error::StorageMsg("[MemoryStorage] transaction is not supported".to_owned())));
     test!(glue "COMMIT", Ok(vec![Payload::Commit]));
     test!(glue "ROLLBACK", Ok(vec![Payload::Rollback]));
+    test!(
+        glue "SAVEPOINT sp1",
+        Err(Error::StorageMsg("[MemoryStorage] savepoint requires a transaction".to_owned()))
+    );
+
+    test!(glue "BEGIN", Ok(vec![Payload::StartTransaction]));
+    test!(
+        glue "BEGIN",
+        Err(Error::StorageMsg("[MemoryStorage] nested transaction is not supported".to_owned()))
+    );
+    test!(glue "ROLLBACK", Ok(vec![Payload::Rollback]));
 }
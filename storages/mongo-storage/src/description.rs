@@ -1,16 +1,24 @@
 use {
-    gluesql_core::ast::{Expr, ForeignKey},
+    gluesql_core::ast::{CheckConstraint, Expr, ForeignKey, KeyConstraint},
     serde::{Deserialize, Serialize},
 };
 
 #[derive(Serialize, Deserialize)]
 pub struct TableDescription {
+    #[serde(default)]
+    pub key_constraints: Vec<KeyConstraint>,
     pub foreign_keys: Vec<ForeignKey>,
+    #[serde(default)]
+    pub checks: Vec<CheckConstraint>,
     pub comment: Option<String>,
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct ColumnDescription {
     pub default: Option<Expr>,
+    #[serde(default)]
+    pub generated: Option<Expr>,
+    #[serde(default)]
+    pub check: Option<CheckConstraint>,
     pub comment: Option<String>,
 }
@@ -10,7 +10,9 @@ use {
     gluesql_core::{
         error::Result,
         store::{
-            AlterTable, CustomFunction, CustomFunctionMut, Index, IndexMut, Metadata, Transaction,
+            AlterTable, CustomFunction, CustomFunctionMut, Index, IndexMut, Metadata, Sequence,
+            SequenceMut, Statistics, StatisticsMut, Transaction, Trigger, TriggerMut, View,
+            ViewMut,
         },
     },
     mongodb::{Client, Database, options::ClientOptions},
@@ -38,6 +40,14 @@ impl Metadata for MongoStorage {}
 impl AlterTable for MongoStorage {}
 impl CustomFunction for MongoStorage {}
 impl CustomFunctionMut for MongoStorage {}
+impl View for MongoStorage {}
+impl ViewMut for MongoStorage {}
+impl Statistics for MongoStorage {}
+impl StatisticsMut for MongoStorage {}
+impl Sequence for MongoStorage {}
+impl SequenceMut for MongoStorage {}
+impl Trigger for MongoStorage {}
+impl TriggerMut for MongoStorage {}
 impl Index for MongoStorage {}
 impl IndexMut for MongoStorage {}
 impl Transaction for MongoStorage {}
@@ -6,10 +6,11 @@ use {
     self::value::IntoValue,
     crate::error::ResultExt,
     gluesql_core::{
+        ast::ColumnDef,
         prelude::{DataType, Key, Result},
         store::DataRow,
     },
-    mongodb::bson::Document,
+    mongodb::bson::{Bson, Document},
 };
 
 pub trait IntoRow {
@@ -18,6 +19,13 @@ pub trait IntoRow {
         data_types: impl Iterator<Item = &'a DataType>,
         is_primary: bool,
     ) -> Result<(Key, DataRow)>;
+
+    /// Reads the columns by name, giving `NULL` for the columns left out by a projection
+    fn into_projected_row(
+        self,
+        column_defs: &[ColumnDef],
+        has_primary: bool,
+    ) -> Result<(Key, DataRow)>;
 }
 
 impl IntoRow for Document {
@@ -26,16 +34,7 @@ impl IntoRow for Document {
         data_types: impl Iterator<Item = &'a DataType>,
         has_primary: bool,
     ) -> Result<(Key, DataRow)> {
-        let key = match has_primary {
-            true => self.get_binary_generic("_id").map_storage_err()?.to_owned(),
-            false => self
-                .get_object_id("_id")
-                .map_storage_err()?
-                .bytes()
-                .to_vec(),
-        };
-        let key = Key::Bytea(key);
-
+        let key = get_key(&self, has_primary)?;
         let row = self
             .into_iter()
             .skip(1)
@@ -45,4 +44,32 @@ impl IntoRow for Document {
 
         Ok((key, DataRow::Vec(row)))
     }
+
+    fn into_projected_row(
+        mut self,
+        column_defs: &[ColumnDef],
+        has_primary: bool,
+    ) -> Result<(Key, DataRow)> {
+        let key = get_key(&self, has_primary)?;
+        let row = column_defs
+            .iter()
+            .map(|column_def| {
+                self.remove(&column_def.name)
+                    .unwrap_or(Bson::Null)
+                    .into_value(&column_def.data_type)
+                    .map_storage_err()
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok((key, DataRow::Vec(row)))
+    }
+}
+
+fn get_key(doc: &Document, has_primary: bool) -> Result<Key> {
+    let key = match has_primary {
+        true => doc.get_binary_generic("_id").map_storage_err()?.to_owned(),
+        false => doc.get_object_id("_id").map_storage_err()?.bytes().to_vec(),
+    };
+
+    Ok(Key::Bytea(key))
 }
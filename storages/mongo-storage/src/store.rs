@@ -3,23 +3,27 @@ use {
         MongoStorage,
         description::{ColumnDescription, TableDescription},
         error::{MongoStorageError, OptionExt, ResultExt},
-        row::{IntoRow, key::KeyIntoBson, value::IntoValue},
+        row::{
+            IntoRow,
+            key::KeyIntoBson,
+            value::{IntoBson, IntoValue},
+        },
         utils::get_primary_key,
     },
     async_trait::async_trait,
     futures::{Stream, StreamExt, TryStreamExt, stream},
     gluesql_core::{
-        ast::{ColumnDef, ColumnUniqueOption},
+        ast::{ColumnDef, ColumnUniqueOption, IndexOperator},
         data::{Key, Schema},
         error::Result,
         parse_sql::parse_data_type,
         prelude::{Error, Value},
-        store::{DataRow, RowIter, Store},
+        store::{DataRow, RowIter, ScanFilter, Store},
         translate::translate_data_type,
     },
     mongodb::{
         IndexModel,
-        bson::{Document, doc, document::ValueAccessError},
+        bson::{Bson, Document, doc, document::ValueAccessError},
         options::{FindOptions, ListIndexesOptions},
     },
     serde_json::from_str,
@@ -52,20 +56,23 @@ impl Store for MongoStorage {
     }
 
     async fn fetch_data(&self, table_name: &str, target: &Key) -> Result<Option<DataRow>> {
-        let column_defs = self
-            .get_column_defs(table_name)
+        let schema = self
+            .fetch_schema(table_name)
             .await?
             .map_storage_err(MongoStorageError::ConflictFetchData)?;
 
-        let primary_key = get_primary_key(&column_defs)
+        let primary_key = get_primary_key(&schema)
             .ok_or(MongoStorageError::Unreachable)
             .map_storage_err()?;
+        let column_defs = schema
+            .column_defs
+            .map_storage_err(MongoStorageError::ConflictFetchData)?;
 
         let filter = doc! { "_id": target.to_owned().into_bson(true).map_storage_err()?};
         let projection = doc! {"_id": 0};
         let options = FindOptions::builder()
             .projection(projection)
-            .sort(doc! { primary_key.name.clone(): 1 })
+            .sort(primary_key)
             .build();
 
         let mut cursor = self
@@ -93,17 +100,15 @@ impl Store for MongoStorage {
     }
 
     async fn scan_data<'a>(&'a self, table_name: &str) -> Result<RowIter<'a>> {
-        let column_defs = self.get_column_defs(table_name).await?;
-
-        let primary_key = column_defs
-            .as_ref()
-            .and_then(|column_defs| get_primary_key(column_defs));
+        let schema = self.fetch_schema(table_name).await?;
+        let primary_key = schema.as_ref().and_then(get_primary_key);
+        let column_defs = schema.and_then(|schema| schema.column_defs);
 
         let has_primary = primary_key.is_some();
 
         let options = FindOptions::builder();
         let options = match primary_key {
-            Some(primary_key) => options.sort(doc! { primary_key.name.to_owned(): 1}).build(),
+            Some(primary_key) => options.sort(primary_key).build(),
             None => options.build(),
         };
 
@@ -150,6 +155,88 @@ impl Store for MongoStorage {
 
         Ok(Box::pin(row_iter))
     }
+
+    async fn scan_data_filtered<'a>(
+        &'a self,
+        table_name: &str,
+        filter: &ScanFilter,
+    ) -> Result<RowIter<'a>> {
+        let schema = self.fetch_schema(table_name).await?;
+        let Some(Schema {
+            column_defs: Some(column_defs),
+            ..
+        }) = &schema
+        else {
+            return self.scan_data(table_name).await;
+        };
+        let primary_key = schema.as_ref().and_then(get_primary_key);
+        let has_primary = primary_key.is_some();
+
+        let conditions = filter
+            .predicates
+            .iter()
+            .filter(|predicate| {
+                matches!(
+                    predicate.value,
+                    Value::I8(_)
+                        | Value::I16(_)
+                        | Value::I32(_)
+                        | Value::I64(_)
+                        | Value::U16(_)
+                        | Value::U32(_)
+                        | Value::F32(_)
+                        | Value::F64(_)
+                        | Value::Bool(_)
+                        | Value::Str(_)
+                )
+            })
+            .map(|predicate| {
+                let op = match predicate.op {
+                    IndexOperator::Eq => "$eq",
+                    IndexOperator::Gt => "$gt",
+                    IndexOperator::GtEq => "$gte",
+                    IndexOperator::Lt => "$lt",
+                    IndexOperator::LtEq => "$lte",
+                };
+                let value = predicate.value.clone().into_bson().map_storage_err()?;
+
+                Ok(Bson::Document(doc! { &predicate.column: { op: value } }))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let query = match conditions.is_empty() {
+            true => Document::new(),
+            false => doc! { "$and": conditions },
+        };
+
+        let projection = filter.columns.as_ref().map(|columns| {
+            columns
+                .iter()
+                .fold(doc! { "_id": 1 }, |mut projection, column| {
+                    projection.insert(column, 1);
+                    projection
+                })
+        });
+
+        let options = FindOptions::builder()
+            .projection(projection)
+            .sort(primary_key)
+            .build();
+
+        let cursor = self
+            .db
+            .collection::<Document>(table_name)
+            .find(query, options)
+            .await
+            .map_storage_err()?;
+
+        let column_defs = column_defs.clone();
+        let row_iter = cursor.map(move |doc| {
+            doc.map_storage_err()?
+                .into_projected_row(&column_defs, has_primary)
+        });
+
+        Ok(Box::pin(row_iter))
+    }
 }
 
 impl MongoStorage {
@@ -241,12 +328,19 @@ impl MongoStorage {
                     .map(|is_primary| ColumnUniqueOption { is_primary });
 
                     let column_description = doc.get_str("description");
-                    let ColumnDescription { default, comment } = match column_description {
+                    let ColumnDescription {
+                        default,
+                        generated,
+                        check,
+                        comment,
+                    } = match column_description {
                         Ok(desc) => {
                             serde_json::from_str::<ColumnDescription>(desc).map_storage_err()?
                         }
                         Err(ValueAccessError::NotPresent) => ColumnDescription {
                             default: None,
+                            generated: None,
+                            check: None,
                             comment: None,
                         },
                         Err(_) => {
@@ -261,7 +355,10 @@ impl MongoStorage {
                         data_type,
                         nullable,
                         default,
+                        identity: None,
+                        generated,
                         unique,
+                        check,
                         comment,
                     };
 
@@ -276,7 +373,9 @@ impl MongoStorage {
 
             let table_description = validator.get_str("description").map_storage_err()?;
             let TableDescription {
+                key_constraints,
                 foreign_keys,
+                checks,
                 comment,
             } = from_str::<TableDescription>(table_description).map_storage_err()?;
 
@@ -285,7 +384,9 @@ impl MongoStorage {
                 column_defs,
                 indexes: Vec::new(),
                 engine: None,
+                key_constraints,
                 foreign_keys,
+                checks,
                 comment,
             };
 
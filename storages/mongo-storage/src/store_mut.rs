@@ -94,6 +94,8 @@ impl StoreMut for MongoStorage {
 
                         let column_description = ColumnDescription {
                             default: column_def.default.clone(),
+                            generated: column_def.generated.clone(),
+                            check: column_def.check.clone(),
                             comment: column_def.comment.clone(),
                         };
                         let column_description =
@@ -122,7 +124,14 @@ impl StoreMut for MongoStorage {
             .unwrap_or_default();
 
         let comment = schema.comment.as_ref().map(ToOwned::to_owned);
-        let validator = Validator::new(labels, column_types, schema.foreign_keys.clone(), comment)?;
+        let validator = Validator::new(
+            labels,
+            column_types,
+            schema.key_constraints.clone(),
+            schema.foreign_keys.clone(),
+            schema.checks.clone(),
+            comment,
+        )?;
 
         let schema_exists = self
             .fetch_schema(&schema.table_name)
@@ -239,11 +248,9 @@ impl StoreMut for MongoStorage {
     }
 
     async fn insert_data(&mut self, table_name: &str, rows: Vec<(Key, DataRow)>) -> Result<()> {
-        let column_defs = self.get_column_defs(table_name).await?;
-
-        let primary_key = column_defs
-            .as_ref()
-            .and_then(|column_defs| get_primary_key(column_defs));
+        let schema = self.fetch_schema(table_name).await?;
+        let has_primary = schema.as_ref().and_then(get_primary_key).is_some();
+        let column_defs = schema.and_then(|schema| schema.column_defs);
 
         for (key, row) in rows {
             let doc = match row {
@@ -253,7 +260,7 @@ impl StoreMut for MongoStorage {
                     .iter()
                     .zip(values.into_iter())
                     .try_fold(
-                        doc! {"_id": key.clone().into_bson(primary_key.is_some()).map_storage_err()?},
+                        doc! {"_id": key.clone().into_bson(has_primary).map_storage_err()?},
                         |mut acc, (column_def, value)| {
                             acc.extend(doc! {column_def.name.clone(): value.into_bson().map_storage_err()?});
 
@@ -270,7 +277,7 @@ impl StoreMut for MongoStorage {
                 ),
             }?;
 
-            let query = doc! {"_id": key.into_bson(primary_key.is_some()).map_storage_err()?};
+            let query = doc! {"_id": key.into_bson(has_primary).map_storage_err()?};
             let options = ReplaceOptions::builder().upsert(Some(true)).build();
 
             self.db
@@ -284,10 +291,12 @@ impl StoreMut for MongoStorage {
     }
 
     async fn delete_data(&mut self, table_name: &str, keys: Vec<Key>) -> Result<()> {
-        let column_defs = self.get_column_defs(table_name).await?;
-        let primary_key = column_defs
+        let has_primary = self
+            .fetch_schema(table_name)
+            .await?
             .as_ref()
-            .and_then(|column_defs| get_primary_key(column_defs));
+            .and_then(get_primary_key)
+            .is_some();
 
         self.db
             .collection::<Bson>(table_name)
@@ -295,7 +304,7 @@ impl StoreMut for MongoStorage {
                 doc! { "_id": {
                     "$in": keys
                         .into_iter()
-                        .map(|key| key.into_bson(primary_key.is_some()).map_storage_err())
+                        .map(|key| key.into_bson(has_primary).map_storage_err())
                         .collect::<Result<Vec<_>>>()?
                 }},
                 None,
@@ -1,18 +1,24 @@
 use {
     crate::{description::TableDescription, error::ResultExt},
-    bson::{Document, doc},
+    bson::{Bson, Document, doc},
     gluesql_core::{
-        ast::{ColumnDef, ForeignKey},
+        ast::{CheckConstraint, ForeignKey, KeyConstraint},
+        data::Schema,
         error::Result,
     },
     mongodb::options::CreateCollectionOptions,
     serde_json::to_string,
 };
 
-pub fn get_primary_key(column_defs: &[ColumnDef]) -> Option<&ColumnDef> {
-    column_defs
-        .iter()
-        .find(|column_def| column_def.unique.map(|x| x.is_primary).unwrap_or(false))
+pub fn get_primary_key(schema: &Schema) -> Option<Document> {
+    let column_defs = schema.column_defs.as_ref()?;
+
+    schema.primary_key_indexes().map(|indexes| {
+        indexes
+            .into_iter()
+            .map(|i| (column_defs[i].name.clone(), Bson::Int32(1)))
+            .collect()
+    })
 }
 
 pub struct Validator {
@@ -23,7 +29,9 @@ impl Validator {
     pub fn new(
         labels: Vec<String>,
         column_types: Document,
+        key_constraints: Vec<KeyConstraint>,
         foreign_keys: Vec<ForeignKey>,
+        checks: Vec<CheckConstraint>,
         comment: Option<String>,
     ) -> Result<Self> {
         let mut required = vec!["_id".to_owned()];
@@ -37,7 +45,9 @@ impl Validator {
         let additional_properties = matches!(required.len(), 1);
         let table_description = to_string(
             &(TableDescription {
+                key_constraints,
                 foreign_keys,
+                checks,
                 comment,
             }),
         )
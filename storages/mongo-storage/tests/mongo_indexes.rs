@@ -23,9 +23,16 @@ async fn mongo_indexes() {
         "name": { "bsonType": ["string"], "title": "TEXT" },
     };
 
-    let options = Validator::new(labels, column_types, Vec::new(), None)
-        .unwrap()
-        .to_options();
+    let options = Validator::new(
+        labels,
+        column_types,
+        Vec::new(),
+        Vec::new(),
+        Vec::new(),
+        None,
+    )
+    .unwrap()
+    .to_options();
 
     let table_name = "collection_with_composite_index";
 
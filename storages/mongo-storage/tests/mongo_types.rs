@@ -31,9 +31,16 @@ async fn mongo_types() {
         "col_maxKey": { "bsonType": ["maxKey"], "title": "TEXT" },
     };
 
-    let options = Validator::new(labels, column_types, Vec::new(), None)
-        .unwrap()
-        .to_options();
+    let options = Validator::new(
+        labels,
+        column_types,
+        Vec::new(),
+        Vec::new(),
+        Vec::new(),
+        None,
+    )
+    .unwrap()
+    .to_options();
 
     let table_name = "mongo_type_collection";
 
@@ -1,4 +1,5 @@
 use {
+    crate::error::ResultExt,
     gluesql_core::{
         ast::{ColumnDef, ColumnUniqueOption},
         parse_sql::parse_expr,
@@ -76,6 +77,8 @@ impl<'a> TryFrom<ParquetSchemaType<'a>> for ColumnDef {
         let nullable = inner.is_optional();
         let mut unique = None;
         let mut default = None;
+        let mut generated = None;
+        let mut check = None;
         let mut comment = None;
 
         if let Some(metadata) = parquet_col_def.get_metadata().as_deref() {
@@ -102,6 +105,18 @@ impl<'a> TryFrom<ParquetSchemaType<'a>> for ColumnDef {
                             default = Some(tran);
                         }
                     }
+                    k if k == format!("generated_{name}") => {
+                        if let Some(value) = &kv.value {
+                            let parsed = parse_expr(value.clone())?;
+
+                            generated = Some(translate_expr(&parsed)?);
+                        }
+                    }
+                    k if k == format!("check_{name}") => {
+                        if let Some(value) = &kv.value {
+                            check = serde_json::from_str(value).map_storage_err()?;
+                        }
+                    }
                     k if k == format!("comment_{name}") => {
                         if let Some(value) = &kv.value {
                             comment = Some(value.clone());
@@ -116,7 +131,10 @@ impl<'a> TryFrom<ParquetSchemaType<'a>> for ColumnDef {
             data_type,
             nullable,
             default,
+            identity: None,
+            generated,
             unique,
+            check,
             comment,
         })
     }
@@ -2,18 +2,26 @@ use {
     column_def::ParquetSchemaType,
     error::{OptionExt, ParquetStorageError, ResultExt},
     gluesql_core::{
-        ast::{ColumnDef, ColumnUniqueOption, ForeignKey},
+        ast::{CheckConstraint, ColumnDef, ForeignKey, IndexOperator, KeyConstraint},
         data::Schema,
         error::{Error, Result},
         prelude::{DataType, Key, Value},
-        store::{DataRow, Metadata},
+        store::{
+            DataRow, Metadata, ScanFilter, ScanPredicate, Sequence, SequenceMut, Statistics,
+            StatisticsMut, Trigger, TriggerMut, View, ViewMut,
+        },
     },
     parquet::{
-        file::{reader::FileReader, serialized_reader::SerializedFileReader},
+        file::{
+            reader::FileReader, serialized_reader::SerializedFileReader,
+            statistics::Statistics as ColumnStatistics,
+        },
         record::Row,
+        schema::types::Type,
     },
     serde_json::from_str,
     std::{
+        cmp::Ordering,
         collections::BTreeMap,
         fs::{self, File},
         path::{Path, PathBuf},
@@ -61,6 +69,8 @@ impl ParquetStorage {
 
         let mut is_schemaless = false;
         let mut foreign_keys = Vec::new();
+        let mut key_constraints = Vec::new();
+        let mut checks = Vec::new();
         let mut comment = None;
         if let Some(metadata) = key_value_file_metadata {
             for kv in metadata.iter() {
@@ -79,6 +89,28 @@ impl ParquetStorage {
                         .map_storage_err()?;
 
                     foreign_keys.push(fk);
+                } else if kv.key.starts_with("key_constraint") {
+                    let key_constraint = kv
+                        .value
+                        .as_ref()
+                        .map(|x| from_str::<KeyConstraint>(x))
+                        .map_storage_err(Error::StorageMsg(
+                            "No value found on metadata".to_owned(),
+                        ))?
+                        .map_storage_err()?;
+
+                    key_constraints.push(key_constraint);
+                } else if kv.key.starts_with("table_check") {
+                    let check = kv
+                        .value
+                        .as_ref()
+                        .map(|x| from_str::<CheckConstraint>(x))
+                        .map_storage_err(Error::StorageMsg(
+                            "No value found on metadata".to_owned(),
+                        ))?
+                        .map_storage_err()?;
+
+                    checks.push(check);
                 }
             }
         }
@@ -105,7 +137,9 @@ impl ParquetStorage {
             column_defs,
             indexes: vec![],
             engine: None,
+            key_constraints,
             foreign_keys,
+            checks,
             comment,
         }))
     }
@@ -134,21 +168,22 @@ impl ParquetStorage {
         let mut rows = Vec::new();
         let mut key_counter: u64 = 0;
 
-        if let Some(column_defs) = &fetched_schema.column_defs {
+        if fetched_schema.column_defs.is_some() {
+            let primary_key_indexes = fetched_schema.primary_key_indexes();
+
             for record in row_iter {
                 let record: Row = record.map_storage_err()?;
                 let mut row = Vec::new();
-                let mut key = None;
 
                 for (idx, (_, field)) in record.get_column_iter().enumerate() {
                     let value = ParquetField(field.clone()).to_value(&fetched_schema, idx)?;
-                    row.push(value.clone());
-
-                    if column_defs[idx].unique == Some(ColumnUniqueOption { is_primary: true }) {
-                        key = Key::try_from(&value).ok();
-                    }
+                    row.push(value);
                 }
 
+                let key = primary_key_indexes
+                    .as_ref()
+                    .and_then(|indexes| Key::from_columns(indexes, &row).ok());
+
                 let generated_key = key.unwrap_or_else(|| {
                     let generated = Key::U64(key_counter);
                     key_counter += 1;
@@ -178,6 +213,112 @@ impl ParquetStorage {
         Ok((Box::new(rows.into_iter()), fetched_schema))
     }
 
+    /// Scans the rows reading only the columns required by `filter` and the row groups whose
+    /// statistics may satisfy its predicates
+    fn scan_filtered_data(&self, table_name: &str, filter: &ScanFilter) -> Result<RowIter> {
+        let fetched_schema = self.fetch_schema(table_name)?.map_storage_err(
+            ParquetStorageError::TableDoesNotExist(table_name.to_owned()),
+        )?;
+        let Some(column_defs) = fetched_schema.column_defs.as_ref() else {
+            return self.scan_data(table_name).map(|(rows, _)| rows);
+        };
+
+        let file = File::open(self.data_path(table_name)).map_storage_err()?;
+        let parquet_reader = SerializedFileReader::new(file).map_storage_err()?;
+
+        let primary_key_indexes = fetched_schema.primary_key_indexes();
+        let predicates = filter
+            .predicates
+            .iter()
+            .filter_map(|predicate| {
+                column_defs
+                    .iter()
+                    .position(|column_def| column_def.name == predicate.column)
+                    .map(|idx| (idx, predicate))
+            })
+            .collect::<Vec<_>>();
+        let mut projected = column_defs
+            .iter()
+            .enumerate()
+            .filter(|(idx, column_def)| {
+                filter.requires(&column_def.name)
+                    || predicates.iter().any(|(i, _)| i == idx)
+                    || primary_key_indexes
+                        .as_ref()
+                        .is_some_and(|indexes| indexes.contains(idx))
+            })
+            .map(|(idx, _)| idx)
+            .collect::<Vec<_>>();
+        if projected.is_empty() {
+            // rows are still counted when no column is required
+            projected.push(0);
+        }
+
+        let root = parquet_reader.metadata().file_metadata().schema();
+        let fields = root.get_fields();
+        let projection = match projected.len() == fields.len() {
+            true => None,
+            false => Type::group_type_builder(root.name())
+                .with_fields(&mut projected.iter().map(|idx| fields[*idx].clone()).collect())
+                .build()
+                .map(Some)
+                .map_storage_err()?,
+        };
+
+        let mut rows = Vec::new();
+        let mut key_counter: u64 = 0;
+
+        for i in 0..parquet_reader.num_row_groups() {
+            let row_group = parquet_reader.get_row_group(i).map_storage_err()?;
+            let metadata = row_group.metadata();
+            let pruned = predicates.iter().any(|(idx, predicate)| {
+                metadata
+                    .column(*idx)
+                    .statistics()
+                    .is_some_and(|statistics| {
+                        !may_satisfy(&column_defs[*idx].data_type, statistics, predicate)
+                    })
+            });
+
+            if pruned {
+                key_counter += metadata.num_rows() as u64;
+                continue;
+            }
+
+            let row_iter = row_group
+                .get_row_iter(projection.clone())
+                .map_storage_err()?;
+
+            for record in row_iter {
+                let record: Row = record.map_storage_err()?;
+                let mut row = vec![Value::Null; column_defs.len()];
+
+                for (idx, (_, field)) in projected.iter().zip(record.get_column_iter()) {
+                    row[*idx] = ParquetField(field.clone()).to_value(&fetched_schema, *idx)?;
+                }
+
+                let key = primary_key_indexes
+                    .as_ref()
+                    .and_then(|indexes| Key::from_columns(indexes, &row).ok());
+
+                let generated_key = key.unwrap_or_else(|| {
+                    let generated = Key::U64(key_counter);
+                    key_counter += 1;
+                    generated
+                });
+
+                if predicates
+                    .iter()
+                    .all(|(idx, predicate)| predicate.check(&row[*idx]))
+                {
+                    rows.push(Ok((generated_key, DataRow::Vec(row))));
+                }
+            }
+        }
+
+        Ok(Box::new(rows.into_iter()))
+    }
+
     fn generate_temp_schema() -> Schema {
         Schema {
             table_name: "temporary".to_owned(),
@@ -186,15 +327,84 @@ impl ParquetStorage {
                 data_type: DataType::Map,
                 nullable: true,
                 default: None,
+                identity: None,
+                generated: None,
                 unique: None,
+                check: None,
                 comment: None,
             }]),
             indexes: vec![],
             engine: None,
+            key_constraints: Vec::new(),
             foreign_keys: Vec::new(),
+            checks: Vec::new(),
             comment: None,
         }
     }
 }
 
+/// Whether a row group with the column `statistics` may have values satisfying `predicate`,
+/// `true` when the statistics cannot be compared with it
+fn may_satisfy(
+    data_type: &DataType,
+    statistics: &ColumnStatistics,
+    predicate: &ScanPredicate,
+) -> bool {
+    if !statistics.has_min_max_set() {
+        return true;
+    }
+
+    let (min, max) = match (data_type, statistics) {
+        (DataType::Boolean, ColumnStatistics::Boolean(s)) => {
+            (Value::Bool(*s.min()), Value::Bool(*s.max()))
+        }
+        (DataType::Int8, ColumnStatistics::Int32(s)) => {
+            (Value::I8(*s.min() as i8), Value::I8(*s.max() as i8))
+        }
+        (DataType::Int16, ColumnStatistics::Int32(s)) => {
+            (Value::I16(*s.min() as i16), Value::I16(*s.max() as i16))
+        }
+        (DataType::Int32, ColumnStatistics::Int32(s)) => {
+            (Value::I32(*s.min()), Value::I32(*s.max()))
+        }
+        (DataType::Int, ColumnStatistics::Int64(s)) => (Value::I64(*s.min()), Value::I64(*s.max())),
+        (DataType::Float32, ColumnStatistics::Float(s)) => {
+            (Value::F32(*s.min()), Value::F32(*s.max()))
+        }
+        (DataType::Float, ColumnStatistics::Double(s)) => {
+            (Value::F64(*s.min()), Value::F64(*s.max()))
+        }
+        (DataType::Text, ColumnStatistics::ByteArray(s)) => {
+            match (s.min().as_utf8(), s.max().as_utf8()) {
+                (Ok(min), Ok(max)) => (Value::Str(min.to_owned()), Value::Str(max.to_owned())),
+                _ => return true,
+            }
+        }
+        _ => return true,
+    };
+
+    let (Some(min), Some(max)) = (
+        min.evaluate_cmp(&predicate.value),
+        max.evaluate_cmp(&predicate.value),
+    ) else {
+        return true;
+    };
+
+    match predicate.op {
+        IndexOperator::Eq => min != Ordering::Greater && max != Ordering::Less,
+        IndexOperator::Gt => max == Ordering::Greater,
+        IndexOperator::GtEq => max != Ordering::Less,
+        IndexOperator::Lt => min == Ordering::Less,
+        IndexOperator::LtEq => min != Ordering::Greater,
+    }
+}
+
 impl Metadata for ParquetStorage {}
+impl View for ParquetStorage {}
+impl ViewMut for ParquetStorage {}
+impl Statistics for ParquetStorage {}
+impl StatisticsMut for ParquetStorage {}
+impl Sequence for ParquetStorage {}
+impl SequenceMut for ParquetStorage {}
+impl Trigger for ParquetStorage {}
+impl TriggerMut for ParquetStorage {}
@@ -8,7 +8,7 @@ use {
     gluesql_core::{
         data::{Key, Schema},
         error::Result,
-        store::{DataRow, RowIter, Store},
+        store::{DataRow, RowIter, ScanFilter, Store},
     },
     std::{ffi::OsStr, fs},
 };
@@ -58,4 +58,13 @@ impl Store for ParquetStorage {
         let rows = self.scan_data(table_name)?.0;
         Ok(Box::pin(iter(rows)))
     }
+
+    async fn scan_data_filtered<'a>(
+        &'a self,
+        table_name: &str,
+        filter: &ScanFilter,
+    ) -> Result<RowIter<'a>> {
+        let rows = self.scan_filtered_data(table_name, filter)?;
+        Ok(Box::pin(iter(rows)))
+    }
 }
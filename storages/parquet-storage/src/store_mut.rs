@@ -417,7 +417,10 @@ impl ParquetStorage {
                     data_type: DataType::Map,
                     nullable: true,
                     default: None,
+                    identity: None,
+                    generated: None,
                     unique: None,
+                    check: None,
                     comment: None,
                 }]
             }
@@ -465,6 +468,20 @@ impl ParquetStorage {
             });
         }
 
+        for (i, key_constraint) in schema.key_constraints.iter().enumerate() {
+            metadata.push(KeyValue {
+                key: format!("key_constraint_{i}"),
+                value: Some(serde_json::to_string(&key_constraint).map_storage_err()?),
+            });
+        }
+
+        for (i, check) in schema.checks.iter().enumerate() {
+            metadata.push(KeyValue {
+                key: format!("table_check_{i}"),
+                value: Some(serde_json::to_string(&check).map_storage_err()?),
+            });
+        }
+
         if let Some(column_defs) = &schema.column_defs {
             for column_def in column_defs {
                 if let Some(unique_option) = &column_def.unique {
@@ -485,6 +502,20 @@ impl ParquetStorage {
                     });
                 }
 
+                if let Some(generated) = &column_def.generated {
+                    metadata.push(KeyValue {
+                        key: format!("generated_{}", column_def.name),
+                        value: Some(ToSql::to_sql(generated)),
+                    });
+                }
+
+                if let Some(check) = &column_def.check {
+                    metadata.push(KeyValue {
+                        key: format!("check_{}", column_def.name),
+                        value: Some(serde_json::to_string(check).map_storage_err()?),
+                    });
+                }
+
                 if let Some(comment) = &column_def.comment {
                     metadata.push(KeyValue {
                         key: format!("comment_{}", column_def.name),
@@ -0,0 +1,96 @@
+use {
+    futures::TryStreamExt,
+    gluesql_core::{
+        ast::IndexOperator,
+        prelude::{Glue, Key, Value},
+        store::{DataRow, ScanFilter, ScanPredicate, Store},
+    },
+    gluesql_parquet_storage::ParquetStorage,
+    std::fs::remove_dir_all,
+};
+
+#[tokio::test]
+async fn scan_data_filtered() {
+    let path = "tmp/scan_data_filtered";
+    let _ = remove_dir_all(path);
+
+    let mut glue = Glue::new(ParquetStorage::new(path).unwrap());
+    glue.execute(
+        "
+        CREATE TABLE Item (id INTEGER, name TEXT, price FLOAT);
+        INSERT INTO Item VALUES (1, 'a', 1.5), (2, 'b', NULL), (3, 'c', 3.5);
+        ",
+    )
+    .await
+    .unwrap();
+
+    let storage = glue.storage;
+    let scan = |filter: ScanFilter| {
+        let storage = &storage;
+
+        async move {
+            storage
+                .scan_data_filtered("Item", &filter)
+                .await
+                .unwrap()
+                .try_collect::<Vec<_>>()
+                .await
+                .unwrap()
+        }
+    };
+    let predicate = |column: &str, op, value| ScanPredicate {
+        column: column.to_owned(),
+        op,
+        value,
+    };
+    let row = |key, values| (Key::U64(key), DataRow::Vec(values));
+
+    assert_eq!(
+        scan(ScanFilter {
+            columns: Some(vec!["name".to_owned()]),
+            predicates: vec![predicate("id", IndexOperator::GtEq, Value::I64(2))],
+        })
+        .await,
+        vec![
+            row(
+                1,
+                vec![Value::I64(2), Value::Str("b".to_owned()), Value::Null]
+            ),
+            row(
+                2,
+                vec![Value::I64(3), Value::Str("c".to_owned()), Value::Null]
+            ),
+        ],
+        "only the required columns are read"
+    );
+    assert_eq!(
+        scan(ScanFilter {
+            columns: None,
+            predicates: vec![predicate("price", IndexOperator::Lt, Value::F64(2.0))],
+        })
+        .await,
+        vec![row(
+            0,
+            vec![Value::I64(1), Value::Str("a".to_owned()), Value::F64(1.5)]
+        )],
+        "NULL does not satisfy predicates"
+    );
+    assert_eq!(
+        scan(ScanFilter {
+            columns: Some(Vec::new()),
+            predicates: vec![predicate("id", IndexOperator::Gt, Value::I64(3))],
+        })
+        .await,
+        Vec::new(),
+        "row groups out of the range are skipped"
+    );
+    assert_eq!(
+        scan(ScanFilter {
+            columns: Some(Vec::new()),
+            predicates: Vec::new(),
+        })
+        .await
+        .len(),
+        3
+    );
+}
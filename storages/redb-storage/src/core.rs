@@ -4,7 +4,7 @@ use {
     bincode::{deserialize, serialize},
     futures::stream::iter,
     gluesql_core::{
-        data::{Key, Schema},
+        data::{Key, Schema, Sequence},
         store::{DataRow, RowIter},
     },
     redb::{Database, ReadableTable, TableDefinition, WriteTransaction},
@@ -14,6 +14,8 @@ use {
 
 const SCHEMA_TABLE_NAME: &str = "__SCHEMA__";
 const SCHEMA_TABLE: TableDefinition<&str, Vec<u8>> = TableDefinition::new(SCHEMA_TABLE_NAME);
+const SEQUENCE_TABLE_NAME: &str = "__SEQUENCE__";
+const SEQUENCE_TABLE: TableDefinition<&str, Vec<u8>> = TableDefinition::new(SEQUENCE_TABLE_NAME);
 
 type Result<T> = std::result::Result<T, StorageError>;
 
@@ -22,6 +24,32 @@ pub enum TransactionState {
     Active {
         txn: Box<WriteTransaction>,
         autocommit: bool,
+        savepoints: Vec<Savepoint>,
+    },
+}
+
+/// Savepoint of a write transaction, undoing the changes made after it in reverse order
+pub struct Savepoint {
+    name: String,
+    sequences: Vec<(String, Vec<u8>)>,
+    undo: Vec<Undo>,
+}
+
+/// Previous state of a table or a row changed after a savepoint was made
+enum Undo {
+    Schema {
+        table_name: String,
+        schema: Option<Vec<u8>>,
+    },
+    Table {
+        table_name: String,
+        schema: Vec<u8>,
+        rows: Vec<(Vec<u8>, Vec<u8>)>,
+    },
+    Data {
+        table_name: String,
+        key: Vec<u8>,
+        row: Option<Vec<u8>>,
     },
 }
 
@@ -51,7 +79,7 @@ impl StorageCore {
         &self,
         table_name: &'a str,
     ) -> Result<TableDefinition<'a, &'static [u8], Vec<u8>>> {
-        if table_name == SCHEMA_TABLE_NAME {
+        if [SCHEMA_TABLE_NAME, SEQUENCE_TABLE_NAME].contains(&table_name) {
             return Err(StorageError::ReservedTableName(table_name.to_owned()));
         }
 
@@ -72,6 +100,19 @@ impl StorageCore {
         }
     }
 
+    /// Keeps `undo` for the latest savepoint, if there is one.
+    fn push_undo(&mut self, undo: Undo) {
+        if let TransactionState::Active { savepoints, .. } = &mut self.state
+            && let Some(savepoint) = savepoints.last_mut()
+        {
+            savepoint.undo.push(undo);
+        }
+    }
+
+    fn has_savepoint(&self) -> bool {
+        matches!(&self.state, TransactionState::Active { savepoints, .. } if !savepoints.is_empty())
+    }
+
     fn take_txn(&mut self) -> Option<WriteTransaction> {
         match std::mem::replace(&mut self.state, TransactionState::None) {
             TransactionState::Active { txn, .. } => Some(*txn),
@@ -131,7 +172,10 @@ impl StorageCore {
     }
 
     pub fn scan_data<'a>(&'a self, table_name: &str) -> Result<RowIter<'a>> {
-        if let TransactionState::Active { autocommit, txn } = &self.state {
+        if let TransactionState::Active {
+            autocommit, txn, ..
+        } = &self.state
+        {
             if !autocommit {
                 let table_def = self.data_table_def(table_name)?;
                 let table = txn.open_table(table_def)?;
@@ -171,67 +215,177 @@ impl StorageCore {
 impl StorageCore {
     pub async fn insert_schema(&mut self, schema: &Schema) -> Result<()> {
         let data_def = self.data_table_def(&schema.table_name)?;
+        let has_savepoint = self.has_savepoint();
         let txn = self.txn_mut()?;
         let mut table = txn.open_table(SCHEMA_TABLE)?;
         let value = serialize(&schema)?;
-        table.insert(schema.table_name.as_str(), value)?;
+        let prev = table
+            .insert(schema.table_name.as_str(), value)?
+            .map(|v| v.value());
+        drop(table);
         txn.open_table(data_def)?;
 
+        if has_savepoint {
+            self.push_undo(Undo::Schema {
+                table_name: schema.table_name.clone(),
+                schema: prev,
+            });
+        }
+
         Ok(())
     }
 
     pub async fn delete_schema(&mut self, table_name: &str) -> Result<()> {
         let table_def = self.data_table_def(table_name)?;
+        let has_savepoint = self.has_savepoint();
         let txn = self.txn_mut()?;
         let mut table = txn.open_table(SCHEMA_TABLE)?;
-        table.remove(table_name)?;
+        let prev = table.remove(table_name)?.map(|v| v.value());
+        drop(table);
+
+        let rows = match (has_savepoint, &prev) {
+            (true, Some(_)) => txn
+                .open_table(table_def)?
+                .iter()?
+                .map(|entry| {
+                    let (key, value) = entry?;
+
+                    Ok((key.value().to_vec(), value.value()))
+                })
+                .collect::<Result<Vec<_>>>()?,
+            _ => Vec::new(),
+        };
         txn.delete_table(table_def)?;
 
+        if has_savepoint && let Some(schema) = prev {
+            self.push_undo(Undo::Table {
+                table_name: table_name.to_owned(),
+                schema,
+                rows,
+            });
+        }
+
         Ok(())
     }
 
     pub async fn append_data(&mut self, table_name: &str, rows: Vec<DataRow>) -> Result<()> {
         let table_def = self.data_table_def(table_name)?;
+        let has_savepoint = self.has_savepoint();
         let txn = self.txn_mut()?;
         let mut table = txn.open_table(table_def)?;
+        let mut prev_rows = Vec::new();
 
         for row in rows {
             let key = Key::Uuid(Uuid::now_v7().as_u128());
             let value = serialize(&(&key, row))?;
             let table_key = key.to_cmp_be_bytes()?;
-            let table_key = table_key.as_slice();
-            table.insert(table_key, value)?;
+            table.insert(table_key.as_slice(), value)?;
+
+            if has_savepoint {
+                prev_rows.push((table_key, None));
+            }
         }
 
+        drop(table);
+        self.push_data_undo(table_name, prev_rows);
+
         Ok(())
     }
 
     pub async fn insert_data(&mut self, table_name: &str, rows: Vec<(Key, DataRow)>) -> Result<()> {
         let table_def = self.data_table_def(table_name)?;
+        let has_savepoint = self.has_savepoint();
         let txn = self.txn_mut()?;
         let mut table = txn.open_table(table_def)?;
+        let mut prev_rows = Vec::new();
 
         for (key, row) in rows {
             let value = serialize(&(&key, row))?;
             let table_key = key.to_cmp_be_bytes()?;
-            let table_key = table_key.as_slice();
-            table.insert(table_key, value)?;
+            let prev = table
+                .insert(table_key.as_slice(), value)?
+                .map(|v| v.value());
+
+            if has_savepoint {
+                prev_rows.push((table_key, prev));
+            }
         }
 
+        drop(table);
+        self.push_data_undo(table_name, prev_rows);
+
         Ok(())
     }
 
     pub async fn delete_data(&mut self, table_name: &str, keys: Vec<Key>) -> Result<()> {
         let table_def = self.data_table_def(table_name)?;
+        let has_savepoint = self.has_savepoint();
         let txn = self.txn_mut()?;
         let mut table = txn.open_table(table_def)?;
+        let mut prev_rows = Vec::new();
 
         for key in keys {
             let table_key = key.to_cmp_be_bytes()?;
-            let table_key = table_key.as_slice();
-            table.remove(table_key)?;
+            let prev = table.remove(table_key.as_slice())?.map(|v| v.value());
+
+            if has_savepoint {
+                prev_rows.push((table_key, prev));
+            }
         }
 
+        drop(table);
+        self.push_data_undo(table_name, prev_rows);
+
+        Ok(())
+    }
+
+    fn push_data_undo(&mut self, table_name: &str, prev_rows: Vec<(Vec<u8>, Option<Vec<u8>>)>) {
+        for (key, row) in prev_rows {
+            self.push_undo(Undo::Data {
+                table_name: table_name.to_owned(),
+                key,
+                row,
+            });
+        }
+    }
+}
+
+// Sequence
+impl StorageCore {
+    pub fn fetch_sequence(&self, sequence_name: &str) -> Result<Option<Sequence>> {
+        let sequence = match &self.state {
+            TransactionState::Active { txn, .. } => txn
+                .open_table(SEQUENCE_TABLE)?
+                .get(sequence_name)?
+                .map(|v| deserialize(&v.value())),
+            TransactionState::None => self
+                .db
+                .begin_write()?
+                .open_table(SEQUENCE_TABLE)?
+                .get(sequence_name)?
+                .map(|v| deserialize(&v.value())),
+        }
+        .transpose()?;
+
+        Ok(sequence)
+    }
+
+    /// Writes through the shared reference as well, `NEXTVAL` stores the advanced sequence
+    /// in the current write transaction.
+    pub fn put_sequence(&self, sequence: &Sequence) -> Result<()> {
+        let txn = self.txn()?;
+        let mut table = txn.open_table(SEQUENCE_TABLE)?;
+        let value = serialize(sequence)?;
+        table.insert(sequence.name.as_str(), value)?;
+
+        Ok(())
+    }
+
+    pub fn delete_sequence(&mut self, sequence_name: &str) -> Result<()> {
+        let txn = self.txn_mut()?;
+        let mut table = txn.open_table(SEQUENCE_TABLE)?;
+        table.remove(sequence_name)?;
+
         Ok(())
     }
 }
@@ -249,6 +403,7 @@ impl StorageCore {
                 self.state = TransactionState::Active {
                     txn: Box::new(write_txn),
                     autocommit,
+                    savepoints: Vec::new(),
                 };
 
                 Ok(autocommit)
@@ -271,4 +426,138 @@ impl StorageCore {
 
         Ok(())
     }
+
+    pub fn savepoint(&mut self, name: &str) -> Result<()> {
+        let sequences = self
+            .txn()?
+            .open_table(SEQUENCE_TABLE)?
+            .iter()?
+            .map(|entry| {
+                let (name, value) = entry?;
+
+                Ok((name.value().to_owned(), value.value()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        if let TransactionState::Active { savepoints, .. } = &mut self.state {
+            savepoints.push(Savepoint {
+                name: name.to_owned(),
+                sequences,
+                undo: Vec::new(),
+            });
+        }
+
+        Ok(())
+    }
+
+    pub fn rollback_to_savepoint(&mut self, name: &str) -> Result<()> {
+        let (txn, savepoints) = self.savepoints(name)?;
+        let depth = find_savepoint(savepoints, name)?;
+
+        for savepoint in savepoints.drain(depth + 1..).rev() {
+            undo(txn, savepoint.undo)?;
+        }
+
+        let savepoint = &mut savepoints[depth];
+        undo(txn, std::mem::take(&mut savepoint.undo))?;
+
+        let mut table = txn.open_table(SEQUENCE_TABLE)?;
+        let sequence_names = table
+            .iter()?
+            .map(|entry| Ok(entry?.0.value().to_owned()))
+            .collect::<Result<Vec<_>>>()?;
+        for sequence_name in sequence_names {
+            table.remove(sequence_name.as_str())?;
+        }
+        for (sequence_name, value) in savepoint.sequences.iter() {
+            table.insert(sequence_name.as_str(), value.clone())?;
+        }
+
+        Ok(())
+    }
+
+    pub fn release_savepoint(&mut self, name: &str) -> Result<()> {
+        let (_, savepoints) = self.savepoints(name)?;
+        let depth = find_savepoint(savepoints, name)?;
+        let released = savepoints
+            .drain(depth..)
+            .flat_map(|savepoint| savepoint.undo)
+            .collect::<Vec<_>>();
+
+        if let Some(parent) = savepoints.last_mut() {
+            parent.undo.extend(released);
+        }
+
+        Ok(())
+    }
+
+    fn savepoints(&mut self, name: &str) -> Result<(&mut WriteTransaction, &mut Vec<Savepoint>)> {
+        match &mut self.state {
+            TransactionState::Active {
+                txn, savepoints, ..
+            } => Ok((txn.as_mut(), savepoints)),
+            TransactionState::None => Err(StorageError::SavepointNotFound(name.to_owned())),
+        }
+    }
+}
+
+/// Returns the depth of the latest savepoint named `name`.
+fn find_savepoint(savepoints: &[Savepoint], name: &str) -> Result<usize> {
+    savepoints
+        .iter()
+        .rposition(|savepoint| savepoint.name == name)
+        .ok_or_else(|| StorageError::SavepointNotFound(name.to_owned()))
+}
+
+fn data_table(table_name: &str) -> TableDefinition<'_, &'static [u8], Vec<u8>> {
+    TableDefinition::new(table_name)
+}
+
+fn undo(txn: &mut WriteTransaction, undo: Vec<Undo>) -> Result<()> {
+    for undo in undo.into_iter().rev() {
+        match undo {
+            Undo::Schema { table_name, schema } => {
+                let mut table = txn.open_table(SCHEMA_TABLE)?;
+
+                match schema {
+                    Some(schema) => {
+                        table.insert(table_name.as_str(), schema)?;
+                    }
+                    None => {
+                        table.remove(table_name.as_str())?;
+                        drop(table);
+
+                        txn.delete_table(data_table(&table_name))?;
+                    }
+                }
+            }
+            Undo::Table {
+                table_name,
+                schema,
+                rows,
+            } => {
+                txn.open_table(SCHEMA_TABLE)?
+                    .insert(table_name.as_str(), schema)?;
+
+                let mut table = txn.open_table(data_table(&table_name))?;
+                for (key, row) in rows {
+                    table.insert(key.as_slice(), row)?;
+                }
+            }
+            Undo::Data {
+                table_name,
+                key,
+                row,
+            } => {
+                let mut table = txn.open_table(data_table(&table_name))?;
+
+                match row {
+                    Some(row) => table.insert(key.as_slice(), row)?,
+                    None => table.remove(key.as_slice())?,
+                };
+            }
+        }
+    }
+
+    Ok(())
 }
@@ -6,6 +6,8 @@ pub enum StorageError {
     NestedTransactionNotSupported,
     #[error("transaction not found")]
     TransactionNotFound,
+    #[error("savepoint not found: {0}")]
+    SavepointNotFound(String),
     #[error("cannot create table with reserved name: {0}")]
     ReservedTableName(String),
 
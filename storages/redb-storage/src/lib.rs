@@ -7,11 +7,12 @@ use {
     async_trait::async_trait,
     core::StorageCore,
     gluesql_core::{
-        data::{Key, Schema},
+        data::{Key, Schema, Sequence as StructSequence},
         error::Result,
         store::{
             AlterTable, CustomFunction, CustomFunctionMut, DataRow, Index, IndexMut, Metadata,
-            RowIter, Store, StoreMut, Transaction,
+            RowIter, Sequence, SequenceMut, Statistics, StatisticsMut, Store, StoreMut,
+            Transaction, Trigger, TriggerMut, View, ViewMut,
         },
     },
     redb::Database,
@@ -94,6 +95,47 @@ impl Transaction for RedbStorage {
     async fn commit(&mut self) -> Result<()> {
         self.0.commit().map_err(Into::into)
     }
+
+    async fn savepoint(&mut self, name: &str) -> Result<()> {
+        self.0.savepoint(name).map_err(Into::into)
+    }
+
+    async fn rollback_to_savepoint(&mut self, name: &str) -> Result<()> {
+        self.0.rollback_to_savepoint(name).map_err(Into::into)
+    }
+
+    async fn release_savepoint(&mut self, name: &str) -> Result<()> {
+        self.0.release_savepoint(name).map_err(Into::into)
+    }
+}
+
+#[async_trait]
+impl Sequence for RedbStorage {
+    async fn fetch_sequence(&self, sequence_name: &str) -> Result<Option<StructSequence>> {
+        self.0.fetch_sequence(sequence_name).map_err(Into::into)
+    }
+
+    async fn next_sequence_value(&self, sequence_name: &str) -> Result<Option<i64>> {
+        let Some(mut sequence) = self.0.fetch_sequence(sequence_name)? else {
+            return Ok(None);
+        };
+
+        let value = sequence.advance()?;
+        self.0.put_sequence(&sequence)?;
+
+        Ok(Some(value))
+    }
+}
+
+#[async_trait]
+impl SequenceMut for RedbStorage {
+    async fn insert_sequence(&mut self, sequence: StructSequence) -> Result<()> {
+        self.0.put_sequence(&sequence).map_err(Into::into)
+    }
+
+    async fn delete_sequence(&mut self, sequence_name: &str) -> Result<()> {
+        self.0.delete_sequence(sequence_name).map_err(Into::into)
+    }
 }
 
 impl AlterTable for RedbStorage {}
@@ -102,3 +144,9 @@ impl IndexMut for RedbStorage {}
 impl Metadata for RedbStorage {}
 impl CustomFunction for RedbStorage {}
 impl CustomFunctionMut for RedbStorage {}
+impl View for RedbStorage {}
+impl ViewMut for RedbStorage {}
+impl Statistics for RedbStorage {}
+impl StatisticsMut for RedbStorage {}
+impl Trigger for RedbStorage {}
+impl TriggerMut for RedbStorage {}
@@ -106,7 +106,10 @@ impl AlterTable for RedisStorage {
                 }
                 (None, true) => Value::Null,
                 (None, false) => {
-                    return Err(AlterTableError::DefaultValueRequired(column_def.clone()).into());
+                    return Err(AlterTableError::DefaultValueRequired(Box::new(
+                        column_def.clone(),
+                    ))
+                    .into());
                 }
             };
 
@@ -18,6 +18,7 @@ impl Index for RedisStorage {
         _table_name: &str,
         _index_name: &str,
         _asc: Option<bool>,
+        _prefix: &[Value],
         _cmp_value: Option<(&IndexOperator, Value)>,
     ) -> Result<RowIter<'a>> {
         Err(Error::StorageMsg(
@@ -32,7 +33,8 @@ impl IndexMut for RedisStorage {
         &mut self,
         _table_name: &str,
         _index_name: &str,
-        _column: &OrderByExpr,
+        _columns: &[OrderByExpr],
+        _unique: bool,
     ) -> Result<()> {
         Err(Error::StorageMsg(
             "[RedisStorage] index is not supported".to_owned(),
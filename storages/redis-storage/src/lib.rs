@@ -14,7 +14,10 @@ use {
         chrono::Utc,
         data::{CustomFunction as StructCustomFunction, Key, Schema, Value},
         error::{Error, Result},
-        store::{CustomFunction, CustomFunctionMut, DataRow, RowIter, Store, StoreMut},
+        store::{
+            CustomFunction, CustomFunctionMut, DataRow, RowIter, Sequence, SequenceMut, Statistics,
+            StatisticsMut, Store, StoreMut, Trigger, TriggerMut, View, ViewMut,
+        },
     },
     redis::{Commands, Connection},
     std::{collections::BTreeMap, sync::Mutex},
@@ -228,6 +231,15 @@ impl CustomFunctionMut for RedisStorage {
     }
 }
 
+impl View for RedisStorage {}
+impl ViewMut for RedisStorage {}
+impl Statistics for RedisStorage {}
+impl StatisticsMut for RedisStorage {}
+impl Sequence for RedisStorage {}
+impl SequenceMut for RedisStorage {}
+impl Trigger for RedisStorage {}
+impl TriggerMut for RedisStorage {}
+
 #[async_trait]
 impl Store for RedisStorage {
     async fn fetch_all_schemas(&self) -> Result<Vec<Schema>> {
@@ -16,6 +16,7 @@ impl Index for SharedMemoryStorage {
         _table_name: &str,
         _index_name: &str,
         _asc: Option<bool>,
+        _prefix: &[Value],
         _cmp_value: Option<(&IndexOperator, Value)>,
     ) -> Result<RowIter<'a>> {
         Err(Error::StorageMsg(
@@ -30,7 +31,8 @@ impl IndexMut for SharedMemoryStorage {
         &mut self,
         _table_name: &str,
         _index_name: &str,
-        _column: &OrderByExpr,
+        _columns: &[OrderByExpr],
+        _unique: bool,
     ) -> Result<()> {
         Err(Error::StorageMsg(
             "[Shared MemoryStorage] index is not supported".to_owned(),
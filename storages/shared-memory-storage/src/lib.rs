@@ -8,18 +8,32 @@ use {
     async_trait::async_trait,
     futures::stream,
     gluesql_core::{
-        data::{Key, Schema},
+        data::{
+            Key, Schema, Sequence as StructSequence, TableStatistics, Trigger as StructTrigger,
+            View as StructView,
+        },
         error::Result,
-        store::{DataRow, Metadata, RowIter, Store, StoreMut},
+        store::{
+            DataRow, Metadata, RowIter, Sequence, SequenceMut, Statistics, StatisticsMut, Store,
+            StoreMut, Trigger, TriggerMut, View, ViewMut,
+        },
     },
     gluesql_memory_storage::MemoryStorage,
-    std::sync::Arc,
-    tokio::sync::RwLock,
+    std::{
+        ops::{Deref, DerefMut},
+        sync::Arc,
+    },
+    tokio::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard},
+    transaction::{Draw, Draws},
 };
 
 #[derive(Clone, Debug)]
 pub struct SharedMemoryStorage {
     pub database: Arc<RwLock<MemoryStorage>>,
+    /// Copy of `database` the transaction in progress works on until it commits
+    transaction: Option<Box<MemoryStorage>>,
+    /// Keys and sequence values the transaction in progress has drawn from `database`
+    draws: Draws,
 }
 
 impl SharedMemoryStorage {
@@ -27,7 +41,66 @@ impl SharedMemoryStorage {
         let database = MemoryStorage::default();
         let database = Arc::new(RwLock::new(database));
 
-        Self { database }
+        Self {
+            database,
+            transaction: None,
+            draws: Draws::default(),
+        }
+    }
+
+    async fn read(&self) -> DatabaseRef<'_> {
+        match &self.transaction {
+            Some(transaction) => DatabaseRef::Transaction(transaction),
+            None => DatabaseRef::Shared(self.database.read().await),
+        }
+    }
+
+    async fn write(&mut self) -> DatabaseMut<'_> {
+        match &mut self.transaction {
+            Some(transaction) => DatabaseMut::Transaction(transaction),
+            None => DatabaseMut::Shared(self.database.write().await),
+        }
+    }
+}
+
+enum DatabaseRef<'a> {
+    Shared(RwLockReadGuard<'a, MemoryStorage>),
+    Transaction(&'a MemoryStorage),
+}
+
+impl Deref for DatabaseRef<'_> {
+    type Target = MemoryStorage;
+
+    fn deref(&self) -> &MemoryStorage {
+        match self {
+            Self::Shared(database) => database,
+            Self::Transaction(database) => database,
+        }
+    }
+}
+
+enum DatabaseMut<'a> {
+    Shared(RwLockWriteGuard<'a, MemoryStorage>),
+    Transaction(&'a mut MemoryStorage),
+}
+
+impl Deref for DatabaseMut<'_> {
+    type Target = MemoryStorage;
+
+    fn deref(&self) -> &MemoryStorage {
+        match self {
+            Self::Shared(database) => database,
+            Self::Transaction(database) => database,
+        }
+    }
+}
+
+impl DerefMut for DatabaseMut<'_> {
+    fn deref_mut(&mut self) -> &mut MemoryStorage {
+        match self {
+            Self::Shared(database) => database,
+            Self::Transaction(database) => database,
+        }
     }
 }
 
@@ -40,38 +113,48 @@ impl Default for SharedMemoryStorage {
 impl From<MemoryStorage> for SharedMemoryStorage {
     fn from(storage: MemoryStorage) -> Self {
         let database = Arc::new(RwLock::new(storage));
-        Self { database }
+        Self {
+            database,
+            transaction: None,
+            draws: Draws::default(),
+        }
     }
 }
 
 #[async_trait]
 impl Store for SharedMemoryStorage {
     async fn fetch_all_schemas(&self) -> Result<Vec<Schema>> {
-        let database = Arc::clone(&self.database);
-        let database = database.read().await;
+        let database = self.read().await;
 
         database.fetch_all_schemas().await
     }
     async fn fetch_schema(&self, table_name: &str) -> Result<Option<Schema>> {
-        let database = Arc::clone(&self.database);
-        let database = database.read().await;
+        let database = self.read().await;
 
         database.fetch_schema(table_name).await
     }
 
     async fn fetch_data(&self, table_name: &str, key: &Key) -> Result<Option<DataRow>> {
-        let database = Arc::clone(&self.database);
-        let database = database.read().await;
+        let database = self.read().await;
 
         database.fetch_data(table_name, key).await
     }
 
     async fn scan_data<'a>(&'a self, table_name: &str) -> Result<RowIter<'a>> {
+        let rows = self.read().await.scan_data(table_name).into_iter().map(Ok);
+
+        Ok(Box::pin(stream::iter(rows)))
+    }
+
+    async fn scan_data_by_prefix<'a>(
+        &'a self,
+        table_name: &str,
+        prefix: &[Key],
+    ) -> Result<RowIter<'a>> {
         let rows = self
-            .database
             .read()
             .await
-            .scan_data(table_name)
+            .scan_data_by_prefix(table_name, prefix)
             .into_iter()
             .map(Ok);
 
@@ -82,36 +165,50 @@ impl Store for SharedMemoryStorage {
 #[async_trait]
 impl StoreMut for SharedMemoryStorage {
     async fn insert_schema(&mut self, schema: &Schema) -> Result<()> {
-        let database = Arc::clone(&self.database);
-        let mut database = database.write().await;
+        let mut database = self.write().await;
 
         database.insert_schema(schema).await
     }
 
     async fn delete_schema(&mut self, table_name: &str) -> Result<()> {
-        let database = Arc::clone(&self.database);
-        let mut database = database.write().await;
+        let mut database = self.write().await;
 
         database.delete_schema(table_name).await
     }
 
     async fn append_data(&mut self, table_name: &str, rows: Vec<DataRow>) -> Result<()> {
-        let database = Arc::clone(&self.database);
-        let mut database = database.write().await;
+        let Some(transaction) = &mut self.transaction else {
+            let mut database = self.database.write().await;
+
+            return database.append_data(table_name, rows).await;
+        };
+
+        let id_counter = {
+            let mut database = self.database.write().await;
+            let id_counter = database.id_counter;
+            database.id_counter += rows.len() as i64;
+            self.draws.push(Draw::Keys {
+                from: id_counter,
+                to: database.id_counter,
+            })?;
 
-        database.append_data(table_name, rows).await
+            id_counter
+        };
+        let keys = (id_counter + 1..).map(Key::I64);
+
+        transaction
+            .insert_data(table_name, keys.zip(rows).collect())
+            .await
     }
 
     async fn insert_data(&mut self, table_name: &str, rows: Vec<(Key, DataRow)>) -> Result<()> {
-        let database = Arc::clone(&self.database);
-        let mut database = database.write().await;
+        let mut database = self.write().await;
 
         database.insert_data(table_name, rows).await
     }
 
     async fn delete_data(&mut self, table_name: &str, keys: Vec<Key>) -> Result<()> {
-        let database = Arc::clone(&self.database);
-        let mut database = database.write().await;
+        let mut database = self.write().await;
 
         database.delete_data(table_name, keys).await
     }
@@ -120,3 +217,143 @@ impl StoreMut for SharedMemoryStorage {
 impl Metadata for SharedMemoryStorage {}
 impl gluesql_core::store::CustomFunction for SharedMemoryStorage {}
 impl gluesql_core::store::CustomFunctionMut for SharedMemoryStorage {}
+
+#[async_trait]
+impl View for SharedMemoryStorage {
+    async fn fetch_view(&self, view_name: &str) -> Result<Option<StructView>> {
+        let database = self.read().await;
+
+        database.fetch_view(view_name).await
+    }
+
+    async fn fetch_all_views(&self) -> Result<Vec<StructView>> {
+        let database = self.read().await;
+
+        database.fetch_all_views().await
+    }
+}
+
+#[async_trait]
+impl ViewMut for SharedMemoryStorage {
+    async fn insert_view(&mut self, view: StructView) -> Result<()> {
+        let mut database = self.write().await;
+
+        database.insert_view(view).await
+    }
+
+    async fn delete_view(&mut self, view_name: &str) -> Result<()> {
+        let mut database = self.write().await;
+
+        database.delete_view(view_name).await
+    }
+}
+
+#[async_trait]
+impl Statistics for SharedMemoryStorage {
+    async fn fetch_statistics(&self, table_name: &str) -> Result<Option<TableStatistics>> {
+        let database = self.read().await;
+
+        database.fetch_statistics(table_name).await
+    }
+}
+
+#[async_trait]
+impl StatisticsMut for SharedMemoryStorage {
+    async fn insert_statistics(&mut self, statistics: TableStatistics) -> Result<()> {
+        let mut database = self.write().await;
+
+        database.insert_statistics(statistics).await
+    }
+
+    async fn delete_statistics(&mut self, table_name: &str) -> Result<()> {
+        let mut database = self.write().await;
+
+        database.delete_statistics(table_name).await
+    }
+}
+
+/// Sequences the transaction in progress has not created, changed or dropped are read from and
+/// advanced in the shared database, so that sessions never draw the same value.
+#[async_trait]
+impl Sequence for SharedMemoryStorage {
+    async fn fetch_sequence(&self, sequence_name: &str) -> Result<Option<StructSequence>> {
+        match &self.transaction {
+            Some(transaction) if !transaction.keeps_sequence(sequence_name)? => {
+                transaction.fetch_sequence(sequence_name).await
+            }
+            _ => {
+                let database = self.database.read().await;
+
+                database.fetch_sequence(sequence_name).await
+            }
+        }
+    }
+
+    async fn next_sequence_value(&self, sequence_name: &str) -> Result<Option<i64>> {
+        let Some(transaction) = &self.transaction else {
+            let database = self.database.read().await;
+
+            return database.next_sequence_value(sequence_name).await;
+        };
+
+        if !transaction.keeps_sequence(sequence_name)? {
+            return transaction.next_sequence_value(sequence_name).await;
+        }
+
+        let database = self.database.write().await;
+        let last_value = database
+            .fetch_sequence(sequence_name)
+            .await?
+            .and_then(|sequence| sequence.last_value);
+        let value = database.next_sequence_value(sequence_name).await?;
+
+        if let Some(value) = value {
+            self.draws.push(Draw::Sequence {
+                name: sequence_name.to_owned(),
+                last_value,
+                value,
+            })?;
+        }
+
+        Ok(value)
+    }
+}
+
+#[async_trait]
+impl SequenceMut for SharedMemoryStorage {
+    async fn insert_sequence(&mut self, sequence: StructSequence) -> Result<()> {
+        let mut database = self.write().await;
+
+        database.insert_sequence(sequence).await
+    }
+
+    async fn delete_sequence(&mut self, sequence_name: &str) -> Result<()> {
+        let mut database = self.write().await;
+
+        database.delete_sequence(sequence_name).await
+    }
+}
+
+#[async_trait]
+impl Trigger for SharedMemoryStorage {
+    async fn fetch_triggers(&self, table_name: &str) -> Result<Vec<StructTrigger>> {
+        let database = self.read().await;
+
+        database.fetch_triggers(table_name).await
+    }
+}
+
+#[async_trait]
+impl TriggerMut for SharedMemoryStorage {
+    async fn insert_trigger(&mut self, trigger: StructTrigger) -> Result<()> {
+        let mut database = self.write().await;
+
+        database.insert_trigger(trigger).await
+    }
+
+    async fn delete_trigger(&mut self, table_name: &str, trigger_name: &str) -> Result<()> {
+        let mut database = self.write().await;
+
+        database.delete_trigger(table_name, trigger_name).await
+    }
+}
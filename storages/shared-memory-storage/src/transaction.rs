@@ -3,31 +3,198 @@ use {
     async_trait::async_trait,
     gluesql_core::{
         error::{Error, Result},
-        store::Transaction,
+        store::{Sequence, SequenceMut, Transaction},
     },
+    std::sync::{Mutex, MutexGuard},
 };
 
+/// Value a transaction drew from the shared database, or a savepoint among them
+#[derive(Debug, Clone)]
+pub enum Draw {
+    /// Keys generated by `append_data`, which moved the key counter from `from` to `to`
+    Keys {
+        from: i64,
+        to: i64,
+    },
+    /// Value `NEXTVAL` drew from the sequence `name`, whose last value was `last_value` before
+    Sequence {
+        name: String,
+        last_value: Option<i64>,
+        value: i64,
+    },
+    Savepoint(String),
+}
+
+/// Draws of the transaction in progress, in the order they were made. `NEXTVAL` records them
+/// through a shared reference.
+#[derive(Debug, Default)]
+pub struct Draws(Mutex<Vec<Draw>>);
+
+impl Draws {
+    fn lock(&self) -> Result<MutexGuard<'_, Vec<Draw>>> {
+        self.0
+            .lock()
+            .map_err(|_| Error::StorageMsg("[Shared MemoryStorage] draw lock poisoned".to_owned()))
+    }
+
+    pub fn push(&self, draw: Draw) -> Result<()> {
+        self.lock()?.push(draw);
+
+        Ok(())
+    }
+
+    /// Returns the position of the latest savepoint named `name` among the draws.
+    fn find_savepoint(&self, name: &str) -> Result<usize> {
+        self.lock()?
+            .iter()
+            .rposition(|draw| matches!(draw, Draw::Savepoint(savepoint) if savepoint == name))
+            .ok_or_else(|| Error::StorageMsg(format!("savepoint not found: {name}")))
+    }
+
+    /// Forgets the savepoint `name` and the ones made after it, keeping the draws made since.
+    fn release_savepoint(&self, name: &str) -> Result<()> {
+        let position = self.find_savepoint(name)?;
+        let mut draws = self.lock()?;
+        let released = draws.split_off(position);
+        draws.extend(
+            released
+                .into_iter()
+                .filter(|draw| !matches!(draw, Draw::Savepoint(_))),
+        );
+
+        Ok(())
+    }
+}
+
+impl Clone for Draws {
+    fn clone(&self) -> Self {
+        let draws = self.0.lock().map(|draws| draws.clone()).unwrap_or_default();
+
+        Self(Mutex::new(draws))
+    }
+}
+
+impl SharedMemoryStorage {
+    /// Gives the keys and sequence values drawn from `position` on back to the shared
+    /// database, latest first. A value is only given back while no other session has drawn
+    /// after it, the others are left as gaps.
+    async fn give_back(&mut self, position: usize) -> Result<()> {
+        let draws = self.draws.lock()?.split_off(position);
+        let mut database = self.database.write().await;
+
+        for draw in draws.into_iter().rev() {
+            match draw {
+                Draw::Keys { from, to } if database.id_counter == to => {
+                    database.id_counter = from;
+                }
+                Draw::Sequence {
+                    name,
+                    last_value,
+                    value,
+                } => {
+                    let Some(mut sequence) = database.fetch_sequence(&name).await? else {
+                        continue;
+                    };
+
+                    if sequence.last_value == Some(value) {
+                        sequence.last_value = last_value;
+                        database.insert_sequence(sequence).await?;
+                    }
+                }
+                Draw::Keys { .. } | Draw::Savepoint(_) => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Each transaction works on its own copy of the database, so other sessions keep seeing the
+/// database as it was until the transaction commits. Committing fails when another session has
+/// changed what the transaction changed in the meantime, and `prepare` checks for that ahead of
+/// the commit without holding the database lock until then.
+///
+/// Generated keys and the values of the sequences the transaction has not changed are drawn
+/// from the shared database instead, so that concurrent transactions never draw the same one.
+/// Rolling back gives them back unless another session has drawn since.
 #[async_trait]
 impl Transaction for SharedMemoryStorage {
     async fn begin(&mut self, autocommit: bool) -> Result<bool> {
-        if autocommit {
-            return Ok(false);
-        }
+        match (&self.transaction, autocommit) {
+            (_, true) => Ok(false),
+            (None, false) => {
+                let mut transaction = self.database.read().await.clone();
+                transaction.begin(false).await?;
+                self.transaction = Some(Box::new(transaction));
+                self.draws.lock()?.clear();
 
-        Err(Error::StorageMsg(
-            "[Shared MemoryStorage] transaction is not supported".to_owned(),
-        ))
+                Ok(false)
+            }
+            (Some(_), false) => Err(Error::StorageMsg(
+                "[Shared MemoryStorage] nested transaction is not supported".to_owned(),
+            )),
+        }
     }
 
     async fn rollback(&mut self) -> Result<()> {
-        Err(Error::StorageMsg(
-            "[Shared MemoryStorage] transaction is not supported".to_owned(),
-        ))
+        if self.transaction.take().is_some() {
+            self.give_back(0).await?;
+        }
+
+        Ok(())
     }
 
     async fn commit(&mut self) -> Result<()> {
-        Err(Error::StorageMsg(
-            "[Shared MemoryStorage] transaction is not supported".to_owned(),
-        ))
+        let Some(mut transaction) = self.transaction.take() else {
+            return Ok(());
+        };
+        self.draws.lock()?.clear();
+
+        let mut database = self.database.write().await;
+
+        transaction.commit_to(&mut database)
+    }
+
+    async fn prepare(&mut self) -> Result<()> {
+        match &mut self.transaction {
+            Some(transaction) => transaction.prepare_commit(&*self.database.read().await),
+            None => Ok(()),
+        }
+    }
+
+    async fn savepoint(&mut self, name: &str) -> Result<()> {
+        match &mut self.transaction {
+            Some(transaction) => {
+                transaction.savepoint(name).await?;
+
+                self.draws.push(Draw::Savepoint(name.to_owned()))
+            }
+            None => Err(Error::StorageMsg(
+                "[Shared MemoryStorage] savepoint requires a transaction".to_owned(),
+            )),
+        }
+    }
+
+    async fn rollback_to_savepoint(&mut self, name: &str) -> Result<()> {
+        match &mut self.transaction {
+            Some(transaction) => {
+                transaction.rollback_to_savepoint(name).await?;
+
+                let position = self.draws.find_savepoint(name)?;
+                self.give_back(position + 1).await
+            }
+            None => Err(Error::StorageMsg(format!("savepoint not found: {name}"))),
+        }
+    }
+
+    async fn release_savepoint(&mut self, name: &str) -> Result<()> {
+        match &mut self.transaction {
+            Some(transaction) => {
+                transaction.release_savepoint(name).await?;
+
+                self.draws.release_savepoint(name)
+            }
+            None => Err(Error::StorageMsg(format!("savepoint not found: {name}"))),
+        }
     }
 }
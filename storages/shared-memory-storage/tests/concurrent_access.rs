@@ -1,6 +1,6 @@
 use {
     futures::executor::block_on,
-    gluesql_core::prelude::{Glue, Payload, Value},
+    gluesql_core::prelude::{Error, Glue, Payload, Value},
     gluesql_shared_memory_storage::SharedMemoryStorage,
 };
 
@@ -40,3 +40,165 @@ async fn concurrent_access() {
     }];
     assert_eq!(actual, expected);
 }
+
+#[tokio::test]
+async fn concurrent_transaction() {
+    let storage = SharedMemoryStorage::new();
+
+    let mut glue = Glue::new(storage.clone());
+    glue.execute("CREATE TABLE Tx (id INTEGER PRIMARY KEY, name TEXT);")
+        .await
+        .unwrap();
+    glue.execute("INSERT INTO Tx VALUES (1, 'a'), (2, 'b');")
+        .await
+        .unwrap();
+
+    let select = |rows: &[(i64, &str)]| {
+        let rows = rows
+            .iter()
+            .map(|(id, name)| vec![Value::I64(*id), Value::Str((*name).to_owned())])
+            .collect();
+
+        Ok(vec![Payload::Select {
+            labels: vec!["id".to_owned(), "name".to_owned()],
+            rows,
+        }])
+    };
+
+    let mut glue_1 = Glue::new(storage.clone());
+    let mut glue_2 = Glue::new(storage.clone());
+
+    glue_1.execute("BEGIN;").await.unwrap();
+    glue_1
+        .execute("UPDATE Tx SET name = 'x' WHERE id = 1;")
+        .await
+        .unwrap();
+    assert_eq!(
+        glue_2.execute("SELECT * FROM Tx;").await,
+        select(&[(1, "a"), (2, "b")]),
+        "changes are not seen by other sessions before COMMIT",
+    );
+
+    glue_2.execute("BEGIN;").await.unwrap();
+    glue_2
+        .execute("UPDATE Tx SET name = 'y' WHERE id = 2;")
+        .await
+        .unwrap();
+    glue_1.execute("COMMIT;").await.unwrap();
+    assert_eq!(
+        glue_2.execute("SELECT * FROM Tx;").await,
+        select(&[(1, "a"), (2, "y")]),
+        "a transaction keeps reading its own snapshot",
+    );
+    glue_2.execute("COMMIT;").await.unwrap();
+    assert_eq!(
+        glue.execute("SELECT * FROM Tx;").await,
+        select(&[(1, "x"), (2, "y")]),
+        "transactions changing different rows both commit",
+    );
+
+    glue_1.execute("BEGIN;").await.unwrap();
+    glue_2.execute("BEGIN;").await.unwrap();
+    glue_1
+        .execute("UPDATE Tx SET name = 'p' WHERE id = 1;")
+        .await
+        .unwrap();
+    glue_2
+        .execute("UPDATE Tx SET name = 'q' WHERE id = 1;")
+        .await
+        .unwrap();
+    glue_1.execute("COMMIT;").await.unwrap();
+    assert_eq!(
+        glue_2.execute("COMMIT;").await,
+        Err(Error::StorageMsg(
+            "[MemoryStorage] transaction conflict: Tx was changed by another transaction"
+                .to_owned()
+        )),
+        "the second of two transactions changing the same row fails to commit",
+    );
+    assert_eq!(
+        glue.execute("SELECT * FROM Tx;").await,
+        select(&[(1, "p"), (2, "y")]),
+    );
+}
+
+#[tokio::test]
+async fn concurrent_transaction_append() {
+    let storage = SharedMemoryStorage::new();
+
+    let mut glue = Glue::new(storage.clone());
+    glue.execute(
+        "
+        CREATE TABLE Log (msg TEXT);
+        CREATE TABLE Account (id INTEGER GENERATED ALWAYS AS IDENTITY, name TEXT);
+        ",
+    )
+    .await
+    .unwrap();
+
+    let mut glue_1 = Glue::new(storage.clone());
+    let mut glue_2 = Glue::new(storage.clone());
+
+    glue_1.execute("BEGIN;").await.unwrap();
+    glue_2.execute("BEGIN;").await.unwrap();
+    glue_1
+        .execute("INSERT INTO Log VALUES ('a'); INSERT INTO Account (name) VALUES ('a');")
+        .await
+        .unwrap();
+    glue_2
+        .execute("INSERT INTO Log VALUES ('b'); INSERT INTO Account (name) VALUES ('b');")
+        .await
+        .unwrap();
+    glue_1.execute("COMMIT;").await.unwrap();
+    assert_eq!(
+        glue_2.execute("COMMIT;").await.map(|_| ()),
+        Ok(()),
+        "transactions appending to the same table both commit",
+    );
+
+    assert_eq!(
+        glue.execute("SELECT * FROM Log;").await,
+        Ok(vec![Payload::Select {
+            labels: vec!["msg".to_owned()],
+            rows: vec![
+                vec![Value::Str("a".to_owned())],
+                vec![Value::Str("b".to_owned())],
+            ],
+        }]),
+    );
+    assert_eq!(
+        glue.execute("SELECT * FROM Account;").await,
+        Ok(vec![Payload::Select {
+            labels: vec!["id".to_owned(), "name".to_owned()],
+            rows: vec![
+                vec![Value::I64(1), Value::Str("a".to_owned())],
+                vec![Value::I64(2), Value::Str("b".to_owned())],
+            ],
+        }]),
+        "identity values are drawn from the shared database",
+    );
+
+    glue_1.execute("BEGIN;").await.unwrap();
+    glue_1
+        .execute("INSERT INTO Account (name) VALUES ('c');")
+        .await
+        .unwrap();
+    glue.execute("INSERT INTO Account (name) VALUES ('d');")
+        .await
+        .unwrap();
+    glue_1.execute("ROLLBACK;").await.unwrap();
+    glue.execute("INSERT INTO Account (name) VALUES ('e');")
+        .await
+        .unwrap();
+    assert_eq!(
+        glue.execute("SELECT * FROM Account WHERE id > 2;").await,
+        Ok(vec![Payload::Select {
+            labels: vec!["id".to_owned(), "name".to_owned()],
+            rows: vec![
+                vec![Value::I64(4), Value::Str("d".to_owned())],
+                vec![Value::I64(5), Value::Str("e".to_owned())],
+            ],
+        }]),
+        "a value drawn before another session's is not given back by ROLLBACK",
+    );
+}
@@ -25,6 +25,19 @@ generate_store_tests!(tokio::test, SharedMemoryTester);
 
 generate_alter_table_tests!(tokio::test, SharedMemoryTester);
 
+generate_view_tests!(tokio::test, SharedMemoryTester);
+
+generate_statistics_tests!(tokio::test, SharedMemoryTester);
+
+generate_sequence_tests!(tokio::test, SharedMemoryTester);
+generate_trigger_tests!(tokio::test, SharedMemoryTester);
+
+generate_transaction_tests!(tokio::test, SharedMemoryTester);
+generate_transaction_alter_table_tests!(tokio::test, SharedMemoryTester);
+generate_transaction_trigger_tests!(tokio::test, SharedMemoryTester);
+
+generate_alter_table_sequence_tests!(tokio::test, SharedMemoryTester);
+
 macro_rules! exec {
     ($glue: ident $sql: literal) => {
         $glue.execute($sql).await.unwrap();
@@ -61,7 +74,7 @@ async fn shared_memory_storage_index() {
 
     assert_eq!(
         storage
-            .scan_indexed_data("Idx", "hello", None, None)
+            .scan_indexed_data("Idx", "hello", None, &[], None)
             .await
             .map(|_| ()),
         Err(Error::StorageMsg(
@@ -84,15 +97,28 @@ async fn shared_memory_storage_index() {
 
 #[tokio::test]
 async fn shared_memory_storage_transaction() {
-    use gluesql_core::{error::Error, prelude::Glue};
+    use gluesql_core::{
+        error::Error,
+        prelude::{Glue, Payload},
+    };
 
     let storage = SharedMemoryStorage::new();
     let mut glue = Glue::new(storage);
 
     exec!(glue "CREATE TABLE TxTest (id INTEGER);");
-    test!(glue "BEGIN", Err(Error::StorageMsg("[Shared MemoryStorage] transaction is not supported".to_owned())));
-    test!(glue "COMMIT", Err(Error::StorageMsg("[Shared MemoryStorage] transaction is not supported".to_owned())));
-    test!(glue "ROLLBACK", Err(Error::StorageMsg("[Shared MemoryStorage] transaction is not supported".to_owned())));
+    test!(glue "COMMIT", Ok(vec![Payload::Commit]));
+    test!(glue "ROLLBACK", Ok(vec![Payload::Rollback]));
+    test!(
+        glue "SAVEPOINT sp1",
+        Err(Error::StorageMsg("[Shared MemoryStorage] savepoint requires a transaction".to_owned()))
+    );
+
+    test!(glue "BEGIN", Ok(vec![Payload::StartTransaction]));
+    test!(
+        glue "BEGIN",
+        Err(Error::StorageMsg("[Shared MemoryStorage] nested transaction is not supported".to_owned()))
+    );
+    test!(glue "ROLLBACK", Ok(vec![Payload::Rollback]));
 }
 
 #[tokio::test]
@@ -51,7 +51,9 @@ impl AlterTable for SledStorage {
                 column_defs,
                 indexes,
                 engine,
+                key_constraints,
                 foreign_keys,
+                checks,
                 comment,
                 ..
             } = old_schema
@@ -63,7 +65,9 @@ impl AlterTable for SledStorage {
                 column_defs,
                 indexes,
                 engine,
+                key_constraints,
                 foreign_keys,
+                checks,
                 comment,
             };
 
@@ -163,7 +167,9 @@ impl AlterTable for SledStorage {
                 column_defs,
                 indexes,
                 engine,
+                key_constraints,
                 foreign_keys,
+                checks,
                 comment: schema_comment,
                 ..
             } = snapshot
@@ -194,7 +200,10 @@ impl AlterTable for SledStorage {
                 data_type,
                 nullable,
                 default,
+                identity,
+                generated,
                 unique,
+                check,
                 comment,
                 ..
             } = column_defs[i].clone();
@@ -204,7 +213,10 @@ impl AlterTable for SledStorage {
                 data_type,
                 nullable,
                 default,
+                identity,
+                generated,
                 unique,
+                check,
                 comment,
             };
             let column_defs = Vector::from(column_defs).update(i, column_def).into();
@@ -214,7 +226,9 @@ impl AlterTable for SledStorage {
                 column_defs: Some(column_defs),
                 indexes,
                 engine,
+                key_constraints,
                 foreign_keys,
+                checks,
                 comment: schema_comment,
             };
             let (snapshot, _) = snapshot.update(txid, schema);
@@ -268,7 +282,9 @@ impl AlterTable for SledStorage {
                 column_defs,
                 indexes,
                 engine,
+                key_constraints,
                 foreign_keys,
+                checks,
                 comment,
             } = schema_snapshot
                 .get(txid, None)
@@ -309,7 +325,7 @@ impl AlterTable for SledStorage {
                 (None, true) => Value::Null,
                 (None, false) => {
                     return Err(ConflictableTransactionError::Abort(
-                        AlterTableError::DefaultValueRequired(column_def.clone()).into(),
+                        AlterTableError::DefaultValueRequired(Box::new(column_def.clone())).into(),
                     ));
                 }
             };
@@ -367,7 +383,9 @@ impl AlterTable for SledStorage {
                 column_defs: Some(column_defs),
                 indexes,
                 engine,
+                key_constraints,
                 foreign_keys,
+                checks,
                 comment,
             };
             let (schema_snapshot, _) = schema_snapshot.update(txid, schema);
@@ -424,7 +442,9 @@ impl AlterTable for SledStorage {
                 column_defs,
                 indexes,
                 engine,
+                key_constraints,
                 foreign_keys,
+                checks,
                 comment,
             } = schema_snapshot
                 .get(txid, None)
@@ -506,7 +526,9 @@ impl AlterTable for SledStorage {
                 column_defs: Some(column_defs),
                 indexes,
                 engine,
+                key_constraints,
                 foreign_keys,
+                checks,
                 comment,
             };
             let (schema_snapshot, _) = schema_snapshot.update(txid, schema);
@@ -3,7 +3,11 @@ use {
         SledStorage, Snapshot, err_into, key,
         lock::{Lock, TxData, get_txdata_key},
     },
-    gluesql_core::{data::Schema, error::Result, store::DataRow},
+    gluesql_core::{
+        data::{Schema, Sequence, Trigger},
+        error::Result,
+        store::DataRow,
+    },
     std::time::{SystemTime, UNIX_EPOCH},
 };
 
@@ -107,6 +111,8 @@ impl SledStorage {
         for txid in txids {
             gc_txid!(txid, key::temp_data_prefix(txid), DataRow);
             gc_txid!(txid, key::temp_schema_prefix(txid), Schema);
+            gc_txid!(txid, key::temp_sequence_prefix(txid), Sequence);
+            gc_txid!(txid, key::temp_trigger_prefix(txid), Trigger);
 
             for (temp_key, data_key) in fetch_keys(key::temp_index_prefix(txid))? {
                 let snapshots: Option<Vec<Snapshot<Vec<u8>>>> = self
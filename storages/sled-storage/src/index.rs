@@ -1,9 +1,5 @@
 use {
-    super::{
-        SledStorage, Snapshot, State, err_into,
-        index_sync::{build_index_key, build_index_key_prefix},
-        lock,
-    },
+    super::{SledStorage, Snapshot, State, err_into, index_sync::build_index_key, key, lock},
     async_trait::async_trait,
     futures::stream::iter,
     gluesql_core::{
@@ -12,9 +8,9 @@ use {
         error::{Error, IndexError, Result},
         store::{DataRow, Index, RowIter},
     },
-    iter_enum::{DoubleEndedIterator, Iterator},
+    iter_enum::Iterator,
     sled::IVec,
-    std::iter::{empty, once},
+    std::iter::once,
     utils::Vector,
 };
 
@@ -25,63 +21,48 @@ impl Index for SledStorage {
         table_name: &str,
         index_name: &str,
         asc: Option<bool>,
+        prefix: &[Value],
         cmp_value: Option<(&IndexOperator, Value)>,
     ) -> Result<RowIter<'a>> {
-        let data_keys = {
-            #[derive(Iterator, DoubleEndedIterator)]
-            enum DataIds<I1, I2, I3, I4> {
-                Empty(I1),
-                Once(I2),
-                Range(I3),
-                Full(I4),
-            }
-
-            let map = |item: std::result::Result<_, _>| item.map(|(_, v)| v);
-
-            match cmp_value {
-                None => {
-                    let prefix = build_index_key_prefix(table_name, index_name);
+        let incr = |key: Vec<u8>| -> Vec<u8> {
+            key.into_iter()
+                .rev()
+                .fold((false, Vector::new()), |(added, upper), v| {
+                    match (added, v) {
+                        (true, _) => (added, upper.push(v)),
+                        (false, u8::MAX) => (added, upper.push(v)),
+                        (false, _) => (true, upper.push(v + 1)),
+                    }
+                })
+                .1
+                .reverse()
+                .into()
+        };
 
-                    DataIds::Full(self.tree.scan_prefix(prefix).map(map))
-                }
-                Some((op, value)) => {
-                    let incr = |key: Vec<u8>| {
-                        key.into_iter()
-                            .rev()
-                            .fold((false, Vector::new()), |(added, upper), v| {
-                                match (added, v) {
-                                    (true, _) => (added, upper.push(v)),
-                                    (false, u8::MAX) => (added, upper.push(v)),
-                                    (false, _) => (true, upper.push(v + 1)),
-                                }
-                            })
-                            .1
-                            .reverse()
-                            .into()
-                    };
-                    let lower = || build_index_key_prefix(table_name, index_name);
-                    let upper = || incr(build_index_key_prefix(table_name, index_name));
-                    let key = build_index_key(table_name, index_name, value)?;
+        // `prefix_key` is a byte prefix of the index keys of all the rows matching `prefix`
+        let prefix_key = build_index_key(table_name, index_name, prefix)?;
+        let data_keys = match cmp_value {
+            None => self.tree.scan_prefix(prefix_key),
+            Some((op, value)) => {
+                let values = prefix
+                    .iter()
+                    .cloned()
+                    .chain(once(value))
+                    .collect::<Vec<_>>();
+                let key = build_index_key(table_name, index_name, &values)?;
+                let lower = || prefix_key.clone();
+                let upper = || incr(prefix_key.clone());
 
-                    match op {
-                        IndexOperator::Eq => match self.tree.get(&key).transpose() {
-                            Some(v) => DataIds::Once(once(v)),
-                            None => DataIds::Empty(empty()),
-                        },
-                        IndexOperator::Gt => {
-                            DataIds::Range(self.tree.range(incr(key)..upper()).map(map))
-                        }
-                        IndexOperator::GtEq => {
-                            DataIds::Range(self.tree.range(key..upper()).map(map))
-                        }
-                        IndexOperator::Lt => DataIds::Range(self.tree.range(lower()..key).map(map)),
-                        IndexOperator::LtEq => {
-                            DataIds::Range(self.tree.range(lower()..=key).map(map))
-                        }
-                    }
+                match op {
+                    IndexOperator::Eq => self.tree.scan_prefix(key),
+                    IndexOperator::Gt => self.tree.range(incr(key)..upper()),
+                    IndexOperator::GtEq => self.tree.range(key..upper()),
+                    IndexOperator::Lt => self.tree.range(lower()..key),
+                    IndexOperator::LtEq => self.tree.range(lower()..incr(key)),
                 }
             }
-        };
+        }
+        .map(|item| item.map(|(_, v)| v));
 
         let (txid, created_at) = match self.state {
             State::Transaction {
@@ -95,7 +76,7 @@ impl Index for SledStorage {
         };
         let lock_txid = lock::fetch(&self.tree, txid, created_at, self.tx_timeout)?;
 
-        let prefix_len = build_index_key_prefix(table_name, index_name).len();
+        let prefix_len = key::data_prefix(table_name).len();
         let tree = self.tree.clone();
         let flat_map = move |keys: Result<IVec>| {
             #[derive(Iterator)]
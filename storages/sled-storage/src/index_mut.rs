@@ -43,7 +43,8 @@ impl IndexMut for SledStorage {
         &mut self,
         table_name: &str,
         index_name: &str,
-        column: &OrderByExpr,
+        columns: &[OrderByExpr],
+        unique: bool,
     ) -> Result<()> {
         let rows = self
             .scan_data(table_name)
@@ -61,7 +62,10 @@ impl IndexMut for SledStorage {
                 }
             };
 
-            let index_expr = &column.expr;
+            let index_exprs = columns
+                .iter()
+                .map(|OrderByExpr { expr, .. }| expr.clone())
+                .collect::<Vec<_>>();
 
             let (schema_key, schema_snapshot) = fetch_schema(tree, table_name)?;
             let schema_snapshot = schema_snapshot
@@ -73,7 +77,9 @@ impl IndexMut for SledStorage {
                 column_defs,
                 indexes,
                 engine,
+                key_constraints,
                 foreign_keys,
+                checks,
                 comment,
                 ..
             } = schema
@@ -88,9 +94,10 @@ impl IndexMut for SledStorage {
 
             let index = SchemaIndex {
                 name: index_name.to_owned(),
-                expr: index_expr.clone(),
+                exprs: index_exprs,
                 order: SchemaIndexOrd::Both,
                 created: Utc::now().naive_utc(),
+                unique,
             };
 
             let indexes = indexes
@@ -103,7 +110,9 @@ impl IndexMut for SledStorage {
                 column_defs,
                 indexes,
                 engine,
+                key_constraints,
                 foreign_keys,
+                checks,
                 comment,
             };
 
@@ -136,7 +145,8 @@ impl IndexMut for SledStorage {
         });
 
         if self.check_retry(tx_result)? {
-            self.create_index(table_name, index_name, column).await?;
+            self.create_index(table_name, index_name, columns, unique)
+                .await?;
         }
 
         Ok(())
@@ -169,7 +179,9 @@ impl IndexMut for SledStorage {
                 column_defs,
                 indexes,
                 engine,
+                key_constraints,
                 foreign_keys,
+                checks,
                 comment,
                 ..
             } = schema
@@ -194,7 +206,9 @@ impl IndexMut for SledStorage {
                 column_defs,
                 indexes,
                 engine,
+                key_constraints,
                 foreign_keys,
+                checks,
                 comment,
             };
 
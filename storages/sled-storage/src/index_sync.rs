@@ -5,7 +5,7 @@ use {
         data::schema::{Schema, SchemaIndex},
         error::{Error, IndexError, Result},
         executor::evaluate_stateless,
-        prelude::Value,
+        prelude::{Key, Value},
         store::DataRow,
     },
     sled::{
@@ -105,18 +105,18 @@ impl<'a> IndexSync<'a> {
     ) -> ConflictableTransactionResult<(), Error> {
         let SchemaIndex {
             name: index_name,
-            expr: index_expr,
+            exprs: index_exprs,
+            unique,
             ..
         } = index;
 
-        let index_key = &evaluate_index_key(
-            self.table_name,
-            index_name,
-            index_expr,
-            self.columns.as_deref(),
-            row,
-        )
-        .await?;
+        let values = evaluate_index_values(index_exprs, self.columns.as_deref(), row).await?;
+        let index_key = &build_index_key(self.table_name, index_name, &values)
+            .map_err(ConflictableTransactionError::Abort)?;
+
+        if *unique && !values.iter().any(Value::is_null) {
+            self.validate_unique(index_name, index_key, data_key)?;
+        }
 
         self.insert_index_data(index_key, data_key)?;
 
@@ -130,32 +130,8 @@ impl<'a> IndexSync<'a> {
         new_row: &DataRow,
     ) -> ConflictableTransactionResult<(), Error> {
         for index in self.indexes.iter() {
-            let SchemaIndex {
-                name: index_name,
-                expr: index_expr,
-                ..
-            } = index;
-
-            let old_index_key = &evaluate_index_key(
-                self.table_name,
-                index_name,
-                index_expr,
-                self.columns.as_deref(),
-                old_row,
-            )
-            .await?;
-
-            let new_index_key = &evaluate_index_key(
-                self.table_name,
-                index_name,
-                index_expr,
-                self.columns.as_deref(),
-                new_row,
-            )
-            .await?;
-
-            self.delete_index_data(old_index_key, data_key)?;
-            self.insert_index_data(new_index_key, data_key)?;
+            self.delete_index(index, data_key, old_row).await?;
+            self.insert_index(index, data_key, new_row).await?;
         }
 
         Ok(())
@@ -181,14 +157,14 @@ impl<'a> IndexSync<'a> {
     ) -> ConflictableTransactionResult<(), Error> {
         let SchemaIndex {
             name: index_name,
-            expr: index_expr,
+            exprs: index_exprs,
             ..
         } = index;
 
         let index_key = &evaluate_index_key(
             self.table_name,
             index_name,
-            index_expr,
+            index_exprs,
             self.columns.as_deref(),
             row,
         )
@@ -199,6 +175,36 @@ impl<'a> IndexSync<'a> {
         Ok(())
     }
 
+    /// Fails when a row other than `data_key` already has `index_key` on the unique index
+    fn validate_unique(
+        &self,
+        index_name: &str,
+        index_key: &[u8],
+        data_key: &IVec,
+    ) -> ConflictableTransactionResult<(), Error> {
+        let data_keys: Vec<Snapshot<Vec<u8>>> = self
+            .tree
+            .get(index_key)?
+            .map(|v| bincode::deserialize(&v))
+            .transpose()
+            .map_err(err_into)
+            .map_err(ConflictableTransactionError::Abort)?
+            .unwrap_or_default();
+
+        let taken = data_keys
+            .iter()
+            .filter_map(|snapshot| snapshot.get(self.txid, None))
+            .any(|key| key != data_key.as_ref());
+
+        if taken {
+            return Err(ConflictableTransactionError::Abort(
+                IndexError::DuplicateEntryOnUniqueIndex(index_name.to_owned()).into(),
+            ));
+        }
+
+        Ok(())
+    }
+
     fn insert_index_data(
         &self,
         index_key: &[u8],
@@ -270,28 +276,51 @@ impl<'a> IndexSync<'a> {
 async fn evaluate_index_key(
     table_name: &str,
     index_name: &str,
-    index_expr: &Expr,
+    index_exprs: &[Expr],
     columns: Option<&[String]>,
     row: &DataRow,
 ) -> ConflictableTransactionResult<Vec<u8>, Error> {
-    let context = Some(row.as_context(columns));
-    let evaluated = evaluate_stateless(context, index_expr)
-        .await
-        .map_err(ConflictableTransactionError::Abort)?;
-    let value: Value = evaluated
-        .try_into()
-        .map_err(ConflictableTransactionError::Abort)?;
-
-    build_index_key(table_name, index_name, value).map_err(ConflictableTransactionError::Abort)
+    let values = evaluate_index_values(index_exprs, columns, row).await?;
+
+    build_index_key(table_name, index_name, &values).map_err(ConflictableTransactionError::Abort)
+}
+
+async fn evaluate_index_values(
+    index_exprs: &[Expr],
+    columns: Option<&[String]>,
+    row: &DataRow,
+) -> ConflictableTransactionResult<Vec<Value>, Error> {
+    let mut values = Vec::with_capacity(index_exprs.len());
+    for index_expr in index_exprs {
+        let context = Some(row.as_context(columns));
+        let evaluated = evaluate_stateless(context, index_expr)
+            .await
+            .map_err(ConflictableTransactionError::Abort)?;
+        let value: Value = evaluated
+            .try_into()
+            .map_err(ConflictableTransactionError::Abort)?;
+
+        values.push(value);
+    }
+
+    Ok(values)
 }
 
 pub fn build_index_key_prefix(table_name: &str, index_name: &str) -> Vec<u8> {
     format!("index/{table_name}/{index_name}/").into_bytes()
 }
 
-pub fn build_index_key(table_name: &str, index_name: &str, value: Value) -> Result<Vec<u8>> {
+/// Encodes the index values as a [`Key::Composite`], so that the key built from the leading
+/// values of a multi-column index is a byte prefix of the keys of all the rows matching them.
+pub fn build_index_key(table_name: &str, index_name: &str, values: &[Value]) -> Result<Vec<u8>> {
+    let key = values
+        .iter()
+        .map(Key::try_from)
+        .collect::<Result<Vec<_>>>()
+        .map(Key::Composite)?;
+
     Ok(build_index_key_prefix(table_name, index_name)
         .into_iter()
-        .chain(value.to_cmp_be_bytes()?)
+        .chain(key.to_cmp_be_bytes()?)
         .collect::<Vec<_>>())
 }
@@ -3,6 +3,9 @@ use sled::IVec;
 const TEMP_DATA: &str = "temp_data/";
 const TEMP_SCHEMA: &str = "temp_schema/";
 const TEMP_INDEX: &str = "temp_index/";
+const TEMP_SEQUENCE: &str = "temp_sequence/";
+const TEMP_TRIGGER: &str = "temp_trigger/";
+const SAVEPOINT: &str = "savepoint/";
 
 pub fn data_prefix(table_name: &str) -> String {
     format!("data/{table_name}/")
@@ -36,6 +39,40 @@ pub fn temp_index_prefix(txid: u64) -> IVec {
     IVec::from_iter(prefix!(txid, TEMP_INDEX))
 }
 
+pub fn temp_sequence_prefix(txid: u64) -> IVec {
+    IVec::from_iter(prefix!(txid, TEMP_SEQUENCE))
+}
+
+pub fn temp_trigger_prefix(txid: u64) -> IVec {
+    IVec::from_iter(prefix!(txid, TEMP_TRIGGER))
+}
+
+pub fn temp_prefixes(txid: u64) -> [IVec; 5] {
+    [
+        temp_data_prefix(txid),
+        temp_schema_prefix(txid),
+        temp_index_prefix(txid),
+        temp_sequence_prefix(txid),
+        temp_trigger_prefix(txid),
+    ]
+}
+
+pub fn savepoint_txid_prefix(txid: u64) -> IVec {
+    IVec::from_iter(prefix!(txid, SAVEPOINT))
+}
+
+pub fn savepoint_prefix(txid: u64, depth: u64) -> IVec {
+    IVec::from_iter(prefix!(txid, SAVEPOINT).chain(depth.to_be_bytes()))
+}
+
+pub fn savepoint(txid: u64, depth: u64, value_key: &IVec) -> IVec {
+    IVec::from_iter(
+        prefix!(txid, SAVEPOINT)
+            .chain(depth.to_be_bytes())
+            .chain(value_key.iter().copied()),
+    )
+}
+
 pub fn temp_data(txid: u64, data_key: &IVec) -> IVec {
     IVec::from_iter(prefix!(txid, TEMP_DATA).chain(data_key.iter().copied()))
 }
@@ -51,3 +88,11 @@ pub fn temp_schema(txid: u64, table_name: &str) -> IVec {
 pub fn temp_index(txid: u64, index_key: &[u8]) -> IVec {
     IVec::from_iter(prefix!(txid, TEMP_INDEX).chain(index_key.iter().copied()))
 }
+
+pub fn temp_sequence(txid: u64, sequence_name: &str) -> IVec {
+    IVec::from_iter(prefix!(txid, TEMP_SEQUENCE).chain(sequence_name.as_bytes().iter().copied()))
+}
+
+pub fn temp_trigger(txid: u64, trigger_key: &str) -> IVec {
+    IVec::from_iter(prefix!(txid, TEMP_TRIGGER).chain(trigger_key.as_bytes().iter().copied()))
+}
@@ -8,10 +8,13 @@ mod index_mut;
 mod index_sync;
 mod key;
 mod lock;
+mod sequence;
 mod snapshot;
+mod statistics;
 mod store;
 mod store_mut;
 mod transaction;
+mod trigger;
 
 // re-export
 pub use sled;
@@ -24,14 +27,19 @@ use {
         error::{Error, Result},
         store::Metadata,
     },
+    lock::LockAcquired,
+    serde::{Serialize, de::DeserializeOwned},
     sled::{
-        Config, Db,
+        Config, Db, IVec,
         transaction::{
             ConflictableTransactionError, ConflictableTransactionResult, TransactionalTree,
         },
     },
+    std::result::Result as StdResult,
 };
 
+type SnapshotUpdate<T, R> = (Option<Snapshot<T>>, R);
+
 /// default transaction timeout : 1 hour
 const DEFAULT_TX_TIMEOUT: u128 = 3600 * 1000;
 
@@ -42,6 +50,8 @@ pub enum State {
         txid: u64,
         created_at: u128,
         autocommit: bool,
+        /// Names of the savepoints of the transaction, in the order they were made
+        savepoints: Vec<String>,
     },
 }
 
@@ -100,6 +110,65 @@ impl SledStorage {
     }
 }
 
+impl SledStorage {
+    /// Stores the snapshot `update` returns for `key` while holding the transaction lock,
+    /// and records `temp_key` of the transaction so that a rollback restores the snapshot.
+    fn update_snapshot<T, R, K, F>(&self, key: String, temp_key: K, update: F) -> Result<R>
+    where
+        T: Clone + Serialize + DeserializeOwned,
+        K: Fn(u64) -> IVec,
+        F: Fn(u64, Option<Snapshot<T>>) -> Result<SnapshotUpdate<T, R>>,
+    {
+        let state = &self.state;
+        let tx_timeout = self.tx_timeout;
+
+        let tx_result = self
+            .tree
+            .transaction(|tree| {
+                let txid = match lock::acquire(tree, state, tx_timeout)? {
+                    LockAcquired::Success { txid, .. } => txid,
+                    LockAcquired::RollbackAndRetry { lock_txid } => {
+                        return Ok(StdResult::Err(lock_txid));
+                    }
+                };
+
+                let snapshot = tree
+                    .get(key.as_bytes())?
+                    .map(|v| bincode::deserialize(&v))
+                    .transpose()
+                    .map_err(err_into)
+                    .map_err(ConflictableTransactionError::Abort)?;
+
+                let (snapshot, output) =
+                    update(txid, snapshot).map_err(ConflictableTransactionError::Abort)?;
+
+                if let Some(snapshot) = snapshot {
+                    let snapshot = bincode::serialize(&snapshot)
+                        .map_err(err_into)
+                        .map_err(ConflictableTransactionError::Abort)?;
+
+                    tree.insert(key.as_bytes(), snapshot)?;
+                    tree.insert(temp_key(txid), key.as_bytes())?;
+                }
+
+                Ok(StdResult::Ok(output))
+            })
+            .map_err(tx_err_into)?;
+
+        match tx_result {
+            Ok(output) => Ok(output),
+            Err(lock_txid) => {
+                self.rollback_txid(lock_txid)?;
+                self.tree
+                    .transaction(move |tree| lock::release(tree, lock_txid))
+                    .map_err(tx_err_into)?;
+
+                self.update_snapshot(key, temp_key, update)
+            }
+        }
+    }
+}
+
 impl TryFrom<Config> for SledStorage {
     type Error = Error;
 
@@ -148,3 +217,5 @@ fn fetch_schema(
 impl Metadata for SledStorage {}
 impl gluesql_core::store::CustomFunction for SledStorage {}
 impl gluesql_core::store::CustomFunctionMut for SledStorage {}
+impl gluesql_core::store::View for SledStorage {}
+impl gluesql_core::store::ViewMut for SledStorage {}
@@ -119,6 +119,7 @@ pub fn acquire(
             txid,
             created_at,
             autocommit,
+            ..
         } => (*txid, *created_at, *autocommit),
         State::Idle => {
             return Err(ConflictableTransactionError::Abort(Error::StorageMsg(
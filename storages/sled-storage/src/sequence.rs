@@ -0,0 +1,94 @@
+use {
+    super::{SledStorage, Snapshot, SnapshotUpdate, State, err_into, key, lock},
+    async_trait::async_trait,
+    gluesql_core::{
+        data::Sequence as StructSequence,
+        error::Result,
+        store::{Sequence, SequenceMut},
+    },
+};
+
+fn sequence_key(sequence_name: &str) -> String {
+    format!("sequence/{sequence_name}")
+}
+
+type SequenceUpdate<R> = SnapshotUpdate<StructSequence, R>;
+
+impl SledStorage {
+    /// Stores the snapshot `update` returns for the sequence while holding the transaction
+    /// lock, so a rollback also restores the values `NEXTVAL` handed out.
+    fn update_sequence<R, F>(&self, sequence_name: &str, update: F) -> Result<R>
+    where
+        F: Fn(u64, Option<Snapshot<StructSequence>>) -> Result<SequenceUpdate<R>>,
+    {
+        self.update_snapshot(
+            sequence_key(sequence_name),
+            |txid| key::temp_sequence(txid, sequence_name),
+            update,
+        )
+    }
+}
+
+#[async_trait]
+impl Sequence for SledStorage {
+    async fn fetch_sequence(&self, sequence_name: &str) -> Result<Option<StructSequence>> {
+        let (txid, created_at, temp) = match self.state {
+            State::Transaction {
+                txid, created_at, ..
+            } => (txid, created_at, false),
+            State::Idle => lock::register(&self.tree, self.id_offset)
+                .map(|(txid, created_at)| (txid, created_at, true))?,
+        };
+        let lock_txid = lock::fetch(&self.tree, txid, created_at, self.tx_timeout)?;
+
+        let sequence = self
+            .tree
+            .get(sequence_key(sequence_name))
+            .map_err(err_into)?
+            .map(|v| bincode::deserialize(&v))
+            .transpose()
+            .map_err(err_into)?
+            .and_then(|snapshot: Snapshot<StructSequence>| snapshot.extract(txid, lock_txid));
+
+        if temp {
+            lock::unregister(&self.tree, txid)?;
+        }
+
+        Ok(sequence)
+    }
+
+    async fn next_sequence_value(&self, sequence_name: &str) -> Result<Option<i64>> {
+        self.update_sequence(sequence_name, |txid, snapshot| {
+            let Some(snapshot) = snapshot else {
+                return Ok((None, None));
+            };
+            let Some(mut sequence) = snapshot.get(txid, Some(txid)) else {
+                return Ok((None, None));
+            };
+
+            let value = sequence.advance()?;
+
+            Ok((Some(snapshot.overwrite(txid, sequence)), Some(value)))
+        })
+    }
+}
+
+#[async_trait]
+impl SequenceMut for SledStorage {
+    async fn insert_sequence(&mut self, sequence: StructSequence) -> Result<()> {
+        self.update_sequence(&sequence.name, |txid, snapshot| {
+            let snapshot = match snapshot {
+                Some(snapshot) => snapshot.update(txid, sequence.clone()).0,
+                None => Snapshot::new(txid, sequence.clone()),
+            };
+
+            Ok((Some(snapshot), ()))
+        })
+    }
+
+    async fn delete_sequence(&mut self, sequence_name: &str) -> Result<()> {
+        self.update_sequence(sequence_name, |txid, snapshot| {
+            Ok((snapshot.map(|snapshot| snapshot.delete(txid).0), ()))
+        })
+    }
+}
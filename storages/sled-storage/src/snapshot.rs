@@ -42,6 +42,19 @@ impl<T: Clone> Snapshot<T> {
         (self, old_data)
     }
 
+    /// Replaces the data in place when `txid` created the latest version itself, instead of
+    /// stacking another version on top of it.
+    pub fn overwrite(mut self, txid: u64, data: T) -> Self {
+        match self.0.first_mut() {
+            Some(item) if item.created_by == txid && item.deleted_by.is_none() => {
+                item.data = data;
+
+                self
+            }
+            _ => self.update(txid, data).0,
+        }
+    }
+
     pub fn delete(mut self, txid: u64) -> (Self, Option<T>) {
         if !self.0.is_empty() {
             self.0[0].deleted_by = Some(txid);
@@ -0,0 +1,46 @@
+use {
+    super::{SledStorage, err_into},
+    async_trait::async_trait,
+    gluesql_core::{
+        data::TableStatistics,
+        error::Result,
+        store::{Statistics, StatisticsMut},
+    },
+};
+
+/// Statistics are kept outside of the transaction snapshots, as they only guide the planner
+/// and `ANALYZE` refreshes them whenever they become stale.
+fn statistics_key(table_name: &str) -> String {
+    format!("statistics/{table_name}")
+}
+
+#[async_trait]
+impl Statistics for SledStorage {
+    async fn fetch_statistics(&self, table_name: &str) -> Result<Option<TableStatistics>> {
+        self.tree
+            .get(statistics_key(table_name))
+            .map_err(err_into)?
+            .map(|value| bincode::deserialize(&value).map_err(err_into))
+            .transpose()
+    }
+}
+
+#[async_trait]
+impl StatisticsMut for SledStorage {
+    async fn insert_statistics(&mut self, statistics: TableStatistics) -> Result<()> {
+        let key = statistics_key(&statistics.table_name);
+        let value = bincode::serialize(&statistics).map_err(err_into)?;
+
+        self.tree.insert(key, value).map_err(err_into)?;
+
+        Ok(())
+    }
+
+    async fn delete_statistics(&mut self, table_name: &str) -> Result<()> {
+        self.tree
+            .remove(statistics_key(table_name))
+            .map_err(err_into)?;
+
+        Ok(())
+    }
+}
@@ -124,4 +124,42 @@ impl Store for SledStorage {
 
         Ok(Box::pin(iter(result_set)))
     }
+
+    async fn scan_data_by_prefix<'a>(
+        &'a self,
+        table_name: &str,
+        prefix: &[Key],
+    ) -> Result<RowIter<'a>> {
+        let (txid, created_at) = match self.state {
+            State::Transaction {
+                txid, created_at, ..
+            } => (txid, created_at),
+            State::Idle => {
+                return Err(Error::StorageMsg(
+                    "conflict - scan_data_by_prefix failed, lock does not exist".to_owned(),
+                ));
+            }
+        };
+        let lock_txid = lock::fetch(&self.tree, txid, created_at, self.tx_timeout)?;
+
+        let prefix_len = key::data_prefix(table_name).len();
+        let prefix = Key::Composite(prefix.to_vec())
+            .to_cmp_be_bytes()
+            .map(|key| key::data(table_name, key))?;
+        let result_set = self
+            .tree
+            .scan_prefix(prefix)
+            .map(move |item| {
+                let (key, value) = item.map_err(err_into)?;
+                let key = key.subslice(prefix_len, key.len() - prefix_len).to_vec();
+                let snapshot: Snapshot<DataRow> = bincode::deserialize(&value).map_err(err_into)?;
+                let row = snapshot.extract(txid, lock_txid);
+                let item = row.map(|row| (Key::Bytea(key), row));
+
+                Ok(item)
+            })
+            .filter_map(|item| item.transpose());
+
+        Ok(Box::pin(iter(result_set)))
+    }
 }
@@ -6,21 +6,25 @@ use {
     },
     async_trait::async_trait,
     gluesql_core::{
-        data::Schema,
+        data::{Schema, Sequence, Trigger},
         error::{Error, Result},
         store::{DataRow, Transaction},
     },
     serde::{Serialize, de::DeserializeOwned},
     sled::{
-        IVec,
+        Batch, IVec,
         transaction::{
             ConflictableTransactionError, ConflictableTransactionResult, TransactionError,
             TransactionalTree,
         },
     },
-    std::result::Result as StdResult,
+    std::{collections::HashMap, result::Result as StdResult},
 };
 
+/// Values of the keys a transaction had changed when a savepoint was made, `None` for the
+/// keys which had no value
+type Saved = HashMap<IVec, Option<Vec<u8>>>;
+
 pub enum TxPayload {
     Success,
     RollbackAndRetry(u64),
@@ -41,6 +45,7 @@ impl Transaction for SledStorage {
                     txid,
                     created_at,
                     autocommit,
+                    savepoints: Vec::new(),
                 };
 
                 Ok(autocommit)
@@ -56,24 +61,15 @@ impl Transaction for SledStorage {
             }
         };
 
-        let rollback = || {
-            let Lock { lock_txid, .. } = self
-                .tree
-                .get("lock/")
-                .map_err(err_into)?
-                .map(|l| bincode::deserialize(&l))
-                .transpose()
-                .map_err(err_into)?
-                .unwrap_or_default();
-
-            if Some(txid) == lock_txid {
-                self.rollback_txid(txid).map(|_| lock_txid)
-            } else {
-                Ok(None)
-            }
-        };
+        let lock_txid = if self.holds_lock(txid)? {
+            self.rollback_txid(txid)?;
+
+            Some(txid)
+        } else {
+            self.remove_savepoints(txid, 0)?;
 
-        let lock_txid = rollback()?;
+            None
+        };
 
         self.tree
             .transaction(move |tree| {
@@ -98,6 +94,7 @@ impl Transaction for SledStorage {
         };
 
         lock::fetch(&self.tree, txid, created_at, self.tx_timeout)?;
+        self.remove_savepoints(txid, 0)?;
 
         self.tree
             .transaction(move |tree| lock::release(tree, txid))
@@ -117,10 +114,148 @@ impl Transaction for SledStorage {
 
         gc_result
     }
+
+    async fn prepare(&mut self) -> Result<()> {
+        match self.state {
+            State::Transaction {
+                txid, created_at, ..
+            } => lock::fetch(&self.tree, txid, created_at, self.tx_timeout).map(|_| ()),
+            State::Idle => Ok(()),
+        }
+    }
+
+    async fn savepoint(&mut self, name: &str) -> Result<()> {
+        let (txid, depth) = match &self.state {
+            State::Transaction {
+                txid, savepoints, ..
+            } => (*txid, savepoints.len() as u64),
+            State::Idle => {
+                return Err(Error::StorageMsg(
+                    "savepoint requires a transaction".to_owned(),
+                ));
+            }
+        };
+
+        let mut batch = Batch::default();
+        for prefix in key::temp_prefixes(txid) {
+            for item in self.tree.scan_prefix(prefix) {
+                let (_, value_key) = item.map_err(err_into)?;
+                let value = self.tree.get(&value_key).map_err(err_into)?;
+                let value =
+                    bincode::serialize(&value.map(|value| value.to_vec())).map_err(err_into)?;
+
+                batch.insert(key::savepoint(txid, depth, &value_key), value);
+            }
+        }
+
+        self.tree.apply_batch(batch).map_err(err_into)?;
+
+        if let State::Transaction { savepoints, .. } = &mut self.state {
+            savepoints.push(name.to_owned());
+        }
+
+        Ok(())
+    }
+
+    async fn rollback_to_savepoint(&mut self, name: &str) -> Result<()> {
+        let (txid, depth) = self.find_savepoint(name)?;
+
+        if self.holds_lock(txid)? {
+            let saved = self.fetch_savepoint(txid, depth)?;
+
+            self.rollback_txid_to(txid, &saved)?;
+        }
+
+        self.remove_savepoints(txid, depth + 1)?;
+
+        if let State::Transaction { savepoints, .. } = &mut self.state {
+            savepoints.truncate(depth as usize + 1);
+        }
+
+        Ok(())
+    }
+
+    async fn release_savepoint(&mut self, name: &str) -> Result<()> {
+        let (txid, depth) = self.find_savepoint(name)?;
+
+        self.remove_savepoints(txid, depth)?;
+
+        if let State::Transaction { savepoints, .. } = &mut self.state {
+            savepoints.truncate(depth as usize);
+        }
+
+        Ok(())
+    }
 }
 
 impl SledStorage {
     pub fn rollback_txid(&self, txid: u64) -> Result<()> {
+        self.rollback_txid_to(txid, &Saved::new())?;
+        self.remove_savepoints(txid, 0)
+    }
+
+    fn holds_lock(&self, txid: u64) -> Result<bool> {
+        let Lock { lock_txid, .. } = self
+            .tree
+            .get("lock/")
+            .map_err(err_into)?
+            .map(|l| bincode::deserialize(&l))
+            .transpose()
+            .map_err(err_into)?
+            .unwrap_or_default();
+
+        Ok(Some(txid) == lock_txid)
+    }
+
+    /// Returns the transaction id and the depth of the latest savepoint named `name`.
+    fn find_savepoint(&self, name: &str) -> Result<(u64, u64)> {
+        match &self.state {
+            State::Transaction {
+                txid, savepoints, ..
+            } => savepoints
+                .iter()
+                .rposition(|savepoint| savepoint == name)
+                .map(|depth| (*txid, depth as u64)),
+            State::Idle => None,
+        }
+        .ok_or_else(|| Error::StorageMsg(format!("savepoint not found: {name}")))
+    }
+
+    fn fetch_savepoint(&self, txid: u64, depth: u64) -> Result<Saved> {
+        let prefix = key::savepoint_prefix(txid, depth);
+
+        self.tree
+            .scan_prefix(&prefix)
+            .map(|item| {
+                let (key, value) = item.map_err(err_into)?;
+                let value_key = IVec::from(&key[prefix.len()..]);
+                let value = bincode::deserialize(&value).map_err(err_into)?;
+
+                Ok((value_key, value))
+            })
+            .collect()
+    }
+
+    /// Removes the values saved for the savepoints of `txid` from `depth` on.
+    fn remove_savepoints(&self, txid: u64, depth: u64) -> Result<()> {
+        let txid_prefix = key::savepoint_txid_prefix(txid);
+        let mut batch = Batch::default();
+
+        for item in self.tree.range(key::savepoint_prefix(txid, depth)..) {
+            let (key, _) = item.map_err(err_into)?;
+            if !key.starts_with(&txid_prefix) {
+                break;
+            }
+
+            batch.remove(key);
+        }
+
+        self.tree.apply_batch(batch).map_err(err_into)
+    }
+
+    /// Undoes the changes of `txid`, restoring the keys of `saved` to the values saved for
+    /// a savepoint instead.
+    fn rollback_txid_to(&self, txid: u64, saved: &Saved) -> Result<()> {
         let fetch_items = |prefix| {
             self.tree
                 .scan_prefix(prefix)
@@ -128,12 +263,30 @@ impl SledStorage {
                 .collect::<Result<Vec<_>>>()
         };
 
+        fn restore(
+            tree: &TransactionalTree,
+            saved: &Saved,
+            value_key: &IVec,
+        ) -> ConflictableTransactionResult<bool, Error> {
+            match saved.get(value_key) {
+                Some(Some(value)) => tree.insert(value_key, value.as_slice()).map(|_| true),
+                Some(None) => tree.remove(value_key).map(|_| true),
+                None => Ok(false),
+            }
+            .map_err(Into::into)
+        }
+
         fn rollback_items<T: Clone + Serialize + DeserializeOwned>(
             tree: &TransactionalTree,
             txid: u64,
+            saved: &Saved,
             items: &[(IVec, IVec)],
         ) -> ConflictableTransactionResult<(), Error> {
             for (temp_key, value_key) in items.iter() {
+                if restore(tree, saved, value_key)? {
+                    continue;
+                }
+
                 tree.remove(temp_key)?;
 
                 let snapshot = tree
@@ -170,13 +323,21 @@ impl SledStorage {
         let data_items = fetch_items(key::temp_data_prefix(txid))?;
         let schema_items = fetch_items(key::temp_schema_prefix(txid))?;
         let index_items = fetch_items(key::temp_index_prefix(txid))?;
+        let sequence_items = fetch_items(key::temp_sequence_prefix(txid))?;
+        let trigger_items = fetch_items(key::temp_trigger_prefix(txid))?;
 
         self.tree
             .transaction(move |tree| {
-                rollback_items::<DataRow>(tree, txid, &data_items)?;
-                rollback_items::<Schema>(tree, txid, &schema_items)?;
+                rollback_items::<DataRow>(tree, txid, saved, &data_items)?;
+                rollback_items::<Schema>(tree, txid, saved, &schema_items)?;
+                rollback_items::<Sequence>(tree, txid, saved, &sequence_items)?;
+                rollback_items::<Trigger>(tree, txid, saved, &trigger_items)?;
 
                 for (temp_key, value_key) in index_items.iter() {
+                    if restore(tree, saved, value_key)? {
+                        continue;
+                    }
+
                     tree.remove(temp_key)?;
 
                     let snapshots = tree
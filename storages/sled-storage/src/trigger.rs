@@ -0,0 +1,82 @@
+use {
+    super::{SledStorage, Snapshot, State, err_into, key, lock},
+    async_trait::async_trait,
+    gluesql_core::{
+        data::Trigger as StructTrigger,
+        error::Result,
+        store::{Trigger, TriggerMut},
+    },
+};
+
+fn trigger_prefix(table_name: &str) -> String {
+    format!("trigger/{table_name}/")
+}
+
+fn trigger_key(table_name: &str, trigger_name: &str) -> String {
+    format!("{}{trigger_name}", trigger_prefix(table_name))
+}
+
+#[async_trait]
+impl Trigger for SledStorage {
+    async fn fetch_triggers(&self, table_name: &str) -> Result<Vec<StructTrigger>> {
+        let (txid, created_at, temp) = match self.state {
+            State::Transaction {
+                txid, created_at, ..
+            } => (txid, created_at, false),
+            State::Idle => lock::register(&self.tree, self.id_offset)
+                .map(|(txid, created_at)| (txid, created_at, true))?,
+        };
+        let lock_txid = lock::fetch(&self.tree, txid, created_at, self.tx_timeout)?;
+
+        let triggers = self
+            .tree
+            .scan_prefix(trigger_prefix(table_name))
+            .map(|item| {
+                let (_, value) = item.map_err(err_into)?;
+                let snapshot: Snapshot<StructTrigger> =
+                    bincode::deserialize(&value).map_err(err_into)?;
+
+                Ok(snapshot.extract(txid, lock_txid))
+            })
+            .filter_map(Result::transpose)
+            .collect::<Result<Vec<_>>>()?;
+
+        if temp {
+            lock::unregister(&self.tree, txid)?;
+        }
+
+        Ok(triggers)
+    }
+}
+
+#[async_trait]
+impl TriggerMut for SledStorage {
+    async fn insert_trigger(&mut self, trigger: StructTrigger) -> Result<()> {
+        let key = trigger_key(&trigger.table_name, &trigger.name);
+
+        self.update_snapshot(
+            key.clone(),
+            |txid| key::temp_trigger(txid, &key),
+            |txid, snapshot| {
+                let snapshot = match snapshot {
+                    Some(snapshot) => snapshot.update(txid, trigger.clone()).0,
+                    None => Snapshot::new(txid, trigger.clone()),
+                };
+
+                Ok((Some(snapshot), ()))
+            },
+        )
+    }
+
+    async fn delete_trigger(&mut self, table_name: &str, trigger_name: &str) -> Result<()> {
+        let key = trigger_key(table_name, trigger_name);
+
+        self.update_snapshot(
+            key.clone(),
+            |txid| key::temp_trigger(txid, &key),
+            |txid, snapshot: Option<Snapshot<StructTrigger>>| {
+                Ok((snapshot.map(|snapshot| snapshot.delete(txid).0), ()))
+            },
+        )
+    }
+}
@@ -88,9 +88,14 @@ async fn sled_basic() {
 
 generate_store_tests!(tokio::test, SledTester);
 generate_index_tests!(tokio::test, SledTester);
+generate_statistics_tests!(tokio::test, SledTester);
 generate_transaction_tests!(tokio::test, SledTester);
 generate_alter_table_tests!(tokio::test, SledTester);
 generate_alter_table_index_tests!(tokio::test, SledTester);
 generate_transaction_alter_table_tests!(tokio::test, SledTester);
 generate_transaction_index_tests!(tokio::test, SledTester);
 generate_metadata_index_tests!(tokio::test, SledTester);
+generate_sequence_tests!(tokio::test, SledTester);
+generate_trigger_tests!(tokio::test, SledTester);
+generate_transaction_trigger_tests!(tokio::test, SledTester);
+generate_alter_table_sequence_tests!(tokio::test, SledTester);
@@ -388,6 +388,7 @@ async fn sled_transaction_gc() {
             .unwrap()
             .as_millis(),
         autocommit: false,
+        savepoints: Vec::new(),
     };
 
     test!(glue1 "SELECT * FROM NewGarlic", Err(Error::StorageMsg("fetch failed - expired transaction has used (txid)".to_owned())));
@@ -6,12 +6,12 @@ use {
     futures::stream::iter,
     gloo_storage::{LocalStorage, SessionStorage, Storage, errors::StorageError},
     gluesql_core::{
-        ast::ColumnUniqueOption,
         data::{Key, Schema},
         error::{Error, Result},
         store::{
             AlterTable, CustomFunction, CustomFunctionMut, DataRow, Index, IndexMut, Metadata,
-            RowIter, Store, StoreMut, Transaction,
+            RowIter, Sequence, SequenceMut, Statistics, StatisticsMut, Store, StoreMut,
+            Transaction, Trigger, TriggerMut, View, ViewMut,
         },
     },
     serde::{Deserialize, Serialize},
@@ -120,20 +120,9 @@ impl Store for WebStorage {
         let path = format!("{}/{}", DATA_PATH, table_name);
         let mut rows = self.get::<Vec<(Key, DataRow)>>(path)?.unwrap_or_default();
 
-        match self.get(format!("{}/{}", SCHEMA_PATH, table_name))? {
-            Some(Schema {
-                column_defs: Some(column_defs),
-                ..
-            }) if column_defs.iter().any(|column_def| {
-                matches!(
-                    column_def.unique,
-                    Some(ColumnUniqueOption { is_primary: true })
-                )
-            }) =>
-            {
-                rows.sort_by(|(key_a, _), (key_b, _)| key_a.cmp(key_b));
-            }
-            _ => {}
+        let schema: Option<Schema> = self.get(format!("{}/{}", SCHEMA_PATH, table_name))?;
+        if schema.is_some_and(|schema| schema.primary_key_indexes().is_some()) {
+            rows.sort_by(|(key_a, _), (key_b, _)| key_a.cmp(key_b));
         }
 
         Ok(Box::pin(iter(rows.into_iter().map(Ok))))
@@ -213,3 +202,11 @@ impl Transaction for WebStorage {}
 impl Metadata for WebStorage {}
 impl CustomFunction for WebStorage {}
 impl CustomFunctionMut for WebStorage {}
+impl View for WebStorage {}
+impl ViewMut for WebStorage {}
+impl Statistics for WebStorage {}
+impl StatisticsMut for WebStorage {}
+impl Sequence for WebStorage {}
+impl SequenceMut for WebStorage {}
+impl Trigger for WebStorage {}
+impl TriggerMut for WebStorage {}
@@ -2,10 +2,14 @@ mod alter_table;
 mod create_table;
 mod drop_indexed;
 mod drop_table;
+mod generated;
+mod identity;
 
 pub use {
-    alter_table::{alter_table_add_drop, alter_table_rename},
+    alter_table::{alter_table_add_drop, alter_table_check, alter_table_rename},
     create_table::create_table,
     drop_indexed::{drop_indexed_column, drop_indexed_table},
     drop_table::drop_table,
+    generated::generated_alter_table,
+    identity::identity_alter_table,
 };
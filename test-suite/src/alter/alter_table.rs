@@ -3,7 +3,7 @@ use {
     gluesql_core::{
         ast::*,
         data::Value::*,
-        error::{AlterError, AlterTableError, EvaluateError, TranslateError},
+        error::{AlterError, AlterTableError, EvaluateError, TranslateError, ValidateError},
         executor::Referencing,
         prelude::Payload,
     },
@@ -58,14 +58,17 @@ test_case!(alter_table_add_drop, {
         ("SELECT * FROM Foo;", Ok(select!(id; I64; 1; 2))),
         (
             "ALTER TABLE Foo ADD COLUMN amount INTEGER NOT NULL",
-            Err(AlterTableError::DefaultValueRequired(ColumnDef {
+            Err(AlterTableError::DefaultValueRequired(Box::new(ColumnDef {
                 name: "amount".to_owned(),
                 data_type: DataType::Int,
                 nullable: false,
                 default: None,
+                identity: None,
+                generated: None,
                 unique: None,
+                check: None,
                 comment: None,
-            })
+            }))
             .into()),
         ),
         (
@@ -255,3 +258,94 @@ test_case!(alter_table_add_drop, {
         g.test(sql, expected).await;
     }
 });
+
+test_case!(alter_table_check, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Stock (id INTEGER, quantity INTEGER, CHECK (quantity >= 0));")
+        .await;
+    g.run("INSERT INTO Stock VALUES (1, 10), (2, 0);").await;
+
+    g.named_test(
+        "add column whose check fails on existing rows",
+        "ALTER TABLE Stock ADD COLUMN price INTEGER DEFAULT 0 CHECK (price > 0)",
+        Err(ValidateError::CheckConstraintViolation {
+            table_name: "Stock".to_owned(),
+            constraint: r#""price" > 0"#.to_owned(),
+        }
+        .into()),
+    )
+    .await;
+
+    g.named_test(
+        "add column whose check passes on existing rows",
+        "ALTER TABLE Stock ADD COLUMN price INTEGER DEFAULT 1 CHECK (price > 0)",
+        Ok(Payload::AlterTable),
+    )
+    .await;
+
+    g.named_test(
+        "added check is enforced",
+        "INSERT INTO Stock VALUES (3, 1, -1)",
+        Err(ValidateError::CheckConstraintViolation {
+            table_name: "Stock".to_owned(),
+            constraint: r#""price" > 0"#.to_owned(),
+        }
+        .into()),
+    )
+    .await;
+
+    g.named_test(
+        "add nullable column with check and no default",
+        "ALTER TABLE Stock ADD COLUMN note TEXT CHECK (note <> '')",
+        Ok(Payload::AlterTable),
+    )
+    .await;
+
+    g.named_test(
+        "rename column used by a table check fails",
+        "ALTER TABLE Stock RENAME COLUMN quantity TO amount",
+        Err(AlterError::CannotAlterCheckedColumn {
+            column_name: "quantity".to_owned(),
+            constraint: r#""quantity" >= 0"#.to_owned(),
+        }
+        .into()),
+    )
+    .await;
+
+    g.named_test(
+        "drop column used by a table check fails",
+        "ALTER TABLE Stock DROP COLUMN quantity",
+        Err(AlterError::CannotAlterCheckedColumn {
+            column_name: "quantity".to_owned(),
+            constraint: r#""quantity" >= 0"#.to_owned(),
+        }
+        .into()),
+    )
+    .await;
+
+    g.named_test(
+        "drop column with its own check",
+        "ALTER TABLE Stock DROP COLUMN price",
+        Ok(Payload::AlterTable),
+    )
+    .await;
+
+    g.named_test(
+        "rename column without checks",
+        "ALTER TABLE Stock RENAME COLUMN id TO stock_id",
+        Ok(Payload::AlterTable),
+    )
+    .await;
+
+    g.named_test(
+        "rows after altering",
+        "SELECT stock_id, quantity, note FROM Stock ORDER BY stock_id",
+        Ok(select_with_null!(
+            stock_id | quantity | note;
+            I64(1)     I64(10)    Null;
+            I64(2)     I64(0)     Null
+        )),
+    )
+    .await;
+});
@@ -62,8 +62,8 @@ test_case!(create_table, {
             Err(TranslateError::UnsupportedDataType("GLOBE".to_owned()).into()),
         ),
         (
-            "CREATE TABLE Gluery (id INTEGER CHECK (true));",
-            Err(TranslateError::UnsupportedColumnOption("CHECK (true)".to_owned()).into()),
+            "CREATE TABLE Gluery (id INTEGER REFERENCES Other (id));",
+            Err(TranslateError::UnsupportedColumnOption("REFERENCES Other (id)".to_owned()).into()),
         ),
         (
             "
@@ -0,0 +1,69 @@
+use {
+    crate::*,
+    gluesql_core::{error::AlterError, prelude::Value::*},
+};
+
+test_case!(generated_alter_table, {
+    let g = get_tester!();
+
+    g.run(
+        "CREATE TABLE Item (
+            price INTEGER,
+            qty INTEGER,
+            total INTEGER GENERATED ALWAYS AS (price * qty) STORED
+        )",
+    )
+    .await;
+    g.run("INSERT INTO Item (price, qty) VALUES (3, 2)").await;
+
+    g.test(
+        "ALTER TABLE Item ADD COLUMN doubled INTEGER GENERATED ALWAYS AS (price * 2) STORED",
+        Err(AlterError::CannotAddGeneratedColumn("doubled".to_owned()).into()),
+    )
+    .await;
+    g.test(
+        "ALTER TABLE Item RENAME COLUMN price TO cost",
+        Err(AlterError::CannotAlterGeneratingColumn {
+            column_name: "price".to_owned(),
+            generated_column: "total".to_owned(),
+        }
+        .into()),
+    )
+    .await;
+    g.test(
+        "ALTER TABLE Item DROP COLUMN qty",
+        Err(AlterError::CannotAlterGeneratingColumn {
+            column_name: "qty".to_owned(),
+            generated_column: "total".to_owned(),
+        }
+        .into()),
+    )
+    .await;
+
+    g.run("ALTER TABLE Item RENAME COLUMN total TO amount")
+        .await;
+    g.run("INSERT INTO Item (price, qty) VALUES (5, 5)").await;
+    g.test(
+        "SELECT price, amount FROM Item",
+        Ok(select!(
+            price | amount
+            I64   | I64;
+            3       6;
+            5       25
+        )),
+    )
+    .await;
+
+    g.run("ALTER TABLE Item DROP COLUMN amount").await;
+    g.run("ALTER TABLE Item DROP COLUMN qty").await;
+    g.test(
+        "SELECT * FROM Item",
+        Ok(select!(
+            price
+            I64;
+            3;
+            5
+        )),
+    )
+    .await;
+});
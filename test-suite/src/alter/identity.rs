@@ -0,0 +1,43 @@
+use {
+    crate::*,
+    gluesql_core::{error::SequenceError, prelude::Value::*},
+};
+
+test_case!(identity_alter_table, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Foo (id INTEGER GENERATED ALWAYS AS IDENTITY, name TEXT)")
+        .await;
+    g.run("INSERT INTO Foo (name) VALUES ('a')").await;
+
+    g.run("ALTER TABLE Foo RENAME COLUMN id TO no").await;
+    g.run("INSERT INTO Foo (name) VALUES ('b')").await;
+    g.test("SELECT CURRVAL('Foo_no_seq') AS n", Ok(select!(n I64; 2)))
+        .await;
+    g.test(
+        "SELECT CURRVAL('Foo_id_seq') AS n",
+        Err(SequenceError::NotFound("Foo_id_seq".to_owned()).into()),
+    )
+    .await;
+
+    g.run("ALTER TABLE Foo RENAME TO Bar").await;
+    g.run("INSERT INTO Bar (name) VALUES ('c')").await;
+    g.test(
+        "SELECT no, name FROM Bar ORDER BY no",
+        Ok(select!(
+            no  | name
+            I64 | Str;
+            1     "a".to_owned();
+            2     "b".to_owned();
+            3     "c".to_owned()
+        )),
+    )
+    .await;
+
+    g.run("ALTER TABLE Bar DROP COLUMN no").await;
+    g.test(
+        "SELECT NEXTVAL('Bar_no_seq') AS n",
+        Err(SequenceError::NotFound("Bar_no_seq".to_owned()).into()),
+    )
+    .await;
+});
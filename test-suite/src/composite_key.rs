@@ -0,0 +1,213 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::{AlterError, UpdateError, ValidateError, ValueError},
+        prelude::{Key, Payload, Value::*},
+    },
+};
+
+test_case!(composite_key, {
+    let g = get_tester!();
+
+    g.named_test(
+        "create table with a composite primary key and a multi-column unique constraint",
+        "
+        CREATE TABLE Member (
+            tenant_id INTEGER,
+            id INTEGER,
+            num INTEGER,
+            name TEXT,
+            PRIMARY KEY (tenant_id, id),
+            CONSTRAINT tenant_num UNIQUE (tenant_id, num)
+        )
+        ",
+        Ok(Payload::Create),
+    )
+    .await;
+
+    g.named_test(
+        "insert rows sharing a part of the key",
+        "
+        INSERT INTO Member VALUES
+            (2, 1, 10, 'Dana'),
+            (1, 2, 20, 'Alex'),
+            (1, 1, 10, 'Blair'),
+            (2, 2, NULL, 'Chris'),
+            (2, 3, NULL, 'Evan');
+        ",
+        Ok(Payload::Insert(5)),
+    )
+    .await;
+
+    g.named_test(
+        "rows are ordered by the composite primary key",
+        "SELECT tenant_id, id, name FROM Member",
+        Ok(select!(
+            tenant_id | id  | name
+            I64       | I64 | Str;
+            1           1     "Blair".to_owned();
+            1           2     "Alex".to_owned();
+            2           1     "Dana".to_owned();
+            2           2     "Chris".to_owned();
+            2           3     "Evan".to_owned()
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "duplicate composite primary key fails",
+        "INSERT INTO Member VALUES (1, 2, 30, 'Fern')",
+        Err(
+            ValidateError::DuplicateEntryOnPrimaryKeyField(Key::Composite(vec![
+                Key::I64(1),
+                Key::I64(2),
+            ]))
+            .into(),
+        ),
+    )
+    .await;
+
+    g.named_test(
+        "duplicate composite primary key within the inserted rows fails",
+        "INSERT INTO Member VALUES (3, 1, 10, 'Fern'), (3, 1, 20, 'Gale')",
+        Err(
+            ValidateError::DuplicateEntryOnPrimaryKeyField(Key::Composite(vec![
+                Key::I64(3),
+                Key::I64(1),
+            ]))
+            .into(),
+        ),
+    )
+    .await;
+
+    g.named_test(
+        "duplicate multi-column unique key fails",
+        "INSERT INTO Member VALUES (1, 3, 20, 'Fern')",
+        Err(ValidateError::DuplicateEntryOnUniqueColumns(
+            Key::Composite(vec![Key::I64(1), Key::I64(20)]),
+            "tenant_id, num".to_owned(),
+        )
+        .into()),
+    )
+    .await;
+
+    g.named_test(
+        "NULL in a multi-column unique key is never a duplicate",
+        "INSERT INTO Member VALUES (2, 4, NULL, 'Fern')",
+        Ok(Payload::Insert(1)),
+    )
+    .await;
+
+    g.named_test(
+        "select by the full composite key",
+        "SELECT name FROM Member WHERE id = 2 AND tenant_id = 1",
+        Ok(select!(name Str; "Alex".to_owned())),
+    )
+    .await;
+
+    g.named_test(
+        "select by the leading column of the composite key",
+        "SELECT id, name FROM Member WHERE tenant_id = 2 AND name <> 'Chris'",
+        Ok(select!(
+            id  | name
+            I64 | Str;
+            1     "Dana".to_owned();
+            3     "Evan".to_owned();
+            4     "Fern".to_owned()
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "select by a non-leading column of the composite key",
+        "SELECT tenant_id, name FROM Member WHERE id = 1",
+        Ok(select!(
+            tenant_id | name
+            I64       | Str;
+            1           "Blair".to_owned();
+            2           "Dana".to_owned()
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "update violating the multi-column unique key fails",
+        "UPDATE Member SET num = 10 WHERE tenant_id = 2 AND id = 2",
+        Err(ValidateError::DuplicateEntryOnUniqueColumns(
+            Key::Composite(vec![Key::I64(2), Key::I64(10)]),
+            "tenant_id, num".to_owned(),
+        )
+        .into()),
+    )
+    .await;
+
+    g.named_test(
+        "update on a composite primary key column is not supported",
+        "UPDATE Member SET id = 5 WHERE tenant_id = 1 AND id = 1",
+        Err(UpdateError::UpdateOnPrimaryKeyNotSupported("id".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "on conflict over the composite primary key updates the row",
+        "
+        INSERT INTO Member VALUES (1, 1, 10, 'Hana')
+        ON CONFLICT (tenant_id, id) DO UPDATE SET name = EXCLUDED.name
+        ",
+        Ok(Payload::Insert(1)),
+    )
+    .await;
+
+    g.named_test(
+        "delete by the composite key",
+        "DELETE FROM Member WHERE tenant_id = 2 AND id = 4",
+        Ok(Payload::Delete(1)),
+    )
+    .await;
+
+    g.named_test(
+        "rows after the changes",
+        "SELECT tenant_id, id, num, name FROM Member",
+        Ok(select_with_null!(
+            tenant_id | id     | num     | name;
+            I64(1)      I64(1)   I64(10)   Str("Hana".to_owned());
+            I64(1)      I64(2)   I64(20)   Str("Alex".to_owned());
+            I64(2)      I64(1)   I64(10)   Str("Dana".to_owned());
+            I64(2)      I64(2)   Null      Str("Chris".to_owned());
+            I64(2)      I64(3)   Null      Str("Evan".to_owned())
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "dropping a key column fails",
+        "ALTER TABLE Member DROP COLUMN num",
+        Err(AlterError::CannotAlterKeyColumn {
+            column_name: "num".to_owned(),
+            constraint: "tenant_num".to_owned(),
+        }
+        .into()),
+    )
+    .await;
+
+    g.named_test(
+        "composite primary key column is not nullable",
+        "INSERT INTO Member VALUES (NULL, 9, 90, 'Ivy')",
+        Err(ValueError::NullValueOnNotNullField.into()),
+    )
+    .await;
+
+    g.named_test(
+        "key constraint on an unknown column fails",
+        "CREATE TABLE Wrong (a INTEGER, PRIMARY KEY (a, b))",
+        Err(AlterError::KeyColumnNotFound("b".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "more than one primary key fails",
+        "CREATE TABLE Wrong (a INTEGER PRIMARY KEY, b INTEGER, c INTEGER, PRIMARY KEY (b, c))",
+        Err(AlterError::MultiplePrimaryKeys.into()),
+    )
+    .await;
+});
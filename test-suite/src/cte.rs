@@ -0,0 +1,240 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::{FetchError, SelectError, TranslateError},
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(cte, {
+    let g = get_tester!();
+
+    g.run(
+        "
+        CREATE TABLE Employee (
+            id INTEGER PRIMARY KEY,
+            name TEXT,
+            manager_id INTEGER NULL
+        );
+    ",
+    )
+    .await;
+    g.run(
+        "
+        INSERT INTO Employee VALUES
+            (1, 'Alice', NULL),
+            (2, 'Bob', 1),
+            (3, 'Carol', 1),
+            (4, 'Dave', 2),
+            (5, 'Eve', 4);
+    ",
+    )
+    .await;
+
+    g.named_test(
+        "CTE is visible to the main query",
+        "
+        WITH Managers AS (SELECT id, name FROM Employee WHERE manager_id IS NULL)
+        SELECT name FROM Managers
+        ",
+        Ok(select!(name; Str; "Alice".to_owned())),
+    )
+    .await;
+
+    g.named_test(
+        "later CTE can refer to the earlier one",
+        "
+        WITH
+            Reports AS (SELECT id, name, manager_id FROM Employee WHERE manager_id IS NOT NULL),
+            BobReports AS (SELECT name FROM Reports WHERE manager_id = 2)
+        SELECT * FROM BobReports
+        ",
+        Ok(select!(name; Str; "Dave".to_owned())),
+    )
+    .await;
+
+    g.named_test(
+        "column aliases of CTE",
+        "
+        WITH Pair (a, b) AS (SELECT id, name FROM Employee WHERE id < 3)
+        SELECT b, a FROM Pair ORDER BY a DESC
+        ",
+        Ok(select!(
+            b                | a
+            Str              | I64;
+            "Bob".to_owned()   2;
+            "Alice".to_owned() 1
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "CTE joined with a table",
+        "
+        WITH Managers AS (SELECT id, name FROM Employee WHERE id IN (1, 2))
+        SELECT e.name AS employee, m.name AS manager
+        FROM Employee e
+        JOIN Managers m ON e.manager_id = m.id
+        ORDER BY e.id
+        ",
+        Ok(select!(
+            employee           | manager
+            Str                | Str;
+            "Bob".to_owned()     "Alice".to_owned();
+            "Carol".to_owned()   "Alice".to_owned();
+            "Dave".to_owned()    "Bob".to_owned()
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "CTE referenced from a subquery",
+        "
+        WITH Leaves AS (
+            SELECT id FROM Employee
+            WHERE id NOT IN (SELECT manager_id FROM Employee WHERE manager_id IS NOT NULL)
+        )
+        SELECT name FROM Employee WHERE id IN (SELECT id FROM Leaves) ORDER BY id
+        ",
+        Ok(select!(name; Str; "Carol".to_owned(); "Eve".to_owned())),
+    )
+    .await;
+
+    g.named_test(
+        "CTE in a derived table",
+        "SELECT * FROM (WITH Top AS (SELECT id FROM Employee WHERE id = 1) SELECT * FROM Top) AS Sub",
+        Ok(select!(id; I64; 1)),
+    )
+    .await;
+
+    g.named_test(
+        "CTE shadows a table with the same name",
+        "
+        WITH Employee AS (SELECT id FROM Employee WHERE id > 3)
+        SELECT id FROM Employee WHERE id = 4
+        ",
+        Ok(select!(id; I64; 4)),
+    )
+    .await;
+
+    g.named_test(
+        "WITH RECURSIVE generates a sequence",
+        "
+        WITH RECURSIVE Nums (n) AS (
+            SELECT 1
+            UNION ALL
+            SELECT n + 1 FROM Nums WHERE n < 5
+        )
+        SELECT SUM(n) AS total FROM Nums
+        ",
+        Ok(select!(total; I64; 15)),
+    )
+    .await;
+
+    g.named_test(
+        "WITH RECURSIVE walks an org chart",
+        "
+        WITH RECURSIVE Chain (id, name, depth) AS (
+            SELECT id, name, 0 FROM Employee WHERE manager_id IS NULL
+            UNION ALL
+            SELECT e.id, e.name, c.depth + 1
+            FROM Employee e
+            JOIN Chain c ON e.manager_id = c.id
+        )
+        SELECT name, depth FROM Chain ORDER BY depth, name
+        ",
+        Ok(select!(
+            name               | depth
+            Str                | I64;
+            "Alice".to_owned()   0;
+            "Bob".to_owned()     1;
+            "Carol".to_owned()   1;
+            "Dave".to_owned()    2;
+            "Eve".to_owned()     3
+        )),
+    )
+    .await;
+
+    g.run("CREATE TABLE Edge (src INTEGER, dst INTEGER)").await;
+    g.run("INSERT INTO Edge VALUES (1, 2), (2, 3), (3, 1), (4, 5)")
+        .await;
+
+    g.named_test(
+        "WITH RECURSIVE using UNION stops on cycles",
+        "
+        WITH RECURSIVE Reach (node) AS (
+            SELECT 1
+            UNION
+            SELECT Edge.dst FROM Edge JOIN Reach ON Edge.src = Reach.node
+        )
+        SELECT node FROM Reach ORDER BY node
+        ",
+        Ok(select!(node; I64; 1; 2; 3)),
+    )
+    .await;
+
+    g.named_test(
+        "ORDER BY and LIMIT of a recursive CTE apply to its whole result",
+        "
+        WITH RECURSIVE Nums (n) AS (
+            SELECT 1
+            UNION ALL
+            SELECT n + 1 FROM Nums WHERE n < 10
+            ORDER BY n DESC
+            LIMIT 3
+        )
+        SELECT n FROM Nums
+        ",
+        Ok(select!(n; I64; 10; 9; 8)),
+    )
+    .await;
+
+    g.named_test(
+        "recursive CTE that never stops hits the recursion limit",
+        "
+        WITH RECURSIVE Nums (n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM Nums)
+        SELECT COUNT(*) FROM Nums
+        ",
+        Err(SelectError::RecursionLimitExceeded {
+            name: "Nums".to_owned(),
+            limit: 1000,
+        }
+        .into()),
+    )
+    .await;
+
+    g.named_test(
+        "recursion limit counts iterations, not rows",
+        "
+        WITH RECURSIVE Nums (n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM Nums WHERE n < 1000)
+        SELECT COUNT(*) AS cnt FROM Nums
+        ",
+        Ok(select!(cnt; I64; 1000)),
+    )
+    .await;
+
+    g.named_test(
+        "INSERT INTO ... WITH ... SELECT",
+        "
+        INSERT INTO Edge
+        WITH RECURSIVE Nums (n) AS (SELECT 6 UNION ALL SELECT n + 1 FROM Nums WHERE n < 8)
+        SELECT n, n + 1 FROM Nums
+        ",
+        Ok(Payload::Insert(3)),
+    )
+    .await;
+
+    g.named_test(
+        "CTE names must be unique",
+        "WITH A AS (SELECT 1), A AS (SELECT 2) SELECT * FROM A",
+        Err(TranslateError::DuplicateCteName("A".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "CTE cannot have more column aliases than columns",
+        "WITH A (x, y) AS (SELECT 1) SELECT * FROM A",
+        Err(FetchError::TooManyColumnAliases("A".to_owned(), 1, 2).into()),
+    )
+    .await;
+});
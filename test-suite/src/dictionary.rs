@@ -65,13 +65,13 @@ test_case!(dictionary, {
     g.test(
         "SELECT * FROM GLUE_TABLE_COLUMNS",
         Ok(select_with_null!(
-            TABLE_NAME | COLUMN_NAME | COLUMN_ID | NULLABLE    | KEY              | DEFAULT     | COMMENT;
-            s("Bar")     s("id")       I64(1)      Bool(true)    s("UNIQUE")        Null          Null;
-            s("Bar")     s("name")     I64(2)      Bool(false)   Null               s("'NONE'")   Null;
-            s("Foo")     s("id")       I64(1)      Bool(true)    Null               Null          Null;
-            s("Foo")     s("name")     I64(2)      Bool(true)    Null               Null          Null;
-            s("Foo")     s("type")     I64(3)      Bool(true)    Null               Null          Null;
-            s("Zoo")     s("id")       I64(1)      Bool(false)   s("PRIMARY KEY")   Null          s("hello") 
+            TABLE_NAME | COLUMN_NAME | COLUMN_ID | NULLABLE    | KEY              | DEFAULT     | GENERATED | COMMENT;
+            s("Bar")     s("id")       I64(1)      Bool(true)    s("UNIQUE")        Null          Null        Null;
+            s("Bar")     s("name")     I64(2)      Bool(false)   Null               s("'NONE'")   Null        Null;
+            s("Foo")     s("id")       I64(1)      Bool(true)    Null               Null          Null        Null;
+            s("Foo")     s("name")     I64(2)      Bool(true)    Null               Null          Null        Null;
+            s("Foo")     s("type")     I64(3)      Bool(true)    Null               Null          Null        Null;
+            s("Zoo")     s("id")       I64(1)      Bool(false)   s("PRIMARY KEY")   Null          Null        s("hello") 
         ))
     ).await;
 });
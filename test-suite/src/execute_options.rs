@@ -0,0 +1,125 @@
+use {
+    crate::*,
+    futures::stream::TryStreamExt,
+    gluesql_core::{
+        error::LimitError,
+        prelude::{CancelToken, ExecuteOptions, Payload, PayloadStream, Value::*},
+    },
+    std::time::Duration,
+};
+
+test_case!(execute_options, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Item (id INTEGER PRIMARY KEY, category TEXT);")
+        .await;
+    g.run("INSERT INTO Item VALUES (1, 'A'), (2, 'B'), (3, 'C'), (4, 'A');")
+        .await;
+
+    g.get_glue().set_options(ExecuteOptions {
+        max_rows: Some(3),
+        ..ExecuteOptions::default()
+    });
+    g.test(
+        "SELECT id FROM Item",
+        Err(LimitError::RowLimitExceeded(3).into()),
+    )
+    .await;
+    g.test(
+        "SELECT id FROM Item WHERE id > 1",
+        Ok(select!(id I64; 2; 3; 4)),
+    )
+    .await;
+    g.named_test(
+        "statements other than SELECT are not limited by max_rows",
+        "UPDATE Item SET category = category",
+        Ok(Payload::Update(4)),
+    )
+    .await;
+
+    match g
+        .get_glue()
+        .execute_stream("SELECT id FROM Item")
+        .await
+        .unwrap()
+    {
+        PayloadStream::Select { rows, .. } => assert_eq!(
+            rows.try_collect::<Vec<_>>().await,
+            Err(LimitError::RowLimitExceeded(3).into()),
+        ),
+        _ => panic!("SELECT must be streamed"),
+    }
+
+    g.get_glue().set_options(ExecuteOptions {
+        max_buffered_rows: Some(2),
+        ..ExecuteOptions::default()
+    });
+    g.named_test(
+        "rows streamed without buffering",
+        "SELECT id FROM Item WHERE id < 4",
+        Ok(select!(id I64; 1; 2; 3)),
+    )
+    .await;
+    g.test(
+        "SELECT id FROM Item ORDER BY category",
+        Err(LimitError::BufferedRowLimitExceeded(2).into()),
+    )
+    .await;
+    g.named_test(
+        "top-N sort keeps only the rows of LIMIT",
+        "SELECT id FROM Item ORDER BY category, id LIMIT 2",
+        Ok(select!(id I64; 1; 4)),
+    )
+    .await;
+    g.test(
+        "SELECT category, COUNT(*) FROM Item GROUP BY category",
+        Err(LimitError::BufferedRowLimitExceeded(2).into()),
+    )
+    .await;
+    g.test(
+        "SELECT DISTINCT category FROM Item",
+        Err(LimitError::BufferedRowLimitExceeded(2).into()),
+    )
+    .await;
+    g.test(
+        "SELECT a.id FROM Item a RIGHT JOIN Item b ON a.id = b.id",
+        Err(LimitError::BufferedRowLimitExceeded(2).into()),
+    )
+    .await;
+
+    let cancel = CancelToken::new();
+    g.get_glue().set_options(ExecuteOptions {
+        cancel: Some(cancel.clone()),
+        ..ExecuteOptions::default()
+    });
+    g.named_test(
+        "statements run until the token is cancelled",
+        "SELECT COUNT(*) AS cnt FROM Item a CROSS JOIN Item b",
+        Ok(select!(cnt I64; 16)),
+    )
+    .await;
+    cancel.cancel();
+    g.test(
+        "SELECT COUNT(*) AS cnt FROM Item a CROSS JOIN Item b",
+        Err(LimitError::Cancelled.into()),
+    )
+    .await;
+
+    g.get_glue().set_options(ExecuteOptions {
+        timeout: Some(Duration::ZERO),
+        ..ExecuteOptions::default()
+    });
+    g.test(
+        "SELECT a.id FROM Item a JOIN Item b ON a.id = b.id",
+        Err(LimitError::Timeout(Duration::ZERO).into()),
+    )
+    .await;
+
+    g.get_glue().set_options(ExecuteOptions::default());
+    g.named_test(
+        "statements run without limits once the options are reset",
+        "SELECT COUNT(*) AS cnt FROM Item a CROSS JOIN Item b",
+        Ok(select!(cnt I64; 16)),
+    )
+    .await;
+});
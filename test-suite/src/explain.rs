@@ -0,0 +1,189 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::TranslateError,
+        executor::{PlanActual, PlanNode},
+        prelude::Payload,
+    },
+    pretty_assertions::assert_eq,
+};
+
+/// Operators of `plan` in pre-order with the rows reported by `EXPLAIN ANALYZE`
+fn actual_rows(plan: &PlanNode) -> Vec<(&str, Option<usize>)> {
+    let rows = plan.actual.map(|PlanActual { rows, .. }| rows);
+
+    std::iter::once((plan.operator.as_str(), rows))
+        .chain(plan.children.iter().flat_map(actual_rows))
+        .collect()
+}
+
+test_case!(explain, {
+    let g = get_tester!();
+
+    g.run(
+        "
+CREATE TABLE Item (
+    id INTEGER PRIMARY KEY,
+    category_id INTEGER,
+    name TEXT,
+    price INTEGER
+)",
+    )
+    .await;
+    g.run("CREATE TABLE Category (id INTEGER PRIMARY KEY, name TEXT)")
+        .await;
+    g.run(
+        "
+        INSERT INTO Item VALUES
+            (1, 1, 'Apple', 30),
+            (2, 1, 'Banana', 10),
+            (3, 2, 'Carrot', 20),
+            (4, 2, 'Daikon', 40),
+            (5, 3, 'Egg', 5);
+    ",
+    )
+    .await;
+    g.run("INSERT INTO Category VALUES (1, 'Fruit'), (2, 'Vegetable')")
+        .await;
+
+    let test_cases = [
+        (
+            "EXPLAIN SELECT * FROM Item",
+            "
+Project: *
+  Seq Scan: Item",
+        ),
+        (
+            "EXPLAIN SELECT name FROM Item WHERE id = 1",
+            "
+Project: name
+  Primary Key Lookup: Item, key: 1",
+        ),
+        (
+            "
+            EXPLAIN SELECT i.name, c.name
+            FROM Item i
+            JOIN Category c ON c.id = i.category_id
+            WHERE i.price > 10
+            ORDER BY i.price DESC
+            LIMIT 2
+            ",
+            "
+Limit: 2
+  Sort: i.price DESC
+    Project: i.name AS name, c.name AS name
+      Filter: i.price > 10
+        Hash Join: inner, hash: c.id = i.category_id
+          Seq Scan: Item AS i
+          Seq Scan: Category AS c",
+        ),
+        (
+            "
+            EXPLAIN SELECT DISTINCT category_id
+            FROM Item
+            LEFT JOIN Category ON Category.id < Item.category_id
+            OFFSET 1
+            ",
+            "
+Limit: offset 1
+  Distinct
+    Project: category_id
+      Nested Loop Join: left outer, on: Category.id < Item.category_id
+        Seq Scan: Item
+        Seq Scan: Category",
+        ),
+        (
+            "
+            EXPLAIN SELECT category_id, COUNT(*)
+            FROM Item
+            GROUP BY category_id
+            HAVING COUNT(*) > 1
+            ",
+            "
+Project: category_id, COUNT(*)
+  Aggregate: group by: category_id, having: COUNT(*) > 1
+    Seq Scan: Item",
+        ),
+        (
+            "EXPLAIN SELECT * FROM (SELECT id FROM Item) AS Sub UNION SELECT 1",
+            "
+Union
+  Project: *
+    Subquery Scan: Sub
+      Project: id
+        Seq Scan: Item
+  Project: 1
+    Series Scan: SERIES(1)",
+        ),
+        (
+            "EXPLAIN VALUES (1), (2)",
+            "
+Values: 2 rows",
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        match g.run(sql).await {
+            Payload::Explain(plan) => assert_eq!(plan.to_string(), expected.trim_start()),
+            payload => panic!("unexpected payload: {payload:?}"),
+        }
+    }
+
+    let plan = match g
+        .run(
+            "
+            EXPLAIN ANALYZE SELECT i.name, c.name
+            FROM Item i
+            JOIN Category c ON c.id = i.category_id
+            WHERE i.price > 10 * c.id
+            ORDER BY i.price DESC
+            LIMIT 2
+            ",
+        )
+        .await
+    {
+        Payload::Explain(plan) => plan,
+        payload => panic!("unexpected payload: {payload:?}"),
+    };
+    assert_eq!(
+        actual_rows(&plan),
+        vec![
+            ("Limit", Some(2)),
+            ("Sort", Some(2)),
+            ("Project", Some(2)),
+            ("Filter", Some(2)),
+            ("Hash Join", Some(4)),
+            ("Seq Scan", Some(5)),
+            ("Seq Scan", None),
+        ]
+    );
+
+    let plan = match g
+        .run("EXPLAIN ANALYZE SELECT category_id, COUNT(*) FROM Item GROUP BY category_id")
+        .await
+    {
+        Payload::Explain(plan) => plan,
+        payload => panic!("unexpected payload: {payload:?}"),
+    };
+    assert_eq!(
+        actual_rows(&plan),
+        vec![
+            ("Project", Some(3)),
+            ("Aggregate", Some(3)),
+            ("Seq Scan", Some(5)),
+        ]
+    );
+
+    let plan = match g.run("EXPLAIN ANALYZE VALUES (1), (2), (3)").await {
+        Payload::Explain(plan) => plan,
+        payload => panic!("unexpected payload: {payload:?}"),
+    };
+    assert_eq!(actual_rows(&plan), vec![("Values", Some(3))]);
+
+    g.named_test(
+        "only queries can be explained",
+        "EXPLAIN DELETE FROM Item",
+        Err(TranslateError::UnsupportedExplainStatement("DELETE FROM Item".to_owned()).into()),
+    )
+    .await;
+});
@@ -5,9 +5,9 @@ use {
             DataType::{Int, Text},
             ForeignKey, ReferentialAction,
         },
-        error::{DeleteError, InsertError, TranslateError, UpdateError},
+        error::{DeleteError, InsertError, UpdateError, ValueError},
         executor::{AlterError, Referencing},
-        prelude::Payload,
+        prelude::{Payload, Value::*},
     },
 };
 
@@ -46,18 +46,14 @@ test_case!(foreign_key, {
     .await;
 
     g.named_test(
-        "Creating table with foreign key should be failed if referenced table has only Unique constraint",
-        "CREATE TABLE ReferencingTable (
+        "Creating table with foreign key should succeed if referenced column is unique",
+        "CREATE TABLE ReferencingUniqueTable (
             id INT,
             name TEXT,
             referenced_id INT,
             FOREIGN KEY (referenced_id) REFERENCES ReferencedTableWithUnique (id)
         );",
-        Err(AlterError::ReferencingNonPKColumn {
-            referenced_table: "ReferencedTableWithUnique".to_owned(),
-            referenced_column: "id".to_owned(),
-        }
-        .into()),
+        Ok(Payload::Create),
     )
     .await;
 
@@ -87,42 +83,6 @@ test_case!(foreign_key, {
     )
     .await;
 
-    g.named_test(
-        "Unsupported foreign key option: CASCADE",
-        "CREATE TABLE ReferencingTable (
-            id INT,
-            name TEXT,
-            referenced_id INT,
-            FOREIGN KEY (referenced_id) REFERENCES ReferencedTableWithPK (id) ON DELETE CASCADE
-        );",
-        Err(TranslateError::UnsupportedConstraint("CASCADE".to_owned()).into()),
-    )
-    .await;
-
-    g.named_test(
-        "Unsupported foreign key option: SET DEFAULT",
-        "CREATE TABLE ReferencingTable (
-            id INT,
-            name TEXT,
-            referenced_id INT,
-            FOREIGN KEY (referenced_id) REFERENCES ReferencedTableWithPK (id) ON DELETE SET DEFAULT
-        );",
-        Err(TranslateError::UnsupportedConstraint("SET DEFAULT".to_owned()).into()),
-    )
-    .await;
-
-    g.named_test(
-        "Unsupported foreign key option: SET NULL",
-        "CREATE TABLE ReferencingTable (
-            id INT,
-            name TEXT,
-            referenced_id INT,
-            FOREIGN KEY (referenced_id) REFERENCES ReferencedTableWithPK (id) ON DELETE SET NULL
-        );",
-        Err(TranslateError::UnsupportedConstraint("SET NULL".to_owned()).into()),
-    )
-    .await;
-
     g.named_test(
         "Referencing column not found",
         "CREATE TABLE ReferencingTable (
@@ -148,7 +108,7 @@ test_case!(foreign_key, {
     .await;
 
     g.named_test(
-        "Creating table with foreign key should be succeeded if referenced table has primary key. NO ACTION(=RESTRICT) is default",
+        "Creating table with foreign key should be succeeded if referenced table has primary key. NO ACTION is default",
         "CREATE TABLE ReferencingTable (
             id INT,
             name TEXT,
@@ -284,7 +244,7 @@ test_case!(foreign_key, {
                         referenced_table_name: "ReferencedTableWithPK".to_owned(),
                         referenced_column_name: "id".to_owned(),
                         on_delete: ReferentialAction::NoAction,
-                        on_update: ReferentialAction::NoAction,
+                        on_update: ReferentialAction::Restrict,
                     },
                 },
                 Referencing {
@@ -330,3 +290,349 @@ test_case!(foreign_key, {
     )
     .await;
 });
+
+test_case!(referential_action, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Parent (id INTEGER PRIMARY KEY, name TEXT);")
+        .await;
+    g.run(
+        "CREATE TABLE Child (
+            id INTEGER PRIMARY KEY,
+            parent_id INTEGER,
+            FOREIGN KEY (parent_id) REFERENCES Parent (id) ON DELETE CASCADE
+        );",
+    )
+    .await;
+    g.run(
+        "CREATE TABLE GrandChild (
+            id INTEGER PRIMARY KEY,
+            child_id INTEGER,
+            FOREIGN KEY (child_id) REFERENCES Child (id) ON DELETE CASCADE
+        );",
+    )
+    .await;
+    g.run(
+        "CREATE TABLE Note (
+            id INTEGER PRIMARY KEY,
+            parent_id INTEGER,
+            FOREIGN KEY (parent_id) REFERENCES Parent (id) ON DELETE SET NULL
+        );",
+    )
+    .await;
+    g.run(
+        "CREATE TABLE Task (
+            id INTEGER PRIMARY KEY,
+            parent_id INTEGER DEFAULT 0,
+            FOREIGN KEY (parent_id) REFERENCES Parent (id) ON DELETE SET DEFAULT
+        );",
+    )
+    .await;
+
+    g.run("INSERT INTO Parent VALUES (0, 'default'), (1, 'a'), (2, 'b'), (3, 'c');")
+        .await;
+    g.run("INSERT INTO Child VALUES (1, 1), (2, 1), (3, 2);")
+        .await;
+    g.run("INSERT INTO GrandChild VALUES (1, 1), (2, 2), (3, 3);")
+        .await;
+    g.run("INSERT INTO Note VALUES (1, 1), (2, 2);").await;
+    g.run("INSERT INTO Task VALUES (1, 1), (2, 3);").await;
+
+    g.named_test(
+        "deleting a parent runs every ON DELETE action",
+        "DELETE FROM Parent WHERE id = 1;",
+        Ok(Payload::Delete(1)),
+    )
+    .await;
+
+    g.named_test(
+        "CASCADE deletes referencing rows",
+        "SELECT id, parent_id FROM Child;",
+        Ok(select!(
+            id  | parent_id
+            I64 | I64;
+            3     2
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "CASCADE recurses into the rows referencing the deleted rows",
+        "SELECT id, child_id FROM GrandChild;",
+        Ok(select!(
+            id  | child_id
+            I64 | I64;
+            3     3
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "SET NULL clears the referencing column",
+        "SELECT id, parent_id FROM Note;",
+        Ok(select_with_null!(
+            id     | parent_id;
+            I64(1)   Null;
+            I64(2)   I64(2)
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "SET DEFAULT writes the column default",
+        "SELECT id, parent_id FROM Task;",
+        Ok(select!(
+            id  | parent_id
+            I64 | I64;
+            1     0;
+            2     3
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "SET DEFAULT fails when the default is not referenced anymore",
+        "DELETE FROM Parent WHERE id IN (0, 3);",
+        Err(UpdateError::CannotFindReferencedValue {
+            table_name: "Parent".to_owned(),
+            column_name: "id".to_owned(),
+            referenced_value: "0".to_owned(),
+        }
+        .into()),
+    )
+    .await;
+
+    g.run(
+        "CREATE TABLE Strict (
+            id INTEGER PRIMARY KEY,
+            parent_id INTEGER,
+            FOREIGN KEY (parent_id) REFERENCES Parent (id) ON DELETE RESTRICT
+        );",
+    )
+    .await;
+    g.run("INSERT INTO Strict VALUES (1, 2);").await;
+
+    g.named_test(
+        "RESTRICT fails and nothing is cascaded",
+        "DELETE FROM Parent WHERE id = 2;",
+        Err(DeleteError::ReferencingColumnExists("Strict.parent_id".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "rows stay after the failed delete",
+        "SELECT id FROM Child;",
+        Ok(select!(id I64; 3)),
+    )
+    .await;
+
+    g.run("DELETE FROM Strict;").await;
+    g.run(
+        "CREATE TABLE Required (
+            id INTEGER PRIMARY KEY,
+            parent_id INTEGER NOT NULL,
+            FOREIGN KEY (parent_id) REFERENCES Parent (id) ON DELETE SET NULL
+        );",
+    )
+    .await;
+    g.run("INSERT INTO Required VALUES (1, 2);").await;
+
+    g.named_test(
+        "SET NULL fails on a NOT NULL column",
+        "DELETE FROM Parent WHERE id = 2;",
+        Err(ValueError::NullValueOnNotNullField.into()),
+    )
+    .await;
+
+    g.run(
+        "CREATE TABLE Node (
+            id INTEGER PRIMARY KEY,
+            next_id INTEGER,
+            FOREIGN KEY (next_id) REFERENCES Node (id) ON DELETE CASCADE
+        );",
+    )
+    .await;
+    g.run("INSERT INTO Node VALUES (1, NULL), (4, NULL);").await;
+    g.run("INSERT INTO Node VALUES (2, 1);").await;
+    g.run("INSERT INTO Node VALUES (3, 2);").await;
+    g.run("UPDATE Node SET next_id = 3 WHERE id = 1;").await;
+
+    g.named_test(
+        "cascades stop on a reference cycle",
+        "DELETE FROM Node WHERE id = 2;",
+        Ok(Payload::Delete(1)),
+    )
+    .await;
+
+    g.named_test(
+        "every row in the cycle is deleted",
+        "SELECT id FROM Node;",
+        Ok(select!(id I64; 4)),
+    )
+    .await;
+});
+
+test_case!(on_update, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Account (id INTEGER PRIMARY KEY, code TEXT UNIQUE);")
+        .await;
+    g.run(
+        "CREATE TABLE Payment (
+            id INTEGER PRIMARY KEY,
+            account_code TEXT NULL,
+            FOREIGN KEY (account_code) REFERENCES Account (code) ON UPDATE CASCADE
+        );",
+    )
+    .await;
+    g.run(
+        "CREATE TABLE Invoice (
+            id INTEGER PRIMARY KEY,
+            account_code TEXT NULL,
+            FOREIGN KEY (account_code) REFERENCES Account (code) ON UPDATE SET NULL
+        );",
+    )
+    .await;
+    g.run(
+        "CREATE TABLE Ticket (
+            id INTEGER PRIMARY KEY,
+            account_code TEXT DEFAULT 'none',
+            FOREIGN KEY (account_code) REFERENCES Account (code) ON UPDATE SET DEFAULT
+        );",
+    )
+    .await;
+
+    g.run("INSERT INTO Account VALUES (0, 'none'), (1, 'a'), (2, 'b');")
+        .await;
+    g.run("INSERT INTO Payment VALUES (1, 'a'), (2, 'a'), (3, 'b');")
+        .await;
+    g.run("INSERT INTO Invoice VALUES (1, 'a'), (2, 'b');")
+        .await;
+    g.run("INSERT INTO Ticket VALUES (1, 'a'), (2, 'b');").await;
+
+    g.named_test(
+        "foreign keys to a unique column are validated by value",
+        "INSERT INTO Payment VALUES (4, 'c');",
+        Err(InsertError::CannotFindReferencedValue {
+            table_name: "Account".to_owned(),
+            column_name: "code".to_owned(),
+            referenced_value: "c".to_owned(),
+        }
+        .into()),
+    )
+    .await;
+
+    g.named_test(
+        "updating a referenced column runs every ON UPDATE action",
+        "UPDATE Account SET code = 'alpha' WHERE id = 1;",
+        Ok(Payload::Update(1)),
+    )
+    .await;
+
+    g.named_test(
+        "CASCADE writes the new value into the referencing rows",
+        "SELECT id, account_code FROM Payment;",
+        Ok(select!(
+            id  | account_code
+            I64 | Str;
+            1     "alpha".to_owned();
+            2     "alpha".to_owned();
+            3     "b".to_owned()
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "SET NULL clears the referencing column",
+        "SELECT id, account_code FROM Invoice;",
+        Ok(select_with_null!(
+            id     | account_code;
+            I64(1)   Null;
+            I64(2)   Str("b".to_owned())
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "SET DEFAULT writes the column default",
+        "SELECT id, account_code FROM Ticket;",
+        Ok(select!(
+            id  | account_code
+            I64 | Str;
+            1     "none".to_owned();
+            2     "b".to_owned()
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "SET DEFAULT fails when the default is not referenced anymore",
+        "UPDATE Account SET code = 'nothing' WHERE id = 0;",
+        Err(UpdateError::CannotFindReferencedValue {
+            table_name: "Account".to_owned(),
+            column_name: "code".to_owned(),
+            referenced_value: "none".to_owned(),
+        }
+        .into()),
+    )
+    .await;
+
+    g.run(
+        "CREATE TABLE Audit (
+            id INTEGER PRIMARY KEY,
+            account_code TEXT,
+            FOREIGN KEY (account_code) REFERENCES Account (code) ON UPDATE RESTRICT
+        );",
+    )
+    .await;
+    g.run("INSERT INTO Audit VALUES (1, 'b');").await;
+
+    g.named_test(
+        "RESTRICT fails and nothing is cascaded",
+        "UPDATE Account SET code = 'beta' WHERE id = 2;",
+        Err(UpdateError::ReferencingColumnExists("Audit.account_code".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "rows stay after the failed update",
+        "SELECT id, account_code FROM Payment WHERE id = 3;",
+        Ok(select!(
+            id  | account_code
+            I64 | Str;
+            3     "b".to_owned()
+        )),
+    )
+    .await;
+
+    g.run(
+        "CREATE TABLE Category (
+            id INTEGER PRIMARY KEY,
+            code TEXT UNIQUE,
+            parent_code TEXT NULL,
+            FOREIGN KEY (parent_code) REFERENCES Category (code) ON UPDATE CASCADE
+        );",
+    )
+    .await;
+    g.run("INSERT INTO Category VALUES (1, 'root', NULL);")
+        .await;
+    g.run("INSERT INTO Category VALUES (2, 'leaf', 'root');")
+        .await;
+
+    g.named_test(
+        "self-referencing CASCADE merges with the rows of the statement",
+        "UPDATE Category SET code = code || '2';",
+        Ok(Payload::Update(2)),
+    )
+    .await;
+
+    g.test(
+        "SELECT id, code, parent_code FROM Category;",
+        Ok(select_with_null!(
+            id     | code                   | parent_code;
+            I64(1)   Str("root2".to_owned())  Null;
+            I64(2)   Str("leaf2".to_owned())  Str("root2".to_owned())
+        )),
+    )
+    .await;
+});
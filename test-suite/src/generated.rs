@@ -0,0 +1,148 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::{AlterError, EvaluateError, InsertError, UpdateError},
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(generated, {
+    let g = get_tester!();
+
+    g.run(
+        "
+        CREATE TABLE Orders (
+            id INTEGER PRIMARY KEY,
+            price INTEGER,
+            qty INTEGER,
+            total INTEGER GENERATED ALWAYS AS (price * qty) STORED,
+            email TEXT,
+            email_lower TEXT GENERATED ALWAYS AS (LOWER(email)) STORED
+        )
+    ",
+    )
+    .await;
+    g.test(
+        "INSERT INTO Orders (id, price, qty, email) VALUES (1, 10, 2, 'Foo@Bar.com'), (2, 5, NULL, 'X@y.com')",
+        Ok(Payload::Insert(2)),
+    )
+    .await;
+    g.test(
+        "INSERT INTO Orders VALUES (3, 1, 1, 1, 'a@b.com', 'a@b.com')",
+        Err(InsertError::CannotInsertIntoGeneratedColumn("total".to_owned()).into()),
+    )
+    .await;
+    g.test(
+        "INSERT INTO Orders (id, email_lower) VALUES (3, 'a@b.com')",
+        Err(InsertError::CannotInsertIntoGeneratedColumn("email_lower".to_owned()).into()),
+    )
+    .await;
+    g.test(
+        "INSERT INTO Orders SELECT id + 10, price, qty, total, email, email_lower FROM Orders",
+        Err(InsertError::CannotInsertIntoGeneratedColumn("total".to_owned()).into()),
+    )
+    .await;
+    g.test(
+        "SELECT id, total, email_lower FROM Orders",
+        Ok(select_with_null!(
+            id     | total   | email_lower;
+            I64(1)   I64(20)   Str("foo@bar.com".to_owned());
+            I64(2)   Null      Str("x@y.com".to_owned())
+        )),
+    )
+    .await;
+
+    g.test(
+        "UPDATE Orders SET qty = 3 WHERE id = 2",
+        Ok(Payload::Update(1)),
+    )
+    .await;
+    g.test(
+        "UPDATE Orders SET total = 0",
+        Err(UpdateError::UpdateOnGeneratedColumn("total".to_owned()).into()),
+    )
+    .await;
+    g.test(
+        "INSERT INTO Orders (id, price, qty, email) VALUES (1, 7, 1, 'New@Bar.com')
+        ON CONFLICT (id) DO UPDATE SET price = EXCLUDED.price, email = EXCLUDED.email",
+        Ok(Payload::Insert(1)),
+    )
+    .await;
+    g.test(
+        "SELECT id, total, email_lower FROM Orders",
+        Ok(select!(
+            id  | total | email_lower
+            I64 | I64   | Str;
+            1     14      "new@bar.com".to_owned();
+            2     15      "x@y.com".to_owned()
+        )),
+    )
+    .await;
+
+    g.test(
+        "SELECT COLUMN_NAME, GENERATED FROM GLUE_TABLE_COLUMNS WHERE TABLE_NAME = 'Orders'",
+        Ok(select_with_null!(
+            COLUMN_NAME                   | GENERATED;
+            Str("id".to_owned())            Null;
+            Str("price".to_owned())         Null;
+            Str("qty".to_owned())           Null;
+            Str("total".to_owned())         Str(r#""price" * "qty""#.to_owned());
+            Str("email".to_owned())         Null;
+            Str("email_lower".to_owned())   Str(r#"LOWER("email")"#.to_owned())
+        )),
+    )
+    .await;
+
+    g.test(
+        "CREATE TABLE Copy AS SELECT * FROM Orders",
+        Ok(Payload::Create),
+    )
+    .await;
+    g.test(
+        "INSERT INTO Copy VALUES (3, 1, 1, 100, NULL, NULL)",
+        Ok(Payload::Insert(1)),
+    )
+    .await;
+
+    g.run(
+        "CREATE TABLE Total (a INTEGER, b INTEGER, c INTEGER GENERATED ALWAYS AS (a + b) STORED)",
+    )
+    .await;
+    g.named_test(
+        "INSERT ... SELECT computes generated columns like VALUES",
+        "INSERT INTO Total (a, b) SELECT 1, 2",
+        Ok(Payload::Insert(1)),
+    )
+    .await;
+    g.run("INSERT INTO Total (a, b) VALUES (3, 4)").await;
+    g.test(
+        "SELECT a, b, c FROM Total",
+        Ok(select!(
+            a   | b   | c
+            I64 | I64 | I64;
+            1     2     3;
+            3     4     7
+        )),
+    )
+    .await;
+
+    g.test(
+        "CREATE TABLE Invalid (a INTEGER, b INTEGER DEFAULT 1 GENERATED ALWAYS AS (a) STORED)",
+        Err(AlterError::GeneratedColumnWithDefault("b".to_owned()).into()),
+    )
+    .await;
+    g.test(
+        "CREATE TABLE Invalid (a INTEGER, b INTEGER GENERATED ALWAYS AS (c) STORED)",
+        Err(EvaluateError::IdentifierNotFound("c".to_owned()).into()),
+    )
+    .await;
+    g.test(
+        "CREATE TABLE Invalid (
+            a INTEGER,
+            b INTEGER GENERATED ALWAYS AS (a + 1) STORED,
+            c INTEGER GENERATED ALWAYS AS (b + 1) STORED
+        )",
+        Err(EvaluateError::IdentifierNotFound("b".to_owned()).into()),
+    )
+    .await;
+});
@@ -1,19 +1,27 @@
 mod and;
 mod basic;
+mod composite;
 mod expr;
+mod generated;
 mod nested;
 mod null;
 mod order_by;
 mod showindexes;
+mod statistics;
+mod unique;
 mod value;
 
 pub use {
     and::and,
     basic::basic,
+    composite::composite,
     expr::expr,
+    generated::generated,
     nested::nested,
     null::null,
-    order_by::{order_by, order_by_multi},
+    order_by::{order_by, order_by_limit, order_by_multi},
     showindexes::showindexes,
+    statistics::statistics,
+    unique::unique,
     value::value,
 };
@@ -0,0 +1,160 @@
+use {
+    crate::*,
+    gluesql_core::{
+        ast::IndexOperator::*,
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(composite, {
+    let g = get_tester!();
+
+    g.run(
+        "
+CREATE TABLE Event (
+    tenant_id INTEGER,
+    created INTEGER,
+    name TEXT
+)",
+    )
+    .await;
+
+    g.run(
+        "
+        INSERT INTO Event
+            (tenant_id, created, name)
+        VALUES
+            (2, 25, 'e'),
+            (1, 20, 'b'),
+            (3, 5, 'g'),
+            (3, NULL, 'f'),
+            (1, 10, 'a'),
+            (2, 10, 'd'),
+            (1, 30, 'c');
+    ",
+    )
+    .await;
+
+    g.test(
+        "CREATE INDEX idx_tenant_created ON Event (tenant_id, created)",
+        Ok(Payload::CreateIndex),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT name FROM Event WHERE tenant_id = 1 AND created > 10",
+        Ok(select!(name Str; "b".to_owned(); "c".to_owned())),
+        idx!(idx_tenant_created, ["1"], Gt, "10"),
+    )
+    .await;
+
+    match g
+        .run("EXPLAIN SELECT name FROM Event WHERE tenant_id = 1 AND created > 10")
+        .await
+    {
+        Payload::Explain(plan) => assert_eq!(
+            plan.to_string(),
+            "Project: name\n  Index Scan: Event, index: idx_tenant_created, prefix: 1, key > 10"
+        ),
+        payload => panic!("unexpected payload: {payload:?}"),
+    }
+
+    g.test_idx(
+        "SELECT name FROM Event WHERE created <= 20 AND 2 = tenant_id",
+        Ok(select!(name Str; "d".to_owned())),
+        idx!(idx_tenant_created, ["2"], LtEq, "20"),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT name FROM Event WHERE tenant_id = 1 AND created = 20",
+        Ok(select!(name Str; "b".to_owned())),
+        idx!(idx_tenant_created, ["1"], Eq, "20"),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT name FROM Event WHERE tenant_id = 3 AND created < 100",
+        Ok(select!(name Str; "g".to_owned())),
+        idx!(idx_tenant_created, ["3"], Lt, "100"),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT name FROM Event WHERE tenant_id = 2 AND name <> 'd'",
+        Ok(select!(name Str; "e".to_owned())),
+        idx!(idx_tenant_created, Eq, "2"),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT name FROM Event WHERE created > 25",
+        Ok(select!(name Str; "c".to_owned())),
+        idx!(),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT name FROM Event ORDER BY tenant_id",
+        Ok(select!(
+            name
+            Str;
+            "a".to_owned();
+            "b".to_owned();
+            "c".to_owned();
+            "d".to_owned();
+            "e".to_owned();
+            "g".to_owned();
+            "f".to_owned()
+        )),
+        idx!(idx_tenant_created),
+    )
+    .await;
+
+    g.test(
+        "CREATE INDEX idx_tenant_decade ON Event (tenant_id, created / 10)",
+        Ok(Payload::CreateIndex),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT name FROM Event WHERE created / 10 = 1 AND tenant_id = 1",
+        Ok(select!(name Str; "a".to_owned())),
+        idx!(idx_tenant_decade, ["1"], Eq, "1"),
+    )
+    .await;
+
+    g.test(
+        "UPDATE Event SET created = 15 WHERE name = 'c'",
+        Ok(Payload::Update(1)),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT name FROM Event WHERE tenant_id = 1 AND created >= 15",
+        Ok(select!(name Str; "c".to_owned(); "b".to_owned())),
+        idx!(idx_tenant_created, ["1"], GtEq, "15"),
+    )
+    .await;
+
+    g.test("DELETE FROM Event WHERE name = 'b'", Ok(Payload::Delete(1)))
+        .await;
+
+    g.test_idx(
+        "SELECT name FROM Event WHERE tenant_id = 1 AND created >= 15",
+        Ok(select!(name Str; "c".to_owned())),
+        idx!(idx_tenant_created, ["1"], GtEq, "15"),
+    )
+    .await;
+
+    g.test(
+        "SHOW INDEXES FROM Event",
+        Ok(select!(
+            TABLE_NAME         | INDEX_NAME                      | ORDER             | EXPRESSION                              | UNIQUENESS;
+            Str                | Str                             | Str               | Str                                     | Bool;
+            "Event".to_owned()   "idx_tenant_created".to_owned()   "BOTH".to_owned()   "tenant_id, created".to_owned()           false;
+            "Event".to_owned()   "idx_tenant_decade".to_owned()    "BOTH".to_owned()   "tenant_id, created / 10".to_owned()      false
+        )),
+    )
+    .await;
+});
@@ -0,0 +1,72 @@
+use {
+    crate::*,
+    gluesql_core::{
+        ast::IndexOperator::*,
+        error::IndexError,
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(generated, {
+    let g = get_tester!();
+
+    g.run(
+        "
+        CREATE TABLE Account (
+            id INTEGER,
+            email TEXT,
+            email_lower TEXT GENERATED ALWAYS AS (LOWER(email)) STORED
+        )
+    ",
+    )
+    .await;
+    g.run("INSERT INTO Account (id, email) VALUES (1, 'Foo@Glue.sql'), (2, 'bar@glue.sql')")
+        .await;
+
+    g.test(
+        "CREATE UNIQUE INDEX idx_email_lower ON Account (email_lower)",
+        Ok(Payload::CreateIndex),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT id, email FROM Account WHERE email_lower = 'foo@glue.sql'",
+        Ok(select!(
+            id  | email
+            I64 | Str;
+            1     "Foo@Glue.sql".to_owned()
+        )),
+        idx!(idx_email_lower, Eq, "'foo@glue.sql'"),
+    )
+    .await;
+
+    g.named_test(
+        "generated value violating a unique index fails",
+        "INSERT INTO Account (id, email) VALUES (3, 'FOO@glue.sql')",
+        Err(IndexError::DuplicateEntryOnUniqueIndex("idx_email_lower".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "index follows the recomputed value",
+        "UPDATE Account SET email = 'Baz@Glue.sql' WHERE id = 1",
+        Ok(Payload::Update(1)),
+    )
+    .await;
+    g.test_idx(
+        "SELECT id, email FROM Account WHERE email_lower = 'baz@glue.sql'",
+        Ok(select!(
+            id  | email
+            I64 | Str;
+            1     "Baz@Glue.sql".to_owned()
+        )),
+        idx!(idx_email_lower, Eq, "'baz@glue.sql'"),
+    )
+    .await;
+    g.test_idx(
+        "SELECT id FROM Account WHERE email_lower = 'foo@glue.sql'",
+        Ok(select!(id)),
+        idx!(idx_email_lower, Eq, "'foo@glue.sql'"),
+    )
+    .await;
+});
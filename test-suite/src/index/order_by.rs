@@ -199,3 +199,125 @@ CREATE TABLE Multi (
     )
     .await;
 });
+
+test_case!(order_by_limit, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Event (tenant_id INTEGER, created INTEGER NULL, name TEXT)")
+        .await;
+    g.run(
+        "
+        INSERT INTO Event VALUES
+            (2, 25, 'e'),
+            (1, 20, 'b'),
+            (3, 5, 'g'),
+            (3, NULL, 'f'),
+            (1, 10, 'a'),
+            (2, 10, 'd'),
+            (1, 30, 'c');
+    ",
+    )
+    .await;
+    g.run("CREATE INDEX idx_tenant_created ON Event (tenant_id, created)")
+        .await;
+
+    macro_rules! explain {
+        ($sql: expr) => {
+            match g.run(&format!("EXPLAIN {}", $sql)).await {
+                Payload::Explain(plan) => plan.to_string(),
+                payload => panic!("unexpected payload: {payload:?}"),
+            }
+        };
+    }
+
+    let sql = "SELECT name FROM Event WHERE tenant_id = 1 ORDER BY created DESC LIMIT 2";
+    g.named_test(
+        "index scan from WHERE gives the order of the next index expression",
+        sql,
+        Ok(select!(name Str; "c".to_owned(); "b".to_owned())),
+    )
+    .await;
+    assert_eq!(
+        explain!(sql),
+        "
+Limit: 2
+  Project: name
+    Index Scan: Event, index: idx_tenant_created, key = 1, order: DESC"
+            .trim_start()
+    );
+
+    let sql = "SELECT name FROM Event WHERE tenant_id = 3 ORDER BY created";
+    g.test(sql, Ok(select!(name Str; "g".to_owned(); "f".to_owned())))
+        .await;
+    assert_eq!(
+        explain!(sql),
+        "
+Project: name
+  Index Scan: Event, index: idx_tenant_created, key = 3, order: ASC"
+            .trim_start()
+    );
+
+    let sql = "SELECT tenant_id, created FROM Event ORDER BY tenant_id, created LIMIT 3";
+    g.named_test(
+        "index scan gives the order of all the ORDER BY expressions",
+        sql,
+        Ok(select!(
+            tenant_id | created
+            I64       | I64;
+            1           10;
+            1           20;
+            1           30
+        )),
+    )
+    .await;
+    assert_eq!(
+        explain!(sql),
+        "
+Limit: 3
+  Project: tenant_id, created
+    Index Scan: Event, index: idx_tenant_created"
+            .trim_start()
+    );
+
+    let sql = "SELECT name FROM Event ORDER BY name, tenant_id DESC, created DESC";
+    g.test(
+        sql,
+        Ok(select!(
+            name
+            Str;
+            "a".to_owned();
+            "b".to_owned();
+            "c".to_owned();
+            "d".to_owned();
+            "e".to_owned();
+            "f".to_owned();
+            "g".to_owned()
+        )),
+    )
+    .await;
+    assert_eq!(
+        explain!(sql),
+        "
+Sort: name
+  Project: name
+    Index Scan: Event, index: idx_tenant_created, order: DESC"
+            .trim_start()
+    );
+
+    match g
+        .run("EXPLAIN ANALYZE SELECT name FROM Event ORDER BY tenant_id, created LIMIT 2 OFFSET 1")
+        .await
+    {
+        Payload::Explain(plan) => {
+            let scan = &plan.children[0].children[0];
+
+            assert_eq!(scan.operator, "Index Scan");
+            assert_eq!(
+                scan.actual.map(|actual| actual.rows),
+                Some(3),
+                "the scan stops once LIMIT and OFFSET rows are read"
+            );
+        }
+        payload => panic!("unexpected payload: {payload:?}"),
+    }
+});
@@ -0,0 +1,96 @@
+use {
+    crate::*,
+    gluesql_core::{
+        ast::IndexOperator::*,
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(statistics, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Account (id INTEGER PRIMARY KEY, num INTEGER)")
+        .await;
+    g.run(
+        "
+        INSERT INTO Account VALUES
+            (1, 1), (2, 2), (3, 3), (4, 4), (5, 5),
+            (6, 6), (7, 7), (8, 8), (9, 9), (10, 10);
+    ",
+    )
+    .await;
+    g.test(
+        "CREATE INDEX idx_num ON Account (num)",
+        Ok(Payload::CreateIndex),
+    )
+    .await;
+
+    g.named_test(
+        "without statistics every matching index is used",
+        "SELECT id FROM Account WHERE num > 7",
+        Ok(select!(id I64; 8; 9; 10)),
+    )
+    .await;
+    g.test_idx(
+        "SELECT id FROM Account WHERE num > 2 AND num < 5",
+        Ok(select!(id I64; 3; 4)),
+        idx!(idx_num, Gt, "2"),
+    )
+    .await;
+
+    g.test("ANALYZE Account", Ok(Payload::Analyze(1))).await;
+
+    g.test_idx(
+        "SELECT id FROM Account WHERE num > 2 AND num < 5",
+        Ok(select!(id I64; 3; 4)),
+        idx!(),
+    )
+    .await;
+    g.test_idx(
+        "SELECT id FROM Account WHERE num >= 9",
+        Ok(select!(id I64; 9; 10)),
+        idx!(idx_num, GtEq, "9"),
+    )
+    .await;
+    g.test_idx(
+        "SELECT id FROM Account WHERE num = 3",
+        Ok(select!(id I64; 3)),
+        idx!(idx_num, Eq, "3"),
+    )
+    .await;
+    g.test_idx(
+        "SELECT COUNT(*) FROM Account WHERE num IS NOT NULL",
+        Ok(select!("COUNT(*)" I64; 10)),
+        idx!(),
+    )
+    .await;
+
+    g.named_test(
+        "index scans ordering the rows are kept",
+        "SELECT id FROM Account WHERE num > 2 ORDER BY num DESC LIMIT 2",
+        Ok(select!(id I64; 10; 9)),
+    )
+    .await;
+
+    g.run("INSERT INTO Account SELECT id + 10, 0 FROM Account")
+        .await;
+    g.named_test(
+        "statistics are used until ANALYZE is run again",
+        "SELECT COUNT(*) FROM Account WHERE num = 0",
+        Ok(select!("COUNT(*)" I64; 10)),
+    )
+    .await;
+    g.test_idx(
+        "SELECT COUNT(*) FROM Account WHERE num = 0",
+        Ok(select!("COUNT(*)" I64; 10)),
+        idx!(idx_num, Eq, "0"),
+    )
+    .await;
+    g.test("ANALYZE", Ok(Payload::Analyze(1))).await;
+    g.test_idx(
+        "SELECT COUNT(*) FROM Account WHERE num = 0",
+        Ok(select!("COUNT(*)" I64; 10)),
+        idx!(),
+    )
+    .await;
+});
@@ -0,0 +1,165 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::{IndexError, ValidateError},
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(unique, {
+    let g = get_tester!();
+
+    g.run(
+        "
+CREATE TABLE Account (
+    id INTEGER PRIMARY KEY,
+    email TEXT UNIQUE,
+    tenant_id INTEGER,
+    num INTEGER
+)",
+    )
+    .await;
+
+    g.run(
+        "
+        INSERT INTO Account
+            (id, email, tenant_id, num)
+        VALUES
+            (1, 'a@glue.sql', 1, 10),
+            (2, 'b@glue.sql', 1, 20),
+            (3, 'c@glue.sql', 2, 10);
+    ",
+    )
+    .await;
+
+    g.test(
+        "CREATE UNIQUE INDEX idx_email ON Account (email)",
+        Ok(Payload::CreateIndex),
+    )
+    .await;
+    g.test(
+        "CREATE UNIQUE INDEX idx_tenant_num ON Account (tenant_id, num)",
+        Ok(Payload::CreateIndex),
+    )
+    .await;
+
+    g.named_test(
+        "unique column is validated by the unique index on it",
+        "INSERT INTO Account VALUES (4, 'b@glue.sql', 3, 10)",
+        Err(ValidateError::DuplicateEntryOnUniqueField(
+            Str("b@glue.sql".to_owned()),
+            "email".to_owned(),
+        )
+        .into()),
+    )
+    .await;
+
+    g.named_test(
+        "duplicate within the inserted rows fails",
+        "INSERT INTO Account VALUES (4, 'd@glue.sql', 3, 10), (5, 'd@glue.sql', 3, 20)",
+        Err(ValidateError::DuplicateEntryOnUniqueField(
+            Str("d@glue.sql".to_owned()),
+            "email".to_owned(),
+        )
+        .into()),
+    )
+    .await;
+
+    g.named_test(
+        "insert violating a unique index fails",
+        "INSERT INTO Account VALUES (4, 'd@glue.sql', 1, 20)",
+        Err(IndexError::DuplicateEntryOnUniqueIndex("idx_tenant_num".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "NULL in a unique index is never a duplicate",
+        "INSERT INTO Account VALUES (4, 'd@glue.sql', 1, NULL), (5, 'e@glue.sql', 1, NULL)",
+        Ok(Payload::Insert(2)),
+    )
+    .await;
+
+    g.named_test(
+        "update violating a unique index fails",
+        "UPDATE Account SET num = 20 WHERE id = 1",
+        Err(IndexError::DuplicateEntryOnUniqueIndex("idx_tenant_num".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "update keeping the indexed values of the row passes",
+        "UPDATE Account SET num = num WHERE id = 1",
+        Ok(Payload::Update(1)),
+    )
+    .await;
+
+    g.named_test(
+        "deleted values can be inserted again",
+        "DELETE FROM Account WHERE id = 2",
+        Ok(Payload::Delete(1)),
+    )
+    .await;
+    g.test(
+        "INSERT INTO Account VALUES (2, 'b@glue.sql', 1, 20)",
+        Ok(Payload::Insert(1)),
+    )
+    .await;
+
+    g.named_test(
+        "rejected rows leave the table unchanged",
+        "SELECT id, email, tenant_id, num FROM Account",
+        Ok(select_with_null!(
+            id     | email                      | tenant_id | num;
+            I64(1)   Str("a@glue.sql".to_owned())   I64(1)      I64(10);
+            I64(2)   Str("b@glue.sql".to_owned())   I64(1)      I64(20);
+            I64(3)   Str("c@glue.sql".to_owned())   I64(2)      I64(10);
+            I64(4)   Str("d@glue.sql".to_owned())   I64(1)      Null;
+            I64(5)   Str("e@glue.sql".to_owned())   I64(1)      Null
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "ON CONFLICT finds the conflicting row through the unique index",
+        "
+        INSERT INTO Account VALUES (6, 'c@glue.sql', 2, 30), (7, 'f@glue.sql', 3, 10)
+        ON CONFLICT (email) DO UPDATE SET num = EXCLUDED.num
+        ",
+        Ok(Payload::Insert(2)),
+    )
+    .await;
+    g.test(
+        "INSERT INTO Account VALUES (8, 'a@glue.sql', 4, 10) ON CONFLICT (email) DO NOTHING",
+        Ok(Payload::Insert(0)),
+    )
+    .await;
+    g.test(
+        "SELECT id, num FROM Account WHERE id IN (3, 7, 8)",
+        Ok(select!(
+            id  | num
+            I64 | I64;
+            3     30;
+            7     10
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "unique index on duplicate values cannot be created",
+        "CREATE UNIQUE INDEX idx_tenant ON Account (tenant_id)",
+        Err(IndexError::DuplicateEntryOnUniqueIndex("idx_tenant".to_owned()).into()),
+    )
+    .await;
+
+    g.test(
+        "SHOW INDEXES FROM Account",
+        Ok(select!(
+            TABLE_NAME           | INDEX_NAME                  | ORDER             | EXPRESSION                  | UNIQUENESS;
+            Str                  | Str                         | Str               | Str                         | Bool;
+            "Account".to_owned()   "PRIMARY".to_owned()          "BOTH".to_owned()   "id".to_owned()               true;
+            "Account".to_owned()   "idx_email".to_owned()        "BOTH".to_owned()   "email".to_owned()            true;
+            "Account".to_owned()   "idx_tenant_num".to_owned()   "BOTH".to_owned()   "tenant_id, num".to_owned()   true
+        )),
+    )
+    .await;
+});
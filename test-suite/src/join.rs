@@ -2,7 +2,7 @@ use {
     crate::*,
     Value::*,
     gluesql_core::{
-        error::{PlanError, TranslateError},
+        error::{JoinError, PlanError, TranslateError},
         prelude::*,
     },
 };
@@ -329,12 +329,8 @@ test_case!(project, {
 
     let error_cases = [
         (
-            "SELECT * FROM TableA JOIN TableA USING (id);",
-            TranslateError::UnsupportedJoinConstraint("USING".to_owned()).into(),
-        ),
-        (
-            "SELECT * FROM TableA CROSS JOIN TableA as A;",
-            TranslateError::UnsupportedJoinOperator("CrossJoin".to_owned()).into(),
+            "SELECT * FROM TableA CROSS APPLY TableA as A;",
+            TranslateError::UnsupportedJoinOperator("CrossApply".to_owned()).into(),
         ),
         (
             "SELECT id FROM Users JOIN Testers ON Users.id = Testers.id;",
@@ -363,3 +359,222 @@ test_case!(project, {
         g.test(sql, Err(error)).await;
     }
 });
+
+test_case!(outer, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Customer (id INTEGER, name TEXT);")
+        .await;
+    g.run("CREATE TABLE Orders (id INTEGER, customer_id INTEGER NULL, amount INTEGER);")
+        .await;
+    g.run("INSERT INTO Customer VALUES (1, 'Alice'), (2, 'Bob'), (3, 'Carol');")
+        .await;
+    g.run(
+        "
+        INSERT INTO Orders VALUES
+            (10, 1, 100),
+            (11, 1, 50),
+            (12, 2, 70),
+            (13, 4, 30),
+            (14, NULL, 20);
+        ",
+    )
+    .await;
+
+    g.named_test(
+        "RIGHT JOIN keeps unmatched rows of the right side",
+        "
+        SELECT c.name, o.id
+        FROM Customer c
+        RIGHT JOIN Orders o ON c.id = o.customer_id
+        ORDER BY o.id
+        ",
+        Ok(select_with_null!(
+            name                    | id;
+            Str("Alice".to_owned())   I64(10);
+            Str("Alice".to_owned())   I64(11);
+            Str("Bob".to_owned())     I64(12);
+            Null                      I64(13);
+            Null                      I64(14)
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "FULL JOIN keeps unmatched rows of both sides",
+        "
+        SELECT c.name, o.id
+        FROM Customer c
+        FULL OUTER JOIN Orders o ON c.id = o.customer_id AND o.amount > 60
+        ORDER BY o.id, c.name
+        ",
+        Ok(select_with_null!(
+            name                    | id;
+            Str("Alice".to_owned())   I64(10);
+            Null                      I64(11);
+            Str("Bob".to_owned())     I64(12);
+            Null                      I64(13);
+            Null                      I64(14);
+            Str("Carol".to_owned())   Null
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "FULL JOIN with nested loop",
+        "
+        SELECT c.name, o.id
+        FROM Customer c
+        FULL JOIN Orders o ON c.id >= o.customer_id AND o.amount >= 70
+        ORDER BY o.id, c.name
+        ",
+        Ok(select_with_null!(
+            name                    | id;
+            Str("Alice".to_owned())   I64(10);
+            Str("Bob".to_owned())     I64(10);
+            Str("Carol".to_owned())   I64(10);
+            Null                      I64(11);
+            Str("Bob".to_owned())     I64(12);
+            Str("Carol".to_owned())   I64(12);
+            Null                      I64(13);
+            Null                      I64(14)
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "RIGHT JOIN after other joins fills every left relation with NULL",
+        "
+        SELECT *
+        FROM Customer c
+        JOIN Orders o ON c.id = o.customer_id
+        RIGHT JOIN Customer d ON d.id = c.id + 2
+        ORDER BY d.id, o.id
+        ",
+        Ok(select_with_null!(
+            id     | name                    | id      | customer_id | amount   | id     | name;
+            Null     Null                      Null      Null          Null       I64(1)   Str("Alice".to_owned());
+            Null     Null                      Null      Null          Null       I64(2)   Str("Bob".to_owned());
+            I64(1)   Str("Alice".to_owned())   I64(10)   I64(1)        I64(100)   I64(3)   Str("Carol".to_owned());
+            I64(1)   Str("Alice".to_owned())   I64(11)   I64(1)        I64(50)    I64(3)   Str("Carol".to_owned())
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "CROSS JOIN",
+        "SELECT c.id, o.id FROM Customer c CROSS JOIN Orders o WHERE o.amount > 60 ORDER BY c.id, o.id",
+        Ok(select!(
+            id  | id
+            I64 | I64;
+            1     10;
+            1     12;
+            2     10;
+            2     12;
+            3     10;
+            3     12
+        )),
+    )
+    .await;
+});
+
+test_case!(using, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Left (id INTEGER, x TEXT);").await;
+    g.run("CREATE TABLE Right (id INTEGER, y TEXT);").await;
+    g.run("CREATE TABLE Other (id INTEGER, x TEXT);").await;
+    g.run("CREATE TABLE Schemaless;").await;
+    g.run("INSERT INTO Left VALUES (1, 'a1'), (2, 'a2'), (3, 'a3');")
+        .await;
+    g.run("INSERT INTO Right VALUES (2, 'b2'), (3, 'b3'), (4, 'b4');")
+        .await;
+    g.run("INSERT INTO Other VALUES (1, 'a1'), (2, 'zz');")
+        .await;
+
+    g.named_test(
+        "USING merges the join column",
+        "SELECT * FROM Left JOIN Right USING (id) ORDER BY id",
+        Ok(select!(
+            id  | x                | y
+            I64 | Str              | Str;
+            2     "a2".to_owned()    "b2".to_owned();
+            3     "a3".to_owned()    "b3".to_owned()
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "FULL JOIN USING coalesces the join column",
+        "SELECT * FROM Left FULL JOIN Right USING (id) ORDER BY id",
+        Ok(select_with_null!(
+            id     | x                    | y;
+            I64(1)   Str("a1".to_owned())   Null;
+            I64(2)   Str("a2".to_owned())   Str("b2".to_owned());
+            I64(3)   Str("a3".to_owned())   Str("b3".to_owned());
+            I64(4)   Null                   Str("b4".to_owned())
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "qualified columns keep the value of each side",
+        "SELECT id, Left.id, Right.id FROM Left RIGHT JOIN Right USING (id) ORDER BY id",
+        Ok(select_with_null!(
+            id     | id     | id;
+            I64(2)   I64(2)   I64(2);
+            I64(3)   I64(3)   I64(3);
+            I64(4)   Null     I64(4)
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "qualified wildcard is not merged",
+        "SELECT Right.* FROM Left JOIN Right USING (id) ORDER BY id",
+        Ok(select!(
+            id  | y
+            I64 | Str;
+            2     "b2".to_owned();
+            3     "b3".to_owned()
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "NATURAL JOIN merges every common column",
+        "SELECT * FROM Left NATURAL JOIN Other",
+        Ok(select!(
+            id  | x
+            I64 | Str;
+            1     "a1".to_owned()
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "NATURAL LEFT JOIN",
+        "SELECT * FROM Left NATURAL LEFT JOIN Right ORDER BY id",
+        Ok(select_with_null!(
+            id     | x                    | y;
+            I64(1)   Str("a1".to_owned())   Null;
+            I64(2)   Str("a2".to_owned())   Str("b2".to_owned());
+            I64(3)   Str("a3".to_owned())   Str("b3".to_owned())
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "USING column must exist on both sides",
+        "SELECT * FROM Left JOIN Right USING (x)",
+        Err(JoinError::UsingColumnNotFound("x".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "NATURAL JOIN requires schema",
+        "SELECT * FROM Left NATURAL JOIN Schemaless",
+        Err(JoinError::NaturalJoinRequiresSchema.into()),
+    )
+    .await;
+});
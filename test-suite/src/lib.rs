@@ -11,7 +11,9 @@ pub mod bitwise_shift_left;
 pub mod bitwise_shift_right;
 pub mod case;
 pub mod column_alias;
+pub mod composite_key;
 pub mod concat;
+pub mod cte;
 pub mod custom_function;
 pub mod data_type;
 pub mod default;
@@ -19,10 +21,13 @@ pub mod delete;
 pub mod dictionary;
 pub mod dictionary_index;
 pub mod distinct;
+pub mod execute_options;
+pub mod explain;
 pub mod expr;
 pub mod filter;
 pub mod foreign_key;
 pub mod function;
+pub mod generated;
 pub mod index;
 pub mod inline_view;
 pub mod insert;
@@ -33,21 +38,31 @@ pub mod metadata;
 pub mod migrate;
 pub mod nested_select;
 pub mod nullable;
+pub mod on_conflict;
 pub mod order_by;
 pub mod ordering;
+pub mod prepared_statement;
 pub mod primary_key;
 pub mod project;
+pub mod returning;
 pub mod schemaless;
+pub mod sequence;
 pub mod series;
+pub mod set_operation;
 pub mod show_columns;
+pub mod statistics;
 pub mod store;
+pub mod stream;
 pub mod synthesize;
 pub mod transaction;
+pub mod trigger;
 pub mod type_match;
 pub mod unary_operator;
 pub mod update;
 pub mod validate;
 pub mod values;
+pub mod view;
+pub mod window;
 
 pub mod tester;
 
@@ -176,10 +191,19 @@ macro_rules! generate_store_tests {
         glue!(function_hex, function::hex::hex);
         glue!(join, join::join);
         glue!(join_project, join::project);
+        glue!(join_outer, join::outer);
+        glue!(join_using, join::using);
         glue!(migrate, migrate::migrate);
         glue!(nested_select, nested_select::nested_select);
         glue!(primary_key, primary_key::primary_key);
+        glue!(composite_key, composite_key::composite_key);
+        glue!(explain, explain::explain);
         glue!(foreign_key, foreign_key::foreign_key);
+        glue!(
+            foreign_key_referential_action,
+            foreign_key::referential_action
+        );
+        glue!(foreign_key_on_update, foreign_key::on_update);
         glue!(series, series::series);
         glue!(nullable, nullable::nullable);
         glue!(nullable_text, nullable::nullable_text);
@@ -189,6 +213,15 @@ macro_rules! generate_store_tests {
         glue!(sql_types, data_type::sql_types::sql_types);
         glue!(show_columns, show_columns::show_columns);
         glue!(distinct, distinct::distinct);
+        glue!(set_operation, set_operation::set_operation);
+        glue!(cte, cte::cte);
+        glue!(window, window::window);
+        glue!(on_conflict, on_conflict::on_conflict);
+        glue!(generated, generated::generated);
+        glue!(prepared_statement, prepared_statement::prepared_statement);
+        glue!(stream, stream::stream);
+        glue!(execute_options, execute_options::execute_options);
+        glue!(returning, returning::returning);
         glue!(int8, data_type::int8::int8);
         glue!(int16, data_type::int16::int16);
         glue!(int32, data_type::int32::int32);
@@ -211,6 +244,7 @@ macro_rules! generate_store_tests {
         glue!(point, data_type::point::point);
         glue!(null, data_type::null::null);
         glue!(synthesize, synthesize::synthesize);
+        glue!(validate_check, validate::check::check);
         glue!(validate_unique, validate::unique::unique);
         glue!(validate_types, validate::types::types);
         glue!(function_extract, function::extract::extract);
@@ -354,6 +388,8 @@ macro_rules! generate_alter_table_tests {
 
         glue!(alter_table_rename, alter::alter_table_rename);
         glue!(alter_table_add_drop, alter::alter_table_add_drop);
+        glue!(alter_table_check, alter::alter_table_check);
+        glue!(alter_table_generated, alter::generated_alter_table);
     };
 }
 
@@ -370,6 +406,76 @@ macro_rules! generate_custom_function_tests {
     };
 }
 
+#[macro_export]
+macro_rules! generate_view_tests {
+    ($test: meta, $storage: ident) => {
+        macro_rules! glue {
+            ($title: ident, $func: path) => {
+                declare_test_fn!($test, $storage, $title, $func);
+            };
+        }
+
+        glue!(view, view::view);
+        glue!(view_materialized, view::materialized);
+    };
+}
+
+#[macro_export]
+macro_rules! generate_statistics_tests {
+    ($test: meta, $storage: ident) => {
+        macro_rules! glue {
+            ($title: ident, $func: path) => {
+                declare_test_fn!($test, $storage, $title, $func);
+            };
+        }
+
+        glue!(statistics, statistics::statistics);
+    };
+}
+
+#[macro_export]
+macro_rules! generate_sequence_tests {
+    ($test: meta, $storage: ident) => {
+        macro_rules! glue {
+            ($title: ident, $func: path) => {
+                declare_test_fn!($test, $storage, $title, $func);
+            };
+        }
+
+        glue!(sequence, sequence::sequence);
+        glue!(sequence_identity, sequence::identity);
+    };
+}
+
+#[macro_export]
+macro_rules! generate_trigger_tests {
+    ($test: meta, $storage: ident) => {
+        macro_rules! glue {
+            ($title: ident, $func: path) => {
+                declare_test_fn!($test, $storage, $title, $func);
+            };
+        }
+
+        glue!(trigger, trigger::trigger);
+        glue!(trigger_recursion, trigger::recursion);
+        glue!(trigger_cascade, trigger::cascade);
+        glue!(trigger_unsupported, trigger::unsupported);
+    };
+}
+
+#[macro_export]
+macro_rules! generate_alter_table_sequence_tests {
+    ($test: meta, $storage: ident) => {
+        macro_rules! glue {
+            ($title: ident, $func: path) => {
+                declare_test_fn!($test, $storage, $title, $func);
+            };
+        }
+
+        glue!(alter_table_identity, alter::identity_alter_table);
+    };
+}
+
 #[macro_export]
 macro_rules! generate_index_tests {
     ($test: meta, $storage: ident) => {
@@ -381,12 +487,17 @@ macro_rules! generate_index_tests {
 
         glue!(index_basic, index::basic);
         glue!(index_and, index::and);
+        glue!(index_composite, index::composite);
+        glue!(index_unique, index::unique);
         glue!(index_nested, index::nested);
         glue!(index_null, index::null);
         glue!(index_expr, index::expr);
+        glue!(index_generated, index::generated);
         glue!(index_value, index::value);
         glue!(index_order_by, index::order_by);
         glue!(index_order_by_multi, index::order_by_multi);
+        glue!(index_order_by_limit, index::order_by_limit);
+        glue!(index_statistics, index::statistics);
         glue!(showindexes, index::showindexes);
         glue!(dictionary_index, dictionary_index::ditionary_index);
     };
@@ -408,6 +519,9 @@ macro_rules! generate_transaction_tests {
         );
         glue!(transaction_dictionary, transaction::dictionary);
         glue!(transaction_ast_builder, transaction::ast_builder);
+        glue!(transaction_sequence, transaction::sequence);
+        glue!(transaction_stream, transaction::stream);
+        glue!(transaction_savepoint, transaction::savepoint);
     };
 }
 
@@ -453,6 +567,19 @@ macro_rules! generate_transaction_alter_table_tests {
     };
 }
 
+#[macro_export]
+macro_rules! generate_transaction_trigger_tests {
+    ($test: meta, $storage: ident) => {
+        macro_rules! glue {
+            ($title: ident, $func: path) => {
+                declare_test_fn!($test, $storage, $title, $func);
+            };
+        }
+
+        glue!(transaction_trigger, transaction::trigger);
+    };
+}
+
 #[macro_export]
 macro_rules! generate_transaction_index_tests {
     ($test: meta, $storage: ident) => {
@@ -47,20 +47,13 @@ test_case!(migrate, {
             "SELECT * FROM Test WHERE Here.User.id = 1",
             TranslateError::UnsupportedExpr("Here.User.id".to_owned()).into(),
         ),
-        (
-            "SELECT * FROM Test NATURAL JOIN Test",
-            TranslateError::UnsupportedJoinConstraint("NATURAL".to_owned()).into(),
-        ),
         (
             "SELECT 1 ^ 2 FROM Test;",
             TranslateError::UnsupportedBinaryOperator("^".to_owned()).into(),
         ),
         (
-            "SELECT * FROM Test UNION SELECT * FROM Test;",
-            TranslateError::UnsupportedQuerySetExpr(
-                "SELECT * FROM Test UNION SELECT * FROM Test".to_owned(),
-            )
-            .into(),
+            "SELECT * FROM Test UNION BY NAME SELECT * FROM Test;",
+            TranslateError::UnsupportedSetQuantifier("BY NAME".to_owned()).into(),
         ),
         (
             "SELECT * FROM Test WHERE noname = 1;",
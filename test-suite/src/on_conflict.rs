@@ -0,0 +1,159 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::{InsertError, UpdateError, ValidateError},
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(on_conflict, {
+    let g = get_tester!();
+
+    g.run(
+        "
+        CREATE TABLE Account (
+            id INTEGER PRIMARY KEY,
+            email TEXT UNIQUE,
+            name TEXT,
+            visits INTEGER
+        );
+        ",
+    )
+    .await;
+    g.run("INSERT INTO Account VALUES (1, 'a@x', 'Alice', 1), (2, 'b@x', 'Bob', 1);")
+        .await;
+
+    g.named_test(
+        "DO NOTHING skips rows conflicting on the target",
+        "
+        INSERT INTO Account VALUES (1, 'z@x', 'Zed', 1), (3, 'c@x', 'Carol', 1)
+        ON CONFLICT (id) DO NOTHING
+        ",
+        Ok(Payload::Insert(1)),
+    )
+    .await;
+
+    g.named_test(
+        "DO NOTHING without target skips conflicts on any unique constraint",
+        "INSERT INTO Account VALUES (4, 'a@x', 'Dup', 1) ON CONFLICT DO NOTHING",
+        Ok(Payload::Insert(0)),
+    )
+    .await;
+
+    g.named_test(
+        "DO UPDATE sets columns from EXCLUDED",
+        "
+        INSERT INTO Account VALUES (1, 'a@x', 'Alicia', 2), (5, 'e@x', 'Eve', 1)
+        ON CONFLICT (id) DO UPDATE
+        SET name = EXCLUDED.name, visits = Account.visits + excluded.visits
+        ",
+        Ok(Payload::Insert(2)),
+    )
+    .await;
+
+    g.named_test(
+        "DO UPDATE skips rows filtered out by WHERE",
+        "
+        INSERT INTO Account VALUES (6, 'b@x', 'Bobby', 1)
+        ON CONFLICT (email) DO UPDATE SET name = EXCLUDED.name
+        WHERE visits > 1
+        ",
+        Ok(Payload::Insert(0)),
+    )
+    .await;
+
+    g.named_test(
+        "upserted rows",
+        "SELECT * FROM Account ORDER BY id",
+        Ok(select!(
+            id  | email            | name                 | visits
+            I64 | Str              | Str                  | I64;
+            1     "a@x".to_owned()   "Alicia".to_owned()    3;
+            2     "b@x".to_owned()   "Bob".to_owned()       1;
+            3     "c@x".to_owned()   "Carol".to_owned()     1;
+            5     "e@x".to_owned()   "Eve".to_owned()       1
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "conflict on other unique constraint fails",
+        "INSERT INTO Account VALUES (7, 'b@x', 'Other', 1) ON CONFLICT (id) DO NOTHING",
+        Err(
+            ValidateError::DuplicateEntryOnUniqueField(Str("b@x".to_owned()), "email".to_owned())
+                .into(),
+        ),
+    )
+    .await;
+
+    g.named_test(
+        "DO UPDATE cannot affect the same row twice",
+        "
+        INSERT INTO Account VALUES (2, 'b@x', 'B1', 1), (2, 'b@x', 'B2', 1)
+        ON CONFLICT (id) DO UPDATE SET name = EXCLUDED.name
+        ",
+        Err(InsertError::ConflictRowAffectedTwice.into()),
+    )
+    .await;
+
+    g.named_test(
+        "conflict target must be unique",
+        "INSERT INTO Account VALUES (8, 'h@x', 'Bob', 1) ON CONFLICT (name) DO NOTHING",
+        Err(InsertError::ConflictTargetNotUnique("name".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "DO UPDATE requires conflict target",
+        "INSERT INTO Account VALUES (1, 'a@x', 'A', 1) ON CONFLICT DO UPDATE SET name = 'A'",
+        Err(InsertError::ConflictTargetRequired.into()),
+    )
+    .await;
+
+    g.named_test(
+        "DO UPDATE cannot update primary key",
+        "INSERT INTO Account VALUES (1, 'a@x', 'A', 1) ON CONFLICT (email) DO UPDATE SET id = 10",
+        Err(UpdateError::UpdateOnPrimaryKeyNotSupported("id".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "failed statements leave rows untouched",
+        "SELECT id, name FROM Account ORDER BY id",
+        Ok(select!(
+            id  | name
+            I64 | Str;
+            1     "Alicia".to_owned();
+            2     "Bob".to_owned();
+            3     "Carol".to_owned();
+            5     "Eve".to_owned()
+        )),
+    )
+    .await;
+
+    g.run("CREATE TABLE Tag (name TEXT UNIQUE, cnt INTEGER);")
+        .await;
+    g.run("INSERT INTO Tag VALUES ('rust', 1);").await;
+
+    g.named_test(
+        "upsert on table without primary key",
+        "
+        INSERT INTO Tag VALUES ('rust', 1), ('sql', 1)
+        ON CONFLICT (name) DO UPDATE SET cnt = cnt + 1
+        ",
+        Ok(Payload::Insert(2)),
+    )
+    .await;
+
+    g.named_test(
+        "counters after upsert",
+        "SELECT name, cnt FROM Tag ORDER BY name",
+        Ok(select!(
+            name              | cnt
+            Str               | I64;
+            "rust".to_owned()   2;
+            "sql".to_owned()    1
+        )),
+    )
+    .await;
+});
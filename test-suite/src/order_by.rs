@@ -2,7 +2,7 @@ use {
     crate::*,
     gluesql_core::{
         error::{SortError, TranslateError},
-        prelude::Value::*,
+        prelude::{Payload, Value::*},
     },
 };
 
@@ -231,4 +231,40 @@ CREATE TABLE Test (
         Err(SortError::ColumnIndexOutOfRange(3).into()),
     )
     .await;
+    g.named_test(
+        "ORDER BY with LIMIT keeps the order of the equal rows",
+        "SELECT id, num FROM Test ORDER BY id LIMIT 3",
+        Ok(select!(
+            id  | num
+            I64 | I64;
+            1     2;
+            1     9;
+            3     4
+        )),
+    )
+    .await;
+    g.test(
+        "SELECT id, num FROM Test ORDER BY id DESC LIMIT 2 OFFSET 1",
+        Ok(select!(
+            id  | num
+            I64 | I64;
+            3     4;
+            1     2
+        )),
+    )
+    .await;
+    g.test(
+        "SELECT id FROM Test ORDER BY num LIMIT 0",
+        Ok(Payload::Select {
+            labels: vec!["id".to_owned()],
+            rows: Vec::new(),
+        }),
+    )
+    .await;
+    g.named_test(
+        "ORDER BY with LIMIT sorts all the rows for DISTINCT",
+        "SELECT DISTINCT id FROM Test ORDER BY id LIMIT 2",
+        Ok(select!(id I64; 1; 3)),
+    )
+    .await;
 });
@@ -0,0 +1,101 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::{EvaluateError, ExecuteError},
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(prepared_statement, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Item (id INTEGER PRIMARY KEY, name TEXT, price INTEGER);")
+        .await;
+
+    let glue = g.get_glue();
+
+    let insert = glue
+        .prepare("INSERT INTO Item VALUES ($1, $2, $3)")
+        .await
+        .unwrap();
+    assert_eq!(insert.num_params(), 3);
+
+    for (id, name, price) in [(1, "Apple", 100), (2, "Banana", 200), (3, "Cherry", 300)] {
+        let params = [I64(id), Str(name.to_owned()), I64(price)];
+
+        assert_eq!(
+            glue.execute_with(&insert, &params).await,
+            Ok(Payload::Insert(1)),
+        );
+    }
+
+    let select = glue
+        .prepare("SELECT id, name FROM Item WHERE price > ? ORDER BY id LIMIT ?")
+        .await
+        .unwrap();
+    assert_eq!(
+        glue.execute_with(&select, &[I64(100), I64(1)]).await,
+        Ok(select!(
+            id  | name
+            I64 | Str;
+            2     "Banana".to_owned()
+        )),
+    );
+    assert_eq!(
+        glue.execute_with(&select, &[I64(0), I64(5)]).await,
+        Ok(select!(
+            id  | name
+            I64 | Str;
+            1     "Apple".to_owned();
+            2     "Banana".to_owned();
+            3     "Cherry".to_owned()
+        )),
+    );
+
+    let by_id = glue
+        .prepare("SELECT name FROM Item WHERE id = $1")
+        .await
+        .unwrap();
+    assert_eq!(
+        glue.execute_with(&by_id, &[I64(3)]).await,
+        Ok(select!(name Str; "Cherry".to_owned())),
+    );
+
+    let update = glue
+        .prepare("UPDATE Item SET price = price + $2 WHERE id = $1")
+        .await
+        .unwrap();
+    assert_eq!(
+        glue.execute_with(&update, &[I64(1), I64(5)]).await,
+        Ok(Payload::Update(1)),
+    );
+
+    let delete = glue
+        .prepare("DELETE FROM Item WHERE name = $1 RETURNING price")
+        .await
+        .unwrap();
+    assert_eq!(
+        glue.execute_with(&delete, &[Str("Apple".to_owned())]).await,
+        Ok(select!(price I64; 105)),
+    );
+
+    assert_eq!(
+        glue.execute_with(&by_id, &[]).await,
+        Err(ExecuteError::ParameterCountMismatch {
+            expected: 1,
+            found: 0,
+        }
+        .into()),
+    );
+    assert_eq!(
+        glue.prepare("SELECT 1; SELECT 2").await,
+        Err(ExecuteError::PrepareRequiresSingleStatement(2).into()),
+    );
+
+    g.named_test(
+        "parameters without bound values",
+        "SELECT id FROM Item WHERE id = $1",
+        Err(EvaluateError::UnboundParameter(1).into()),
+    )
+    .await;
+});
@@ -0,0 +1,142 @@
+use {
+    crate::*,
+    gluesql_core::{
+        data::Value,
+        error::FetchError,
+        prelude::{Payload, Value::*},
+    },
+    serde_json::json,
+};
+
+test_case!(returning, {
+    let g = get_tester!();
+
+    g.run(
+        "
+        CREATE TABLE Item (
+            id INTEGER PRIMARY KEY,
+            name TEXT,
+            price INTEGER DEFAULT 100
+        );
+        ",
+    )
+    .await;
+
+    g.named_test(
+        "INSERT RETURNING * includes default values",
+        "INSERT INTO Item (id, name) VALUES (1, 'Apple'), (2, 'Banana') RETURNING *",
+        Ok(select!(
+            id  | name                 | price
+            I64 | Str                  | I64;
+            1     "Apple".to_owned()     100;
+            2     "Banana".to_owned()    100
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "INSERT RETURNING expressions with alias",
+        "INSERT INTO Item VALUES (3, 'Cherry', 300) RETURNING id, price * 2 AS doubled",
+        Ok(select!(
+            id  | doubled
+            I64 | I64;
+            3     600
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "INSERT ON CONFLICT RETURNING only returns written rows",
+        "
+        INSERT INTO Item VALUES (1, 'Apricot', 150), (4, 'Durian', 400)
+        ON CONFLICT (id) DO NOTHING
+        RETURNING name
+        ",
+        Ok(select!(
+            name
+            Str;
+            "Durian".to_owned()
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "UPDATE RETURNING projects the updated rows",
+        "UPDATE Item SET price = price + 1 WHERE id < 3 RETURNING Item.id, price",
+        Ok(select!(
+            id  | price
+            I64 | I64;
+            1     101;
+            2     101
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "UPDATE RETURNING on no matched rows",
+        "UPDATE Item SET price = 0 WHERE id > 100 RETURNING id",
+        Ok(Payload::Select {
+            labels: vec!["id".to_owned()],
+            rows: Vec::new(),
+        }),
+    )
+    .await;
+
+    g.named_test(
+        "DELETE RETURNING projects the deleted rows",
+        "DELETE FROM Item WHERE price > 200 RETURNING Item.*",
+        Ok(select!(
+            id  | name                 | price
+            I64 | Str                  | I64;
+            3     "Cherry".to_owned()    300;
+            4     "Durian".to_owned()    400
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "RETURNING with unknown table alias",
+        "DELETE FROM Item WHERE id > 0 RETURNING Other.*",
+        Err(FetchError::TableAliasNotFound("Other".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "rows are kept when RETURNING fails",
+        "SELECT id FROM Item",
+        Ok(select!(
+            id
+            I64;
+            1;
+            2
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "statements without RETURNING keep reporting counts",
+        "DELETE FROM Item WHERE id = 2",
+        Ok(Payload::Delete(1)),
+    )
+    .await;
+
+    g.run("CREATE TABLE Log").await;
+
+    g.named_test(
+        "INSERT RETURNING * on schemaless table",
+        r#"INSERT INTO Log VALUES ('{"id": 1, "msg": "hello"}') RETURNING *"#,
+        Ok(select_map!(json!({"id": 1, "msg": "hello"}))),
+    )
+    .await;
+
+    g.named_test(
+        "DELETE RETURNING columns on schemaless table",
+        "DELETE FROM Log WHERE id = 1 RETURNING msg",
+        Ok(select!(
+            msg
+            Str;
+            "hello".to_owned()
+        )),
+    )
+    .await;
+});
@@ -0,0 +1,293 @@
+use {
+    crate::*,
+    gluesql_core::{
+        ast::DataType,
+        error::{
+            AlterError, EvaluateError, FetchError, InsertError, SequenceError, TranslateError,
+        },
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(sequence, {
+    let g = get_tester!();
+
+    g.test("CREATE SEQUENCE Seq", Ok(Payload::CreateSequence))
+        .await;
+    g.test(
+        "CREATE SEQUENCE Seq",
+        Err(SequenceError::AlreadyExists("Seq".to_owned()).into()),
+    )
+    .await;
+    g.test(
+        "CREATE SEQUENCE IF NOT EXISTS Seq",
+        Ok(Payload::CreateSequence),
+    )
+    .await;
+    g.test(
+        "SELECT CURRVAL('Seq') AS n",
+        Err(SequenceError::CurrentValueNotDefined("Seq".to_owned()).into()),
+    )
+    .await;
+    g.test("SELECT NEXTVAL('Seq') AS n", Ok(select!(n I64; 1)))
+        .await;
+    g.test("SELECT NEXTVAL('Seq') AS n", Ok(select!(n I64; 2)))
+        .await;
+    g.test("SELECT CURRVAL('Seq') AS n", Ok(select!(n I64; 2)))
+        .await;
+    g.test(
+        "SELECT NEXTVAL('Nothing') AS n",
+        Err(SequenceError::NotFound("Nothing".to_owned()).into()),
+    )
+    .await;
+    g.test(
+        "SELECT NEXTVAL(1) AS n",
+        Err(EvaluateError::FunctionRequiresStringValue("NEXTVAL".to_owned()).into()),
+    )
+    .await;
+
+    g.run("CREATE SEQUENCE Down INCREMENT BY -5 MINVALUE 0 MAXVALUE 10 CYCLE")
+        .await;
+    g.run("CREATE TABLE Numbers (n INTEGER)").await;
+    g.run("INSERT INTO Numbers VALUES (1), (2), (3), (4)").await;
+    g.test(
+        "SELECT NEXTVAL('Down') AS n FROM Numbers",
+        Ok(select!(n I64; 10; 5; 0; 10)),
+    )
+    .await;
+
+    g.run("CREATE SEQUENCE Limited MAXVALUE 2 START WITH 2")
+        .await;
+    g.test("SELECT NEXTVAL('Limited') AS n", Ok(select!(n I64; 2)))
+        .await;
+    g.test(
+        "SELECT NEXTVAL('Limited') AS n",
+        Err(SequenceError::Exhausted("Limited".to_owned()).into()),
+    )
+    .await;
+
+    g.test(
+        "CREATE SEQUENCE Zero INCREMENT BY 0",
+        Err(SequenceError::ZeroIncrement.into()),
+    )
+    .await;
+    g.test(
+        "CREATE SEQUENCE Empty MINVALUE 5 MAXVALUE 5",
+        Err(SequenceError::InvalidRange {
+            min_value: 5,
+            max_value: 5,
+        }
+        .into()),
+    )
+    .await;
+    g.test(
+        "CREATE SEQUENCE Outside MINVALUE 5 START WITH 1",
+        Err(SequenceError::StartOutOfRange { start: 1 }.into()),
+    )
+    .await;
+    g.test(
+        "CREATE SEQUENCE Cached CACHE 10",
+        Err(TranslateError::UnsupportedSequenceOption("CACHE 10".to_owned()).into()),
+    )
+    .await;
+    g.test(
+        "CREATE SEQUENCE Fraction START WITH 1.5",
+        Err(TranslateError::SequenceOptionIntegerRequired("1.5".to_owned()).into()),
+    )
+    .await;
+
+    g.run("CREATE SEQUENCE OrderNo START WITH 100").await;
+    g.run("CREATE TABLE Orders (no INTEGER DEFAULT NEXTVAL('OrderNo'), item TEXT)")
+        .await;
+    g.test(
+        "INSERT INTO Orders (item) VALUES ('apple'), ('banana')",
+        Ok(Payload::Insert(2)),
+    )
+    .await;
+    g.test(
+        "INSERT INTO Orders VALUES (NEXTVAL('OrderNo') + 10, 'cherry')",
+        Ok(Payload::Insert(1)),
+    )
+    .await;
+    g.test(
+        "SELECT no, item FROM Orders ORDER BY no",
+        Ok(select!(
+            no  | item
+            I64 | Str;
+            100   "apple".to_owned();
+            101   "banana".to_owned();
+            112   "cherry".to_owned()
+        )),
+    )
+    .await;
+
+    g.test("DROP SEQUENCE Seq, Down", Ok(Payload::DropSequence(2)))
+        .await;
+    g.test(
+        "DROP SEQUENCE Seq",
+        Err(SequenceError::NotFound("Seq".to_owned()).into()),
+    )
+    .await;
+    g.test(
+        "DROP SEQUENCE IF EXISTS Seq, Limited",
+        Ok(Payload::DropSequence(1)),
+    )
+    .await;
+    g.test(
+        "SELECT NEXTVAL('Seq') AS n",
+        Err(SequenceError::NotFound("Seq".to_owned()).into()),
+    )
+    .await;
+});
+
+test_case!(identity, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Account (id INTEGER GENERATED ALWAYS AS IDENTITY, name TEXT)")
+        .await;
+    g.test(
+        "INSERT INTO Account (name) VALUES ('a'), ('b')",
+        Ok(Payload::Insert(2)),
+    )
+    .await;
+    g.test(
+        "INSERT INTO Account VALUES (10, 'c')",
+        Err(InsertError::IdentityColumnGeneratedAlways("id".to_owned()).into()),
+    )
+    .await;
+    g.test(
+        "INSERT INTO Account SELECT 10, 'c'",
+        Err(InsertError::IdentityColumnGeneratedAlways("id".to_owned()).into()),
+    )
+    .await;
+    g.test(
+        "INSERT INTO Account (name) VALUES ('c')",
+        Ok(Payload::Insert(1)),
+    )
+    .await;
+    g.named_test(
+        "INSERT ... SELECT draws identity values like VALUES",
+        "INSERT INTO Account (name) SELECT 'd' UNION ALL SELECT 'e'",
+        Ok(Payload::Insert(2)),
+    )
+    .await;
+    g.test(
+        "SELECT id, name FROM Account ORDER BY id",
+        Ok(select!(
+            id  | name
+            I64 | Str;
+            1     "a".to_owned();
+            2     "b".to_owned();
+            3     "c".to_owned();
+            4     "d".to_owned();
+            5     "e".to_owned()
+        )),
+    )
+    .await;
+    g.test(
+        "SELECT CURRVAL('Account_id_seq') AS n",
+        Ok(select!(n I64; 5)),
+    )
+    .await;
+    g.test(
+        "DROP SEQUENCE Account_id_seq",
+        Err(AlterError::CannotDropIdentitySequence("Account_id_seq".to_owned()).into()),
+    )
+    .await;
+
+    g.run(
+        "CREATE TABLE Item (
+            id INTEGER GENERATED BY DEFAULT AS IDENTITY (INCREMENT BY 10 START WITH 100),
+            name TEXT
+        )",
+    )
+    .await;
+    g.run("INSERT INTO Item (name) VALUES ('a')").await;
+    g.run("INSERT INTO Item VALUES (1, 'b')").await;
+    g.run("INSERT INTO Item (name) VALUES ('c')").await;
+    g.run("INSERT INTO Item (name) SELECT 'd'").await;
+    g.run("INSERT INTO Item SELECT 2, 'e'").await;
+    g.test(
+        "SELECT id, name FROM Item ORDER BY id",
+        Ok(select!(
+            id  | name
+            I64 | Str;
+            1     "b".to_owned();
+            2     "e".to_owned();
+            100   "a".to_owned();
+            110   "c".to_owned();
+            120   "d".to_owned()
+        )),
+    )
+    .await;
+
+    g.run("CREATE TABLE Serial (id SERIAL, small SMALLSERIAL, name TEXT)")
+        .await;
+    g.run("INSERT INTO Serial (name) VALUES ('a'), ('b')").await;
+    g.test(
+        "SELECT id, small, name FROM Serial ORDER BY id",
+        Ok(select!(
+            id  | small | name
+            I64 | I16   | Str;
+            1     1       "a".to_owned();
+            2     2       "b".to_owned()
+        )),
+    )
+    .await;
+    g.test(
+        "INSERT INTO Serial VALUES (NULL, NULL, 'c')",
+        Err(gluesql_core::error::ValueError::NullValueOnNotNullField.into()),
+    )
+    .await;
+
+    g.test(
+        "CREATE TABLE WithDefault (id INTEGER DEFAULT 1 GENERATED ALWAYS AS IDENTITY)",
+        Err(AlterError::IdentityColumnWithDefault("id".to_owned()).into()),
+    )
+    .await;
+    g.test(
+        "CREATE TABLE Text (id TEXT GENERATED ALWAYS AS IDENTITY)",
+        Err(
+            AlterError::UnsupportedDataTypeForIdentityColumn("id".to_owned(), DataType::Text)
+                .into(),
+        ),
+    )
+    .await;
+    g.test(
+        "ALTER TABLE Item ADD COLUMN no INTEGER GENERATED ALWAYS AS IDENTITY",
+        Err(AlterError::CannotAddIdentityColumn("no".to_owned()).into()),
+    )
+    .await;
+
+    g.run("CREATE SEQUENCE Taken_id_seq").await;
+    g.test(
+        "CREATE TABLE Taken (id INTEGER GENERATED ALWAYS AS IDENTITY)",
+        Err(SequenceError::AlreadyExists("Taken_id_seq".to_owned()).into()),
+    )
+    .await;
+    g.test(
+        "SELECT * FROM Taken",
+        Err(FetchError::TableNotFound("Taken".to_owned()).into()),
+    )
+    .await;
+
+    g.run("CREATE TABLE AccountCopy AS SELECT * FROM Account")
+        .await;
+    g.test(
+        "INSERT INTO AccountCopy VALUES (4, 'd')",
+        Ok(Payload::Insert(1)),
+    )
+    .await;
+
+    g.run("DROP TABLE Account").await;
+    g.test(
+        "SELECT NEXTVAL('Account_id_seq') AS n",
+        Err(SequenceError::NotFound("Account_id_seq".to_owned()).into()),
+    )
+    .await;
+    g.run("CREATE TABLE Account (id INTEGER GENERATED ALWAYS AS IDENTITY, name TEXT)")
+        .await;
+    g.run("INSERT INTO Account (name) VALUES ('a')").await;
+    g.test("SELECT id FROM Account", Ok(select!(id I64; 1)))
+        .await;
+});
@@ -0,0 +1,160 @@
+use {
+    crate::*,
+    gluesql_core::{
+        ast::SetOperator,
+        error::SelectError,
+        prelude::{Payload, Value, Value::*},
+    },
+    serde_json::json,
+};
+
+test_case!(set_operation, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Alpha (id INTEGER, name TEXT)").await;
+    g.run("CREATE TABLE Beta (id INTEGER, name TEXT)").await;
+    g.run("INSERT INTO Alpha VALUES (1, 'a'), (2, 'b'), (2, 'b'), (3, 'c')")
+        .await;
+    g.run("INSERT INTO Beta VALUES (2, 'b'), (3, 'c'), (3, 'c'), (4, 'd')")
+        .await;
+
+    g.named_test(
+        "UNION removes duplicates across both sides",
+        "SELECT id, name FROM Alpha UNION SELECT id, name FROM Beta ORDER BY id",
+        Ok(select!(
+            id  | name
+            I64 | Str;
+            1     "a".to_owned();
+            2     "b".to_owned();
+            3     "c".to_owned();
+            4     "d".to_owned()
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "UNION ALL keeps every row",
+        "SELECT id FROM Alpha UNION ALL SELECT id FROM Beta ORDER BY id",
+        Ok(select!(id; I64; 1; 2; 2; 2; 3; 3; 3; 4)),
+    )
+    .await;
+
+    g.named_test(
+        "INTERSECT returns distinct rows found on both sides",
+        "SELECT id FROM Alpha INTERSECT SELECT id FROM Beta ORDER BY id",
+        Ok(select!(id; I64; 2; 3)),
+    )
+    .await;
+
+    g.named_test(
+        "INTERSECT ALL keeps the smaller multiplicity",
+        "SELECT id FROM Alpha INTERSECT ALL SELECT id FROM Beta ORDER BY id",
+        Ok(select!(id; I64; 2; 3)),
+    )
+    .await;
+
+    g.named_test(
+        "EXCEPT returns distinct left rows missing on the right",
+        "SELECT id FROM Alpha EXCEPT SELECT id FROM Beta",
+        Ok(select!(id; I64; 1)),
+    )
+    .await;
+
+    g.named_test(
+        "EXCEPT ALL subtracts multiplicities",
+        "SELECT id FROM Alpha EXCEPT ALL SELECT id FROM Beta ORDER BY id",
+        Ok(select!(id; I64; 1; 2)),
+    )
+    .await;
+
+    g.named_test(
+        "labels come from the leftmost query",
+        "SELECT id AS left_id FROM Alpha UNION SELECT id AS right_id FROM Beta ORDER BY left_id DESC LIMIT 2",
+        Ok(select!(left_id; I64; 4; 3)),
+    )
+    .await;
+
+    g.named_test(
+        "OFFSET applies to the combined result",
+        "SELECT id FROM Alpha UNION SELECT id FROM Beta ORDER BY id LIMIT 2 OFFSET 1",
+        Ok(select!(id; I64; 2; 3)),
+    )
+    .await;
+
+    g.named_test(
+        "INTERSECT binds tighter than UNION",
+        "SELECT id FROM Alpha WHERE id = 1 UNION SELECT id FROM Alpha INTERSECT SELECT id FROM Beta ORDER BY id",
+        Ok(select!(id; I64; 1; 2; 3)),
+    )
+    .await;
+
+    g.named_test(
+        "parenthesized operands keep their own ORDER BY and LIMIT",
+        "(SELECT id FROM Alpha ORDER BY id DESC LIMIT 1) UNION ALL (SELECT id FROM Beta ORDER BY id LIMIT 1) ORDER BY id",
+        Ok(select!(id; I64; 2; 3)),
+    )
+    .await;
+
+    g.named_test(
+        "set operation with VALUES",
+        "SELECT id, name FROM Alpha WHERE id = 1 UNION ALL VALUES (10, 'z') ORDER BY id",
+        Ok(select!(
+            id  | name
+            I64 | Str;
+            1     "a".to_owned();
+            10    "z".to_owned()
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "set operation in a derived table",
+        "SELECT COUNT(*) AS cnt FROM (SELECT id FROM Alpha UNION SELECT id FROM Beta) AS Ids",
+        Ok(select!(cnt; I64; 4)),
+    )
+    .await;
+
+    g.named_test(
+        "set operation in a subquery",
+        "SELECT id FROM Beta WHERE id IN (SELECT id FROM Alpha EXCEPT SELECT 2) ORDER BY id",
+        Ok(select!(id; I64; 3; 3)),
+    )
+    .await;
+
+    g.named_test(
+        "number of columns must match",
+        "SELECT id, name FROM Alpha UNION SELECT id FROM Beta",
+        Err(SelectError::SetOperationColumnCountMismatch {
+            op: SetOperator::Union,
+            left: 2,
+            right: 1,
+        }
+        .into()),
+    )
+    .await;
+
+    g.run("CREATE TABLE Logs").await;
+    g.run(r#"INSERT INTO Logs VALUES ('{"id": 1}'), ('{"id": 2}')"#)
+        .await;
+
+    g.named_test(
+        "schemaless rows can be combined with each other",
+        "SELECT * FROM Logs UNION SELECT * FROM Logs",
+        Ok(select_map!(json!({"id": 1}), json!({"id": 2}))),
+    )
+    .await;
+
+    g.named_test(
+        "schemaless rows cannot be combined with schema rows",
+        "SELECT * FROM Logs UNION SELECT id FROM Alpha",
+        Err(SelectError::SetOperationSchemalessMismatch(SetOperator::Union).into()),
+    )
+    .await;
+
+    g.named_test(
+        "INSERT INTO ... SELECT with set operation",
+        "INSERT INTO Alpha SELECT id, name FROM Beta EXCEPT SELECT id, name FROM Alpha",
+        Ok(Payload::Insert(1)),
+    )
+    .await;
+});
@@ -0,0 +1,100 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::ExecuteError,
+        prelude::{Payload, Value::*},
+    },
+    pretty_assertions::assert_eq,
+};
+
+test_case!(statistics, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Small (id INTEGER PRIMARY KEY, name TEXT)")
+        .await;
+    g.run("CREATE TABLE Big (id INTEGER PRIMARY KEY, small_id INTEGER)")
+        .await;
+    g.run("INSERT INTO Small VALUES (1, 'a'), (2, 'b')").await;
+    g.run(
+        "
+        INSERT INTO Big VALUES
+            (1, 1), (2, 2), (3, 1), (4, 2), (5, 1),
+            (6, 2), (7, 1), (8, 2), (9, 1), (10, 2);
+    ",
+    )
+    .await;
+
+    let explain = "
+        EXPLAIN SELECT Small.name, Big.id
+        FROM Small
+        JOIN Big ON Big.small_id = Small.id
+        WHERE Big.id < 4
+    ";
+    let plan = |payload| match payload {
+        Payload::Explain(plan) => plan.to_string(),
+        payload => panic!("unexpected payload: {payload:?}"),
+    };
+
+    assert_eq!(
+        plan(g.run(explain).await),
+        "
+Project: Small.name AS name, Big.id AS id
+  Filter: Big.id < 4
+    Hash Join: inner, hash: Big.small_id = Small.id
+      Seq Scan: Small
+      Seq Scan: Big"
+            .trim_start(),
+        "without statistics tables are joined in the written order"
+    );
+
+    g.test("ANALYZE", Ok(Payload::Analyze(2))).await;
+
+    assert_eq!(
+        plan(g.run(explain).await),
+        "
+Project: Small.name AS name, Big.id AS id
+  Filter: Big.id < 4
+    Hash Join: inner, hash: Small.id = Big.small_id
+      Seq Scan: Big
+      Seq Scan: Small"
+            .trim_start(),
+        "the largest table drives the join and the smaller one is hashed"
+    );
+    g.test(
+        "
+        SELECT Small.name, Big.id
+        FROM Small
+        JOIN Big ON Big.small_id = Small.id
+        WHERE Big.id < 4
+        ORDER BY Big.id
+        ",
+        Ok(select!(
+            name        | id
+            Str         | I64;
+            "a".to_owned()  1;
+            "b".to_owned()  2;
+            "a".to_owned()  3
+        )),
+    )
+    .await;
+    g.named_test(
+        "wildcard keeps the written order of the columns",
+        "SELECT * FROM Small JOIN Big ON Big.small_id = Small.id WHERE Big.small_id = 2 AND Big.id < 5",
+        Ok(select!(
+            id  | name           | id  | small_id
+            I64 | Str            | I64 | I64;
+            2     "b".to_owned()   2     2;
+            2     "b".to_owned()   4     2
+        )),
+    )
+    .await;
+
+    g.test("ANALYZE TABLE Big", Ok(Payload::Analyze(1))).await;
+    g.test(
+        "ANALYZE Missing",
+        Err(ExecuteError::TableNotFound("Missing".to_owned()).into()),
+    )
+    .await;
+    g.test("DROP TABLE Big", Ok(Payload::DropTable(1))).await;
+    g.test("ANALYZE", Ok(Payload::Analyze(1))).await;
+});
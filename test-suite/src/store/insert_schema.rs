@@ -13,7 +13,10 @@ test_case!(insert_schema, {
         data_type: DataType::Int,
         nullable: false,
         default: Some(Expr::Literal(AstLiteral::Number(11.into()))),
+        identity: None,
+        generated: None,
         unique: None,
+        check: None,
         comment: Some("default value is lucky eleven".to_owned()),
     }]);
 
@@ -22,7 +25,9 @@ test_case!(insert_schema, {
         column_defs,
         indexes: Vec::new(),
         engine: None,
+        key_constraints: Vec::new(),
         foreign_keys: Vec::new(),
+        checks: Vec::new(),
         comment: Some("this is comment for table".to_owned()),
     };
 
@@ -35,7 +40,10 @@ test_case!(insert_schema, {
             data_type: DataType::Text,
             nullable: false,
             default: None,
+            identity: None,
+            generated: None,
             unique: None,
+            check: None,
             comment: Some("this is comment for name column".to_owned()),
         });
 
@@ -56,7 +64,9 @@ test_case!(insert_schema, {
         column_defs: None,
         indexes: Vec::new(),
         engine: None,
+        key_constraints: Vec::new(),
         foreign_keys: Vec::new(),
+        checks: Vec::new(),
         comment: Some("this is comment for schemaless table".to_owned()),
     };
     storage.insert_schema(&schema).await.unwrap();
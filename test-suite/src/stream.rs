@@ -0,0 +1,100 @@
+use {
+    crate::*,
+    futures::stream::{StreamExt, TryStreamExt},
+    gluesql_core::{
+        error::{ExecuteError, FetchError},
+        prelude::{Payload, PayloadStream, Value::*},
+    },
+    serde_json::json,
+};
+
+test_case!(stream, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Item (id INTEGER PRIMARY KEY, name TEXT);")
+        .await;
+    g.run("INSERT INTO Item VALUES (1, 'Apple'), (2, 'Banana'), (3, 'Cherry');")
+        .await;
+
+    let glue = g.get_glue();
+
+    match glue
+        .execute_stream("SELECT id, name FROM Item ORDER BY id")
+        .await
+        .unwrap()
+    {
+        PayloadStream::Select { labels, rows } => {
+            assert_eq!(labels, vec!["id".to_owned(), "name".to_owned()]);
+            assert_eq!(
+                rows.try_collect::<Vec<_>>().await,
+                Ok(vec![
+                    vec![I64(1), Str("Apple".to_owned())],
+                    vec![I64(2), Str("Banana".to_owned())],
+                    vec![I64(3), Str("Cherry".to_owned())],
+                ]),
+            );
+        }
+        _ => panic!("SELECT must be streamed"),
+    }
+
+    match glue
+        .execute_stream("SELECT name FROM Item ORDER BY id DESC")
+        .await
+        .unwrap()
+    {
+        PayloadStream::Select { mut rows, .. } => {
+            assert_eq!(rows.next().await, Some(Ok(vec![Str("Cherry".to_owned())])));
+        }
+        _ => panic!("SELECT must be streamed"),
+    }
+
+    let rows = glue
+        .execute_stream("SELECT id, name FROM Item WHERE id < 3")
+        .await
+        .unwrap()
+        .into_json_rows()
+        .unwrap();
+    assert_eq!(
+        rows.try_collect::<Vec<_>>().await,
+        Ok(vec![
+            json!({ "id": 1, "name": "Apple" }),
+            json!({ "id": 2, "name": "Banana" }),
+        ]),
+    );
+
+    assert!(matches!(
+        glue.execute_stream("INSERT INTO Item VALUES (4, 'Durian')")
+            .await,
+        Ok(PayloadStream::Payload(Payload::Insert(1))),
+    ));
+    assert!(matches!(
+        glue.execute_stream("SELECT * FROM Nothing").await,
+        Err(error) if error == FetchError::TableNotFound("Nothing".to_owned()).into(),
+    ));
+    assert!(matches!(
+        glue.execute_stream("SELECT 1; SELECT 2").await,
+        Err(error) if error == ExecuteError::StreamRequiresSingleStatement(2).into(),
+    ));
+
+    g.run("CREATE TABLE Logs;").await;
+    g.run(r#"INSERT INTO Logs VALUES ('{ "id": 1, "tags": ["a", "b"] }');"#)
+        .await;
+    let rows = g
+        .get_glue()
+        .execute_stream("SELECT * FROM Logs")
+        .await
+        .unwrap()
+        .into_json_rows()
+        .unwrap();
+    assert_eq!(
+        rows.try_collect::<Vec<_>>().await,
+        Ok(vec![json!({ "id": 1, "tags": ["a", "b"] })]),
+    );
+
+    g.named_test(
+        "statements run after a stream dropped halfway",
+        "SELECT COUNT(*) AS cnt FROM Item",
+        Ok(select!(cnt I64; 4)),
+    )
+    .await;
+});
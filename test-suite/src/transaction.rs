@@ -3,8 +3,13 @@ mod ast_builder;
 mod basic;
 mod dictionary;
 mod index;
+mod savepoint;
+mod sequence;
+mod stream;
 mod table;
+mod trigger;
 
 pub use {
-    alter_table::*, ast_builder::*, basic::basic, dictionary::dictionary, index::*, table::*,
+    alter_table::*, ast_builder::*, basic::basic, dictionary::dictionary, index::*,
+    savepoint::savepoint, sequence::sequence, stream::stream, table::*, trigger::trigger,
 };
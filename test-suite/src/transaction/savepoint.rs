@@ -0,0 +1,100 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::FetchError,
+        prelude::{Error, Payload, Value::*},
+    },
+};
+
+test_case!(savepoint, {
+    let g = get_tester!();
+
+    g.run("CREATE SEQUENCE Seq").await;
+    g.run("CREATE TABLE Item (id INTEGER PRIMARY KEY, name TEXT)")
+        .await;
+    g.run("INSERT INTO Item VALUES (1, 'a')").await;
+
+    // ROLLBACK TO SAVEPOINT keeps the changes made before the savepoint
+    g.run("BEGIN;").await;
+    g.run("INSERT INTO Item VALUES (2, 'b')").await;
+    g.test("SAVEPOINT sp1", Ok(Payload::Savepoint)).await;
+    g.run("INSERT INTO Item VALUES (3, 'c')").await;
+    g.run("UPDATE Item SET name = 'x' WHERE id = 1").await;
+    g.run("DELETE FROM Item WHERE id = 2").await;
+    g.test("SELECT NEXTVAL('Seq') AS n", Ok(select!(n I64; 1)))
+        .await;
+    g.test(
+        "ROLLBACK TO SAVEPOINT sp1",
+        Ok(Payload::RollbackToSavepoint),
+    )
+    .await;
+    g.test(
+        "SELECT id, name FROM Item",
+        Ok(select!(
+            id  | name
+            I64 | Str;
+            1     "a".to_owned();
+            2     "b".to_owned()
+        )),
+    )
+    .await;
+    g.test("SELECT NEXTVAL('Seq') AS n", Ok(select!(n I64; 1)))
+        .await;
+
+    // the savepoint stays after rolling back to it
+    g.run("INSERT INTO Item VALUES (4, 'd')").await;
+    g.run("ROLLBACK TO SAVEPOINT sp1").await;
+    g.run("COMMIT;").await;
+    g.test(
+        "SELECT id, name FROM Item",
+        Ok(select!(
+            id  | name
+            I64 | Str;
+            1     "a".to_owned();
+            2     "b".to_owned()
+        )),
+    )
+    .await;
+
+    // nested savepoints and tables created after a savepoint
+    g.run("BEGIN;").await;
+    g.run("SAVEPOINT sp1").await;
+    g.run("CREATE TABLE Temp (id INTEGER)").await;
+    g.run("INSERT INTO Item VALUES (5, 'e')").await;
+    g.run("SAVEPOINT sp2").await;
+    g.run("DROP TABLE Item").await;
+    g.run("ROLLBACK TO SAVEPOINT sp2").await;
+    g.test("SELECT id FROM Item WHERE id > 2", Ok(select!(id I64; 5)))
+        .await;
+    g.run("ROLLBACK TO SAVEPOINT sp1").await;
+    g.test(
+        "ROLLBACK TO SAVEPOINT sp2",
+        Err(Error::StorageMsg("savepoint not found: sp2".to_owned())),
+    )
+    .await;
+    g.run("COMMIT;").await;
+    g.test("SELECT id FROM Item WHERE id > 2", Ok(select!(id)))
+        .await;
+    g.test(
+        "SELECT * FROM Temp",
+        Err(FetchError::TableNotFound("Temp".to_owned()).into()),
+    )
+    .await;
+
+    // RELEASE SAVEPOINT keeps the changes, ROLLBACK still undoes them
+    g.run("BEGIN;").await;
+    g.run("SAVEPOINT sp1").await;
+    g.run("INSERT INTO Item VALUES (6, 'f')").await;
+    g.test("RELEASE SAVEPOINT sp1", Ok(Payload::ReleaseSavepoint))
+        .await;
+    g.test(
+        "ROLLBACK TO SAVEPOINT sp1",
+        Err(Error::StorageMsg("savepoint not found: sp1".to_owned())),
+    )
+    .await;
+    g.test("SELECT id FROM Item WHERE id > 2", Ok(select!(id I64; 6)))
+        .await;
+    g.run("ROLLBACK;").await;
+    g.test("SELECT id FROM Item WHERE id > 2", Ok(select!(id)))
+        .await;
+});
@@ -0,0 +1,70 @@
+use {
+    crate::*,
+    gluesql_core::{error::SequenceError, prelude::Value::*},
+};
+
+test_case!(sequence, {
+    let g = get_tester!();
+
+    g.run("CREATE SEQUENCE Seq").await;
+    g.run("CREATE TABLE Foo (id INTEGER GENERATED ALWAYS AS IDENTITY, name TEXT)")
+        .await;
+    g.run("INSERT INTO Foo (name) VALUES ('a')").await;
+
+    // NEXTVAL && ROLLBACK
+    g.run("BEGIN;").await;
+    g.run("INSERT INTO Foo (name) VALUES ('b'), ('c')").await;
+    g.test("SELECT NEXTVAL('Seq') AS n", Ok(select!(n I64; 1)))
+        .await;
+    g.run("ROLLBACK;").await;
+    g.test(
+        "SELECT CURRVAL('Seq') AS n",
+        Err(SequenceError::CurrentValueNotDefined("Seq".to_owned()).into()),
+    )
+    .await;
+    g.run("INSERT INTO Foo (name) VALUES ('d')").await;
+    g.test(
+        "SELECT id, name FROM Foo ORDER BY id",
+        Ok(select!(
+            id  | name
+            I64 | Str;
+            1     "a".to_owned();
+            2     "d".to_owned()
+        )),
+    )
+    .await;
+
+    // NEXTVAL && COMMIT
+    g.run("BEGIN;").await;
+    g.test("SELECT NEXTVAL('Seq') AS n", Ok(select!(n I64; 1)))
+        .await;
+    g.test("SELECT NEXTVAL('Seq') AS n", Ok(select!(n I64; 2)))
+        .await;
+    g.run("COMMIT;").await;
+    g.test("SELECT CURRVAL('Seq') AS n", Ok(select!(n I64; 2)))
+        .await;
+
+    // CREATE && ROLLBACK
+    g.run("BEGIN;").await;
+    g.run("CREATE SEQUENCE Temp").await;
+    g.test("SELECT NEXTVAL('Temp') AS n", Ok(select!(n I64; 1)))
+        .await;
+    g.run("ROLLBACK;").await;
+    g.test(
+        "SELECT NEXTVAL('Temp') AS n",
+        Err(SequenceError::NotFound("Temp".to_owned()).into()),
+    )
+    .await;
+
+    // DROP && ROLLBACK
+    g.run("BEGIN;").await;
+    g.run("DROP SEQUENCE Seq").await;
+    g.test(
+        "SELECT NEXTVAL('Seq') AS n",
+        Err(SequenceError::NotFound("Seq".to_owned()).into()),
+    )
+    .await;
+    g.run("ROLLBACK;").await;
+    g.test("SELECT NEXTVAL('Seq') AS n", Ok(select!(n I64; 3)))
+        .await;
+});
@@ -0,0 +1,38 @@
+use {
+    crate::*,
+    futures::stream::StreamExt,
+    gluesql_core::prelude::{PayloadStream, Value::*},
+};
+
+test_case!(stream, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Item (id INTEGER)").await;
+    g.run("INSERT INTO Item VALUES (1), (2)").await;
+
+    // stream dropped halfway && COMMIT
+    g.run("BEGIN;").await;
+    g.run("INSERT INTO Item VALUES (3)").await;
+    match g.get_glue().execute_stream("SELECT id FROM Item").await {
+        Ok(PayloadStream::Select { mut rows, .. }) => {
+            assert_eq!(rows.next().await, Some(Ok(vec![I64(1)])));
+        }
+        _ => panic!("SELECT must be streamed"),
+    }
+    g.run("COMMIT;").await;
+    g.test("SELECT id FROM Item", Ok(select!(id I64; 1; 2; 3)))
+        .await;
+
+    // stream dropped halfway && BEGIN
+    match g.get_glue().execute_stream("SELECT id FROM Item").await {
+        Ok(PayloadStream::Select { mut rows, .. }) => {
+            assert_eq!(rows.next().await, Some(Ok(vec![I64(1)])));
+        }
+        _ => panic!("SELECT must be streamed"),
+    }
+    g.run("BEGIN;").await;
+    g.run("DELETE FROM Item WHERE id = 1").await;
+    g.run("ROLLBACK;").await;
+    g.test("SELECT id FROM Item", Ok(select!(id I64; 1; 2; 3)))
+        .await;
+});
@@ -0,0 +1,60 @@
+use {
+    crate::*,
+    gluesql_core::{error::TriggerError, prelude::Value::*},
+};
+
+test_case!(trigger, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Item (id INTEGER)").await;
+    g.run("CREATE TABLE ItemLog (id INTEGER)").await;
+    g.run(
+        "CREATE TRIGGER log_insert AFTER INSERT ON Item
+        FOR EACH ROW EXECUTE INSERT INTO ItemLog VALUES (NEW.id)",
+    )
+    .await;
+
+    // trigger writes && ROLLBACK
+    g.run("BEGIN;").await;
+    g.run("INSERT INTO Item VALUES (1), (2)").await;
+    g.test("SELECT id FROM ItemLog", Ok(select!(id I64; 1; 2)))
+        .await;
+    g.run("ROLLBACK;").await;
+    g.test("SELECT id FROM ItemLog", Ok(select!(id))).await;
+
+    // trigger writes && COMMIT
+    g.run("BEGIN;").await;
+    g.run("INSERT INTO Item VALUES (3)").await;
+    g.run("COMMIT;").await;
+    g.test("SELECT id FROM ItemLog", Ok(select!(id I64; 3)))
+        .await;
+
+    // CREATE && ROLLBACK
+    g.run("BEGIN;").await;
+    g.run(
+        "CREATE TRIGGER log_delete AFTER DELETE ON Item
+        FOR EACH ROW EXECUTE INSERT INTO ItemLog VALUES (OLD.id)",
+    )
+    .await;
+    g.run("ROLLBACK;").await;
+    g.run("DELETE FROM Item WHERE id = 3").await;
+    g.test("SELECT id FROM ItemLog", Ok(select!(id I64; 3)))
+        .await;
+
+    // DROP && ROLLBACK
+    g.run("BEGIN;").await;
+    g.run("DROP TRIGGER log_insert ON Item").await;
+    g.test(
+        "DROP TRIGGER log_insert ON Item",
+        Err(TriggerError::NotFound {
+            name: "log_insert".to_owned(),
+            table_name: "Item".to_owned(),
+        }
+        .into()),
+    )
+    .await;
+    g.run("ROLLBACK;").await;
+    g.run("INSERT INTO Item VALUES (4)").await;
+    g.test("SELECT id FROM ItemLog", Ok(select!(id I64; 3; 4)))
+        .await;
+});
@@ -0,0 +1,337 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::{InsertError, LimitError, TranslateError, TriggerError},
+        prelude::{CancelToken, ExecuteOptions, Payload, Value::*},
+    },
+};
+
+test_case!(trigger, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Item (id INTEGER PRIMARY KEY, name TEXT, price INTEGER)")
+        .await;
+    g.run(
+        "CREATE TABLE ItemLog (
+            item_id INTEGER,
+            action TEXT,
+            old_price INTEGER NULL,
+            new_price INTEGER NULL
+        )",
+    )
+    .await;
+    g.run("CREATE TABLE Counter (name TEXT PRIMARY KEY, n INTEGER)")
+        .await;
+    g.run("INSERT INTO Counter VALUES ('items', 0)").await;
+
+    g.test(
+        "CREATE TRIGGER log_insert AFTER INSERT ON Item
+        FOR EACH ROW EXECUTE
+        BEGIN
+            INSERT INTO ItemLog VALUES (NEW.id, 'insert', NULL, NEW.price);
+            UPDATE Counter SET n = n + 1 WHERE name = 'items';
+        END",
+        Ok(Payload::Create),
+    )
+    .await;
+    g.test(
+        "CREATE TRIGGER log_insert AFTER INSERT ON Item
+        FOR EACH ROW EXECUTE DELETE FROM ItemLog",
+        Err(TriggerError::AlreadyExists {
+            name: "log_insert".to_owned(),
+            table_name: "Item".to_owned(),
+        }
+        .into()),
+    )
+    .await;
+    g.run(
+        "CREATE TRIGGER log_update AFTER UPDATE ON Item
+        FOR EACH ROW WHEN (OLD.price <> NEW.price) EXECUTE
+        INSERT INTO ItemLog VALUES (NEW.id, 'update', OLD.price, NEW.price)",
+    )
+    .await;
+    g.run(
+        "CREATE TRIGGER log_delete BEFORE DELETE ON Item
+        FOR EACH ROW EXECUTE
+        BEGIN
+            INSERT INTO ItemLog VALUES (OLD.id, 'delete', OLD.price, NULL);
+            UPDATE Counter SET n = n - 1 WHERE name = 'items';
+        END",
+    )
+    .await;
+
+    g.test(
+        "INSERT INTO Item VALUES (1, 'apple', 100), (2, 'banana', 200), (3, 'cherry', 300)",
+        Ok(Payload::Insert(3)),
+    )
+    .await;
+    g.test(
+        "UPDATE Item SET price = price * 2 WHERE id < 3",
+        Ok(Payload::Update(2)),
+    )
+    .await;
+    g.named_test(
+        "WHEN condition skips the rows not satisfying it",
+        "UPDATE Item SET name = 'cranberry' WHERE id = 3",
+        Ok(Payload::Update(1)),
+    )
+    .await;
+    g.test("DELETE FROM Item WHERE id = 2", Ok(Payload::Delete(1)))
+        .await;
+
+    g.test(
+        "SELECT * FROM ItemLog",
+        Ok(select_with_null!(
+            item_id | action                  | old_price | new_price;
+            I64(1)    Str("insert".to_owned())  Null        I64(100);
+            I64(2)    Str("insert".to_owned())  Null        I64(200);
+            I64(3)    Str("insert".to_owned())  Null        I64(300);
+            I64(1)    Str("update".to_owned())  I64(100)    I64(200);
+            I64(2)    Str("update".to_owned())  I64(200)    I64(400);
+            I64(2)    Str("delete".to_owned())  I64(400)    Null
+        )),
+    )
+    .await;
+    g.test(
+        "SELECT n FROM Counter WHERE name = 'items'",
+        Ok(select!(n I64; 2)),
+    )
+    .await;
+
+    g.run(
+        "CREATE TRIGGER count_update BEFORE UPDATE ON Item
+        FOR EACH ROW EXECUTE UPDATE Counter SET n = n + 10 WHERE name = 'items'",
+    )
+    .await;
+    g.named_test(
+        "RETURNING sees the changes of BEFORE triggers",
+        "UPDATE Item SET price = 200 WHERE id = 1
+        RETURNING id, (SELECT n FROM Counter WHERE name = 'items') AS n",
+        Ok(select!(
+            id  | n
+            I64 | I64;
+            1     12
+        )),
+    )
+    .await;
+    g.run("DROP TRIGGER count_update ON Item").await;
+
+    g.named_test(
+        "failing trigger fails the statement",
+        "CREATE OR REPLACE TRIGGER log_insert BEFORE INSERT ON Item
+        FOR EACH ROW EXECUTE INSERT INTO Nothing VALUES (NEW.id)",
+        Ok(Payload::Create),
+    )
+    .await;
+    g.test(
+        "INSERT INTO Item VALUES (4, 'durian', 400)",
+        Err(InsertError::TableNotFound("Nothing".to_owned()).into()),
+    )
+    .await;
+    g.test("SELECT id FROM Item", Ok(select!(id I64; 1; 3)))
+        .await;
+
+    g.test("DROP TRIGGER log_insert ON Item", Ok(Payload::DropTrigger))
+        .await;
+    g.test(
+        "DROP TRIGGER log_insert ON Item",
+        Err(TriggerError::NotFound {
+            name: "log_insert".to_owned(),
+            table_name: "Item".to_owned(),
+        }
+        .into()),
+    )
+    .await;
+    g.test(
+        "DROP TRIGGER IF EXISTS log_insert ON Item",
+        Ok(Payload::DropTrigger),
+    )
+    .await;
+    g.run("INSERT INTO Item VALUES (4, 'durian', 400)").await;
+    g.test(
+        "SELECT COUNT(*) AS cnt FROM ItemLog",
+        Ok(select!(cnt I64; 6)),
+    )
+    .await;
+
+    g.named_test(
+        "triggers are dropped along with their table",
+        "DROP TABLE Item",
+        Ok(Payload::DropTable(1)),
+    )
+    .await;
+    g.run("CREATE TABLE Item (id INTEGER PRIMARY KEY, name TEXT, price INTEGER)")
+        .await;
+    g.run("INSERT INTO Item VALUES (1, 'apple', 100)").await;
+    g.run("DELETE FROM Item").await;
+    g.test(
+        "SELECT COUNT(*) AS cnt FROM ItemLog",
+        Ok(select!(cnt I64; 6)),
+    )
+    .await;
+});
+
+test_case!(recursion, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Node (id INTEGER)").await;
+    g.run(
+        "CREATE TRIGGER grow AFTER INSERT ON Node
+        FOR EACH ROW EXECUTE INSERT INTO Node VALUES (NEW.id + 1)",
+    )
+    .await;
+    g.test(
+        "INSERT INTO Node VALUES (1)",
+        Err(TriggerError::DepthExceeded(16).into()),
+    )
+    .await;
+    g.run("DELETE FROM Node").await;
+
+    g.run(
+        "CREATE OR REPLACE TRIGGER grow AFTER INSERT ON Node
+        FOR EACH ROW WHEN (NEW.id < 5) EXECUTE INSERT INTO Node VALUES (NEW.id + 1)",
+    )
+    .await;
+    g.test("INSERT INTO Node VALUES (1)", Ok(Payload::Insert(1)))
+        .await;
+    g.test("SELECT id FROM Node", Ok(select!(id I64; 1; 2; 3; 4; 5)))
+        .await;
+
+    g.run("CREATE TABLE Pair (id INTEGER)").await;
+    g.run(
+        "CREATE TRIGGER pair BEFORE INSERT ON Node
+        FOR EACH ROW EXECUTE
+        INSERT INTO Pair SELECT a.id FROM Node a JOIN Node b ON a.id = b.id",
+    )
+    .await;
+
+    let cancel = CancelToken::new();
+    cancel.cancel();
+    g.get_glue().set_options(ExecuteOptions {
+        cancel: Some(cancel),
+        ..ExecuteOptions::default()
+    });
+    g.named_test(
+        "trigger bodies run under the limits of the firing statement",
+        "INSERT INTO Node VALUES (10)",
+        Err(LimitError::Cancelled.into()),
+    )
+    .await;
+
+    g.get_glue().set_options(ExecuteOptions::default());
+    g.test("INSERT INTO Node VALUES (10)", Ok(Payload::Insert(1)))
+        .await;
+    g.test("SELECT COUNT(*) AS cnt FROM Pair", Ok(select!(cnt I64; 5)))
+        .await;
+});
+
+test_case!(cascade, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Parent (id INTEGER PRIMARY KEY, code TEXT UNIQUE)")
+        .await;
+    g.run(
+        "CREATE TABLE Child (
+            id INTEGER PRIMARY KEY,
+            parent_id INTEGER,
+            parent_code TEXT NULL,
+            FOREIGN KEY (parent_id) REFERENCES Parent (id) ON DELETE CASCADE,
+            FOREIGN KEY (parent_code) REFERENCES Parent (code) ON UPDATE CASCADE
+        )",
+    )
+    .await;
+    g.run(
+        "CREATE TABLE ChildLog (
+            child_id INTEGER,
+            action TEXT,
+            old_code TEXT NULL,
+            new_code TEXT NULL
+        )",
+    )
+    .await;
+    g.run(
+        "CREATE TRIGGER log_update AFTER UPDATE ON Child
+        FOR EACH ROW EXECUTE
+        INSERT INTO ChildLog VALUES (NEW.id, 'update', OLD.parent_code, NEW.parent_code)",
+    )
+    .await;
+    g.run(
+        "CREATE TRIGGER log_delete BEFORE DELETE ON Child
+        FOR EACH ROW EXECUTE
+        INSERT INTO ChildLog VALUES (OLD.id, 'delete', OLD.parent_code, NULL)",
+    )
+    .await;
+
+    g.run("INSERT INTO Parent VALUES (1, 'a'), (2, 'b')").await;
+    g.run("INSERT INTO Child VALUES (1, 1, 'a'), (2, 2, NULL)")
+        .await;
+    g.test(
+        "UPDATE Parent SET code = 'alpha' WHERE id = 1",
+        Ok(Payload::Update(1)),
+    )
+    .await;
+    g.test("DELETE FROM Parent WHERE id = 2", Ok(Payload::Delete(1)))
+        .await;
+
+    g.named_test(
+        "triggers fire for the rows changed by ON UPDATE and ON DELETE actions",
+        "SELECT * FROM ChildLog",
+        Ok(select_with_null!(
+            child_id | action                  | old_code              | new_code;
+            I64(1)     Str("update".to_owned())  Str("a".to_owned())     Str("alpha".to_owned());
+            I64(2)     Str("delete".to_owned())  Null                    Null
+        )),
+    )
+    .await;
+});
+
+test_case!(unsupported, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Item (id INTEGER)").await;
+    g.run("CREATE TABLE ItemLog (id INTEGER)").await;
+
+    let test_cases = [
+        (
+            "CREATE TRIGGER t AFTER INSERT ON Nothing
+            FOR EACH ROW EXECUTE INSERT INTO ItemLog VALUES (NEW.id)",
+            Err(TriggerError::TableNotFound("Nothing".to_owned()).into()),
+        ),
+        (
+            "CREATE TRIGGER t AFTER INSERT ON Item
+            FOR EACH STATEMENT EXECUTE INSERT INTO ItemLog VALUES (1)",
+            Err(TranslateError::UnsupportedTriggerOption("FOR EACH STATEMENT".to_owned()).into()),
+        ),
+        (
+            "CREATE TRIGGER t INSTEAD OF INSERT ON Item
+            FOR EACH ROW EXECUTE INSERT INTO ItemLog VALUES (NEW.id)",
+            Err(TranslateError::UnsupportedTriggerOption("INSTEAD OF".to_owned()).into()),
+        ),
+        (
+            "CREATE TRIGGER t AFTER TRUNCATE ON Item
+            FOR EACH ROW EXECUTE INSERT INTO ItemLog VALUES (1)",
+            Err(TranslateError::UnsupportedTriggerOption("TRUNCATE".to_owned()).into()),
+        ),
+        (
+            "CREATE TRIGGER t AFTER INSERT ON Item
+            FOR EACH ROW EXECUTE SELECT * FROM ItemLog",
+            Err(
+                TranslateError::UnsupportedTriggerStatement("SELECT * FROM ItemLog".to_owned())
+                    .into(),
+            ),
+        ),
+        (
+            "CREATE TRIGGER t AFTER INSERT ON Item
+            FOR EACH ROW EXECUTE FUNCTION log_item()",
+            Err(TranslateError::UnsupportedStatement(
+                "CREATE TRIGGER t AFTER INSERT ON Item FOR EACH ROW EXECUTE FUNCTION log_item"
+                    .to_owned(),
+            )
+            .into()),
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        g.test(sql, expected).await;
+    }
+});
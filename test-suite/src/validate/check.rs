@@ -0,0 +1,107 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::{EvaluateError, ValidateError},
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(check, {
+    let g = get_tester!();
+
+    g.named_test(
+        "create table with column and table check constraints",
+        "
+        CREATE TABLE Item (
+            id INTEGER PRIMARY KEY,
+            price INTEGER CHECK (price > 0),
+            discount INTEGER DEFAULT 0,
+            CONSTRAINT valid_discount CHECK (discount BETWEEN 0 AND price)
+        )
+        ",
+        Ok(Payload::Create),
+    )
+    .await;
+
+    g.named_test(
+        "insert rows satisfying the checks",
+        "INSERT INTO Item VALUES (1, 100, 10), (2, 50, 0)",
+        Ok(Payload::Insert(2)),
+    )
+    .await;
+
+    g.named_test(
+        "NULL result of a check passes",
+        "INSERT INTO Item (id, price) VALUES (3, NULL)",
+        Ok(Payload::Insert(1)),
+    )
+    .await;
+
+    g.named_test(
+        "insert violating a column check fails",
+        "INSERT INTO Item VALUES (4, 0, 0)",
+        Err(ValidateError::CheckConstraintViolation {
+            table_name: "Item".to_owned(),
+            constraint: r#""price" > 0"#.to_owned(),
+        }
+        .into()),
+    )
+    .await;
+
+    g.named_test(
+        "insert violating a named table check fails",
+        "INSERT INTO Item VALUES (4, 10, 20)",
+        Err(ValidateError::CheckConstraintViolation {
+            table_name: "Item".to_owned(),
+            constraint: "valid_discount".to_owned(),
+        }
+        .into()),
+    )
+    .await;
+
+    g.named_test(
+        "update violating a table check fails",
+        "UPDATE Item SET price = 5 WHERE id = 1",
+        Err(ValidateError::CheckConstraintViolation {
+            table_name: "Item".to_owned(),
+            constraint: "valid_discount".to_owned(),
+        }
+        .into()),
+    )
+    .await;
+
+    g.named_test(
+        "update satisfying the checks",
+        "UPDATE Item SET price = price * 2",
+        Ok(Payload::Update(3)),
+    )
+    .await;
+
+    g.named_test(
+        "rejected rows leave the table unchanged",
+        "SELECT id, price, discount FROM Item ORDER BY id",
+        Ok(select_with_null!(
+            id     | price     | discount;
+            I64(1)   I64(200)    I64(10);
+            I64(2)   I64(100)    I64(0);
+            I64(3)   Null        I64(0)
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "check referring to an unknown column fails",
+        "CREATE TABLE Wrong (id INTEGER CHECK (amount > 0))",
+        Err(EvaluateError::IdentifierNotFound("amount".to_owned()).into()),
+    )
+    .await;
+
+    g.run("CREATE TABLE Label (name TEXT CHECK (name));").await;
+
+    g.named_test(
+        "check not evaluating to a boolean fails",
+        "INSERT INTO Label VALUES ('glue')",
+        Err(ValidateError::CheckConstraintNotBoolean(r#""name""#.to_owned()).into()),
+    )
+    .await;
+});
@@ -0,0 +1,310 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::{AlterError, FetchError, PlanError},
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(view, {
+    let g = get_tester!();
+
+    g.run(
+        "
+        CREATE TABLE Author (id INTEGER PRIMARY KEY, name TEXT);
+        ",
+    )
+    .await;
+    g.run(
+        "
+        CREATE TABLE Book (id INTEGER PRIMARY KEY, author_id INTEGER, title TEXT, price INTEGER);
+        ",
+    )
+    .await;
+    g.run("INSERT INTO Author VALUES (1, 'Kim'), (2, 'Lee');")
+        .await;
+    g.run(
+        "
+        INSERT INTO Book VALUES
+            (1, 1, 'Rust', 30),
+            (2, 1, 'SQL', 20),
+            (3, 2, 'Glue', 10);
+        ",
+    )
+    .await;
+
+    g.named_test(
+        "create view over a join",
+        "
+        CREATE VIEW AuthorBook AS
+        SELECT Author.name AS author, Book.title, Book.price
+        FROM Book
+        JOIN Author ON Author.id = Book.author_id
+        ",
+        Ok(Payload::Create),
+    )
+    .await;
+
+    g.named_test(
+        "select from view",
+        "SELECT author, title FROM AuthorBook WHERE price > 15 ORDER BY title",
+        Ok(select!(
+            author            | title
+            Str               | Str;
+            "Kim".to_owned()    "Rust".to_owned();
+            "Kim".to_owned()    "SQL".to_owned()
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "view reflects changes of base tables",
+        "INSERT INTO Book VALUES (4, 2, 'Data', 40)",
+        Ok(Payload::Insert(1)),
+    )
+    .await;
+
+    g.named_test(
+        "view with alias and aggregate",
+        "SELECT ab.author, SUM(ab.price) AS total FROM AuthorBook ab GROUP BY ab.author ORDER BY ab.author",
+        Ok(select!(
+            author            | total
+            Str               | I64;
+            "Kim".to_owned()    50;
+            "Lee".to_owned()    50
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "view with column aliases over another view",
+        "CREATE VIEW Cheap (who, what) AS SELECT author, title FROM AuthorBook WHERE price < 25",
+        Ok(Payload::Create),
+    )
+    .await;
+
+    g.named_test(
+        "select view in subquery and CTE",
+        "
+        WITH c AS (SELECT * FROM Cheap)
+        SELECT title FROM Book WHERE title IN (SELECT what FROM c) ORDER BY title
+        ",
+        Ok(select!(
+            title
+            Str;
+            "Glue".to_owned();
+            "SQL".to_owned()
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "CTE shadows view of the same name",
+        "WITH Cheap AS (SELECT 1 AS n) SELECT * FROM Cheap",
+        Ok(select!(n I64; 1)),
+    )
+    .await;
+
+    g.named_test(
+        "create existing view fails",
+        "CREATE VIEW Cheap AS SELECT 1",
+        Err(AlterError::ViewAlreadyExists("Cheap".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "create view over existing table fails",
+        "CREATE VIEW Book AS SELECT 1",
+        Err(AlterError::TableAlreadyExists("Book".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "create table over existing view fails",
+        "CREATE TABLE Cheap (id INTEGER)",
+        Err(AlterError::ViewAlreadyExists("Cheap".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "view defined in terms of itself fails",
+        "CREATE OR REPLACE VIEW AuthorBook AS SELECT * FROM Cheap",
+        Err(PlanError::RecursiveView("Cheap".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "replace view",
+        "CREATE OR REPLACE VIEW Cheap AS SELECT title FROM Book WHERE price < 15",
+        Ok(Payload::Create),
+    )
+    .await;
+
+    g.named_test(
+        "select replaced view",
+        "SELECT * FROM Cheap",
+        Ok(select!(title Str; "Glue".to_owned())),
+    )
+    .await;
+
+    g.named_test(
+        "views are listed in GLUE_OBJECTS",
+        "SELECT OBJECT_NAME, OBJECT_TYPE FROM GLUE_OBJECTS WHERE OBJECT_TYPE = 'VIEW'",
+        Ok(select!(
+            OBJECT_NAME              | OBJECT_TYPE
+            Str                      | Str;
+            "AuthorBook".to_owned()    "VIEW".to_owned();
+            "Cheap".to_owned()         "VIEW".to_owned()
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "drop views",
+        "DROP VIEW Cheap, AuthorBook",
+        Ok(Payload::DropView(2)),
+    )
+    .await;
+
+    g.named_test(
+        "dropped view is gone",
+        "SELECT * FROM Cheap",
+        Err(FetchError::TableNotFound("Cheap".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "drop missing view fails",
+        "DROP VIEW Cheap",
+        Err(AlterError::ViewNotFound("Cheap".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "drop missing view with IF EXISTS",
+        "DROP VIEW IF EXISTS Cheap",
+        Ok(Payload::DropView(0)),
+    )
+    .await;
+});
+
+test_case!(materialized, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Sale (id INTEGER PRIMARY KEY, region TEXT, amount INTEGER);")
+        .await;
+    g.run("INSERT INTO Sale VALUES (1, 'east', 10), (2, 'west', 20), (3, 'east', 5);")
+        .await;
+
+    g.named_test(
+        "create materialized view",
+        "
+        CREATE MATERIALIZED VIEW RegionTotal AS
+        SELECT region, SUM(amount) AS total FROM Sale GROUP BY region
+        ",
+        Ok(Payload::Create),
+    )
+    .await;
+
+    g.run("INSERT INTO Sale VALUES (4, 'west', 100);").await;
+
+    g.named_test(
+        "materialized view keeps rows of the last refresh",
+        "SELECT region, total FROM RegionTotal ORDER BY region",
+        Ok(select!(
+            region             | total
+            Str                | I64;
+            "east".to_owned()    15;
+            "west".to_owned()    20
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "refresh materialized view",
+        "REFRESH MATERIALIZED VIEW RegionTotal",
+        Ok(Payload::RefreshMaterializedView(2)),
+    )
+    .await;
+
+    g.named_test(
+        "materialized view after refresh",
+        "SELECT region, total FROM RegionTotal ORDER BY region",
+        Ok(select!(
+            region             | total
+            Str                | I64;
+            "east".to_owned()    15;
+            "west".to_owned()    120
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "materialized views are listed in GLUE_OBJECTS",
+        "SELECT OBJECT_NAME, OBJECT_TYPE FROM GLUE_OBJECTS ORDER BY OBJECT_NAME",
+        Ok(select!(
+            OBJECT_NAME               | OBJECT_TYPE
+            Str                       | Str;
+            "RegionTotal".to_owned()    "MATERIALIZED VIEW".to_owned();
+            "Sale".to_owned()           "TABLE".to_owned()
+        )),
+    )
+    .await;
+
+    g.run("CREATE VIEW East AS SELECT * FROM Sale WHERE region = 'east';")
+        .await;
+
+    g.named_test(
+        "refresh plain view fails",
+        "REFRESH MATERIALIZED VIEW East",
+        Err(AlterError::NotMaterializedView("East".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "refresh missing view fails",
+        "REFRESH MATERIALIZED VIEW Missing",
+        Err(AlterError::ViewNotFound("Missing".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "materialized view over a view with column aliases",
+        "CREATE MATERIALIZED VIEW EastIds (sale_id) AS SELECT id FROM East",
+        Ok(Payload::Create),
+    )
+    .await;
+
+    g.named_test(
+        "select materialized view with column aliases",
+        "SELECT sale_id FROM EastIds ORDER BY sale_id",
+        Ok(select!(
+            sale_id
+            I64;
+            1;
+            3
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "too many column aliases fails",
+        "CREATE MATERIALIZED VIEW Wide (a, b) AS SELECT id FROM Sale",
+        Err(AlterError::TooManyViewColumns("Wide".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "drop materialized view with its backing table",
+        "DROP MATERIALIZED VIEW RegionTotal, EastIds",
+        Ok(Payload::DropView(2)),
+    )
+    .await;
+
+    g.named_test(
+        "dropped materialized view is gone",
+        "SELECT * FROM RegionTotal",
+        Err(FetchError::TableNotFound("RegionTotal".to_owned()).into()),
+    )
+    .await;
+});
@@ -0,0 +1,230 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::{TranslateError, WindowError},
+        prelude::Value::*,
+    },
+};
+
+test_case!(window, {
+    let g = get_tester!();
+
+    g.run(
+        "
+        CREATE TABLE Score (
+            id INTEGER,
+            team TEXT,
+            score INTEGER
+        );
+    ",
+    )
+    .await;
+    g.run(
+        "
+        INSERT INTO Score VALUES
+            (1, 'A', 10),
+            (2, 'A', 20),
+            (3, 'A', 20),
+            (4, 'B', 5),
+            (5, 'B', 15);
+    ",
+    )
+    .await;
+
+    g.named_test(
+        "ROW_NUMBER, RANK and DENSE_RANK in each partition",
+        "
+        SELECT
+            id,
+            ROW_NUMBER() OVER (PARTITION BY team ORDER BY score DESC) AS rn,
+            RANK() OVER (PARTITION BY team ORDER BY score DESC) AS rnk,
+            DENSE_RANK() OVER (PARTITION BY team ORDER BY score DESC) AS dense
+        FROM Score
+        ORDER BY id
+        ",
+        Ok(select!(
+            id  | rn  | rnk | dense
+            I64 | I64 | I64 | I64;
+            1     3     3     2;
+            2     1     1     1;
+            3     2     1     1;
+            4     2     2     2;
+            5     1     1     1
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "LAG and LEAD with offset and default",
+        "
+        SELECT
+            id,
+            LAG(score) OVER (ORDER BY id) AS prev,
+            LEAD(score, 2, 0) OVER (ORDER BY id) AS next
+        FROM Score
+        ORDER BY id
+        ",
+        Ok(select_with_null!(
+            id     | prev    | next;
+            I64(1)   Null      I64(20);
+            I64(2)   I64(10)   I64(5);
+            I64(3)   I64(20)   I64(15);
+            I64(4)   I64(20)   I64(0);
+            I64(5)   I64(5)    I64(0)
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "FIRST_VALUE and LAST_VALUE follow the frame",
+        "
+        SELECT
+            id,
+            FIRST_VALUE(score) OVER (PARTITION BY team ORDER BY id) AS first,
+            LAST_VALUE(score) OVER (PARTITION BY team ORDER BY id) AS last,
+            LAST_VALUE(score) OVER (
+                PARTITION BY team ORDER BY id
+                ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING
+            ) AS last_in_team
+        FROM Score
+        ORDER BY id
+        ",
+        Ok(select!(
+            id  | first | last | last_in_team
+            I64 | I64   | I64  | I64;
+            1     10      10     20;
+            2     10      20     20;
+            3     10      20     20;
+            4     5       5      15;
+            5     5       15     15
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "running SUM includes peers by default, ROWS frame does not",
+        "
+        SELECT
+            id,
+            SUM(score) OVER (ORDER BY score) AS range_sum,
+            SUM(score) OVER (
+                ORDER BY score, id ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW
+            ) AS rows_sum
+        FROM Score
+        ORDER BY id
+        ",
+        Ok(select!(
+            id  | range_sum | rows_sum
+            I64 | I64       | I64;
+            1     15          15;
+            2     70          50;
+            3     70          70;
+            4     5           5;
+            5     30          30
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "sliding ROWS frame",
+        "
+        SELECT
+            id,
+            SUM(score) OVER (ORDER BY id ROWS BETWEEN 1 PRECEDING AND 1 FOLLOWING) AS total,
+            AVG(score) OVER (ORDER BY id ROWS 1 PRECEDING) AS average
+        FROM Score
+        ORDER BY id
+        ",
+        Ok(select!(
+            id  | total | average
+            I64 | I64   | F64;
+            1     30      10.0;
+            2     50      15.0;
+            3     45      20.0;
+            4     40      12.5;
+            5     20      10.0
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "RANGE frame with offsets",
+        "
+        SELECT
+            id,
+            COUNT(*) OVER (ORDER BY score RANGE BETWEEN 5 PRECEDING AND 5 FOLLOWING) AS near
+        FROM Score
+        ORDER BY id
+        ",
+        Ok(select!(
+            id  | near
+            I64 | I64;
+            1     3;
+            2     3;
+            3     3;
+            4     2;
+            5     4
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "window over the whole result",
+        "SELECT id, COUNT(*) OVER () AS cnt, MAX(score) OVER (PARTITION BY team) AS best FROM Score ORDER BY id",
+        Ok(select!(
+            id  | cnt | best
+            I64 | I64 | I64;
+            1     5     20;
+            2     5     20;
+            3     5     20;
+            4     5     15;
+            5     5     15
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "window function over grouped aggregates",
+        "
+        SELECT team, SUM(score) AS total, RANK() OVER (ORDER BY SUM(score) DESC) AS rnk
+        FROM Score
+        GROUP BY team
+        ORDER BY team
+        ",
+        Ok(select!(
+            team             | total | rnk
+            Str              | I64   | I64;
+            "A".to_owned()     50      1;
+            "B".to_owned()     20      2
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "window function in ORDER BY",
+        "SELECT id FROM Score ORDER BY ROW_NUMBER() OVER (ORDER BY score DESC, id) LIMIT 3",
+        Ok(select!(id; I64; 2; 3; 5)),
+    )
+    .await;
+
+    g.named_test(
+        "ranking function requires OVER",
+        "SELECT ROW_NUMBER() FROM Score",
+        Err(TranslateError::WindowFunctionRequiresOver("ROW_NUMBER".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "scalar function cannot be a window function",
+        "SELECT LOWER(team) OVER () FROM Score",
+        Err(TranslateError::UnsupportedWindowFunction("LOWER".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "RANGE offset requires a single ORDER BY expression",
+        "SELECT SUM(score) OVER (ORDER BY team, score RANGE 1 PRECEDING) FROM Score",
+        Err(WindowError::RangeOffsetRequiresSingleOrderBy(2).into()),
+    )
+    .await;
+});